@@ -0,0 +1,166 @@
+use crate::options::{CsvOptions, CsvTypes};
+use crate::{CsvExtensionError, CsvTableExprResult};
+use partiql_types::{PartiqlShape, Static};
+use partiql_value::{DateTime, Tuple, Value};
+use rust_decimal::Decimal;
+use std::io::Read;
+use std::str::FromStr;
+use time::format_description::well_known::Rfc3339;
+use time::format_description::BorrowedFormatItem;
+use time::macros::format_description;
+
+/// The conversion of the fields of a column.
+#[derive(Debug, Clone)]
+enum ColumnType {
+    String,
+    Infer,
+    Static(Static),
+}
+
+/// Reads the CSV data of `reader` as a stream of tuples, one per record.
+///
+/// A field is read as `NULL` if it is the `null_value` of `options` or if it is empty and its
+/// column has a non-string type; a field which cannot be converted to the type of its column
+/// results in a [`CsvExtensionError::DataError`] for its record.
+pub fn decode_csv<'a, R: Read + 'a>(reader: R, options: &CsvOptions) -> CsvTableExprResult<'a> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .quote(options.quote)
+        .has_headers(options.header)
+        .comment(options.comment)
+        .from_reader(reader);
+
+    let names: Vec<String> = match (&options.columns, options.header) {
+        (Some(columns), _) => columns.clone(),
+        (None, true) => rdr.headers()?.iter().map(str::to_string).collect(),
+        (None, false) => vec![],
+    };
+    let types = names
+        .iter()
+        .map(|name| column_type(&options.types, name))
+        .collect::<Vec<_>>();
+    let fallback = column_type(&options.types, "");
+    let null_value = options.null_value.clone();
+
+    let rows = rdr.into_records().map(move |row| {
+        let row = row.map_err(|err| CsvExtensionError::DataError(err.into()))?;
+        let fields = row.iter().enumerate().map(|(i, field)| {
+            let name = names
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("_{}", i + 1));
+            let ty = types.get(i).unwrap_or(&fallback);
+            let value = match ty {
+                _ if null_value.as_deref() == Some(field) => Ok(Value::Null),
+                ColumnType::String => Ok(Value::from(field)),
+                ColumnType::Infer => Ok(infer(field)),
+                ColumnType::Static(_) if field.is_empty() => Ok(Value::Null),
+                ColumnType::Static(ty) => convert(field, ty).ok_or_else(|| {
+                    CsvExtensionError::DataError(
+                        format!("cannot convert `{field}` of column `{name}` to {ty}").into(),
+                    )
+                }),
+            };
+            value.map(|value| (name, value))
+        });
+        Ok(fields.collect::<Result<Tuple, _>>()?.into())
+    });
+    Ok(Box::new(rows))
+}
+
+fn column_type(types: &CsvTypes, column: &str) -> ColumnType {
+    match types {
+        CsvTypes::Strings => ColumnType::String,
+        CsvTypes::Infer => ColumnType::Infer,
+        CsvTypes::Schema { schema, fallback } => {
+            let field = match schema {
+                PartiqlShape::Static(st) => match st.ty() {
+                    Static::Struct(s) => s.fields().find(|f| f.name() == column),
+                    _ => None,
+                },
+                _ => None,
+            };
+            match field.map(|f| f.ty()) {
+                Some(PartiqlShape::Static(st)) => ColumnType::Static(st.ty().clone()),
+                Some(PartiqlShape::Dynamic) => ColumnType::Infer,
+                _ => column_type(fallback, column),
+            }
+        }
+    }
+}
+
+fn convert(field: &str, ty: &Static) -> Option<Value> {
+    let field = field.trim();
+    match ty {
+        Static::Int | Static::Int8 | Static::Int16 | Static::Int32 | Static::Int64 => {
+            field.parse::<i64>().ok().map(Value::from)
+        }
+        Static::Bool => parse_bool(field).map(Value::from),
+        Static::Decimal | Static::DecimalP(_, _) => parse_decimal(field).map(Value::from),
+        Static::Float32 | Static::Float64 => field.parse::<f64>().ok().map(Value::from),
+        Static::String | Static::StringFixed(_) | Static::StringVarying(_) => {
+            Some(Value::from(field))
+        }
+        Static::DateTime => parse_datetime(field).map(Value::from),
+        Static::Struct(_) | Static::Bag(_) | Static::Array(_) | Static::Graph() => None,
+    }
+}
+
+/// Converts `field` to a boolean, integer, decimal or datetime if it is the text of one.
+fn infer(field: &str) -> Value {
+    let trimmed = field.trim();
+    if let Some(b) = parse_bool(trimmed) {
+        Value::from(b)
+    } else if let Ok(i) = trimmed.parse::<i64>() {
+        Value::from(i)
+    } else if let Some(d) = parse_decimal(trimmed) {
+        Value::from(d)
+    } else if let Some(dt) = parse_datetime(trimmed) {
+        Value::from(dt)
+    } else {
+        Value::from(field)
+    }
+}
+
+fn parse_bool(field: &str) -> Option<bool> {
+    if field.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if field.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn parse_decimal(field: &str) -> Option<Decimal> {
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let unsigned = field.strip_prefix(['+', '-']).unwrap_or(field);
+    let (int, frac) = unsigned.split_once('.')?;
+    if (is_number(int) || int.is_empty()) && (is_number(frac) || frac.is_empty()) {
+        Decimal::from_str(field).ok()
+    } else {
+        None
+    }
+}
+
+const DATE: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME: &[BorrowedFormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+const TIMESTAMP: &[BorrowedFormatItem<'_>] = format_description!(
+    "[year]-[month]-[day][first [T][ ]][hour]:[minute]:[second][optional [.[subsecond]]]"
+);
+
+/// Parses an ISO 8601 date, time or timestamp (with or without a UTC offset).
+fn parse_datetime(field: &str) -> Option<DateTime> {
+    if let Ok(date) = time::Date::parse(field, DATE) {
+        Some(DateTime::Date(date))
+    } else if let Ok(time) = time::Time::parse(field, TIME) {
+        Some(DateTime::Time(time))
+    } else if let Ok(ts) = time::PrimitiveDateTime::parse(field, TIMESTAMP) {
+        Some(DateTime::Timestamp(ts))
+    } else if let Ok(ts) = time::OffsetDateTime::parse(field, &Rfc3339) {
+        Some(DateTime::TimestampWithTz(ts))
+    } else {
+        None
+    }
+}
@@ -9,13 +9,21 @@ use partiql_catalog::table_fn::{
     BaseTableExpr, BaseTableExprResult, BaseTableFunctionInfo, TableFunction,
 };
 use partiql_logical as logical;
-use partiql_value::{Tuple, Value};
+use partiql_value::Value;
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::Debug;
 use std::fs::File;
+use std::io::Cursor;
 use std::path::PathBuf;
 use thiserror::Error;
+use unicase::UniCase;
+
+mod decode;
+mod options;
+
+pub use decode::decode_csv;
+pub use options::{CsvOptions, CsvTypes};
 
 /// Errors in csv extension.
 ///
@@ -34,7 +42,7 @@ pub enum CsvExtensionError {
 
     /// CSV read error.
     #[error("`scan_csv` io error: `{}`", .0)]
-    CsvReadError(Box<dyn Error>),
+    CsvReadError(Box<dyn Error + Send + Sync>),
 
     /// Data error. Generally this will result in a `MISSING` in place of this data item.
     #[error("Data error: `{}`", .0)]
@@ -83,13 +91,67 @@ impl partiql_catalog::extension::Extension for CsvExtension {
     }
 
     fn load(&self, catalog: &mut dyn Catalog) -> Result<(), ExtensionResultError> {
-        match catalog.add_table_function(TableFunction::new(Box::new(ScanCsvFunction::new()))) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(ExtensionResultError::LoadError(e.into())),
+        for function in [
+            TableFunction::new(Box::new(ScanCsvFunction::new())),
+            TableFunction::new(Box::new(ReadCsvFunction::new())),
+        ] {
+            if let Err(e) = catalog.add_table_function(function) {
+                return Err(ExtensionResultError::LoadError(e.into()));
+            }
         }
+        Ok(())
+    }
+}
+
+/// The [`CallDef`] of a CSV table function `name`, which takes a source argument followed by
+/// either a struct of options or any of the options as named arguments, e.g.,
+/// `scan_csv('data.tsv', format: 'tsv', infer_types: true)`.
+fn csv_call_def(name: &'static str) -> CallDef {
+    let call = move |arguments| {
+        logical::ValueExpr::Call(logical::CallExpr {
+            name: logical::CallName::ByName(name.to_string()),
+            arguments,
+        })
+    };
+    let options = std::iter::once(CallSpecArg::Positional).chain(
+        options::OPTION_NAMES
+            .iter()
+            .map(|name| CallSpecArg::OptionalNamed(UniCase::new(name))),
+    );
+    CallDef {
+        names: vec![name],
+        overloads: vec![
+            CallSpec {
+                input: vec![CallSpecArg::Positional, CallSpecArg::Positional],
+                output: Box::new(call),
+            },
+            CallSpec {
+                input: options.collect(),
+                output: Box::new(move |mut args| {
+                    let values = args.split_off(1);
+                    let attrs = options::OPTION_NAMES
+                        .iter()
+                        .map(|name| {
+                            logical::ValueExpr::Lit(Box::new(logical::Lit::String(
+                                name.to_string(),
+                            )))
+                        })
+                        .collect();
+                    let options = logical::TupleExpr { attrs, values };
+                    args.push(logical::ValueExpr::TupleExpr(options));
+                    call(args)
+                }),
+            },
+        ],
     }
 }
 
+/// The [`CsvOptions`] of the (optional) second argument of a CSV table function.
+fn options_arg(args: &[Cow<'_, Value>]) -> Result<CsvOptions, ExtensionResultError> {
+    let options = args.get(1).map_or(&Value::Missing, AsRef::as_ref);
+    CsvOptions::from_value(options).map_err(|e| ExtensionResultError::ReadError(e.into()))
+}
+
 #[derive(Debug)]
 pub(crate) struct ScanCsvFunction {
     call_def: CallDef,
@@ -99,18 +161,7 @@ pub(crate) struct ScanCsvFunction {
 impl ScanCsvFunction {
     pub fn new() -> Self {
         ScanCsvFunction {
-            call_def: CallDef {
-                names: vec!["scan_csv"],
-                overloads: vec![CallSpec {
-                    input: vec![CallSpecArg::Positional],
-                    output: Box::new(|args| {
-                        logical::ValueExpr::Call(logical::CallExpr {
-                            name: logical::CallName::ByName("scan_csv".to_string()),
-                            arguments: args,
-                        })
-                    }),
-                }],
-            },
+            call_def: csv_call_def("scan_csv"),
         }
     }
 }
@@ -136,9 +187,12 @@ impl BaseTableExpr for EvalFnScanCsv {
     ) -> BaseTableExprResult<'c> {
         if let Some(arg1) = args.first() {
             match arg1.as_ref() {
-                Value::String(path) => Ok(Box::new(
-                    parse_csv_file(path)?.map(|it| it.map_err(Into::into)),
-                )),
+                Value::String(path) => {
+                    let options = options_arg(args)?;
+                    Ok(Box::new(
+                        parse_csv_file(path, &options)?.map(|it| it.map_err(Into::into)),
+                    ))
+                }
                 _ => {
                     let error = CsvExtensionError::FunctionError(
                         "expected string path argument".to_string(),
@@ -153,24 +207,58 @@ impl BaseTableExpr for EvalFnScanCsv {
     }
 }
 
-fn parse_csv_file<'a>(path: &str) -> CsvTableExprResult<'a> {
+fn parse_csv_file<'a>(path: &str, options: &CsvOptions) -> CsvTableExprResult<'a> {
     let path = PathBuf::from(path).canonicalize()?;
     let file = File::open(path)?;
+    decode_csv(file, options)
+}
 
-    let mut rdr = csv::Reader::from_reader(file);
+#[derive(Debug)]
+pub(crate) struct ReadCsvFunction {
+    call_def: CallDef,
+}
 
-    let keys = rdr.headers()?.clone();
-    let data = rdr.into_records();
+/// `read_csv` reads csv data held in a string or blob (rather than in a file).
+impl ReadCsvFunction {
+    pub fn new() -> Self {
+        ReadCsvFunction {
+            call_def: csv_call_def("read_csv"),
+        }
+    }
+}
+
+impl BaseTableFunctionInfo for ReadCsvFunction {
+    fn call_def(&self) -> &CallDef {
+        &self.call_def
+    }
+
+    fn plan_eval(&self) -> Box<dyn BaseTableExpr> {
+        Box::new(EvalFnReadCsv {})
+    }
+}
 
-    let rows = data.map(move |row| {
-        match row {
-            Ok(row) => {
-                //
-                let vals = row.iter();
-                Ok(keys.iter().zip(vals).collect::<Tuple>().into())
+#[derive(Debug)]
+pub(crate) struct EvalFnReadCsv {}
+
+impl BaseTableExpr for EvalFnReadCsv {
+    fn evaluate<'c>(
+        &self,
+        args: &[Cow<'_, Value>],
+        _ctx: &'c dyn SessionContext,
+    ) -> BaseTableExprResult<'c> {
+        let data = match args.first().map(AsRef::as_ref) {
+            Some(Value::String(data)) => data.as_bytes().to_vec(),
+            Some(Value::Blob(data)) => data.as_ref().clone(),
+            _ => {
+                let error = CsvExtensionError::FunctionError(
+                    "expected string or blob data argument".to_string(),
+                );
+                return Err(ExtensionResultError::ReadError(error.into()));
             }
-            Err(err) => Err(CsvExtensionError::DataError(err.into())),
-        }
-    });
-    Ok(Box::new(rows))
+        };
+        let options = options_arg(args)?;
+        Ok(Box::new(
+            decode_csv(Cursor::new(data), &options)?.map(|it| it.map_err(Into::into)),
+        ))
+    }
 }
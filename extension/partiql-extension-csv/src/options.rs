@@ -0,0 +1,259 @@
+use crate::CsvExtensionError;
+use partiql_types::{
+    PartiqlShape, PartiqlShapeBuilder, Static, StructConstraint, StructField, StructType,
+};
+use partiql_value::{Tuple, Value};
+
+/// The names of the options of `scan_csv` and `read_csv`, in the order of their arguments.
+pub(crate) const OPTION_NAMES: [&str; 9] = [
+    "format",
+    "delimiter",
+    "quote",
+    "header",
+    "columns",
+    "null_value",
+    "comment",
+    "infer_types",
+    "schema",
+];
+
+/// How the fields of a CSV record are converted to [`Value`]s.
+#[derive(Debug, Clone, Default)]
+pub enum CsvTypes {
+    /// Every field is a `Value::String`.
+    #[default]
+    Strings,
+    /// Each field is converted to a boolean, integer, decimal or datetime if it is the text of
+    /// one, else it is a `Value::String`.
+    Infer,
+    /// The fields of the columns of the given struct type are converted to the types of those
+    /// columns; the fields of any other column are as for `fallback`.
+    Schema {
+        schema: PartiqlShape,
+        fallback: Box<CsvTypes>,
+    },
+}
+
+/// Options for reading CSV data, e.g. for reading TSV data with type inference:
+///
+/// ```
+/// use partiql_extension_csv::{CsvOptions, CsvTypes};
+/// let options = CsvOptions::tsv().with_types(CsvTypes::Infer);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub quote: u8,
+    /// Whether the first record holds the names of the columns.
+    pub header: bool,
+    /// The names of the columns; if given, any header record is skipped rather than used.
+    pub columns: Option<Vec<String>>,
+    /// A field which is read as `NULL`.
+    pub null_value: Option<String>,
+    /// Records which begin with this byte are skipped.
+    pub comment: Option<u8>,
+    pub types: CsvTypes,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: b',',
+            quote: b'"',
+            header: true,
+            columns: None,
+            null_value: None,
+            comment: None,
+            types: CsvTypes::Strings,
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Options for reading tab-separated values.
+    #[must_use]
+    pub fn tsv() -> Self {
+        CsvOptions {
+            delimiter: b'\t',
+            ..CsvOptions::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    #[must_use]
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    #[must_use]
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    #[must_use]
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    #[must_use]
+    pub fn with_null_value(mut self, null_value: impl Into<String>) -> Self {
+        self.null_value = Some(null_value.into());
+        self
+    }
+
+    #[must_use]
+    pub fn with_comment(mut self, comment: u8) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+
+    #[must_use]
+    pub fn with_types(mut self, types: CsvTypes) -> Self {
+        self.types = types;
+        self
+    }
+
+    /// Reads options from the tuple `options`, as passed to `scan_csv` or `read_csv`; `MISSING`
+    /// options take their default values.
+    pub(crate) fn from_value(options: &Value) -> Result<Self, CsvExtensionError> {
+        let options = match options {
+            Value::Missing => return Ok(CsvOptions::default()),
+            Value::Tuple(options) => options,
+            _ => return Err(option_error("options", "a struct")),
+        };
+
+        let format = options
+            .pairs()
+            .find(|(name, _)| name.eq_ignore_ascii_case("format"));
+        let mut result = match format.map(|(_, value)| value) {
+            None | Some(Value::Missing) => CsvOptions::default(),
+            Some(Value::String(format)) if format.eq_ignore_ascii_case("csv") => {
+                CsvOptions::default()
+            }
+            Some(Value::String(format)) if format.eq_ignore_ascii_case("tsv") => CsvOptions::tsv(),
+            Some(_) => return Err(option_error("format", "'csv' or 'tsv'")),
+        };
+
+        let mut infer = false;
+        let mut schema = None;
+        for (name, value) in options.pairs() {
+            let name = name.to_lowercase();
+            match (name.as_str(), value) {
+                (_, Value::Missing) => {}
+                ("format", _) => {}
+                ("delimiter", v) => result.delimiter = byte_option("delimiter", v)?,
+                ("quote", v) => result.quote = byte_option("quote", v)?,
+                ("header", v) => result.header = bool_option("header", v)?,
+                ("columns", v) => {
+                    let columns = match v {
+                        Value::List(list) => list
+                            .iter()
+                            .map(|c| match c {
+                                Value::String(c) => Some(c.to_string()),
+                                _ => None,
+                            })
+                            .collect::<Option<Vec<_>>>(),
+                        _ => None,
+                    }
+                    .ok_or_else(|| option_error("columns", "a list of strings"))?;
+                    result.columns = Some(columns);
+                }
+                ("null_value", Value::String(null)) => result.null_value = Some(null.to_string()),
+                ("null_value", _) => return Err(option_error("null_value", "a string")),
+                ("comment", v) => result.comment = Some(byte_option("comment", v)?),
+                ("infer_types", v) => infer = bool_option("infer_types", v)?,
+                ("schema", Value::Tuple(columns)) => schema = Some(schema_option(columns)?),
+                ("schema", _) => return Err(option_error("schema", "a struct of type names")),
+                (name, _) => {
+                    return Err(CsvExtensionError::FunctionError(format!(
+                        "unknown option `{name}`"
+                    )))
+                }
+            }
+        }
+
+        let fallback = if infer {
+            CsvTypes::Infer
+        } else {
+            CsvTypes::Strings
+        };
+        result.types = match schema {
+            Some(schema) => CsvTypes::Schema {
+                schema,
+                fallback: Box::new(fallback),
+            },
+            None => fallback,
+        };
+        Ok(result)
+    }
+}
+
+fn option_error(name: &str, expected: &str) -> CsvExtensionError {
+    CsvExtensionError::FunctionError(format!("expected option `{name}` to be {expected}"))
+}
+
+fn bool_option(name: &str, value: &Value) -> Result<bool, CsvExtensionError> {
+    match value {
+        Value::Boolean(b) => Ok(*b),
+        _ => Err(option_error(name, "a boolean")),
+    }
+}
+
+/// A single (ASCII) character option; `\t` denotes a tab.
+fn byte_option(name: &str, value: &Value) -> Result<u8, CsvExtensionError> {
+    match value {
+        Value::String(s) if s.as_str() == "\\t" => Ok(b'\t'),
+        Value::String(s) if s.len() == 1 => Ok(s.as_bytes()[0]),
+        _ => Err(option_error(name, "a single character string")),
+    }
+}
+
+/// Converts a struct of column names to type names (e.g., `{'age': 'INT'}`) to a struct type.
+fn schema_option(columns: &Tuple) -> Result<PartiqlShape, CsvExtensionError> {
+    let mut bld = PartiqlShapeBuilder::default();
+    let fields = columns
+        .pairs()
+        .map(|(column, ty)| {
+            let ty = match ty {
+                Value::String(ty) => static_type(ty),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                option_error(
+                    "schema",
+                    "a struct of type names (e.g., 'INT', 'DECIMAL', 'BOOL', 'TIMESTAMP', 'STRING')",
+                )
+            })?;
+            Ok(StructField::new(column, bld.new_static(ty)))
+        })
+        .collect::<Result<_, CsvExtensionError>>()?;
+    let fields = StructConstraint::Fields(fields);
+    Ok(bld.new_struct(StructType::new([fields].into())))
+}
+
+fn static_type(name: &str) -> Option<Static> {
+    let name = name.to_lowercase();
+    Some(match name.as_str() {
+        "int" | "integer" | "bigint" => Static::Int,
+        "smallint" => Static::Int16,
+        "bool" | "boolean" => Static::Bool,
+        "decimal" | "numeric" => Static::Decimal,
+        "real" | "float" => Static::Float32,
+        "double" | "double precision" => Static::Float64,
+        "string" | "varchar" | "text" => Static::String,
+        "date" | "time" | "timestamp" | "datetime" => Static::DateTime,
+        _ => return None,
+    })
+}
@@ -7,7 +7,8 @@ use partiql_eval::eval::BasicContext;
 use partiql_eval::plan::EvaluationMode;
 use partiql_extension_csv::CsvExtension;
 use partiql_parser::{Parsed, ParserResult};
-use partiql_value::{DateTime, Value};
+use partiql_value::{bag, tuple, DateTime, Value};
+use rust_decimal::Decimal;
 use std::path::PathBuf;
 
 #[track_caller]
@@ -125,3 +126,104 @@ fn join() {
 
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn options() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("resources/test/people.csv");
+    let path = path.as_path().display();
+
+    let query = format!(
+        "SELECT VALUE p FROM scan_csv('{path}', header: false, \"columns\": ['n', 'p', 'c']) AS p \
+         WHERE p.p = 'Cat'"
+    );
+    let expected = bag![tuple![("n", "Jaquie"), ("p", "Cat"), ("c", "Pink")]];
+    pass_eval(&query, &None, &expected.into());
+
+    let query = format!(
+        "SELECT VALUE p.Name FROM scan_csv('{path}', {{'null_value': 'Dog'}}) AS p \
+         WHERE p.Pet IS NULL"
+    );
+    pass_eval(&query, &None, &bag!["Azzy", "Ashton", "Lisa"].into());
+}
+
+#[test]
+fn read_tsv() {
+    let query = "SELECT * FROM read_csv('id\tname\n# a comment\n1\tone\n2\t\n', \
+                 format: 'tsv', comment: '#', null_value: '') AS t";
+    let expected = bag![
+        tuple![("id", "1"), ("name", "one")],
+        tuple![("id", "2"), ("name", Value::Null)],
+    ];
+    pass_eval(query, &None, &expected.into());
+}
+
+#[test]
+fn read_delimiter_quote() {
+    let query = "SELECT * FROM read_csv('a;b\n|x;y|;z\n', DELIMITER: ';', QUOTE: '|') AS t";
+    let expected = bag![tuple![("a", "x;y"), ("b", "z")]];
+    pass_eval(query, &None, &expected.into());
+}
+
+#[test]
+fn infer_types() {
+    let query = "SELECT * FROM read_csv('i,d,b,t,s\n1,1.5,true,2024-01-02,x\n', \
+                 infer_types: true) AS t";
+    let date = DateTime::from_ymd(2024, std::num::NonZeroU8::new(1).unwrap(), 2);
+    let expected = bag![tuple![
+        ("i", 1),
+        ("d", Value::from(Decimal::new(15, 1))),
+        ("b", true),
+        ("t", Value::from(date)),
+        ("s", "x"),
+    ]];
+    pass_eval(query, &None, &expected.into());
+}
+
+#[test]
+fn schema() {
+    let query = "SELECT * FROM read_csv('id,score,ok,note\n1,2.5,false,7\n2,,TRUE,8\n', \
+                 schema: {'id': 'INT', 'score': 'DOUBLE', 'ok': 'BOOLEAN'}) AS t";
+    let expected = bag![
+        tuple![("id", 1), ("score", 2.5), ("ok", false), ("note", "7")],
+        tuple![
+            ("id", 2),
+            ("score", Value::Null),
+            ("ok", true),
+            ("note", "8")
+        ],
+    ];
+    pass_eval(query, &None, &expected.into());
+
+    // columns not in the schema are inferred
+    let query = "SELECT * FROM read_csv('id,note\n1,7\n', \
+                 schema: {'id': 'STRING'}, infer_types: true) AS t";
+    let expected = bag![tuple![("id", "1"), ("note", 7)]];
+    pass_eval(query, &None, &expected.into());
+}
+
+#[test]
+fn conversion_error() {
+    let query = "SELECT * FROM read_csv('id\n1\none\n', schema: {'id': 'INT'}) AS t";
+    let (result, errs) = evaluate_with_csv_scan(query, &None);
+    assert_eq!(result, bag![tuple![("id", 1)], tuple![]].into());
+    assert_eq!(errs.len(), 1);
+    let err = format!("{:?}", errs[0]);
+    assert!(
+        err.contains("cannot convert `one` of column `id` to Int"),
+        "{err}"
+    );
+}
+
+#[test]
+fn invalid_options() {
+    for query in [
+        "SELECT * FROM read_csv('a\n1\n', delimiter: ';;') AS t",
+        "SELECT * FROM read_csv('a\n1\n', {'format': 'xml'}) AS t",
+        "SELECT * FROM read_csv('a\n1\n', schema: {'a': 'BLOB'}) AS t",
+        "SELECT * FROM read_csv(1) AS t",
+    ] {
+        let (_, errs) = evaluate_with_csv_scan(query, &None);
+        assert!(!errs.is_empty(), "expected an error for {query}");
+    }
+}
@@ -33,7 +33,7 @@ pub enum IonExtensionError {
 
     /// Ion Read Error
     #[error("Ion Read Error: `{}`", .0)]
-    IonReadError(Box<dyn Error>),
+    IonReadError(Box<dyn Error + Send + Sync>),
 
     /// Io error.
     #[error("`read_ion` io error: `{}`", .0)]
@@ -97,12 +97,17 @@ fn parse_ion_file<'a>(path: &str) -> IonTableExprResult<'a> {
     }
 }
 
-fn parse_ion_buff<'a, I: 'a + Read + 'static>(input: BufReader<I>) -> IonTableExprResult<'a> {
+fn parse_ion_buff<'a, I: 'a + Read + Send + 'static>(
+    input: BufReader<I>,
+) -> IonTableExprResult<'a> {
     let iter = BoxedIonType {}.stream_from_read(input)?.try_into_iter()?;
     let iter = iter.map(|value| match value {
         Ok(v) => Ok(v.into_value()),
         Err(e) => Err(match e {
-            err @ IonError::Conversion(_) => IonExtensionError::DataError(Box::new(err)),
+            BoxedIonError::IonReadError(err @ IonError::Conversion(_)) => {
+                IonExtensionError::DataError(Box::new(err))
+            }
+            BoxedIonError::IonReadError(err) => IonExtensionError::IonReadError(Box::new(err)),
             err => IonExtensionError::IonReadError(Box::new(err)),
         }),
     });
@@ -1,7 +1,7 @@
 use crate::util::{PartiqlValueTarget, ToPartiqlValue};
 use ion_rs::{
-    AnyEncoding, Element, ElementReader, IonData, IonError, IonResult, IonType,
-    OwnedSequenceIterator, Reader, Sequence, Struct, Symbol,
+    Element, ElementReader, IonData, IonError, IonResult, IonType, OwnedSequenceIterator, Sequence,
+    Struct, Symbol,
 };
 use itertools::Itertools;
 use partiql_value::boxed_variant::{
@@ -15,7 +15,6 @@ use partiql_value::datum::{
     TupleDatum,
 };
 use partiql_value::{Bag, BindingsName, List, NullableEq, Tuple, Value, Variant};
-use peekmore::{PeekMore, PeekMoreIterator};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::any::Any;
@@ -26,7 +25,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::io::{BufReader, Cursor, Read};
 use std::ops::DerefMut;
-use std::rc::Rc;
+use std::sync::{Arc, Mutex, MutexGuard};
 use thiserror::Error;
 
 #[derive(Default, Debug, Copy, Clone)]
@@ -64,7 +63,7 @@ impl BoxedVariantType for BoxedIonType {
 }
 
 impl BoxedIonType {
-    pub fn value_from_read<I: Read + 'static>(
+    pub fn value_from_read<I: Read + Send + 'static>(
         &self,
         mut input: BufReader<I>,
     ) -> BoxedIonResult<BoxedIon> {
@@ -81,7 +80,7 @@ impl BoxedIonType {
         BoxedIon::parse(cursor, BoxedIonStreamType::SingleTLV)
     }
 
-    pub fn stream_from_read<I: Read + 'static>(
+    pub fn stream_from_read<I: Read + Send + 'static>(
         &self,
         input: BufReader<I>,
     ) -> BoxedIonResult<BoxedIon> {
@@ -128,6 +127,10 @@ pub enum BoxedIonError {
     /// Expected a sequence error.
     #[error("Expected a sequence, but was `{elt}`")]
     NotASequence { elt: Box<Element> },
+
+    /// The Ion reader is unusable, as a thread panicked while reading from it.
+    #[error("Ion reader is unusable, as a thread panicked while reading from it")]
+    ReaderPoisoned,
 }
 
 pub type BoxedIonResult<T> = std::result::Result<T, BoxedIonError>;
@@ -145,22 +148,74 @@ impl<R: ElementReader> Iterator for ElementIterator<R> {
 }
 
 struct IonContext {
-    reader: PeekMoreIterator<ElementIterator<Reader<AnyEncoding, Box<dyn Read>>>>,
+    reader: element_stream::ElementStream,
 }
 
 impl IonContext {
-    fn new_ctx(data: Box<dyn Read>) -> IonResult<Self> {
-        let reader = Reader::new(AnyEncoding, data)?;
-        let reader = ElementIterator { reader }.peekmore();
+    fn new_ctx(data: Box<dyn Read + Send>) -> IonResult<Self> {
+        let reader = element_stream::ElementStream::new(data)?;
         Ok(Self { reader })
     }
 
-    pub fn new_ptr(data: Box<dyn Read>) -> IonResult<IonContextPtr> {
-        Ok(Rc::new(RefCell::new(Self::new_ctx(Box::new(data))?)))
+    pub fn new_ptr(data: Box<dyn Read + Send>) -> IonResult<IonContextPtr> {
+        Ok(Arc::new(Mutex::new(Self::new_ctx(data)?)))
     }
 }
 
-type IonContextPtr = Rc<RefCell<IonContext>>;
+mod element_stream {
+    use super::ElementIterator;
+    use ion_rs::{AnyEncoding, Element, IonError, IonResult, Reader};
+    use peekmore::{PeekMore, PeekMoreIterator};
+    use std::io::Read;
+
+    /// The top-level [`Element`]s of an Ion stream.
+    ///
+    /// This wraps the ion-rs reader, which is not `Send`, so that none of its state can escape:
+    /// the reader is private to this module and only owned `Element`s (or errors) are handed out.
+    pub(super) struct ElementStream {
+        reader: PeekMoreIterator<ElementIterator<Reader<AnyEncoding, Box<dyn Read>>>>,
+    }
+
+    // SAFETY: checked against ion-rs 1.0.0-rc.12, to which the extensions' manifests pin ion-rs.
+    // The reader is `!Send` only because its symbol and macro tables are `Rc`-shared between the
+    // reader's own internals; it never hands out those `Rc`s, and `read_next_element` returns
+    // owned `Element`s and `IonError`s, which are `Send + Sync` (as asserted below) and do not
+    // reference the reader. The reader's input is `Read + Send` (see `new`). As an
+    // `ElementStream` exposes nothing but those and booleans, all of the `Rc`s move between
+    // threads together with the only reader that uses them, which is sound.
+    // Re-check this invariant, and keep the version pinned, when upgrading ion-rs.
+    unsafe impl Send for ElementStream {}
+
+    const _: fn() = || {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Element>();
+        assert_send_sync::<IonError>();
+    };
+
+    impl ElementStream {
+        pub(super) fn new(data: Box<dyn Read + Send>) -> IonResult<Self> {
+            let reader = Reader::new(AnyEncoding, data as Box<dyn Read>)?;
+            let reader = ElementIterator { reader }.peekmore();
+            Ok(Self { reader })
+        }
+
+        /// Returns whether the stream has at least `n + 1` more elements, without consuming any.
+        pub(super) fn has_nth(&mut self, n: usize) -> bool {
+            self.reader.peek_nth(n).is_some()
+        }
+
+        pub(super) fn next(&mut self) -> Option<IonResult<Element>> {
+            self.reader.next()
+        }
+    }
+}
+
+type IonContextPtr = Arc<Mutex<IonContext>>;
+
+/// Locks the [`IonContext`] of `ctx`, failing if another thread panicked while holding it.
+fn lock_context(ctx: &IonContextPtr) -> BoxedIonResult<MutexGuard<'_, IonContext>> {
+    ctx.lock().map_err(|_| BoxedIonError::ReaderPoisoned)
+}
 
 #[derive(Clone)]
 pub struct BoxedIon {
@@ -513,24 +568,27 @@ impl BoxedIon {
         self.child(child).into_value()
     }
 
-    pub(crate) fn parse(data: Box<dyn Read>, expected: BoxedIonStreamType) -> BoxedIonResult<Self> {
+    pub(crate) fn parse(
+        data: Box<dyn Read + Send>,
+        expected: BoxedIonStreamType,
+    ) -> BoxedIonResult<Self> {
         let mut ctx = IonContext::new_ptr(data)?;
         let doc = Self::init_doc(&mut ctx, expected);
         Ok(Self::new(doc?, ctx))
     }
 
     #[allow(dead_code)]
-    pub(crate) fn parse_unknown(data: Box<dyn Read>) -> BoxedIonResult<Self> {
+    pub(crate) fn parse_unknown(data: Box<dyn Read + Send>) -> BoxedIonResult<Self> {
         Self::parse(data, BoxedIonStreamType::Unknown)
     }
 
     #[allow(dead_code)]
-    pub(crate) fn parse_tlv(data: Box<dyn Read>) -> BoxedIonResult<Self> {
+    pub(crate) fn parse_tlv(data: Box<dyn Read + Send>) -> BoxedIonResult<Self> {
         Self::parse(data, BoxedIonStreamType::SingleTLV)
     }
 
     #[allow(dead_code)]
-    pub(crate) fn parse_stream(data: Box<dyn Read>) -> BoxedIonResult<Self> {
+    pub(crate) fn parse_stream(data: Box<dyn Read + Send>) -> BoxedIonResult<Self> {
         Self::parse(data, BoxedIonStreamType::Stream)
     }
 
@@ -538,10 +596,11 @@ impl BoxedIon {
         ctx: &mut IonContextPtr,
         expected: BoxedIonStreamType,
     ) -> BoxedIonResult<BoxedIonValue> {
-        let reader = &mut ctx.borrow_mut().reader;
+        let mut ctx = lock_context(ctx)?;
+        let reader = &mut ctx.reader;
         let expected = match expected {
             BoxedIonStreamType::Unknown => {
-                if reader.peek_nth(1).is_some() {
+                if reader.has_nth(1) {
                     BoxedIonStreamType::Stream
                 } else {
                     BoxedIonStreamType::SingleTLV
@@ -556,7 +615,7 @@ impl BoxedIon {
             BoxedIonStreamType::Stream => BoxedIonValue::Stream(),
             BoxedIonStreamType::SingleTLV => {
                 if let Some(elt) = reader.next() {
-                    if reader.peek().is_some() {
+                    if reader.has_nth(0) {
                         // TODO error on stream instead of TLV?
                     }
                     BoxedIonValue::Value(elt?)
@@ -851,12 +910,18 @@ pub struct BoxedIonIterator {
 }
 
 impl Iterator for BoxedIonIterator {
-    type Item = IonResult<BoxedIon>;
+    type Item = BoxedIonResult<BoxedIon>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let elt = match self.inner.borrow_mut().deref_mut() {
-            BoxedIonIterType::Stream() => self.ctx.borrow_mut().deref_mut().reader.next(),
-            BoxedIonIterType::Sequence(seq) => Ok(seq.next()).transpose(),
+            BoxedIonIterType::Stream() => match lock_context(&self.ctx) {
+                Ok(mut ctx) => ctx
+                    .reader
+                    .next()
+                    .map(|res| res.map_err(BoxedIonError::from)),
+                Err(err) => Some(Err(err)),
+            },
+            BoxedIonIterType::Sequence(seq) => seq.next().map(Ok),
         };
         elt.map(|res| res.map(|elt| BoxedIon::new(BoxedIonValue::Value(elt), self.ctx.clone())))
     }
@@ -150,6 +150,7 @@ impl ToDot<ast::Expr> for AstToDot {
         match &ast {
             Expr::Lit(l) => self.to_dot(&mut expr_subgraph, l),
             Expr::VarRef(v) => self.to_dot(&mut expr_subgraph, v),
+            Expr::Parameter(p) => self.to_dot(&mut expr_subgraph, p),
             Expr::BinOp(bop) => self.to_dot(&mut expr_subgraph, bop),
             Expr::UniOp(unop) => self.to_dot(&mut expr_subgraph, unop),
             Expr::Like(like) => self.to_dot(&mut expr_subgraph, like),
@@ -162,9 +163,11 @@ impl ToDot<ast::Expr> for AstToDot {
             Expr::Path(p) => self.to_dot(&mut expr_subgraph, p),
             Expr::Call(c) => self.to_dot(&mut expr_subgraph, c),
             Expr::CallAgg(c) => self.to_dot(&mut expr_subgraph, c),
+            Expr::Window(w) => self.to_dot(&mut expr_subgraph, w),
             Expr::Query(q) => self.to_dot(&mut expr_subgraph, q),
             Expr::Error => todo!(),
             Expr::GraphMatch(_) => todo!(),
+            Expr::Type(t) => self.to_dot(&mut expr_subgraph, t),
         }
     }
 }
@@ -239,6 +242,14 @@ fn type_to_str(ty: &ast::Type) -> String {
     }
 }
 
+impl ToDot<ast::Type> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::Type) -> Targets {
+        let mut node = out.node_auto_labelled(&type_to_str(ast));
+        node.set("shape", "parallelogram", false);
+        vec![node.id()]
+    }
+}
+
 impl ToDot<ast::Lit> for AstToDot {
     fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::Lit) -> Targets {
         let lbl = lit_to_str(ast);
@@ -351,7 +362,97 @@ impl ToDot<ast::QuerySet> for AstToDot {
             QuerySet::Expr(e) => self.to_dot(out, e),
             QuerySet::Values(_) => todo!(),
             QuerySet::Table(_) => todo!(),
+            QuerySet::Dml(dml) => self.to_dot(out, dml),
+        }
+    }
+}
+
+impl ToDot<ast::Dml> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::Dml) -> Targets {
+        let id = out.node_auto_labelled("Dml").id();
+
+        self.to_dot(out, &ast.ops).edges(out, &id, "");
+        out.with_cluster("FROM", |mut cl| self.to_dot(&mut cl, &ast.from_clause))
+            .edges(out, &id, "");
+        out.with_cluster("WHERE", |mut cl| self.to_dot(&mut cl, &ast.where_clause))
+            .edges(out, &id, "");
+        out.with_cluster("RETURNING", |mut cl| self.to_dot(&mut cl, &ast.returning))
+            .edges(out, &id, "");
+
+        vec![id]
+    }
+}
+
+impl ToDot<ast::DmlOp> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::DmlOp) -> Targets {
+        use ast::DmlOp;
+        match &ast {
+            DmlOp::Insert(insert) => {
+                let id = out.node_auto_labelled("Insert").id();
+                self.to_dot(out, &insert.target).edges(out, &id, "target");
+                self.to_dot(out, &insert.values).edges(out, &id, "values");
+                self.to_dot(out, &insert.on_conflict)
+                    .edges(out, &id, "on_conflict");
+                vec![id]
+            }
+            DmlOp::InsertValue(insert) => {
+                let id = out.node_auto_labelled("InsertValue").id();
+                self.to_dot(out, &insert.target).edges(out, &id, "target");
+                self.to_dot(out, &insert.value).edges(out, &id, "value");
+                self.to_dot(out, &insert.index).edges(out, &id, "at");
+                self.to_dot(out, &insert.on_conflict)
+                    .edges(out, &id, "on_conflict");
+                vec![id]
+            }
+            DmlOp::Set(set) => {
+                let id = out.node_auto_labelled("Set").id();
+                self.to_dot(out, &set.assignment.target)
+                    .edges(out, &id, "target");
+                self.to_dot(out, &set.assignment.value)
+                    .edges(out, &id, "value");
+                vec![id]
+            }
+            DmlOp::Remove(remove) => {
+                let id = out.node_auto_labelled("Remove").id();
+                self.to_dot(out, &remove.target).edges(out, &id, "target");
+                vec![id]
+            }
+            DmlOp::Delete(_) => vec![out.node_auto_labelled("Delete").id()],
+        }
+    }
+}
+
+impl ToDot<ast::OnConflict> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::OnConflict) -> Targets {
+        let id = out.node_auto_labelled("DoNothing").id();
+        self.to_dot(out, &ast.expr).edges(out, &id, "where");
+        vec![id]
+    }
+}
+
+impl ToDot<ast::ReturningExpr> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::ReturningExpr) -> Targets {
+        self.to_dot(out, &ast.elems)
+    }
+}
+
+impl ToDot<ast::ReturningElem> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::ReturningElem) -> Targets {
+        let lbl = match &ast.mapping {
+            ast::ReturningMapping::ModifiedNew => "Modified New",
+            ast::ReturningMapping::ModifiedOld => "Modified Old",
+            ast::ReturningMapping::AllNew => "All New",
+            ast::ReturningMapping::AllOld => "All Old",
+        };
+        let id = out.node_auto_labelled(lbl).id();
+
+        match &ast.column {
+            ast::ColumnComponent::ReturningWildcard => vec![out.node_auto_labelled("*").id()],
+            ast::ColumnComponent::ReturningColumn(column) => self.to_dot(out, &column.expr),
         }
+        .edges(out, &id, "");
+
+        vec![id]
     }
 }
 
@@ -475,6 +576,18 @@ impl ToDot<ast::VarRef> for AstToDot {
     }
 }
 
+impl ToDot<ast::Parameter> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::Parameter) -> Targets {
+        let lbl = match ast {
+            ast::Parameter::Positional(_) => "?".to_string(),
+            ast::Parameter::Named(name) => format!(":{}", symbol_primitive_to_label(name)),
+        };
+        let id = out.node_auto_labelled(&lbl).id();
+
+        vec![id]
+    }
+}
+
 impl ToDot<ast::OrderByExpr> for AstToDot {
     fn to_dot(&mut self, _out: &mut Scope<'_, '_>, _ast: &ast::OrderByExpr) -> Targets {
         todo!("OrderByExpr");
@@ -583,22 +696,14 @@ impl ToDot<ast::CallArg> for AstToDot {
                     .edges(out, &id, "value");
                 vec![id]
             }
-            CallArg::PositionalType(ty) => {
-                let mut node = out.node_auto_labelled(&type_to_str(ty));
-                node.set("shape", "parallelogram", false);
-                vec![node.id()]
-            }
+            CallArg::PositionalType(ty) => self.to_dot(out, ty),
             CallArg::NamedType(call_arg_named_type) => {
                 let id = out.node_auto_labelled("Named").id();
                 self.to_dot(out, &call_arg_named_type.name)
                     .edges(out, &id, "name");
 
-                let ty_target = {
-                    let mut ty_node = out.node_auto_labelled(&type_to_str(&call_arg_named_type.ty));
-                    ty_node.set("shape", "parallelogram", false);
-                    vec![ty_node.id()]
-                };
-                ty_target.edges(out, &id, "type");
+                self.to_dot(out, &call_arg_named_type.ty)
+                    .edges(out, &id, "type");
 
                 vec![id]
             }
@@ -618,6 +723,19 @@ impl ToDot<ast::CallAgg> for AstToDot {
     }
 }
 
+impl ToDot<ast::Window> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::Window) -> Targets {
+        let id = out.node_auto_labelled("Window").id();
+
+        self.to_dot(out, &ast.func_name).edges(out, &id, "name");
+        self.to_dot(out, &ast.args).edges(out, &id, "args");
+        self.to_dot(out, &ast.spec.node.partition_by)
+            .edges(out, &id, "partition_by");
+
+        vec![id]
+    }
+}
+
 impl ToDot<ast::Path> for AstToDot {
     fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::Path) -> Targets {
         let id = out.node_auto_labelled("Path").id();
@@ -651,7 +769,20 @@ impl ToDot<ast::PathExpr> for AstToDot {
 }
 
 impl ToDot<ast::Let> for AstToDot {
-    fn to_dot(&mut self, _out: &mut Scope<'_, '_>, _ast: &ast::Let) -> Targets {
-        todo!("Let");
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::Let) -> Targets {
+        let mut list = vec![];
+        for binding in &ast.let_bindings {
+            list.extend(self.to_dot(out, binding));
+        }
+        list
+    }
+}
+
+impl ToDot<ast::LetBinding> for AstToDot {
+    fn to_dot(&mut self, out: &mut Scope<'_, '_>, ast: &ast::LetBinding) -> Targets {
+        let id = out.node_auto_labelled("LetBinding").id();
+        self.to_dot(out, &ast.expr).edges(out, &id, "");
+        self.to_dot(out, &ast.as_alias).edges(out, &id, "as");
+        vec![id]
     }
 }
@@ -1,7 +1,7 @@
 use dot_writer::{Attributes, DotWriter, NodeId, Scope, Shape};
 use itertools::Itertools;
 use partiql_logical::{
-    AggregateExpression, BinaryOp, BindingsOp, JoinKind, LogicalPlan, ValueExpr,
+    AggregateExpression, BinaryOp, BindingsOp, DmlOp, ExcludePath, JoinKind, LogicalPlan, ValueExpr,
 };
 
 use std::collections::HashMap;
@@ -47,6 +47,15 @@ impl PlanToDot {
                 &u.at_key.as_deref().unwrap_or("")
             ),
             BindingsOp::Filter(f) => format!("{{filter | {} }}", expr_to_str(&f.expr)),
+            BindingsOp::Let(l) => {
+                format!(
+                    "{{let | {} }}",
+                    l.bindings
+                        .iter()
+                        .map(|(k, e)| format!("{}:{}", k, expr_to_str(e)))
+                        .join(" | "),
+                )
+            }
             BindingsOp::OrderBy(o) => {
                 let specs = o
                     .specs
@@ -84,10 +93,16 @@ impl PlanToDot {
                     JoinKind::Full => "full",
                     JoinKind::Cross => "cross",
                 };
+                let using = join
+                    .using
+                    .iter()
+                    .map(|(k, e)| format!(" | {}:{}", k, expr_to_str(e)))
+                    .join("");
                 format!(
-                    "{{ {} join | {} }}",
+                    "{{ {} join | {}{} }}",
                     kind,
-                    join.on.as_ref().map(expr_to_str).unwrap_or("".to_string())
+                    join.on.as_ref().map(expr_to_str).unwrap_or("".to_string()),
+                    using
                 )
             }
             BindingsOp::BagOp(_) => "bag op (TODO)".to_string(),
@@ -123,6 +138,35 @@ impl PlanToDot {
             BindingsOp::Having(h) => {
                 format!("{{ having | {} }}", expr_to_str(&h.expr))
             }
+            BindingsOp::With(w) => {
+                format!(
+                    "{{ with | {} }}",
+                    w.ctes.iter().map(|cte| cte.name.as_str()).join(" | ")
+                )
+            }
+            BindingsOp::Window(w) => {
+                format!(
+                    "{{ window | {} }}",
+                    w.exprs
+                        .iter()
+                        .map(|e| format!("{}:{:?}", e.name, e.func))
+                        .join(" | ")
+                )
+            }
+            BindingsOp::Exclude(e) => {
+                format!(
+                    "{{ exclude | {} }}",
+                    e.paths.iter().map(exclude_path_to_str).join(" | ")
+                )
+            }
+            BindingsOp::Dml(dml) => {
+                let op = match &dml.op {
+                    DmlOp::Insert { .. } => "insert",
+                    DmlOp::Update { .. } => "update",
+                    DmlOp::Delete { .. } => "delete",
+                };
+                format!("{{ {op} | {} }}", dml.target.as_key)
+            }
             BindingsOp::Sink => "sink".to_string(),
         };
         node.set_shape(Shape::Mrecord).set_label(&label.to_string());
@@ -158,8 +202,21 @@ fn expr_to_str(expr: &ValueExpr) -> String {
     }
 }
 
+fn exclude_path_to_str(path: &ExcludePath) -> String {
+    let path: String = format!("{:?}:{:?}", path.root, path.steps)
+        .escape_default()
+        .collect();
+    let path = path.replace('{', "\\{");
+    path.replace('}', "\\}")
+}
+
 fn agg_expr_to_str(agg_expr: &AggregateExpression) -> String {
-    let expr: String = format!("{:?}", agg_expr.expr).escape_default().collect();
+    let expr: String = std::iter::once(&agg_expr.expr)
+        .chain(agg_expr.args.iter())
+        .map(|e| format!("{e:?}"))
+        .join(", ")
+        .escape_default()
+        .collect();
     let expr = expr.replace('{', "\\{");
     let expr = expr.replace('}', "\\}");
     format!(
@@ -1,4 +1,5 @@
 use partiql_catalog::call_defs::CallLookupError;
+use partiql_common::node::NodeId;
 use std::error::Error;
 use thiserror::Error;
 
@@ -6,6 +7,23 @@ use thiserror::Error;
 #[derive(Debug)]
 pub struct AstTransformationError {
     pub errors: Vec<AstTransformError>,
+    /// The id of the AST node at which the error of the same index in `errors` occurred, if known
+    pub nodes: Vec<Option<NodeId>>,
+}
+
+impl AstTransformationError {
+    /// Creates an [`AstTransformationError`] of `errors` which occurred at no known AST node.
+    #[must_use]
+    pub fn new(errors: Vec<AstTransformError>) -> Self {
+        let nodes = vec![None; errors.len()];
+        AstTransformationError { errors, nodes }
+    }
+
+    /// Returns the errors, each with the id of the AST node at which it occurred, if known.
+    pub fn located(&self) -> impl Iterator<Item = (&AstTransformError, Option<NodeId>)> {
+        let nodes = self.nodes.iter().copied().chain(std::iter::repeat(None));
+        self.errors.iter().zip(nodes)
+    }
 }
 
 /// Represents an AST transform Error
@@ -44,6 +62,43 @@ pub enum AstTransformError {
     #[error("HAVING clause provided without GROUP BY")]
     HavingWithoutGroupBy,
 
+    /// Indicates that a `WITH` clause defines the same name more than once
+    #[error("WITH clause defines `{0}` more than once")]
+    DuplicateWithElement(String),
+
+    /// Indicates that a `WITH RECURSIVE` element is not of the form `<anchor> UNION [ALL] <recursive>`
+    /// or that a `SEARCH`/`CYCLE` clause is attached to a non-recursive `WITH` element
+    #[error("Invalid recursive WITH element `{0}`")]
+    InvalidRecursiveWithElement(String),
+
+    /// Indicates that a window function's `OVER` clause is not valid for the function
+    #[error("Invalid window specification: {0}")]
+    InvalidWindowSpecification(String),
+
+    /// Indicates that a window function is used in a clause, such as `WHERE` or `HAVING`, which
+    /// is evaluated before the window functions of its query
+    #[error("Window function `{0}` not allowed in {1} clause")]
+    WindowFunctionNotAllowed(String, String),
+
+    /// Indicates that the target of a data modification statement, or of one of its `SET` or
+    /// `REMOVE` clauses, is not a collection name or a path into the modified row, respectively
+    #[error("Invalid data modification target: {0}")]
+    InvalidDmlTarget(String),
+
+    /// Indicates that a type is unknown or that its parameters are not valid
+    #[error("Invalid type: {0}")]
+    InvalidType(String),
+
+    /// Indicates that the columns of a `NATURAL` join cannot be determined, as the attributes of
+    /// the tuples bound by one of its `FROM` sources are not statically known
+    #[error("Cannot determine the columns of NATURAL JOIN: {0}")]
+    UnknownNaturalJoinColumns(String),
+
+    /// Indicates that the right operand of a `RIGHT` or `FULL` join refers to a variable of its
+    /// left operand, which it cannot depend upon
+    #[error("Right operand of {0} JOIN may not refer to `{1}` of its left operand")]
+    InvalidJoinReference(String, String),
+
     /// Some other error; likely from a plugin
     #[error(transparent)]
     Other(Box<dyn Error>),
@@ -6,7 +6,9 @@ use partiql_ast::ast::{GraphPattern, GroupByExpr, GroupKey};
 use partiql_ast::visit::{Traverse, Visit, Visitor};
 use partiql_catalog::catalog::SharedCatalog;
 use partiql_common::node::NodeId;
+use partiql_types::{PartiqlShape, Static};
 use std::sync::atomic::{AtomicU32, Ordering};
+use unicase::UniCase;
 
 type FnvIndexSet<T> = IndexSet<T, FnvBuildHasher>;
 
@@ -78,6 +80,8 @@ struct KeyRefs {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 enum EnclosingClause {
     FromLet,
+    JoinSpec,
+    Let,
     Query,
 }
 
@@ -100,8 +104,9 @@ pub struct NameResolver<'c> {
     schema: FnvIndexMap<NodeId, KeySchema>,
     aliases: FnvIndexMap<NodeId, Symbol>,
 
-    // errors that occur during name resolution
+    // errors that occur during name resolution, and the ids of the nodes at which they occur
     errors: Vec<AstTransformError>,
+    error_nodes: Vec<Option<NodeId>>,
     catalog: &'c dyn SharedCatalog,
 }
 
@@ -123,6 +128,7 @@ impl<'c> NameResolver<'c> {
 
             // errors that occur during name resolution
             errors: Default::default(),
+            error_nodes: Default::default(),
             catalog,
         }
     }
@@ -133,8 +139,10 @@ impl<'c> NameResolver<'c> {
     ) -> Result<KeyRegistry, AstTransformationError> {
         query.visit(self);
         if !self.errors.is_empty() {
+            self.locate_errors();
             return Err(AstTransformationError {
                 errors: std::mem::take(&mut self.errors),
+                nodes: std::mem::take(&mut self.error_nodes),
             });
         }
 
@@ -153,6 +161,13 @@ impl<'c> NameResolver<'c> {
         self.id_path_to_root.last().unwrap()
     }
 
+    /// Records the current node as the node at which the errors since the last call occurred.
+    #[inline]
+    fn locate_errors(&mut self) {
+        let node = self.id_path_to_root.last().copied();
+        self.error_nodes.resize(self.errors.len(), node);
+    }
+
     #[inline]
     fn is_from_path(&self) -> bool {
         let is_qnode = |typ, id| {
@@ -210,10 +225,85 @@ impl<'c> NameResolver<'c> {
     fn push_consume_name(&mut self, name: NameRef) {
         self.keyref_stack.last_mut().unwrap().consume.insert(name);
     }
+
+    /// The columns of a `NATURAL` join, i.e., the attributes common to the tuples bound by its
+    /// left and right `FROM` sources.
+    fn natural_join_columns(
+        &self,
+        join: &ast::Join,
+    ) -> Result<Vec<ast::SymbolPrimitive>, AstTransformError> {
+        let left = self.source_attrs(&join.left)?;
+        let right = self.source_attrs(&join.right)?;
+        Ok(left
+            .into_iter()
+            .filter(|attr| right.iter().any(|r| UniCase::new(r) == UniCase::new(attr)))
+            .map(|value| ast::SymbolPrimitive {
+                value,
+                case: ast::CaseSensitivity::CaseInsensitive,
+            })
+            .collect())
+    }
+
+    /// The attributes of the tuples bound by the `FROM` source `source`, which are statically known
+    /// if it names its columns (e.g., `FROM t AS v (x, y)`) or scans a table of the catalog whose
+    /// type is a collection of closed structs.
+    fn source_attrs(&self, source: &ast::FromSource) -> Result<Vec<String>, AstTransformError> {
+        match source {
+            ast::FromSource::FromLet(from_let) => {
+                let from_let = &from_let.node;
+                if let Some(columns) = &from_let.columns {
+                    return Ok(columns.iter().map(|col| col.value.clone()).collect());
+                }
+                let ty = match (&from_let.kind, from_let.expr.as_ref()) {
+                    (ast::FromLetKind::Scan, ast::Expr::VarRef(var_ref)) => self
+                        .catalog
+                        .resolve_type(&var_ref.node.name.value)
+                        .map(|entry| entry.ty().clone()),
+                    _ => None,
+                };
+                ty.as_ref().and_then(element_attrs).ok_or_else(|| {
+                    let source = from_let.as_alias.as_ref().map_or_else(
+                        || "a FROM source".to_string(),
+                        |sym| format!("`{}`", sym.value),
+                    );
+                    AstTransformError::UnknownNaturalJoinColumns(format!(
+                        "the attributes of {source} are not statically known"
+                    ))
+                })
+            }
+            ast::FromSource::Join(join) => {
+                let mut attrs = self.source_attrs(&join.node.left)?;
+                for attr in self.source_attrs(&join.node.right)? {
+                    if !attrs.iter().any(|a| UniCase::new(a) == UniCase::new(&attr)) {
+                        attrs.push(attr);
+                    }
+                }
+                Ok(attrs)
+            }
+        }
+    }
+}
+
+/// The attributes of the elements of a collection of type `ty`, if they are statically known
+/// (i.e., if its elements are closed structs).
+fn element_attrs(ty: &PartiqlShape) -> Option<Vec<String>> {
+    let element = match ty {
+        PartiqlShape::Static(s) => match s.ty() {
+            Static::Bag(bag) => bag.element_type(),
+            Static::Array(array) => array.element_type(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let element = element.expect_struct().ok()?;
+    element
+        .is_closed()
+        .then(|| element.fields().map(|f| f.name().to_string()).collect())
 }
 
 impl<'ast> Visitor<'ast> for NameResolver<'_> {
     fn enter_ast_node(&mut self, id: NodeId) -> Traverse {
+        self.locate_errors();
         self.id_path_to_root.push(id);
         if let Some(children) = self.id_child_stack.last_mut() {
             children.push(id);
@@ -221,6 +311,7 @@ impl<'ast> Visitor<'ast> for NameResolver<'_> {
         Traverse::Continue
     }
     fn exit_ast_node(&mut self, id: NodeId) -> Traverse {
+        self.locate_errors();
         assert_eq!(self.id_path_to_root.pop(), Some(id));
         Traverse::Continue
     }
@@ -287,11 +378,45 @@ impl<'ast> Visitor<'ast> for NameResolver<'_> {
         Traverse::Continue
     }
 
-    fn exit_join(&mut self, _join: &'ast ast::Join) -> Traverse {
+    fn exit_join(&mut self, join: &'ast ast::Join) -> Traverse {
         if let Err(e) = self.exit_child_stack() {
             self.errors.push(e);
             return Traverse::Stop;
         };
+
+        // The columns of a `USING` or `NATURAL` join are variables of its joined bindings
+        let Some(spec) = &join.predicate else {
+            return Traverse::Continue;
+        };
+        let columns = match &spec.node {
+            ast::JoinSpec::On(_) => return Traverse::Continue,
+            ast::JoinSpec::Using(columns) => columns.clone(),
+            ast::JoinSpec::Natural => match self.natural_join_columns(join) {
+                Ok(columns) => columns,
+                Err(e) => {
+                    self.errors.push(e);
+                    return Traverse::Stop;
+                }
+            },
+        };
+        self.enclosing_clause
+            .entry(EnclosingClause::JoinSpec)
+            .or_default()
+            .push(spec.id);
+
+        // Scopes above this join in the AST are in-scope to use variables defined by its columns
+        for in_scope in &self.id_path_to_root {
+            self.in_scope.entry(*in_scope).or_default().push(spec.id);
+        }
+
+        let produce: Names = columns.into_iter().map(Symbol::Known).collect();
+        self.schema.insert(
+            spec.id,
+            KeySchema {
+                consume: Default::default(),
+                produce,
+            },
+        );
         Traverse::Continue
     }
 
@@ -357,6 +482,79 @@ impl<'ast> Visitor<'ast> for NameResolver<'_> {
         Traverse::Continue
     }
 
+    fn enter_let(&mut self, _let: &'ast ast::Let) -> Traverse {
+        let id = *self.current_node();
+        self.enclosing_clause
+            .entry(EnclosingClause::Let)
+            .or_default()
+            .push(id);
+
+        // The `LET` bindings are in-scope of the variables defined by the enclosing query's `FROM`
+        let query = self.id_path_to_root.iter().rev().skip(1).find(|id| {
+            self.enclosing_clause
+                .get(&EnclosingClause::Query)
+                .is_some_and(|nodes| nodes.contains(id))
+        });
+        if let Some(in_scope) = query.and_then(|query| self.in_scope.get(query)).cloned() {
+            self.in_scope.entry(id).or_default().extend(in_scope);
+        }
+        Traverse::Continue
+    }
+
+    fn exit_let(&mut self, let_clause: &'ast ast::Let) -> Traverse {
+        let id = *self.current_node();
+
+        // Scopes above this `LET` in the AST are in-scope to use variables defined by this `LET`
+        for in_scope in self.id_path_to_root.iter().rev().skip(1) {
+            self.in_scope.entry(*in_scope).or_default().push(id);
+        }
+
+        let produce: Names = let_clause
+            .let_bindings
+            .iter()
+            .map(|binding| Symbol::Known(binding.node.as_alias.clone()))
+            .collect();
+        let consume = Default::default();
+        self.schema.insert(id, KeySchema { consume, produce });
+        Traverse::Continue
+    }
+
+    fn enter_let_binding(&mut self, _binding: &'ast ast::LetBinding) -> Traverse {
+        let id = *self.current_node();
+        self.enter_keyref();
+
+        // A `LET` binding is in-scope of the variables in scope of its `LET`, which include the
+        // preceding bindings of the `LET`
+        let let_id = self.id_path_to_root.iter().rev().nth(1).copied();
+        if let Some(in_scope) = let_id
+            .and_then(|let_id| self.in_scope.get(&let_id))
+            .cloned()
+        {
+            self.in_scope.entry(id).or_default().extend(in_scope);
+        }
+        Traverse::Continue
+    }
+
+    fn exit_let_binding(&mut self, binding: &'ast ast::LetBinding) -> Traverse {
+        let id = *self.current_node();
+        let KeyRefs { consume, .. } = match self.exit_keyref() {
+            Ok(kr) => kr,
+            Err(e) => {
+                self.errors.push(e);
+                return Traverse::Stop;
+            }
+        };
+
+        // The following bindings of the enclosing `LET` are in-scope of this binding
+        if let Some(&let_id) = self.id_path_to_root.iter().rev().nth(1) {
+            self.in_scope.entry(let_id).or_default().push(id);
+        }
+
+        let produce: Names = std::iter::once(Symbol::Known(binding.as_alias.clone())).collect();
+        self.schema.insert(id, KeySchema { consume, produce });
+        Traverse::Continue
+    }
+
     fn enter_var_ref(&mut self, var_ref: &'ast ast::VarRef) -> Traverse {
         let is_from_path = self.is_from_path();
 
@@ -426,6 +624,15 @@ impl<'ast> Visitor<'ast> for NameResolver<'_> {
             .for_each(|enclosing_clause| {
                 self.in_scope.entry(id).or_default().push(*enclosing_clause);
             });
+        // `GROUP BY` keys may use the variables defined by `LET` and by the columns of joins
+        for clause in [EnclosingClause::Let, EnclosingClause::JoinSpec] {
+            if let Some(ids) = self.enclosing_clause.get(&clause) {
+                self.in_scope
+                    .entry(id)
+                    .or_default()
+                    .extend(ids.iter().copied());
+            }
+        }
 
         self.enclosing_clause
             .entry(EnclosingClause::Query)
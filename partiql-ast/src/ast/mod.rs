@@ -114,8 +114,10 @@ pub struct DropIndex {
 #[derive(Visit, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Dml {
-    pub op: DmlOp,
-    pub from_clause: Option<FromClause>,
+    /// The operations of the statement; an `UPDATE` may `SET` and `REMOVE` several paths
+    pub ops: Vec<DmlOp>,
+    /// The collection modified by an `UPDATE` or `DELETE`
+    pub from_clause: Option<AstNode<FromClause>>,
     pub where_clause: Option<Box<Expr>>,
     pub returning: Option<ReturningExpr>,
 }
@@ -148,18 +150,18 @@ pub struct ReturningExpr {
 pub struct ReturningElem {
     #[visit(skip)]
     pub mapping: ReturningMapping,
-    #[visit(skip)]
     pub column: ColumnComponent,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Visit, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ColumnComponent {
+    #[visit(skip)]
     ReturningWildcard,
     ReturningColumn(ReturningColumn),
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Visit, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ReturningColumn {
     pub expr: Box<Expr>,
@@ -180,6 +182,7 @@ pub enum ReturningMapping {
 pub struct Insert {
     pub target: Box<Expr>,
     pub values: Box<Expr>,
+    pub on_conflict: Option<OnConflict>,
 }
 
 #[derive(Visit, Clone, Debug, PartialEq)]
@@ -254,6 +257,9 @@ pub struct TopLevelQuery {
 #[derive(Visit, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Query {
+    /// The `WITH` clause of a query nested in a statement, e.g. of a subquery or of the source of
+    /// an `INSERT`; that of a top-level query is held by its [`TopLevelQuery`].
+    pub with: Option<AstNode<WithClause>>,
     pub set: AstNode<QuerySet>,
     pub order_by: Option<Box<AstNode<OrderByExpr>>>,
     pub limit_offset: Option<Box<AstNode<LimitOffsetClause>>>,
@@ -275,6 +281,38 @@ pub struct WithElement {
     #[visit(skip)]
     pub columns: Option<Vec<SymbolPrimitive>>,
     pub subquery: AstNode<Expr>,
+    #[visit(skip)]
+    pub search: Option<WithSearch>,
+    #[visit(skip)]
+    pub cycle: Option<WithCycle>,
+}
+
+/// The `SEARCH` clause of a recursive `WITH` element, e.g. `SEARCH DEPTH FIRST BY id SET ord`
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WithSearch {
+    pub order: SearchOrder,
+    pub by: Vec<SymbolPrimitive>,
+    pub set: SymbolPrimitive,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SearchOrder {
+    DepthFirst,
+    BreadthFirst,
+}
+
+/// The `CYCLE` clause of a recursive `WITH` element, e.g.
+/// `CYCLE id SET is_cycle TO 'Y' DEFAULT 'N' USING cycle_path`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WithCycle {
+    pub columns: Vec<SymbolPrimitive>,
+    pub mark: SymbolPrimitive,
+    /// The `TO` and `DEFAULT` values of the mark column; `TRUE` and `FALSE` if absent.
+    pub mark_values: Option<(Lit, Lit)>,
+    pub path: SymbolPrimitive,
 }
 
 #[derive(Visit, Clone, Debug, PartialEq)]
@@ -285,6 +323,8 @@ pub enum QuerySet {
     Expr(Box<Expr>),
     Values(Vec<Box<Expr>>),
     Table(QueryTable),
+    /// A data modification statement, whose result is the bag of its `RETURNING` rows
+    Dml(Box<AstNode<Dml>>),
 }
 
 #[derive(Visit, Clone, Debug, PartialEq)]
@@ -399,6 +439,8 @@ pub enum Expr {
     Lit(AstNode<Lit>),
     /// Variable reference
     VarRef(AstNode<VarRef>),
+    /// Query parameter, e.g. `?` or `:name`
+    Parameter(AstNode<Parameter>),
     /// Binary operator
     BinOp(AstNode<BinOp>),
     /// Unary operators
@@ -416,8 +458,13 @@ pub enum Expr {
     Path(AstNode<Path>),
     Call(AstNode<Call>),
     CallAgg(AstNode<CallAgg>),
+    /// Window function call, e.g. `RANK() OVER (PARTITION BY a ORDER BY b)`
+    Window(AstNode<Window>),
     /// <expr> MATCH <graph_pattern>
     GraphMatch(Box<AstNode<GraphMatch>>),
+    /// A type, e.g. `INT` in `a IS INT`
+    #[visit(skip)]
+    Type(AstNode<Type>),
 
     /// Query, e.g. `UNION` | `EXCEPT` | `INTERSECT` | `SELECT` and their parts.
     Query(AstNode<Query>),
@@ -432,6 +479,7 @@ impl IdAnnotated<NodeId> for Expr {
         match self {
             Expr::Lit(l) => l.id(),
             Expr::VarRef(v) => v.id(),
+            Expr::Parameter(p) => p.id(),
             Expr::BinOp(b) => b.id(),
             Expr::UniOp(u) => u.id(),
             Expr::Like(l) => l.id(),
@@ -444,7 +492,9 @@ impl IdAnnotated<NodeId> for Expr {
             Expr::Path(p) => p.id(),
             Expr::Call(c) => c.id(),
             Expr::CallAgg(c) => c.id(),
+            Expr::Window(w) => w.id(),
             Expr::GraphMatch(g) => g.id(),
+            Expr::Type(t) => t.id(),
             Expr::Query(q) => q.id(),
             Expr::Error => unreachable!(),
         }
@@ -539,6 +589,19 @@ pub struct VarRef {
     pub qualifier: ScopeQualifier,
 }
 
+/// A query parameter, the value of which is bound when the query is executed.
+#[derive(Visit, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[visit(skip_recurse)]
+pub enum Parameter {
+    /// A positional parameter, i.e. `?`, numbered from 1 in order of appearance in the query.
+    #[visit(skip)]
+    Positional(usize),
+    /// A named parameter, e.g. `:name`
+    #[visit(skip)]
+    Named(SymbolPrimitive),
+}
+
 /// Indicates scope search order when resolving variables.
 /// Has no effect except within `FROM` sources.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -726,6 +789,54 @@ pub struct CallArgNamedType {
     pub ty: Type,
 }
 
+/// <func>(<args>) OVER (<window_spec>)
+#[derive(Visit, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Window {
+    #[visit(skip)]
+    pub func_name: SymbolPrimitive,
+    pub args: Vec<AstNode<CallArg>>,
+    pub spec: AstNode<WindowSpec>,
+}
+
+/// [PARTITION BY <expr>...] [ORDER BY <sort_spec>...] [<window_frame>]
+#[derive(Visit, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowSpec {
+    pub partition_by: Vec<Box<Expr>>,
+    pub order_by: Vec<AstNode<SortSpec>>,
+    #[visit(skip)]
+    pub frame: Option<WindowFrame>,
+}
+
+/// ROWS | RANGE BETWEEN <start> AND <end>
+///
+/// A frame with only a start bound (e.g., `ROWS UNBOUNDED PRECEDING`) ends at the `CURRENT ROW`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
 #[derive(Visit, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Path {
@@ -776,7 +887,7 @@ pub enum ExcludePathStep {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Let {
     /// A list of LET bindings
-    pub let_bindings: Vec<LetBinding>,
+    pub let_bindings: Vec<AstNode<LetBinding>>,
 }
 
 #[derive(Visit, Clone, Debug, PartialEq)]
@@ -826,6 +937,9 @@ pub struct FromLet {
     pub at_alias: Option<SymbolPrimitive>,
     #[visit(skip)]
     pub by_alias: Option<SymbolPrimitive>,
+    /// The column aliases of the `AS` alias, e.g. `(x, y)` in `FROM t AS v (x, y)`
+    #[visit(skip)]
+    pub columns: Option<Vec<SymbolPrimitive>>,
 }
 
 /// Indicates the type of `FromLet`, see the following for more details:
@@ -863,7 +977,8 @@ pub enum JoinKind {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum JoinSpec {
     On(Box<Expr>),
-    Using(Vec<Path>),
+    #[visit(skip)]
+    Using(Vec<SymbolPrimitive>),
     #[visit(skip)]
     Natural,
 }
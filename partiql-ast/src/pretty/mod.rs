@@ -29,10 +29,13 @@ impl PrettyDoc for TopLevelQuery {
         D::Doc: Clone,
         A: Clone,
     {
-        if self.with.is_some() {
-            todo!("WITH Clause")
+        let query = self.query.pretty_doc(arena);
+        match &self.with {
+            None => query,
+            Some(with) => arena
+                .intersperse([with.pretty_doc(arena), query], arena.hardline())
+                .group(),
         }
-        self.query.pretty_doc(arena)
     }
 }
 
@@ -44,12 +47,13 @@ impl PrettyDoc for Query {
         A: Clone,
     {
         let Query {
+            with,
             set,
             order_by,
             limit_offset,
         } = self;
-
         let clauses = [
+            with.as_ref().map(|inner| inner.pretty_doc(arena)),
             Some(set.pretty_doc(arena)),
             order_by.as_ref().map(|inner| inner.pretty_doc(arena)),
             limit_offset.as_ref().map(|inner| inner.pretty_doc(arena)),
@@ -61,6 +65,118 @@ impl PrettyDoc for Query {
     }
 }
 
+impl PrettyDoc for WithClause {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let annot = if self.recursive {
+            "WITH RECURSIVE"
+        } else {
+            "WITH"
+        };
+        let withs = pretty_list(&self.withs, PRETTY_INDENT_MINOR_NEST, arena);
+        pretty_prefixed_doc(annot, withs, arena)
+    }
+}
+
+impl PrettyDoc for WithElement {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let WithElement {
+            query_name,
+            columns,
+            subquery,
+            search,
+            cycle,
+        } = self;
+
+        let name = match columns {
+            None => query_name.pretty_doc(arena),
+            Some(cols) => query_name
+                .pretty_doc(arena)
+                .append(arena.space())
+                .append(pretty_parenthesized_doc(pretty_list(cols, 0, arena), arena)),
+        };
+        let subquery = match &subquery.node {
+            Expr::Query(_) => subquery.pretty_doc(arena),
+            _ => pretty_parenthesized_expr(subquery, PRETTY_INDENT_MINOR_NEST, arena),
+        };
+        let head = name
+            .append(arena.space())
+            .append(pretty_prefixed_doc("AS", subquery, arena));
+
+        let clauses = [
+            Some(head),
+            search.as_ref().map(|inner| inner.pretty_doc(arena)),
+            cycle.as_ref().map(|inner| inner.pretty_doc(arena)),
+        ]
+        .into_iter()
+        .flatten();
+
+        arena.intersperse(clauses, arena.softline()).group()
+    }
+}
+
+impl PrettyDoc for WithSearch {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let annot = match self.order {
+            SearchOrder::DepthFirst => "SEARCH DEPTH FIRST BY",
+            SearchOrder::BreadthFirst => "SEARCH BREADTH FIRST BY",
+        };
+        let by = pretty_list(&self.by, PRETTY_INDENT_MINOR_NEST, arena);
+        let set = pretty_prefixed_doc("SET", self.set.pretty_doc(arena), arena);
+        pretty_prefixed_doc(annot, arena.intersperse([by, set], arena.softline()), arena)
+    }
+}
+
+impl PrettyDoc for WithCycle {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let WithCycle {
+            columns,
+            mark,
+            mark_values,
+            path,
+        } = self;
+
+        let set = pretty_prefixed_doc("SET", mark.pretty_doc(arena), arena);
+        let clauses = [
+            Some(pretty_list(columns, PRETTY_INDENT_MINOR_NEST, arena)),
+            Some(set),
+            mark_values.as_ref().map(|(to, default)| {
+                arena.intersperse(
+                    [
+                        pretty_prefixed_doc("TO", to.pretty_doc(arena), arena),
+                        pretty_prefixed_doc("DEFAULT", default.pretty_doc(arena), arena),
+                    ],
+                    arena.softline(),
+                )
+            }),
+            Some(pretty_prefixed_doc("USING", path.pretty_doc(arena), arena)),
+        ]
+        .into_iter()
+        .flatten();
+
+        pretty_prefixed_doc("CYCLE", arena.intersperse(clauses, arena.softline()), arena)
+    }
+}
+
 impl PrettyDoc for QuerySet {
     fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
     where
@@ -74,6 +190,7 @@ impl PrettyDoc for QuerySet {
             QuerySet::Expr(e) => e.pretty_doc(arena),
             QuerySet::Values(v) => pretty_prefixed_doc("VALUES", pretty_list(v, 0, arena), arena),
             QuerySet::Table(t) => pretty_prefixed_expr("TABLE", t, 0, arena),
+            QuerySet::Dml(dml) => dml.pretty_doc(arena),
         }
     }
 }
@@ -118,6 +235,174 @@ impl PrettyDoc for QueryTable {
     }
 }
 
+impl PrettyDoc for Dml {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let Dml {
+            ops,
+            from_clause,
+            where_clause,
+            returning,
+        } = self;
+
+        let target = from_clause.as_ref().map(|from| {
+            let kw = match ops.first() {
+                Some(DmlOp::Delete(_)) => "DELETE FROM",
+                _ => "UPDATE",
+            };
+            pretty_prefixed_expr(kw, &from.source, PRETTY_INDENT_MINOR_NEST, arena)
+        });
+        let ops = ops
+            .iter()
+            .filter(|op| !matches!(op, DmlOp::Delete(_)))
+            .map(|op| op.pretty_doc(arena).group());
+        let where_clause = where_clause
+            .as_ref()
+            .map(|expr| pretty_prefixed_expr("WHERE", expr, PRETTY_INDENT_MINOR_NEST, arena));
+        let returning = returning.as_ref().map(|ret| ret.pretty_doc(arena));
+
+        let clauses = target
+            .into_iter()
+            .chain(ops)
+            .chain(where_clause)
+            .chain(returning);
+        arena.intersperse(clauses, arena.line()).group()
+    }
+}
+
+impl PrettyDoc for DmlOp {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        match self {
+            DmlOp::Insert(Insert {
+                target,
+                values,
+                on_conflict,
+            }) => {
+                let kw = pretty_prefixed_expr("INSERT INTO", target, 0, arena);
+                let values = arena
+                    .intersperse([kw, values.pretty_doc(arena)], arena.line())
+                    .nest(PRETTY_INDENT_MINOR_NEST);
+                let on_conflict = on_conflict.as_ref().map(|oc| oc.pretty_doc(arena));
+                let clauses = [Some(values), on_conflict].into_iter().flatten();
+                arena.intersperse(clauses, arena.line())
+            }
+            DmlOp::InsertValue(InsertValue {
+                target,
+                value,
+                index,
+                on_conflict,
+            }) => {
+                let kw = pretty_prefixed_expr("INSERT INTO", target, 0, arena);
+                let value = pretty_prefixed_expr("VALUE", value, PRETTY_INDENT_MINOR_NEST, arena);
+                let index = index
+                    .as_ref()
+                    .map(|idx| pretty_prefixed_expr("AT", idx, PRETTY_INDENT_MINOR_NEST, arena));
+                let on_conflict = on_conflict.as_ref().map(|oc| oc.pretty_doc(arena));
+                let clauses = [Some(kw), Some(value), index, on_conflict]
+                    .into_iter()
+                    .flatten();
+                arena.intersperse(clauses, arena.line())
+            }
+            DmlOp::Set(Set { assignment }) => {
+                pretty_prefixed_expr("SET", assignment, PRETTY_INDENT_MINOR_NEST, arena)
+            }
+            DmlOp::Remove(Remove { target }) => {
+                pretty_prefixed_expr("REMOVE", target, PRETTY_INDENT_MINOR_NEST, arena)
+            }
+            // `DELETE` is printed along with the statement's target
+            DmlOp::Delete(_) => arena.nil(),
+        }
+    }
+}
+
+impl PrettyDoc for Assignment {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let Assignment { target, value } = self;
+        arena
+            .intersperse(
+                [
+                    target.pretty_doc(arena),
+                    arena.text("="),
+                    value.pretty_doc(arena),
+                ],
+                arena.space(),
+            )
+            .group()
+    }
+}
+
+impl PrettyDoc for OnConflict {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let OnConflict {
+            expr,
+            conflict_action,
+        } = self;
+        let action = match conflict_action {
+            ConflictAction::DoNothing => "DO NOTHING",
+        };
+        let condition =
+            pretty_prefixed_expr("ON CONFLICT WHERE", expr, PRETTY_INDENT_MINOR_NEST, arena);
+        arena
+            .intersperse([condition, arena.text(action)], arena.line())
+            .group()
+    }
+}
+
+impl PrettyDoc for ReturningExpr {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        pretty_prefixed_doc(
+            "RETURNING",
+            pretty_list(&self.elems, PRETTY_INDENT_MINOR_NEST, arena),
+            arena,
+        )
+    }
+}
+
+impl PrettyDoc for ReturningElem {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let mapping = match self.mapping {
+            ReturningMapping::ModifiedNew => "MODIFIED NEW",
+            ReturningMapping::ModifiedOld => "MODIFIED OLD",
+            ReturningMapping::AllNew => "ALL NEW",
+            ReturningMapping::AllOld => "ALL OLD",
+        };
+        let column = match &self.column {
+            ColumnComponent::ReturningWildcard => arena.text("*"),
+            ColumnComponent::ReturningColumn(ReturningColumn { expr }) => expr.pretty_doc(arena),
+        };
+        pretty_prefixed_doc(mapping, column, arena)
+    }
+}
+
 impl PrettyDoc for Select {
     fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
     where
@@ -306,6 +591,7 @@ impl PrettyDoc for Expr {
         match self {
             Expr::Lit(inner) => inner.pretty_doc(arena),
             Expr::VarRef(inner) => inner.pretty_doc(arena),
+            Expr::Parameter(inner) => inner.pretty_doc(arena),
             Expr::BinOp(inner) => inner.pretty_doc(arena),
             Expr::UniOp(inner) => inner.pretty_doc(arena),
             Expr::Like(inner) => inner.pretty_doc(arena),
@@ -318,7 +604,9 @@ impl PrettyDoc for Expr {
             Expr::Path(inner) => inner.pretty_doc(arena),
             Expr::Call(inner) => inner.pretty_doc(arena),
             Expr::CallAgg(inner) => inner.pretty_doc(arena),
+            Expr::Window(inner) => inner.pretty_doc(arena),
             Expr::GraphMatch(inner) => inner.pretty_doc(arena),
+            Expr::Type(inner) => inner.pretty_doc(arena),
             Expr::Query(inner) => {
                 let inner = inner.pretty_doc(arena).group();
                 arena
@@ -359,6 +647,20 @@ impl PrettyDoc for Path {
     }
 }
 
+impl PrettyDoc for Parameter {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        match self {
+            Parameter::Positional(_) => arena.text("?"),
+            Parameter::Named(name) => arena.text(":").append(name.pretty_doc(arena)),
+        }
+    }
+}
+
 impl PrettyDoc for VarRef {
     fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
     where
@@ -813,6 +1115,101 @@ impl PrettyDoc for CallAgg {
     }
 }
 
+impl PrettyDoc for Window {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let name = self.func_name.pretty_doc(arena);
+        let list = pretty_list(&self.args, 0, arena);
+        name.append(arena.text("("))
+            .append(list.nest(PRETTY_INDENT_MINOR_NEST))
+            .append(arena.text(")"))
+            .append(arena.space())
+            .append(arena.text("OVER"))
+            .append(arena.space())
+            .append(pretty_parenthesized_doc(self.spec.pretty_doc(arena), arena))
+            .group()
+    }
+}
+
+impl PrettyDoc for WindowSpec {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let WindowSpec {
+            partition_by,
+            order_by,
+            frame,
+        } = self;
+        let mut clauses = vec![];
+        if !partition_by.is_empty() {
+            clauses.push(pretty_prefixed_doc(
+                "PARTITION BY",
+                pretty_list(partition_by, PRETTY_INDENT_MINOR_NEST, arena),
+                arena,
+            ));
+        }
+        if !order_by.is_empty() {
+            clauses.push(pretty_prefixed_doc(
+                "ORDER BY",
+                pretty_list(order_by, PRETTY_INDENT_MINOR_NEST, arena),
+                arena,
+            ));
+        }
+        if let Some(frame) = frame {
+            clauses.push(frame.pretty_doc(arena));
+        }
+        arena.intersperse(clauses, arena.softline()).group()
+    }
+}
+
+impl PrettyDoc for WindowFrame {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        let units = match self.units {
+            WindowFrameUnits::Rows => "ROWS",
+            WindowFrameUnits::Range => "RANGE",
+        };
+        arena.intersperse(
+            [
+                arena.text(units),
+                arena.text("BETWEEN"),
+                self.start.pretty_doc(arena),
+                arena.text("AND"),
+                self.end.pretty_doc(arena),
+            ],
+            arena.space(),
+        )
+    }
+}
+
+impl PrettyDoc for WindowFrameBound {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        match self {
+            WindowFrameBound::UnboundedPreceding => arena.text("UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(n) => arena.text(format!("{n} PRECEDING")),
+            WindowFrameBound::CurrentRow => arena.text("CURRENT ROW"),
+            WindowFrameBound::Following(n) => arena.text(format!("{n} FOLLOWING")),
+            WindowFrameBound::UnboundedFollowing => arena.text("UNBOUNDED FOLLOWING"),
+        }
+    }
+}
+
 impl PrettyDoc for CallArg {
     fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
     where
@@ -901,10 +1298,19 @@ impl PrettyDoc for FromLet {
             as_alias,
             at_alias,
             by_alias,
+            columns,
         } = self;
 
         let expr = expr.pretty_doc(arena);
-        let as_alias = pretty_as_alias(as_alias.as_ref(), arena);
+        let as_alias = pretty_as_alias(as_alias.as_ref(), arena).map(|as_alias| match columns {
+            Some(columns) => as_alias
+                .append(arena.space())
+                .append(pretty_parenthesized_doc(
+                    pretty_list(columns, 0, arena),
+                    arena,
+                )),
+            None => as_alias,
+        });
         let at_alias = pretty_at_alias(at_alias.as_ref(), arena);
         let by_alias = pretty_by_alias(by_alias.as_ref(), arena);
         let aliases: Vec<_> = [as_alias, at_alias, by_alias]
@@ -972,7 +1378,7 @@ impl PrettyDoc for Join {
                 }
                 JoinSpec::Using(using) => {
                     let join = pretty_seperated(kw_join, arms, 0, arena);
-                    let using = pretty_list(using, PRETTY_INDENT_MINOR_NEST, arena);
+                    let using = pretty_parenthesized_doc(pretty_list(using, 0, arena), arena);
                     let pred = arena
                         .softline()
                         .append(arena.text("USING"))
@@ -987,13 +1393,29 @@ impl PrettyDoc for Join {
 }
 
 impl PrettyDoc for Let {
-    fn pretty_doc<'b, D, A>(&'b self, _arena: &'b D) -> DocBuilder<'b, D, A>
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
+    where
+        D: DocAllocator<'b, A>,
+        D::Doc: Clone,
+        A: Clone,
+    {
+        pretty_prefixed_doc(
+            "LET",
+            pretty_list(&self.let_bindings, PRETTY_INDENT_MINOR_NEST, arena),
+            arena,
+        )
+    }
+}
+
+impl PrettyDoc for LetBinding {
+    fn pretty_doc<'b, D, A>(&'b self, arena: &'b D) -> DocBuilder<'b, D, A>
     where
         D: DocAllocator<'b, A>,
         D::Doc: Clone,
         A: Clone,
     {
-        todo!("LET")
+        pretty_source_as_alias(&self.expr, Some(&self.as_alias), arena)
+            .unwrap_or_else(|| self.expr.pretty_doc(arena))
     }
 }
 
@@ -165,6 +165,18 @@ pub trait Visitor<'ast> {
     fn exit_returning_elem(&mut self, _returning_elem: &'ast ast::ReturningElem) -> Traverse {
         Traverse::Continue
     }
+    fn enter_column_component(&mut self, _column: &'ast ast::ColumnComponent) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_column_component(&mut self, _column: &'ast ast::ColumnComponent) -> Traverse {
+        Traverse::Continue
+    }
+    fn enter_returning_column(&mut self, _column: &'ast ast::ReturningColumn) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_returning_column(&mut self, _column: &'ast ast::ReturningColumn) -> Traverse {
+        Traverse::Continue
+    }
     fn enter_insert(&mut self, _insert: &'ast ast::Insert) -> Traverse {
         Traverse::Continue
     }
@@ -327,6 +339,12 @@ pub trait Visitor<'ast> {
     fn exit_var_ref(&mut self, _var_ref: &'ast ast::VarRef) -> Traverse {
         Traverse::Continue
     }
+    fn enter_parameter(&mut self, _parameter: &'ast ast::Parameter) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_parameter(&mut self, _parameter: &'ast ast::Parameter) -> Traverse {
+        Traverse::Continue
+    }
     fn enter_bin_op(&mut self, _bin_op: &'ast ast::BinOp) -> Traverse {
         Traverse::Continue
     }
@@ -435,6 +453,18 @@ pub trait Visitor<'ast> {
     fn exit_call_agg(&mut self, _call_agg: &'ast ast::CallAgg) -> Traverse {
         Traverse::Continue
     }
+    fn enter_window(&mut self, _window: &'ast ast::Window) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_window(&mut self, _window: &'ast ast::Window) -> Traverse {
+        Traverse::Continue
+    }
+    fn enter_window_spec(&mut self, _window_spec: &'ast ast::WindowSpec) -> Traverse {
+        Traverse::Continue
+    }
+    fn exit_window_spec(&mut self, _window_spec: &'ast ast::WindowSpec) -> Traverse {
+        Traverse::Continue
+    }
     fn enter_path(&mut self, _path: &'ast ast::Path) -> Traverse {
         Traverse::Continue
     }
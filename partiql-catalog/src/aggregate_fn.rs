@@ -0,0 +1,114 @@
+use crate::extension::ExtensionResultError;
+use dyn_clone::DynClone;
+use partiql_types::PartiqlShape;
+use partiql_value::Value;
+use std::fmt::{Debug, Formatter};
+use std::ops::RangeInclusive;
+
+pub type AggregateFnExprResult = Result<Value, ExtensionResultError>;
+
+/// An aggregation function (e.g., `median`, `string_agg`), computed over the values of a group.
+///
+/// The state of each group is kept as a [`Value`]; it starts out as [`AggregateFnExpr::init`],
+/// is fed each of the group's values by [`AggregateFnExpr::accumulate`] and is turned into the
+/// function's result by [`AggregateFnExpr::finalize`]. A group whose values are aggregated in
+/// parts (e.g., across threads) has the states of its parts combined, in the order of the parts'
+/// values, by [`AggregateFnExpr::merge`]. As for the built-in aggregation functions,
+/// `NULL` and `MISSING` values are not accumulated and, for a `DISTINCT` call, each value is
+/// accumulated only once per group.
+///
+/// The aggregated value is the first argument of a call; the values of any further arguments
+/// (e.g., the delimiter of `string_agg(x, ',')`) are evaluated with the same row as the value and
+/// passed along with it.
+pub trait AggregateFnExpr: DynClone + Debug + Send + Sync {
+    /// The state of a group to which no value has yet been accumulated.
+    fn init(&self) -> Value;
+
+    /// Adds `value`, along with the values `args` of the call's further arguments, to the group
+    /// `state`.
+    fn accumulate(
+        &self,
+        state: &mut Value,
+        value: &Value,
+        args: &[Value],
+    ) -> Result<(), ExtensionResultError>;
+
+    /// Combines `other`, the state of a part of a group whose values follow those of `state`,
+    /// into `state`.
+    fn merge(&self, state: &mut Value, other: Value) -> Result<(), ExtensionResultError>;
+
+    /// The result of the aggregation function for the group `state`.
+    fn finalize(&self, state: Value) -> AggregateFnExprResult;
+
+    /// The type of the function's result for values of type `arg`, used for static typing; by
+    /// default, the result may be of any type.
+    fn result_type(&self, _arg: &PartiqlShape) -> PartiqlShape {
+        PartiqlShape::Dynamic
+    }
+
+    /// The numbers of arguments, including the aggregated value, a call of the function may have;
+    /// by default, only the aggregated value.
+    fn arity(&self) -> RangeInclusive<usize> {
+        1..=1
+    }
+}
+
+dyn_clone::clone_trait_object!(AggregateFnExpr);
+
+#[derive(Clone)]
+pub struct AggregateFnCallDef {
+    pub names: Vec<&'static str>,
+    pub expr: Box<dyn AggregateFnExpr>,
+}
+
+impl Debug for AggregateFnCallDef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AggregateFnCallDef {:?}", &self.names)
+    }
+}
+
+pub trait AggregateFunctionInfo: Debug {
+    fn call_def(&self) -> &AggregateFnCallDef;
+
+    fn into_call_def(self: Box<Self>) -> AggregateFnCallDef;
+}
+
+#[derive(Debug)]
+pub struct SimpleAggregateFunctionInfo {
+    call_def: AggregateFnCallDef,
+}
+
+impl SimpleAggregateFunctionInfo {
+    pub fn new(call_def: AggregateFnCallDef) -> Self {
+        Self { call_def }
+    }
+}
+
+impl AggregateFunctionInfo for SimpleAggregateFunctionInfo {
+    fn call_def(&self) -> &AggregateFnCallDef {
+        &self.call_def
+    }
+
+    fn into_call_def(self: Box<Self>) -> AggregateFnCallDef {
+        self.call_def
+    }
+}
+
+#[derive(Debug)]
+pub struct AggregateFunction {
+    info: Box<dyn AggregateFunctionInfo>,
+}
+
+impl AggregateFunction {
+    pub fn new(info: Box<dyn AggregateFunctionInfo>) -> Self {
+        AggregateFunction { info }
+    }
+
+    pub fn call_def(&self) -> &AggregateFnCallDef {
+        self.info.call_def()
+    }
+
+    pub fn into_call_def(self) -> AggregateFnCallDef {
+        self.info.into_call_def()
+    }
+}
@@ -14,6 +14,9 @@ pub enum CallLookupError {
     /// Invalid number of arguments to the function call.
     #[error("Invalid number of arguments: {0}")]
     InvalidNumberOfArguments(String),
+    /// An aggregation function called other than as an aggregation.
+    #[error("Aggregation function called as a scalar function: {0}")]
+    AggregateAsScalar(String),
 }
 
 #[derive(Debug)]
@@ -27,13 +30,19 @@ impl CallDef {
     pub fn lookup(&self, args: &[CallArgument], name: &str) -> Result<ValueExpr, CallLookupError> {
         'overload: for overload in &self.overloads {
             let formals = &overload.input;
-            if formals.len() != args.len() {
+            let (required, optional) = formals.split_at(
+                formals
+                    .iter()
+                    .position(|formal| matches!(formal, CallSpecArg::OptionalNamed(_)))
+                    .unwrap_or(formals.len()),
+            );
+            if args.len() < required.len() || args.len() > formals.len() {
                 continue 'overload;
             }
 
             let mut actuals = vec![];
-            for i in 0..formals.len() {
-                let formal = &formals[i];
+            for i in 0..required.len() {
+                let formal = &required[i];
                 let actual = &args[i];
                 if let Some(vexpr) = formal.transform(actual) {
                     actuals.push(vexpr);
@@ -42,6 +51,24 @@ impl CallDef {
                 }
             }
 
+            // optional named arguments may be given in any order; omitted ones are `MISSING`
+            let mut optional_actuals = vec![None; optional.len()];
+            for actual in &args[required.len()..] {
+                let matched = optional
+                    .iter()
+                    .enumerate()
+                    .find_map(|(idx, formal)| formal.transform(actual).map(|vexpr| (idx, vexpr)));
+                match matched {
+                    Some((idx, vexpr)) if optional_actuals[idx].is_none() => {
+                        optional_actuals[idx] = Some(vexpr);
+                    }
+                    _ => continue 'overload,
+                }
+            }
+            actuals.extend(optional_actuals.into_iter().map(|actual| {
+                actual.unwrap_or_else(|| ValueExpr::Lit(Box::new(logical::Lit::Missing)))
+            }));
+
             return Ok((overload.output)(actuals));
         }
         Err(CallLookupError::InvalidNumberOfArguments(name.into()))
@@ -51,7 +78,11 @@ impl CallDef {
 #[derive(Debug, Eq, PartialEq)]
 pub enum CallArgument {
     Positional(ValueExpr),
+    /// A type given as an argument, e.g. `INT` in `foo(INT)`
+    PositionalType(logical::Type),
     Named(String, ValueExpr),
+    /// A type given as a named argument, e.g. `"as": INT` in `cast(x, "as": INT)`
+    NamedType(String, logical::Type),
     Star,
 }
 
@@ -59,13 +90,21 @@ pub enum CallArgument {
 pub enum CallSpecArg {
     Positional,
     Named(UniCase<&'static str>),
+    /// A named argument which may be omitted, in which case it is `MISSING`.
+    ///
+    /// Optional arguments must follow all other arguments of a [`CallSpec`]; they may be given in
+    /// any order.
+    OptionalNamed(UniCase<&'static str>),
 }
 
 impl CallSpecArg {
     pub(crate) fn transform(&self, arg: &CallArgument) -> Option<ValueExpr> {
         match (self, arg) {
             (CallSpecArg::Positional, CallArgument::Positional(ve)) => Some(ve.clone()),
-            (CallSpecArg::Named(formal_name), CallArgument::Named(arg_name, ve)) => {
+            (
+                CallSpecArg::Named(formal_name) | CallSpecArg::OptionalNamed(formal_name),
+                CallArgument::Named(arg_name, ve),
+            ) => {
                 if formal_name == &UniCase::new(arg_name.as_str()) {
                     Some(ve.clone())
                 } else {
@@ -1,3 +1,4 @@
+use crate::aggregate_fn::{AggregateFnExpr, AggregateFunction};
 use crate::call_defs::ScalarFnCallSpecs;
 use crate::scalar_fn::ScalarFunction;
 use crate::table_fn::TableFunction;
@@ -46,6 +47,8 @@ pub enum CatalogErrorKind {
 pub trait MutableCatalog: Debug {
     fn add_table_function(&mut self, info: TableFunction) -> Result<ObjectId, CatalogError>;
     fn add_scalar_function(&mut self, info: ScalarFunction) -> Result<ObjectId, CatalogError>;
+    fn add_aggregate_function(&mut self, info: AggregateFunction)
+        -> Result<ObjectId, CatalogError>;
     fn add_type_entry(&mut self, entry: TypeEnvEntry<'_>) -> Result<ObjectId, CatalogError>;
 }
 
@@ -106,7 +109,7 @@ pub struct FunctionEntry<'a> {
 pub enum FunctionEntryFunction {
     Table(TableFunction),
     Scalar(ScalarFnCallSpecs),
-    Aggregate(),
+    Aggregate(Box<dyn AggregateFnExpr>),
 }
 
 impl<'a> FunctionEntry<'a> {
@@ -183,6 +186,28 @@ impl MutableCatalog for PartiqlCatalog {
         }
     }
 
+    fn add_aggregate_function(
+        &mut self,
+        info: AggregateFunction,
+    ) -> Result<ObjectId, CatalogError> {
+        let id = self.id;
+        let call_def = info.into_call_def();
+        let names = call_def.names;
+        if let Some((name, aliases)) = names.split_first() {
+            self.functions
+                .add(
+                    name,
+                    aliases,
+                    FunctionEntryFunction::Aggregate(call_def.expr),
+                )
+                .map(|eid| ObjectId::new(id, eid))
+        } else {
+            Err(CatalogError::new(vec![CatalogErrorKind::EntryError(
+                "Function definition has no name".into(),
+            )]))
+        }
+    }
+
     fn add_type_entry(&mut self, entry: TypeEnvEntry<'_>) -> Result<ObjectId, CatalogError> {
         let eid = self
             .types
@@ -315,6 +340,76 @@ impl<T> CatalogEntrySet<T> {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::aggregate_fn::{
+        AggregateFnCallDef, AggregateFnExprResult, SimpleAggregateFunctionInfo,
+    };
+    use crate::extension::ExtensionResultError;
+    use partiql_value::Value;
+
+    #[derive(Debug, Clone)]
+    struct Product;
+
+    impl AggregateFnExpr for Product {
+        fn init(&self) -> Value {
+            Value::from(1)
+        }
+
+        fn accumulate(
+            &self,
+            state: &mut Value,
+            value: &Value,
+            _args: &[Value],
+        ) -> Result<(), ExtensionResultError> {
+            *state = &*state * value;
+            Ok(())
+        }
+
+        fn merge(&self, state: &mut Value, other: Value) -> Result<(), ExtensionResultError> {
+            *state = &*state * &other;
+            Ok(())
+        }
+
+        fn finalize(&self, state: Value) -> AggregateFnExprResult {
+            Ok(state)
+        }
+    }
+
+    fn product() -> AggregateFunction {
+        AggregateFunction::new(Box::new(SimpleAggregateFunctionInfo::new(
+            AggregateFnCallDef {
+                names: vec!["product", "prod"],
+                expr: Box::new(Product),
+            },
+        )))
+    }
+
     #[test]
-    fn todo() {}
+    fn add_aggregate_function() {
+        let mut catalog = PartiqlCatalog::default();
+        let id = catalog.add_aggregate_function(product()).expect("add");
+
+        let entry = catalog.get_function("PROD").expect("by alias");
+        assert_eq!(entry.id(), &id);
+        let FunctionEntryFunction::Aggregate(expr) = entry.entry() else {
+            panic!("expected an aggregate function");
+        };
+
+        let mut state = expr.init();
+        for v in [2, 3, 4] {
+            expr.accumulate(&mut state, &Value::from(v), &[])
+                .expect("accumulate");
+        }
+        assert_eq!(expr.finalize(state).expect("finalize"), Value::from(24));
+
+        assert!(catalog.get_function_by_id(id).is_some());
+        let err = catalog.add_aggregate_function(product());
+        assert_eq!(
+            err,
+            Err(CatalogError::new(vec![
+                CatalogErrorKind::EntryExists("prod".to_string()),
+                CatalogErrorKind::EntryExists("product".to_string()),
+            ]))
+        );
+    }
 }
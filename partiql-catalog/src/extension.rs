@@ -9,7 +9,7 @@ pub trait Extension: Debug {
     fn load(&self, catalog: &mut dyn Catalog) -> Result<(), ExtensionResultError>;
 }
 
-pub type ExtensionError = Box<dyn Error>;
+pub type ExtensionError = Box<dyn Error + Send + Sync>;
 
 /// Errors in extension.
 ///
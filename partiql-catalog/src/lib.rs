@@ -5,7 +5,9 @@ pub mod call_defs;
 
 pub mod context;
 
+pub mod aggregate_fn;
 pub mod catalog;
 pub mod extension;
+pub mod mutation;
 pub mod scalar_fn;
 pub mod table_fn;
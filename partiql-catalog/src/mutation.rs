@@ -0,0 +1,223 @@
+use partiql_value::{Bag, BindingsName, List, Value};
+use std::cell::RefCell;
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// Identifies a row of a mutable collection, as assigned by the [`MutationSink`] which listed it.
+pub type RowId = usize;
+
+/// A change to a mutable collection, produced by the evaluation of a DML statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mutation {
+    /// Adds the value to the collection.
+    Insert(Value),
+    /// Replaces the row with the value.
+    Replace(RowId, Value),
+    /// Removes the row from the collection.
+    Delete(RowId),
+}
+
+/// Errors in applying the changes of a DML statement.
+///
+/// ### Notes
+/// This is marked `#[non_exhaustive]`, to reserve the right to add more variants in the future.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MutationError {
+    /// The target of the statement is not known to the sink.
+    #[error("Unknown mutation target: `{0}`")]
+    UnknownTarget(String),
+    /// The target of the statement is not a collection.
+    #[error("Mutation target `{0}` is not a collection")]
+    NotACollection(String),
+    /// A mutation refers to a row which the target does not contain.
+    #[error("Mutation target `{0}` has no row `{1}`")]
+    UnknownRow(String, RowId),
+    /// The sink failed to apply the changes.
+    #[error("Mutation failed: {0}")]
+    Failed(String),
+}
+
+/// The extension point through which DML statements (i.e., `INSERT`, `UPDATE` and `DELETE`) read
+/// and modify the collections they target.
+///
+/// Evaluation of a statement lists the rows of its target, computes all of the statement's
+/// changes and then hands them to [`MutationSink::apply`] at once, so a statement never observes
+/// its own changes.
+pub trait MutationSink: Debug {
+    /// Lists the rows of the collection `target`, each paired with the [`RowId`] by which a
+    /// [`Mutation`] refers to it.
+    fn rows(&self, target: &BindingsName<'_>) -> Result<Vec<(RowId, Value)>, MutationError>;
+
+    /// Applies `mutations` to the collection `target`; the [`RowId`]s of the mutations are those
+    /// of the rows most recently listed by [`MutationSink::rows`].
+    fn apply(
+        &self,
+        target: &BindingsName<'_>,
+        mutations: Vec<Mutation>,
+    ) -> Result<(), MutationError>;
+}
+
+/// A [`MutationSink`] over named, in-memory `Value::Bag` (or `Value::List`) collections.
+///
+/// Rows are identified by their position in their collection; the changes of a statement are
+/// applied all-or-nothing, with inserted values appended to the collection.
+#[derive(Debug, Default)]
+pub struct InMemoryMutationSink {
+    collections: RefCell<Vec<(String, Value)>>,
+}
+
+impl InMemoryMutationSink {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds the collection `value` to `name`, replacing any collection previously bound to it.
+    pub fn insert(&mut self, name: &str, value: Value) {
+        let collections = self.collections.get_mut();
+        match collections.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = value,
+            None => collections.push((name.to_string(), value)),
+        }
+    }
+
+    /// The current value of the collection bound to `name`, if any.
+    #[must_use]
+    pub fn get(&self, name: &BindingsName<'_>) -> Option<Value> {
+        let matcher = name.matcher();
+        self.collections
+            .borrow()
+            .iter()
+            .find(|(n, _)| matcher.matches(n))
+            .map(|(_, value)| value.clone())
+    }
+
+    /// Consumes the sink, returning its collections along with their names.
+    #[must_use]
+    pub fn into_collections(self) -> Vec<(String, Value)> {
+        self.collections.into_inner()
+    }
+}
+
+fn target_name(target: &BindingsName<'_>) -> String {
+    match target {
+        BindingsName::CaseSensitive(name) | BindingsName::CaseInsensitive(name) => name.to_string(),
+    }
+}
+
+impl MutationSink for InMemoryMutationSink {
+    fn rows(&self, target: &BindingsName<'_>) -> Result<Vec<(RowId, Value)>, MutationError> {
+        match self.get(target) {
+            Some(Value::Bag(bag)) => Ok(bag.to_vec().into_iter().enumerate().collect()),
+            Some(Value::List(list)) => Ok(list.to_vec().into_iter().enumerate().collect()),
+            Some(_) => Err(MutationError::NotACollection(target_name(target))),
+            None => Err(MutationError::UnknownTarget(target_name(target))),
+        }
+    }
+
+    fn apply(
+        &self,
+        target: &BindingsName<'_>,
+        mutations: Vec<Mutation>,
+    ) -> Result<(), MutationError> {
+        let mut collections = self.collections.borrow_mut();
+        let matcher = target.matcher();
+        let Some((_, collection)) = collections.iter_mut().find(|(n, _)| matcher.matches(n)) else {
+            return Err(MutationError::UnknownTarget(target_name(target)));
+        };
+
+        let (ordered, rows) = match collection {
+            Value::Bag(bag) => (false, bag.as_ref().clone().to_vec()),
+            Value::List(list) => (true, list.as_ref().clone().to_vec()),
+            _ => return Err(MutationError::NotACollection(target_name(target))),
+        };
+        let mut rows: Vec<_> = rows.into_iter().map(Some).collect();
+        let mut inserted = vec![];
+        for mutation in mutations {
+            let row = match mutation {
+                Mutation::Insert(value) => {
+                    inserted.push(value);
+                    continue;
+                }
+                Mutation::Replace(id, _) | Mutation::Delete(id) => id,
+            };
+            let Some(slot) = rows.get_mut(row).filter(|slot| slot.is_some()) else {
+                return Err(MutationError::UnknownRow(target_name(target), row));
+            };
+            *slot = match mutation {
+                Mutation::Replace(_, value) => Some(value),
+                _ => None,
+            };
+        }
+
+        let rows = rows.into_iter().flatten().chain(inserted);
+        *collection = if ordered {
+            Value::from(rows.collect::<List>())
+        } else {
+            Value::from(rows.collect::<Bag>())
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use partiql_value::{bag, list, tuple};
+    use std::borrow::Cow;
+
+    fn name(name: &'static str) -> BindingsName<'static> {
+        BindingsName::CaseInsensitive(Cow::Borrowed(name))
+    }
+
+    #[test]
+    fn apply_in_memory() {
+        let mut sink = InMemoryMutationSink::new();
+        sink.insert(
+            "t",
+            bag![tuple![("a", 1)], tuple![("a", 2)], tuple![("a", 3)]].into(),
+        );
+        sink.insert("l", list![1, 2].into());
+
+        let rows = sink.rows(&name("T")).expect("rows");
+        assert_eq!(rows.len(), 3);
+        sink.apply(
+            &name("t"),
+            vec![
+                Mutation::Delete(0),
+                Mutation::Replace(2, tuple![("a", 30)].into()),
+                Mutation::Insert(tuple![("a", 4)].into()),
+            ],
+        )
+        .expect("apply");
+        assert_eq!(
+            sink.get(&name("t")),
+            Some(bag![tuple![("a", 2)], tuple![("a", 30)], tuple![("a", 4)]].into())
+        );
+
+        sink.apply(&name("l"), vec![Mutation::Insert(3.into())])
+            .expect("apply");
+        assert_eq!(sink.get(&name("l")), Some(list![1, 2, 3].into()));
+    }
+
+    #[test]
+    fn apply_is_all_or_nothing() {
+        let mut sink = InMemoryMutationSink::new();
+        sink.insert("t", bag![1, 2].into());
+        sink.insert("scalar", 1.into());
+
+        let err = sink.apply(&name("t"), vec![Mutation::Delete(0), Mutation::Delete(0)]);
+        assert_eq!(err, Err(MutationError::UnknownRow("t".to_string(), 0)));
+        assert_eq!(sink.get(&name("t")), Some(bag![1, 2].into()));
+
+        assert_eq!(
+            sink.rows(&name("scalar")),
+            Err(MutationError::NotACollection("scalar".to_string()))
+        );
+        assert_eq!(
+            sink.rows(&name("missing")),
+            Err(MutationError::UnknownTarget("missing".to_string()))
+        );
+    }
+}
@@ -4,6 +4,7 @@ use crate::context::SessionContext;
 use crate::extension::ExtensionResultError;
 use dyn_clone::DynClone;
 use partiql_common::FN_VAR_ARG_MAX;
+use partiql_types::PartiqlShape;
 use partiql_value::Value;
 use std::borrow::Cow;
 use std::fmt::{Debug, Formatter};
@@ -17,6 +18,12 @@ pub trait ScalarFnExpr: DynClone + Debug + Send + Sync {
         args: &[Cow<'_, Value>],
         ctx: &'c dyn SessionContext,
     ) -> ScalarFnExprResult<'c>;
+
+    /// The type of the function's result for arguments of types `args`, used for static typing;
+    /// by default, the result may be of any type.
+    fn result_type(&self, _args: &[PartiqlShape]) -> PartiqlShape {
+        PartiqlShape::Dynamic
+    }
 }
 
 dyn_clone::clone_trait_object!(ScalarFnExpr);
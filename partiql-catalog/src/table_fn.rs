@@ -9,7 +9,7 @@ pub type BaseTableExprResultValueIter<'a> =
     Box<dyn 'a + Iterator<Item = Result<Value, ExtensionResultError>>>;
 pub type BaseTableExprResult<'a> = Result<BaseTableExprResultValueIter<'a>, ExtensionResultError>;
 
-pub trait BaseTableExpr: Debug {
+pub trait BaseTableExpr: Debug + Send + Sync {
     fn evaluate<'c>(
         &self,
         args: &[Cow<'_, Value>],
@@ -14,8 +14,7 @@ use partiql_eval::plan::EvaluationMode;
 use partiql_logical as logical;
 use partiql_logical::BindingsOp::{Project, ProjectAll};
 use partiql_logical::{
-    BinaryOp, BindingsOp, JoinKind, LogicalPlan, PathComponent, ProjectAllMode, ValueExpr,
-    VarRefType,
+    BinaryOp, BindingsOp, JoinKind, LogicalPlan, PathComponent, ValueExpr, VarRefType,
 };
 use partiql_value::{bag, list, tuple, BindingsName, DateTime, Value};
 pub(crate) static SHARED_CATALOG: Lazy<PartiqlSharedCatalog> = Lazy::new(init_shared_catalog);
@@ -86,6 +85,7 @@ fn scan(name: &str, as_key: &str) -> BindingsOp {
         ),
         as_key: as_key.to_string(),
         at_key: None,
+        columns: None,
     })
 }
 
@@ -127,6 +127,7 @@ fn logical_plan() -> LogicalPlan<BindingsOp> {
             Box::new(path_var("c", "id")),
             Box::new(path_var("o", "custId")),
         )),
+        using: vec![],
     }));
 
     let sink = lg.add_operator(BindingsOp::Sink);
@@ -181,8 +182,9 @@ fn eval_bench(c: &mut Criterion) {
             ),
             as_key: "x".to_string(),
             at_key: None,
+            columns: None,
         }));
-        let project_all = logical_plan.add_operator(ProjectAll(ProjectAllMode::Unwrap));
+        let project_all = logical_plan.add_operator(ProjectAll(Default::default()));
         let sink = logical_plan.add_operator(BindingsOp::Sink);
 
         logical_plan.add_flow(from, project_all);
@@ -2,6 +2,8 @@ use crate::eval::evaluable::Evaluable;
 use crate::eval::expr::EvalExpr;
 use crate::eval::EvalContext;
 use partiql_catalog::extension::ExtensionResultError;
+use partiql_catalog::mutation::MutationError;
+use partiql_common::syntax::location::{BytePosition, Location};
 use partiql_value::datum::RefTupleView;
 use partiql_value::Value;
 use std::borrow::Cow;
@@ -11,6 +13,9 @@ use thiserror::Error;
 #[derive(Debug)]
 pub struct PlanErr {
     pub errors: Vec<PlanningError>,
+    /// The span of the query text from which the operator was lowered at which the error of the
+    /// same index in `errors` occurred, if known
+    pub locations: Vec<Option<Location<BytePosition>>>,
 }
 
 /// An error that can happen during [`partiql_logical::LogicalPlan`] to [`eval::EvalPlan`] creation.
@@ -44,12 +49,50 @@ pub enum EvaluationError {
     /// Feature has not yet been implemented.
     #[error("Not yet implemented: {0}")]
     NotYetImplemented(String),
+    /// The values bound to the query's parameters do not match the parameters of the query.
+    #[error("Parameter binding error: {0}")]
+    ParameterBinding(String),
+    /// A data modification statement was evaluated without a [`partiql_catalog::mutation::MutationSink`].
+    #[error("No mutation sink is available to apply data modifications")]
+    NoMutationSink,
+    /// The target of a `SET` or `REMOVE` clause does not denote a modifiable part of a row.
+    #[error("Invalid assignment: {0}")]
+    InvalidAssignment(String),
+    /// A data modification statement made no changes to its target, as its evaluation failed.
+    #[error("Data modification of `{0}` was not applied")]
+    DmlNotApplied(String),
 
+    /// A value cannot be converted to the type of a `CAST`.
+    #[error("Invalid cast: {0}")]
+    InvalidCast(String),
+    /// The recursive part of a `WITH RECURSIVE` element did not reach a fixpoint.
+    #[error("Recursion limit: `{0}` did not reach a fixpoint within {1} iterations")]
+    RecursionLimit(String, usize),
+
+    /// Error in applying data modifications
+    #[error("Mutation Error: {0}")]
+    Mutation(#[from] MutationError),
     /// Error originating in an extension
     #[error("Extension Result Expression Error")]
     ExtensionResultError(#[from] ExtensionResultError),
 }
 
+impl EvaluationError {
+    /// Whether the error leaves evaluation without a result to return, such that it fails even in
+    /// [`EvaluationMode::Permissive`](crate::plan::EvaluationMode::Permissive); this includes any
+    /// error which keeps a data modification statement from applying its changes.
+    pub fn is_fatal(&self) -> bool {
+        matches!(
+            self,
+            EvaluationError::RecursionLimit(..)
+                | EvaluationError::NoMutationSink
+                | EvaluationError::InvalidAssignment(_)
+                | EvaluationError::DmlNotApplied(_)
+                | EvaluationError::Mutation(_)
+        )
+    }
+}
+
 /// Used when an error occurs during the logical to eval plan conversion. Allows the conversion
 /// to continue in order to report multiple errors.
 #[derive(Debug)]
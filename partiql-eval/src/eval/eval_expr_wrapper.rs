@@ -98,7 +98,7 @@ pub(crate) fn unwrap_args<const N: usize>(
 }
 
 /// An expression that is evaluated over `N` input arguments
-pub(crate) trait ExecuteEvalExpr<const N: usize>: Debug {
+pub(crate) trait ExecuteEvalExpr<const N: usize>: Debug + Send + Sync {
     /// Evaluate the expression
     fn evaluate<'a, 'c, 'o>(
         &'a self,
@@ -132,7 +132,7 @@ pub(crate) struct ArgValidateError {
 }
 
 /// A type which performs argument checking during evaluation.
-pub(crate) trait ArgChecker: Debug {
+pub(crate) trait ArgChecker: Debug + Send + Sync {
     /// Check an argument against an expected type.
     fn arg_check<'a>(
         typ: &PartiqlShape,
@@ -146,7 +146,7 @@ pub(crate) trait ArgChecker: Debug {
 }
 
 /// How to handle argument mismatch and `MISSING` propagation
-pub(crate) trait ArgShortCircuit: Debug {
+pub(crate) trait ArgShortCircuit: Debug + Send + Sync {
     /// Whether a mismatch is an error in `STRICT` mode
     fn is_strict_error() -> bool;
     /// What to propagate on mismatch/`MISSING`
@@ -496,7 +496,7 @@ pub(crate) struct UnaryValueExpr {}
 
 impl<F> ExecuteEvalExpr<1> for EvalExprWrapper<UnaryValueExpr, F>
 where
-    F: Fn(&Value, &dyn EvalContext) -> Value,
+    F: Fn(&Value, &dyn EvalContext) -> Value + Send + Sync,
 {
     #[inline]
     fn evaluate<'a, 'c, 'o>(
@@ -520,7 +520,7 @@ impl UnaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value) -> Value,
+        F: 'static + Fn(&Value) -> Value + Send + Sync,
     {
         Self::create_typed::<STRICT, F>([PartiqlShape::Dynamic; 1], args, f)
     }
@@ -533,7 +533,7 @@ impl UnaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value) -> Value,
+        F: 'static + Fn(&Value) -> Value + Send + Sync,
     {
         Self::create_typed_with_ctx::<{ STRICT }, _>(types, args, move |val, _| f(val))
     }
@@ -546,7 +546,7 @@ impl UnaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &dyn EvalContext) -> Value,
+        F: 'static + Fn(&Value, &dyn EvalContext) -> Value + Send + Sync,
     {
         type Check<const STRICT: bool> = DefaultArgChecker<STRICT, PropagateMissing<true>>;
         Self::create_checked_with_ctx::<{ STRICT }, Check<STRICT>, F>(types, args, f)
@@ -560,7 +560,7 @@ impl UnaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value) -> Value,
+        F: 'static + Fn(&Value) -> Value + Send + Sync,
         ArgC: 'static + ArgChecker,
     {
         Self::create_checked_with_ctx::<{ STRICT }, ArgC, _>(types, args, move |val, _| f(val))
@@ -574,7 +574,7 @@ impl UnaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &dyn EvalContext) -> Value,
+        F: 'static + Fn(&Value, &dyn EvalContext) -> Value + Send + Sync,
         ArgC: 'static + ArgChecker,
     {
         EvalExprWrapper::create_checked_with_ctx::<{ STRICT }, 1, ArgC>(
@@ -592,7 +592,7 @@ pub(crate) struct BinaryValueExpr {}
 
 impl<F> ExecuteEvalExpr<2> for EvalExprWrapper<BinaryValueExpr, F>
 where
-    F: Fn(&Value, &Value, &dyn EvalContext) -> Value,
+    F: Fn(&Value, &Value, &dyn EvalContext) -> Value + Send + Sync,
 {
     #[inline]
     fn evaluate<'a, 'c, 'o>(
@@ -616,7 +616,7 @@ impl BinaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value) -> Value + Send + Sync,
     {
         Self::create_typed::<STRICT, F>([TYPE_DYNAMIC; 2], args, f)
     }
@@ -629,7 +629,7 @@ impl BinaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value) -> Value + Send + Sync,
     {
         type Check<const STRICT: bool> = DefaultArgChecker<STRICT, PropagateMissing<true>>;
         Self::create_checked::<{ STRICT }, Check<STRICT>, F>(types, args, f)
@@ -643,7 +643,7 @@ impl BinaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value) -> Value + Send + Sync,
         ArgC: 'static + ArgChecker,
     {
         EvalExprWrapper::create_checked::<{ STRICT }, 2, ArgC>(
@@ -661,7 +661,7 @@ pub(crate) struct TernaryValueExpr {}
 
 impl<F> ExecuteEvalExpr<3> for EvalExprWrapper<TernaryValueExpr, F>
 where
-    F: Fn(&Value, &Value, &Value, &dyn EvalContext) -> Value,
+    F: Fn(&Value, &Value, &Value, &dyn EvalContext) -> Value + Send + Sync,
 {
     #[inline]
     fn evaluate<'a, 'c, 'o>(
@@ -685,7 +685,7 @@ impl TernaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value, &Value) -> Value + Send + Sync,
     {
         Self::create_typed::<STRICT, F>([TYPE_DYNAMIC; 3], args, f)
     }
@@ -698,7 +698,7 @@ impl TernaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value, &Value) -> Value + Send + Sync,
     {
         type Check<const STRICT: bool> = DefaultArgChecker<STRICT, PropagateMissing<true>>;
         Self::create_checked::<{ STRICT }, Check<STRICT>, F>(types, args, f)
@@ -712,7 +712,7 @@ impl TernaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value, &Value) -> Value + Send + Sync,
         ArgC: 'static + ArgChecker,
     {
         EvalExprWrapper::create_checked::<{ STRICT }, 3, ArgC>(
@@ -730,7 +730,7 @@ pub(crate) struct QuaternaryValueExpr {}
 
 impl<F> ExecuteEvalExpr<4> for EvalExprWrapper<QuaternaryValueExpr, F>
 where
-    F: Fn(&Value, &Value, &Value, &Value, &dyn EvalContext) -> Value,
+    F: Fn(&Value, &Value, &Value, &Value, &dyn EvalContext) -> Value + Send + Sync,
 {
     #[inline]
     fn evaluate<'a, 'c, 'o>(
@@ -760,7 +760,7 @@ impl QuaternaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value, &Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value, &Value, &Value) -> Value + Send + Sync,
     {
         Self::create_typed::<STRICT, F>([TYPE_DYNAMIC; 4], args, f)
     }
@@ -773,7 +773,7 @@ impl QuaternaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value, &Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value, &Value, &Value) -> Value + Send + Sync,
     {
         type Check<const STRICT: bool> = DefaultArgChecker<STRICT, PropagateMissing<true>>;
         Self::create_checked::<{ STRICT }, Check<STRICT>, F>(types, args, f)
@@ -787,7 +787,7 @@ impl QuaternaryValueExpr {
         f: F,
     ) -> Result<Box<dyn EvalExpr>, BindError>
     where
-        F: 'static + Fn(&Value, &Value, &Value, &Value) -> Value,
+        F: 'static + Fn(&Value, &Value, &Value, &Value) -> Value + Send + Sync,
         ArgC: 'static + ArgChecker,
     {
         EvalExprWrapper::create_checked::<{ STRICT }, 4, ArgC>(
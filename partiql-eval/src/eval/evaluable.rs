@@ -1,23 +1,25 @@
 use crate::error::EvaluationError;
 use crate::eval::expr::EvalExpr;
+use crate::eval::parallel::{evaluate_partitions, Parallelism};
 use crate::eval::{EvalContext, EvalPlan, NestedContext};
 use itertools::Itertools;
 use partiql_value::Value::{Boolean, Missing, Null};
 use partiql_value::{
-    bag, list, tuple, Bag, List, NullSortedValue, Tuple, Value, ValueIntoIterator,
+    bag, list, tuple, Bag, BindingsName, List, NullSortedValue, Tuple, Value, ValueIntoIterator,
 };
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxBuildHasher, FxHashMap};
 use std::borrow::Borrow;
 use std::borrow::Cow;
-use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
+use std::hash::BuildHasher;
 
 use crate::env::basic::MapBindings;
+use partiql_catalog::aggregate_fn::AggregateFnExpr;
+use partiql_catalog::mutation::{Mutation, MutationSink};
 use partiql_value::datum::{Datum, DatumLower, DatumLowerResult, DatumTupleRef, RefTupleView};
-use std::rc::Rc;
 
 #[macro_export]
 macro_rules! take_input {
@@ -41,9 +43,73 @@ pub enum EvalType {
     GraphManaged,
 }
 
+/// A stream of values passed between [`Evaluable`]s by [`EvalPlan::execute_iter`].
+pub type ValueIter<'a> = Box<dyn Iterator<Item = Value> + 'a>;
+
+macro_rules! take_input_iter {
+    ($expr:expr, $ctx:expr) => {
+        match $expr {
+            None => {
+                $ctx.add_error($crate::error::EvaluationError::IllegalState(
+                    "Error in retrieving input value".to_string(),
+                ));
+                return Box::new(std::iter::empty());
+            }
+            Some(val) => val,
+        }
+    };
+}
+
+/// Evaluates `evaluable` by buffering its streamed inputs into bags.
+fn evaluate_buffered<'a, E>(
+    evaluable: &'a E,
+    inputs: [Option<ValueIter<'a>>; 2],
+    ctx: &'a dyn EvalContext,
+) -> ValueIter<'a>
+where
+    E: Evaluable + ?Sized,
+{
+    let inputs = inputs.map(|input| input.map(|values| Value::from(values.collect::<Bag>())));
+    Box::new(evaluable.evaluate(inputs, ctx).into_iter())
+}
+
+/// Evaluates `f` over each of `values` in order, keeping the values it returns; if `parallelism`
+/// allows, `values` are partitioned and evaluated across threads.
+fn filter_map_values<T, F>(
+    parallelism: Parallelism,
+    values: impl IntoIterator<Item = T>,
+    ctx: &dyn EvalContext,
+    f: F,
+) -> Vec<Value>
+where
+    T: Send,
+    F: Fn(T, &dyn EvalContext) -> Option<Value> + Sync,
+{
+    if !parallelism.is_parallel() {
+        return values.into_iter().filter_map(|v| f(v, ctx)).collect();
+    }
+    let partitioned =
+        parallelism.map_partitions(values.into_iter().collect(), ctx, |values, ctx| {
+            values.into_iter().filter_map(|v| f(v, ctx)).collect_vec()
+        });
+    match partitioned {
+        Ok(partitions) => partitions.into_iter().flatten().collect(),
+        Err(values) => values.into_iter().filter_map(|v| f(v, ctx)).collect(),
+    }
+}
+
 /// `Evaluable` represents each evaluation operator in the evaluation plan as an evaluable entity.
-pub trait Evaluable: Debug {
+pub trait Evaluable: Debug + Send + Sync {
     fn evaluate(&self, inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value;
+    /// Evaluates the operator over streams of its inputs' values, returning the stream of its
+    /// output's values. By default, the inputs are buffered and passed to [`Evaluable::evaluate`].
+    fn evaluate_iter<'a>(
+        &'a self,
+        inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        evaluate_buffered(self, inputs, ctx)
+    }
     fn get_vars(&self) -> Option<&[String]> {
         None
     }
@@ -59,6 +125,8 @@ pub(crate) struct EvalScan {
     pub(crate) expr: Box<dyn EvalExpr>,
     pub(crate) as_key: String,
     pub(crate) at_key: Option<String>,
+    pub(crate) columns: Option<Vec<String>>,
+    pub(crate) parallelism: Parallelism,
 
     // cached values
     attrs: Vec<String>,
@@ -71,6 +139,10 @@ impl Debug for EvalScan {
 
         write!(f, " AS {}", self.as_key)?;
 
+        if let Some(columns) = &self.columns {
+            write!(f, " ({})", columns.join(", "))?;
+        }
+
         if let Some(at_key) = &self.at_key {
             write!(f, " AT {at_key}")?;
         }
@@ -86,6 +158,8 @@ impl EvalScan {
             expr,
             as_key: as_key.to_string(),
             at_key: None,
+            columns: None,
+            parallelism: Parallelism::default(),
 
             attrs,
         }
@@ -96,10 +170,104 @@ impl EvalScan {
             expr,
             as_key: as_key.to_string(),
             at_key: Some(at_key.to_string()),
+            columns: None,
+            parallelism: Parallelism::default(),
 
             attrs,
         }
     }
+
+    pub(crate) fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    pub(crate) fn with_columns(mut self, columns: Option<Vec<String>>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Lazily scans the value of `expr` evaluated with the given `binding`.
+    fn scan<'a>(
+        &'a self,
+        binding: &Value,
+        ctx: &'a dyn EvalContext,
+    ) -> impl Iterator<Item = Value> + 'a {
+        let binding_tuple = binding.as_datum_tuple_ref();
+        let v = self.expr.evaluate(&binding_tuple, ctx).into_owned();
+        let ordered = v.is_ordered();
+        v.into_iter()
+            .enumerate()
+            .map(move |(idx, t)| self.bind(idx, t, ordered))
+    }
+
+    /// Scans the values of `expr` evaluated with the given `binding` as `expr` streams them (e.g.,
+    /// the rows of a table function); the value of any other `expr` is scanned by [`Self::scan`].
+    fn scan_iter<'a>(&'a self, binding: &Value, ctx: &'a dyn EvalContext) -> ValueIter<'a> {
+        let binding_tuple = binding.as_datum_tuple_ref();
+        match self.expr.evaluate_iter(&binding_tuple, ctx) {
+            // a streamed value is a bag
+            Some(values) => Box::new(
+                values
+                    .enumerate()
+                    .map(move |(idx, t)| self.bind(idx, t, false)),
+            ),
+            None => Box::new(self.scan(binding, ctx)),
+        }
+    }
+
+    /// Creates the binding tuple of the `idx`th value `t` of the scanned value.
+    fn bind(&self, idx: usize, t: Value, ordered: bool) -> Value {
+        let t = match &self.columns {
+            Some(columns) => rename_row(columns, t),
+            None => t,
+        };
+        let mut out = Tuple::from([(self.as_key.as_str(), t)]);
+        if let Some(at_key) = &self.at_key {
+            let at_id = if ordered {
+                Value::from(idx as i64)
+            } else {
+                Missing
+            };
+            out.insert(at_key, at_id);
+        }
+        Value::Tuple(Box::new(out))
+    }
+
+    /// Scans each of `bindings` with their values partitioned across threads; a single binding
+    /// (e.g., of a `FROM` clause's first item) has the values of its scanned value partitioned.
+    fn scan_parallel(&self, bindings: Bag, ctx: &dyn EvalContext) -> Vec<Value> {
+        if bindings.len() == 1 {
+            let binding = bindings.into_iter().next().unwrap_or(Missing);
+            let v = self
+                .expr
+                .evaluate(&binding.as_datum_tuple_ref(), ctx)
+                .into_owned();
+            let ordered = v.is_ordered();
+            filter_map_values(
+                self.parallelism,
+                v.into_iter().enumerate(),
+                ctx,
+                |(idx, t), _| Some(self.bind(idx, t, ordered)),
+            )
+        } else {
+            let bindings = bindings.into_iter().collect_vec();
+            match self
+                .parallelism
+                .map_partitions(bindings, ctx, |bindings, ctx| {
+                    bindings
+                        .iter()
+                        .flat_map(|binding| self.scan(binding, ctx))
+                        .collect_vec()
+                }) {
+                Ok(partitions) => partitions.into_iter().flatten().collect(),
+                Err(bindings) => bindings
+                    .iter()
+                    .flat_map(|binding| self.scan(binding, ctx))
+                    .collect(),
+            }
+        }
+    }
 }
 
 impl Evaluable for EvalScan {
@@ -111,35 +279,29 @@ impl Evaluable for EvalScan {
             Value::Tuple(t) => bag![*t],
             _ => bag![tuple![]],
         };
-        let mut value = bag![];
-        bindings.iter().for_each(|binding| {
-            let binding_tuple = binding.as_datum_tuple_ref();
-            let v = self.expr.evaluate(&binding_tuple, ctx).into_owned();
-            let mut at_index_counter: i64 = 0;
-            if let Some(at_key) = &self.at_key {
-                let ordered = v.is_ordered();
-                for t in v {
-                    let mut out = Tuple::from([(self.as_key.as_str(), t)]);
-                    let at_id = if ordered {
-                        at_index_counter.into()
-                    } else {
-                        Missing
-                    };
-                    out.insert(at_key, at_id);
-                    value.push(Value::Tuple(Box::new(out)));
-                    at_index_counter += 1;
-                }
-            } else {
-                for t in v {
-                    let out = Tuple::from([(self.as_key.as_str(), t)]);
-                    value.push(Value::Tuple(Box::new(out)));
-                }
-            }
-        });
+        let value = if self.parallelism.is_parallel() {
+            Bag::from(self.scan_parallel(bindings, ctx))
+        } else {
+            bindings
+                .iter()
+                .flat_map(|binding| self.scan(binding, ctx))
+                .collect::<Bag>()
+        };
 
         Value::Bag(Box::new(value))
     }
 
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let bindings = inputs[0]
+            .take()
+            .unwrap_or_else(|| Box::new(std::iter::once(Value::from(tuple![]))));
+        Box::new(bindings.flat_map(move |binding| self.scan_iter(&binding, ctx)))
+    }
+
     fn get_vars(&self) -> Option<&[String]> {
         Some(&self.attrs)
     }
@@ -148,12 +310,23 @@ impl Evaluable for EvalScan {
 /// Represents an evaluation `Join` operator; `Join` joins the tuples from its LHS and RHS based on a logic defined
 /// by [`EvalJoinKind`]. For semantics of `PartiQL` joins and their distinction with SQL's see sections
 /// 5.3 – 5.7 of [PartiQL Specification — August 1, 2019](https://partiql.org/assets/PartiQL-Specification.pdf).
+///
+/// A join is evaluated as a nested loop, unless it has [`EvalHashJoinKeys`], in which case it is
+/// evaluated as a hash join whenever those keys apply.
 pub(crate) struct EvalJoin {
     pub(crate) kind: EvalJoinKind,
     pub(crate) on: Option<Box<dyn EvalExpr>>,
 
     pub(crate) left: Box<dyn Evaluable>,
     pub(crate) right: Box<dyn Evaluable>,
+
+    pub(crate) hash_keys: Option<EvalHashJoinKeys>,
+
+    /// The join columns of a `USING` or `NATURAL` join, bound in each joined binding.
+    pub(crate) using: Vec<(String, Box<dyn EvalExpr>)>,
+
+    // cached values
+    attrs: Vec<String>,
 }
 
 #[derive(Debug)]
@@ -164,6 +337,110 @@ pub(crate) enum EvalJoinKind {
     Full,
 }
 
+/// The equality conjuncts `l_1 = r_1 AND ... AND l_n = r_n` of a join condition, where each `l_i`
+/// references only variables of the left side of the join and each `r_i` only variables of the
+/// right side, by which a join is evaluated as a hash join.
+///
+/// As a hash join evaluates each side of the join once, rather than evaluating one side for every
+/// binding of the other, the keys only apply if neither side depends upon the bindings of the
+/// other.
+pub(crate) struct EvalHashJoinKeys {
+    pub(crate) left: Vec<Box<dyn EvalExpr>>,
+    pub(crate) right: Vec<Box<dyn EvalExpr>>,
+    /// Global variables that must resolve for the keys to apply; a side of the join that looks up
+    /// one of these globals otherwise falls back to looking it up in the bindings of the other side.
+    pub(crate) globals: Vec<BindingsName<'static>>,
+}
+
+impl EvalHashJoinKeys {
+    #[inline]
+    fn applies(&self, ctx: &dyn EvalContext) -> bool {
+        self.globals
+            .iter()
+            .all(|global| ctx.get(global).is_some_and(|v| !v.is_missing()))
+    }
+}
+
+/// The key under which a hash join buckets the value of a join key; values that are equal
+/// according to `PartiQL`'s `=` have equal keys.
+#[derive(Debug, Hash, PartialEq, Eq)]
+enum JoinKey {
+    Value(Value),
+    /// The key of all values which contain a value that cannot be canonicalized, which are
+    /// bucketed together.
+    Unhashable,
+}
+
+impl JoinKey {
+    /// Returns the key of `value`, or `None` if `value` equals no value, i.e. it is `NULL` or
+    /// `MISSING`.
+    fn new(value: &Value) -> Option<Self> {
+        match value {
+            Null | Missing => None,
+            _ => Some(canonical_join_value(value).map_or(JoinKey::Unhashable, JoinKey::Value)),
+        }
+    }
+}
+
+/// Numbers with a magnitude of at least `2^53`, above which not every integer is representable as
+/// a `REAL`, are canonicalized as `REAL`s, as that is how an `INT` is compared with a `REAL`.
+const MAX_EXACT_REAL_INT: f64 = 9_007_199_254_740_992.0;
+
+/// Returns the canonical form of `value`, which is structurally equal for values that are equal
+/// according to `PartiQL`'s `=`, which coerces between numeric types, also within collections;
+/// returns `None` for values that cannot be canonicalized.
+fn canonical_join_value(value: &Value) -> Option<Value> {
+    use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+    use rust_decimal::Decimal;
+
+    let decimal = |d: Decimal| {
+        let d = if d.is_zero() {
+            Decimal::ZERO
+        } else {
+            d.normalize()
+        };
+        match d.to_f64() {
+            Some(f) if f.abs() >= MAX_EXACT_REAL_INT => Value::from(f),
+            _ => Value::from(d),
+        }
+    };
+    Some(match value {
+        Value::Integer(i) if (*i as f64).abs() < MAX_EXACT_REAL_INT => decimal(Decimal::from(*i)),
+        Value::Integer(i) => Value::from(*i as f64),
+        Value::Decimal(d) => decimal(**d),
+        Value::Real(r) if r.is_finite() && r.abs() < MAX_EXACT_REAL_INT => {
+            Decimal::from_f64(r.0).map_or_else(|| value.clone(), decimal)
+        }
+        Value::List(l) => Value::from(
+            l.iter()
+                .map(canonical_join_value)
+                .collect::<Option<List>>()?,
+        ),
+        Value::Bag(b) => Value::from(
+            b.iter()
+                .map(canonical_join_value)
+                .collect::<Option<Bag>>()?,
+        ),
+        Value::Tuple(t) => Value::from(
+            t.pairs()
+                .map(|(k, v)| canonical_join_value(v).map(|v| (k.as_str(), v)))
+                .collect::<Option<Tuple>>()?,
+        ),
+        Value::Variant(_) | Value::Graph(_) => return None,
+        _ => value.clone(),
+    })
+}
+
+/// Creates a `Tuple` with attributes `attrs`, each with value `Null`
+#[inline]
+fn tuple_with_null_vals<I, S>(attrs: I) -> Tuple
+where
+    S: Into<String>,
+    I: IntoIterator<Item = S>,
+{
+    attrs.into_iter().map(|k| (k.into(), Null)).collect()
+}
+
 impl Debug for EvalJoin {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:#?} JOIN", &self.kind)?;
@@ -171,6 +448,13 @@ impl Debug for EvalJoin {
             write!(f, " ON ")?;
             on.fmt(f)?;
         }
+        if !self.using.is_empty() {
+            write!(
+                f,
+                " USING ({})",
+                self.using.iter().map(|(name, _)| name).join(", ")
+            )?;
+        }
         Ok(())
     }
 }
@@ -182,155 +466,435 @@ impl EvalJoin {
         right: Box<dyn Evaluable>,
         on: Option<Box<dyn EvalExpr>>,
     ) -> Self {
+        let attrs = [left.get_vars(), right.get_vars()]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .cloned()
+            .collect();
         EvalJoin {
             kind,
             on,
 
             left,
             right,
+
+            hash_keys: None,
+
+            using: vec![],
+
+            attrs,
         }
     }
-}
 
-impl Evaluable for EvalJoin {
-    fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
-        /// Creates a `Tuple` with attributes `attrs`, each with value `Null`
-        #[inline]
-        fn tuple_with_null_vals<I, S>(attrs: I) -> Tuple
-        where
-            S: Into<String>,
-            I: IntoIterator<Item = S>,
-        {
-            attrs.into_iter().map(|k| (k.into(), Null)).collect()
+    pub(crate) fn with_hash_keys(mut self, hash_keys: EvalHashJoinKeys) -> Self {
+        self.hash_keys = Some(hash_keys);
+        self
+    }
+
+    pub(crate) fn with_using(mut self, using: Vec<(String, Box<dyn EvalExpr>)>) -> Self {
+        self.attrs
+            .extend(using.iter().map(|(name, _)| name.clone()));
+        self.using = using;
+        self
+    }
+
+    /// Binds the join columns of a `USING` or `NATURAL` join in the joined binding `b_l_b_r`.
+    fn bind_join_columns(&self, b_l_b_r: Value, ctx: &dyn EvalContext) -> Value {
+        if self.using.is_empty() {
+            return b_l_b_r;
+        }
+        let mut binding = b_l_b_r.coerce_into_tuple();
+        let tuple_ref = DatumTupleRef::Tuple(&binding);
+        let columns = self
+            .using
+            .iter()
+            .map(|(name, expr)| (name, expr.evaluate(&tuple_ref, ctx).into_owned()))
+            .collect_vec();
+        for (name, value) in columns {
+            // a column of a nested join with the same name is superseded
+            binding.remove(&BindingsName::CaseSensitive(Cow::Borrowed(name)));
+            binding.insert(name, value);
         }
+        Value::from(binding)
+    }
 
-        let mut output_bag = bag![];
-        let input_env = inputs[0].take().unwrap_or_else(|| Value::from(tuple![]));
-        let lhs_values = self.left.evaluate([Some(input_env.clone()), None], ctx);
-        let left_bindings = match lhs_values {
-            Value::Bag(t) => *t,
+    /// Returns the [`EvalHashJoinKeys`] of the join, if they apply in `ctx`.
+    #[inline]
+    fn applicable_hash_keys(&self, ctx: &dyn EvalContext) -> Option<&EvalHashJoinKeys> {
+        self.hash_keys.as_ref().filter(|keys| keys.applies(ctx))
+    }
+
+    /// Returns whether the join condition holds for the concatenated binding `b_l || b_r` in the
+    /// environment `env`; joins without a condition match every pair of bindings.
+    #[inline]
+    fn matches(&self, env: &Value, b_l_b_r: &Tuple, ctx: &dyn EvalContext) -> bool {
+        match &self.on {
+            None => true,
+            Some(condition) => {
+                let env_b_l_b_r = env.as_tuple_ref().as_ref().tuple_concat(b_l_b_r);
+                let tuple_ref = DatumTupleRef::Tuple(&env_b_l_b_r);
+                let cond = condition.evaluate(&tuple_ref, ctx);
+                cond.as_ref() == &Value::Boolean(true)
+            }
+        }
+    }
+    /// Returns the bindings produced by joining the binding `b_o` of the side driving the iteration
+    /// of an `INNER`, `LEFT`, or `RIGHT` join with the bindings of the other side.
+    ///
+    /// For `INNER` and `LEFT` joins, `b_o` is a binding of the left side and the right side is
+    /// evaluated in the environment `env || b_o`. For `RIGHT` joins, which are evaluated as the
+    /// mirror image of a `LEFT` join, `b_o` is a binding of the right side; as the left side may
+    /// not depend upon the right side, its bindings `left_bindings` are evaluated once in `env`.
+    fn join_binding(
+        &self,
+        env: &Value,
+        b_o: &Value,
+        left_bindings: Option<&Bag>,
+        ctx: &dyn EvalContext,
+    ) -> Vec<Value> {
+        let outer_is_left = match self.kind {
+            EvalJoinKind::Inner | EvalJoinKind::Left => true,
+            EvalJoinKind::Right => false,
+            EvalJoinKind::Full => unreachable!("FULL joins are not evaluated per binding"),
+        };
+
+        let inner_bindings = match left_bindings {
+            Some(bindings) if !outer_is_left => Cow::Borrowed(bindings),
             _ => {
-                ctx.add_error(EvaluationError::IllegalState(
-                    "Left side of FROM source should result in a bag of bindings".to_string(),
-                ));
-                return Missing;
+                let env_b_o = env
+                    .as_tuple_ref()
+                    .as_ref()
+                    .tuple_concat(b_o.as_tuple_ref().borrow());
+                match self.right.evaluate([Some(Value::from(env_b_o)), None], ctx) {
+                    Value::Bag(t) => Cow::Owned(*t),
+                    _ => Cow::Owned(bag![tuple![]]),
+                }
             }
         };
 
-        // Current implementations follow pseudocode defined in section 5.6 of spec
-        // https://partiql.org/assets/PartiQL-Specification.pdf#subsection.5.6
+        // define empty bag q, then for each binding b_i in eval(p0, (p || b_o), i), add the
+        // concatenated binding to q if eval(p0, (p || b_l || b_r), c) is true
+        let matched = inner_bindings
+            .iter()
+            .filter_map(|b_i| {
+                let (b_l, b_r) = if outer_is_left {
+                    (b_o, b_i)
+                } else {
+                    (b_i, b_o)
+                };
+                let b_l_b_r = b_l
+                    .as_tuple_ref()
+                    .as_ref()
+                    .tuple_concat(b_r.as_tuple_ref().borrow());
+                self.matches(env, &b_l_b_r, ctx)
+                    .then(|| Value::from(b_l_b_r))
+            })
+            .collect_vec();
+
+        if matched.is_empty() {
+            self.unmatched_binding(b_o).into_iter().collect()
+        } else {
+            matched
+        }
+    }
+
+    /// Returns the binding of an outer join for the binding `b_o` of the side driving its
+    /// iteration that matched no binding of the other side, if the join kind keeps it.
+    fn unmatched_binding(&self, b_o: &Value) -> Option<Value> {
         match self.kind {
-            EvalJoinKind::Inner => {
-                // for each binding b_l in eval(p0, p, l)
-                left_bindings.iter().for_each(|b_l| {
-                    let env_b_l = input_env
-                        .as_tuple_ref()
-                        .as_ref()
-                        .tuple_concat(b_l.as_tuple_ref().borrow());
-                    let rhs_values = self.right.evaluate([Some(Value::from(env_b_l)), None], ctx);
-
-                    let right_bindings = match rhs_values {
-                        Value::Bag(t) => *t,
-                        _ => bag![tuple![]],
-                    };
+            EvalJoinKind::Inner => None,
+            // pad b_o with `NULL` values for the other side's variables
+            EvalJoinKind::Left | EvalJoinKind::Full => {
+                let attrs = self.right.get_vars().unwrap_or(&[]);
+                let new_binding = b_o
+                    .as_tuple_ref()
+                    .as_ref()
+                    .tuple_concat(&tuple_with_null_vals(attrs));
+                // b_l || <v_1_r: NULL, ..., v_n_r: NULL>
+                Some(Value::from(new_binding))
+            }
+            EvalJoinKind::Right => {
+                let attrs = self.left.get_vars().unwrap_or(&[]);
+                let new_binding =
+                    tuple_with_null_vals(attrs).tuple_concat(b_o.as_tuple_ref().borrow());
+                // <v_1_l: NULL, ..., v_n_l: NULL> || b_r
+                Some(Value::from(new_binding))
+            }
+        }
+    }
 
-                    // for each binding b_r in eval (p0, (p || b_l), r)
-                    for b_r in &right_bindings {
-                        match &self.on {
-                            None => {
-                                let b_l_b_r = b_l
-                                    .as_tuple_ref()
-                                    .as_ref()
-                                    .tuple_concat(b_r.as_tuple_ref().borrow());
-                                output_bag.push(Value::from(b_l_b_r));
-                            }
-                            // if eval(p0, (p || b_l || b_r), c) is true, add b_l || b_r to output bag
-                            Some(condition) => {
-                                let b_l_b_r = b_l
-                                    .as_tuple_ref()
-                                    .as_ref()
-                                    .tuple_concat(b_r.as_tuple_ref().borrow());
-                                let env_b_l_b_r =
-                                    &input_env.as_tuple_ref().as_ref().tuple_concat(&b_l_b_r);
-                                let tuple_ref = DatumTupleRef::Tuple(env_b_l_b_r);
-                                let cond = condition.evaluate(&tuple_ref, ctx);
-                                if let Value::Boolean(true) = cond.as_ref() {
-                                    output_bag.push(Value::Tuple(Box::new(b_l_b_r)));
-                                }
-                            }
-                        }
-                    }
-                });
+    /// Returns the side of an `INNER`, `LEFT`, or `RIGHT` join that drives its iteration.
+    #[inline]
+    fn outer(&self) -> &dyn Evaluable {
+        match self.kind {
+            EvalJoinKind::Right => self.right.as_ref(),
+            _ => self.left.as_ref(),
+        }
+    }
+
+    /// Evaluates the `side` of the join named `name` in the environment `env`, reporting an
+    /// error if it does not result in a bag of bindings.
+    fn evaluate_side(
+        side: &dyn Evaluable,
+        name: &str,
+        env: &Value,
+        ctx: &dyn EvalContext,
+    ) -> Option<Bag> {
+        match side.evaluate([Some(env.clone()), None], ctx) {
+            Value::Bag(t) => Some(*t),
+            _ => {
+                ctx.add_error(EvaluationError::IllegalState(format!(
+                    "{name} side of FROM source should result in a bag of bindings"
+                )));
+                None
             }
-            EvalJoinKind::Left => {
-                // for each binding b_l in eval(p0, p, l)
-                left_bindings.iter().for_each(|b_l| {
-                    // define empty bag q_r
-                    let mut output_bag_left = bag![];
-                    let env_b_l = input_env
-                        .as_tuple_ref()
-                        .as_ref()
-                        .tuple_concat(b_l.as_tuple_ref().borrow());
-                    let rhs_values = self.right.evaluate([Some(Value::from(env_b_l)), None], ctx);
-
-                    let right_bindings = match rhs_values {
-                        Value::Bag(t) => *t,
-                        _ => bag![tuple![]],
-                    };
+        }
+    }
 
-                    // for each binding b_r in eval (p0, (p || b_l), r)
-                    for b_r in &right_bindings {
-                        match &self.on {
-                            None => {
-                                let b_l_b_r = b_l
-                                    .as_tuple_ref()
-                                    .as_ref()
-                                    .tuple_concat(b_r.as_tuple_ref().borrow());
-                                output_bag_left.push(Value::from(b_l_b_r));
-                            }
-                            // if eval(p0, (p || b_l || b_r), c) is true, add b_l || b_r to q_r
-                            Some(condition) => {
-                                let b_l_b_r = b_l
-                                    .as_tuple_ref()
-                                    .as_ref()
-                                    .tuple_concat(b_r.as_tuple_ref().borrow());
-                                let env_b_l_b_r =
-                                    &input_env.as_tuple_ref().as_ref().tuple_concat(&b_l_b_r);
-                                let tuple_ref = DatumTupleRef::Tuple(env_b_l_b_r);
-                                let cond = condition.evaluate(&tuple_ref, ctx);
-                                if cond.as_ref() == &Value::Boolean(true) {
-                                    output_bag_left.push(Value::Tuple(Box::new(b_l_b_r)));
-                                }
-                            }
-                        }
-                    }
+    /// Joins the bindings of the left and right sides of the join.
+    fn evaluate_join(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        let input_env = inputs[0].take().unwrap_or_else(|| Value::from(tuple![]));
 
-                    // if q_r is the empty bag
-                    if output_bag_left.is_empty() {
-                        let attrs = self.right.get_vars().unwrap_or(&[]);
-                        let new_binding = b_l
-                            .as_tuple_ref()
-                            .as_ref()
-                            .tuple_concat(&tuple_with_null_vals(attrs));
-                        // add b_l || <v_1_r: NULL, ..., v_n_r: NULL> to output bag
-                        output_bag.push(Value::from(new_binding));
-                    } else {
-                        // otherwise for each binding b_r in q_r, add b_l || b_r to output bag
-                        for elem in output_bag_left {
-                            output_bag.push(elem);
-                        }
+        if let Some(keys) = self.applicable_hash_keys(ctx) {
+            return self.evaluate_hash_join(keys, &input_env, ctx);
+        }
+
+        // Current implementations follow pseudocode defined in section 5.6 of spec
+        // https://partiql.org/assets/PartiQL-Specification.pdf#subsection.5.6
+        if !matches!(self.kind, EvalJoinKind::Full) {
+            let side = match self.kind {
+                EvalJoinKind::Right => "Right",
+                _ => "Left",
+            };
+            let Some(outer_bindings) = Self::evaluate_side(self.outer(), side, &input_env, ctx)
+            else {
+                return Missing;
+            };
+            let left_bindings = match self.kind {
+                EvalJoinKind::Right => {
+                    match Self::evaluate_side(self.left.as_ref(), "Left", &input_env, ctx) {
+                        Some(bindings) => Some(bindings),
+                        None => return Missing,
                     }
-                });
+                }
+                _ => None,
+            };
+            // for each binding b_o in eval(p0, p, o)
+            let output_bag = outer_bindings
+                .iter()
+                .flat_map(|b_o| self.join_binding(&input_env, b_o, left_bindings.as_ref(), ctx))
+                .collect::<Bag>();
+            return Value::Bag(Box::new(output_bag));
+        }
+
+        let mut output_bag = bag![];
+        let Some(left_bindings) = Self::evaluate_side(self.left.as_ref(), "Left", &input_env, ctx)
+        else {
+            return Missing;
+        };
+
+        // Neither side of a FULL JOIN may depend upon the other, so both are evaluated
+        // once in the input environment.
+        let Some(right_bindings) =
+            Self::evaluate_side(self.right.as_ref(), "Right", &input_env, ctx)
+        else {
+            return Missing;
+        };
+        let right_bindings = right_bindings.into_iter().collect_vec();
+        let mut right_matched = vec![false; right_bindings.len()];
+
+        // for each binding b_l in eval(p0, p, l)
+        left_bindings.iter().for_each(|b_l| {
+            let mut left_matched = false;
+
+            // for each binding b_r in eval(p0, p, r)
+            for (b_r, matched) in right_bindings.iter().zip(right_matched.iter_mut()) {
+                let b_l_b_r = b_l
+                    .as_tuple_ref()
+                    .as_ref()
+                    .tuple_concat(b_r.as_tuple_ref().borrow());
+                // if eval(p0, (p || b_l || b_r), c) is true, add b_l || b_r to output bag
+                if self.matches(&input_env, &b_l_b_r, ctx) {
+                    output_bag.push(Value::from(b_l_b_r));
+                    left_matched = true;
+                    *matched = true;
+                }
             }
-            EvalJoinKind::Full | EvalJoinKind::Right => {
-                ctx.add_error(EvaluationError::NotYetImplemented(
-                    "FULL and RIGHT JOIN".to_string(),
-                ));
-                return Missing;
+
+            // if no b_r matched, add b_l || <v_1_r: NULL, ..., v_n_r: NULL> to output bag
+            if !left_matched {
+                output_bag.extend(self.unmatched_binding(b_l));
             }
+        });
+
+        // for each b_r that matched no b_l, add <v_1_l: NULL, ..., v_n_l: NULL> || b_r to
+        // output bag
+        let attrs = self.left.get_vars().unwrap_or(&[]);
+        for (b_r, _) in right_bindings
+            .iter()
+            .zip(right_matched)
+            .filter(|(_, matched)| !matched)
+        {
+            let new_binding = tuple_with_null_vals(attrs).tuple_concat(b_r.as_tuple_ref().borrow());
+            output_bag.push(Value::from(new_binding));
+        }
+        Value::Bag(Box::new(output_bag))
+    }
+
+    /// Evaluates the join as a hash join on `keys`: the bindings of the side which does not drive
+    /// the iteration are bucketed by their keys, such that each binding of the driving side is
+    /// only checked against the bindings with equal keys.
+    ///
+    /// Bindings are produced in the same order as by a nested loop; the join condition is still
+    /// evaluated for each pair of bindings with equal keys, so its semantics are unchanged.
+    fn evaluate_hash_join(
+        &self,
+        keys: &EvalHashJoinKeys,
+        env: &Value,
+        ctx: &dyn EvalContext,
+    ) -> Value {
+        let (outer, outer_keys, inner, inner_keys, outer_is_left) = match self.kind {
+            EvalJoinKind::Right => (&self.right, &keys.right, &self.left, &keys.left, false),
+            _ => (&self.left, &keys.left, &self.right, &keys.right, true),
         };
+        let (outer_name, inner_name) = if outer_is_left {
+            ("Left", "Right")
+        } else {
+            ("Right", "Left")
+        };
+
+        let Some(outer_bindings) = Self::evaluate_side(outer.as_ref(), outer_name, env, ctx) else {
+            return Missing;
+        };
+        if outer_bindings.is_empty() && !matches!(self.kind, EvalJoinKind::Full) {
+            return Value::from(bag![]);
+        }
+        let Some(inner_bindings) = Self::evaluate_side(inner.as_ref(), inner_name, env, ctx) else {
+            return Missing;
+        };
+        let inner_bindings = inner_bindings.into_iter().collect_vec();
+
+        let join_key = |exprs: &[Box<dyn EvalExpr>], b: &Value| -> Option<Vec<JoinKey>> {
+            let env_b = env
+                .as_tuple_ref()
+                .as_ref()
+                .tuple_concat(b.as_tuple_ref().borrow());
+            let tuple_ref = DatumTupleRef::Tuple(&env_b);
+            exprs
+                .iter()
+                .map(|expr| JoinKey::new(expr.evaluate(&tuple_ref, ctx).as_ref()))
+                .collect()
+        };
+
+        let mut table: FxHashMap<Vec<JoinKey>, Vec<usize>> = FxHashMap::default();
+        if !outer_bindings.is_empty() {
+            for (idx, b_i) in inner_bindings.iter().enumerate() {
+                if let Some(key) = join_key(inner_keys, b_i) {
+                    table.entry(key).or_default().push(idx);
+                }
+            }
+        }
+
+        let mut inner_matched = vec![false; inner_bindings.len()];
+        let mut output_bag = bag![];
+        for b_o in outer_bindings.iter() {
+            let candidates = if table.is_empty() {
+                None
+            } else {
+                join_key(outer_keys, b_o).and_then(|key| table.get(&key))
+            };
+
+            let mut matched = false;
+            for &idx in candidates.into_iter().flatten() {
+                let b_i = &inner_bindings[idx];
+                let (b_l, b_r) = if outer_is_left {
+                    (b_o, b_i)
+                } else {
+                    (b_i, b_o)
+                };
+                let b_l_b_r = b_l
+                    .as_tuple_ref()
+                    .as_ref()
+                    .tuple_concat(b_r.as_tuple_ref().borrow());
+                if self.matches(env, &b_l_b_r, ctx) {
+                    output_bag.push(Value::from(b_l_b_r));
+                    matched = true;
+                    inner_matched[idx] = true;
+                }
+            }
+            if !matched {
+                output_bag.extend(self.unmatched_binding(b_o));
+            }
+        }
+
+        if let EvalJoinKind::Full = self.kind {
+            let attrs = self.left.get_vars().unwrap_or(&[]);
+            for (b_r, _) in inner_bindings
+                .iter()
+                .zip(inner_matched)
+                .filter(|(_, matched)| !matched)
+            {
+                let new_binding =
+                    tuple_with_null_vals(attrs).tuple_concat(b_r.as_tuple_ref().borrow());
+                output_bag.push(Value::from(new_binding));
+            }
+        }
         Value::Bag(Box::new(output_bag))
     }
+}
+
+impl Evaluable for EvalJoin {
+    fn evaluate(&self, inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        let joined = self.evaluate_join(inputs, ctx);
+        match joined {
+            Value::Bag(bindings) if !self.using.is_empty() => bindings
+                .into_iter()
+                .map(|b_l_b_r| self.bind_join_columns(b_l_b_r, ctx))
+                .collect::<Bag>()
+                .into(),
+            joined => joined,
+        }
+    }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        if matches!(self.kind, EvalJoinKind::Full) || self.applicable_hash_keys(ctx).is_some() {
+            // the unmatched bindings of the right side of a FULL join are only known once the
+            // left is exhausted, and a hash join buckets one side before producing any binding
+            return evaluate_buffered(self, inputs, ctx);
+        }
+
+        let input_env = inputs[0]
+            .take()
+            .and_then(|mut env| env.next())
+            .unwrap_or_else(|| Value::from(tuple![]));
+        let left_bindings = match self.kind {
+            EvalJoinKind::Right => {
+                match Self::evaluate_side(self.left.as_ref(), "Left", &input_env, ctx) {
+                    Some(bindings) => Some(bindings),
+                    None => return Box::new(Missing.into_iter()),
+                }
+            }
+            _ => None,
+        };
+        let env = Box::new(std::iter::once(input_env.clone()));
+        let outer_bindings = self.outer().evaluate_iter([Some(env), None], ctx);
+        Box::new(
+            outer_bindings
+                .flat_map(move |b_o| {
+                    self.join_binding(&input_env, &b_o, left_bindings.as_ref(), ctx)
+                })
+                .map(move |b_l_b_r| self.bind_join_columns(b_l_b_r, ctx)),
+        )
+    }
+
+    fn get_vars(&self) -> Option<&[String]> {
+        Some(&self.attrs)
+    }
 
     fn eval_type(&self) -> EvalType {
         EvalType::SelfManaged
@@ -352,29 +916,66 @@ impl Evaluable for EvalJoin {
 pub(crate) struct AggregateExpression {
     pub(crate) name: String,
     pub(crate) expr: Box<dyn EvalExpr>,
+    pub(crate) args: Vec<Box<dyn EvalExpr>>,
     pub(crate) func: Box<dyn AggregateFunction>,
 }
 
+impl AggregateExpression {
+    /// The values of the call's further arguments for the row `bindings`.
+    fn eval_args<'a, 'c>(
+        &'a self,
+        bindings: &'a DatumTupleRef<'a>,
+        ctx: &'c dyn EvalContext,
+    ) -> Vec<Value>
+    where
+        'c: 'a,
+    {
+        self.args
+            .iter()
+            .map(|arg| arg.evaluate(bindings, ctx).into_owned())
+            .collect()
+    }
+}
+
 impl AggregateFunction for AggregateExpression {
     #[inline]
     fn next_distinct(
         &self,
         input_value: &Value,
+        args: &[Value],
         state: &mut Option<Value>,
         seen: &mut FxHashMap<Value, ()>,
-    ) {
+    ) -> Result<(), EvaluationError> {
         if input_value.is_present() {
-            self.func.next_distinct(input_value, state, seen);
+            self.func.next_distinct(input_value, args, state, seen)
+        } else {
+            Ok(())
         }
     }
 
     #[inline]
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         if input_value.is_present() {
-            self.func.next_value(input_value, state);
+            self.func.next_value(input_value, args, state)
+        } else {
+            Ok(())
         }
     }
 
+    #[inline]
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        self.func.merge(state, other)
+    }
+
     #[inline]
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
         self.func.finalize(state)
@@ -382,34 +983,70 @@ impl AggregateFunction for AggregateExpression {
 }
 
 /// Represents an SQL aggregation function computed on a collection of input values.
-pub trait AggregateFunction: Debug {
+pub trait AggregateFunction: Debug + Send + Sync {
     #[inline]
     fn next_distinct(
         &self,
         input_value: &Value,
+        args: &[Value],
         state: &mut Option<Value>,
         seen: &mut FxHashMap<Value, ()>,
-    ) {
+    ) -> Result<(), EvaluationError> {
         match seen.entry(input_value.clone()) {
-            Entry::Occupied(_) => {}
+            Entry::Occupied(_) => Ok(()),
             Entry::Vacant(v) => {
                 v.insert(());
-                self.next_value(input_value, state);
+                self.next_value(input_value, args, state)
             }
         }
     }
-    /// Provides the next value for the given `group`.
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>);
+    /// Provides the next value for the given `group`, along with the values of the call's further
+    /// arguments (e.g., the delimiter of `string_agg(x, ',')`).
+    fn next_value(
+        &self,
+        input_value: &Value,
+        args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError>;
+    /// Combines `other`, the state of a part of a `group` whose values follow those of `state`,
+    /// into `state`.
+    fn merge(&self, state: &mut Option<Value>, other: Option<Value>)
+        -> Result<(), EvaluationError>;
     /// Returns the result of the aggregation function for a given `group`.
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError>;
 }
 
+/// Combines the state `other` of a part of a group into `state` by `combine`; a part without a
+/// state, to which no value was aggregated, leaves `state` unchanged.
+#[inline]
+fn merge_state(
+    state: &mut Option<Value>,
+    other: Option<Value>,
+    combine: impl FnOnce(&mut Value, Value) -> Result<(), EvaluationError>,
+) -> Result<(), EvaluationError> {
+    let Some(other) = other else {
+        return Ok(());
+    };
+    match state {
+        Some(state) => combine(state, other),
+        None => {
+            *state = Some(other);
+            Ok(())
+        }
+    }
+}
+
 /// Represents SQL's `AVG` aggregation function
 #[derive(Debug)]
 pub(crate) struct Avg {}
 
 impl AggregateFunction for Avg {
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        _args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         match state {
             None => *state = Some(Value::from(list![Value::from(1), input_value.clone()])),
             Some(Value::List(list)) => {
@@ -422,6 +1059,28 @@ impl AggregateFunction for Avg {
             }
             _ => unreachable!(),
         };
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |state, other| {
+            let (Value::List(list), Value::List(other)) = (state, other) else {
+                return Err(EvaluationError::IllegalState(
+                    "Bad merge state for Avg".to_string(),
+                ));
+            };
+            // the counts and the sums of the parts add up
+            for (idx, value) in other.iter().enumerate() {
+                if let Some(acc) = list.get_mut(idx as i64) {
+                    *acc += value;
+                }
+            }
+            Ok(())
+        })
     }
 
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
@@ -453,7 +1112,12 @@ impl AggregateFunction for Avg {
 pub(crate) struct Count {}
 
 impl AggregateFunction for Count {
-    fn next_value(&self, _: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        _: &Value,
+        _args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         match state {
             None => *state = Some(Value::from(1)),
             Some(Value::Integer(i)) => {
@@ -461,6 +1125,18 @@ impl AggregateFunction for Count {
             }
             _ => unreachable!(),
         };
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |count, other| {
+            *count += &other;
+            Ok(())
+        })
     }
 
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
@@ -473,7 +1149,12 @@ impl AggregateFunction for Count {
 pub(crate) struct Max {}
 
 impl AggregateFunction for Max {
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        _args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         match state {
             None => *state = Some(input_value.clone()),
             Some(max) => {
@@ -482,6 +1163,20 @@ impl AggregateFunction for Max {
                 }
             }
         };
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |max, other| {
+            if *max < other {
+                *max = other;
+            }
+            Ok(())
+        })
     }
 
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
@@ -494,7 +1189,12 @@ impl AggregateFunction for Max {
 pub(crate) struct Min {}
 
 impl AggregateFunction for Min {
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        _args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         match state {
             None => *state = Some(input_value.clone()),
             Some(min) => {
@@ -503,6 +1203,20 @@ impl AggregateFunction for Min {
                 }
             }
         };
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |min, other| {
+            if *min > other {
+                *min = other;
+            }
+            Ok(())
+        })
     }
 
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
@@ -515,11 +1229,28 @@ impl AggregateFunction for Min {
 pub(crate) struct Sum {}
 
 impl AggregateFunction for Sum {
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        _args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         match state {
             None => *state = Some(input_value.clone()),
             Some(ref mut sum) => *sum += input_value,
         };
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |sum, other| {
+            *sum += &other;
+            Ok(())
+        })
     }
 
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
@@ -532,7 +1263,12 @@ impl AggregateFunction for Sum {
 pub(crate) struct Any {}
 
 impl AggregateFunction for Any {
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        _args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         match state {
             None => {
                 *state = Some(match input_value {
@@ -547,6 +1283,21 @@ impl AggregateFunction for Any {
                 }
             }
         };
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |acc, other| {
+            *acc = match (&acc, other) {
+                (Boolean(acc), Boolean(other)) => Boolean(*acc || other),
+                _ => Missing,
+            };
+            Ok(())
+        })
     }
 
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
@@ -559,7 +1310,12 @@ impl AggregateFunction for Any {
 pub(crate) struct Every {}
 
 impl AggregateFunction for Every {
-    fn next_value(&self, input_value: &Value, state: &mut Option<Value>) {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        _args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
         match state {
             None => {
                 *state = Some(match input_value {
@@ -574,6 +1330,21 @@ impl AggregateFunction for Every {
                 }
             }
         };
+        Ok(())
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |acc, other| {
+            *acc = match (&acc, other) {
+                (Boolean(acc), Boolean(other)) => Boolean(*acc && other),
+                _ => Missing,
+            };
+            Ok(())
+        })
     }
 
     fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
@@ -581,6 +1352,39 @@ impl AggregateFunction for Every {
     }
 }
 
+/// Represents an aggregation function registered in the catalog by an extension
+#[derive(Debug)]
+pub(crate) struct CatalogAggregate {
+    pub(crate) expr: Box<dyn AggregateFnExpr>,
+}
+
+impl AggregateFunction for CatalogAggregate {
+    fn next_value(
+        &self,
+        input_value: &Value,
+        args: &[Value],
+        state: &mut Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        let state = state.get_or_insert_with(|| self.expr.init());
+        Ok(self.expr.accumulate(state, input_value, args)?)
+    }
+
+    fn merge(
+        &self,
+        state: &mut Option<Value>,
+        other: Option<Value>,
+    ) -> Result<(), EvaluationError> {
+        merge_state(state, other, |state, other| {
+            Ok(self.expr.merge(state, other)?)
+        })
+    }
+
+    fn finalize(&self, state: Option<Value>) -> Result<Value, EvaluationError> {
+        let state = state.unwrap_or_else(|| self.expr.init());
+        Ok(self.expr.finalize(state)?)
+    }
+}
+
 /// Represents an evaluation `GROUP BY` operator. For `GROUP BY` operational semantics, see section
 /// `11` of
 /// [PartiQL Specification — August 1, 2019](https://partiql.org/assets/PartiQL-Specification.pdf).
@@ -593,6 +1397,7 @@ pub(crate) struct EvalGroupBy {
     pub(crate) aggs: Vec<AggregateExpression>,
     pub(crate) distinct_aggs: Vec<AggregateExpression>,
     pub(crate) group_as_alias: Option<String>,
+    pub(crate) parallelism: Parallelism,
 }
 
 type GroupKey = Vec<Value>;
@@ -625,9 +1430,15 @@ impl EvalGroupBy {
             aggs,
             distinct_aggs,
             group_as_alias,
+            parallelism: Parallelism::default(),
         }
     }
 
+    pub(crate) fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
     #[inline]
     fn group_key<'a, 'c>(
         &'a self,
@@ -639,105 +1450,236 @@ impl EvalGroupBy {
     {
         self.group
             .iter()
-            .map(|expr| match expr.evaluate(bindings, ctx).as_ref() {
-                Missing => Value::Null,
-                val => val.clone(),
-            })
+            .map(
+                |expr| match (&self.strategy, expr.evaluate(bindings, ctx).as_ref()) {
+                    // `GROUP ALL` coalesces `MISSING` grouping keys with `NULL`; `GROUP PARTIAL` keeps
+                    // them distinct
+                    (EvalGroupingStrategy::GroupFull, Missing) => Value::Null,
+                    (_, val) => val.clone(),
+                },
+            )
             .collect()
     }
 }
 
+impl EvalGroupBy {
+    /// Aggregates the `rows`, each paired with its index in the input and its group key, into
+    /// the state of each group, along with the index of the group's first row.
+    fn aggregate(
+        &self,
+        rows: impl IntoIterator<Item = (usize, GroupKey, Value)>,
+        ctx: &dyn EvalContext,
+    ) -> FxHashMap<GroupKey, (usize, CombinedState)> {
+        let mut grouped: FxHashMap<GroupKey, (usize, CombinedState)> = FxHashMap::default();
+        let state = std::iter::repeat_n(None, self.aggs.len()).collect_vec();
+        let distinct_state = std::iter::repeat_with(|| (None, FxHashMap::default()))
+            .take(self.distinct_aggs.len())
+            .collect_vec();
+        let group_as = self.group_as_alias.as_ref().map(|_| vec![]);
+
+        let combined = CombinedState(state, distinct_state, group_as);
+
+        for (idx, group_key, v) in rows {
+            let v_as_tuple = v.as_datum_tuple_ref();
+            let (_, CombinedState(state, distinct_state, group_as)) = grouped
+                .entry(group_key)
+                .or_insert_with(|| (idx, combined.clone()));
+
+            // Compute next aggregation result for each of the aggregation expressions
+            for (agg_expr, state) in self.aggs.iter().zip(state.iter_mut()) {
+                let evaluated = agg_expr.expr.evaluate(&v_as_tuple, ctx);
+                let args = agg_expr.eval_args(&v_as_tuple, ctx);
+                if let Err(err) = agg_expr.next_value(evaluated.as_ref(), &args, state) {
+                    ctx.add_error(err);
+                }
+            }
+
+            // Compute next aggregation result for each of the distinct aggregation expressions
+            for (distinct_expr, (state, seen)) in
+                self.distinct_aggs.iter().zip(distinct_state.iter_mut())
+            {
+                let evaluated = distinct_expr.expr.evaluate(&v_as_tuple, ctx);
+                let args = distinct_expr.eval_args(&v_as_tuple, ctx);
+                if let Err(err) =
+                    distinct_expr.next_distinct(evaluated.as_ref(), &args, state, seen)
+                {
+                    ctx.add_error(err);
+                }
+            }
+
+            // Add tuple to `GROUP AS` if applicable
+            if let Some(ref mut tuples) = group_as {
+                tuples.push(Value::from(v.coerce_into_tuple()));
+            }
+        }
+        grouped
+    }
+
+    /// Aggregates `rows` across threads, or returns them as the error if they are to be
+    /// aggregated sequentially.
+    ///
+    /// The rows are split into contiguous partitions, each of which is aggregated on its own
+    /// thread; the partial states of each group are then merged in order of the partitions.
+    /// `DISTINCT` aggregates, whose partial states cannot be merged, instead have the rows
+    /// hash-partitioned by their group keys, so that each group is aggregated in full, and in
+    /// order of its rows, by a single thread.
+    fn aggregate_parallel(
+        &self,
+        rows: Vec<Value>,
+        ctx: &dyn EvalContext,
+    ) -> Result<FxHashMap<GroupKey, (usize, CombinedState)>, Vec<Value>> {
+        if !self.distinct_aggs.is_empty() {
+            return self.aggregate_by_group(rows, ctx);
+        }
+
+        let rows = rows.into_iter().enumerate().collect_vec();
+        let partials = self
+            .parallelism
+            .map_partitions(rows, ctx, |rows, ctx| {
+                let rows = rows.into_iter().map(|(idx, v)| {
+                    let group_key = self.group_key(&v.as_datum_tuple_ref(), ctx);
+                    (idx, group_key, v)
+                });
+                self.aggregate(rows, ctx)
+            })
+            .map_err(|rows| rows.into_iter().map(|(_, v)| v).collect_vec())?;
+
+        let mut partials = partials.into_iter();
+        let mut grouped = partials.next().unwrap_or_default();
+        for partial in partials {
+            for (group_key, (idx, state)) in partial {
+                match grouped.entry(group_key) {
+                    Entry::Occupied(mut entry) => {
+                        let (_, CombinedState(agg_state, _, group_as)) = entry.get_mut();
+                        let CombinedState(other_state, _, other_group_as) = state;
+                        for ((agg_expr, state), other) in
+                            self.aggs.iter().zip(agg_state.iter_mut()).zip(other_state)
+                        {
+                            if let Err(err) = agg_expr.merge(state, other) {
+                                ctx.add_error(err);
+                            }
+                        }
+                        if let (Some(tuples), Some(other)) = (group_as, other_group_as) {
+                            tuples.extend(other);
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert((idx, state));
+                    }
+                }
+            }
+        }
+        Ok(grouped)
+    }
+
+    /// Aggregates `rows` with the rows hash-partitioned by their group keys across threads, so
+    /// that each group is aggregated in full, and in order of its rows, by a single thread. If
+    /// `rows` are to be aggregated sequentially, they are returned as the error.
+    fn aggregate_by_group(
+        &self,
+        rows: Vec<Value>,
+        ctx: &dyn EvalContext,
+    ) -> Result<FxHashMap<GroupKey, (usize, CombinedState)>, Vec<Value>> {
+        let keyed = self.parallelism.map_partitions(rows, ctx, |rows, ctx| {
+            rows.into_iter()
+                .map(|v| (self.group_key(&v.as_datum_tuple_ref(), ctx), v))
+                .collect_vec()
+        })?;
+        let keyed = keyed.into_iter().flatten().collect_vec();
+        let num_rows = keyed.len();
+        let keyed = keyed.into_iter().enumerate();
+
+        let Some(contexts) = self.parallelism.partition_contexts(num_rows, ctx) else {
+            return Ok(self.aggregate(keyed.map(|(idx, (key, v))| (idx, key, v)), ctx));
+        };
+        let mut partitions = std::iter::repeat_with(Vec::new)
+            .take(contexts.len())
+            .collect_vec();
+        let num_partitions = partitions.len() as u64;
+        for (idx, (key, v)) in keyed {
+            let partition = FxBuildHasher.hash_one(&key) % num_partitions;
+            partitions[partition as usize].push((idx, key, v));
+        }
+
+        let partitioned = evaluate_partitions(partitions, contexts, ctx, |rows, ctx| {
+            self.aggregate(rows, ctx)
+        });
+        Ok(partitioned.into_iter().flatten().collect())
+    }
+}
+
 impl Evaluable for EvalGroupBy {
     fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
         let group_as_alias = &self.group_as_alias;
         let input_value = take_input!(inputs[0].take(), ctx);
 
-        match self.strategy {
-            EvalGroupingStrategy::GroupPartial => {
-                ctx.add_error(EvaluationError::NotYetImplemented(
-                    "GROUP PARTIAL".to_string(),
-                ));
-                Missing
-            }
-            EvalGroupingStrategy::GroupFull => {
-                let mut grouped: FxHashMap<GroupKey, CombinedState> = FxHashMap::default();
-                let state = std::iter::repeat_n(None, self.aggs.len()).collect_vec();
-                let distinct_state = std::iter::repeat_with(|| (None, FxHashMap::default()))
-                    .take(self.distinct_aggs.len())
-                    .collect_vec();
-                let group_as = group_as_alias.as_ref().map(|_| vec![]);
-
-                let combined = CombinedState(state, distinct_state, group_as);
-
-                for v in input_value {
-                    let v_as_tuple = v.as_datum_tuple_ref();
-                    let group_key = self.group_key(&v_as_tuple, ctx);
-                    let CombinedState(state, distinct_state, group_as) =
-                        grouped.entry(group_key).or_insert_with(|| combined.clone());
-
-                    // Compute next aggregation result for each of the aggregation expressions
-                    for (agg_expr, state) in self.aggs.iter().zip(state.iter_mut()) {
-                        let evaluated = agg_expr.expr.evaluate(&v_as_tuple, ctx);
-                        agg_expr.next_value(evaluated.as_ref(), state);
-                    }
+        let aggregate_sequential = |rows: Vec<Value>| {
+            let rows = rows.into_iter().enumerate().map(|(idx, v)| {
+                let group_key = self.group_key(&v.as_datum_tuple_ref(), ctx);
+                (idx, group_key, v)
+            });
+            self.aggregate(rows, ctx)
+        };
+        let rows = input_value.into_iter().collect_vec();
+        let grouped = if self.parallelism.is_parallel() {
+            self.aggregate_parallel(rows, ctx)
+                .unwrap_or_else(aggregate_sequential)
+        } else {
+            aggregate_sequential(rows)
+        };
 
-                    // Compute next aggregation result for each of the distinct aggregation expressions
-                    for (distinct_expr, (state, seen)) in
-                        self.distinct_aggs.iter().zip(distinct_state.iter_mut())
-                    {
-                        let evaluated = distinct_expr.expr.evaluate(&v_as_tuple, ctx);
-                        distinct_expr.next_distinct(evaluated.as_ref(), state, seen);
-                    }
+        // groups are output in order of their first rows, however the rows were aggregated
+        let mut grouped = grouped.into_iter().collect_vec();
+        grouped.sort_unstable_by_key(|(_, (idx, _))| *idx);
 
-                    // Add tuple to `GROUP AS` if applicable
-                    if let Some(ref mut tuples) = group_as {
-                        tuples.push(Value::from(v.coerce_into_tuple()));
-                    }
-                }
+        let vals = grouped
+            .into_iter()
+            .map(|(group_key, (_, state))| {
+                let CombinedState(agg_state, distinct_state, group_as) = state;
+                // under `GROUP PARTIAL`, `MISSING` grouping keys are omitted from the output
+                let group = self
+                    .aliases
+                    .iter()
+                    .cloned()
+                    .zip(group_key)
+                    .filter(|(_, key)| !key.is_missing());
+
+                // finalize all aggregates
+                let aggs_with_state = self.aggs.iter().zip(agg_state);
+                let daggs_with_state = self
+                    .distinct_aggs
+                    .iter()
+                    .zip(distinct_state.into_iter().map(|(state, _)| state));
+                let agg_data =
+                    aggs_with_state
+                        .chain(daggs_with_state)
+                        .map(|(aggregate_expr, state)| {
+                            let val = match aggregate_expr.finalize(state) {
+                                Ok(agg_result) => agg_result,
+                                Err(err) => {
+                                    ctx.add_error(err);
+                                    Missing
+                                }
+                            };
 
-                let vals = grouped
-                    .into_iter()
-                    .map(|(group_key, state)| {
-                        let CombinedState(agg_state, distinct_state, group_as) = state;
-                        let group = self.aliases.iter().cloned().zip(group_key);
+                            (aggregate_expr.name.to_string(), val)
+                        });
 
-                        // finalize all aggregates
-                        let aggs_with_state = self.aggs.iter().zip(agg_state);
-                        let daggs_with_state = self
-                            .distinct_aggs
-                            .iter()
-                            .zip(distinct_state.into_iter().map(|(state, _)| state));
-                        let agg_data = aggs_with_state.chain(daggs_with_state).map(
-                            |(aggregate_expr, state)| {
-                                let val = match aggregate_expr.finalize(state) {
-                                    Ok(agg_result) => agg_result,
-                                    Err(err) => {
-                                        ctx.add_error(err);
-                                        Missing
-                                    }
-                                };
-
-                                (aggregate_expr.name.to_string(), val)
-                            },
-                        );
-
-                        let mut tuple = Tuple::from_iter(group.chain(agg_data));
-
-                        // insert `GROUP AS` if applicable
-                        if let Some(tuples) = group_as {
-                            tuple.insert(
-                                group_as_alias.as_ref().unwrap(),
-                                Value::from(Bag::from(tuples)),
-                            );
-                        }
+                let mut tuple = Tuple::from_iter(group.chain(agg_data));
+
+                // insert `GROUP AS` if applicable
+                if let Some(tuples) = group_as {
+                    tuple.insert(
+                        group_as_alias.as_ref().unwrap(),
+                        Value::from(Bag::from(tuples)),
+                    );
+                }
 
-                        Value::from(tuple)
-                    })
-                    .collect_vec();
+                Value::from(tuple)
+            })
+            .collect_vec();
 
-                Value::from(Bag::from(vals))
-            }
-        }
+        Value::from(Bag::from(vals))
     }
 }
 
@@ -785,6 +1727,7 @@ pub(crate) struct EvalUnpivot {
     pub(crate) expr: Box<dyn EvalExpr>,
     pub(crate) as_key: String,
     pub(crate) at_key: Option<String>,
+    pub(crate) parallelism: Parallelism,
 
     // cached values
     attrs: Vec<String>,
@@ -802,10 +1745,16 @@ impl EvalUnpivot {
             expr,
             as_key: as_key.to_string(),
             at_key,
+            parallelism: Parallelism::default(),
 
             attrs,
         }
     }
+
+    pub(crate) fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
 }
 
 impl Evaluable for EvalUnpivot {
@@ -817,7 +1766,20 @@ impl Evaluable for EvalUnpivot {
 
         let as_key = self.as_key.as_str();
         let pairs = tuple;
-        let unpivoted = if let Some(at_key) = &self.at_key {
+        let unpivoted = if self.parallelism.is_parallel() {
+            Bag::from(filter_map_values(
+                self.parallelism,
+                pairs,
+                ctx,
+                |(k, v), _| {
+                    let mut out = Tuple::from([(as_key, v)]);
+                    if let Some(at_key) = &self.at_key {
+                        out.insert(at_key, k.into());
+                    }
+                    Some(Value::from(out))
+                },
+            ))
+        } else if let Some(at_key) = &self.at_key {
             pairs
                 .map(|(k, v)| Tuple::from([(as_key, v), (at_key.as_str(), k.into())]))
                 .collect::<Bag>()
@@ -829,6 +1791,30 @@ impl Evaluable for EvalUnpivot {
         Value::from(unpivoted)
     }
 
+    fn evaluate_iter<'a>(
+        &'a self,
+        _inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        // The unpivoted value is evaluated once its first attribute is pulled
+        let pairs = std::iter::once_with(move || {
+            match self.expr.evaluate(&DatumTupleRef::Empty, ctx).into_owned() {
+                Value::Tuple(tuple) => *tuple,
+                other => other.coerce_into_tuple(),
+            }
+        })
+        .flatten();
+
+        let as_key = self.as_key.as_str();
+        Box::new(pairs.map(move |(k, v)| {
+            let mut out = Tuple::from([(as_key, v)]);
+            if let Some(at_key) = &self.at_key {
+                out.insert(at_key, k.into());
+            }
+            Value::from(out)
+        }))
+    }
+
     fn get_vars(&self) -> Option<&[String]> {
         Some(&self.attrs)
     }
@@ -840,11 +1826,20 @@ impl Evaluable for EvalUnpivot {
 #[derive(Debug)]
 pub(crate) struct EvalFilter {
     pub(crate) expr: Box<dyn EvalExpr>,
+    pub(crate) parallelism: Parallelism,
 }
 
 impl EvalFilter {
     pub(crate) fn new(expr: Box<dyn EvalExpr>) -> Self {
-        EvalFilter { expr }
+        EvalFilter {
+            expr,
+            parallelism: Parallelism::default(),
+        }
+    }
+
+    pub(crate) fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
     }
 
     #[inline]
@@ -871,10 +1866,69 @@ impl Evaluable for EvalFilter {
     fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
         let input_value = take_input!(inputs[0].take(), ctx);
 
-        let filtered = input_value
-            .into_iter()
-            .filter(|v| self.eval_filter(&v.as_datum_tuple_ref(), ctx));
-        Value::from(filtered.collect::<Bag>())
+        let filtered = filter_map_values(self.parallelism, input_value, ctx, |v, ctx| {
+            self.eval_filter(&v.as_datum_tuple_ref(), ctx).then_some(v)
+        });
+        Value::from(Bag::from(filtered))
+    }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        Box::new(input_values.filter(move |v| self.eval_filter(&v.as_datum_tuple_ref(), ctx)))
+    }
+}
+
+/// Represents an evaluation `Let` operator; for an input bag of binding tuples the `Let` operator
+/// extends each binding tuple with the value of each of `bindings`' expressions under its name,
+/// e.g. `b` in `LET t.a * 2 AS b`. The bindings are evaluated in order, each in the binding tuple
+/// extended by the preceding ones.
+#[derive(Debug)]
+pub(crate) struct EvalLet {
+    pub(crate) bindings: Vec<(String, Box<dyn EvalExpr>)>,
+}
+
+impl EvalLet {
+    pub(crate) fn new(bindings: Vec<(String, Box<dyn EvalExpr>)>) -> Self {
+        EvalLet { bindings }
+    }
+
+    #[inline]
+    fn extend(&self, binding: Value, ctx: &dyn EvalContext) -> Value {
+        let mut tuple = binding.coerce_into_tuple();
+        for (name, expr) in &self.bindings {
+            let value = {
+                let tuple_ref = DatumTupleRef::Tuple(&tuple);
+                expr.evaluate(&tuple_ref, ctx).into_owned()
+            };
+            tuple.insert(name, value);
+        }
+        Value::from(tuple)
+    }
+}
+
+impl Evaluable for EvalLet {
+    fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        let input_value = take_input!(inputs[0].take(), ctx);
+        let ordered = input_value.is_ordered();
+
+        let values = input_value.into_iter().map(|v| self.extend(v, ctx));
+        match ordered {
+            true => Value::from(values.collect::<List>()),
+            false => Value::from(values.collect::<Bag>()),
+        }
+    }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        Box::new(input_values.map(move |v| self.extend(v, ctx)))
     }
 }
 
@@ -942,6 +1996,7 @@ pub(crate) enum EvalOrderBySortSpec {
 #[derive(Debug)]
 pub(crate) struct EvalOrderBy {
     pub(crate) cmp: Vec<EvalOrderBySortCondition>,
+    pub(crate) parallelism: Parallelism,
 }
 
 impl EvalOrderBy {
@@ -981,6 +2036,52 @@ impl EvalOrderBy {
             .find_or_last(|o| o != &Ordering::Equal)
             .unwrap_or(Ordering::Equal)
     }
+
+    /// Sorts contiguous partitions of `values` across threads and merges the sorted partitions;
+    /// as the merge favors the earlier of equal values, the sort is stable, as is the sequential
+    /// sort. If `values` are to be sorted sequentially, they are returned as the error.
+    fn sort_parallel(
+        &self,
+        values: Vec<Value>,
+        ctx: &dyn EvalContext,
+    ) -> Result<Vec<Value>, Vec<Value>> {
+        let mut runs = self
+            .parallelism
+            .map_partitions(values, ctx, |mut run, ctx| {
+                run.sort_by(|l, r| self.compare(l, r, ctx));
+                run
+            })?;
+        while runs.len() > 1 {
+            let mut merged = Vec::with_capacity(runs.len().div_ceil(2));
+            let mut pairs = runs.into_iter();
+            while let Some(left) = pairs.next() {
+                merged.push(match pairs.next() {
+                    Some(right) => self.merge(left, right, ctx),
+                    None => left,
+                });
+            }
+            runs = merged;
+        }
+        Ok(runs.pop().unwrap_or_default())
+    }
+
+    /// Merges the sorted runs `left` and `right`, taking from `left` first among equal values.
+    fn merge(&self, left: Vec<Value>, right: Vec<Value>, ctx: &dyn EvalContext) -> Vec<Value> {
+        let mut merged = Vec::with_capacity(left.len() + right.len());
+        let mut left = left.into_iter().peekable();
+        let mut right = right.into_iter().peekable();
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            let next = if self.compare(l, r, ctx) == Ordering::Greater {
+                right.next()
+            } else {
+                left.next()
+            };
+            merged.extend(next);
+        }
+        merged.extend(left);
+        merged.extend(right);
+        merged
+    }
 }
 
 impl Evaluable for EvalOrderBy {
@@ -990,80 +2091,455 @@ impl Evaluable for EvalOrderBy {
         let values: DatumLowerResult<Vec<_>> =
             input_value.into_iter().map(|v| v.into_lower()).collect();
         // TODO handle lowering error
-        let mut values = values.expect("lower");
-        values.sort_by(|l, r| self.compare(l, r, ctx));
+        let values = values.expect("lower");
+        let values = if self.parallelism.is_parallel() {
+            self.sort_parallel(values, ctx)
+                .unwrap_or_else(|mut values| {
+                    values.sort_by(|l, r| self.compare(l, r, ctx));
+                    values
+                })
+        } else {
+            let mut values = values;
+            values.sort_by(|l, r| self.compare(l, r, ctx));
+            values
+        };
         Value::from(List::from(values))
     }
 }
 
-/// Represents an evaluation `LIMIT` and/or `OFFSET` operator.
+/// Represents an evaluation `Window` operator; e.g. `RANK() OVER (PARTITION BY a ORDER BY b)` in
+/// `SELECT RANK() OVER (PARTITION BY a ORDER BY b) FROM t`. Adds the result of each window function
+/// to each of the input binding tuples.
 #[derive(Debug)]
-pub(crate) struct EvalLimitOffset {
-    pub(crate) limit: Option<Box<dyn EvalExpr>>,
-    pub(crate) offset: Option<Box<dyn EvalExpr>>,
+pub(crate) struct EvalWindow {
+    pub(crate) exprs: Vec<EvalWindowExpr>,
 }
 
-impl Evaluable for EvalLimitOffset {
-    fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
-        let input_value = take_input!(inputs[0].take(), ctx);
+/// A window function call with its arguments and window specification.
+#[derive(Debug)]
+pub(crate) struct EvalWindowExpr {
+    pub(crate) name: String,
+    pub(crate) func: EvalWindowFunc,
+    pub(crate) args: Vec<Box<dyn EvalExpr>>,
+    pub(crate) partition_by: Vec<Box<dyn EvalExpr>>,
+    pub(crate) order_by: EvalOrderBy,
+    pub(crate) frame: EvalWindowFrame,
+}
 
-        let empty_bindings = DatumTupleRef::Empty;
+#[derive(Debug)]
+pub(crate) enum EvalWindowFunc {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    Agg {
+        func: Box<dyn AggregateFunction>,
+        distinct: bool,
+    },
+}
 
-        let offset = match &self.offset {
-            None => 0,
-            Some(expr) => match expr.evaluate(&empty_bindings, ctx).as_ref() {
-                Value::Integer(i) => {
-                    if *i >= 0 {
-                        *i as usize
-                    } else {
-                        0
-                    }
-                }
-                _ => 0,
-            },
-        };
+#[derive(Debug)]
+pub(crate) struct EvalWindowFrame {
+    pub(crate) units: EvalWindowFrameUnits,
+    pub(crate) start: EvalWindowFrameBound,
+    pub(crate) end: EvalWindowFrameBound,
+}
 
-        let limit = match &self.limit {
-            None => None,
-            Some(expr) => match expr.evaluate(&empty_bindings, ctx).as_ref() {
-                Value::Integer(i) => {
-                    if *i >= 0 {
-                        Some(*i as usize)
-                    } else {
-                        None
-                    }
-                }
-                _ => None,
-            },
-        };
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum EvalWindowFrameUnits {
+    Rows,
+    Range,
+}
 
-        let ordered = input_value.is_ordered();
-        fn collect(values: impl Iterator<Item = Value>, ordered: bool) -> Value {
-            match ordered {
-                true => Value::from(values.collect::<List>()),
-                false => Value::from(values.collect::<Bag>()),
-            }
+#[derive(Debug)]
+pub(crate) enum EvalWindowFrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+/// The rows of a sorted window partition, along with the bounds of each row's peers (i.e., the rows
+/// that are equal according to the window's `ORDER BY`).
+struct WindowPartition<'a> {
+    rows: Vec<&'a Value>,
+    peers: Vec<(usize, usize)>,
+    peer_groups: Vec<usize>,
+}
+
+impl EvalWindowExpr {
+    fn evaluate(&self, rows: &[Value], ctx: &dyn EvalContext) -> Vec<Value> {
+        let mut partitions: FxHashMap<Vec<Value>, Vec<usize>> = FxHashMap::default();
+        for (idx, row) in rows.iter().enumerate() {
+            let row = row.as_datum_tuple_ref();
+            let key = self
+                .partition_by
+                .iter()
+                .map(|expr| expr.evaluate(&row, ctx).into_owned())
+                .collect_vec();
+            partitions.entry(key).or_default().push(idx);
         }
 
-        let offsetted = input_value.into_iter().skip(offset);
-        match limit {
-            Some(n) => collect(offsetted.take(n), ordered),
-            None => collect(offsetted, ordered),
+        let mut results = vec![Missing; rows.len()];
+        for (_, mut indices) in partitions {
+            indices.sort_by(|l, r| self.order_by.compare(&rows[*l], &rows[*r], ctx));
+            let partition = self.partition(indices.iter().map(|idx| &rows[*idx]).collect(), ctx);
+            for (pos, idx) in indices.into_iter().enumerate() {
+                results[idx] = self.evaluate_row(&partition, pos, ctx);
+            }
+        }
+        results
+    }
+
+    fn partition<'a>(&self, rows: Vec<&'a Value>, ctx: &dyn EvalContext) -> WindowPartition<'a> {
+        let mut peers = Vec::with_capacity(rows.len());
+        let mut peer_groups = Vec::with_capacity(rows.len());
+        let mut start = 0;
+        for pos in 1..=rows.len() {
+            let is_peer = pos < rows.len()
+                && self.order_by.compare(rows[start], rows[pos], ctx) == Ordering::Equal;
+            if !is_peer {
+                let group = peer_groups.last().map_or(0, |g| g + 1);
+                for _ in start..pos {
+                    peers.push((start, pos - 1));
+                    peer_groups.push(group);
+                }
+                start = pos;
+            }
+        }
+        WindowPartition {
+            rows,
+            peers,
+            peer_groups,
         }
     }
-}
 
-/// Represents an evaluation `SelectValue` operator; `SelectValue` implements `PartiQL` Core's
-/// `SELECT VALUE` clause semantics. For `SelectValue` operational semantics, see section `6.1` of
-/// [PartiQL Specification — August 1, 2019](https://partiql.org/assets/PartiQL-Specification.pdf).
-#[derive(Debug)]
-pub(crate) struct EvalSelectValue {
-    pub(crate) expr: Box<dyn EvalExpr>,
-}
+    #[inline]
+    fn arg(&self, idx: usize, row: &Value, ctx: &dyn EvalContext) -> Option<Value> {
+        self.args
+            .get(idx)
+            .map(|arg| arg.evaluate(&row.as_datum_tuple_ref(), ctx).into_owned())
+    }
+
+    fn evaluate_row(
+        &self,
+        partition: &WindowPartition<'_>,
+        pos: usize,
+        ctx: &dyn EvalContext,
+    ) -> Value {
+        let row = partition.rows[pos];
+        match &self.func {
+            EvalWindowFunc::RowNumber => Value::from(pos as i64 + 1),
+            EvalWindowFunc::Rank => Value::from(partition.peers[pos].0 as i64 + 1),
+            EvalWindowFunc::DenseRank => Value::from(partition.peer_groups[pos] as i64 + 1),
+            EvalWindowFunc::Lag | EvalWindowFunc::Lead => {
+                let offset = match self.arg(1, row, ctx).unwrap_or(Value::from(1)) {
+                    Value::Integer(n) if n >= 0 => n as usize,
+                    _ => {
+                        ctx.add_error(EvaluationError::IllegalState(
+                            "LAG/LEAD offset must be a non-negative integer".to_string(),
+                        ));
+                        return Missing;
+                    }
+                };
+                let target = if matches!(self.func, EvalWindowFunc::Lag) {
+                    pos.checked_sub(offset)
+                } else {
+                    pos.checked_add(offset)
+                };
+                match target.and_then(|target| partition.rows.get(target)) {
+                    Some(target) => self.arg(0, target, ctx).unwrap_or(Null),
+                    None => self.arg(2, row, ctx).unwrap_or(Null),
+                }
+            }
+            EvalWindowFunc::FirstValue => self
+                .frame(partition, pos, ctx)
+                .and_then(|(lo, _)| self.arg(0, partition.rows[lo], ctx))
+                .unwrap_or(Null),
+            EvalWindowFunc::LastValue => self
+                .frame(partition, pos, ctx)
+                .and_then(|(_, hi)| self.arg(0, partition.rows[hi], ctx))
+                .unwrap_or(Null),
+            EvalWindowFunc::Agg { func, distinct } => {
+                let mut state = None;
+                let mut seen = FxHashMap::default();
+                if let Some((lo, hi)) = self.frame(partition, pos, ctx) {
+                    for row in &partition.rows[lo..=hi] {
+                        let value = self.arg(0, row, ctx).unwrap_or(Missing);
+                        if value.is_present() {
+                            // an argument which fails to evaluate keeps its position as `MISSING`
+                            let args = (1..self.args.len())
+                                .map(|idx| self.arg(idx, row, ctx).unwrap_or(Missing))
+                                .collect_vec();
+                            let next = if *distinct {
+                                func.next_distinct(&value, &args, &mut state, &mut seen)
+                            } else {
+                                func.next_value(&value, &args, &mut state)
+                            };
+                            if let Err(err) = next {
+                                ctx.add_error(err);
+                            }
+                        }
+                    }
+                }
+                func.finalize(state).unwrap_or_else(|err| {
+                    ctx.add_error(err);
+                    Missing
+                })
+            }
+        }
+    }
+
+    /// Returns the inclusive bounds of the frame of the row at `pos`, or `None` if the frame is empty.
+    fn frame(
+        &self,
+        partition: &WindowPartition<'_>,
+        pos: usize,
+        ctx: &dyn EvalContext,
+    ) -> Option<(usize, usize)> {
+        let len = partition.rows.len() as i64;
+        let current = pos as i64;
+        let (start, end) = match self.frame.units {
+            EvalWindowFrameUnits::Rows => {
+                let bound = |bound: &EvalWindowFrameBound| match bound {
+                    EvalWindowFrameBound::UnboundedPreceding => 0,
+                    EvalWindowFrameBound::Preceding(n) => current - *n as i64,
+                    EvalWindowFrameBound::CurrentRow => current,
+                    EvalWindowFrameBound::Following(n) => current + *n as i64,
+                    EvalWindowFrameBound::UnboundedFollowing => len - 1,
+                };
+                (bound(&self.frame.start), bound(&self.frame.end))
+            }
+            EvalWindowFrameUnits::Range => {
+                let (first_peer, last_peer) = partition.peers[pos];
+                let start = match &self.frame.start {
+                    EvalWindowFrameBound::UnboundedPreceding => 0,
+                    EvalWindowFrameBound::CurrentRow => first_peer as i64,
+                    EvalWindowFrameBound::UnboundedFollowing => len,
+                    bound => self
+                        .range_offset(partition, pos, bound, true, ctx)
+                        .unwrap_or(first_peer as i64),
+                };
+                let end = match &self.frame.end {
+                    EvalWindowFrameBound::UnboundedPreceding => -1,
+                    EvalWindowFrameBound::CurrentRow => last_peer as i64,
+                    EvalWindowFrameBound::UnboundedFollowing => len - 1,
+                    bound => self
+                        .range_offset(partition, pos, bound, false, ctx)
+                        .unwrap_or(last_peer as i64),
+                };
+                (start, end)
+            }
+        };
+        let (start, end) = (start.max(0), end.min(len - 1));
+        (start <= end).then_some((start as usize, end as usize))
+    }
+
+    /// Returns the first (if `is_start`) or last position of the partition whose `ORDER BY` key is
+    /// within the offset `bound` of the key of the row at `pos`, or `None` if the current row's key
+    /// is not a value to which the offset can be applied.
+    fn range_offset(
+        &self,
+        partition: &WindowPartition<'_>,
+        pos: usize,
+        bound: &EvalWindowFrameBound,
+        is_start: bool,
+        ctx: &dyn EvalContext,
+    ) -> Option<i64> {
+        let spec = self.order_by.cmp.first()?;
+        let key = |row: &Value| {
+            spec.expr
+                .evaluate(&row.as_datum_tuple_ref(), ctx)
+                .into_owned()
+        };
+        let descending = matches!(
+            spec.spec,
+            EvalOrderBySortSpec::DescNullsFirst | EvalOrderBySortSpec::DescNullsLast
+        );
+
+        let current = key(partition.rows[pos]);
+        let offset = match bound {
+            EvalWindowFrameBound::Preceding(n) if descending => Value::from(*n as i64),
+            EvalWindowFrameBound::Preceding(n) => Value::from(-(*n as i64)),
+            EvalWindowFrameBound::Following(n) if descending => Value::from(-(*n as i64)),
+            EvalWindowFrameBound::Following(n) => Value::from(*n as i64),
+            _ => return None,
+        };
+        let target = &current + &offset;
+        if current.is_absent() || target.is_absent() {
+            return None;
+        }
+
+        let in_frame = |row: &Value| {
+            let key = key(row);
+            if key.is_absent() {
+                return false;
+            }
+            let ordering = if descending {
+                target.cmp(&key)
+            } else {
+                key.cmp(&target)
+            };
+            if is_start {
+                ordering != Ordering::Less
+            } else {
+                ordering != Ordering::Greater
+            }
+        };
+        let rows = partition.rows.iter().enumerate();
+        let found = if is_start {
+            rows.clone().find(|(_, row)| in_frame(row))
+        } else {
+            rows.clone().rev().find(|(_, row)| in_frame(row))
+        };
+        Some(found.map_or(
+            if is_start {
+                partition.rows.len() as i64
+            } else {
+                -1
+            },
+            |(pos, _)| pos as i64,
+        ))
+    }
+}
+
+impl Evaluable for EvalWindow {
+    fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        let input_value = take_input!(inputs[0].take(), ctx);
+        let ordered = matches!(input_value, Value::List(_));
+
+        let rows: DatumLowerResult<Vec<_>> =
+            input_value.into_iter().map(|v| v.into_lower()).collect();
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(err) => {
+                ctx.add_error(EvaluationError::IllegalState(format!(
+                    "window input row: {err}"
+                )));
+                return Missing;
+            }
+        };
+
+        let mut results = self
+            .exprs
+            .iter()
+            .map(|expr| expr.evaluate(&rows, ctx))
+            .collect_vec();
+
+        let values = rows.into_iter().enumerate().map(|(idx, row)| {
+            let mut tuple = row.coerce_into_tuple();
+            for (expr, results) in self.exprs.iter().zip(results.iter_mut()) {
+                tuple.insert(&expr.name, std::mem::replace(&mut results[idx], Missing));
+            }
+            Value::from(tuple)
+        });
+        if ordered {
+            Value::from(values.collect::<List>())
+        } else {
+            Value::from(values.collect::<Bag>())
+        }
+    }
+}
+
+/// Represents an evaluation `LIMIT` and/or `OFFSET` operator.
+#[derive(Debug)]
+pub(crate) struct EvalLimitOffset {
+    pub(crate) limit: Option<Box<dyn EvalExpr>>,
+    pub(crate) offset: Option<Box<dyn EvalExpr>>,
+}
+
+impl EvalLimitOffset {
+    /// Evaluates the offset and limit expressions; an absent or invalid offset skips no values and
+    /// an absent or invalid limit takes all values.
+    fn offset_limit(&self, ctx: &dyn EvalContext) -> (usize, Option<usize>) {
+        let empty_bindings = DatumTupleRef::Empty;
+
+        let offset = match &self.offset {
+            None => 0,
+            Some(expr) => match expr.evaluate(&empty_bindings, ctx).as_ref() {
+                Value::Integer(i) if *i >= 0 => *i as usize,
+                _ => 0,
+            },
+        };
+
+        let limit = match &self.limit {
+            None => None,
+            Some(expr) => match expr.evaluate(&empty_bindings, ctx).as_ref() {
+                Value::Integer(i) if *i >= 0 => Some(*i as usize),
+                _ => None,
+            },
+        };
+
+        (offset, limit)
+    }
+}
+
+impl Evaluable for EvalLimitOffset {
+    fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        let input_value = take_input!(inputs[0].take(), ctx);
+        let (offset, limit) = self.offset_limit(ctx);
+
+        let ordered = input_value.is_ordered();
+        fn collect(values: impl Iterator<Item = Value>, ordered: bool) -> Value {
+            match ordered {
+                true => Value::from(values.collect::<List>()),
+                false => Value::from(values.collect::<Bag>()),
+            }
+        }
+
+        let offsetted = input_value.into_iter().skip(offset);
+        match limit {
+            Some(n) => collect(offsetted.take(n), ordered),
+            None => collect(offsetted, ordered),
+        }
+    }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        let (offset, limit) = self.offset_limit(ctx);
+
+        let offsetted = input_values.skip(offset);
+        match limit {
+            Some(n) => Box::new(offsetted.take(n)),
+            None => Box::new(offsetted),
+        }
+    }
+}
+
+/// Represents an evaluation `SelectValue` operator; `SelectValue` implements `PartiQL` Core's
+/// `SELECT VALUE` clause semantics. For `SelectValue` operational semantics, see section `6.1` of
+/// [PartiQL Specification — August 1, 2019](https://partiql.org/assets/PartiQL-Specification.pdf).
+#[derive(Debug)]
+pub(crate) struct EvalSelectValue {
+    pub(crate) expr: Box<dyn EvalExpr>,
+    pub(crate) parallelism: Parallelism,
+}
 
 impl EvalSelectValue {
     pub(crate) fn new(expr: Box<dyn EvalExpr>) -> Self {
-        EvalSelectValue { expr }
+        EvalSelectValue {
+            expr,
+            parallelism: Parallelism::default(),
+        }
+    }
+
+    pub(crate) fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    #[inline]
+    fn select(&self, binding: Value, ctx: &dyn EvalContext) -> Value {
+        let v_as_tuple = binding.as_datum_tuple_ref();
+        self.expr.evaluate(&v_as_tuple, ctx).into_owned()
     }
 }
 
@@ -1073,16 +2549,24 @@ impl Evaluable for EvalSelectValue {
 
         let ordered = input_value.is_ordered();
 
-        let values = input_value.into_iter().map(|v| {
-            let v_as_tuple = v.as_datum_tuple_ref();
-            self.expr.evaluate(&v_as_tuple, ctx).into_owned()
+        let values = filter_map_values(self.parallelism, input_value, ctx, |v, ctx| {
+            Some(self.select(v, ctx))
         });
 
         match ordered {
-            true => Value::from(values.collect::<List>()),
-            false => Value::from(values.collect::<Bag>()),
+            true => Value::from(List::from(values)),
+            false => Value::from(Bag::from(values)),
         }
     }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        Box::new(input_values.map(move |v| self.select(v, ctx)))
+    }
 }
 
 /// Represents an evaluation `Project` operator; for a given bag of input binding tuples as input
@@ -1091,11 +2575,35 @@ impl Evaluable for EvalSelectValue {
 /// [PartiQL Specification — August 1, 2019](https://partiql.org/assets/PartiQL-Specification.pdf).
 pub(crate) struct EvalSelect {
     pub(crate) exprs: Vec<(String, Box<dyn EvalExpr>)>,
+    pub(crate) parallelism: Parallelism,
 }
 
 impl EvalSelect {
     pub(crate) fn new(exprs: Vec<(String, Box<dyn EvalExpr>)>) -> Self {
-        EvalSelect { exprs }
+        EvalSelect {
+            exprs,
+            parallelism: Parallelism::default(),
+        }
+    }
+
+    pub(crate) fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    #[inline]
+    fn select(&self, binding: Value, ctx: &dyn EvalContext) -> Value {
+        let v_as_tuple = binding.as_datum_tuple_ref();
+
+        let tuple_pairs = self.exprs.iter().filter_map(|(alias, expr)| {
+            let evaluated_val = expr.evaluate(&v_as_tuple, ctx);
+            match evaluated_val.as_ref() {
+                Missing => None,
+                _ => Some((alias.as_str(), evaluated_val.into_owned())),
+            }
+        });
+
+        Value::from(tuple_pairs.collect::<Tuple>())
     }
 }
 
@@ -1120,25 +2628,24 @@ impl Evaluable for EvalSelect {
 
         let ordered = input_value.is_ordered();
 
-        let values = input_value.into_iter().map(|v| {
-            let v_as_tuple = v.as_datum_tuple_ref();
-
-            let tuple_pairs = self.exprs.iter().filter_map(|(alias, expr)| {
-                let evaluated_val = expr.evaluate(&v_as_tuple, ctx);
-                match evaluated_val.as_ref() {
-                    Missing => None,
-                    _ => Some((alias.as_str(), evaluated_val.into_owned())),
-                }
-            });
-
-            tuple_pairs.collect::<Tuple>()
+        let values = filter_map_values(self.parallelism, input_value, ctx, |v, ctx| {
+            Some(self.select(v, ctx))
         });
 
         match ordered {
-            true => Value::from(values.collect::<List>()),
-            false => Value::from(values.collect::<Bag>()),
+            true => Value::from(List::from(values)),
+            false => Value::from(Bag::from(values)),
         }
     }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        Box::new(input_values.map(move |v| self.select(v, ctx)))
+    }
 }
 
 /// Represents an evaluation `ProjectAll` operator; `ProjectAll` implements SQL's `SELECT *`
@@ -1146,11 +2653,72 @@ impl Evaluable for EvalSelect {
 #[derive(Debug, Default)]
 pub(crate) struct EvalSelectAll {
     pub(crate) passthrough: bool,
+    /// The join columns of `USING` and `NATURAL` joins, which are projected once each.
+    pub(crate) join_columns: Vec<String>,
+    pub(crate) parallelism: Parallelism,
 }
 
 impl EvalSelectAll {
     pub(crate) fn new(passthrough: bool) -> Self {
-        Self { passthrough }
+        Self {
+            passthrough,
+            join_columns: vec![],
+            parallelism: Parallelism::default(),
+        }
+    }
+
+    pub(crate) fn with_join_columns(mut self, join_columns: Vec<String>) -> Self {
+        self.join_columns = join_columns;
+        self
+    }
+
+    #[inline]
+    fn is_join_column(&self, name: &str) -> bool {
+        self.join_columns
+            .iter()
+            .any(|column| unicase::eq(column.as_str(), name))
+    }
+
+    pub(crate) fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    #[inline]
+    fn select(&self, binding: Value) -> Value {
+        if self.passthrough {
+            Value::from(binding.coerce_into_tuple())
+        } else if self.join_columns.is_empty() {
+            let tuple = binding
+                .coerce_into_tuple()
+                .into_values()
+                .flat_map(|v| v.coerce_into_tuple().into_pairs())
+                .collect::<Tuple>();
+            Value::from(tuple)
+        } else {
+            // The join columns come first, followed by the other attributes of the variables
+            let (columns, vars): (Vec<_>, Vec<_>) = binding
+                .coerce_into_tuple()
+                .into_pairs()
+                .partition(|(name, _)| self.is_join_column(name));
+            let columns = self.join_columns.iter().filter_map(|column| {
+                columns
+                    .iter()
+                    .rev()
+                    .find(|(name, _)| unicase::eq(name.as_str(), column.as_str()))
+                    .map(|(name, value)| (name.clone(), value.clone()))
+            });
+            // the variables of the side an outer join pads have no attributes
+            let attrs = vars
+                .into_iter()
+                .filter(|(_, v)| !v.is_absent())
+                .flat_map(|(_, v)| {
+                    v.coerce_into_tuple()
+                        .into_pairs()
+                        .filter(|(name, _)| !self.is_join_column(name))
+                });
+            Value::from(columns.chain(attrs).collect::<Tuple>())
+        }
     }
 }
 
@@ -1160,22 +2728,24 @@ impl Evaluable for EvalSelectAll {
 
         let ordered = input_value.is_ordered();
 
-        let values = input_value.into_iter().map(|val| {
-            if self.passthrough {
-                val.coerce_into_tuple()
-            } else {
-                val.coerce_into_tuple()
-                    .into_values()
-                    .flat_map(|v| v.coerce_into_tuple().into_pairs())
-                    .collect::<Tuple>()
-            }
+        let values = filter_map_values(self.parallelism, input_value, ctx, |v, _| {
+            Some(self.select(v))
         });
 
         match ordered {
-            true => Value::from(values.collect::<List>()),
-            false => Value::from(values.collect::<Bag>()),
+            true => Value::from(List::from(values)),
+            false => Value::from(Bag::from(values)),
         }
     }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        Box::new(input_values.map(move |v| self.select(v)))
+    }
 }
 
 /// Represents an evaluation `ExprQuery` operator; in `PartiQL` as opposed to SQL, the following
@@ -1202,6 +2772,112 @@ impl Evaluable for EvalExprQuery {
     }
 }
 
+/// Represents an evaluation `Exclude` operator; e.g. `EXCLUDE t.a.b, t.c[*].d` in
+/// `SELECT * EXCLUDE t.a.b, t.c[*].d FROM t`. Removes the values at each of the `paths` from each
+/// of the input binding tuples; paths which do not exist in a binding tuple are ignored.
+#[derive(Debug)]
+pub(crate) struct EvalExclude {
+    /// The steps of each path, starting with the [`EvalExcludeStep::Key`] of its root binding.
+    pub(crate) paths: Vec<Vec<EvalExcludeStep>>,
+}
+
+/// A step of the path to the values removed by an [`EvalExclude`].
+#[derive(Debug, Clone)]
+pub(crate) enum EvalExcludeStep {
+    Key(BindingsName<'static>),
+    Index(i64),
+    AllElements,
+    AllFields,
+}
+
+impl EvalExclude {
+    fn exclude(&self, bindings: Value) -> Value {
+        let paths: Vec<_> = self.paths.iter().map(Vec::as_slice).collect();
+        exclude(bindings, &paths)
+    }
+}
+
+/// Removes the values at `paths` from `value`. All paths are applied at once, so that, e.g.,
+/// excluding `[0]` and `[1]` from a list removes its first two elements.
+fn exclude(value: Value, paths: &[&[EvalExcludeStep]]) -> Value {
+    // Returns the remainders of the paths whose first step addresses a nested value, or `None`
+    // if one of them ends there, i.e., the nested value is removed.
+    fn rests<'p>(
+        paths: &[&'p [EvalExcludeStep]],
+        matches: impl Fn(&EvalExcludeStep) -> bool,
+    ) -> Option<Vec<&'p [EvalExcludeStep]>> {
+        let mut rests = vec![];
+        for (step, rest) in paths.iter().filter_map(|path| path.split_first()) {
+            if matches(step) {
+                if rest.is_empty() {
+                    return None;
+                }
+                rests.push(rest);
+            }
+        }
+        Some(rests)
+    }
+    let exclude_nested = |value: Value, rests: Vec<&[EvalExcludeStep]>| match rests.is_empty() {
+        true => value,
+        false => exclude(value, &rests),
+    };
+
+    match value {
+        Value::Tuple(tuple) => {
+            let pairs = tuple.into_pairs().filter_map(|(k, v)| {
+                rests(paths, |step| match step {
+                    EvalExcludeStep::Key(key) => key.matcher().matches(&k),
+                    EvalExcludeStep::AllFields => true,
+                    _ => false,
+                })
+                .map(|rests| (k, exclude_nested(v, rests)))
+            });
+            Value::from(pairs.collect::<Tuple>())
+        }
+        Value::List(list) => {
+            let values = list.to_vec().into_iter().enumerate().filter_map(|(i, v)| {
+                rests(paths, |step| match step {
+                    EvalExcludeStep::Index(idx) => usize::try_from(*idx) == Ok(i),
+                    EvalExcludeStep::AllElements => true,
+                    _ => false,
+                })
+                .map(|rests| exclude_nested(v, rests))
+            });
+            Value::from(values.collect::<List>())
+        }
+        Value::Bag(bag) => {
+            let values = bag.to_vec().into_iter().filter_map(|v| {
+                rests(paths, |step| matches!(step, EvalExcludeStep::AllElements))
+                    .map(|rests| exclude_nested(v, rests))
+            });
+            Value::from(values.collect::<Bag>())
+        }
+        value => value,
+    }
+}
+
+impl Evaluable for EvalExclude {
+    fn evaluate(&self, mut inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        let input_value = take_input!(inputs[0].take(), ctx);
+        let ordered = input_value.is_ordered();
+
+        let values = input_value.into_iter().map(|v| self.exclude(v));
+        match ordered {
+            true => Value::from(values.collect::<List>()),
+            false => Value::from(values.collect::<Bag>()),
+        }
+    }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        Box::new(input_values.map(|v| self.exclude(v)))
+    }
+}
+
 /// Represents an SQL `DISTINCT` operator, e.g. in `SELECT DISTINCT a FROM t`.
 #[derive(Debug, Default)]
 pub(crate) struct EvalDistinct {}
@@ -1223,6 +2899,15 @@ impl Evaluable for EvalDistinct {
             false => Value::from(values.collect::<Bag>()),
         }
     }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        let input_values = take_input_iter!(inputs[0].take(), ctx);
+        Box::new(input_values.unique())
+    }
 }
 
 /// Represents an operator that captures the output of a (sub)query in the plan.
@@ -1232,6 +2917,16 @@ impl Evaluable for EvalSink {
     fn evaluate(&self, mut inputs: [Option<Value>; 2], _ctx: &dyn EvalContext) -> Value {
         inputs[0].take().unwrap_or(Missing)
     }
+
+    fn evaluate_iter<'a>(
+        &'a self,
+        mut inputs: [Option<ValueIter<'a>>; 2],
+        _ctx: &'a dyn EvalContext,
+    ) -> ValueIter<'a> {
+        inputs[0]
+            .take()
+            .unwrap_or_else(|| Box::new(std::iter::once(Missing)))
+    }
 }
 
 impl Debug for EvalSink {
@@ -1240,18 +2935,365 @@ impl Debug for EvalSink {
     }
 }
 
+/// Represents an evaluation operator for data modification statements; `Dml` computes the changes
+/// its statement makes to the rows of its `target`, applies them through the context's
+/// [`MutationSink`] and outputs the rows of the statement's `RETURNING` clause.
+///
+/// All expressions of the statement are evaluated over the rows of the target as they were before
+/// the statement, with each row bound to `as_key`; the `ON CONFLICT` condition of an `INSERT` is
+/// also evaluated over the rows the statement inserts before the row being inserted.
+#[derive(Debug)]
+pub(crate) struct EvalDml {
+    pub(crate) target: BindingsName<'static>,
+    pub(crate) as_key: String,
+    pub(crate) op: EvalDmlOp,
+    pub(crate) returning: Vec<EvalReturningElem>,
+}
+
+#[derive(Debug)]
+pub(crate) enum EvalDmlOp {
+    Insert {
+        values: Box<dyn EvalExpr>,
+        on_conflict: Option<Box<dyn EvalExpr>>,
+    },
+    Update {
+        filter: Option<Box<dyn EvalExpr>>,
+        changes: Vec<EvalUpdateChange>,
+    },
+    Delete {
+        filter: Option<Box<dyn EvalExpr>>,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) enum EvalUpdateChange {
+    Set(Vec<EvalPathStep>, Box<dyn EvalExpr>),
+    Remove(Vec<EvalPathStep>),
+}
+
+/// A step of the path from a row to the part of it modified by an [`EvalUpdateChange`].
+#[derive(Debug, Clone)]
+pub(crate) enum EvalPathStep {
+    Key(BindingsName<'static>),
+    Index(i64),
+}
+
+#[derive(Debug)]
+pub(crate) struct EvalReturningElem {
+    pub(crate) modified_only: bool,
+    pub(crate) new: bool,
+    /// The expression over the row and the name of its output, or `None` for `*`.
+    pub(crate) expr: Option<(String, Box<dyn EvalExpr>)>,
+}
+
+/// A row of the target of an [`EvalDml`], as it was before and after the statement.
+struct DmlRow {
+    old: Option<Value>,
+    new: Option<Value>,
+    modified: bool,
+}
+
+const EXCLUDED: &str = "EXCLUDED";
+
+impl EvalDml {
+    fn modify(
+        &self,
+        sink: &dyn MutationSink,
+        ctx: &dyn EvalContext,
+    ) -> Result<Value, EvaluationError> {
+        let mut mutations = vec![];
+        let mut rows = vec![];
+        let existing = sink.rows(&self.target)?;
+        match &self.op {
+            EvalDmlOp::Insert {
+                values,
+                on_conflict,
+            } => {
+                let empty = Tuple::new();
+                let values = values
+                    .evaluate(&DatumTupleRef::Tuple(&empty), ctx)
+                    .into_owned();
+                if !matches!(values, Value::Bag(_) | Value::List(_)) {
+                    return Err(EvaluationError::IllegalState(format!(
+                        "INSERT values must be a collection, found `{values:?}`"
+                    )));
+                }
+                for value in values {
+                    // a row conflicts with the existing rows and those inserted before it
+                    let conflict = on_conflict.as_ref().is_some_and(|cond| {
+                        let inserted = rows.iter().filter_map(|row: &DmlRow| row.new.as_ref());
+                        existing
+                            .iter()
+                            .map(|(_, row)| row)
+                            .chain(inserted)
+                            .any(|row| self.holds(cond.as_ref(), row, Some(&value), ctx))
+                    });
+                    if !conflict {
+                        mutations.push(Mutation::Insert(value.clone()));
+                        rows.push(DmlRow {
+                            old: None,
+                            new: Some(value),
+                            modified: true,
+                        });
+                    }
+                }
+                let unchanged = existing.into_iter().map(|(_, row)| DmlRow {
+                    old: Some(row.clone()),
+                    new: Some(row),
+                    modified: false,
+                });
+                rows.splice(0..0, unchanged);
+            }
+            EvalDmlOp::Update { filter, changes } => {
+                for (id, row) in existing {
+                    if !self.matches(filter, &row, ctx) {
+                        rows.push(DmlRow::unmodified(row));
+                        continue;
+                    }
+                    let mut new = row.clone();
+                    for change in changes {
+                        new = match change {
+                            EvalUpdateChange::Set(path, expr) => {
+                                let value = self.eval_over(expr.as_ref(), &row, None, ctx);
+                                assign(new, path, Some(value))?
+                            }
+                            EvalUpdateChange::Remove(path) => assign(new, path, None)?,
+                        };
+                    }
+                    mutations.push(Mutation::Replace(id, new.clone()));
+                    rows.push(DmlRow {
+                        old: Some(row),
+                        new: Some(new),
+                        modified: true,
+                    });
+                }
+            }
+            EvalDmlOp::Delete { filter } => {
+                for (id, row) in existing {
+                    if self.matches(filter, &row, ctx) {
+                        mutations.push(Mutation::Delete(id));
+                        rows.push(DmlRow {
+                            old: Some(row),
+                            new: None,
+                            modified: true,
+                        });
+                    } else {
+                        rows.push(DmlRow::unmodified(row));
+                    }
+                }
+            }
+        }
+
+        // A statement whose evaluation raised errors makes no changes
+        if ctx.has_errors() {
+            return Err(EvaluationError::DmlNotApplied(key_name(&self.target)));
+        }
+        sink.apply(&self.target, mutations)?;
+        Ok(Value::from(self.returning(rows, ctx)))
+    }
+
+    fn returning(&self, rows: Vec<DmlRow>, ctx: &dyn EvalContext) -> Bag {
+        if self.returning.is_empty() {
+            return Bag::default();
+        }
+        let all = self.returning.iter().any(|elem| !elem.modified_only);
+        rows.into_iter()
+            .filter(|row| row.modified || all)
+            .map(|row| {
+                let mut out = Tuple::new();
+                for elem in &self.returning {
+                    if elem.modified_only && !row.modified {
+                        continue;
+                    }
+                    let version = if elem.new { &row.new } else { &row.old };
+                    let Some(version) = version else {
+                        continue;
+                    };
+                    match &elem.expr {
+                        None => match version {
+                            Value::Tuple(t) => {
+                                for (k, v) in t.pairs() {
+                                    out.insert(k, v.clone());
+                                }
+                            }
+                            v => out.insert(&self.as_key, v.clone()),
+                        },
+                        Some((name, expr)) => {
+                            match self.eval_over(expr.as_ref(), version, None, ctx) {
+                                Missing => {}
+                                value => out.insert(name, value),
+                            }
+                        }
+                    }
+                }
+                Value::from(out)
+            })
+            .collect()
+    }
+
+    fn matches(
+        &self,
+        filter: &Option<Box<dyn EvalExpr>>,
+        row: &Value,
+        ctx: &dyn EvalContext,
+    ) -> bool {
+        filter
+            .as_ref()
+            .is_none_or(|filter| self.holds(filter.as_ref(), row, None, ctx))
+    }
+
+    fn holds(
+        &self,
+        expr: &dyn EvalExpr,
+        row: &Value,
+        excluded: Option<&Value>,
+        ctx: &dyn EvalContext,
+    ) -> bool {
+        matches!(self.eval_over(expr, row, excluded, ctx), Boolean(true))
+    }
+
+    /// Evaluates `expr` with `row` bound to the target's alias and, for `ON CONFLICT` conditions,
+    /// the value to be inserted bound to `EXCLUDED`.
+    fn eval_over(
+        &self,
+        expr: &dyn EvalExpr,
+        row: &Value,
+        excluded: Option<&Value>,
+        ctx: &dyn EvalContext,
+    ) -> Value {
+        let mut bindings = tuple![(self.as_key.as_str(), row.clone())];
+        if let Some(excluded) = excluded {
+            bindings.insert(EXCLUDED, excluded.clone());
+        }
+        let nested_ctx = NestedContext::new(MapBindings::from(&bindings), ctx);
+        expr.evaluate(&DatumTupleRef::Tuple(&bindings), &nested_ctx)
+            .into_owned()
+    }
+}
+
+impl DmlRow {
+    fn unmodified(row: Value) -> Self {
+        DmlRow {
+            old: Some(row.clone()),
+            new: Some(row),
+            modified: false,
+        }
+    }
+}
+
+/// Sets (or, if `value` is `None`, removes) the part of `target` at `path`.
+fn assign(
+    target: Value,
+    path: &[EvalPathStep],
+    value: Option<Value>,
+) -> Result<Value, EvaluationError> {
+    let invalid = |msg: String| Err(EvaluationError::InvalidAssignment(msg));
+    let Some((step, rest)) = path.split_first() else {
+        return match value {
+            Some(value) => Ok(value),
+            None => invalid("cannot REMOVE a row; use DELETE instead".to_string()),
+        };
+    };
+    match (step, target) {
+        (EvalPathStep::Key(key), Value::Tuple(tuple)) => {
+            let matcher = key.matcher();
+            let mut pairs = vec![];
+            let mut found = false;
+            for (k, v) in tuple.into_pairs() {
+                if found || !matcher.matches(&k) {
+                    pairs.push((k, v));
+                } else {
+                    found = true;
+                    if rest.is_empty() {
+                        if let Some(value) = value.clone() {
+                            pairs.push((k, value));
+                        }
+                    } else {
+                        pairs.push((k, assign(v, rest, value.clone())?));
+                    }
+                }
+            }
+            match (found, rest.is_empty(), value) {
+                (false, true, Some(value)) => pairs.push((key_name(key), value)),
+                (false, false, Some(_)) => {
+                    return invalid(format!("no attribute `{}` to assign into", key_name(key)))
+                }
+                _ => {}
+            }
+            Ok(Value::from(pairs.into_iter().collect::<Tuple>()))
+        }
+        (EvalPathStep::Index(idx), Value::List(list)) => {
+            let mut values = list.to_vec();
+            let Some(pos) = usize::try_from(*idx).ok().filter(|pos| *pos < values.len()) else {
+                return invalid(format!("list index `{idx}` is out of bounds"));
+            };
+            match (rest.is_empty(), value) {
+                (true, Some(value)) => values[pos] = value,
+                (true, None) => {
+                    values.remove(pos);
+                }
+                (false, value) => {
+                    let v = std::mem::take(&mut values[pos]);
+                    values[pos] = assign(v, rest, value)?;
+                }
+            }
+            Ok(Value::from(values.into_iter().collect::<List>()))
+        }
+        (EvalPathStep::Key(key), _) => invalid(format!(
+            "cannot access attribute `{}` of a non-tuple",
+            key_name(key)
+        )),
+        (EvalPathStep::Index(idx), _) => {
+            invalid(format!("cannot access index `{idx}` of a non-list"))
+        }
+    }
+}
+
+/// Positionally binds the attribute values of `row`, if a tuple, or its elements, if a list, or
+/// else `row` itself to the attributes `columns` of a new tuple.
+fn rename_row(columns: &[String], row: Value) -> Value {
+    let tuple: Tuple = match row {
+        Value::Tuple(t) => columns.iter().zip(t.into_values()).collect(),
+        Value::List(l) => columns.iter().zip(l.to_vec()).collect(),
+        row => columns.iter().zip(std::iter::once(row)).collect(),
+    };
+    Value::from(tuple)
+}
+
+fn key_name(key: &BindingsName<'_>) -> String {
+    match key {
+        BindingsName::CaseSensitive(name) | BindingsName::CaseInsensitive(name) => name.to_string(),
+    }
+}
+
+impl Evaluable for EvalDml {
+    fn evaluate(&self, _inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        let Some(sink) = ctx.mutation_sink() else {
+            ctx.add_error(EvaluationError::NoMutationSink);
+            return Missing;
+        };
+        self.modify(sink, ctx).unwrap_or_else(|err| {
+            // the statement fails in every mode, as it did not apply its changes
+            let fatal = err.is_fatal();
+            ctx.add_error(err);
+            if !fatal {
+                ctx.add_error(EvaluationError::DmlNotApplied(key_name(&self.target)));
+            }
+            Missing
+        })
+    }
+}
+
 /// Represents an evaluation operator for sub-queries, e.g. `SELECT a FROM b` in
 /// `SELECT b.c, (SELECT a FROM b) FROM books AS b`.
 #[derive(Debug)]
 pub(crate) struct EvalSubQueryExpr {
-    pub(crate) plan: Rc<RefCell<EvalPlan>>,
+    pub(crate) plan: EvalPlan,
 }
 
 impl EvalSubQueryExpr {
     pub(crate) fn new(plan: EvalPlan) -> Self {
-        EvalSubQueryExpr {
-            plan: Rc::new(RefCell::new(plan)),
-        }
+        EvalSubQueryExpr { plan }
     }
 }
 
@@ -1269,9 +3311,7 @@ impl EvalExpr for EvalSubQueryExpr {
             let bindings = MapBindings::from(bindings);
             let nested_ctx = NestedContext::new(bindings, ctx);
 
-            let plan = RefCell::borrow(&self.plan);
-
-            let value = match plan.execute(&nested_ctx) {
+            let value = match self.plan.execute(&nested_ctx) {
                 Ok(evaluated) => evaluated.result,
                 Err(err) => {
                     for e in err.errors {
@@ -1287,6 +3327,322 @@ impl EvalExpr for EvalSubQueryExpr {
     }
 }
 
+/// Represents an evaluation `WITH` operator, e.g. `WITH b AS (SELECT * FROM a) SELECT * FROM b`.
+///
+/// Each common table expression is evaluated once, in order, and bound under its name for the
+/// evaluation of the subsequent common table expressions and of the `WITH`'s body.
+#[derive(Debug)]
+pub(crate) struct EvalWith {
+    pub(crate) ctes: Vec<EvalCte>,
+    pub(crate) body: EvalPlan,
+}
+
+impl EvalWith {
+    fn evaluate_from(&self, idx: usize, ctx: &dyn EvalContext) -> Value {
+        match self.ctes.get(idx) {
+            None => execute_plan(&self.body, ctx),
+            Some(cte) => {
+                let mut bindings = MapBindings::default();
+                bindings.insert(&cte.name, cte.evaluate(ctx));
+                let nested_ctx = NestedContext::new(bindings, ctx);
+                self.evaluate_from(idx + 1, &nested_ctx)
+            }
+        }
+    }
+}
+
+impl Evaluable for EvalWith {
+    fn evaluate(&self, _inputs: [Option<Value>; 2], ctx: &dyn EvalContext) -> Value {
+        self.evaluate_from(0, ctx)
+    }
+}
+
+/// Executes a nested plan, reporting its errors to `ctx`.
+fn execute_plan(plan: &EvalPlan, ctx: &dyn EvalContext) -> Value {
+    match plan.execute(ctx) {
+        Ok(evaluated) => evaluated.result,
+        Err(err) => {
+            for e in err.errors {
+                ctx.add_error(e);
+            }
+            Missing
+        }
+    }
+}
+
+/// A common table expression of an [`EvalWith`].
+#[derive(Debug)]
+pub(crate) struct EvalCte {
+    pub(crate) name: String,
+    pub(crate) columns: Option<Vec<String>>,
+    pub(crate) plan: EvalPlan,
+    pub(crate) recursive: Option<EvalRecursiveStep>,
+}
+
+/// The recursive part of a `WITH RECURSIVE` element along with its `SEARCH` and `CYCLE` clauses.
+#[derive(Debug)]
+pub(crate) struct EvalRecursiveStep {
+    pub(crate) plan: EvalPlan,
+    pub(crate) setq: SetQuantifier,
+    pub(crate) search: Option<EvalCteSearch>,
+    pub(crate) cycle: Option<EvalCteCycle>,
+    pub(crate) parent: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) struct EvalCteSearch {
+    pub(crate) order: EvalSearchOrder,
+    pub(crate) by: Vec<String>,
+    pub(crate) set: String,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum EvalSearchOrder {
+    DepthFirst,
+    BreadthFirst,
+}
+
+#[derive(Debug)]
+pub(crate) struct EvalCteCycle {
+    pub(crate) columns: Vec<String>,
+    pub(crate) mark: String,
+    pub(crate) mark_value: Value,
+    pub(crate) default_value: Value,
+    pub(crate) path: String,
+}
+
+/// The maximum number of iterations of the recursive part of a `WITH RECURSIVE` element; guards
+/// against recursion over cyclic data without a `CYCLE` clause.
+const MAX_RECURSIVE_ITERATIONS: usize = 10_000;
+
+impl EvalCte {
+    fn evaluate(&self, ctx: &dyn EvalContext) -> Value {
+        let value = self.rename(execute_plan(&self.plan, ctx));
+        match &self.recursive {
+            None => value,
+            Some(step) => self.evaluate_recursive(step, value, ctx),
+        }
+    }
+
+    /// Evaluates a `WITH RECURSIVE` element by iterating the recursive part to a fixpoint.
+    ///
+    /// Each iteration evaluates the recursive part with the element's name bound to the rows
+    /// produced by the previous iteration (starting with the rows of the anchor), except for those
+    /// which close a cycle. If the element has a `SEARCH` or `CYCLE` clause, each new row's
+    /// ordering and path columns are derived from the row of the previous iteration it derives
+    /// from, which the recursive part carries along.
+    ///
+    /// Evaluation fails if no fixpoint is reached within [`MAX_RECURSIVE_ITERATIONS`] iterations.
+    fn evaluate_recursive(
+        &self,
+        step: &EvalRecursiveStep,
+        anchor: Value,
+        ctx: &dyn EvalContext,
+    ) -> Value {
+        let mut seen = HashSet::new();
+        let mut admit = |rows: Vec<Value>| match step.setq {
+            SetQuantifier::All => rows,
+            SetQuantifier::Distinct => rows
+                .into_iter()
+                .filter(|row| seen.insert(row.clone()))
+                .collect(),
+        };
+
+        let anchor = rows(anchor)
+            .into_iter()
+            .map(|row| step.start(row))
+            .collect();
+        let mut working: Vec<Value> = admit(anchor);
+        let mut result = working.clone();
+        let mut depth = 0;
+        while !working.is_empty() {
+            if depth as usize == MAX_RECURSIVE_ITERATIONS {
+                ctx.add_error(EvaluationError::RecursionLimit(
+                    self.name.clone(),
+                    MAX_RECURSIVE_ITERATIONS,
+                ));
+                return Missing;
+            }
+            depth += 1;
+            let parents = working
+                .into_iter()
+                .filter(|row| !step.is_cycle(row))
+                .collect();
+            let produced = self
+                .evaluate_step(step, parents, ctx)
+                .into_iter()
+                .map(|(row, parent)| match parent {
+                    Some(parent) => step.derive(row, &parent, depth),
+                    None => row,
+                })
+                .collect();
+            working = admit(produced);
+            result.extend(working.iter().cloned());
+        }
+        Value::from(result.into_iter().collect::<Bag>())
+    }
+
+    /// Evaluates the recursive part over the rows `working` of the previous iteration, returning
+    /// each row it produces along with the row of `working` it derives from, if carried.
+    fn evaluate_step(
+        &self,
+        step: &EvalRecursiveStep,
+        working: Vec<Value>,
+        ctx: &dyn EvalContext,
+    ) -> Vec<(Value, Option<Value>)> {
+        let mut bindings = MapBindings::default();
+        bindings.insert(&self.name, working.into_iter().collect::<Bag>().into());
+        let nested_ctx = NestedContext::new(bindings, ctx);
+        rows(execute_plan(&step.plan, &nested_ctx))
+            .into_iter()
+            .map(|mut row| {
+                let parent = step.parent.as_ref().map(|attr| take_attr(&mut row, attr));
+                let row = match &self.columns {
+                    Some(columns) => rename_row(columns, row),
+                    None => row,
+                };
+                (row, parent)
+            })
+            .collect()
+    }
+
+    /// Positionally renames the attributes of `value`'s rows to the element's column names, if any.
+    fn rename(&self, value: Value) -> Value {
+        let Some(columns) = &self.columns else {
+            return value;
+        };
+        if value.is_absent() {
+            return value;
+        }
+        let ordered = value.is_ordered();
+        let renamed = value.into_iter().map(|row| rename_row(columns, row));
+        if ordered {
+            Value::from(renamed.collect::<List>())
+        } else {
+            Value::from(renamed.collect::<Bag>())
+        }
+    }
+}
+
+/// The rows of a query result; an absent result (e.g., due to an evaluation error) has no rows.
+fn rows(value: Value) -> Vec<Value> {
+    match value {
+        Missing | Null => vec![],
+        value => value.into_iter().collect(),
+    }
+}
+
+fn get_attr(row: &Value, name: &str) -> Value {
+    match row {
+        Value::Tuple(t) => Tuple::get(t, &BindingsName::CaseInsensitive(Cow::Borrowed(name)))
+            .cloned()
+            .unwrap_or(Missing),
+        _ => Missing,
+    }
+}
+
+fn take_attr(row: &mut Value, name: &str) -> Value {
+    match row {
+        Value::Tuple(t) => t
+            .remove(&BindingsName::CaseSensitive(Cow::Borrowed(name)))
+            .unwrap_or(Missing),
+        _ => Missing,
+    }
+}
+
+fn set_attr(row: &mut Value, name: &str, value: Value) {
+    if let Value::Tuple(t) = row {
+        t.remove(&BindingsName::CaseInsensitive(Cow::Borrowed(name)));
+        t.insert(name, value);
+    }
+}
+
+impl EvalRecursiveStep {
+    /// Adds the `SEARCH` and `CYCLE` columns to a row of the anchor.
+    fn start(&self, mut row: Value) -> Value {
+        if let Some(search) = &self.search {
+            let key = search.key(&row);
+            let seq: List = match search.order {
+                EvalSearchOrder::DepthFirst => list![key],
+                EvalSearchOrder::BreadthFirst => {
+                    std::iter::once(Value::Integer(0)).chain(key).collect()
+                }
+            };
+            set_attr(&mut row, &search.set, seq.into());
+        }
+        if let Some(cycle) = &self.cycle {
+            let key = cycle.key(&row);
+            set_attr(&mut row, &cycle.mark, cycle.default_value.clone());
+            set_attr(&mut row, &cycle.path, list![key].into());
+        }
+        row
+    }
+
+    /// Adds the `SEARCH` and `CYCLE` columns to a row produced at `depth` from `parent`.
+    fn derive(&self, mut row: Value, parent: &Value, depth: i64) -> Value {
+        if let Some(search) = &self.search {
+            let key = search.key(&row);
+            let seq: List = match search.order {
+                EvalSearchOrder::DepthFirst => {
+                    let mut seq = match get_attr(parent, &search.set) {
+                        Value::List(seq) => *seq,
+                        _ => List::default(),
+                    };
+                    seq.push(key);
+                    seq
+                }
+                EvalSearchOrder::BreadthFirst => {
+                    std::iter::once(Value::Integer(depth)).chain(key).collect()
+                }
+            };
+            set_attr(&mut row, &search.set, seq.into());
+        }
+        if let Some(cycle) = &self.cycle {
+            let key = cycle.key(&row);
+            let mut path = match get_attr(parent, &cycle.path) {
+                Value::List(path) => *path,
+                _ => List::default(),
+            };
+            let mark = if path.iter().contains(&key) {
+                cycle.mark_value.clone()
+            } else {
+                cycle.default_value.clone()
+            };
+            path.push(key);
+            set_attr(&mut row, &cycle.mark, mark);
+            set_attr(&mut row, &cycle.path, path.into());
+        }
+        row
+    }
+
+    /// Whether `row` closes a cycle and therefore must not be recursed into.
+    fn is_cycle(&self, row: &Value) -> bool {
+        self.cycle
+            .as_ref()
+            .is_some_and(|cycle| get_attr(row, &cycle.mark) == cycle.mark_value)
+    }
+}
+
+impl EvalCteSearch {
+    fn key(&self, row: &Value) -> Value {
+        self.by
+            .iter()
+            .map(|col| get_attr(row, col))
+            .collect::<List>()
+            .into()
+    }
+}
+
+impl EvalCteCycle {
+    fn key(&self, row: &Value) -> Value {
+        self.columns
+            .iter()
+            .map(|col| get_attr(row, col))
+            .collect::<List>()
+            .into()
+    }
+}
+
 ///
 /// Coercion function F for bag operators described in RFC-0007
 /// - `F(absent_value`) -> << >>
@@ -1,7 +1,8 @@
+use crate::eval::evaluable::ValueIter;
 use crate::eval::expr::EvalExpr;
 use crate::eval::EvalContext;
 use itertools::Itertools;
-use partiql_catalog::table_fn::BaseTableExpr;
+use partiql_catalog::table_fn::{BaseTableExpr, BaseTableExprResult};
 
 use partiql_value::Value::Missing;
 use partiql_value::{Bag, Value};
@@ -18,23 +19,36 @@ pub(crate) struct EvalFnBaseTableExpr {
     pub(crate) expr: Box<dyn BaseTableExpr>,
 }
 
-impl EvalExpr for EvalFnBaseTableExpr {
-    #[inline]
-    fn evaluate<'a, 'c, 'o>(
+impl EvalFnBaseTableExpr {
+    fn call<'a, 'c>(
         &'a self,
         bindings: &'a dyn RefTupleView<'a, Value>,
         ctx: &'c dyn EvalContext,
-    ) -> Cow<'o, Value>
+    ) -> BaseTableExprResult<'c>
     where
         'c: 'a,
-        'a: 'o,
     {
         let args = self
             .args
             .iter()
             .map(|arg| arg.evaluate(bindings, ctx))
             .collect_vec();
-        let results = self.expr.evaluate(&args, ctx);
+        self.expr.evaluate(&args, ctx)
+    }
+}
+
+impl EvalExpr for EvalFnBaseTableExpr {
+    #[inline]
+    fn evaluate<'a, 'c, 'o>(
+        &'a self,
+        bindings: &'a dyn RefTupleView<'a, Value>,
+        ctx: &'c dyn EvalContext,
+    ) -> Cow<'o, Value>
+    where
+        'c: 'a,
+        'a: 'o,
+    {
+        let results = self.call(bindings, ctx);
         let result = match results {
             Ok(it) => {
                 let bag: Result<Bag, _> = it
@@ -69,4 +83,36 @@ impl EvalExpr for EvalFnBaseTableExpr {
         };
         Cow::Owned(result)
     }
+
+    fn evaluate_iter<'a, 'c>(
+        &'a self,
+        bindings: &'a dyn RefTupleView<'a, Value>,
+        ctx: &'c dyn EvalContext,
+    ) -> Option<ValueIter<'c>>
+    where
+        'c: 'a,
+    {
+        let values: ValueIter<'c> = match self.call(bindings, ctx) {
+            Ok(it) => Box::new(it.map_while(move |r| match r {
+                Ok(v) => Some(v),
+                Err(err @ ExtensionResultError::DataError(_)) => {
+                    // This is an error for this data item; coerce it to `Missing` and continue
+                    ctx.add_error(err.into());
+                    Some(Missing)
+                }
+                Err(err) => {
+                    // Error on read and/or stream; the values already streamed cannot be
+                    // retracted, so the stream ends
+                    ctx.add_error(err.into());
+                    None
+                }
+            })),
+            Err(err) => {
+                // Error on read and/or stream; Treat whole stream as `Missing`
+                ctx.add_error(err.into());
+                Box::new(std::iter::once(Missing))
+            }
+        };
+        Some(values)
+    }
 }
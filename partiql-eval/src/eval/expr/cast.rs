@@ -0,0 +1,522 @@
+use crate::error::EvaluationError;
+use crate::eval::expr::EvalExpr;
+use crate::eval::EvalContext;
+
+use partiql_logical::{CastKind, Type};
+use partiql_value::datum::{DatumLower, RefTupleView};
+use partiql_value::{Bag, DateTime, List, Value};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::str::FromStr;
+use time::format_description::well_known::Rfc3339;
+use time::format_description::BorrowedFormatItem;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+/// Represents a `PartiQL` evaluation `CAST`, `CAN_CAST` or `CAN_LOSSLESS_CAST` operator, e.g.
+/// `CAST(a AS INT)`.
+///
+/// A value which cannot be cast results in an error in `STRICT` mode and `MISSING` otherwise.
+#[derive(Debug)]
+pub(crate) struct EvalCastExpr<const STRICT: bool> {
+    pub(crate) expr: Box<dyn EvalExpr>,
+    pub(crate) kind: CastKind,
+    pub(crate) cast_type: Type,
+}
+
+impl<const STRICT: bool> EvalExpr for EvalCastExpr<STRICT> {
+    fn evaluate<'a, 'c, 'o>(
+        &'a self,
+        bindings: &'a dyn RefTupleView<'a, Value>,
+        ctx: &'c dyn EvalContext,
+    ) -> Cow<'o, Value>
+    where
+        'c: 'a,
+        'a: 'o,
+    {
+        let value = self.expr.evaluate(bindings, ctx);
+        let result = match self.kind {
+            CastKind::Cast => match cast(&value, &self.cast_type) {
+                Ok(value) => value,
+                Err(err) => {
+                    if STRICT {
+                        ctx.add_error(EvaluationError::InvalidCast(err));
+                    }
+                    Value::Missing
+                }
+            },
+            CastKind::CanCast => cast(&value, &self.cast_type).is_ok().into(),
+            CastKind::CanLosslessCast => can_lossless_cast(&value, &self.cast_type).into(),
+        };
+        Cow::Owned(result)
+    }
+}
+
+/// Whether `value` can be cast to `ty` and back to its own type without a change of its value.
+fn can_lossless_cast(value: &Value, ty: &Type) -> bool {
+    let Ok(value) = value.lower() else {
+        return false;
+    };
+    let Some(value_type) = type_of(&value) else {
+        return matches!(ty, Type::AnyType);
+    };
+    cast(&value, ty)
+        .and_then(|cast_value| cast(&cast_value, &value_type))
+        .is_ok_and(|round_trip| round_trip == *value)
+}
+
+/// The type of the values of the same kind as `value`, if there is one.
+fn type_of(value: &Value) -> Option<Type> {
+    Some(match value {
+        Value::Null => Type::NullType,
+        Value::Missing => Type::MissingType,
+        Value::Boolean(_) => Type::BooleanType,
+        Value::Integer(_) => Type::Integer8Type,
+        Value::Real(_) => Type::DoublePrecisionType,
+        Value::Decimal(_) => Type::DecimalType(None, None),
+        Value::String(_) => Type::StringType,
+        Value::Blob(_) => Type::BlobType,
+        Value::DateTime(dt) => match dt.as_ref() {
+            DateTime::Date(_) => Type::DateType,
+            DateTime::Time(_) => Type::TimeType(None),
+            DateTime::TimeWithTz(..) => Type::ZonedTimeType(None),
+            DateTime::Timestamp(_) => Type::TimestampType(None),
+            DateTime::TimestampWithTz(_) => Type::ZonedTimestampType(None),
+        },
+        Value::List(_) => Type::ListType,
+        Value::Bag(_) => Type::BagType,
+        Value::Tuple(_) => Type::StructType,
+        Value::Graph(_) | Value::Variant(_) => return None,
+    })
+}
+
+/// The name of the type of `value`, as it is written in a query.
+pub(crate) fn value_type_name(value: &Value) -> String {
+    match (value, type_of(value)) {
+        (_, Some(ty)) => type_name(&ty),
+        (Value::Graph(_), None) => "GRAPH".to_string(),
+        (_, None) => "VARIANT".to_string(),
+    }
+}
+
+/// Whether `value` is of the same kind as `ty`, disregarding any precision, scale or length of
+/// `ty`: numbers are of all numeric types, strings of all character string types, and so on.
+///
+/// `NULL` and `MISSING` are of every type.
+pub(crate) fn conforms_to(value: &Value, ty: &Type) -> bool {
+    let is_numeric = |ty: &Type| {
+        matches!(
+            ty,
+            Type::Integer2Type
+                | Type::Integer4Type
+                | Type::Integer8Type
+                | Type::DecimalType(..)
+                | Type::NumericType(..)
+                | Type::RealType
+                | Type::DoublePrecisionType
+        )
+    };
+    let value_type = match value {
+        Value::Null | Value::Missing => return true,
+        _ => type_of(value),
+    };
+    match (value_type, ty) {
+        (_, Type::AnyType) => true,
+        (None, _) => false,
+        (Some(value_type), ty) if is_numeric(&value_type) => is_numeric(ty),
+        (
+            Some(Type::StringType),
+            Type::CharacterType(_)
+            | Type::CharacterVaryingType(_)
+            | Type::StringType
+            | Type::SymbolType,
+        ) => true,
+        (Some(Type::BlobType), Type::BlobType | Type::ClobType) => true,
+        (Some(Type::ListType), Type::ListType | Type::SexpType) => true,
+        (Some(Type::StructType), Type::StructType | Type::TupleType) => true,
+        (Some(value_type), ty) => std::mem::discriminant(&value_type) == std::mem::discriminant(ty),
+    }
+}
+
+type CastResult<T> = Result<T, String>;
+
+/// Converts `value` to `ty`, as specified for `CAST` by the `PartiQL` specification.
+///
+/// `NULL` and `MISSING` are cast to themselves and every value can be cast to `ANY`, `NULL` and
+/// `MISSING`.
+pub(crate) fn cast(value: &Value, ty: &Type) -> CastResult<Value> {
+    let value = value.lower().map_err(|err| err.to_string())?;
+    let value = value.as_ref();
+    let invalid = || Err(format!("cannot cast {value:?} to {}", type_name(ty)));
+
+    match (value, ty) {
+        (_, Type::AnyType) => Ok(value.clone()),
+        (_, Type::MissingType) | (Value::Missing, _) => Ok(Value::Missing),
+        (_, Type::NullType) | (Value::Null, _) => Ok(Value::Null),
+        (_, Type::BooleanType) => to_bool(value).map(Value::from),
+        (_, Type::Integer2Type) => to_int(value, "SMALLINT", i16::MIN.into(), i16::MAX.into()),
+        (_, Type::Integer4Type) => to_int(value, "INT", i32::MIN.into(), i32::MAX.into()),
+        (_, Type::Integer8Type) => to_int(value, "BIGINT", i64::MIN, i64::MAX),
+        (_, Type::DecimalType(precision, scale) | Type::NumericType(precision, scale)) => {
+            to_decimal(value, *precision, *scale).map(Value::from)
+        }
+        (_, Type::RealType) => {
+            let f = to_float(value)?;
+            let real = f as f32;
+            if real.is_infinite() && f.is_finite() {
+                Err(format!("{value:?} is out of the range of REAL"))
+            } else {
+                Ok(Value::from(f64::from(real)))
+            }
+        }
+        (_, Type::DoublePrecisionType) => to_float(value).map(Value::from),
+        (_, Type::StringType | Type::SymbolType) => to_string(value).map(Value::from),
+        (_, Type::CharacterType(length)) => {
+            // a `CHAR` without a length has a length of 1
+            let length = length.unwrap_or(1) as usize;
+            let s = to_string(value)?;
+            let s: String = s
+                .chars()
+                .chain(std::iter::repeat(' '))
+                .take(length)
+                .collect();
+            Ok(Value::from(s))
+        }
+        (_, Type::CharacterVaryingType(length)) => {
+            let s = to_string(value)?;
+            Ok(Value::from(match length {
+                Some(length) => s.chars().take(*length as usize).collect(),
+                None => s,
+            }))
+        }
+        (Value::Blob(_), Type::BlobType | Type::ClobType) => Ok(value.clone()),
+        (Value::DateTime(dt), _) => cast_datetime(dt, ty).map_or_else(invalid, Ok),
+        (Value::String(s), Type::DateType) => parse_date(s.trim()).map_or_else(invalid, Ok),
+        (Value::String(s), Type::TimeType(precision)) => {
+            let time = parse_time(s.trim()).map(|(time, _)| time);
+            time.map_or_else(invalid, |t| {
+                Ok(DateTime::Time(truncate(t, *precision)).into())
+            })
+        }
+        (Value::String(s), Type::ZonedTimeType(precision)) => {
+            let time = parse_time(s.trim()).map(|(time, offset)| {
+                DateTime::TimeWithTz(truncate(time, *precision), offset.unwrap_or(UtcOffset::UTC))
+            });
+            time.map_or_else(invalid, |t| Ok(t.into()))
+        }
+        (Value::String(s), Type::TimestampType(precision)) => {
+            let ts = parse_timestamp(s.trim()).map(|ts| {
+                let ts = PrimitiveDateTime::new(ts.date(), ts.time());
+                DateTime::Timestamp(ts.replace_time(truncate(ts.time(), *precision)))
+            });
+            ts.map_or_else(invalid, |ts| Ok(ts.into()))
+        }
+        (Value::String(s), Type::ZonedTimestampType(precision)) => {
+            let ts = parse_timestamp(s.trim()).map(|ts| {
+                DateTime::TimestampWithTz(ts.replace_time(truncate(ts.time(), *precision)))
+            });
+            ts.map_or_else(invalid, |ts| Ok(ts.into()))
+        }
+        (Value::Tuple(_), Type::StructType | Type::TupleType) => Ok(value.clone()),
+        (Value::List(_), Type::ListType | Type::SexpType | Type::BagType)
+        | (Value::Bag(_), Type::BagType | Type::ListType | Type::SexpType) => {
+            let elements = value.iter().cloned();
+            Ok(match ty {
+                Type::BagType => Value::from(elements.collect::<Bag>()),
+                _ => Value::from(elements.collect::<List>()),
+            })
+        }
+        _ => invalid(),
+    }
+}
+
+fn to_bool(value: &Value) -> CastResult<bool> {
+    match value {
+        Value::Boolean(b) => Ok(*b),
+        Value::Integer(i) => Ok(*i != 0),
+        Value::Real(f) => Ok(f.0 != 0.0),
+        Value::Decimal(d) => Ok(!d.is_zero()),
+        Value::String(s) if s.trim().eq_ignore_ascii_case("true") => Ok(true),
+        Value::String(s) if s.trim().eq_ignore_ascii_case("false") => Ok(false),
+        _ => Err(format!("cannot cast {value:?} to BOOL")),
+    }
+}
+
+/// Converts `value` to an integer in `min..=max` of the integer type `name`; the fractional part
+/// of a number is truncated.
+fn to_int(value: &Value, name: &str, min: i64, max: i64) -> CastResult<Value> {
+    let out_of_range = || format!("{value:?} is out of the range of {name}");
+    let i: i128 = match value {
+        Value::Boolean(b) => i128::from(*b),
+        Value::Integer(i) => i128::from(*i),
+        Value::Real(f) if f.is_finite() => f.trunc() as i128,
+        Value::Decimal(d) => d.trunc().to_i128().ok_or_else(out_of_range)?,
+        Value::String(s) => {
+            let s = s.trim();
+            match s.parse::<i128>() {
+                Ok(i) => i,
+                Err(_) => match parse_decimal(s) {
+                    Some(d) => d.trunc().to_i128().ok_or_else(out_of_range)?,
+                    // a number which is too large even for a decimal
+                    None if s.parse::<f64>().is_ok_and(f64::is_finite) => {
+                        return Err(out_of_range())
+                    }
+                    None => return Err(format!("{value:?} cannot be converted to {name}")),
+                },
+            }
+        }
+        _ => return Err(format!("cannot cast {value:?} to {name}")),
+    };
+    if (i128::from(min)..=i128::from(max)).contains(&i) {
+        Ok(Value::from(i as i64))
+    } else {
+        Err(out_of_range())
+    }
+}
+
+/// Converts `value` to a decimal which is rounded to `scale` digits after the decimal point and
+/// which has at most `precision` digits.
+fn to_decimal(value: &Value, precision: Option<u32>, scale: Option<u32>) -> CastResult<Decimal> {
+    let d = match value {
+        Value::Boolean(b) => Some(Decimal::from(u8::from(*b))),
+        Value::Integer(i) => Some(Decimal::from(*i)),
+        Value::Real(f) => Decimal::try_from(f.0).ok(),
+        Value::Decimal(d) => Some(*d.as_ref()),
+        Value::String(s) => parse_decimal(s.trim()),
+        _ => return Err(format!("cannot cast {value:?} to DECIMAL")),
+    };
+    let mut d = d.ok_or_else(|| format!("cannot cast {value:?} to DECIMAL"))?;
+
+    if let Some(scale) = scale {
+        d = d.round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+        d.rescale(scale);
+    }
+    if let Some(precision) = precision {
+        let integer_digits = precision.saturating_sub(scale.unwrap_or(0));
+        let integer = d.trunc().abs().normalize();
+        if !integer.is_zero() && integer.to_string().len() > integer_digits as usize {
+            return Err(format!(
+                "{value:?} is out of the range of DECIMAL({precision}, {})",
+                scale.unwrap_or(0)
+            ));
+        }
+    }
+    Ok(d)
+}
+
+fn parse_decimal(s: &str) -> Option<Decimal> {
+    Decimal::from_str(s)
+        .or_else(|_| Decimal::from_scientific(s))
+        .ok()
+}
+
+fn to_float(value: &Value) -> CastResult<f64> {
+    match value {
+        Value::Boolean(b) => Ok(f64::from(u8::from(*b))),
+        Value::Integer(i) => Ok(*i as f64),
+        Value::Real(f) => Ok(f.0),
+        Value::Decimal(d) => d
+            .to_f64()
+            .ok_or_else(|| format!("cannot cast {value:?} to DOUBLE PRECISION")),
+        Value::String(s) => s
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| format!("cannot cast {value:?} to DOUBLE PRECISION")),
+        _ => Err(format!("cannot cast {value:?} to DOUBLE PRECISION")),
+    }
+}
+
+fn to_string(value: &Value) -> CastResult<String> {
+    match value {
+        Value::Boolean(b) => Ok(b.to_string()),
+        Value::Integer(i) => Ok(i.to_string()),
+        Value::Real(f) if f.is_nan() => Ok("nan".to_string()),
+        Value::Real(f) if f.is_infinite() => {
+            Ok(if f.0 > 0.0 { "+inf" } else { "-inf" }.to_string())
+        }
+        Value::Real(f) => Ok(f.0.to_string()),
+        Value::Decimal(d) => Ok(d.to_string()),
+        Value::String(s) => Ok(s.to_string()),
+        Value::DateTime(dt) => Ok(format_datetime(dt)),
+        _ => Err(format!("cannot cast {value:?} to STRING")),
+    }
+}
+
+fn cast_datetime(dt: &DateTime, ty: &Type) -> Option<Value> {
+    let midnight = |date: Date| PrimitiveDateTime::new(date, Time::MIDNIGHT);
+    let dt = match (dt, ty) {
+        (DateTime::Date(d), Type::DateType) => DateTime::Date(*d),
+        (DateTime::Timestamp(ts), Type::DateType) => DateTime::Date(ts.date()),
+        (DateTime::TimestampWithTz(ts), Type::DateType) => DateTime::Date(ts.date()),
+
+        (DateTime::Time(t) | DateTime::TimeWithTz(t, _), Type::TimeType(p)) => {
+            DateTime::Time(truncate(*t, *p))
+        }
+        (DateTime::Timestamp(ts), Type::TimeType(p)) => DateTime::Time(truncate(ts.time(), *p)),
+        (DateTime::TimestampWithTz(ts), Type::TimeType(p)) => {
+            DateTime::Time(truncate(ts.time(), *p))
+        }
+
+        (DateTime::Time(t), Type::ZonedTimeType(p)) => {
+            DateTime::TimeWithTz(truncate(*t, *p), UtcOffset::UTC)
+        }
+        (DateTime::TimeWithTz(t, offset), Type::ZonedTimeType(p)) => {
+            DateTime::TimeWithTz(truncate(*t, *p), *offset)
+        }
+        (DateTime::Timestamp(ts), Type::ZonedTimeType(p)) => {
+            DateTime::TimeWithTz(truncate(ts.time(), *p), UtcOffset::UTC)
+        }
+        (DateTime::TimestampWithTz(ts), Type::ZonedTimeType(p)) => {
+            DateTime::TimeWithTz(truncate(ts.time(), *p), ts.offset())
+        }
+
+        (DateTime::Date(d), Type::TimestampType(_)) => DateTime::Timestamp(midnight(*d)),
+        (DateTime::Timestamp(ts), Type::TimestampType(p)) => {
+            DateTime::Timestamp(ts.replace_time(truncate(ts.time(), *p)))
+        }
+        (DateTime::TimestampWithTz(ts), Type::TimestampType(p)) => {
+            DateTime::Timestamp(PrimitiveDateTime::new(ts.date(), truncate(ts.time(), *p)))
+        }
+
+        (DateTime::Date(d), Type::ZonedTimestampType(_)) => {
+            DateTime::TimestampWithTz(midnight(*d).assume_utc())
+        }
+        (DateTime::Timestamp(ts), Type::ZonedTimestampType(p)) => {
+            DateTime::TimestampWithTz(ts.replace_time(truncate(ts.time(), *p)).assume_utc())
+        }
+        (DateTime::TimestampWithTz(ts), Type::ZonedTimestampType(p)) => {
+            DateTime::TimestampWithTz(ts.replace_time(truncate(ts.time(), *p)))
+        }
+
+        _ => return None,
+    };
+    Some(dt.into())
+}
+
+/// Truncates the fractional seconds of `time` to `precision` digits.
+pub(crate) fn truncate(time: Time, precision: Option<u32>) -> Time {
+    match precision {
+        Some(precision) if precision < 9 => {
+            let unit = 10u32.pow(9 - precision);
+            let nanos = time.nanosecond() - time.nanosecond() % unit;
+            time.replace_nanosecond(nanos).unwrap_or(time)
+        }
+        _ => time,
+    }
+}
+
+const DATE: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME: &[BorrowedFormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+const OFFSET: &[BorrowedFormatItem<'_>] =
+    format_description!("[offset_hour sign:mandatory]:[offset_minute]");
+const TIMESTAMP: &[BorrowedFormatItem<'_>] = format_description!(
+    "[year]-[month]-[day][first [T][t][ ]][hour]:[minute]:[second][optional [.[subsecond]]]"
+);
+
+fn parse_date(s: &str) -> Option<Value> {
+    Date::parse(s, DATE)
+        .ok()
+        .map(|date| DateTime::Date(date).into())
+}
+
+/// Parses a time with an optional UTC offset, e.g. `12:30:00` or `12:30:00.5+01:00`.
+fn parse_time(s: &str) -> Option<(Time, Option<UtcOffset>)> {
+    let split = s.find(['+', '-', 'Z', 'z']).unwrap_or(s.len());
+    let (time, offset) = s.split_at(split);
+    let time = Time::parse(time, TIME).ok()?;
+    let offset = match offset {
+        "" => None,
+        "Z" | "z" => Some(UtcOffset::UTC),
+        offset => Some(UtcOffset::parse(offset, OFFSET).ok()?),
+    };
+    Some((time, offset))
+}
+
+/// Parses a date, or a timestamp with an optional UTC offset (which defaults to UTC).
+fn parse_timestamp(s: &str) -> Option<OffsetDateTime> {
+    if let Ok(date) = Date::parse(s, DATE) {
+        Some(PrimitiveDateTime::new(date, Time::MIDNIGHT).assume_utc())
+    } else if let Ok(ts) = PrimitiveDateTime::parse(s, TIMESTAMP) {
+        Some(ts.assume_utc())
+    } else {
+        OffsetDateTime::parse(s, &Rfc3339).ok()
+    }
+}
+
+/// Formats `dt` in the form from which it is cast by [`cast`].
+fn format_datetime(dt: &DateTime) -> String {
+    fn date(d: &Date) -> String {
+        format!("{:04}-{:02}-{:02}", d.year(), u8::from(d.month()), d.day())
+    }
+    fn time(t: &Time) -> String {
+        let (h, m, s, nanos) = t.as_hms_nano();
+        let fraction = format!("{nanos:09}");
+        let fraction = fraction.trim_end_matches('0');
+        match fraction {
+            "" => format!("{h:02}:{m:02}:{s:02}"),
+            fraction => format!("{h:02}:{m:02}:{s:02}.{fraction}"),
+        }
+    }
+    fn offset(o: &UtcOffset) -> String {
+        let (h, m, _) = o.as_hms();
+        let sign = if o.is_negative() { '-' } else { '+' };
+        format!("{sign}{:02}:{:02}", h.abs(), m.abs())
+    }
+
+    match dt {
+        DateTime::Date(d) => date(d),
+        DateTime::Time(t) => time(t),
+        DateTime::TimeWithTz(t, o) => format!("{}{}", time(t), offset(o)),
+        DateTime::Timestamp(ts) => format!("{}T{}", date(&ts.date()), time(&ts.time())),
+        DateTime::TimestampWithTz(ts) => format!(
+            "{}T{}{}",
+            date(&ts.date()),
+            time(&ts.time()),
+            offset(&ts.offset())
+        ),
+    }
+}
+
+/// The name of `ty` as it is written in a query.
+pub(crate) fn type_name(ty: &Type) -> String {
+    let params = |params: &[Option<u32>]| {
+        let params: Vec<_> = params.iter().flatten().map(u32::to_string).collect();
+        if params.is_empty() {
+            String::new()
+        } else {
+            format!("({})", params.join(", "))
+        }
+    };
+    match ty {
+        Type::NullType => "NULL".to_string(),
+        Type::BooleanType => "BOOL".to_string(),
+        Type::Integer2Type => "INT2".to_string(),
+        Type::Integer4Type => "INT4".to_string(),
+        Type::Integer8Type => "INT8".to_string(),
+        Type::DecimalType(p, s) => format!("DECIMAL{}", params(&[*p, *s])),
+        Type::NumericType(p, s) => format!("NUMERIC{}", params(&[*p, *s])),
+        Type::RealType => "REAL".to_string(),
+        Type::DoublePrecisionType => "DOUBLE PRECISION".to_string(),
+        Type::TimestampType(p) => format!("TIMESTAMP{}", params(&[*p])),
+        Type::CharacterType(n) => format!("CHAR{}", params(&[*n])),
+        Type::CharacterVaryingType(n) => format!("VARCHAR{}", params(&[*n])),
+        Type::MissingType => "MISSING".to_string(),
+        Type::StringType => "STRING".to_string(),
+        Type::SymbolType => "SYMBOL".to_string(),
+        Type::BlobType => "BLOB".to_string(),
+        Type::ClobType => "CLOB".to_string(),
+        Type::DateType => "DATE".to_string(),
+        Type::TimeType(p) => format!("TIME{}", params(&[*p])),
+        Type::ZonedTimeType(p) => format!("TIME{} WITH TIME ZONE", params(&[*p])),
+        Type::ZonedTimestampType(p) => format!("TIMESTAMP{} WITH TIME ZONE", params(&[*p])),
+        Type::StructType => "STRUCT".to_string(),
+        Type::TupleType => "TUPLE".to_string(),
+        Type::ListType => "LIST".to_string(),
+        Type::SexpType => "SEXP".to_string(),
+        Type::BagType => "BAG".to_string(),
+        Type::AnyType => "ANY".to_string(),
+    }
+}
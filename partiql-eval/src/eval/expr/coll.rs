@@ -46,7 +46,7 @@ impl BindEvalExpr for EvalCollFn {
             f: F,
         ) -> Result<Box<dyn EvalExpr>, BindError>
         where
-            F: Fn(ValueIter<'_>) -> Value + 'static,
+            F: Fn(ValueIter<'_>) -> Value + Send + Sync + 'static,
         {
             UnaryValueExpr::create_typed::<{ STRICT }, _>(types, args, move |value| {
                 value.sequence_iter().map_or(Missing, &f)
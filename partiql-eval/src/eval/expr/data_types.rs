@@ -1,15 +1,15 @@
 use crate::error::EvaluationError;
 
-use crate::eval::expr::EvalExpr;
+use crate::eval::expr::{truncate, EvalExpr};
 use crate::eval::EvalContext;
 
 use partiql_value::Value::Missing;
-use partiql_value::{Bag, List, Tuple, Value};
+use partiql_value::{Bag, DateTime, List, Tuple, Value};
 use std::borrow::Cow;
 use std::fmt::Debug;
 
 use partiql_logical::Type;
-use partiql_value::datum::{DatumCategory, DatumCategoryRef, RefTupleView};
+use partiql_value::datum::{DatumLower, RefTupleView};
 use std::ops::Not;
 
 /// Represents an evaluation operator for Tuple expressions such as `{t1.a: t1.b * 2}` in
@@ -124,15 +124,10 @@ impl EvalExpr for EvalIsTypeExpr {
         'a: 'o,
     {
         let expr = self.expr.evaluate(bindings, ctx);
-        let result = match (&self.is_type, expr.category()) {
-            (Type::NullType, DatumCategoryRef::Null | DatumCategoryRef::Missing) => true,
-            (Type::MissingType, DatumCategoryRef::Missing) => true,
-            (Type::NullType, _) => false,
-            (Type::MissingType, _) => false,
-            _ => {
-                ctx.add_error(EvaluationError::NotYetImplemented(
-                    "`IS` for other types".to_string(),
-                ));
+        let result = match expr.lower() {
+            Ok(value) => is_type(&value, &self.is_type),
+            Err(err) => {
+                ctx.add_error(EvaluationError::IllegalState(err.to_string()));
                 false
             }
         };
@@ -141,3 +136,63 @@ impl EvalExpr for EvalIsTypeExpr {
         Cow::Owned(result.into())
     }
 }
+
+/// Whether `value` is of type `ty`, including any precision, scale or length of `ty`.
+///
+/// `MISSING` is also of type `NULL`, and `NULL` and `MISSING` are of no other type but `ANY`.
+/// A timestamp with a time zone is also of type `TIMESTAMP`, so that Ion timestamps, which always
+/// carry an offset, are timestamps.
+fn is_type(value: &Value, ty: &Type) -> bool {
+    match (value, ty) {
+        (_, Type::AnyType) => true,
+        (Value::Null | Value::Missing, Type::NullType) => true,
+        (Value::Missing, Type::MissingType) => true,
+        (Value::Boolean(_), Type::BooleanType) => true,
+        (Value::Integer(i), Type::Integer2Type) => i16::try_from(*i).is_ok(),
+        (Value::Integer(i), Type::Integer4Type) => i32::try_from(*i).is_ok(),
+        (Value::Integer(_), Type::Integer8Type) => true,
+        (Value::Decimal(d), Type::DecimalType(precision, scale))
+        | (Value::Decimal(d), Type::NumericType(precision, scale)) => {
+            let d = d.normalize();
+            let scale = scale.unwrap_or(d.scale());
+            let integer_digits = d.trunc().abs().to_string().trim_start_matches('0').len();
+            d.scale() <= scale && precision.is_none_or(|p| integer_digits as u32 + scale <= p)
+        }
+        (Value::Real(f), Type::RealType) => {
+            !f.is_finite() || (f.0 as f32).is_finite() && f64::from(f.0 as f32) == f.0
+        }
+        (Value::Real(_), Type::DoublePrecisionType) => true,
+        (Value::String(s), Type::CharacterType(length)) => {
+            s.chars().count() == length.unwrap_or(1) as usize
+        }
+        (Value::String(s), Type::CharacterVaryingType(length)) => {
+            length.is_none_or(|length| s.chars().count() <= length as usize)
+        }
+        (Value::String(_), Type::StringType | Type::SymbolType) => true,
+        (Value::Blob(_), Type::BlobType) => true,
+        (Value::DateTime(dt), ty) => match (dt.as_ref(), ty) {
+            (DateTime::Date(_), Type::DateType) => true,
+            (DateTime::Time(t), Type::TimeType(precision))
+            | (DateTime::TimeWithTz(t, _), Type::ZonedTimeType(precision)) => {
+                has_precision(t, *precision)
+            }
+            (DateTime::Timestamp(ts), Type::TimestampType(precision)) => {
+                has_precision(&ts.time(), *precision)
+            }
+            (
+                DateTime::TimestampWithTz(ts),
+                Type::TimestampType(precision) | Type::ZonedTimestampType(precision),
+            ) => has_precision(&ts.time(), *precision),
+            _ => false,
+        },
+        (Value::Tuple(_), Type::StructType | Type::TupleType) => true,
+        (Value::List(_), Type::ListType) => true,
+        (Value::Bag(_), Type::BagType) => true,
+        _ => false,
+    }
+}
+
+/// Whether the fractional seconds of `time` have at most `precision` digits.
+fn has_precision(time: &time::Time, precision: Option<u32>) -> bool {
+    truncate(*time, precision) == *time
+}
@@ -5,19 +5,35 @@ use crate::eval::graph::simple_graph::engine::SimpleGraphEngine;
 
 use crate::eval::graph::plan::PathPatternMatch;
 use crate::eval::graph::string_graph::StringGraphTypes;
+use crate::eval::EvalContext;
 use partiql_types::{type_graph, PartiqlNoIdShapeBuilder};
+use partiql_value::datum::RefTupleView;
 use partiql_value::Value::Missing;
-use partiql_value::{Graph, Value};
+use partiql_value::{BindingsName, Graph, Value};
+use std::borrow::Cow;
 
 /// Represents an evaluation `MATCH` operator, e.g. in `graph MATCH () -> ()'`.
 #[derive(Debug)]
 pub(crate) struct EvalGraphMatch {
     pub(crate) pattern: PathPatternMatch<StringGraphTypes>,
+    /// The name of the variable matched against, if the matched expression is a variable
+    pub(crate) name: Option<BindingsName<'static>>,
 }
 
 impl EvalGraphMatch {
     pub(crate) fn new(pattern: PathPatternMatch<StringGraphTypes>) -> Self {
-        EvalGraphMatch { pattern }
+        EvalGraphMatch {
+            pattern,
+            name: None,
+        }
+    }
+
+    /// Sets the name of the variable matched against, so that the match is evaluated against the
+    /// graph of that name in the context's [`NamedGraphs`](crate::eval::graph::named::NamedGraphs),
+    /// if any.
+    pub(crate) fn with_name(mut self, name: BindingsName<'static>) -> Self {
+        self.name = Some(name);
+        self
     }
 }
 
@@ -26,9 +42,12 @@ impl BindEvalExpr for EvalGraphMatch {
         self,
         args: Vec<Box<dyn EvalExpr>>,
     ) -> Result<Box<dyn EvalExpr>, BindError> {
+        let EvalGraphMatch { pattern, name } = self;
+        let named = name.map(|name| (name, pattern.clone()));
+
         // use DummyShapeBuilder, as we don't care about shape Ids for evaluation dispatch
         let mut bld = PartiqlNoIdShapeBuilder::default();
-        UnaryValueExpr::create_typed_with_ctx::<{ STRICT }, _>(
+        let value_match = UnaryValueExpr::create_typed_with_ctx::<{ STRICT }, _>(
             [type_graph!(bld)],
             args,
             move |value, ctx| match value {
@@ -36,12 +55,47 @@ impl BindEvalExpr for EvalGraphMatch {
                     Graph::Simple(g) => {
                         let engine = SimpleGraphEngine::new(g.clone());
                         let ge = GraphEvaluator::new(engine);
-                        ge.eval(&self.pattern, ctx)
+                        ge.eval(&pattern, ctx)
                     }
                 },
                 _ => Missing,
             },
-        )
+        )?;
+
+        Ok(match named {
+            None => value_match,
+            Some((name, pattern)) => Box::new(EvalNamedGraphMatch {
+                name,
+                pattern,
+                value_match,
+            }),
+        })
+    }
+}
+
+/// A `MATCH` against a variable, which is evaluated against the context's graph of the variable's
+/// name, if any, and otherwise against the variable's value.
+#[derive(Debug)]
+struct EvalNamedGraphMatch {
+    name: BindingsName<'static>,
+    pattern: PathPatternMatch<StringGraphTypes>,
+    value_match: Box<dyn EvalExpr>,
+}
+
+impl EvalExpr for EvalNamedGraphMatch {
+    fn evaluate<'a, 'c, 'o>(
+        &'a self,
+        bindings: &'a dyn RefTupleView<'a, Value>,
+        ctx: &'c dyn EvalContext,
+    ) -> Cow<'o, Value>
+    where
+        'c: 'a,
+        'a: 'o,
+    {
+        match ctx.graphs().and_then(|graphs| graphs.get(&self.name)) {
+            Some(graph) => Cow::Owned(graph.eval(&self.pattern, ctx)),
+            None => self.value_match.evaluate(bindings, ctx),
+        }
     }
 }
 
@@ -1,5 +1,7 @@
 mod base_table;
 pub(crate) use base_table::*;
+mod cast;
+pub(crate) use cast::*;
 mod coll;
 pub(crate) use coll::*;
 mod control_flow;
@@ -22,6 +24,7 @@ mod operators;
 
 pub(crate) use operators::*;
 
+use crate::eval::evaluable::ValueIter;
 use crate::eval::EvalContext;
 
 use partiql_value::datum::{DatumLowerError, RefTupleView};
@@ -31,7 +34,7 @@ use std::fmt::Debug;
 use thiserror::Error;
 
 /// A trait for expressions that require evaluation, e.g. `a + b` or `c > 2`.
-pub trait EvalExpr: Debug {
+pub trait EvalExpr: Debug + Send + Sync {
     fn evaluate<'a, 'c, 'o>(
         &'a self,
         bindings: &'a dyn RefTupleView<'a, Value>,
@@ -40,6 +43,20 @@ pub trait EvalExpr: Debug {
     where
         'c: 'a,
         'a: 'o;
+
+    /// Evaluates the expression to a stream of the values of the bag it results in, if the
+    /// expression produces its values lazily (e.g., a table function); otherwise returns `None`,
+    /// and the expression is evaluated by [`EvalExpr::evaluate`].
+    fn evaluate_iter<'a, 'c>(
+        &'a self,
+        _bindings: &'a dyn RefTupleView<'a, Value>,
+        _ctx: &'c dyn EvalContext,
+    ) -> Option<ValueIter<'c>>
+    where
+        'c: 'a,
+    {
+        None
+    }
 }
 
 #[derive(Error, Debug)]
@@ -1,10 +1,12 @@
 pub use core::borrow::Borrow;
 
+use crate::error::EvaluationError;
 use crate::eval::expr::{BindError, BindEvalExpr, EvalExpr};
 use crate::eval::EvalContext;
 
+use partiql_logical::Parameter;
 use partiql_value::Value::Missing;
-use partiql_value::{BindingsName, Value};
+use partiql_value::{Bag, BindingsName, Value};
 
 use partiql_value::datum::{
     DatumCategory, DatumCategoryOwned, DatumCategoryRef, OwnedSequenceView, OwnedTupleView,
@@ -15,7 +17,10 @@ use std::fmt::{Debug, Formatter};
 
 /// Represents an evaluation operator for path navigation expressions as outlined in Section `4` of
 /// [PartiQL Specification — August 1, 2019](https://partiql.org/assets/PartiQL-Specification.pdf).
-pub(crate) struct EvalPath {
+///
+/// A wildcard step (`[*]` or `.*`) over a value which is not a collection (resp. tuple) results in
+/// an error in `STRICT` mode.
+pub(crate) struct EvalPath<const STRICT: bool> {
     pub(crate) expr: Box<dyn EvalExpr>,
     pub(crate) components: Vec<EvalPathComponent>,
 }
@@ -25,6 +30,8 @@ pub(crate) enum EvalPathComponent {
     KeyExpr(Box<dyn EvalExpr>),
     Index(i64),
     IndexExpr(Box<dyn EvalExpr>),
+    ForEach,
+    Unpivot,
 }
 
 impl Debug for EvalPathComponent {
@@ -45,11 +52,13 @@ impl Debug for EvalPathComponent {
                 ie.fmt(f)?;
                 write!(f, "]")
             }
+            EvalPathComponent::ForEach => write!(f, "[*]"),
+            EvalPathComponent::Unpivot => write!(f, ".*"),
         }
     }
 }
 
-impl Debug for EvalPath {
+impl<const STRICT: bool> Debug for EvalPath<STRICT> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         self.expr.fmt(f)?;
         for cmp in &self.components {
@@ -81,6 +90,14 @@ fn as_int(v: &Value) -> Option<i64> {
 }
 
 impl EvalPathComponent {
+    #[inline]
+    fn is_wildcard(&self) -> bool {
+        matches!(
+            self,
+            EvalPathComponent::ForEach | EvalPathComponent::Unpivot
+        )
+    }
+
     #[inline]
     fn get_val<'a, 'c>(
         &'a self,
@@ -134,7 +151,7 @@ impl EvalPathComponent {
     }
 }
 
-impl EvalExpr for EvalPath {
+impl<const STRICT: bool> EvalExpr for EvalPath<STRICT> {
     fn evaluate<'a, 'c, 'o>(
         &'a self,
         bindings: &'a dyn RefTupleView<'a, Value>,
@@ -145,6 +162,24 @@ impl EvalExpr for EvalPath {
         'a: 'o,
     {
         let evaluated = self.expr.evaluate(bindings, ctx);
+        if self.components.iter().any(EvalPathComponent::is_wildcard) {
+            let mut results = vec![];
+            let expanded = self.expand(
+                evaluated.into_owned(),
+                &self.components,
+                bindings,
+                ctx,
+                &mut results,
+            );
+            return Cow::Owned(match expanded {
+                Ok(()) => Value::from(Bag::from(results)),
+                Err(err) => {
+                    ctx.add_error(err);
+                    Value::Missing
+                }
+            });
+        }
+
         let mut path_componenents = self.components.iter();
 
         path_componenents
@@ -156,6 +191,63 @@ impl EvalExpr for EvalPath {
     }
 }
 
+impl<const STRICT: bool> EvalPath<STRICT> {
+    /// Navigates `value` through `components`, pushing the results into `results`. Following the
+    /// rewrites of wildcards into queries (i.e., of `e[*]` into `SELECT VALUE v FROM e AS v` and of
+    /// `e.*` into `SELECT VALUE v FROM UNPIVOT e AS v`), the remainder of the path navigates from
+    /// each element (resp. attribute value) of the value reached so far. Non-collection
+    /// (resp. non-tuple) values are an error in `STRICT` mode, and are otherwise navigated as if
+    /// they were wrapped in a singleton bag (resp. tuple).
+    fn expand<'a, 'c>(
+        &'a self,
+        value: Value,
+        components: &'a [EvalPathComponent],
+        bindings: &'a dyn RefTupleView<'a, Value>,
+        ctx: &'c dyn EvalContext,
+        results: &mut Vec<Value>,
+    ) -> Result<(), EvaluationError>
+    where
+        'c: 'a,
+    {
+        let (prefix, rest) = match components.iter().position(EvalPathComponent::is_wildcard) {
+            None => (components, &components[components.len()..]),
+            Some(idx) => components.split_at(idx),
+        };
+        let value = prefix
+            .iter()
+            .try_fold(Cow::Owned(value), |value, path| match value {
+                Cow::Borrowed(borrowed) => path.get_val(borrowed, bindings, ctx),
+                Cow::Owned(owned) => path.take_val(owned, bindings, ctx),
+            })
+            .map_or(Value::Missing, Cow::into_owned);
+
+        match rest.split_first() {
+            None => results.push(value),
+            Some((EvalPathComponent::ForEach, rest)) => {
+                if STRICT && !matches!(value.category(), DatumCategoryRef::Sequence(_)) {
+                    return Err(EvaluationError::IllegalState(format!(
+                        "path wildcard `[*]` expected a collection, found `{value:?}`"
+                    )));
+                }
+                for v in value {
+                    self.expand(v, rest, bindings, ctx, results)?;
+                }
+            }
+            Some((_, rest)) => {
+                if STRICT && !matches!(value.category(), DatumCategoryRef::Tuple(_)) {
+                    return Err(EvaluationError::IllegalState(format!(
+                        "path wildcard `.*` expected a tuple, found `{value:?}`"
+                    )));
+                }
+                for v in value.coerce_into_tuple().into_values() {
+                    self.expand(v, rest, bindings, ctx, results)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Represents an operator for dynamic variable name resolution of a (sub)query.
 #[derive(Debug)]
 pub(crate) struct EvalDynamicLookup {
@@ -266,3 +358,32 @@ impl EvalExpr for EvalGlobalVarRef {
         }
     }
 }
+
+/// Represents a reference to a query parameter, e.g. `?` or `:name`, the value of which is bound
+/// in the [`EvalContext`].
+pub(crate) struct EvalParameter {
+    pub(crate) param: Parameter,
+}
+
+impl Debug for EvalParameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.param)
+    }
+}
+
+impl EvalExpr for EvalParameter {
+    fn evaluate<'a, 'c, 'o>(
+        &'a self,
+        _bindings: &'a dyn RefTupleView<'a, Value>,
+        ctx: &'c dyn EvalContext,
+    ) -> Cow<'o, Value>
+    where
+        'c: 'a,
+        'a: 'o,
+    {
+        match ctx.parameters().and_then(|params| params.get(&self.param)) {
+            None => Cow::Owned(Value::Missing),
+            Some(v) => Cow::Borrowed(v),
+        }
+    }
+}
@@ -37,7 +37,7 @@ impl BindEvalExpr for EvalStringFn {
             f: F,
         ) -> Result<Box<dyn EvalExpr>, BindError>
         where
-            F: Fn(&Box<String>) -> R + 'static,
+            F: Fn(&Box<String>) -> R + Send + Sync + 'static,
             R: Into<Value> + 'static,
         {
             // use DummyShapeBuilder, as we don't care about shape Ids for evaluation dispatch
@@ -0,0 +1,356 @@
+//! A [`GraphEngine`] for graphs held in adjacency lists outside of `PartiQL` values, which serves as
+//! an example of a user-supplied engine (see [`NamedGraphs`](crate::eval::graph::named::NamedGraphs)).
+
+use crate::eval::graph::engine::{GraphAccess, GraphEngine, GraphFilter, TripleScan};
+use crate::eval::graph::plan::{
+    BindSpec, EdgeFilter, GraphPlanConvert, LabelFilter, NodeFilter, TripleFilter, ValueFilter,
+};
+use crate::eval::graph::result::{GraphElement, PathPatternNodes, Triple};
+use crate::eval::graph::string_graph::StringGraphTypes;
+use crate::eval::graph::types::GraphTypes;
+use crate::eval::EvalContext;
+use indexmap::IndexSet;
+use partiql_value::Value;
+use rustc_hash::{FxBuildHasher, FxHashSet};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AdjacencyListGraphTypes;
+
+impl GraphTypes for AdjacencyListGraphTypes {
+    type Binder = String;
+    type Label = String;
+    type NodeId = usize;
+    type EdgeId = usize;
+}
+
+type Binders = (
+    BindSpec<AdjacencyListGraphTypes>,
+    BindSpec<AdjacencyListGraphTypes>,
+    BindSpec<AdjacencyListGraphTypes>,
+);
+
+/// A graph whose nodes each list their incident edges.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyListGraph {
+    nodes: Vec<AdjacencyNode>,
+    edges: Vec<AdjacencyEdge>,
+}
+
+#[derive(Debug, Clone)]
+struct AdjacencyNode {
+    labels: FxHashSet<String>,
+    value: Option<Value>,
+    /// The directed edges from the node
+    outgoing: Vec<usize>,
+    /// The directed edges to the node
+    incoming: Vec<usize>,
+    undirected: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct AdjacencyEdge {
+    labels: FxHashSet<String>,
+    value: Option<Value>,
+    from: usize,
+    to: usize,
+}
+
+impl AdjacencyListGraph {
+    /// Adds a node with `labels` and payload `value`, returning its id.
+    pub fn add_node<L: Into<String>>(
+        &mut self,
+        labels: impl IntoIterator<Item = L>,
+        value: Option<Value>,
+    ) -> usize {
+        self.nodes.push(AdjacencyNode {
+            labels: labels.into_iter().map(Into::into).collect(),
+            value,
+            outgoing: vec![],
+            incoming: vec![],
+            undirected: vec![],
+        });
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge between nodes `from` and `to` (from `from` to `to`, if `directed`) with
+    /// `labels` and payload `value`, returning its id.
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` is not the id of a node.
+    pub fn add_edge<L: Into<String>>(
+        &mut self,
+        from: usize,
+        to: usize,
+        directed: bool,
+        labels: impl IntoIterator<Item = L>,
+        value: Option<Value>,
+    ) -> usize {
+        assert!(
+            from < self.nodes.len() && to < self.nodes.len(),
+            "expected node"
+        );
+        let id = self.edges.len();
+        self.edges.push(AdjacencyEdge {
+            labels: labels.into_iter().map(Into::into).collect(),
+            value,
+            from,
+            to,
+        });
+        if directed {
+            self.nodes[from].outgoing.push(id);
+            self.nodes[to].incoming.push(id);
+        } else {
+            self.nodes[from].undirected.push(id);
+            if from != to {
+                self.nodes[to].undirected.push(id);
+            }
+        }
+        id
+    }
+}
+
+/// [`GraphEngine`] for [`AdjacencyListGraph`]
+#[derive(Debug, Clone)]
+pub struct AdjacencyListGraphEngine {
+    graph: Arc<AdjacencyListGraph>,
+}
+
+impl AdjacencyListGraphEngine {
+    pub fn new(graph: Arc<AdjacencyListGraph>) -> Self {
+        Self { graph }
+    }
+}
+
+impl GraphEngine<AdjacencyListGraphTypes> for AdjacencyListGraphEngine {}
+
+impl GraphAccess<AdjacencyListGraphTypes> for AdjacencyListGraphEngine {
+    fn node(&self, id: &usize) -> &Option<Value> {
+        &self.graph.nodes[*id].value
+    }
+
+    fn edge(&self, id: &usize) -> &Option<Value> {
+        &self.graph.edges[*id].value
+    }
+}
+
+// binders and labels are strings in both the plan and the graph, so conversions are identities
+fn convert_label_filter<In, Out>(label: &LabelFilter<In>) -> LabelFilter<Out>
+where
+    In: GraphTypes<Label = String>,
+    Out: GraphTypes<Label = String>,
+{
+    match label {
+        LabelFilter::Always => LabelFilter::Always,
+        LabelFilter::Never => LabelFilter::Never,
+        LabelFilter::Named(l) => LabelFilter::Named(l.clone()),
+        LabelFilter::Negated(inner) => LabelFilter::Negated(Box::new(convert_label_filter(inner))),
+        LabelFilter::Conjunction(inner) => {
+            LabelFilter::Conjunction(inner.iter().map(convert_label_filter).collect())
+        }
+        LabelFilter::Disjunction(inner) => {
+            LabelFilter::Disjunction(inner.iter().map(convert_label_filter).collect())
+        }
+    }
+}
+
+impl GraphPlanConvert<StringGraphTypes, AdjacencyListGraphTypes> for AdjacencyListGraphEngine {
+    fn convert_label_filter(
+        &self,
+        label: &LabelFilter<StringGraphTypes>,
+    ) -> LabelFilter<AdjacencyListGraphTypes> {
+        convert_label_filter(label)
+    }
+
+    fn convert_binder(
+        &self,
+        binder: &BindSpec<StringGraphTypes>,
+    ) -> BindSpec<AdjacencyListGraphTypes> {
+        BindSpec(binder.0.clone())
+    }
+}
+
+impl GraphPlanConvert<AdjacencyListGraphTypes, StringGraphTypes> for AdjacencyListGraphEngine {
+    fn convert_label_filter(
+        &self,
+        label: &LabelFilter<AdjacencyListGraphTypes>,
+    ) -> LabelFilter<StringGraphTypes> {
+        convert_label_filter(label)
+    }
+
+    fn convert_binder(
+        &self,
+        binder: &BindSpec<AdjacencyListGraphTypes>,
+    ) -> BindSpec<StringGraphTypes> {
+        BindSpec(binder.0.clone())
+    }
+}
+
+impl TripleScan<AdjacencyListGraphTypes> for AdjacencyListGraphEngine {
+    fn scan_directed_from_to(
+        &self,
+        binders: &Binders,
+        spec: &TripleFilter<AdjacencyListGraphTypes>,
+        allow_repeated_nodes: bool,
+        filter: &ValueFilter,
+        ctx: &dyn EvalContext,
+    ) -> impl Iterator<Item = Triple<AdjacencyListGraphTypes>> {
+        let adjacent = move |n: usize| self.outgoing(n);
+        self.scan_adjacent(binders, spec, allow_repeated_nodes, filter, ctx, adjacent)
+    }
+
+    fn scan_directed_to_from(
+        &self,
+        binders: &Binders,
+        spec: &TripleFilter<AdjacencyListGraphTypes>,
+        allow_repeated_nodes: bool,
+        filter: &ValueFilter,
+        ctx: &dyn EvalContext,
+    ) -> impl Iterator<Item = Triple<AdjacencyListGraphTypes>> {
+        let adjacent = move |n: usize| self.incoming(n);
+        self.scan_adjacent(binders, spec, allow_repeated_nodes, filter, ctx, adjacent)
+    }
+
+    fn scan_directed_both(
+        &self,
+        binders: &Binders,
+        spec: &TripleFilter<AdjacencyListGraphTypes>,
+        allow_repeated_nodes: bool,
+        filter: &ValueFilter,
+        ctx: &dyn EvalContext,
+    ) -> impl Iterator<Item = Triple<AdjacencyListGraphTypes>> {
+        let adjacent = move |n: usize| self.outgoing(n).chain(self.incoming(n));
+        self.scan_adjacent(binders, spec, allow_repeated_nodes, filter, ctx, adjacent)
+    }
+
+    fn scan_undirected(
+        &self,
+        binders: &Binders,
+        spec: &TripleFilter<AdjacencyListGraphTypes>,
+        allow_repeated_nodes: bool,
+        filter: &ValueFilter,
+        ctx: &dyn EvalContext,
+    ) -> impl Iterator<Item = Triple<AdjacencyListGraphTypes>> {
+        let adjacent = move |n: usize| {
+            self.graph.nodes[n].undirected.iter().map(move |&e| {
+                let edge = &self.graph.edges[e];
+                (e, if edge.from == n { edge.to } else { edge.from })
+            })
+        };
+        self.scan_adjacent(binders, spec, allow_repeated_nodes, filter, ctx, adjacent)
+    }
+
+    fn get(
+        &self,
+        binder: &BindSpec<AdjacencyListGraphTypes>,
+        spec: &NodeFilter<AdjacencyListGraphTypes>,
+        ctx: &dyn EvalContext,
+    ) -> Vec<usize> {
+        (0..self.graph.nodes.len())
+            .filter(|&node| self.node_matches(binder, spec, node, ctx))
+            .collect()
+    }
+}
+
+type FxIndexSet<T> = IndexSet<T, FxBuildHasher>;
+
+impl GraphFilter<AdjacencyListGraphTypes> for AdjacencyListGraphEngine {
+    fn filter_path_nodes(
+        &self,
+        binders: &[BindSpec<AdjacencyListGraphTypes>],
+        spec: &ValueFilter,
+        mut bindings: FxIndexSet<PathPatternNodes<AdjacencyListGraphTypes>>,
+        ctx: &dyn EvalContext,
+    ) -> FxIndexSet<PathPatternNodes<AdjacencyListGraphTypes>> {
+        bindings.retain(|path_nodes| {
+            let values = path_nodes.iter().map(|elt| match elt {
+                GraphElement::Node(node) => self.node(node),
+                GraphElement::Edge(edge) => self.edge(edge),
+            });
+            spec.matches(binders.iter().map(|b| b.0.as_str()).zip(values), ctx)
+        });
+        bindings
+    }
+}
+
+impl AdjacencyListGraphEngine {
+    /// The directed edges from node `n`, with the nodes they lead to.
+    fn outgoing(&self, n: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let edges = &self.graph.edges;
+        let outgoing = self.graph.nodes[n].outgoing.iter();
+        outgoing.map(move |&e| (e, edges[e].to))
+    }
+
+    /// The directed edges to node `n`, with the nodes they lead from.
+    fn incoming(&self, n: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let edges = &self.graph.edges;
+        let incoming = self.graph.nodes[n].incoming.iter();
+        incoming.map(move |&e| (e, edges[e].from))
+    }
+
+    /// Scans the triples from each node matching the left-hand side of `spec` along the edges
+    /// given, with the nodes at their other ends, by `adjacent`.
+    fn scan_adjacent<'s, 'b, 'p, 'f, 'c, A, I>(
+        &'s self,
+        binders: &'b Binders,
+        spec: &'p TripleFilter<AdjacencyListGraphTypes>,
+        allow_repeated_nodes: bool,
+        filter: &'f ValueFilter,
+        ctx: &'c dyn EvalContext,
+        adjacent: A,
+    ) -> impl Iterator<Item = Triple<AdjacencyListGraphTypes>> + use<'s, 'b, 'p, 'f, 'c, A, I>
+    where
+        A: Fn(usize) -> I,
+        I: Iterator<Item = (usize, usize)>,
+    {
+        let (bl, be, br) = binders;
+        (0..self.graph.nodes.len())
+            .filter(move |&lhs| self.node_matches(bl, &spec.lhs, lhs, ctx))
+            .flat_map(move |lhs| adjacent(lhs).map(move |(e, rhs)| Triple { lhs, e, rhs }))
+            .filter(move |t| allow_repeated_nodes || t.lhs != t.rhs)
+            .filter(move |t| {
+                self.edge_matches(be, &spec.e, t.e, ctx)
+                    && self.node_matches(br, &spec.rhs, t.rhs, ctx)
+                    && self.triple_value_matches(binders, filter, t, ctx)
+            })
+    }
+
+    fn triple_value_matches(
+        &self,
+        (bl, be, br): &Binders,
+        filter: &ValueFilter,
+        triple: &Triple<AdjacencyListGraphTypes>,
+        ctx: &dyn EvalContext,
+    ) -> bool {
+        let bindings = [
+            (bl.0.as_str(), self.node(&triple.lhs)),
+            (be.0.as_str(), self.edge(&triple.e)),
+            (br.0.as_str(), self.node(&triple.rhs)),
+        ];
+        filter.matches(bindings, ctx)
+    }
+
+    fn node_matches(
+        &self,
+        binder: &BindSpec<AdjacencyListGraphTypes>,
+        spec: &NodeFilter<AdjacencyListGraphTypes>,
+        node: usize,
+        ctx: &dyn EvalContext,
+    ) -> bool {
+        let node = &self.graph.nodes[node];
+        spec.label.matches(&|l| node.labels.contains(l))
+            && spec.filter.matches([(binder.0.as_str(), &node.value)], ctx)
+    }
+
+    fn edge_matches(
+        &self,
+        binder: &BindSpec<AdjacencyListGraphTypes>,
+        spec: &EdgeFilter<AdjacencyListGraphTypes>,
+        edge: usize,
+        ctx: &dyn EvalContext,
+    ) -> bool {
+        let edge = &self.graph.edges[edge];
+        spec.label.matches(&|l| edge.labels.contains(l))
+            && spec.filter.matches([(binder.0.as_str(), &edge.value)], ctx)
+    }
+}
@@ -13,9 +13,24 @@ use crate::eval::graph::types::GraphTypes;
 use crate::eval::EvalContext;
 use partiql_value::{Bag, Tuple, Value};
 use rustc_hash::{FxBuildHasher, FxHashMap};
+use std::fmt::Debug;
 use std::marker::PhantomData;
 
+/// A graph against which [`PathPatternMatch`]s are evaluated, regardless of the [`GraphTypes`] of
+/// the [`GraphEngine`] which accesses it.
+pub trait GraphMatcher: Debug {
+    /// Evaluates `matcher` against the graph into a bag of tuples of the values bound by it.
+    fn eval(&self, matcher: &PathPatternMatch<StringGraphTypes>, ctx: &dyn EvalContext) -> Value;
+}
+
+impl<GT: GraphTypes, G: GraphEngine<GT>> GraphMatcher for GraphEvaluator<GT, G> {
+    fn eval(&self, matcher: &PathPatternMatch<StringGraphTypes>, ctx: &dyn EvalContext) -> Value {
+        GraphEvaluator::eval(self, matcher, ctx)
+    }
+}
+
 /// An evaluator for [`PathPatternMatch`]s over a graph.
+#[derive(Debug)]
 pub struct GraphEvaluator<GT: GraphTypes, G: GraphEngine<GT>> {
     graph: G,
     phantom: PhantomData<GT>,
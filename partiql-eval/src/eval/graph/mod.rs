@@ -1,7 +1,9 @@
-pub(crate) mod engine;
-pub(crate) mod evaluator;
-pub(crate) mod plan;
-pub(crate) mod result;
-pub(crate) mod simple_graph;
+pub mod adjacency_list;
+pub mod engine;
+pub mod evaluator;
+pub mod named;
+pub mod plan;
+pub mod result;
+pub mod simple_graph;
 pub mod string_graph;
 pub mod types;
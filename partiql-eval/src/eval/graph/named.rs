@@ -0,0 +1,69 @@
+use crate::eval::graph::engine::GraphEngine;
+use crate::eval::graph::evaluator::{GraphEvaluator, GraphMatcher};
+use crate::eval::graph::types::GraphTypes;
+use partiql_value::BindingsName;
+use rustc_hash::FxHashMap;
+use unicase::UniCase;
+
+/// Graphs held outside of `PartiQL` values (e.g., in an external store), by name.
+///
+/// When evaluated in a context holding `NamedGraphs` (see
+/// [`EvalContext::graphs`](crate::eval::EvalContext::graphs)), a `MATCH` expression over a variable
+/// (e.g., `(g MATCH (x) -> (y))`) whose name is that of one of the graphs is evaluated by that
+/// graph's [`GraphEngine`], instead of against the value of the variable.
+///
+/// # Examples
+/// ```
+/// use partiql_eval::eval::graph::adjacency_list::{AdjacencyListGraph, AdjacencyListGraphEngine};
+/// use partiql_eval::eval::graph::named::NamedGraphs;
+/// use std::sync::Arc;
+///
+/// let mut graph = AdjacencyListGraph::default();
+/// let a = graph.add_node(["Person"], Some("Alice".into()));
+/// let b = graph.add_node(["Person"], Some("Bob".into()));
+/// graph.add_edge(a, b, true, ["Knows"], None);
+///
+/// let mut graphs = NamedGraphs::new();
+/// graphs.insert("social", AdjacencyListGraphEngine::new(Arc::new(graph)));
+/// assert!(graphs.contains("SOCIAL"));
+/// ```
+#[derive(Debug, Default)]
+pub struct NamedGraphs {
+    graphs: FxHashMap<UniCase<String>, Box<dyn GraphMatcher>>,
+}
+
+impl NamedGraphs {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the graph accessed by `engine` under `name`, replacing any graph of the same name;
+    /// names are matched case-insensitively.
+    pub fn insert<GT, G>(&mut self, name: &str, engine: G)
+    where
+        GT: GraphTypes,
+        G: GraphEngine<GT> + 'static,
+    {
+        self.graphs.insert(
+            UniCase::new(name.to_string()),
+            Box::new(GraphEvaluator::new(engine)),
+        );
+    }
+
+    /// Returns whether there is a graph named `name`.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.graphs.contains_key(&UniCase::new(name.to_string()))
+    }
+
+    /// Returns the graph referenced by a variable named `name`, if any.
+    #[must_use]
+    pub fn get(&self, name: &BindingsName<'_>) -> Option<&dyn GraphMatcher> {
+        let (BindingsName::CaseSensitive(n) | BindingsName::CaseInsensitive(n)) = name;
+        let (key, graph) = self.graphs.get_key_value(&UniCase::new(n.to_string()))?;
+        name.matcher()
+            .matches(key.as_str())
+            .then_some(graph.as_ref())
+    }
+}
@@ -1,8 +1,13 @@
 use crate::eval::expr::EvalExpr;
 use crate::eval::graph::types::GraphTypes;
+use crate::eval::EvalContext;
+use partiql_value::datum::DatumTupleRef;
+use partiql_value::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
-use std::rc::Rc;
+use std::sync::Arc;
 
 /// A plan specification for an edge's direction filtering.
 #[allow(clippy::upper_case_acronyms)]
@@ -46,12 +51,56 @@ pub enum LabelFilter<GT: GraphTypes> {
     Never,
 }
 
+impl<GT: GraphTypes> LabelFilter<GT> {
+    /// Returns whether an element for which `has_label` holds exactly for its labels matches the
+    /// filter.
+    pub fn matches(&self, has_label: &impl Fn(&GT::Label) -> bool) -> bool {
+        match self {
+            LabelFilter::Always => true,
+            LabelFilter::Never => false,
+            LabelFilter::Named(l) => has_label(l),
+            LabelFilter::Negated(inner) => !inner.matches(has_label),
+            LabelFilter::Disjunction(inner) => inner.iter().any(|l| l.matches(has_label)),
+            LabelFilter::Conjunction(inner) => inner.iter().all(|l| l.matches(has_label)),
+        }
+    }
+}
+
 /// A plan specification for value filtering.
 #[derive(Debug, Clone, Default)]
 pub enum ValueFilter {
     #[default]
     Always,
-    Filter(Vec<Rc<dyn EvalExpr>>),
+    Filter(Vec<Arc<dyn EvalExpr>>),
+}
+
+impl ValueFilter {
+    /// Returns whether the filter's expressions all evaluate to `true` with each of the given
+    /// binder names bound to the payload of its element; elements without payloads are unbound.
+    pub fn matches<'a>(
+        &self,
+        bindings: impl IntoIterator<Item = (&'a str, &'a Option<Value>)>,
+        ctx: &dyn EvalContext,
+    ) -> bool {
+        let ValueFilter::Filter(exprs) = self else {
+            return true;
+        };
+        let map: HashMap<_, _> = bindings
+            .into_iter()
+            .filter_map(|(k, v)| v.as_ref().map(|v| (Cow::Borrowed(k), v)))
+            .collect();
+        let bindings = match map.len() {
+            0 => DatumTupleRef::Empty,
+            1 => {
+                let (key, payload) = map.into_iter().next().unwrap();
+                DatumTupleRef::SingleKey(key, payload)
+            }
+            _ => DatumTupleRef::Bindings(&map),
+        };
+        exprs
+            .iter()
+            .all(|expr| matches!(expr.evaluate(&bindings, ctx).as_ref(), Value::Boolean(true)))
+    }
 }
 
 /// A plan specification for node label & value filtering.
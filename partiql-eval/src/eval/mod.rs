@@ -10,6 +10,7 @@ use petgraph::prelude::StableGraph;
 use petgraph::{Directed, Incoming, Outgoing};
 use std::fmt::Debug;
 
+use partiql_logical::{Parameter, Type};
 use partiql_value::{BindingsName, Value};
 
 use crate::env::basic::MapBindings;
@@ -18,11 +19,14 @@ use petgraph::graph::NodeIndex;
 
 use crate::error::{EvalErr, EvaluationError};
 use partiql_catalog::context::{Bindings, SessionContext, SystemContext};
+use partiql_catalog::mutation::MutationSink;
 use petgraph::visit::EdgeRef;
 use rustc_hash::FxHashMap;
 use unicase::UniCase;
 
-use crate::eval::evaluable::{EvalType, Evaluable};
+use crate::eval::evaluable::{EvalType, Evaluable, ValueIter};
+use crate::eval::expr::{conforms_to, type_name, value_type_name};
+use crate::eval::graph::named::NamedGraphs;
 use crate::plan::EvaluationMode;
 
 #[cfg(feature = "serde")]
@@ -32,6 +36,7 @@ pub(crate) mod eval_expr_wrapper;
 pub mod evaluable;
 pub mod expr;
 pub mod graph;
+pub(crate) mod parallel;
 
 /// Represents a `PartiQL` evaluation query plan which is a plan that can be evaluated to produce
 /// a result. The plan uses a directed `petgraph::StableGraph`.
@@ -39,6 +44,10 @@ pub mod graph;
 pub struct EvalPlan {
     mode: EvaluationMode,
     plan_graph: StableGraph<Box<dyn Evaluable>, u8, Directed>,
+    /// The parameters of the query, against which bound values are checked before execution.
+    parameters: Option<Vec<Parameter>>,
+    /// The types of the parameters, as declared or inferred at planning.
+    parameter_types: FxHashMap<Parameter, Type>,
 }
 
 impl Default for EvalPlan {
@@ -61,7 +70,103 @@ impl EvalPlan {
         mode: EvaluationMode,
         plan_graph: StableGraph<Box<dyn Evaluable>, u8, Directed>,
     ) -> Self {
-        EvalPlan { mode, plan_graph }
+        EvalPlan {
+            mode,
+            plan_graph,
+            parameters: None,
+            parameter_types: Default::default(),
+        }
+    }
+
+    /// Declares `parameters` as the parameters of the plan; upon execution, the parameter values
+    /// of the [`EvalContext`] must bind exactly these parameters.
+    #[must_use]
+    pub fn with_parameters(mut self, parameters: Vec<Parameter>) -> Self {
+        self.parameters = Some(parameters);
+        self
+    }
+
+    /// Declares the types of the parameters of the plan; upon execution, a value bound to a typed
+    /// parameter must be of its type (or of a type of the same kind, e.g. any number for `INT`).
+    #[must_use]
+    pub fn with_parameter_types(mut self, parameter_types: FxHashMap<Parameter, Type>) -> Self {
+        self.parameter_types = parameter_types;
+        self
+    }
+
+    /// The type of the parameter `param`, if it was declared or inferred.
+    #[must_use]
+    pub fn parameter_type(&self, param: &Parameter) -> Option<&Type> {
+        self.parameter_types.get(param)
+    }
+
+    /// The declared parameters of the plan, if any.
+    #[must_use]
+    pub fn parameters(&self) -> Option<&[Parameter]> {
+        self.parameters.as_deref()
+    }
+
+    /// Checks that the parameter values of `ctx` bind exactly the declared parameters of the plan,
+    /// and that the values bound to typed parameters are of their types.
+    fn bind_parameters(&self, ctx: &dyn EvalContext) -> Result<(), EvalErr> {
+        let Some(declared) = &self.parameters else {
+            return Ok(());
+        };
+        let empty = Parameters::default();
+        let bound = ctx.parameters().unwrap_or(&empty);
+
+        let mut errors = vec![];
+        let positional = declared
+            .iter()
+            .filter(|p| matches!(p, Parameter::Positional(_)))
+            .count();
+        if positional != bound.positional.len() {
+            errors.push(EvaluationError::ParameterBinding(format!(
+                "expected {positional} positional parameter value(s), but {} were bound",
+                bound.positional.len()
+            )));
+        }
+        for param in declared {
+            if let Parameter::Named(name) = param {
+                if !bound.named.contains_key(&UniCase::new(name.clone())) {
+                    errors.push(EvaluationError::ParameterBinding(format!(
+                        "no value bound for parameter `{param}`"
+                    )));
+                }
+            }
+        }
+        for name in bound.named.keys() {
+            let param = Parameter::Named(name.to_string());
+            let declared = declared.iter().any(|p| match p {
+                Parameter::Named(n) => UniCase::new(n) == UniCase::new(name.as_str()),
+                Parameter::Positional(_) => false,
+            });
+            if !declared {
+                errors.push(EvaluationError::ParameterBinding(format!(
+                    "value bound for unknown parameter `{param}`"
+                )));
+            }
+        }
+
+        for param in declared {
+            let (Some(ty), Some(value)) = (self.parameter_types.get(param), bound.get(param))
+            else {
+                continue;
+            };
+            if !conforms_to(value, ty) {
+                errors.push(EvaluationError::ParameterBinding(format!(
+                    "parameter `{param}` expects a value of type {}, but a value of type {} was bound",
+                    type_name(ty),
+                    value_type_name(value)
+                )));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(EvalErr { errors })
+        }
     }
 
     #[inline]
@@ -86,6 +191,7 @@ impl EvalPlan {
         // that all v ∈ V \{v0} are reachable from v0. Note that this is the definition of trees
         // without the condition |E| = |V | − 1. Hence, all trees are DAGs.
         // Reference: https://link.springer.com/article/10.1007/s00450-009-0061-0
+        self.bind_parameters(ctx)?;
         let ops = toposort(&self.plan_graph, None).map_err(|e| EvalErr {
             errors: vec![EvaluationError::InvalidEvaluationPlan(format!(
                 "Malformed evaluation plan detected: {e:?}"
@@ -130,10 +236,8 @@ impl EvalPlan {
                 result = Some(src.evaluate(input, ctx));
 
                 // return on first evaluation error
-                if ctx.has_errors() && self.mode == EvaluationMode::Strict {
-                    return Err(EvalErr {
-                        errors: ctx.errors(),
-                    });
+                if let Some(err) = failure(self.mode == EvaluationMode::Strict, ctx) {
+                    return Err(err);
                 }
 
                 let num_destinations = destinations.len();
@@ -156,6 +260,73 @@ impl EvalPlan {
         Ok(Evaluated { result })
     }
 
+    /// Executes the plan lazily, returning an iterator over the values of the result.
+    ///
+    /// Rather than passing fully materialized collections between operators as
+    /// [`EvalPlan::execute`] does, operators pull binding tuples from their inputs one at a time.
+    /// Streaming operators (e.g., `Scan`, `Filter`, `Project`, `LIMIT`/`OFFSET`) never buffer
+    /// their input; blocking operators (e.g., `ORDER BY`, `GROUP BY`) buffer only the input they
+    /// require.
+    ///
+    /// If the result of the query is a collection, the iterator yields its elements; otherwise the
+    /// iterator yields the single result value.
+    pub fn execute_iter<'a>(
+        &'a self,
+        ctx: &'a dyn EvalContext,
+    ) -> Result<EvaluatedIter<'a>, EvalErr> {
+        self.bind_parameters(ctx)?;
+        let ops = toposort(&self.plan_graph, None).map_err(|e| EvalErr {
+            errors: vec![EvaluationError::InvalidEvaluationPlan(format!(
+                "Malformed evaluation plan detected: {e:?}"
+            ))],
+        })?;
+        let root = *ops
+            .last()
+            .ok_or_else(|| err_illegal_state("Error in retrieving eval output"))?;
+
+        // An operator whose output feeds more than one operator cannot be streamed to each of
+        // them; such plans are executed eagerly.
+        let fan_out = ops.iter().any(|idx| {
+            self.plan_graph
+                .edges_directed(*idx, Outgoing)
+                .nth(1)
+                .is_some()
+        });
+        let values = if fan_out {
+            Box::new(self.execute(ctx)?.result.into_iter())
+        } else {
+            self.stream(root, ctx)?
+        };
+
+        Ok(EvaluatedIter {
+            strict: self.mode == EvaluationMode::Strict,
+            ctx,
+            values,
+            done: false,
+        })
+    }
+
+    /// Creates the stream of values output by the operator at `idx` by (recursively) streaming
+    /// its inputs.
+    fn stream<'a>(
+        &'a self,
+        idx: NodeIndex,
+        ctx: &'a dyn EvalContext,
+    ) -> Result<ValueIter<'a>, EvalErr> {
+        let node = self.get_node(idx)?;
+        let mut inputs = [None, None];
+        // Some evaluables (i.e., `JOIN`) manage their own inputs
+        if node.eval_type() == EvalType::GraphManaged {
+            for edge in self.plan_graph().edges_directed(idx, Incoming) {
+                let input = inputs
+                    .get_mut(*edge.weight() as usize)
+                    .ok_or_else(|| err_illegal_state("Invalid input branch"))?;
+                *input = Some(self.stream(edge.source(), ctx)?);
+            }
+        }
+        Ok(node.evaluate_iter(inputs, ctx))
+    }
+
     #[must_use]
     pub fn to_dot_graph(&self) -> String {
         format!("{:?}", Dot::with_config(&self.plan_graph, &[]))
@@ -173,16 +344,140 @@ pub struct Evaluated {
     pub result: Value,
 }
 
+/// Represents the lazily evaluated result of [`EvalPlan::execute_iter`].
+///
+/// In [`EvaluationMode::Strict`], iteration ends with an `Err` upon the first evaluation error; in
+/// [`EvaluationMode::Permissive`], upon the first fatal one.
+pub struct EvaluatedIter<'a> {
+    strict: bool,
+    ctx: &'a dyn EvalContext,
+    values: ValueIter<'a>,
+    done: bool,
+}
+
+impl Debug for EvaluatedIter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvaluatedIter")
+            .field("strict", &self.strict)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl Iterator for EvaluatedIter<'_> {
+    type Item = Result<Value, EvalErr>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let next = self.values.next();
+        // return on first evaluation error
+        if let Some(err) = failure(self.strict, self.ctx) {
+            self.done = true;
+            return Some(Err(err));
+        }
+        if next.is_none() {
+            self.done = true;
+        }
+        next.map(Ok)
+    }
+}
+
+/// Returns the errors raised in `ctx` if they end evaluation: in strict mode, any error does; in
+/// permissive mode, only a fatal one (see [`EvaluationError::is_fatal`]) does.
+fn failure(strict: bool, ctx: &dyn EvalContext) -> Option<EvalErr> {
+    if !ctx.has_errors() {
+        return None;
+    }
+    if strict {
+        return Some(EvalErr {
+            errors: ctx.errors(),
+        });
+    }
+    let errors = ctx.errors();
+    if errors.iter().any(EvaluationError::is_fatal) {
+        Some(EvalErr { errors })
+    } else {
+        errors.into_iter().for_each(|err| ctx.add_error(err));
+        None
+    }
+}
+
 /// Represents an evaluation context that is used during evaluation of a plan.
 pub trait EvalContext: Bindings<Value> + SessionContext + Debug {
     fn add_error(&self, error: EvaluationError);
     fn has_errors(&self) -> bool;
     fn errors(&self) -> Vec<EvaluationError>;
+    /// The values bound to the parameters of the query, if any.
+    fn parameters(&self) -> Option<&Parameters> {
+        None
+    }
+    /// The sink through which data modification statements modify their targets, if any.
+    fn mutation_sink(&self) -> Option<&dyn MutationSink> {
+        None
+    }
+    /// The graphs held outside of values against which `MATCH` expressions are evaluated, if any.
+    fn graphs(&self) -> Option<&NamedGraphs> {
+        None
+    }
+    /// Creates a context, which may be sent to another thread, for evaluating a partition of an
+    /// operator's input in parallel; `None` if this context cannot be shared across threads
+    /// (e.g., as it holds user contexts), in which case operators evaluate sequentially.
+    fn partition_context(&self) -> Option<PartitionContext<'_>> {
+        None
+    }
+}
+
+/// Values bound to the parameters (i.e., `?` and `:name`) of a query for its execution.
+#[derive(Debug, Clone, Default)]
+pub struct Parameters {
+    positional: Vec<Value>,
+    named: FxHashMap<UniCase<String>, Value>,
+}
+
+impl Parameters {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds `value` to the next positional parameter.
+    pub fn push(&mut self, value: impl Into<Value>) {
+        self.positional.push(value.into());
+    }
+
+    /// Binds `value` to the named parameter `name`; names are matched case-insensitively.
+    pub fn insert(&mut self, name: &str, value: impl Into<Value>) {
+        self.named
+            .insert(UniCase::new(name.to_string()), value.into());
+    }
+
+    /// Returns the value bound to `param`, if any.
+    #[must_use]
+    pub fn get(&self, param: &Parameter) -> Option<&Value> {
+        match param {
+            Parameter::Positional(n) => n.checked_sub(1).and_then(|i| self.positional.get(i)),
+            Parameter::Named(name) => self.named.get(&UniCase::new(name.clone())),
+        }
+    }
+}
+
+impl From<Vec<Value>> for Parameters {
+    fn from(positional: Vec<Value>) -> Self {
+        Parameters {
+            positional,
+            named: Default::default(),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct BasicContext<'u> {
     pub bindings: MapBindings<Value>,
+    pub parameters: Parameters,
+    pub mutation_sink: Option<&'u dyn MutationSink>,
+    pub graphs: Option<&'u NamedGraphs>,
 
     pub sys: SystemContext,
     pub user: FxHashMap<UniCase<String>, &'u dyn Any>,
@@ -190,16 +485,42 @@ pub struct BasicContext<'u> {
     pub errors: RefCell<Vec<EvaluationError>>,
 }
 
-impl BasicContext<'_> {
+impl<'u> BasicContext<'u> {
     #[must_use]
     pub fn new(bindings: MapBindings<Value>, sys: SystemContext) -> Self {
         BasicContext {
             bindings,
+            parameters: Default::default(),
+            mutation_sink: None,
+            graphs: None,
             sys,
             user: Default::default(),
             errors: RefCell::new(vec![]),
         }
     }
+
+    /// Binds `parameters` to the parameters of the query to be executed in this context.
+    #[must_use]
+    pub fn with_parameters(mut self, parameters: Parameters) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Sets the sink through which data modification statements executed in this context modify
+    /// their targets.
+    #[must_use]
+    pub fn with_mutation_sink(mut self, sink: &'u dyn MutationSink) -> Self {
+        self.mutation_sink = Some(sink);
+        self
+    }
+
+    /// Sets the graphs against which `MATCH` expressions over variables of their names are
+    /// evaluated in this context.
+    #[must_use]
+    pub fn with_graphs(mut self, graphs: &'u NamedGraphs) -> Self {
+        self.graphs = Some(graphs);
+        self
+    }
 }
 
 impl SessionContext for BasicContext<'_> {
@@ -231,6 +552,28 @@ impl EvalContext for BasicContext<'_> {
     fn errors(&self) -> Vec<EvaluationError> {
         self.errors.take()
     }
+
+    fn parameters(&self) -> Option<&Parameters> {
+        Some(&self.parameters)
+    }
+
+    fn mutation_sink(&self) -> Option<&dyn MutationSink> {
+        self.mutation_sink
+    }
+
+    fn graphs(&self) -> Option<&NamedGraphs> {
+        self.graphs
+    }
+
+    fn partition_context(&self) -> Option<PartitionContext<'_>> {
+        let shared = self.user.is_empty() && self.mutation_sink.is_none() && self.graphs.is_none();
+        shared.then(|| PartitionContext {
+            bindings: vec![&self.bindings],
+            parameters: Some(&self.parameters),
+            sys: &self.sys,
+            errors: RefCell::new(vec![]),
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -269,6 +612,71 @@ impl EvalContext for NestedContext<'_> {
             fn add_error(&self, error: EvaluationError);
             fn has_errors(&self) -> bool;
             fn errors(&self) -> Vec<EvaluationError>;
+            fn parameters(&self) -> Option<&Parameters>;
+            fn mutation_sink(&self) -> Option<&dyn MutationSink>;
+            fn graphs(&self) -> Option<&NamedGraphs>;
         }
     }
+
+    fn partition_context(&self) -> Option<PartitionContext<'_>> {
+        let mut ctx = self.parent.partition_context()?;
+        ctx.bindings.insert(0, &self.bindings);
+        Some(ctx)
+    }
+}
+
+/// The context in which a partition of an operator's input is evaluated in parallel, created by
+/// [`EvalContext::partition_context`].
+///
+/// A `PartitionContext` shares the bindings, parameters and system context of the context it was
+/// created from, but collects its own errors; these are added to that context once the partition
+/// has been evaluated.
+#[derive(Debug)]
+pub struct PartitionContext<'c> {
+    /// The bindings in scope, innermost first.
+    bindings: Vec<&'c MapBindings<Value>>,
+    parameters: Option<&'c Parameters>,
+    sys: &'c SystemContext,
+    errors: RefCell<Vec<EvaluationError>>,
+}
+
+impl PartitionContext<'_> {
+    /// Consumes the context, returning the errors of the evaluation of its partition.
+    pub(crate) fn into_errors(self) -> Vec<EvaluationError> {
+        self.errors.into_inner()
+    }
+}
+
+impl SessionContext for PartitionContext<'_> {
+    fn system_context(&self) -> &SystemContext {
+        self.sys
+    }
+
+    fn user_context(&self, _name: &str) -> Option<&dyn Any> {
+        None
+    }
+}
+
+impl Bindings<Value> for PartitionContext<'_> {
+    fn get<'a>(&'a self, name: &BindingsName<'_>) -> Option<Cow<'a, Value>> {
+        self.bindings.iter().find_map(|bindings| bindings.get(name))
+    }
+}
+
+impl EvalContext for PartitionContext<'_> {
+    fn add_error(&self, error: EvaluationError) {
+        self.errors.borrow_mut().push(error);
+    }
+
+    fn has_errors(&self) -> bool {
+        !self.errors.borrow().is_empty()
+    }
+
+    fn errors(&self) -> Vec<EvaluationError> {
+        self.errors.take()
+    }
+
+    fn parameters(&self) -> Option<&Parameters> {
+        self.parameters
+    }
 }
@@ -0,0 +1,115 @@
+use crate::eval::{EvalContext, PartitionContext};
+use std::num::NonZeroUsize;
+use std::thread;
+
+/// The minimum number of input values per partition; smaller inputs are split across fewer
+/// threads, as the cost of spawning a thread would outweigh the work of the partition.
+const MIN_PARTITION_LEN: usize = 1024;
+
+/// The number of threads across which an operator partitions its input when evaluated by
+/// [`EvalPlan::execute`](crate::eval::EvalPlan::execute); an operator with a parallelism of `1`
+/// evaluates sequentially.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct Parallelism(NonZeroUsize);
+
+impl Default for Parallelism {
+    fn default() -> Self {
+        Parallelism(NonZeroUsize::MIN)
+    }
+}
+
+impl Parallelism {
+    pub(crate) fn new(threads: NonZeroUsize) -> Self {
+        Parallelism(threads)
+    }
+
+    pub(crate) fn is_parallel(&self) -> bool {
+        self.0.get() > 1
+    }
+
+    /// Creates a [`PartitionContext`] of `ctx` for each of the partitions into which an input of
+    /// `len` values is split, or `None` if the input is to be evaluated sequentially.
+    pub(crate) fn partition_contexts<'c>(
+        &self,
+        len: usize,
+        ctx: &'c dyn EvalContext,
+    ) -> Option<Vec<PartitionContext<'c>>> {
+        let partitions = self.0.get().min(len / MIN_PARTITION_LEN);
+        if partitions < 2 {
+            return None;
+        }
+        (0..partitions).map(|_| ctx.partition_context()).collect()
+    }
+
+    /// Splits `values` into contiguous partitions, one per context of
+    /// [`Parallelism::partition_contexts`], and evaluates `f` over each partition on its own
+    /// thread, returning the results of the partitions in order. If the input is to be evaluated
+    /// sequentially, `values` are returned as the error.
+    pub(crate) fn map_partitions<T, R, F>(
+        &self,
+        values: Vec<T>,
+        ctx: &dyn EvalContext,
+        f: F,
+    ) -> Result<Vec<R>, Vec<T>>
+    where
+        T: Send,
+        R: Send,
+        F: Fn(Vec<T>, &dyn EvalContext) -> R + Sync,
+    {
+        let Some(contexts) = self.partition_contexts(values.len(), ctx) else {
+            return Err(values);
+        };
+        let partition_len = values.len().div_ceil(contexts.len());
+        let mut values = values.into_iter();
+        let partitions = std::iter::repeat_with(|| values.by_ref().take(partition_len).collect())
+            .take(contexts.len())
+            .collect();
+        Ok(evaluate_partitions(partitions, contexts, ctx, f))
+    }
+}
+
+/// Evaluates `f` over each of `partitions` in its context of `contexts` on its own thread,
+/// returning the results of the partitions in order. The errors of each partition are added to
+/// `ctx`, in the order of the partitions.
+pub(crate) fn evaluate_partitions<P, R, F>(
+    partitions: Vec<P>,
+    contexts: Vec<PartitionContext<'_>>,
+    ctx: &dyn EvalContext,
+    f: F,
+) -> Vec<R>
+where
+    P: Send,
+    R: Send,
+    F: Fn(P, &dyn EvalContext) -> R + Sync,
+{
+    let f = &f;
+    let evaluated: Vec<_> = thread::scope(|s| {
+        let handles: Vec<_> = partitions
+            .into_iter()
+            .zip(contexts)
+            .map(|(partition, partition_ctx)| {
+                s.spawn(move || {
+                    let result = f(partition, &partition_ctx);
+                    (result, partition_ctx.into_errors())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| match handle.join() {
+                Ok(evaluated) => evaluated,
+                Err(panic) => std::panic::resume_unwind(panic),
+            })
+            .collect()
+    });
+
+    evaluated
+        .into_iter()
+        .map(|(result, errors)| {
+            for err in errors {
+                ctx.add_error(err);
+            }
+            result
+        })
+        .collect()
+}
@@ -83,6 +83,7 @@ mod tests {
             ),
             as_key: as_key.to_string(),
             at_key: None,
+            columns: None,
         })
     }
 
@@ -115,6 +116,23 @@ mod tests {
         bindings
     }
 
+    fn join_data_outer() -> MapBindings<Value> {
+        let customers = list![
+            tuple![("id", 5), ("name", "Joe")],
+            tuple![("id", 7), ("name", "Mary")],
+        ];
+
+        let orders = list![
+            tuple![("custId", 7), ("productId", 101)],
+            tuple![("custId", 9), ("productId", 523)],
+        ];
+
+        let mut bindings = MapBindings::default();
+        bindings.insert("customers", customers.into());
+        bindings.insert("orders", orders.into());
+        bindings
+    }
+
     fn join_data_sensors() -> MapBindings<Value> {
         let sensors = list![
             tuple![("readings", list![tuple![("v", 1.3)], tuple![("v", 2)],])],
@@ -177,6 +195,7 @@ mod tests {
             ),
             as_key: "data".to_string(),
             at_key: None,
+            columns: None,
         }));
 
         let project = plan.add_operator(Project(logical::Project {
@@ -688,6 +707,7 @@ mod tests {
                 ),
                 as_key: "data".to_string(),
                 at_key: None,
+                columns: None,
             }));
 
             let project = plan.add_operator(Project(logical::Project {
@@ -778,6 +798,7 @@ mod tests {
                 Box::new(path_var("c", "id")),
                 Box::new(path_var("o", "custId")),
             )),
+            using: vec![],
         }));
 
         let sink = lg.add_operator(BindingsOp::Sink);
@@ -809,6 +830,7 @@ mod tests {
             expr: path_var("s", "readings"),
             as_key: "r".to_string(),
             at_key: None,
+            columns: None,
         });
 
         let project = lg.add_operator(Project(logical::Project {
@@ -820,6 +842,7 @@ mod tests {
             left: Box::new(from_lhs),
             right: Box::new(from_rhs),
             on: None,
+            using: vec![],
         }));
 
         let sink = lg.add_operator(BindingsOp::Sink);
@@ -854,6 +877,7 @@ mod tests {
             expr: path_var("s", "readings"),
             as_key: "r".to_string(),
             at_key: None,
+            columns: None,
         });
 
         let project = lg.add_operator(Project(logical::Project {
@@ -865,6 +889,7 @@ mod tests {
             left: Box::new(from_lhs),
             right: Box::new(from_rhs),
             on: None,
+            using: vec![],
         }));
 
         let sink = lg.add_operator(BindingsOp::Sink);
@@ -901,6 +926,7 @@ mod tests {
             expr: path_var("s", "readings"),
             as_key: "r".to_string(),
             at_key: None,
+            columns: None,
         });
 
         let project = lg.add_operator(Project(logical::Project {
@@ -915,6 +941,7 @@ mod tests {
             left: Box::new(from_lhs),
             right: Box::new(from_rhs),
             on: Some(ValueExpr::Lit(Box::new(Value::from(true).into()))),
+            using: vec![],
         }));
 
         let sink = lg.add_operator(BindingsOp::Sink);
@@ -937,6 +964,74 @@ mod tests {
         });
     }
 
+    // Creates the plan: `SELECT c AS c, o AS o FROM customers AS c <kind> JOIN orders AS o
+    // ON c.id = o.custId`
+    fn outer_join_plan(kind: JoinKind) -> LogicalPlan<BindingsOp> {
+        let mut lg = LogicalPlan::new();
+
+        let var = |name: &str| {
+            ValueExpr::VarRef(
+                BindingsName::CaseInsensitive(name.to_string().into()),
+                VarRefType::Local,
+            )
+        };
+        let project = lg.add_operator(Project(logical::Project {
+            exprs: Vec::from([("c".to_string(), var("c")), ("o".to_string(), var("o"))]),
+        }));
+
+        let join = lg.add_operator(BindingsOp::Join(logical::Join {
+            kind,
+            left: Box::new(scan("customers", "c")),
+            right: Box::new(scan("orders", "o")),
+            on: Some(ValueExpr::BinaryExpr(
+                BinaryOp::Eq,
+                Box::new(path_var("c", "id")),
+                Box::new(path_var("o", "custId")),
+            )),
+            using: vec![],
+        }));
+
+        let sink = lg.add_operator(BindingsOp::Sink);
+        lg.add_flow_with_branch_num(join, project, 0);
+        lg.add_flow_with_branch_num(project, sink, 0);
+        lg
+    }
+
+    #[test]
+    fn select_with_right_join() {
+        let out = evaluate(outer_join_plan(JoinKind::Right), join_data_outer());
+        println!("{:?}", &out);
+
+        assert_matches!(out, Value::Bag(bag) => {
+            let expected = bag![
+                tuple![
+                    ("c", tuple![("id", 7), ("name", "Mary")]),
+                    ("o", tuple![("custId", 7), ("productId", 101)]),
+                ],
+                tuple![("c", Null), ("o", tuple![("custId", 9), ("productId", 523)])],
+            ];
+            assert_eq!(*bag, expected);
+        });
+    }
+
+    #[test]
+    fn select_with_full_join() {
+        let out = evaluate(outer_join_plan(JoinKind::Full), join_data_outer());
+        println!("{:?}", &out);
+
+        assert_matches!(out, Value::Bag(bag) => {
+            let expected = bag![
+                tuple![("c", tuple![("id", 5), ("name", "Joe")]), ("o", Null)],
+                tuple![
+                    ("c", tuple![("id", 7), ("name", "Mary")]),
+                    ("o", tuple![("custId", 7), ("productId", 101)]),
+                ],
+                tuple![("c", Null), ("o", tuple![("custId", 9), ("productId", 523)])],
+            ];
+            assert_eq!(*bag, expected);
+        });
+    }
+
     fn simple_case_expr_with_default() -> logical::SimpleCase {
         logical::SimpleCase {
             expr: Box::new(path_var("n", "a")),
@@ -1168,6 +1263,7 @@ mod tests {
             ),
             as_key: "data".to_string(),
             at_key: None,
+            columns: None,
         }));
 
         let project = plan.add_operator(Project(logical::Project {
@@ -1235,6 +1331,7 @@ mod tests {
             ),
             as_key: "data".to_string(),
             at_key: None,
+            columns: None,
         }));
 
         let project = plan.add_operator(Project(logical::Project {
@@ -1294,6 +1391,7 @@ mod tests {
             ),
             as_key: "data".to_string(),
             at_key: None,
+            columns: None,
         }));
 
         fn index_to_valueexpr(i: usize) -> ValueExpr {
@@ -2126,6 +2224,7 @@ mod tests {
             expr: ValueExpr::SubQueryExpr(logical::SubQueryExpr { plan: subq_plan }),
             as_key: "s".to_string(),
             at_key: None,
+            columns: None,
         });
 
         let join = lg.add_operator(BindingsOp::Join(logical::Join {
@@ -2133,6 +2232,7 @@ mod tests {
             left: Box::new(from_lhs),
             right: Box::new(from_rhs),
             on: None,
+            using: vec![],
         }));
 
         let ta = path_var("t", "a");
@@ -2323,7 +2423,7 @@ mod tests {
             let table_ref = EvalGlobalVarRef {
                 name: BindingsName::CaseInsensitive("someOrderedTable".to_string().into()),
             };
-            let path_to_scalar = EvalPath {
+            let path_to_scalar = EvalPath::<false> {
                 expr: Box::new(table_ref),
                 components: vec![
                     EvalPathComponent::Index(0),
@@ -2351,7 +2451,7 @@ mod tests {
             let table_ref = EvalGlobalVarRef {
                 name: BindingsName::CaseInsensitive("someOrderedTable".to_string().into()),
             };
-            let path_to_scalar = EvalPath {
+            let path_to_scalar = EvalPath::<false> {
                 expr: Box::new(table_ref),
                 components: vec![
                     EvalPathComponent::Index(0),
@@ -2435,4 +2535,114 @@ mod tests {
             assert_eq!(Value::Bag(Box::new(expected)), res);
         }
     }
+
+    mod streaming {
+        use std::borrow::Cow;
+        use std::sync::atomic::{AtomicI64, Ordering};
+        use std::sync::Arc;
+
+        use partiql_catalog::call_defs::{CallDef, CallSpec};
+        use partiql_catalog::catalog::MutableCatalog;
+        use partiql_catalog::context::SessionContext;
+        use partiql_catalog::table_fn::{
+            BaseTableExpr, BaseTableExprResult, BaseTableFunctionInfo, TableFunction,
+        };
+
+        use super::*;
+
+        /// A table function `counter()` of an unbounded number of rows, which counts the rows
+        /// pulled from it.
+        #[derive(Debug)]
+        struct CounterFunction {
+            call_def: CallDef,
+            pulled: Arc<AtomicI64>,
+        }
+
+        impl CounterFunction {
+            fn new(pulled: Arc<AtomicI64>) -> Self {
+                CounterFunction {
+                    call_def: CallDef {
+                        names: vec!["counter"],
+                        overloads: vec![CallSpec {
+                            input: vec![],
+                            output: Box::new(|args| {
+                                ValueExpr::Call(logical::CallExpr {
+                                    name: logical::CallName::ByName("counter".to_string()),
+                                    arguments: args,
+                                })
+                            }),
+                        }],
+                    },
+                    pulled,
+                }
+            }
+        }
+
+        impl BaseTableFunctionInfo for CounterFunction {
+            fn call_def(&self) -> &CallDef {
+                &self.call_def
+            }
+
+            fn plan_eval(&self) -> Box<dyn BaseTableExpr> {
+                Box::new(EvalCounter {
+                    pulled: self.pulled.clone(),
+                })
+            }
+        }
+
+        #[derive(Debug)]
+        struct EvalCounter {
+            pulled: Arc<AtomicI64>,
+        }
+
+        impl BaseTableExpr for EvalCounter {
+            fn evaluate<'c>(
+                &self,
+                _args: &[Cow<'_, Value>],
+                _ctx: &'c dyn SessionContext,
+            ) -> BaseTableExprResult<'c> {
+                let pulled = self.pulled.clone();
+                Ok(Box::new(std::iter::repeat_with(move || {
+                    let n = pulled.fetch_add(1, Ordering::Relaxed) + 1;
+                    Ok(Value::from(tuple![("n", n)]))
+                })))
+            }
+        }
+
+        #[test]
+        fn limit_pulls_only_required_rows_of_table_function() {
+            let pulled = Arc::new(AtomicI64::new(0));
+            let mut catalog = PartiqlCatalog::default();
+            catalog
+                .add_table_function(TableFunction::new(Box::new(CounterFunction::new(
+                    pulled.clone(),
+                ))))
+                .expect("table function");
+            let catalog = catalog.to_shared_catalog();
+
+            let query = "SELECT VALUE c.n FROM counter() AS c WHERE c.n > 1 LIMIT 3";
+            let parsed = partiql_parser::Parser::default()
+                .parse(query)
+                .expect("Expect no parse error");
+            let logical = partiql_logical_planner::LogicalPlanner::new(&catalog)
+                .lower(&parsed)
+                .expect("Expect no lowering error");
+            let mut planner = plan::EvaluatorPlanner::new(EvaluationMode::Strict, &catalog);
+            let plan = planner.compile(&logical).expect("Expect no plan error");
+
+            let sys = SystemContext {
+                now: DateTime::from_system_now_utc(),
+            };
+            let ctx = BasicContext::new(MapBindings::default(), sys);
+            let result = plan
+                .execute_iter(&ctx)
+                .expect("Expect no plan error")
+                .collect::<Result<Vec<_>, _>>()
+                .expect("Expect no evaluation error");
+
+            let expected: Vec<Value> = (2..=4).map(Value::from).collect();
+            assert_eq!(expected, result);
+            assert_eq!(4, pulled.load(Ordering::Relaxed));
+        }
+    }
 }
@@ -1,36 +1,44 @@
 use crate::error::{ErrorNode, PlanErr, PlanningError};
 use crate::eval;
 use crate::eval::evaluable::{
-    AggregateFunction, Any, Avg, Count, EvalGroupingStrategy, EvalJoinKind, EvalOrderBy,
-    EvalOrderBySortCondition, EvalOrderBySortSpec, EvalOuterExcept, EvalOuterIntersect,
-    EvalOuterUnion, EvalSubQueryExpr, Evaluable, Every, Max, Min, Sum,
+    AggregateFunction, Any, Avg, CatalogAggregate, Count, EvalDml, EvalDmlOp, EvalExcludeStep,
+    EvalGroupingStrategy, EvalHashJoinKeys, EvalJoinKind, EvalOrderBy, EvalOrderBySortCondition,
+    EvalOrderBySortSpec, EvalOuterExcept, EvalOuterIntersect, EvalOuterUnion, EvalPathStep,
+    EvalReturningElem, EvalSearchOrder, EvalSubQueryExpr, EvalUpdateChange, EvalWindowFrameBound,
+    EvalWindowFrameUnits, EvalWindowFunc, Evaluable, Every, Max, Min, Sum,
 };
 use crate::eval::expr::{
-    BindError, BindEvalExpr, EvalBagExpr, EvalBetweenExpr, EvalCollFn, EvalDynamicLookup, EvalExpr,
-    EvalExtractFn, EvalFnAbs, EvalFnBaseTableExpr, EvalFnCardinality, EvalFnExists, EvalFnOverlay,
-    EvalFnPosition, EvalFnSubstring, EvalGraphMatch, EvalIsTypeExpr, EvalLikeMatch,
-    EvalLikeNonStringNonLiteralMatch, EvalListExpr, EvalLitExpr, EvalOpBinary, EvalOpUnary,
-    EvalPath, EvalSearchedCaseExpr, EvalStringFn, EvalTrimFn, EvalTupleExpr, EvalVarRef,
+    BindError, BindEvalExpr, EvalBagExpr, EvalBetweenExpr, EvalCastExpr, EvalCollFn,
+    EvalDynamicLookup, EvalExpr, EvalExtractFn, EvalFnAbs, EvalFnBaseTableExpr, EvalFnCardinality,
+    EvalFnExists, EvalFnOverlay, EvalFnPosition, EvalFnSubstring, EvalGraphMatch, EvalIsTypeExpr,
+    EvalLikeMatch, EvalLikeNonStringNonLiteralMatch, EvalListExpr, EvalLitExpr, EvalOpBinary,
+    EvalOpUnary, EvalParameter, EvalPath, EvalSearchedCaseExpr, EvalStringFn, EvalTrimFn,
+    EvalTupleExpr, EvalVarRef,
 };
 use crate::eval::graph::plan::ValueFilter;
 use crate::eval::graph::string_graph::StringGraphTypes;
+use crate::eval::parallel::Parallelism;
 use crate::eval::EvalPlan;
 use eval::graph::plan as physical;
 use itertools::{Either, Itertools};
 use partiql_catalog::catalog::{FunctionEntryFunction, SharedCatalog};
+use partiql_common::syntax::location::{BytePosition, Location};
 use partiql_extension_ion::boxed_ion::BoxedIonType;
 use partiql_logical as logical;
 use partiql_logical::{
-    AggFunc, BagOperator, BinaryOp, BindingsOp, CallName, GraphMatchExpr, GroupingStrategy,
-    IsTypeExpr, JoinKind, Lit, LogicalPlan, OpId, PathComponent, Pattern, PatternMatchExpr,
-    ProjectAllMode, SearchedCase, SetQuantifier, SortSpecNullOrder, SortSpecOrder, Type, UnaryOp,
-    ValueExpr, VarRefType,
+    AggFunc, BagOperator, BinaryOp, BindingsOp, CallName, CastKind, GraphMatchExpr,
+    GroupingStrategy, IsTypeExpr, JoinKind, Lit, LogicalPlan, OpId, Parameter, PathComponent,
+    Pattern, PatternMatchExpr, ProjectAllMode, ReturningColumn, ReturningMapping, SearchedCase,
+    SetQuantifier, SortSpecNullOrder, SortSpecOrder, Type, UnaryOp, ValueExpr, VarRefType,
+    WindowFunc,
 };
 use partiql_value::boxed_variant::DynBoxedVariantTypeFactory;
-use partiql_value::{Bag, List, Tuple, Value, Variant};
+use partiql_value::{Bag, BindingsName, List, Tuple, Value, Variant};
 use petgraph::prelude::StableGraph;
 use rustc_hash::FxHashMap;
-use std::rc::Rc;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use unicase::UniCase;
 
 #[macro_export]
 macro_rules! correct_num_args_or_err {
@@ -53,16 +61,40 @@ macro_rules! correct_num_args_or_err {
     };
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum EvaluationMode {
     Strict,
     Permissive,
 }
 
+/// Whether the operators of a compiled plan evaluate their input on the calling thread or
+/// partitioned across threads.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum ExecutionMode {
+    #[default]
+    Sequential,
+    /// `Scan`, `Filter`, projection, `Unpivot`, `GROUP BY` and `ORDER BY` operators partition
+    /// sufficiently large inputs across up to the given number of threads when executed by
+    /// [`EvalPlan::execute`], with results identical to those of sequential execution.
+    ///
+    /// Operators are evaluated sequentially when streamed by [`EvalPlan::execute_iter`], or
+    /// executed in an [`EvalContext`](crate::eval::EvalContext) which cannot be shared across
+    /// threads (see [`EvalContext::partition_context`](crate::eval::EvalContext::partition_context)).
+    Parallel(NonZeroUsize),
+}
+
 pub struct EvaluatorPlanner<'c> {
     mode: EvaluationMode,
+    execution_mode: ExecutionMode,
     catalog: &'c dyn SharedCatalog,
     errors: Vec<PlanningError>,
+    /// The locations of the operators at which the `errors` occurred, as far as they are known.
+    error_locations: Vec<Option<Location<BytePosition>>>,
+    /// The distinct parameters of the plan being compiled, in order of their planning.
+    parameters: Vec<Parameter>,
+    /// The types of the parameters of the plan being compiled, as declared by `CAST`s or inferred
+    /// from the operands they are compared to.
+    parameter_types: FxHashMap<Parameter, Type>,
 }
 
 impl From<(&str, BindError)> for PlanningError {
@@ -129,8 +161,26 @@ impl<'c> EvaluatorPlanner<'c> {
     pub fn new(mode: EvaluationMode, catalog: &'c dyn SharedCatalog) -> Self {
         EvaluatorPlanner {
             mode,
+            execution_mode: ExecutionMode::default(),
             catalog,
             errors: vec![],
+            error_locations: vec![],
+            parameters: vec![],
+            parameter_types: Default::default(),
+        }
+    }
+
+    /// Sets the [`ExecutionMode`] of compiled plans; defaults to [`ExecutionMode::Sequential`].
+    #[must_use]
+    pub fn with_execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    fn parallelism(&self) -> Parallelism {
+        match self.execution_mode {
+            ExecutionMode::Sequential => Parallelism::default(),
+            ExecutionMode::Parallel(threads) => Parallelism::new(threads),
         }
     }
 
@@ -140,14 +190,32 @@ impl<'c> EvaluatorPlanner<'c> {
             EvaluationMode::Strict => self.plan_eval::<true>(plan),
             EvaluationMode::Permissive => self.plan_eval::<false>(plan),
         };
+        self.locate_errors(None);
         let errors = std::mem::take(&mut self.errors);
+        let locations = std::mem::take(&mut self.error_locations);
+        let parameter_types = std::mem::take(&mut self.parameter_types);
+        // positional parameters are declared in order, followed by named parameters
+        let mut parameters = std::mem::take(&mut self.parameters);
+        parameters.sort_by_key(|p| match p {
+            Parameter::Positional(n) => (0, *n),
+            Parameter::Named(_) => (1, 0),
+        });
         if !errors.is_empty() {
-            Err(PlanErr { errors })
+            Err(PlanErr { errors, locations })
         } else {
-            Ok(plan)
+            Ok(plan
+                .with_parameters(parameters)
+                .with_parameter_types(parameter_types))
         }
     }
 
+    /// Records `location` as the location of the errors since the last call.
+    #[inline]
+    fn locate_errors(&mut self, location: Option<&Location<BytePosition>>) {
+        self.error_locations
+            .resize(self.errors.len(), location.cloned());
+    }
+
     #[inline]
     fn plan_eval<const STRICT: bool>(&mut self, lg: &LogicalPlan<BindingsOp>) -> EvalPlan {
         let flows = lg.flows();
@@ -159,13 +227,23 @@ impl<'c> EvaluatorPlanner<'c> {
             let mut add_node = |op_id: &OpId| {
                 let logical_op = lg.operator(*op_id).unwrap();
                 *seen.entry(*op_id).or_insert_with(|| {
-                    plan_graph.add_node(self.get_eval_node::<{ STRICT }>(logical_op))
+                    let node = self.get_eval_node::<{ STRICT }>(logical_op);
+                    self.locate_errors(lg.location(*op_id));
+                    plan_graph.add_node(node)
                 })
             };
 
             let (s, d) = (add_node(s), add_node(d));
             plan_graph.add_edge(s, d, *branch_num);
         }
+        // A plan without flows (e.g., the plan of a `VALUES` subquery) consists of a single operator
+        if flows.is_empty() {
+            for (op_id, op) in lg.operators_by_id() {
+                let node = self.get_eval_node::<{ STRICT }>(op);
+                self.locate_errors(lg.location(op_id));
+                plan_graph.add_node(node);
+            }
+        }
         let mode = if STRICT {
             EvaluationMode::Strict
         } else {
@@ -180,37 +258,54 @@ impl<'c> EvaluatorPlanner<'c> {
                 expr,
                 as_key,
                 at_key,
+                columns,
             }) => {
-                if let Some(at_key) = at_key {
-                    Box::new(eval::evaluable::EvalScan::new_with_at_key(
+                let scan = if let Some(at_key) = at_key {
+                    eval::evaluable::EvalScan::new_with_at_key(
                         self.plan_value::<{ STRICT }>(expr),
                         as_key,
                         at_key,
-                    ))
+                    )
                 } else {
-                    Box::new(eval::evaluable::EvalScan::new(
-                        self.plan_value::<{ STRICT }>(expr),
-                        as_key,
-                    ))
-                }
+                    eval::evaluable::EvalScan::new(self.plan_value::<{ STRICT }>(expr), as_key)
+                };
+                Box::new(
+                    scan.with_columns(columns.clone())
+                        .with_parallelism(self.parallelism()),
+                )
             }
             BindingsOp::Project(logical::Project { exprs }) => {
                 let exprs: Vec<(_, _)> = exprs
                     .iter()
                     .map(|(k, v)| (k.clone(), self.plan_value::<{ STRICT }>(v)))
                     .collect();
-                Box::new(eval::evaluable::EvalSelect::new(exprs))
+                Box::new(
+                    eval::evaluable::EvalSelect::new(exprs).with_parallelism(self.parallelism()),
+                )
             }
-            BindingsOp::ProjectAll(mode) => Box::new(eval::evaluable::EvalSelectAll::new(
-                mode == &ProjectAllMode::PassThrough,
-            )),
+            BindingsOp::ProjectAll(logical::ProjectAll { mode, join_columns }) => Box::new(
+                eval::evaluable::EvalSelectAll::new(mode == &ProjectAllMode::PassThrough)
+                    .with_join_columns(join_columns.clone())
+                    .with_parallelism(self.parallelism()),
+            ),
             BindingsOp::ProjectValue(logical::ProjectValue { expr }) => {
                 let expr = self.plan_value::<{ STRICT }>(expr);
-                Box::new(eval::evaluable::EvalSelectValue::new(expr))
+                Box::new(
+                    eval::evaluable::EvalSelectValue::new(expr)
+                        .with_parallelism(self.parallelism()),
+                )
             }
             BindingsOp::Filter(logical::Filter { expr }) => Box::new(
-                eval::evaluable::EvalFilter::new(self.plan_value::<{ STRICT }>(expr)),
+                eval::evaluable::EvalFilter::new(self.plan_value::<{ STRICT }>(expr))
+                    .with_parallelism(self.parallelism()),
             ),
+            BindingsOp::Let(logical::Let { bindings }) => {
+                let bindings = bindings
+                    .iter()
+                    .map(|(k, v)| (k.clone(), self.plan_value::<{ STRICT }>(v)))
+                    .collect();
+                Box::new(eval::evaluable::EvalLet::new(bindings))
+            }
             BindingsOp::Having(logical::Having { expr }) => Box::new(
                 eval::evaluable::EvalHaving::new(self.plan_value::<{ STRICT }>(expr)),
             ),
@@ -226,16 +321,20 @@ impl<'c> EvaluatorPlanner<'c> {
                 expr,
                 as_key,
                 at_key,
-            }) => Box::new(eval::evaluable::EvalUnpivot::new(
-                self.plan_value::<{ STRICT }>(expr),
-                as_key,
-                at_key.clone(),
-            )),
+            }) => Box::new(
+                eval::evaluable::EvalUnpivot::new(
+                    self.plan_value::<{ STRICT }>(expr),
+                    as_key,
+                    at_key.clone(),
+                )
+                .with_parallelism(self.parallelism()),
+            ),
             BindingsOp::Join(logical::Join {
                 kind,
                 left,
                 right,
                 on,
+                using,
             }) => {
                 let kind = match kind {
                     // Model CROSS JOINs as INNER JOINs as mentioned by equivalence mentioned in
@@ -245,15 +344,27 @@ impl<'c> EvaluatorPlanner<'c> {
                     JoinKind::Right => EvalJoinKind::Right,
                     JoinKind::Full => EvalJoinKind::Full,
                 };
+                let hash_keys = on
+                    .as_ref()
+                    .and_then(|on| self.plan_hash_join_keys::<{ STRICT }>(left, right, on));
                 let on = on
                     .as_ref()
                     .map(|on_condition| self.plan_value::<{ STRICT }>(on_condition));
-                Box::new(eval::evaluable::EvalJoin::new(
+                let using = using
+                    .iter()
+                    .map(|(name, expr)| (name.clone(), self.plan_value::<{ STRICT }>(expr)))
+                    .collect();
+                let join = eval::evaluable::EvalJoin::new(
                     kind,
                     self.get_eval_node::<{ STRICT }>(left),
                     self.get_eval_node::<{ STRICT }>(right),
                     on,
-                ))
+                )
+                .with_using(using);
+                match hash_keys {
+                    Some(hash_keys) => Box::new(join.with_hash_keys(hash_keys)),
+                    None => Box::new(join),
+                }
             }
             BindingsOp::GroupBy(logical::GroupBy {
                 strategy,
@@ -270,22 +381,17 @@ impl<'c> EvaluatorPlanner<'c> {
                     .map(|(k, v)| (k.clone(), self.plan_value::<{ STRICT }>(v)))
                     .unzip();
 
-                let mut plan_agg = |a_e: &logical::AggregateExpression| {
-                    let func = match &a_e.func {
-                        AggFunc::AggAvg => Box::new(Avg {}) as Box<dyn AggregateFunction>,
-                        AggFunc::AggCount => Box::new(Count {}) as Box<dyn AggregateFunction>,
-                        AggFunc::AggMax => Box::new(Max {}) as Box<dyn AggregateFunction>,
-                        AggFunc::AggMin => Box::new(Min {}) as Box<dyn AggregateFunction>,
-                        AggFunc::AggSum => Box::new(Sum {}) as Box<dyn AggregateFunction>,
-                        AggFunc::AggAny => Box::new(Any {}) as Box<dyn AggregateFunction>,
-                        AggFunc::AggEvery => Box::new(Every {}) as Box<dyn AggregateFunction>,
-                    };
-                    eval::evaluable::AggregateExpression {
+                let mut plan_agg =
+                    |a_e: &logical::AggregateExpression| eval::evaluable::AggregateExpression {
                         name: a_e.name.to_string(),
                         expr: self.plan_value::<{ STRICT }>(&a_e.expr),
-                        func,
-                    }
-                };
+                        args: a_e
+                            .args
+                            .iter()
+                            .map(|arg| self.plan_value::<{ STRICT }>(arg))
+                            .collect(),
+                        func: self.plan_agg_func(&a_e.func),
+                    };
 
                 let (aggs, distinct_aggs) =
                     aggregate_exprs.iter().partition_map(|ae| match ae.setq {
@@ -296,42 +402,24 @@ impl<'c> EvaluatorPlanner<'c> {
                 let group_as_alias = group_as_alias
                     .as_ref()
                     .map(std::string::ToString::to_string);
-                Box::new(eval::evaluable::EvalGroupBy::new(
-                    strategy,
-                    exprs,
-                    aliases,
-                    aggs,
-                    distinct_aggs,
-                    group_as_alias,
-                ))
+                Box::new(
+                    eval::evaluable::EvalGroupBy::new(
+                        strategy,
+                        exprs,
+                        aliases,
+                        aggs,
+                        distinct_aggs,
+                        group_as_alias,
+                    )
+                    .with_parallelism(self.parallelism()),
+                )
             }
             BindingsOp::ExprQuery(logical::ExprQuery { expr }) => {
                 let expr = self.plan_value::<{ STRICT }>(expr);
                 Box::new(eval::evaluable::EvalExprQuery::new(expr))
             }
             BindingsOp::OrderBy(logical::OrderBy { specs }) => {
-                let cmp = specs
-                    .iter()
-                    .map(|spec| {
-                        let expr = self.plan_value::<{ STRICT }>(&spec.expr);
-                        let spec = match (&spec.order, &spec.null_order) {
-                            (SortSpecOrder::Asc, SortSpecNullOrder::First) => {
-                                EvalOrderBySortSpec::AscNullsFirst
-                            }
-                            (SortSpecOrder::Asc, SortSpecNullOrder::Last) => {
-                                EvalOrderBySortSpec::AscNullsLast
-                            }
-                            (SortSpecOrder::Desc, SortSpecNullOrder::First) => {
-                                EvalOrderBySortSpec::DescNullsFirst
-                            }
-                            (SortSpecOrder::Desc, SortSpecNullOrder::Last) => {
-                                EvalOrderBySortSpec::DescNullsLast
-                            }
-                        };
-                        EvalOrderBySortCondition { expr, spec }
-                    })
-                    .collect_vec();
-                Box::new(EvalOrderBy { cmp })
+                Box::new(self.plan_order_by::<{ STRICT }>(specs))
             }
             BindingsOp::LimitOffset(logical::LimitOffset { limit, offset }) => {
                 Box::new(eval::evaluable::EvalLimitOffset {
@@ -339,6 +427,40 @@ impl<'c> EvaluatorPlanner<'c> {
                     offset: offset.as_ref().map(|e| self.plan_value::<{ STRICT }>(e)),
                 })
             }
+            BindingsOp::Window(logical::Window { exprs }) => {
+                let exprs = exprs
+                    .iter()
+                    .map(|expr| self.plan_window_expr::<{ STRICT }>(expr))
+                    .collect();
+                Box::new(eval::evaluable::EvalWindow { exprs })
+            }
+            BindingsOp::Exclude(logical::Exclude { paths }) => {
+                let paths = paths
+                    .iter()
+                    .map(|path| {
+                        let root = EvalExcludeStep::Key(path.root.clone());
+                        let steps = path.steps.iter().map(|step| match step {
+                            logical::ExcludeStep::Key(key) => EvalExcludeStep::Key(key.clone()),
+                            logical::ExcludeStep::Index(idx) => EvalExcludeStep::Index(*idx),
+                            logical::ExcludeStep::AllElements => EvalExcludeStep::AllElements,
+                            logical::ExcludeStep::AllFields => EvalExcludeStep::AllFields,
+                        });
+                        std::iter::once(root).chain(steps).collect()
+                    })
+                    .collect();
+                Box::new(eval::evaluable::EvalExclude { paths })
+            }
+            BindingsOp::Dml(dml) => Box::new(self.plan_dml::<{ STRICT }>(dml)),
+            BindingsOp::With(logical::With { ctes, body }) => {
+                let ctes = ctes
+                    .iter()
+                    .map(|cte| self.plan_cte::<{ STRICT }>(cte))
+                    .collect();
+                Box::new(eval::evaluable::EvalWith {
+                    ctes,
+                    body: self.plan_eval::<{ STRICT }>(body),
+                })
+            }
             BindingsOp::BagOp(logical::BagOp {
                 bag_op: setop,
                 setq,
@@ -356,6 +478,310 @@ impl<'c> EvaluatorPlanner<'c> {
         }
     }
 
+    /// Plans the [`EvalHashJoinKeys`] of a join of `left` and `right` on the condition `on`, if
+    /// `on` has conjuncts equating an expression over the left side with one over the right side,
+    /// and neither side depends upon the bindings of the other.
+    fn plan_hash_join_keys<const STRICT: bool>(
+        &mut self,
+        left: &BindingsOp,
+        right: &BindingsOp,
+        on: &ValueExpr,
+    ) -> Option<EvalHashJoinKeys> {
+        let left_vars = binding_vars(left)?;
+        let right_vars = binding_vars(right)?;
+        let mut globals = vec![];
+        if !independent_of(left, &right_vars, &mut globals)
+            || !independent_of(right, &left_vars, &mut globals)
+        {
+            return None;
+        }
+
+        let mut conjuncts = vec![];
+        collect_conjuncts(on, &mut conjuncts);
+        let keys = conjuncts
+            .into_iter()
+            .filter_map(|conjunct| match conjunct {
+                ValueExpr::BinaryExpr(BinaryOp::Eq, lhs, rhs) => {
+                    let sides = |expr| referenced_sides(expr, &left_vars, &right_vars);
+                    match (sides(lhs), sides(rhs)) {
+                        (Some((true, false)), Some((false, true))) => Some((lhs, rhs)),
+                        (Some((false, true)), Some((true, false))) => Some((rhs, lhs)),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect_vec();
+        if keys.is_empty() {
+            return None;
+        }
+
+        let (left, right) = keys
+            .into_iter()
+            .map(|(l, r)| {
+                (
+                    self.plan_value::<{ STRICT }>(l),
+                    self.plan_value::<{ STRICT }>(r),
+                )
+            })
+            .unzip();
+        Some(EvalHashJoinKeys {
+            left,
+            right,
+            globals,
+        })
+    }
+
+    fn plan_dml<const STRICT: bool>(&mut self, dml: &logical::Dml) -> EvalDml {
+        let logical::Dml {
+            target,
+            op,
+            returning,
+        } = dml;
+        let op = match op {
+            logical::DmlOp::Insert {
+                values,
+                on_conflict,
+            } => EvalDmlOp::Insert {
+                values: self.plan_value::<{ STRICT }>(values),
+                on_conflict: on_conflict
+                    .as_ref()
+                    .map(|cond| self.plan_value::<{ STRICT }>(cond)),
+            },
+            logical::DmlOp::Update { filter, changes } => EvalDmlOp::Update {
+                filter: filter
+                    .as_ref()
+                    .map(|filter| self.plan_value::<{ STRICT }>(filter)),
+                changes: changes
+                    .iter()
+                    .map(|change| match change {
+                        logical::UpdateChange::Set(path, value) => EvalUpdateChange::Set(
+                            self.plan_assignment_path(path),
+                            self.plan_value::<{ STRICT }>(value),
+                        ),
+                        logical::UpdateChange::Remove(path) => {
+                            EvalUpdateChange::Remove(self.plan_assignment_path(path))
+                        }
+                    })
+                    .collect(),
+            },
+            logical::DmlOp::Delete { filter } => EvalDmlOp::Delete {
+                filter: filter
+                    .as_ref()
+                    .map(|filter| self.plan_value::<{ STRICT }>(filter)),
+            },
+        };
+        let returning = returning
+            .iter()
+            .map(|elem| {
+                let (modified_only, new) = match elem.mapping {
+                    ReturningMapping::ModifiedNew => (true, true),
+                    ReturningMapping::ModifiedOld => (true, false),
+                    ReturningMapping::AllNew => (false, true),
+                    ReturningMapping::AllOld => (false, false),
+                };
+                let expr = match &elem.column {
+                    ReturningColumn::Wildcard => None,
+                    ReturningColumn::Expr(name, expr) => {
+                        Some((name.clone(), self.plan_value::<{ STRICT }>(expr)))
+                    }
+                };
+                EvalReturningElem {
+                    modified_only,
+                    new,
+                    expr,
+                }
+            })
+            .collect();
+        EvalDml {
+            target: target.name.clone(),
+            as_key: target.as_key.clone(),
+            op,
+            returning,
+        }
+    }
+
+    fn plan_assignment_path(&mut self, path: &[PathComponent]) -> Vec<EvalPathStep> {
+        path.iter()
+            .filter_map(|component| match component {
+                PathComponent::Key(key) => Some(EvalPathStep::Key(key.clone())),
+                PathComponent::Index(idx) => Some(EvalPathStep::Index(*idx)),
+                PathComponent::KeyExpr(_) | PathComponent::IndexExpr(_) => {
+                    self.errors.push(PlanningError::NotYetImplemented(
+                        "Computed paths in SET and REMOVE clauses".to_string(),
+                    ));
+                    None
+                }
+                PathComponent::ForEach | PathComponent::Unpivot => {
+                    self.errors.push(PlanningError::NotYetImplemented(
+                        "Wildcard paths in SET and REMOVE clauses".to_string(),
+                    ));
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn plan_order_by<const STRICT: bool>(&mut self, specs: &[logical::SortSpec]) -> EvalOrderBy {
+        let cmp = specs
+            .iter()
+            .map(|spec| {
+                let expr = self.plan_value::<{ STRICT }>(&spec.expr);
+                let spec = match (&spec.order, &spec.null_order) {
+                    (SortSpecOrder::Asc, SortSpecNullOrder::First) => {
+                        EvalOrderBySortSpec::AscNullsFirst
+                    }
+                    (SortSpecOrder::Asc, SortSpecNullOrder::Last) => {
+                        EvalOrderBySortSpec::AscNullsLast
+                    }
+                    (SortSpecOrder::Desc, SortSpecNullOrder::First) => {
+                        EvalOrderBySortSpec::DescNullsFirst
+                    }
+                    (SortSpecOrder::Desc, SortSpecNullOrder::Last) => {
+                        EvalOrderBySortSpec::DescNullsLast
+                    }
+                };
+                EvalOrderBySortCondition { expr, spec }
+            })
+            .collect_vec();
+        EvalOrderBy {
+            cmp,
+            parallelism: self.parallelism(),
+        }
+    }
+
+    fn plan_agg_func(&mut self, func: &AggFunc) -> Box<dyn AggregateFunction> {
+        match func {
+            AggFunc::AggAvg => Box::new(Avg {}),
+            AggFunc::AggCount => Box::new(Count {}),
+            AggFunc::AggMax => Box::new(Max {}),
+            AggFunc::AggMin => Box::new(Min {}),
+            AggFunc::AggSum => Box::new(Sum {}),
+            AggFunc::AggAny => Box::new(Any {}),
+            AggFunc::AggEvery => Box::new(Every {}),
+            AggFunc::AggById(name, oid) => {
+                let expr =
+                    self.catalog
+                        .get_function_by_id(*oid)
+                        .and_then(|func| match func.entry() {
+                            FunctionEntryFunction::Aggregate(expr) => Some(expr.clone()),
+                            _ => None,
+                        });
+                if let Some(expr) = expr {
+                    Box::new(CatalogAggregate { expr })
+                } else {
+                    self.errors.push(PlanningError::IllegalState(format!(
+                        "Aggregation function {name} does not exist in catalog",
+                    )));
+                    // continue planning with `Avg` to report further errors
+                    Box::new(Avg {})
+                }
+            }
+        }
+    }
+
+    fn plan_window_expr<const STRICT: bool>(
+        &mut self,
+        expr: &logical::WindowExpression,
+    ) -> eval::evaluable::EvalWindowExpr {
+        let func = match &expr.func {
+            WindowFunc::RowNumber => EvalWindowFunc::RowNumber,
+            WindowFunc::Rank => EvalWindowFunc::Rank,
+            WindowFunc::DenseRank => EvalWindowFunc::DenseRank,
+            WindowFunc::Lag => EvalWindowFunc::Lag,
+            WindowFunc::Lead => EvalWindowFunc::Lead,
+            WindowFunc::FirstValue => EvalWindowFunc::FirstValue,
+            WindowFunc::LastValue => EvalWindowFunc::LastValue,
+            WindowFunc::Agg(func) => EvalWindowFunc::Agg {
+                func: self.plan_agg_func(func),
+                distinct: expr.setq == SetQuantifier::Distinct,
+            },
+        };
+        let bound = |bound: &logical::WindowFrameBound| match bound {
+            logical::WindowFrameBound::UnboundedPreceding => {
+                EvalWindowFrameBound::UnboundedPreceding
+            }
+            logical::WindowFrameBound::Preceding(n) => EvalWindowFrameBound::Preceding(*n),
+            logical::WindowFrameBound::CurrentRow => EvalWindowFrameBound::CurrentRow,
+            logical::WindowFrameBound::Following(n) => EvalWindowFrameBound::Following(*n),
+            logical::WindowFrameBound::UnboundedFollowing => {
+                EvalWindowFrameBound::UnboundedFollowing
+            }
+        };
+        let frame = eval::evaluable::EvalWindowFrame {
+            units: match expr.frame.units {
+                logical::WindowFrameUnits::Rows => EvalWindowFrameUnits::Rows,
+                logical::WindowFrameUnits::Range => EvalWindowFrameUnits::Range,
+            },
+            start: bound(&expr.frame.start),
+            end: bound(&expr.frame.end),
+        };
+        eval::evaluable::EvalWindowExpr {
+            name: expr.name.clone(),
+            func,
+            args: expr
+                .args
+                .iter()
+                .map(|arg| self.plan_value::<{ STRICT }>(arg))
+                .collect(),
+            partition_by: expr
+                .partition_by
+                .iter()
+                .map(|e| self.plan_value::<{ STRICT }>(e))
+                .collect(),
+            order_by: self.plan_order_by::<{ STRICT }>(&expr.order_by),
+            frame,
+        }
+    }
+
+    fn plan_cte<const STRICT: bool>(
+        &mut self,
+        cte: &logical::CommonTableExpr,
+    ) -> eval::evaluable::EvalCte {
+        let recursive = cte.recursive.as_ref().map(|step| {
+            let search = step
+                .search
+                .as_ref()
+                .map(|search| eval::evaluable::EvalCteSearch {
+                    order: match search.order {
+                        logical::SearchOrder::DepthFirst => EvalSearchOrder::DepthFirst,
+                        logical::SearchOrder::BreadthFirst => EvalSearchOrder::BreadthFirst,
+                    },
+                    by: search.by.clone(),
+                    set: search.set.clone(),
+                });
+            let cycle = step.cycle.as_ref().map(|cycle| {
+                let mut plan_mark_value = |lit| match plan_lit(lit) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        self.errors.push(e);
+                        Value::Missing
+                    }
+                };
+                eval::evaluable::EvalCteCycle {
+                    columns: cycle.columns.clone(),
+                    mark: cycle.mark.clone(),
+                    mark_value: plan_mark_value(&cycle.mark_value),
+                    default_value: plan_mark_value(&cycle.default_value),
+                    path: cycle.path.clone(),
+                }
+            });
+            eval::evaluable::EvalRecursiveStep {
+                plan: self.plan_eval::<{ STRICT }>(&step.plan),
+                setq: (&step.setq).into(),
+                search,
+                cycle,
+                parent: step.parent.clone(),
+            }
+        });
+        eval::evaluable::EvalCte {
+            name: cte.name.clone(),
+            columns: cte.columns.clone(),
+            plan: self.plan_eval::<{ STRICT }>(&cte.plan),
+            recursive,
+        }
+    }
+
     #[inline]
     fn err_nyi(&mut self, feature: &str) -> Box<ErrorNode> {
         let msg = format!("{feature} not yet implemented in evaluator");
@@ -387,6 +813,23 @@ impl<'c> EvaluatorPlanner<'c> {
             .collect_vec()
     }
 
+    /// If `param` is a parameter of no declared type and the type of `operand`, to which it is
+    /// compared, is statically known, infers that type as the type of the parameter.
+    fn infer_parameter_type(&mut self, param: &ValueExpr, operand: &ValueExpr) {
+        let ValueExpr::Parameter(param) = param else {
+            return;
+        };
+        let ty = match operand {
+            ValueExpr::Lit(lit) => lit_type(lit),
+            ValueExpr::CastExpr(c) if c.kind == CastKind::Cast => Some(c.cast_type.clone()),
+            ValueExpr::Parameter(other) => self.parameter_types.get(other).cloned(),
+            _ => None,
+        };
+        if let Some(ty) = ty {
+            self.parameter_types.entry(param.clone()).or_insert(ty);
+        }
+    }
+
     fn plan_value<const STRICT: bool>(&mut self, ve: &ValueExpr) -> Box<dyn EvalExpr> {
         let mut plan_args = |arguments: &[&ValueExpr]| {
             self.plan_values::<{ STRICT }, _>(arguments.iter().map(std::ops::Deref::deref))
@@ -397,10 +840,25 @@ impl<'c> EvaluatorPlanner<'c> {
                 "unary operator",
                 EvalOpUnary::from(op).bind::<{ STRICT }>(plan_args(&[operand])),
             ),
-            ValueExpr::BinaryExpr(op, lhs, rhs) => (
-                "binary operator",
-                EvalOpBinary::from(op).bind::<{ STRICT }>(plan_args(&[lhs, rhs])),
-            ),
+            ValueExpr::BinaryExpr(op, lhs, rhs) => {
+                let args = plan_args(&[lhs, rhs]);
+                if matches!(
+                    op,
+                    BinaryOp::Eq
+                        | BinaryOp::Neq
+                        | BinaryOp::Gt
+                        | BinaryOp::Gteq
+                        | BinaryOp::Lt
+                        | BinaryOp::Lteq
+                ) {
+                    self.infer_parameter_type(lhs, rhs);
+                    self.infer_parameter_type(rhs, lhs);
+                }
+                (
+                    "binary operator",
+                    EvalOpBinary::from(op).bind::<{ STRICT }>(args),
+                )
+            }
             ValueExpr::Lit(lit) => (
                 "literal",
                 match plan_lit(lit.as_ref()) {
@@ -410,7 +868,7 @@ impl<'c> EvaluatorPlanner<'c> {
             ),
             ValueExpr::Path(expr, components) => (
                 "path",
-                Ok(Box::new(EvalPath {
+                Ok(Box::new(EvalPath::<{ STRICT }> {
                     expr: self.plan_value::<{ STRICT }>(expr),
                     components: components
                         .iter()
@@ -425,6 +883,8 @@ impl<'c> EvaluatorPlanner<'c> {
                                     self.plan_value::<{ STRICT }>(i),
                                 )
                             }
+                            PathComponent::ForEach => eval::expr::EvalPathComponent::ForEach,
+                            PathComponent::Unpivot => eval::expr::EvalPathComponent::Unpivot,
                         })
                         .collect(),
                 }) as Box<dyn EvalExpr>),
@@ -477,6 +937,10 @@ impl<'c> EvaluatorPlanner<'c> {
             }
             ValueExpr::BetweenExpr(logical::BetweenExpr { value, from, to }) => {
                 let args = plan_args(&[value, from, to]);
+                self.infer_parameter_type(value, from);
+                self.infer_parameter_type(value, to);
+                self.infer_parameter_type(from, value);
+                self.infer_parameter_type(to, value);
                 ("between", EvalBetweenExpr {}.bind::<{ STRICT }>(args))
             }
             ValueExpr::PatternMatchExpr(PatternMatchExpr { value, pattern }) => {
@@ -502,11 +966,29 @@ impl<'c> EvaluatorPlanner<'c> {
                 let GraphMatchExpr { value, pattern } = graph_match.as_ref();
                 let args = plan_args(&[value]);
                 let expr = match self.plan_graph_plan::<{ STRICT }>(pattern) {
-                    Ok(pattern) => EvalGraphMatch::new(pattern).bind::<{ STRICT }>(args),
+                    Ok(pattern) => {
+                        let graph_match = EvalGraphMatch::new(pattern);
+                        let graph_match = match graph_var_name(value) {
+                            Some(name) => graph_match.with_name(name),
+                            None => graph_match,
+                        };
+                        graph_match.bind::<{ STRICT }>(args)
+                    }
                     Err(e) => Ok(self.err(e) as Box<dyn EvalExpr>),
                 };
                 ("graphmatch expr", expr)
             }
+            ValueExpr::Parameter(param) => {
+                if !self.parameters.contains(param) {
+                    self.parameters.push(param.clone());
+                }
+                (
+                    "parameter",
+                    Ok(Box::new(EvalParameter {
+                        param: param.clone(),
+                    }) as Box<dyn EvalExpr>),
+                )
+            }
             ValueExpr::SubQueryExpr(expr) => (
                 "subquery",
                 Ok(Box::new(EvalSubQueryExpr::new(
@@ -574,6 +1056,21 @@ impl<'c> EvaluatorPlanner<'c> {
                     invert: i.not,
                 }) as Box<dyn EvalExpr>),
             ),
+            ValueExpr::CastExpr(c) => {
+                if let (CastKind::Cast, ValueExpr::Parameter(param)) = (c.kind, c.expr.as_ref()) {
+                    // `CAST(? AS t)` declares the type of the parameter
+                    self.parameter_types
+                        .insert(param.clone(), c.cast_type.clone());
+                }
+                (
+                    "cast",
+                    Ok(Box::new(EvalCastExpr::<{ STRICT }> {
+                        expr: self.plan_value::<{ STRICT }>(c.expr.as_ref()),
+                        kind: c.kind,
+                        cast_type: c.cast_type.clone(),
+                    }) as Box<dyn EvalExpr>),
+                )
+            }
             ValueExpr::NullIfExpr(n) => {
                 // NULLIF can be rewritten using CASE WHEN expressions as per section 6.9 pg 142 of SQL-92 spec:
                 //     1) NULLIF (V1, V2) is equivalent to the following <case specification>:
@@ -749,8 +1246,12 @@ impl<'c> EvaluatorPlanner<'c> {
                                         expr: tbl_fn.plan_eval(),
                                     }) as Box<dyn EvalExpr>)
                                 }
-                                FunctionEntryFunction::Aggregate() => {
-                                    todo!("Aggregate functions in catalog by name")
+                                FunctionEntryFunction::Aggregate(_) => {
+                                    self.errors.push(PlanningError::IllegalState(format!(
+                                        "Aggregation function {name} called as a scalar function",
+                                    )));
+
+                                    Ok(Box::new(ErrorNode::new()) as Box<dyn EvalExpr>)
                                 }
                             },
                         };
@@ -775,8 +1276,12 @@ impl<'c> EvaluatorPlanner<'c> {
                                         Some(overload) => overload.clone().bind::<{ STRICT }>(args),
                                     }
                                 }
-                                FunctionEntryFunction::Aggregate() => {
-                                    todo!("Aggregate functions in catalog by id")
+                                FunctionEntryFunction::Aggregate(_) => {
+                                    self.errors.push(PlanningError::IllegalState(format!(
+                                        "Aggregation function {name} called as a scalar function",
+                                    )));
+
+                                    Ok(Box::new(ErrorNode::new()) as Box<dyn EvalExpr>)
                                 }
                             },
                             None => {
@@ -847,7 +1352,7 @@ impl<'c> EvaluatorPlanner<'c> {
             logical::graph::ValueFilter::Always => physical::ValueFilter::Always,
             logical::graph::ValueFilter::Filter(exprs) => {
                 let filters = self.plan_values::<{ STRICT }, _>(exprs.iter());
-                physical::ValueFilter::Filter(filters.into_iter().map(Rc::from).collect())
+                physical::ValueFilter::Filter(filters.into_iter().map(Arc::from).collect())
             }
         })
     }
@@ -973,6 +1478,19 @@ impl<'c> EvaluatorPlanner<'c> {
     }
 }
 
+/// The name of the variable referenced by the value of a `MATCH` expression, if it is one.
+fn graph_var_name(value: &ValueExpr) -> Option<BindingsName<'static>> {
+    match value {
+        ValueExpr::VarRef(name, _) => Some(name.clone()),
+        ValueExpr::DynamicLookup(lookups) => {
+            let mut names = lookups.iter().map(graph_var_name);
+            let name = names.next()??;
+            names.all(|n| n.as_ref() == Some(&name)).then_some(name)
+        }
+        _ => None,
+    }
+}
+
 fn plan_path_mode(
     path_mode: &logical::graph::PathMode,
 ) -> Result<physical::PathMode, PlanningError> {
@@ -984,6 +1502,23 @@ fn plan_path_mode(
     })
 }
 
+/// The type of the literal `lit`, if it is of a single type.
+fn lit_type(lit: &Lit) -> Option<Type> {
+    Some(match lit {
+        Lit::Null | Lit::Missing | Lit::Variant(..) => return None,
+        Lit::Int8(_) | Lit::Int16(_) => Type::Integer2Type,
+        Lit::Int32(_) => Type::Integer4Type,
+        Lit::Int64(_) => Type::Integer8Type,
+        Lit::Decimal(_) => Type::DecimalType(None, None),
+        Lit::Double(_) => Type::DoublePrecisionType,
+        Lit::Bool(_) => Type::BooleanType,
+        Lit::String(_) => Type::StringType,
+        Lit::Struct(_) => Type::StructType,
+        Lit::Bag(_) => Type::BagType,
+        Lit::List(_) => Type::ListType,
+    })
+}
+
 fn plan_lit(lit: &Lit) -> Result<Value, PlanningError> {
     let lit_to_val = |lit| plan_lit(lit);
     Ok(match lit {
@@ -1021,6 +1556,173 @@ fn plan_lit(lit: &Lit) -> Result<Value, PlanningError> {
     })
 }
 
+/// Returns the variables bound by the `FROM` source `op`, if they are known.
+fn binding_vars(op: &BindingsOp) -> Option<Vec<&str>> {
+    match op {
+        BindingsOp::Scan(logical::Scan { as_key, at_key, .. })
+        | BindingsOp::Unpivot(logical::Unpivot { as_key, at_key, .. }) => Some(
+            std::iter::once(as_key)
+                .chain(at_key)
+                .map(String::as_str)
+                .collect(),
+        ),
+        BindingsOp::Join(logical::Join {
+            left, right, using, ..
+        }) => {
+            let mut vars = binding_vars(left)?;
+            vars.extend(binding_vars(right)?);
+            vars.extend(using.iter().map(|(name, _)| name.as_str()));
+            Some(vars)
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the `FROM` source `op` can be evaluated independently of the bindings of
+/// `vars`, provided that the global variables it adds to `globals` resolve.
+fn independent_of(
+    op: &BindingsOp,
+    vars: &[&str],
+    globals: &mut Vec<BindingsName<'static>>,
+) -> bool {
+    match op {
+        BindingsOp::Scan(logical::Scan { expr, .. })
+        | BindingsOp::Unpivot(logical::Unpivot { expr, .. }) => match expr {
+            // a `FROM` source naming a variable not known to be local is looked up as a global,
+            // falling back to looking it up in the bindings in scope only if there is no such global
+            ValueExpr::DynamicLookup(lookups) => match lookups.as_slice() {
+                [ValueExpr::VarRef(name, VarRefType::Global), fallbacks @ ..] => {
+                    if fallbacks
+                        .iter()
+                        .any(|fallback| references_any(fallback, vars))
+                    {
+                        globals.push(name.clone());
+                    }
+                    true
+                }
+                _ => !references_any(expr, vars),
+            },
+            _ => !references_any(expr, vars),
+        },
+        BindingsOp::Join(logical::Join {
+            left,
+            right,
+            on,
+            using,
+            ..
+        }) => {
+            independent_of(left, vars, globals)
+                && independent_of(right, vars, globals)
+                && on.as_ref().is_none_or(|on| !references_any(on, vars))
+                && using.iter().all(|(_, expr)| !references_any(expr, vars))
+        }
+        _ => false,
+    }
+}
+
+/// Splits `expr` into the operands of its top-level `AND`s.
+fn collect_conjuncts<'e>(expr: &'e ValueExpr, conjuncts: &mut Vec<&'e ValueExpr>) {
+    match expr {
+        ValueExpr::BinaryExpr(BinaryOp::And, lhs, rhs) => {
+            collect_conjuncts(lhs, conjuncts);
+            collect_conjuncts(rhs, conjuncts);
+        }
+        _ => conjuncts.push(expr),
+    }
+}
+
+/// Returns whether `expr` references any of `vars`, which a subquery or graph match is assumed to.
+fn references_any(expr: &ValueExpr, vars: &[&str]) -> bool {
+    referenced_sides(expr, vars, &[]).is_none_or(|(refs_vars, _)| refs_vars)
+}
+
+/// Returns whether `expr` references any of `left_vars` and whether it references any of
+/// `right_vars`, or `None` if `expr` contains a subquery or graph match.
+fn referenced_sides(
+    expr: &ValueExpr,
+    left_vars: &[&str],
+    right_vars: &[&str],
+) -> Option<(bool, bool)> {
+    let mut refs = vec![];
+    if !collect_local_refs(expr, &mut refs) {
+        return None;
+    }
+    // compare names case-insensitively, as an over-approximation of the references of `expr`
+    let refs_any = |vars: &[&str]| {
+        refs.iter().any(|name| {
+            let name = match name {
+                BindingsName::CaseSensitive(s) | BindingsName::CaseInsensitive(s) => s,
+            };
+            vars.iter()
+                .any(|var| UniCase::new(name.as_ref()) == UniCase::new(var))
+        })
+    };
+    Some((refs_any(left_vars), refs_any(right_vars)))
+}
+
+/// Collects the names of the local variables referenced by `expr` into `refs`; returns `false` if
+/// `expr` contains a subquery or graph match, the references of which are not collected.
+fn collect_local_refs<'e>(expr: &'e ValueExpr, refs: &mut Vec<&'e BindingsName<'static>>) -> bool {
+    let mut collect = |expr: &'e ValueExpr| collect_local_refs(expr, refs);
+    match expr {
+        ValueExpr::VarRef(name, VarRefType::Local) => {
+            refs.push(name);
+            true
+        }
+        ValueExpr::VarRef(_, VarRefType::Global) | ValueExpr::Lit(_) | ValueExpr::Parameter(_) => {
+            true
+        }
+        ValueExpr::UnExpr(_, operand) => collect(operand),
+        ValueExpr::BinaryExpr(_, lhs, rhs) => collect(lhs) && collect(rhs),
+        ValueExpr::DynamicLookup(lookups) => lookups.iter().all(collect),
+        ValueExpr::Path(root, components) => {
+            collect(root)
+                && components.iter().all(|component| match component {
+                    PathComponent::KeyExpr(e) | PathComponent::IndexExpr(e) => collect(e),
+                    PathComponent::Key(_)
+                    | PathComponent::Index(_)
+                    | PathComponent::ForEach
+                    | PathComponent::Unpivot => true,
+                })
+        }
+        ValueExpr::TupleExpr(tuple) => tuple.attrs.iter().chain(&tuple.values).all(collect),
+        ValueExpr::ListExpr(list) => list.elements.iter().all(collect),
+        ValueExpr::BagExpr(bag) => bag.elements.iter().all(collect),
+        ValueExpr::BetweenExpr(between) => {
+            collect(&between.value) && collect(&between.from) && collect(&between.to)
+        }
+        ValueExpr::PatternMatchExpr(PatternMatchExpr { value, pattern }) => {
+            collect(value)
+                && match pattern {
+                    Pattern::Like(_) => true,
+                    Pattern::LikeNonStringNonLiteral(like) => {
+                        collect(&like.pattern) && collect(&like.escape)
+                    }
+                }
+        }
+        ValueExpr::SimpleCase(case) => {
+            collect(&case.expr)
+                && case
+                    .cases
+                    .iter()
+                    .all(|(when, then)| collect(when) && collect(then))
+                && case.default.as_deref().is_none_or(collect)
+        }
+        ValueExpr::SearchedCase(case) => {
+            case.cases
+                .iter()
+                .all(|(when, then)| collect(when) && collect(then))
+                && case.default.as_deref().is_none_or(collect)
+        }
+        ValueExpr::IsTypeExpr(is_type) => collect(&is_type.expr),
+        ValueExpr::CastExpr(cast) => collect(&cast.expr),
+        ValueExpr::NullIfExpr(nullif) => collect(&nullif.lhs) && collect(&nullif.rhs),
+        ValueExpr::CoalesceExpr(coalesce) => coalesce.elements.iter().all(collect),
+        ValueExpr::Call(call) => call.arguments.iter().all(collect),
+        ValueExpr::SubQueryExpr(_) | ValueExpr::GraphMatch(_) => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
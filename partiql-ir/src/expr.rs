@@ -0,0 +1,263 @@
+use crate::graph::PathPatternMatch;
+use crate::{Name, ObjectIdx, Plan};
+use serde::{Deserialize, Serialize};
+
+/// An expression, evaluating to a `PartiQL` value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Expr {
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Lit(Lit),
+    /// The value of the first of the expressions whose value is not `MISSING`
+    DynamicLookup(Vec<Expr>),
+    Path(Box<Expr>, Vec<PathStep>),
+    VarRef(Name, VarRefKind),
+    Parameter(Parameter),
+    /// `{<attr>: <value>, ...}`
+    Tuple(Vec<(Expr, Expr)>),
+    List(Vec<Expr>),
+    Bag(Vec<Expr>),
+    /// `<value> BETWEEN <from> AND <to>`
+    Between {
+        value: Box<Expr>,
+        from: Box<Expr>,
+        to: Box<Expr>,
+    },
+    /// `<value> LIKE <pattern>`
+    Like {
+        value: Box<Expr>,
+        pattern: LikePattern,
+    },
+    SubQuery(Plan),
+    /// `CASE <expr> WHEN <expr> THEN <expr> ... ELSE <default> END`
+    SimpleCase {
+        expr: Box<Expr>,
+        cases: Vec<(Expr, Expr)>,
+        default: Option<Box<Expr>>,
+    },
+    /// `CASE WHEN <expr> THEN <expr> ... ELSE <default> END`
+    SearchedCase {
+        cases: Vec<(Expr, Expr)>,
+        default: Option<Box<Expr>>,
+    },
+    /// `<expr> IS [NOT] <is_type>`
+    IsType {
+        not: bool,
+        expr: Box<Expr>,
+        is_type: Type,
+    },
+    Cast {
+        kind: CastKind,
+        expr: Box<Expr>,
+        cast_type: Type,
+    },
+    NullIf(Box<Expr>, Box<Expr>),
+    Coalesce(Vec<Expr>),
+    Call(Func, Vec<Expr>),
+    /// `<value> MATCH <pattern>`
+    GraphMatch {
+        value: Box<Expr>,
+        pattern: Box<PathPatternMatch>,
+    },
+}
+
+/// A literal value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Lit {
+    Null,
+    Missing,
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    /// A decimal in its canonical textual representation, e.g. `"1.50"`
+    Decimal(String),
+    Double(f64),
+    Bool(bool),
+    String(String),
+    /// A value of a type provided by an extension, encoded as bytes
+    Variant {
+        bytes: Vec<u8>,
+        type_name: String,
+    },
+    Struct(Vec<(String, Lit)>),
+    Bag(Vec<Lit>),
+    List(Vec<Lit>),
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum UnaryOp {
+    Pos,
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BinaryOp {
+    And,
+    Or,
+    Concat,
+    Eq,
+    Neq,
+    Gt,
+    Gteq,
+    Lt,
+    Lteq,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Exp,
+    In,
+}
+
+/// A step of a path expression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PathStep {
+    /// E.g. `b` in `a.b`
+    Key(Name),
+    /// E.g. `4` in `a[4]`
+    Index(i64),
+    /// E.g. `b || c` in `a[b || c]`, when the value of the expression is a string
+    KeyExpr(Box<Expr>),
+    /// E.g. `b + 1` in `a[b + 1]`, when the value of the expression is an integer
+    IndexExpr(Box<Expr>),
+    /// E.g. `[*]` in `a[*].b`
+    ForEach,
+    /// E.g. `.*` in `a.*.b`
+    Unpivot,
+}
+
+/// Whether a variable is looked up in the global environment or the local scope.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum VarRefKind {
+    Global,
+    Local,
+}
+
+/// A query parameter, bound to a value when the plan is executed.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Parameter {
+    /// `?`, numbered from 1 in order of appearance in the query
+    Positional(u32),
+    /// E.g. `:name`
+    Named(String),
+}
+
+/// The pattern of a `LIKE` expression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LikePattern {
+    /// A pattern and escape which are string literals
+    Lit { pattern: String, escape: String },
+    /// A pattern or escape which is not a string literal
+    Expr {
+        pattern: Box<Expr>,
+        escape: Box<Expr>,
+    },
+}
+
+/// A type, with the optional parameters given in the query, e.g. `Some(20)` for `VARCHAR(20)`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Type {
+    Null,
+    Boolean,
+    Integer2,
+    Integer4,
+    Integer8,
+    Decimal(Option<u32>, Option<u32>),
+    Numeric(Option<u32>, Option<u32>),
+    Real,
+    DoublePrecision,
+    Timestamp(Option<u32>),
+    Character(Option<u32>),
+    CharacterVarying(Option<u32>),
+    Missing,
+    String,
+    Symbol,
+    Blob,
+    Clob,
+    Date,
+    Time(Option<u32>),
+    ZonedTime(Option<u32>),
+    ZonedTimestamp(Option<u32>),
+    Struct,
+    Tuple,
+    List,
+    Sexp,
+    Bag,
+    Any,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum CastKind {
+    /// `CAST`
+    Cast,
+    /// `CAN_CAST`
+    CanCast,
+    /// `CAN_LOSSLESS_CAST`
+    CanLosslessCast,
+}
+
+/// Indicates if a set should be reduced to its distinct elements or not.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SetQuantifier {
+    All,
+    Distinct,
+}
+
+/// A function called by an [`Expr::Call`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Func {
+    Lower,
+    Upper,
+    CharLength,
+    OctetLength,
+    BitLength,
+    LTrim,
+    BTrim,
+    RTrim,
+    Substring,
+    Position,
+    Overlay,
+    Exists,
+    Abs,
+    Mod,
+    Cardinality,
+    ExtractYear,
+    ExtractMonth,
+    ExtractDay,
+    ExtractHour,
+    ExtractMinute,
+    ExtractSecond,
+    ExtractTimezoneHour,
+    ExtractTimezoneMinute,
+    CollAvg(SetQuantifier),
+    CollCount(SetQuantifier),
+    CollMax(SetQuantifier),
+    CollMin(SetQuantifier),
+    CollSum(SetQuantifier),
+    CollAny(SetQuantifier),
+    CollEvery(SetQuantifier),
+    /// A function which is resolved by name during evaluation
+    ByName(String),
+    /// The `overload`-th overload of a function in the catalog
+    Catalog {
+        object: ObjectIdx,
+        overload: u32,
+    },
+}
+
+/// An aggregation function.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AggFunc {
+    Avg,
+    Count,
+    Max,
+    Min,
+    Sum,
+    Any,
+    Every,
+    /// An aggregation function in the catalog
+    Catalog(ObjectIdx),
+}
@@ -0,0 +1,93 @@
+//! Graph pattern matching (GPML) patterns of an [`Expr::GraphMatch`](crate::Expr::GraphMatch).
+
+use crate::Expr;
+use serde::{Deserialize, Serialize};
+
+/// The directions in which an edge pattern matches edges.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum DirectionFilter {
+    /// `<-`
+    Left,
+    /// `~`
+    Undirected,
+    /// `->`
+    Right,
+    /// `<~`
+    LeftOrUndirected,
+    /// `~>`
+    UndirectedOrRight,
+    /// `<->`
+    LeftOrRight,
+    /// `-`
+    Any,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum PathMode {
+    Walk,
+    Trail,
+    Acyclic,
+    Simple,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum LabelFilter {
+    Always,
+    Named(String),
+    Negated(Box<LabelFilter>),
+    Conjunction(Vec<LabelFilter>),
+    Disjunction(Vec<LabelFilter>),
+    Never,
+}
+
+/// The conditions on the variables bound by a pattern, or `None` if there are none.
+pub type ValueFilter = Option<Vec<Expr>>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementFilter {
+    pub label: LabelFilter,
+    pub filter: ValueFilter,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TripleFilter {
+    pub lhs: ElementFilter,
+    pub edge: ElementFilter,
+    pub rhs: ElementFilter,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StepFilter {
+    pub dir: DirectionFilter,
+    pub triple: TripleFilter,
+}
+
+/// A node or edge pattern, binding the matched elements to `binder`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElementMatch {
+    pub binder: String,
+    pub spec: ElementFilter,
+}
+
+/// A node-edge-node pattern, binding the matched elements to `binders`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TripleMatch {
+    pub binders: (String, String, String),
+    pub spec: StepFilter,
+    pub filter: ValueFilter,
+    pub path_mode: PathMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TripleSeriesMatch {
+    pub triples: Vec<TripleMatch>,
+    pub filter: ValueFilter,
+    pub path_mode: PathMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PathPatternMatch {
+    Node(ElementMatch),
+    Match(TripleMatch),
+    Concat(Vec<TripleSeriesMatch>, PathMode),
+}
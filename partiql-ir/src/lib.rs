@@ -1,8 +1,226 @@
 #![deny(rust_2018_idioms)]
 #![deny(clippy::all)]
 
+//! A serializable intermediate representation (IR) of `PartiQL` logical plans.
+//!
+//! The IR describes a compiled query independently of the process that compiled it, so that a
+//! query may be parsed and planned once (e.g., in a control plane), shipped to other processes as
+//! bytes in any [`serde`] format, and executed there without re-parsing. Its three main entities
+//! are [`IrPlan`], [`Operator`] and [`Expr`], which mirror `partiql-logical`'s `LogicalPlan`,
+//! `BindingsOp` and `ValueExpr`.
+//!
+//! Unlike the logical plan, the IR does not hold identifiers which are only meaningful to the
+//! catalog of the compiling process: catalog functions are referenced by name through the plan's
+//! table of [`ObjectRef`]s, and are resolved against the catalog of the process loading the plan.
+//!
+//! The IR is versioned by [`IrVersion`]; see [`IR_VERSION`] for the compatibility guarantees.
+
+mod expr;
+pub mod graph;
+mod op;
+
+pub use expr::*;
+pub use op::*;
+
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// The version of the IR defined by this crate.
+///
+/// The minor version increases with additions to the IR (e.g., a new operator) which plans
+/// produced by older versions do not use; the major version increases with any other change.
+/// Hence a plan can be loaded by any version of the IR with the same major version and an equal
+/// or greater minor version.
+///
+/// Version 1.0 is the IR as first released with this crate; changes made before that release
+/// are part of it.
+pub const IR_VERSION: IrVersion = IrVersion { major: 1, minor: 0 };
+
+/// The version of an [`IrPlan`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct IrVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl IrVersion {
+    /// Returns whether a plan of version `other` can be read as a plan of this version.
+    #[must_use]
+    pub fn can_read(&self, other: &IrVersion) -> bool {
+        self.major == other.major && self.minor >= other.minor
+    }
+}
+
+impl Display for IrVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// A versioned, self-contained `PartiQL` query plan.
+///
+/// # Examples
+/// ```
+/// use partiql_ir::{Expr, IrPlan, Lit, Operator, Plan, ProjectValue, IR_VERSION};
+///
+/// // Plan for `SELECT VALUE 1 FROM <<2>>`, as it could be read back from JSON
+/// let mut plan = Plan::default();
+/// let scan = plan.add_operator(Operator::Scan(partiql_ir::Scan {
+///     expr: Expr::Bag(vec![Expr::Lit(Lit::Int64(2))]),
+///     as_key: "x".to_string(),
+///     at_key: None,
+///     columns: None,
+/// }));
+/// let project = plan.add_operator(Operator::ProjectValue(ProjectValue {
+///     expr: Expr::Lit(Lit::Int64(1)),
+/// }));
+/// let sink = plan.add_operator(Operator::Sink);
+/// plan.add_flow(scan, project, 0);
+/// plan.add_flow(project, sink, 0);
+///
+/// let ir = IrPlan::new(plan, vec![]);
+/// assert_eq!(ir.version, IR_VERSION);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IrPlan {
+    /// The version of the IR in which the plan is expressed
+    pub version: IrVersion,
+    /// The catalog objects referenced by the plan, indexed by [`ObjectIdx`]
+    pub objects: Vec<ObjectRef>,
+    pub plan: Plan,
+}
+
+impl IrPlan {
+    /// Creates a plan of the current [`IR_VERSION`].
+    #[must_use]
+    pub fn new(plan: Plan, objects: Vec<ObjectRef>) -> Self {
+        IrPlan {
+            version: IR_VERSION,
+            objects,
+            plan,
+        }
+    }
+
+    /// Returns the catalog object referenced by `idx`.
+    #[must_use]
+    pub fn object(&self, idx: ObjectIdx) -> Option<&ObjectRef> {
+        self.objects.get(idx.0 as usize)
+    }
+}
+
+/// A graph of [`Operator`]s connected by data [`Flow`]s.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    pub operators: Vec<Operator>,
+    pub flows: Vec<Flow>,
+}
+
+impl Plan {
+    /// Adds an operator to the plan.
+    pub fn add_operator(&mut self, op: Operator) -> OperatorIdx {
+        self.operators.push(op);
+        OperatorIdx(self.operators.len() as u32 - 1)
+    }
+
+    /// Adds a data flow from `src` into the `branch`-th input of `dst`.
+    pub fn add_flow(&mut self, src: OperatorIdx, dst: OperatorIdx, branch: u8) {
+        self.flows.push(Flow { src, dst, branch });
+    }
+
+    /// Returns the operator at `idx`.
+    #[must_use]
+    pub fn operator(&self, idx: OperatorIdx) -> Option<&Operator> {
+        self.operators.get(idx.0 as usize)
+    }
+}
+
+/// The index of an [`Operator`] in the operators of a [`Plan`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct OperatorIdx(pub u32);
+
+/// A flow of data from the output of operator `src` into the `branch`-th input of operator `dst`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Flow {
+    pub src: OperatorIdx,
+    pub dst: OperatorIdx,
+    pub branch: u8,
+}
+
+/// The index of an [`ObjectRef`] in the objects of an [`IrPlan`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ObjectIdx(pub u32);
+
+/// A reference to an object of the catalog against which a plan is loaded.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ObjectRef {
+    pub kind: ObjectKind,
+    /// The name under which the object is registered in the catalog
+    pub name: String,
+}
+
+/// The kind of a catalog object referenced by a plan.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ObjectKind {
+    /// A scalar function
+    ScalarFunction,
+    /// An aggregation function
+    AggregateFunction,
+}
+
+/// A name, matched either case-sensitively (e.g., `"a"`) or case-insensitively (e.g., `a`).
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Name {
+    CaseSensitive(String),
+    CaseInsensitive(String),
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
-    fn todo() {}
+    fn versions() {
+        let v1_0 = IrVersion { major: 1, minor: 0 };
+        let v1_1 = IrVersion { major: 1, minor: 1 };
+        let v2_0 = IrVersion { major: 2, minor: 0 };
+        assert!(v1_1.can_read(&v1_0));
+        assert!(v1_1.can_read(&v1_1));
+        assert!(!v1_0.can_read(&v1_1));
+        assert!(!v2_0.can_read(&v1_0));
+        assert_eq!(v1_1.to_string(), "1.1");
+    }
+
+    #[test]
+    fn plans() {
+        let mut plan = Plan::default();
+        let scan = plan.add_operator(Operator::Scan(Scan {
+            expr: Expr::VarRef(Name::CaseInsensitive("t".to_string()), VarRefKind::Global),
+            as_key: "x".to_string(),
+            at_key: None,
+            columns: None,
+        }));
+        let sink = plan.add_operator(Operator::Sink);
+        plan.add_flow(scan, sink, 0);
+        assert_eq!((scan, sink), (OperatorIdx(0), OperatorIdx(1)));
+        assert_eq!(plan.operator(sink), Some(&Operator::Sink));
+        assert_eq!(plan.operator(OperatorIdx(2)), None);
+
+        let ir = IrPlan::new(
+            plan,
+            vec![ObjectRef {
+                kind: ObjectKind::ScalarFunction,
+                name: "f".to_string(),
+            }],
+        );
+        assert_eq!(ir.object(ObjectIdx(0)).map(|o| o.name.as_str()), Some("f"));
+        assert_eq!(ir.object(ObjectIdx(1)), None);
+
+        let json = serde_json::to_string(&ir).expect("serialize");
+        assert_eq!(
+            serde_json::from_str::<IrPlan>(&json).expect("deserialize"),
+            ir
+        );
+    }
 }
@@ -0,0 +1,335 @@
+use crate::{AggFunc, Expr, Lit, Name, PathStep, Plan, SetQuantifier};
+use serde::{Deserialize, Serialize};
+
+/// An operator of a [`Plan`], which operates on binding tuples.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Operator {
+    Scan(Scan),
+    Pivot(Pivot),
+    Unpivot(Unpivot),
+    Filter(Expr),
+    /// `LET <expr> AS <name>, ...`
+    Let(Vec<(String, Expr)>),
+    OrderBy(Vec<SortSpec>),
+    LimitOffset(LimitOffset),
+    Join(Join),
+    BagOp(BagOp),
+    /// `SELECT <expr> AS <name>, ...`
+    Project(Vec<(String, Expr)>),
+    ProjectAll(ProjectAll),
+    ProjectValue(ProjectValue),
+    ExprQuery(Expr),
+    Distinct,
+    GroupBy(GroupBy),
+    Having(Expr),
+    With(With),
+    Window(Vec<WindowExpr>),
+    /// `EXCLUDE <path>, ...`
+    Exclude(Vec<ExcludePath>),
+    Dml(Dml),
+    Sink,
+}
+
+/// Binds each element of the value of `expr` to `as_key`, and its position to `at_key`; with
+/// `columns`, each element's attribute values (or own elements) are first bound by position to a
+/// tuple of those attributes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scan {
+    pub expr: Expr,
+    pub as_key: String,
+    pub at_key: Option<String>,
+    pub columns: Option<Vec<String>>,
+}
+
+/// `PIVOT <value> AT <key>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pivot {
+    pub key: Expr,
+    pub value: Expr,
+}
+
+/// Binds each attribute value of the value of `expr` to `as_key`, and its name to `at_key`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Unpivot {
+    pub expr: Expr,
+    pub as_key: String,
+    pub at_key: Option<String>,
+}
+
+/// `<expr> [ASC | DESC] [NULLS FIRST | NULLS LAST]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SortSpec {
+    pub expr: Expr,
+    pub order: SortOrder,
+    pub null_order: NullOrder,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum NullOrder {
+    First,
+    Last,
+}
+
+/// `LIMIT <limit> OFFSET <offset>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LimitOffset {
+    pub limit: Option<Expr>,
+    pub offset: Option<Expr>,
+}
+
+/// A join of the binding tuples of `left` and `right`, which are inline operators.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Join {
+    pub kind: JoinKind,
+    pub left: Box<Operator>,
+    pub right: Box<Operator>,
+    pub on: Option<Expr>,
+    /// The join columns of a `USING` or `NATURAL` join, bound in the joined binding tuples.
+    pub using: Vec<(String, Expr)>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+}
+
+/// E.g. `UNION ALL`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct BagOp {
+    pub op: BagOperator,
+    pub setq: SetQuantifier,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BagOperator {
+    Union,
+    Except,
+    Intersect,
+    OuterUnion,
+    OuterExcept,
+    OuterIntersect,
+}
+
+/// `SELECT *`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectAll {
+    pub mode: ProjectAllMode,
+    /// The join columns of the `USING` and `NATURAL` joins, which are projected once each.
+    pub join_columns: Vec<String>,
+}
+
+/// How `SELECT *` projects the attributes of its binding tuples.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ProjectAllMode {
+    /// Projects the attributes of the value of each binding
+    Unwrap,
+    /// Projects the bindings themselves
+    PassThrough,
+}
+
+/// `SELECT VALUE <expr>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectValue {
+    pub expr: Expr,
+}
+
+/// `GROUP [PARTIAL] BY <expr> AS <name>, ... [GROUP AS <group_as_alias>]`, computing
+/// `aggregate_exprs` over each group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupBy {
+    pub strategy: GroupingStrategy,
+    /// The grouping keys, ordered by name
+    pub exprs: Vec<(String, Expr)>,
+    pub aggregate_exprs: Vec<AggregateExpr>,
+    pub group_as_alias: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum GroupingStrategy {
+    Full,
+    Partial,
+}
+
+/// An aggregation function call, bound to `name`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateExpr {
+    pub name: String,
+    pub func: AggFunc,
+    pub expr: Expr,
+    pub args: Vec<Expr>,
+    pub setq: SetQuantifier,
+}
+
+/// `WITH <ctes> <body>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct With {
+    pub ctes: Vec<CommonTableExpr>,
+    pub body: Plan,
+}
+
+/// `<name> (<columns>) AS (<plan>)`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommonTableExpr {
+    pub name: String,
+    pub columns: Option<Vec<String>>,
+    pub plan: Plan,
+    pub recursive: Option<RecursiveStep>,
+}
+
+/// The recursive part of a `WITH RECURSIVE` element.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecursiveStep {
+    pub plan: Plan,
+    pub setq: SetQuantifier,
+    pub search: Option<CteSearch>,
+    pub cycle: Option<CteCycle>,
+    pub parent: Option<String>,
+}
+
+/// `SEARCH {DEPTH | BREADTH} FIRST BY <by> SET <set>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CteSearch {
+    pub order: SearchOrder,
+    pub by: Vec<String>,
+    pub set: String,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SearchOrder {
+    DepthFirst,
+    BreadthFirst,
+}
+
+/// `CYCLE <columns> SET <mark> TO <mark_value> DEFAULT <default_value> USING <path>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CteCycle {
+    pub columns: Vec<String>,
+    pub mark: String,
+    pub mark_value: Lit,
+    pub default_value: Lit,
+    pub path: String,
+}
+
+/// A window function call, bound to `name`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowExpr {
+    pub name: String,
+    pub func: WindowFunc,
+    pub args: Vec<Expr>,
+    pub setq: SetQuantifier,
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<SortSpec>,
+    pub frame: WindowFrame,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum WindowFunc {
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag,
+    Lead,
+    FirstValue,
+    LastValue,
+    Agg(AggFunc),
+}
+
+/// `{ROWS | RANGE} BETWEEN <start> AND <end>`
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+/// A path to the values an `EXCLUDE` removes, starting at the binding `root`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExcludePath {
+    pub root: Name,
+    pub steps: Vec<ExcludeStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExcludeStep {
+    /// E.g. `b` in `t.a.b`
+    Key(Name),
+    /// E.g. `4` in `t.a[4]`
+    Index(i64),
+    /// `[*]`
+    AllElements,
+    /// `.*`
+    AllFields,
+}
+
+/// An `INSERT`, `UPDATE` or `DELETE` of the collection `target`, each row of which is bound to
+/// `as_key`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dml {
+    pub target: Name,
+    pub as_key: String,
+    pub op: DmlOp,
+    pub returning: Vec<ReturningElem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DmlOp {
+    Insert {
+        values: Expr,
+        on_conflict: Option<Expr>,
+    },
+    Update {
+        filter: Option<Expr>,
+        changes: Vec<UpdateChange>,
+    },
+    Delete {
+        filter: Option<Expr>,
+    },
+}
+
+/// A change to the rows modified by an `UPDATE`, addressed by its path from the row.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum UpdateChange {
+    Set(Vec<PathStep>, Expr),
+    Remove(Vec<PathStep>),
+}
+
+/// `{MODIFIED | ALL} {NEW | OLD} {* | <expr>}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReturningElem {
+    pub mapping: ReturningMapping,
+    /// The column's name and expression, or `None` for `*`
+    pub column: Option<(String, Expr)>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum ReturningMapping {
+    ModifiedNew,
+    ModifiedOld,
+    AllNew,
+    AllOld,
+}
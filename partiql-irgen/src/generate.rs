@@ -0,0 +1,721 @@
+use partiql_ir as ir;
+use partiql_ir::graph as ir_graph;
+use partiql_logical as logical;
+use partiql_logical::graph;
+use partiql_logical::{BindingsOp, LogicalPlan, ValueExpr};
+use partiql_value::BindingsName;
+use rustc_hash::FxHashMap;
+
+/// Generates [`ir::IrPlan`]s from logical plans.
+///
+/// # Examples
+/// ```
+/// use partiql_catalog::catalog::PartiqlCatalog;
+/// use partiql_irgen::IrGenerator;
+/// use partiql_logical_planner::LogicalPlanner;
+/// use partiql_parser::Parser;
+///
+/// let catalog = PartiqlCatalog::default().to_shared_catalog();
+/// let parsed = Parser::default().parse("SELECT VALUE x.a FROM t AS x").unwrap();
+/// let logical = LogicalPlanner::new(&catalog).lower(&parsed).unwrap();
+///
+/// let ir = IrGenerator::new().generate(&logical);
+/// assert_eq!(ir.plan.operators.len(), 3);
+/// ```
+#[derive(Debug, Default)]
+pub struct IrGenerator {
+    objects: Vec<ir::ObjectRef>,
+    object_idxs: FxHashMap<ir::ObjectRef, ir::ObjectIdx>,
+}
+
+impl IrGenerator {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates the IR of `plan`, consuming the generator.
+    #[must_use]
+    pub fn generate(mut self, plan: &LogicalPlan<BindingsOp>) -> ir::IrPlan {
+        let plan = self.plan(plan);
+        ir::IrPlan::new(plan, self.objects)
+    }
+
+    fn object(&mut self, kind: ir::ObjectKind, name: &str) -> ir::ObjectIdx {
+        let object = ir::ObjectRef {
+            kind,
+            name: name.to_string(),
+        };
+        let next = ir::ObjectIdx(self.objects.len() as u32);
+        *self.object_idxs.entry(object.clone()).or_insert_with(|| {
+            self.objects.push(object);
+            next
+        })
+    }
+
+    fn plan(&mut self, plan: &LogicalPlan<BindingsOp>) -> ir::Plan {
+        let operators = plan.operators().iter().map(|op| self.op(op)).collect();
+        let flows = plan
+            .flows()
+            .iter()
+            .map(|(src, dst, branch)| ir::Flow {
+                src: ir::OperatorIdx(src.index() as u32 - 1),
+                dst: ir::OperatorIdx(dst.index() as u32 - 1),
+                branch: *branch,
+            })
+            .collect();
+        ir::Plan { operators, flows }
+    }
+
+    fn op(&mut self, op: &BindingsOp) -> ir::Operator {
+        match op {
+            BindingsOp::Scan(scan) => ir::Operator::Scan(ir::Scan {
+                expr: self.expr(&scan.expr),
+                as_key: scan.as_key.clone(),
+                at_key: scan.at_key.clone(),
+                columns: scan.columns.clone(),
+            }),
+            BindingsOp::Pivot(pivot) => ir::Operator::Pivot(ir::Pivot {
+                key: self.expr(&pivot.key),
+                value: self.expr(&pivot.value),
+            }),
+            BindingsOp::Unpivot(unpivot) => ir::Operator::Unpivot(ir::Unpivot {
+                expr: self.expr(&unpivot.expr),
+                as_key: unpivot.as_key.clone(),
+                at_key: unpivot.at_key.clone(),
+            }),
+            BindingsOp::Filter(filter) => ir::Operator::Filter(self.expr(&filter.expr)),
+            BindingsOp::Let(let_) => ir::Operator::Let(self.named_exprs(&let_.bindings)),
+            BindingsOp::OrderBy(order_by) => {
+                ir::Operator::OrderBy(self.sort_specs(&order_by.specs))
+            }
+            BindingsOp::LimitOffset(limit_offset) => ir::Operator::LimitOffset(ir::LimitOffset {
+                limit: self.opt_expr(limit_offset.limit.as_ref()),
+                offset: self.opt_expr(limit_offset.offset.as_ref()),
+            }),
+            BindingsOp::Join(join) => ir::Operator::Join(ir::Join {
+                kind: match join.kind {
+                    logical::JoinKind::Inner => ir::JoinKind::Inner,
+                    logical::JoinKind::Left => ir::JoinKind::Left,
+                    logical::JoinKind::Right => ir::JoinKind::Right,
+                    logical::JoinKind::Full => ir::JoinKind::Full,
+                    logical::JoinKind::Cross => ir::JoinKind::Cross,
+                },
+                left: Box::new(self.op(&join.left)),
+                right: Box::new(self.op(&join.right)),
+                on: self.opt_expr(join.on.as_ref()),
+                using: self.named_exprs(&join.using),
+            }),
+            BindingsOp::BagOp(bag_op) => ir::Operator::BagOp(ir::BagOp {
+                op: match bag_op.bag_op {
+                    logical::BagOperator::Union => ir::BagOperator::Union,
+                    logical::BagOperator::Except => ir::BagOperator::Except,
+                    logical::BagOperator::Intersect => ir::BagOperator::Intersect,
+                    logical::BagOperator::OuterUnion => ir::BagOperator::OuterUnion,
+                    logical::BagOperator::OuterExcept => ir::BagOperator::OuterExcept,
+                    logical::BagOperator::OuterIntersect => ir::BagOperator::OuterIntersect,
+                },
+                setq: setq(&bag_op.setq),
+            }),
+            BindingsOp::Project(project) => ir::Operator::Project(self.named_exprs(&project.exprs)),
+            BindingsOp::ProjectAll(logical::ProjectAll { mode, join_columns }) => {
+                ir::Operator::ProjectAll(ir::ProjectAll {
+                    mode: match mode {
+                        logical::ProjectAllMode::Unwrap => ir::ProjectAllMode::Unwrap,
+                        logical::ProjectAllMode::PassThrough => ir::ProjectAllMode::PassThrough,
+                    },
+                    join_columns: join_columns.clone(),
+                })
+            }
+            BindingsOp::ProjectValue(project) => ir::Operator::ProjectValue(ir::ProjectValue {
+                expr: self.expr(&project.expr),
+            }),
+            BindingsOp::ExprQuery(query) => ir::Operator::ExprQuery(self.expr(&query.expr)),
+            BindingsOp::Distinct => ir::Operator::Distinct,
+            BindingsOp::GroupBy(group_by) => {
+                // grouping keys are ordered by name so that the IR of a plan is deterministic
+                let mut keys: Vec<_> = group_by.exprs.iter().collect();
+                keys.sort_by_key(|(name, _)| *name);
+                ir::Operator::GroupBy(ir::GroupBy {
+                    strategy: match group_by.strategy {
+                        logical::GroupingStrategy::GroupFull => ir::GroupingStrategy::Full,
+                        logical::GroupingStrategy::GroupPartial => ir::GroupingStrategy::Partial,
+                    },
+                    exprs: keys
+                        .into_iter()
+                        .map(|(name, expr)| (name.clone(), self.expr(expr)))
+                        .collect(),
+                    aggregate_exprs: group_by
+                        .aggregate_exprs
+                        .iter()
+                        .map(|agg| ir::AggregateExpr {
+                            name: agg.name.clone(),
+                            func: self.agg_func(&agg.func),
+                            expr: self.expr(&agg.expr),
+                            args: agg.args.iter().map(|arg| self.expr(arg)).collect(),
+                            setq: setq(&agg.setq),
+                        })
+                        .collect(),
+                    group_as_alias: group_by.group_as_alias.clone(),
+                })
+            }
+            BindingsOp::Having(having) => ir::Operator::Having(self.expr(&having.expr)),
+            BindingsOp::With(with) => ir::Operator::With(ir::With {
+                ctes: with.ctes.iter().map(|cte| self.cte(cte)).collect(),
+                body: self.plan(&with.body),
+            }),
+            BindingsOp::Window(window) => ir::Operator::Window(
+                window
+                    .exprs
+                    .iter()
+                    .map(|expr| self.window_expr(expr))
+                    .collect(),
+            ),
+            BindingsOp::Exclude(exclude) => ir::Operator::Exclude(
+                exclude
+                    .paths
+                    .iter()
+                    .map(|path| ir::ExcludePath {
+                        root: name(&path.root),
+                        steps: path.steps.iter().map(exclude_step).collect(),
+                    })
+                    .collect(),
+            ),
+            BindingsOp::Dml(dml) => ir::Operator::Dml(self.dml(dml)),
+            BindingsOp::Sink => ir::Operator::Sink,
+        }
+    }
+
+    fn cte(&mut self, cte: &logical::CommonTableExpr) -> ir::CommonTableExpr {
+        ir::CommonTableExpr {
+            name: cte.name.clone(),
+            columns: cte.columns.clone(),
+            plan: self.plan(&cte.plan),
+            recursive: cte.recursive.as_ref().map(|step| ir::RecursiveStep {
+                plan: self.plan(&step.plan),
+                setq: setq(&step.setq),
+                search: step.search.as_ref().map(|search| ir::CteSearch {
+                    order: match search.order {
+                        logical::SearchOrder::DepthFirst => ir::SearchOrder::DepthFirst,
+                        logical::SearchOrder::BreadthFirst => ir::SearchOrder::BreadthFirst,
+                    },
+                    by: search.by.clone(),
+                    set: search.set.clone(),
+                }),
+                cycle: step.cycle.as_ref().map(|cycle| ir::CteCycle {
+                    columns: cycle.columns.clone(),
+                    mark: cycle.mark.clone(),
+                    mark_value: lit(&cycle.mark_value),
+                    default_value: lit(&cycle.default_value),
+                    path: cycle.path.clone(),
+                }),
+                parent: step.parent.clone(),
+            }),
+        }
+    }
+
+    fn window_expr(&mut self, expr: &logical::WindowExpression) -> ir::WindowExpr {
+        let frame = &expr.frame;
+        ir::WindowExpr {
+            name: expr.name.clone(),
+            func: match &expr.func {
+                logical::WindowFunc::RowNumber => ir::WindowFunc::RowNumber,
+                logical::WindowFunc::Rank => ir::WindowFunc::Rank,
+                logical::WindowFunc::DenseRank => ir::WindowFunc::DenseRank,
+                logical::WindowFunc::Lag => ir::WindowFunc::Lag,
+                logical::WindowFunc::Lead => ir::WindowFunc::Lead,
+                logical::WindowFunc::FirstValue => ir::WindowFunc::FirstValue,
+                logical::WindowFunc::LastValue => ir::WindowFunc::LastValue,
+                logical::WindowFunc::Agg(func) => ir::WindowFunc::Agg(self.agg_func(func)),
+            },
+            args: self.exprs(&expr.args),
+            setq: setq(&expr.setq),
+            partition_by: self.exprs(&expr.partition_by),
+            order_by: self.sort_specs(&expr.order_by),
+            frame: ir::WindowFrame {
+                units: match frame.units {
+                    logical::WindowFrameUnits::Rows => ir::WindowFrameUnits::Rows,
+                    logical::WindowFrameUnits::Range => ir::WindowFrameUnits::Range,
+                },
+                start: frame_bound(&frame.start),
+                end: frame_bound(&frame.end),
+            },
+        }
+    }
+
+    fn dml(&mut self, dml: &logical::Dml) -> ir::Dml {
+        ir::Dml {
+            target: name(&dml.target.name),
+            as_key: dml.target.as_key.clone(),
+            op: match &dml.op {
+                logical::DmlOp::Insert {
+                    values,
+                    on_conflict,
+                } => ir::DmlOp::Insert {
+                    values: self.expr(values),
+                    on_conflict: self.opt_expr(on_conflict.as_ref()),
+                },
+                logical::DmlOp::Update { filter, changes } => ir::DmlOp::Update {
+                    filter: self.opt_expr(filter.as_ref()),
+                    changes: changes
+                        .iter()
+                        .map(|change| match change {
+                            logical::UpdateChange::Set(path, value) => {
+                                ir::UpdateChange::Set(self.path(path), self.expr(value))
+                            }
+                            logical::UpdateChange::Remove(path) => {
+                                ir::UpdateChange::Remove(self.path(path))
+                            }
+                        })
+                        .collect(),
+                },
+                logical::DmlOp::Delete { filter } => ir::DmlOp::Delete {
+                    filter: self.opt_expr(filter.as_ref()),
+                },
+            },
+            returning: dml
+                .returning
+                .iter()
+                .map(|elem| ir::ReturningElem {
+                    mapping: match elem.mapping {
+                        logical::ReturningMapping::ModifiedNew => ir::ReturningMapping::ModifiedNew,
+                        logical::ReturningMapping::ModifiedOld => ir::ReturningMapping::ModifiedOld,
+                        logical::ReturningMapping::AllNew => ir::ReturningMapping::AllNew,
+                        logical::ReturningMapping::AllOld => ir::ReturningMapping::AllOld,
+                    },
+                    column: match &elem.column {
+                        logical::ReturningColumn::Wildcard => None,
+                        logical::ReturningColumn::Expr(name, expr) => {
+                            Some((name.clone(), self.expr(expr)))
+                        }
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn sort_specs(&mut self, specs: &[logical::SortSpec]) -> Vec<ir::SortSpec> {
+        specs
+            .iter()
+            .map(|spec| ir::SortSpec {
+                expr: self.expr(&spec.expr),
+                order: match spec.order {
+                    logical::SortSpecOrder::Asc => ir::SortOrder::Asc,
+                    logical::SortSpecOrder::Desc => ir::SortOrder::Desc,
+                },
+                null_order: match spec.null_order {
+                    logical::SortSpecNullOrder::First => ir::NullOrder::First,
+                    logical::SortSpecNullOrder::Last => ir::NullOrder::Last,
+                },
+            })
+            .collect()
+    }
+
+    fn agg_func(&mut self, func: &logical::AggFunc) -> ir::AggFunc {
+        match func {
+            logical::AggFunc::AggAvg => ir::AggFunc::Avg,
+            logical::AggFunc::AggCount => ir::AggFunc::Count,
+            logical::AggFunc::AggMax => ir::AggFunc::Max,
+            logical::AggFunc::AggMin => ir::AggFunc::Min,
+            logical::AggFunc::AggSum => ir::AggFunc::Sum,
+            logical::AggFunc::AggAny => ir::AggFunc::Any,
+            logical::AggFunc::AggEvery => ir::AggFunc::Every,
+            logical::AggFunc::AggById(name, _) => {
+                ir::AggFunc::Catalog(self.object(ir::ObjectKind::AggregateFunction, name))
+            }
+        }
+    }
+
+    fn named_exprs(&mut self, exprs: &[(String, ValueExpr)]) -> Vec<(String, ir::Expr)> {
+        exprs
+            .iter()
+            .map(|(name, expr)| (name.clone(), self.expr(expr)))
+            .collect()
+    }
+
+    fn exprs(&mut self, exprs: &[ValueExpr]) -> Vec<ir::Expr> {
+        exprs.iter().map(|expr| self.expr(expr)).collect()
+    }
+
+    fn opt_expr(&mut self, expr: Option<&ValueExpr>) -> Option<ir::Expr> {
+        expr.map(|expr| self.expr(expr))
+    }
+
+    fn boxed(&mut self, expr: &ValueExpr) -> Box<ir::Expr> {
+        Box::new(self.expr(expr))
+    }
+
+    fn cases(&mut self, cases: &[(Box<ValueExpr>, Box<ValueExpr>)]) -> Vec<(ir::Expr, ir::Expr)> {
+        cases
+            .iter()
+            .map(|(when, then)| (self.expr(when), self.expr(then)))
+            .collect()
+    }
+
+    fn expr(&mut self, expr: &ValueExpr) -> ir::Expr {
+        match expr {
+            ValueExpr::UnExpr(op, operand) => ir::Expr::Unary(
+                match op {
+                    logical::UnaryOp::Pos => ir::UnaryOp::Pos,
+                    logical::UnaryOp::Neg => ir::UnaryOp::Neg,
+                    logical::UnaryOp::Not => ir::UnaryOp::Not,
+                },
+                self.boxed(operand),
+            ),
+            ValueExpr::BinaryExpr(op, lhs, rhs) => {
+                ir::Expr::Binary(binary_op(op), self.boxed(lhs), self.boxed(rhs))
+            }
+            ValueExpr::Lit(value) => ir::Expr::Lit(lit(value)),
+            ValueExpr::DynamicLookup(lookups) => ir::Expr::DynamicLookup(self.exprs(lookups)),
+            ValueExpr::Path(root, path) => ir::Expr::Path(self.boxed(root), self.path(path)),
+            ValueExpr::VarRef(var, kind) => ir::Expr::VarRef(
+                name(var),
+                match kind {
+                    logical::VarRefType::Global => ir::VarRefKind::Global,
+                    logical::VarRefType::Local => ir::VarRefKind::Local,
+                },
+            ),
+            ValueExpr::Parameter(param) => ir::Expr::Parameter(match param {
+                logical::Parameter::Positional(n) => ir::Parameter::Positional(*n as u32),
+                logical::Parameter::Named(name) => ir::Parameter::Named(name.clone()),
+            }),
+            ValueExpr::TupleExpr(tuple) => ir::Expr::Tuple(
+                tuple
+                    .attrs
+                    .iter()
+                    .zip(&tuple.values)
+                    .map(|(attr, value)| (self.expr(attr), self.expr(value)))
+                    .collect(),
+            ),
+            ValueExpr::ListExpr(list) => ir::Expr::List(self.exprs(&list.elements)),
+            ValueExpr::BagExpr(bag) => ir::Expr::Bag(self.exprs(&bag.elements)),
+            ValueExpr::BetweenExpr(between) => ir::Expr::Between {
+                value: self.boxed(&between.value),
+                from: self.boxed(&between.from),
+                to: self.boxed(&between.to),
+            },
+            ValueExpr::PatternMatchExpr(pattern_match) => ir::Expr::Like {
+                value: self.boxed(&pattern_match.value),
+                pattern: match &pattern_match.pattern {
+                    logical::Pattern::Like(like) => ir::LikePattern::Lit {
+                        pattern: like.pattern.clone(),
+                        escape: like.escape.clone(),
+                    },
+                    logical::Pattern::LikeNonStringNonLiteral(like) => ir::LikePattern::Expr {
+                        pattern: self.boxed(&like.pattern),
+                        escape: self.boxed(&like.escape),
+                    },
+                },
+            },
+            ValueExpr::SubQueryExpr(subquery) => ir::Expr::SubQuery(self.plan(&subquery.plan)),
+            ValueExpr::SimpleCase(case) => ir::Expr::SimpleCase {
+                expr: self.boxed(&case.expr),
+                cases: self.cases(&case.cases),
+                default: case.default.as_ref().map(|default| self.boxed(default)),
+            },
+            ValueExpr::SearchedCase(case) => ir::Expr::SearchedCase {
+                cases: self.cases(&case.cases),
+                default: case.default.as_ref().map(|default| self.boxed(default)),
+            },
+            ValueExpr::IsTypeExpr(is_type) => ir::Expr::IsType {
+                not: is_type.not,
+                expr: self.boxed(&is_type.expr),
+                is_type: ty(&is_type.is_type),
+            },
+            ValueExpr::CastExpr(cast) => ir::Expr::Cast {
+                kind: match cast.kind {
+                    logical::CastKind::Cast => ir::CastKind::Cast,
+                    logical::CastKind::CanCast => ir::CastKind::CanCast,
+                    logical::CastKind::CanLosslessCast => ir::CastKind::CanLosslessCast,
+                },
+                expr: self.boxed(&cast.expr),
+                cast_type: ty(&cast.cast_type),
+            },
+            ValueExpr::NullIfExpr(null_if) => {
+                ir::Expr::NullIf(self.boxed(&null_if.lhs), self.boxed(&null_if.rhs))
+            }
+            ValueExpr::CoalesceExpr(coalesce) => ir::Expr::Coalesce(self.exprs(&coalesce.elements)),
+            ValueExpr::Call(call) => {
+                ir::Expr::Call(self.func(&call.name), self.exprs(&call.arguments))
+            }
+            ValueExpr::GraphMatch(graph_match) => ir::Expr::GraphMatch {
+                value: self.boxed(&graph_match.value),
+                pattern: Box::new(self.path_pattern(&graph_match.pattern)),
+            },
+        }
+    }
+
+    fn path(&mut self, path: &[logical::PathComponent]) -> Vec<ir::PathStep> {
+        path.iter()
+            .map(|component| match component {
+                logical::PathComponent::Key(key) => ir::PathStep::Key(name(key)),
+                logical::PathComponent::Index(idx) => ir::PathStep::Index(*idx),
+                logical::PathComponent::KeyExpr(expr) => ir::PathStep::KeyExpr(self.boxed(expr)),
+                logical::PathComponent::IndexExpr(expr) => {
+                    ir::PathStep::IndexExpr(self.boxed(expr))
+                }
+                logical::PathComponent::ForEach => ir::PathStep::ForEach,
+                logical::PathComponent::Unpivot => ir::PathStep::Unpivot,
+            })
+            .collect()
+    }
+
+    fn func(&mut self, func: &logical::CallName) -> ir::Func {
+        use logical::CallName;
+        match func {
+            CallName::Lower => ir::Func::Lower,
+            CallName::Upper => ir::Func::Upper,
+            CallName::CharLength => ir::Func::CharLength,
+            CallName::OctetLength => ir::Func::OctetLength,
+            CallName::BitLength => ir::Func::BitLength,
+            CallName::LTrim => ir::Func::LTrim,
+            CallName::BTrim => ir::Func::BTrim,
+            CallName::RTrim => ir::Func::RTrim,
+            CallName::Substring => ir::Func::Substring,
+            CallName::Position => ir::Func::Position,
+            CallName::Overlay => ir::Func::Overlay,
+            CallName::Exists => ir::Func::Exists,
+            CallName::Abs => ir::Func::Abs,
+            CallName::Mod => ir::Func::Mod,
+            CallName::Cardinality => ir::Func::Cardinality,
+            CallName::ExtractYear => ir::Func::ExtractYear,
+            CallName::ExtractMonth => ir::Func::ExtractMonth,
+            CallName::ExtractDay => ir::Func::ExtractDay,
+            CallName::ExtractHour => ir::Func::ExtractHour,
+            CallName::ExtractMinute => ir::Func::ExtractMinute,
+            CallName::ExtractSecond => ir::Func::ExtractSecond,
+            CallName::ExtractTimezoneHour => ir::Func::ExtractTimezoneHour,
+            CallName::ExtractTimezoneMinute => ir::Func::ExtractTimezoneMinute,
+            CallName::CollAvg(q) => ir::Func::CollAvg(setq(q)),
+            CallName::CollCount(q) => ir::Func::CollCount(setq(q)),
+            CallName::CollMax(q) => ir::Func::CollMax(setq(q)),
+            CallName::CollMin(q) => ir::Func::CollMin(setq(q)),
+            CallName::CollSum(q) => ir::Func::CollSum(setq(q)),
+            CallName::CollAny(q) => ir::Func::CollAny(setq(q)),
+            CallName::CollEvery(q) => ir::Func::CollEvery(setq(q)),
+            CallName::ByName(name) => ir::Func::ByName(name.clone()),
+            CallName::ById(name, _, overload) => ir::Func::Catalog {
+                object: self.object(ir::ObjectKind::ScalarFunction, name),
+                overload: *overload as u32,
+            },
+        }
+    }
+
+    fn path_pattern(&mut self, pattern: &graph::PathPatternMatch) -> ir_graph::PathPatternMatch {
+        match pattern {
+            graph::PathPatternMatch::Node(node) => {
+                ir_graph::PathPatternMatch::Node(ir_graph::ElementMatch {
+                    binder: node.binder.0.clone(),
+                    spec: self.element_filter(&node.spec.label, &node.spec.filter),
+                })
+            }
+            graph::PathPatternMatch::Match(triple) => {
+                ir_graph::PathPatternMatch::Match(self.triple(triple))
+            }
+            graph::PathPatternMatch::Concat(series, mode) => ir_graph::PathPatternMatch::Concat(
+                series
+                    .iter()
+                    .map(|series| ir_graph::TripleSeriesMatch {
+                        triples: series
+                            .triples
+                            .iter()
+                            .map(|triple| self.triple(triple))
+                            .collect(),
+                        filter: self.value_filter(&series.filter),
+                        path_mode: path_mode(&series.path_mode),
+                    })
+                    .collect(),
+                path_mode(mode),
+            ),
+        }
+    }
+
+    fn triple(&mut self, triple: &graph::TripleMatch) -> ir_graph::TripleMatch {
+        let (lhs, edge, rhs) = &triple.binders;
+        let filter = &triple.spec.triple;
+        ir_graph::TripleMatch {
+            binders: (lhs.0.clone(), edge.0.clone(), rhs.0.clone()),
+            spec: ir_graph::StepFilter {
+                dir: direction(&triple.spec.dir),
+                triple: ir_graph::TripleFilter {
+                    lhs: self.element_filter(&filter.lhs.label, &filter.lhs.filter),
+                    edge: self.element_filter(&filter.e.label, &filter.e.filter),
+                    rhs: self.element_filter(&filter.rhs.label, &filter.rhs.filter),
+                },
+            },
+            filter: self.value_filter(&triple.filter),
+            path_mode: path_mode(&triple.path_mode),
+        }
+    }
+
+    fn element_filter(
+        &mut self,
+        label: &graph::LabelFilter,
+        filter: &graph::ValueFilter,
+    ) -> ir_graph::ElementFilter {
+        ir_graph::ElementFilter {
+            label: label_filter(label),
+            filter: self.value_filter(filter),
+        }
+    }
+
+    fn value_filter(&mut self, filter: &graph::ValueFilter) -> ir_graph::ValueFilter {
+        match filter {
+            graph::ValueFilter::Always => None,
+            graph::ValueFilter::Filter(exprs) => Some(self.exprs(exprs)),
+        }
+    }
+}
+
+fn exclude_step(step: &logical::ExcludeStep) -> ir::ExcludeStep {
+    match step {
+        logical::ExcludeStep::Key(key) => ir::ExcludeStep::Key(name(key)),
+        logical::ExcludeStep::Index(idx) => ir::ExcludeStep::Index(*idx),
+        logical::ExcludeStep::AllElements => ir::ExcludeStep::AllElements,
+        logical::ExcludeStep::AllFields => ir::ExcludeStep::AllFields,
+    }
+}
+
+fn name(name: &BindingsName<'_>) -> ir::Name {
+    match name {
+        BindingsName::CaseSensitive(s) => ir::Name::CaseSensitive(s.to_string()),
+        BindingsName::CaseInsensitive(s) => ir::Name::CaseInsensitive(s.to_string()),
+    }
+}
+
+fn setq(setq: &logical::SetQuantifier) -> ir::SetQuantifier {
+    match setq {
+        logical::SetQuantifier::All => ir::SetQuantifier::All,
+        logical::SetQuantifier::Distinct => ir::SetQuantifier::Distinct,
+    }
+}
+
+fn binary_op(op: &logical::BinaryOp) -> ir::BinaryOp {
+    use logical::BinaryOp;
+    match op {
+        BinaryOp::And => ir::BinaryOp::And,
+        BinaryOp::Or => ir::BinaryOp::Or,
+        BinaryOp::Concat => ir::BinaryOp::Concat,
+        BinaryOp::Eq => ir::BinaryOp::Eq,
+        BinaryOp::Neq => ir::BinaryOp::Neq,
+        BinaryOp::Gt => ir::BinaryOp::Gt,
+        BinaryOp::Gteq => ir::BinaryOp::Gteq,
+        BinaryOp::Lt => ir::BinaryOp::Lt,
+        BinaryOp::Lteq => ir::BinaryOp::Lteq,
+        BinaryOp::Add => ir::BinaryOp::Add,
+        BinaryOp::Sub => ir::BinaryOp::Sub,
+        BinaryOp::Mul => ir::BinaryOp::Mul,
+        BinaryOp::Div => ir::BinaryOp::Div,
+        BinaryOp::Mod => ir::BinaryOp::Mod,
+        BinaryOp::Exp => ir::BinaryOp::Exp,
+        BinaryOp::In => ir::BinaryOp::In,
+    }
+}
+
+fn lit(lit: &logical::Lit) -> ir::Lit {
+    match lit {
+        logical::Lit::Null => ir::Lit::Null,
+        logical::Lit::Missing => ir::Lit::Missing,
+        logical::Lit::Int8(i) => ir::Lit::Int8(*i),
+        logical::Lit::Int16(i) => ir::Lit::Int16(*i),
+        logical::Lit::Int32(i) => ir::Lit::Int32(*i),
+        logical::Lit::Int64(i) => ir::Lit::Int64(*i),
+        logical::Lit::Decimal(d) => ir::Lit::Decimal(d.to_string()),
+        logical::Lit::Double(f) => ir::Lit::Double(f.0),
+        logical::Lit::Bool(b) => ir::Lit::Bool(*b),
+        logical::Lit::String(s) => ir::Lit::String(s.clone()),
+        logical::Lit::Variant(bytes, type_name) => ir::Lit::Variant {
+            bytes: bytes.clone(),
+            type_name: type_name.clone(),
+        },
+        logical::Lit::Struct(attrs) => ir::Lit::Struct(
+            attrs
+                .iter()
+                .map(|(name, value)| (name.clone(), self::lit(value)))
+                .collect(),
+        ),
+        logical::Lit::Bag(elements) => ir::Lit::Bag(elements.iter().map(self::lit).collect()),
+        logical::Lit::List(elements) => ir::Lit::List(elements.iter().map(self::lit).collect()),
+    }
+}
+
+fn ty(ty: &logical::Type) -> ir::Type {
+    use logical::Type;
+    match *ty {
+        Type::NullType => ir::Type::Null,
+        Type::BooleanType => ir::Type::Boolean,
+        Type::Integer2Type => ir::Type::Integer2,
+        Type::Integer4Type => ir::Type::Integer4,
+        Type::Integer8Type => ir::Type::Integer8,
+        Type::DecimalType(p, s) => ir::Type::Decimal(p, s),
+        Type::NumericType(p, s) => ir::Type::Numeric(p, s),
+        Type::RealType => ir::Type::Real,
+        Type::DoublePrecisionType => ir::Type::DoublePrecision,
+        Type::TimestampType(p) => ir::Type::Timestamp(p),
+        Type::CharacterType(l) => ir::Type::Character(l),
+        Type::CharacterVaryingType(l) => ir::Type::CharacterVarying(l),
+        Type::MissingType => ir::Type::Missing,
+        Type::StringType => ir::Type::String,
+        Type::SymbolType => ir::Type::Symbol,
+        Type::BlobType => ir::Type::Blob,
+        Type::ClobType => ir::Type::Clob,
+        Type::DateType => ir::Type::Date,
+        Type::TimeType(p) => ir::Type::Time(p),
+        Type::ZonedTimeType(p) => ir::Type::ZonedTime(p),
+        Type::ZonedTimestampType(p) => ir::Type::ZonedTimestamp(p),
+        Type::StructType => ir::Type::Struct,
+        Type::TupleType => ir::Type::Tuple,
+        Type::ListType => ir::Type::List,
+        Type::SexpType => ir::Type::Sexp,
+        Type::BagType => ir::Type::Bag,
+        Type::AnyType => ir::Type::Any,
+    }
+}
+
+fn frame_bound(bound: &logical::WindowFrameBound) -> ir::WindowFrameBound {
+    match *bound {
+        logical::WindowFrameBound::UnboundedPreceding => ir::WindowFrameBound::UnboundedPreceding,
+        logical::WindowFrameBound::Preceding(n) => ir::WindowFrameBound::Preceding(n),
+        logical::WindowFrameBound::CurrentRow => ir::WindowFrameBound::CurrentRow,
+        logical::WindowFrameBound::Following(n) => ir::WindowFrameBound::Following(n),
+        logical::WindowFrameBound::UnboundedFollowing => ir::WindowFrameBound::UnboundedFollowing,
+    }
+}
+
+fn direction(dir: &graph::DirectionFilter) -> ir_graph::DirectionFilter {
+    match dir {
+        graph::DirectionFilter::L => ir_graph::DirectionFilter::Left,
+        graph::DirectionFilter::U => ir_graph::DirectionFilter::Undirected,
+        graph::DirectionFilter::R => ir_graph::DirectionFilter::Right,
+        graph::DirectionFilter::LU => ir_graph::DirectionFilter::LeftOrUndirected,
+        graph::DirectionFilter::UR => ir_graph::DirectionFilter::UndirectedOrRight,
+        graph::DirectionFilter::LR => ir_graph::DirectionFilter::LeftOrRight,
+        graph::DirectionFilter::LUR => ir_graph::DirectionFilter::Any,
+    }
+}
+
+fn path_mode(mode: &graph::PathMode) -> ir_graph::PathMode {
+    match mode {
+        graph::PathMode::Walk => ir_graph::PathMode::Walk,
+        graph::PathMode::Trail => ir_graph::PathMode::Trail,
+        graph::PathMode::Acyclic => ir_graph::PathMode::Acyclic,
+        graph::PathMode::Simple => ir_graph::PathMode::Simple,
+    }
+}
+
+fn label_filter(label: &graph::LabelFilter) -> ir_graph::LabelFilter {
+    match label {
+        graph::LabelFilter::Always => ir_graph::LabelFilter::Always,
+        graph::LabelFilter::Named(name) => ir_graph::LabelFilter::Named(name.clone()),
+        graph::LabelFilter::Negated(inner) => {
+            ir_graph::LabelFilter::Negated(Box::new(label_filter(inner)))
+        }
+        graph::LabelFilter::Conjunction(labels) => {
+            ir_graph::LabelFilter::Conjunction(labels.iter().map(label_filter).collect())
+        }
+        graph::LabelFilter::Disjunction(labels) => {
+            ir_graph::LabelFilter::Disjunction(labels.iter().map(label_filter).collect())
+        }
+        graph::LabelFilter::Never => ir_graph::LabelFilter::Never,
+    }
+}
@@ -1,8 +1,235 @@
 #![deny(rust_2018_idioms)]
 #![deny(clippy::all)]
 
+//! Generation of the serializable intermediate representation of `partiql-ir` from logical plans,
+//! and loading of that representation back into logical plans.
+//!
+//! [`IrGenerator`] turns a `LogicalPlan<BindingsOp>` into an [`IrPlan`](partiql_ir::IrPlan),
+//! replacing the ids of catalog functions by references to their names. [`IrLoader`] loads an
+//! `IrPlan` into a logical plan, resolving those names against a (possibly different) catalog, so
+//! that the plan can be compiled and evaluated by `partiql-eval`.
+//!
+//! # Examples
+//! ```
+//! use partiql_catalog::catalog::PartiqlCatalog;
+//! use partiql_ir::IrPlan;
+//! use partiql_irgen::{IrGenerator, IrLoader};
+//! use partiql_logical_planner::LogicalPlanner;
+//! use partiql_parser::Parser;
+//!
+//! // in the control plane
+//! let catalog = PartiqlCatalog::default().to_shared_catalog();
+//! let parsed = Parser::default().parse("SELECT x.a FROM t AS x WHERE x.b > 1").unwrap();
+//! let logical = LogicalPlanner::new(&catalog).lower(&parsed).unwrap();
+//! let bytes = serde_json::to_vec(&IrGenerator::new().generate(&logical)).unwrap();
+//!
+//! // in a worker
+//! let ir: IrPlan = serde_json::from_slice(&bytes).unwrap();
+//! let loaded = IrLoader::new(&PartiqlCatalog::default()).load(&ir).unwrap();
+//! assert_eq!(loaded, logical);
+//! ```
+
+mod generate;
+mod load;
+
+pub use generate::IrGenerator;
+pub use load::IrLoader;
+
+use partiql_ir::IrVersion;
+use thiserror::Error;
+
+/// An error loading an [`IrPlan`](partiql_ir::IrPlan).
+///
+/// ### Notes
+/// This is marked `#[non_exhaustive]`, to reserve the right to add more variants in the future.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IrLoadError {
+    /// The plan's version of the IR is not readable by this version of the IR.
+    #[error(
+        "IR version {0} is not supported (supported: {})",
+        partiql_ir::IR_VERSION
+    )]
+    UnsupportedVersion(IrVersion),
+    /// A catalog object referenced by the plan does not exist in the catalog.
+    #[error("Catalog object `{0}` does not exist")]
+    UnknownObject(String),
+    /// A catalog object referenced by the plan is not of the kind expected by the plan.
+    #[error("Catalog object `{0}` is not of the expected kind")]
+    ObjectKindMismatch(String),
+    /// A function call references an overload which the catalog function does not have.
+    #[error("Catalog function `{0}` has no overload #{1}")]
+    InvalidOverload(String, usize),
+    /// An expression references an object which is not in the plan's table of objects.
+    #[error("Invalid catalog object reference #{0}")]
+    InvalidObjectIdx(u32),
+    /// A data flow references an operator which is not in the plan.
+    #[error("Invalid data flow from operator #{0} to operator #{1}")]
+    InvalidFlow(u32, u32),
+    /// A decimal literal is not a valid decimal.
+    #[error("Invalid decimal literal `{0}`")]
+    InvalidDecimal(String),
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use partiql_catalog::catalog::{PartiqlCatalog, PartiqlSharedCatalog};
+    use partiql_catalog::extension::Extension;
+    use partiql_extension_value_functions::PartiqlValueFnExtension;
+    use partiql_ir::{Expr, Func, IrPlan, ObjectIdx, ObjectKind, ObjectRef, Operator};
+    use partiql_logical::{BindingsOp, LogicalPlan};
+    use partiql_logical_planner::LogicalPlanner;
+    use partiql_parser::Parser;
+
+    fn catalog() -> PartiqlSharedCatalog {
+        let mut catalog = PartiqlCatalog::default();
+        PartiqlValueFnExtension::default()
+            .load(&mut catalog)
+            .expect("extension");
+        catalog.to_shared_catalog()
+    }
+
+    fn lower(catalog: &PartiqlSharedCatalog, query: &str) -> LogicalPlan<BindingsOp> {
+        let parsed = Parser::default().parse(query).expect(query);
+        LogicalPlanner::new(catalog).lower(&parsed).expect(query)
+    }
+
+    /// Asserts that the logical plan of `query` is unchanged by a round trip through JSON.
+    #[track_caller]
+    fn assert_round_trip(query: &str) {
+        let catalog = catalog();
+        let logical = lower(&catalog, query);
+        let ir = IrGenerator::new().generate(&logical);
+        let json = serde_json::to_string(&ir).expect("serialize");
+        let ir: IrPlan = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(IrLoader::new(&catalog).load(&ir), Ok(logical), "{query}");
+    }
+
     #[test]
-    fn todo() {}
+    fn round_trips() {
+        for query in [
+            "1 + 2 * 3 - -4 / 5 % 6",
+            "SELECT VALUE {'a': x.a, 'b': [x.b, 1.50, 2e0], 'c': <<NULL, MISSING, TRUE>>} FROM t AS x",
+            "SELECT x.a AS a, \"x\".\"B\" FROM t AS x AT i WHERE x.a BETWEEN ? AND :hi",
+            "SELECT DISTINCT x.a FROM t AS x ORDER BY x.a DESC NULLS FIRST LIMIT 10 OFFSET 2",
+            "SELECT x.a, COUNT(*) AS c, SUM(DISTINCT x.b) AS s FROM t AS x \
+             GROUP PARTIAL BY x.a, x.c AS k GROUP AS g HAVING COUNT(*) > 1",
+            "SELECT x.a, y.b FROM t AS x LEFT JOIN u AS y ON x.a = y.a, v AS z",
+            "PIVOT x.v AT x.k FROM t AS x",
+            "SELECT k, v FROM UNPIVOT {'a': 1} AS v AT k",
+            "(SELECT * FROM t) UNION ALL (SELECT * FROM u) EXCEPT (SELECT * FROM v)",
+            "SELECT x.a FROM (SELECT y.a FROM t AS y) AS x WHERE EXISTS(x.b)",
+            "SELECT CASE x.a WHEN 1 THEN 'one' ELSE 'many' END, \
+             CASE WHEN x.a IS NULL THEN 0 END FROM t AS x",
+            "SELECT CAST(x.a AS DECIMAL(5, 2)), x.b IS NOT VARCHAR(3), NULLIF(x.a, 1), \
+             COALESCE(x.a, x.b) FROM t AS x",
+            "SELECT LOWER(x.a), SUBSTRING(x.a FROM 2 FOR 3), x.a LIKE 'a%' ESCAPE '!', \
+             x.a LIKE x.b, EXTRACT(YEAR FROM x.d), COLL_SUM(x.l) FROM t AS x",
+            "SELECT tupleunion(x, {'b': 2}) AS u, tupleunion(x) AS v FROM t AS x",
+            "SELECT x.a, RANK() OVER (PARTITION BY x.b ORDER BY x.a), \
+             SUM(x.a) OVER (ORDER BY x.a ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) FROM t AS x",
+            "WITH RECURSIVE r (n) AS (SELECT VALUE 1 FROM <<1>> \
+             UNION ALL SELECT VALUE r.n + 1 FROM r WHERE r.n < 3) SELECT * FROM r",
+            "SELECT x.a[0], x.b[x.i], x['d'], x.c[x.k || 'e'] FROM t AS x",
+            "SELECT * EXCLUDE x.a.b, x.\"C\"[1], x.d[*].e, x.f.*, x.g['h'] FROM t AS x",
+            "SELECT v.x FROM (VALUES (1, 2), (3, 4)) AS v (x, y)",
+            "TABLE t",
+            "SELECT y FROM t AS x LET x.a * 2 AS y, x.b AS z WHERE z > y",
+            "SELECT x.a[*].b, x.c.*[0] FROM t AS x",
+            "SELECT a FROM (g MATCH (x:A WHERE x.v > 1) -[e:E|F]-> (y)) AS m",
+            "UPDATE t AS x SET x.a = 1 REMOVE x.b WHERE x.c > 0 RETURNING MODIFIED NEW x.a",
+            "INSERT INTO t VALUE {'a': 1} ON CONFLICT WHERE t.a = EXCLUDED.a DO NOTHING",
+            "DELETE FROM t AS x WHERE x.a = 1 RETURNING ALL OLD *",
+        ] {
+            assert_round_trip(query);
+        }
+    }
+
+    #[test]
+    fn references_catalog_objects_by_name() {
+        let catalog = catalog();
+        let logical = lower(
+            &catalog,
+            "SELECT tupleunion(x, y) AS a, TupleUnion(y) AS b FROM t AS x, u AS y",
+        );
+        let ir = IrGenerator::new().generate(&logical);
+        assert_eq!(
+            ir.objects,
+            vec![ObjectRef {
+                kind: ObjectKind::ScalarFunction,
+                name: "tupleunion".to_string(),
+            }]
+        );
+
+        assert_eq!(IrLoader::new(&catalog).load(&ir), Ok(logical));
+
+        let err = IrLoader::new(&PartiqlCatalog::default()).load(&ir);
+        assert_eq!(
+            err,
+            Err(IrLoadError::UnknownObject("tupleunion".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_plans() {
+        let catalog = catalog();
+        let logical = lower(&catalog, "SELECT tupleunion(x) AS a FROM t AS x");
+        let ir = IrGenerator::new().generate(&logical);
+        let loader = IrLoader::new(&catalog);
+
+        let mut newer = ir.clone();
+        newer.version.minor += 1;
+        assert_eq!(
+            loader.load(&newer),
+            Err(IrLoadError::UnsupportedVersion(newer.version))
+        );
+
+        let mut aggregate = ir.clone();
+        aggregate.objects[0].kind = ObjectKind::AggregateFunction;
+        assert_eq!(
+            loader.load(&aggregate),
+            Err(IrLoadError::ObjectKindMismatch("tupleunion".to_string()))
+        );
+
+        let mut dangling = ir.clone();
+        dangling.plan.flows[0].dst.0 = 99;
+        assert!(matches!(
+            loader.load(&dangling),
+            Err(IrLoadError::InvalidFlow(_, 99))
+        ));
+
+        // replaces the call's function
+        let with_func = |func: Func| {
+            let mut ir = ir.clone();
+            let exprs = ir
+                .plan
+                .operators
+                .iter_mut()
+                .find_map(|op| match op {
+                    Operator::Project(exprs) => Some(exprs),
+                    _ => None,
+                })
+                .expect("projection");
+            let Expr::Call(f, _) = &mut exprs[0].1 else {
+                panic!("call")
+            };
+            *f = func;
+            ir
+        };
+        assert_eq!(
+            loader.load(&with_func(Func::Catalog {
+                object: ObjectIdx(0),
+                overload: 1000,
+            })),
+            Err(IrLoadError::InvalidOverload("tupleunion".to_string(), 1000))
+        );
+        assert_eq!(
+            loader.load(&with_func(Func::Catalog {
+                object: ObjectIdx(1),
+                overload: 0,
+            })),
+            Err(IrLoadError::InvalidObjectIdx(1))
+        );
+    }
 }
@@ -0,0 +1,861 @@
+use crate::IrLoadError;
+use ordered_float::OrderedFloat;
+use partiql_catalog::catalog::{FunctionEntryFunction, ReadOnlyCatalog};
+use partiql_common::catalog::ObjectId;
+use partiql_ir as ir;
+use partiql_ir::graph as ir_graph;
+use partiql_logical as logical;
+use partiql_logical::graph;
+use partiql_logical::{BindingsOp, LogicalPlan, ValueExpr};
+use partiql_value::BindingsName;
+use rust_decimal::Decimal;
+use rustc_hash::FxHashMap;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+type Result<T> = std::result::Result<T, IrLoadError>;
+
+/// Loads [`ir::IrPlan`]s into logical plans, resolving the catalog objects they reference against
+/// a catalog.
+///
+/// # Examples
+/// ```
+/// use partiql_catalog::catalog::PartiqlCatalog;
+/// use partiql_irgen::{IrGenerator, IrLoader};
+/// use partiql_logical_planner::LogicalPlanner;
+/// use partiql_parser::Parser;
+///
+/// let catalog = PartiqlCatalog::default().to_shared_catalog();
+/// let parsed = Parser::default().parse("SELECT VALUE x.a FROM t AS x").unwrap();
+/// let logical = LogicalPlanner::new(&catalog).lower(&parsed).unwrap();
+///
+/// let ir = IrGenerator::new().generate(&logical);
+/// assert_eq!(IrLoader::new(&catalog).load(&ir), Ok(logical));
+/// ```
+#[derive(Debug)]
+pub struct IrLoader<'c> {
+    catalog: &'c dyn ReadOnlyCatalog,
+}
+
+impl<'c> IrLoader<'c> {
+    #[must_use]
+    pub fn new(catalog: &'c dyn ReadOnlyCatalog) -> Self {
+        IrLoader { catalog }
+    }
+
+    /// Loads `ir` into a logical plan.
+    pub fn load(&self, ir: &ir::IrPlan) -> Result<LogicalPlan<BindingsOp>> {
+        if !ir::IR_VERSION.can_read(&ir.version) {
+            return Err(IrLoadError::UnsupportedVersion(ir.version));
+        }
+        let objects = ir
+            .objects
+            .iter()
+            .map(|object| self.resolve(object))
+            .collect::<Result<Vec<_>>>()?;
+        Loader {
+            objects: &ir.objects,
+            ids: objects,
+        }
+        .plan(&ir.plan)
+    }
+
+    fn resolve(&self, object: &ir::ObjectRef) -> Result<(ObjectId, usize)> {
+        let entry = self
+            .catalog
+            .get_function(&object.name)
+            .ok_or_else(|| IrLoadError::UnknownObject(object.name.clone()))?;
+        let overloads = match (object.kind, entry.entry()) {
+            (ir::ObjectKind::ScalarFunction, FunctionEntryFunction::Scalar(overloads)) => {
+                overloads.len()
+            }
+            (ir::ObjectKind::AggregateFunction, FunctionEntryFunction::Aggregate(_)) => 0,
+            _ => return Err(IrLoadError::ObjectKindMismatch(object.name.clone())),
+        };
+        Ok((*entry.id(), overloads))
+    }
+}
+
+/// The state of loading a single [`ir::IrPlan`].
+struct Loader<'a> {
+    objects: &'a [ir::ObjectRef],
+    /// The id in the catalog and the number of overloads of each of the plan's objects
+    ids: Vec<(ObjectId, usize)>,
+}
+
+impl Loader<'_> {
+    /// Returns the name, id and number of overloads of the object of kind `kind` at `idx`.
+    fn object(&self, idx: ir::ObjectIdx, kind: ir::ObjectKind) -> Result<(&str, ObjectId, usize)> {
+        let i = idx.0 as usize;
+        match (self.objects.get(i), self.ids.get(i)) {
+            (Some(object), Some((id, overloads))) if object.kind == kind => {
+                Ok((&object.name, *id, *overloads))
+            }
+            (Some(object), _) => Err(IrLoadError::ObjectKindMismatch(object.name.clone())),
+            _ => Err(IrLoadError::InvalidObjectIdx(idx.0)),
+        }
+    }
+
+    fn plan(&self, plan: &ir::Plan) -> Result<LogicalPlan<BindingsOp>> {
+        let mut logical = LogicalPlan::new();
+        let mut ids = Vec::with_capacity(plan.operators.len());
+        for op in &plan.operators {
+            ids.push(logical.add_operator(self.op(op)?));
+        }
+        for flow in &plan.flows {
+            match (ids.get(flow.src.0 as usize), ids.get(flow.dst.0 as usize)) {
+                (Some(src), Some(dst)) => logical.add_flow_with_branch_num(*src, *dst, flow.branch),
+                _ => return Err(IrLoadError::InvalidFlow(flow.src.0, flow.dst.0)),
+            }
+        }
+        Ok(logical)
+    }
+
+    fn op(&self, op: &ir::Operator) -> Result<BindingsOp> {
+        Ok(match op {
+            ir::Operator::Scan(scan) => BindingsOp::Scan(logical::Scan {
+                expr: self.expr(&scan.expr)?,
+                as_key: scan.as_key.clone(),
+                at_key: scan.at_key.clone(),
+                columns: scan.columns.clone(),
+            }),
+            ir::Operator::Pivot(pivot) => BindingsOp::Pivot(logical::Pivot {
+                key: self.expr(&pivot.key)?,
+                value: self.expr(&pivot.value)?,
+            }),
+            ir::Operator::Unpivot(unpivot) => BindingsOp::Unpivot(logical::Unpivot {
+                expr: self.expr(&unpivot.expr)?,
+                as_key: unpivot.as_key.clone(),
+                at_key: unpivot.at_key.clone(),
+            }),
+            ir::Operator::Filter(expr) => BindingsOp::Filter(logical::Filter {
+                expr: self.expr(expr)?,
+            }),
+            ir::Operator::Let(bindings) => BindingsOp::Let(logical::Let {
+                bindings: self.named_exprs(bindings)?,
+            }),
+            ir::Operator::OrderBy(specs) => BindingsOp::OrderBy(logical::OrderBy {
+                specs: self.sort_specs(specs)?,
+            }),
+            ir::Operator::LimitOffset(limit_offset) => {
+                BindingsOp::LimitOffset(logical::LimitOffset {
+                    limit: self.opt_expr(limit_offset.limit.as_ref())?,
+                    offset: self.opt_expr(limit_offset.offset.as_ref())?,
+                })
+            }
+            ir::Operator::Join(join) => BindingsOp::Join(logical::Join {
+                kind: match join.kind {
+                    ir::JoinKind::Inner => logical::JoinKind::Inner,
+                    ir::JoinKind::Left => logical::JoinKind::Left,
+                    ir::JoinKind::Right => logical::JoinKind::Right,
+                    ir::JoinKind::Full => logical::JoinKind::Full,
+                    ir::JoinKind::Cross => logical::JoinKind::Cross,
+                },
+                left: Box::new(self.op(&join.left)?),
+                right: Box::new(self.op(&join.right)?),
+                on: self.opt_expr(join.on.as_ref())?,
+                using: self.named_exprs(&join.using)?,
+            }),
+            ir::Operator::BagOp(bag_op) => BindingsOp::BagOp(logical::BagOp {
+                bag_op: match bag_op.op {
+                    ir::BagOperator::Union => logical::BagOperator::Union,
+                    ir::BagOperator::Except => logical::BagOperator::Except,
+                    ir::BagOperator::Intersect => logical::BagOperator::Intersect,
+                    ir::BagOperator::OuterUnion => logical::BagOperator::OuterUnion,
+                    ir::BagOperator::OuterExcept => logical::BagOperator::OuterExcept,
+                    ir::BagOperator::OuterIntersect => logical::BagOperator::OuterIntersect,
+                },
+                setq: setq(bag_op.setq),
+            }),
+            ir::Operator::Project(exprs) => BindingsOp::Project(logical::Project {
+                exprs: self.named_exprs(exprs)?,
+            }),
+            ir::Operator::ProjectAll(ir::ProjectAll { mode, join_columns }) => {
+                BindingsOp::ProjectAll(logical::ProjectAll {
+                    mode: match mode {
+                        ir::ProjectAllMode::Unwrap => logical::ProjectAllMode::Unwrap,
+                        ir::ProjectAllMode::PassThrough => logical::ProjectAllMode::PassThrough,
+                    },
+                    join_columns: join_columns.clone(),
+                })
+            }
+            ir::Operator::ProjectValue(project) => {
+                BindingsOp::ProjectValue(logical::ProjectValue {
+                    expr: self.expr(&project.expr)?,
+                })
+            }
+            ir::Operator::ExprQuery(expr) => BindingsOp::ExprQuery(logical::ExprQuery {
+                expr: self.expr(expr)?,
+            }),
+            ir::Operator::Distinct => BindingsOp::Distinct,
+            ir::Operator::GroupBy(group_by) => BindingsOp::GroupBy(logical::GroupBy {
+                strategy: match group_by.strategy {
+                    ir::GroupingStrategy::Full => logical::GroupingStrategy::GroupFull,
+                    ir::GroupingStrategy::Partial => logical::GroupingStrategy::GroupPartial,
+                },
+                exprs: self
+                    .named_exprs(&group_by.exprs)?
+                    .into_iter()
+                    .collect::<FxHashMap<_, _>>(),
+                aggregate_exprs: group_by
+                    .aggregate_exprs
+                    .iter()
+                    .map(|agg| {
+                        Ok(logical::AggregateExpression {
+                            name: agg.name.clone(),
+                            expr: self.expr(&agg.expr)?,
+                            args: agg
+                                .args
+                                .iter()
+                                .map(|arg| self.expr(arg))
+                                .collect::<Result<_>>()?,
+                            func: self.agg_func(&agg.func)?,
+                            setq: setq(agg.setq),
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+                group_as_alias: group_by.group_as_alias.clone(),
+            }),
+            ir::Operator::Having(expr) => BindingsOp::Having(logical::Having {
+                expr: self.expr(expr)?,
+            }),
+            ir::Operator::With(with) => BindingsOp::With(logical::With {
+                ctes: with
+                    .ctes
+                    .iter()
+                    .map(|cte| self.cte(cte))
+                    .collect::<Result<_>>()?,
+                body: self.plan(&with.body)?,
+            }),
+            ir::Operator::Window(exprs) => BindingsOp::Window(logical::Window {
+                exprs: exprs
+                    .iter()
+                    .map(|expr| self.window_expr(expr))
+                    .collect::<Result<_>>()?,
+            }),
+            ir::Operator::Exclude(paths) => BindingsOp::Exclude(logical::Exclude {
+                paths: paths
+                    .iter()
+                    .map(|path| logical::ExcludePath {
+                        root: name(&path.root),
+                        steps: path.steps.iter().map(exclude_step).collect(),
+                    })
+                    .collect(),
+            }),
+            ir::Operator::Dml(dml) => BindingsOp::Dml(self.dml(dml)?),
+            ir::Operator::Sink => BindingsOp::Sink,
+        })
+    }
+
+    fn cte(&self, cte: &ir::CommonTableExpr) -> Result<logical::CommonTableExpr> {
+        let recursive = match &cte.recursive {
+            None => None,
+            Some(step) => Some(logical::RecursiveStep {
+                plan: self.plan(&step.plan)?,
+                setq: setq(step.setq),
+                search: step.search.as_ref().map(|search| logical::CteSearch {
+                    order: match search.order {
+                        ir::SearchOrder::DepthFirst => logical::SearchOrder::DepthFirst,
+                        ir::SearchOrder::BreadthFirst => logical::SearchOrder::BreadthFirst,
+                    },
+                    by: search.by.clone(),
+                    set: search.set.clone(),
+                }),
+                cycle: match &step.cycle {
+                    None => None,
+                    Some(cycle) => Some(logical::CteCycle {
+                        columns: cycle.columns.clone(),
+                        mark: cycle.mark.clone(),
+                        mark_value: lit(&cycle.mark_value)?,
+                        default_value: lit(&cycle.default_value)?,
+                        path: cycle.path.clone(),
+                    }),
+                },
+                parent: step.parent.clone(),
+            }),
+        };
+        Ok(logical::CommonTableExpr {
+            name: cte.name.clone(),
+            columns: cte.columns.clone(),
+            plan: self.plan(&cte.plan)?,
+            recursive,
+        })
+    }
+
+    fn window_expr(&self, expr: &ir::WindowExpr) -> Result<logical::WindowExpression> {
+        let frame = &expr.frame;
+        Ok(logical::WindowExpression {
+            name: expr.name.clone(),
+            func: match &expr.func {
+                ir::WindowFunc::RowNumber => logical::WindowFunc::RowNumber,
+                ir::WindowFunc::Rank => logical::WindowFunc::Rank,
+                ir::WindowFunc::DenseRank => logical::WindowFunc::DenseRank,
+                ir::WindowFunc::Lag => logical::WindowFunc::Lag,
+                ir::WindowFunc::Lead => logical::WindowFunc::Lead,
+                ir::WindowFunc::FirstValue => logical::WindowFunc::FirstValue,
+                ir::WindowFunc::LastValue => logical::WindowFunc::LastValue,
+                ir::WindowFunc::Agg(func) => logical::WindowFunc::Agg(self.agg_func(func)?),
+            },
+            args: self.exprs(&expr.args)?,
+            setq: setq(expr.setq),
+            partition_by: self.exprs(&expr.partition_by)?,
+            order_by: self.sort_specs(&expr.order_by)?,
+            frame: logical::WindowFrame {
+                units: match frame.units {
+                    ir::WindowFrameUnits::Rows => logical::WindowFrameUnits::Rows,
+                    ir::WindowFrameUnits::Range => logical::WindowFrameUnits::Range,
+                },
+                start: frame_bound(frame.start),
+                end: frame_bound(frame.end),
+            },
+        })
+    }
+
+    fn dml(&self, dml: &ir::Dml) -> Result<logical::Dml> {
+        Ok(logical::Dml {
+            target: logical::DmlTarget {
+                name: name(&dml.target),
+                as_key: dml.as_key.clone(),
+            },
+            op: match &dml.op {
+                ir::DmlOp::Insert {
+                    values,
+                    on_conflict,
+                } => logical::DmlOp::Insert {
+                    values: self.expr(values)?,
+                    on_conflict: self.opt_expr(on_conflict.as_ref())?,
+                },
+                ir::DmlOp::Update { filter, changes } => logical::DmlOp::Update {
+                    filter: self.opt_expr(filter.as_ref())?,
+                    changes: changes
+                        .iter()
+                        .map(|change| {
+                            Ok(match change {
+                                ir::UpdateChange::Set(path, value) => {
+                                    logical::UpdateChange::Set(self.path(path)?, self.expr(value)?)
+                                }
+                                ir::UpdateChange::Remove(path) => {
+                                    logical::UpdateChange::Remove(self.path(path)?)
+                                }
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                },
+                ir::DmlOp::Delete { filter } => logical::DmlOp::Delete {
+                    filter: self.opt_expr(filter.as_ref())?,
+                },
+            },
+            returning: dml
+                .returning
+                .iter()
+                .map(|elem| {
+                    Ok(logical::ReturningElem {
+                        mapping: match elem.mapping {
+                            ir::ReturningMapping::ModifiedNew => {
+                                logical::ReturningMapping::ModifiedNew
+                            }
+                            ir::ReturningMapping::ModifiedOld => {
+                                logical::ReturningMapping::ModifiedOld
+                            }
+                            ir::ReturningMapping::AllNew => logical::ReturningMapping::AllNew,
+                            ir::ReturningMapping::AllOld => logical::ReturningMapping::AllOld,
+                        },
+                        column: match &elem.column {
+                            None => logical::ReturningColumn::Wildcard,
+                            Some((name, expr)) => {
+                                logical::ReturningColumn::Expr(name.clone(), self.expr(expr)?)
+                            }
+                        },
+                    })
+                })
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    fn sort_specs(&self, specs: &[ir::SortSpec]) -> Result<Vec<logical::SortSpec>> {
+        specs
+            .iter()
+            .map(|spec| {
+                Ok(logical::SortSpec {
+                    expr: self.expr(&spec.expr)?,
+                    order: match spec.order {
+                        ir::SortOrder::Asc => logical::SortSpecOrder::Asc,
+                        ir::SortOrder::Desc => logical::SortSpecOrder::Desc,
+                    },
+                    null_order: match spec.null_order {
+                        ir::NullOrder::First => logical::SortSpecNullOrder::First,
+                        ir::NullOrder::Last => logical::SortSpecNullOrder::Last,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn agg_func(&self, func: &ir::AggFunc) -> Result<logical::AggFunc> {
+        Ok(match func {
+            ir::AggFunc::Avg => logical::AggFunc::AggAvg,
+            ir::AggFunc::Count => logical::AggFunc::AggCount,
+            ir::AggFunc::Max => logical::AggFunc::AggMax,
+            ir::AggFunc::Min => logical::AggFunc::AggMin,
+            ir::AggFunc::Sum => logical::AggFunc::AggSum,
+            ir::AggFunc::Any => logical::AggFunc::AggAny,
+            ir::AggFunc::Every => logical::AggFunc::AggEvery,
+            ir::AggFunc::Catalog(idx) => {
+                let (name, id, _) = self.object(*idx, ir::ObjectKind::AggregateFunction)?;
+                logical::AggFunc::AggById(name.to_string(), id)
+            }
+        })
+    }
+
+    fn named_exprs(&self, exprs: &[(String, ir::Expr)]) -> Result<Vec<(String, ValueExpr)>> {
+        exprs
+            .iter()
+            .map(|(name, expr)| Ok((name.clone(), self.expr(expr)?)))
+            .collect()
+    }
+
+    fn exprs(&self, exprs: &[ir::Expr]) -> Result<Vec<ValueExpr>> {
+        exprs.iter().map(|expr| self.expr(expr)).collect()
+    }
+
+    fn opt_expr(&self, expr: Option<&ir::Expr>) -> Result<Option<ValueExpr>> {
+        expr.map(|expr| self.expr(expr)).transpose()
+    }
+
+    fn boxed(&self, expr: &ir::Expr) -> Result<Box<ValueExpr>> {
+        Ok(Box::new(self.expr(expr)?))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn cases(
+        &self,
+        cases: &[(ir::Expr, ir::Expr)],
+    ) -> Result<Vec<(Box<ValueExpr>, Box<ValueExpr>)>> {
+        cases
+            .iter()
+            .map(|(when, then)| Ok((self.boxed(when)?, self.boxed(then)?)))
+            .collect()
+    }
+
+    fn expr(&self, expr: &ir::Expr) -> Result<ValueExpr> {
+        Ok(match expr {
+            ir::Expr::Unary(op, operand) => ValueExpr::UnExpr(
+                match op {
+                    ir::UnaryOp::Pos => logical::UnaryOp::Pos,
+                    ir::UnaryOp::Neg => logical::UnaryOp::Neg,
+                    ir::UnaryOp::Not => logical::UnaryOp::Not,
+                },
+                self.boxed(operand)?,
+            ),
+            ir::Expr::Binary(op, lhs, rhs) => {
+                ValueExpr::BinaryExpr(binary_op(*op), self.boxed(lhs)?, self.boxed(rhs)?)
+            }
+            ir::Expr::Lit(value) => ValueExpr::Lit(Box::new(lit(value)?)),
+            ir::Expr::DynamicLookup(lookups) => {
+                ValueExpr::DynamicLookup(Box::new(self.exprs(lookups)?))
+            }
+            ir::Expr::Path(root, path) => ValueExpr::Path(self.boxed(root)?, self.path(path)?),
+            ir::Expr::VarRef(var, kind) => ValueExpr::VarRef(
+                name(var),
+                match kind {
+                    ir::VarRefKind::Global => logical::VarRefType::Global,
+                    ir::VarRefKind::Local => logical::VarRefType::Local,
+                },
+            ),
+            ir::Expr::Parameter(param) => ValueExpr::Parameter(match param {
+                ir::Parameter::Positional(n) => logical::Parameter::Positional(*n as usize),
+                ir::Parameter::Named(name) => logical::Parameter::Named(name.clone()),
+            }),
+            ir::Expr::Tuple(attrs) => {
+                let mut tuple = logical::TupleExpr::new();
+                for (attr, value) in attrs {
+                    tuple.attrs.push(self.expr(attr)?);
+                    tuple.values.push(self.expr(value)?);
+                }
+                ValueExpr::TupleExpr(tuple)
+            }
+            ir::Expr::List(elements) => ValueExpr::ListExpr(logical::ListExpr {
+                elements: self.exprs(elements)?,
+            }),
+            ir::Expr::Bag(elements) => ValueExpr::BagExpr(logical::BagExpr {
+                elements: self.exprs(elements)?,
+            }),
+            ir::Expr::Between { value, from, to } => ValueExpr::BetweenExpr(logical::BetweenExpr {
+                value: self.boxed(value)?,
+                from: self.boxed(from)?,
+                to: self.boxed(to)?,
+            }),
+            ir::Expr::Like { value, pattern } => {
+                ValueExpr::PatternMatchExpr(logical::PatternMatchExpr {
+                    value: self.boxed(value)?,
+                    pattern: match pattern {
+                        ir::LikePattern::Lit { pattern, escape } => {
+                            logical::Pattern::Like(logical::LikeMatch {
+                                pattern: pattern.clone(),
+                                escape: escape.clone(),
+                            })
+                        }
+                        ir::LikePattern::Expr { pattern, escape } => {
+                            logical::Pattern::LikeNonStringNonLiteral(
+                                logical::LikeNonStringNonLiteralMatch {
+                                    pattern: self.boxed(pattern)?,
+                                    escape: self.boxed(escape)?,
+                                },
+                            )
+                        }
+                    },
+                })
+            }
+            ir::Expr::SubQuery(plan) => ValueExpr::SubQueryExpr(logical::SubQueryExpr {
+                plan: self.plan(plan)?,
+            }),
+            ir::Expr::SimpleCase {
+                expr,
+                cases,
+                default,
+            } => ValueExpr::SimpleCase(logical::SimpleCase {
+                expr: self.boxed(expr)?,
+                cases: self.cases(cases)?,
+                default: default.as_deref().map(|d| self.boxed(d)).transpose()?,
+            }),
+            ir::Expr::SearchedCase { cases, default } => {
+                ValueExpr::SearchedCase(logical::SearchedCase {
+                    cases: self.cases(cases)?,
+                    default: default.as_deref().map(|d| self.boxed(d)).transpose()?,
+                })
+            }
+            ir::Expr::IsType { not, expr, is_type } => ValueExpr::IsTypeExpr(logical::IsTypeExpr {
+                not: *not,
+                expr: self.boxed(expr)?,
+                is_type: ty(*is_type),
+            }),
+            ir::Expr::Cast {
+                kind,
+                expr,
+                cast_type,
+            } => ValueExpr::CastExpr(logical::CastExpr {
+                kind: match kind {
+                    ir::CastKind::Cast => logical::CastKind::Cast,
+                    ir::CastKind::CanCast => logical::CastKind::CanCast,
+                    ir::CastKind::CanLosslessCast => logical::CastKind::CanLosslessCast,
+                },
+                expr: self.boxed(expr)?,
+                cast_type: ty(*cast_type),
+            }),
+            ir::Expr::NullIf(lhs, rhs) => ValueExpr::NullIfExpr(logical::NullIfExpr {
+                lhs: self.boxed(lhs)?,
+                rhs: self.boxed(rhs)?,
+            }),
+            ir::Expr::Coalesce(elements) => ValueExpr::CoalesceExpr(logical::CoalesceExpr {
+                elements: self.exprs(elements)?,
+            }),
+            ir::Expr::Call(func, args) => ValueExpr::Call(logical::CallExpr {
+                name: self.func(func)?,
+                arguments: self.exprs(args)?,
+            }),
+            ir::Expr::GraphMatch { value, pattern } => {
+                ValueExpr::GraphMatch(Box::new(logical::GraphMatchExpr {
+                    value: self.boxed(value)?,
+                    pattern: self.path_pattern(pattern)?,
+                }))
+            }
+        })
+    }
+
+    fn path(&self, path: &[ir::PathStep]) -> Result<Vec<logical::PathComponent>> {
+        path.iter()
+            .map(|step| {
+                Ok(match step {
+                    ir::PathStep::Key(key) => logical::PathComponent::Key(name(key)),
+                    ir::PathStep::Index(idx) => logical::PathComponent::Index(*idx),
+                    ir::PathStep::KeyExpr(expr) => {
+                        logical::PathComponent::KeyExpr(self.boxed(expr)?)
+                    }
+                    ir::PathStep::IndexExpr(expr) => {
+                        logical::PathComponent::IndexExpr(self.boxed(expr)?)
+                    }
+                    ir::PathStep::ForEach => logical::PathComponent::ForEach,
+                    ir::PathStep::Unpivot => logical::PathComponent::Unpivot,
+                })
+            })
+            .collect()
+    }
+
+    fn func(&self, func: &ir::Func) -> Result<logical::CallName> {
+        use logical::CallName;
+        Ok(match func {
+            ir::Func::Lower => CallName::Lower,
+            ir::Func::Upper => CallName::Upper,
+            ir::Func::CharLength => CallName::CharLength,
+            ir::Func::OctetLength => CallName::OctetLength,
+            ir::Func::BitLength => CallName::BitLength,
+            ir::Func::LTrim => CallName::LTrim,
+            ir::Func::BTrim => CallName::BTrim,
+            ir::Func::RTrim => CallName::RTrim,
+            ir::Func::Substring => CallName::Substring,
+            ir::Func::Position => CallName::Position,
+            ir::Func::Overlay => CallName::Overlay,
+            ir::Func::Exists => CallName::Exists,
+            ir::Func::Abs => CallName::Abs,
+            ir::Func::Mod => CallName::Mod,
+            ir::Func::Cardinality => CallName::Cardinality,
+            ir::Func::ExtractYear => CallName::ExtractYear,
+            ir::Func::ExtractMonth => CallName::ExtractMonth,
+            ir::Func::ExtractDay => CallName::ExtractDay,
+            ir::Func::ExtractHour => CallName::ExtractHour,
+            ir::Func::ExtractMinute => CallName::ExtractMinute,
+            ir::Func::ExtractSecond => CallName::ExtractSecond,
+            ir::Func::ExtractTimezoneHour => CallName::ExtractTimezoneHour,
+            ir::Func::ExtractTimezoneMinute => CallName::ExtractTimezoneMinute,
+            ir::Func::CollAvg(q) => CallName::CollAvg(setq(*q)),
+            ir::Func::CollCount(q) => CallName::CollCount(setq(*q)),
+            ir::Func::CollMax(q) => CallName::CollMax(setq(*q)),
+            ir::Func::CollMin(q) => CallName::CollMin(setq(*q)),
+            ir::Func::CollSum(q) => CallName::CollSum(setq(*q)),
+            ir::Func::CollAny(q) => CallName::CollAny(setq(*q)),
+            ir::Func::CollEvery(q) => CallName::CollEvery(setq(*q)),
+            ir::Func::ByName(name) => CallName::ByName(name.clone()),
+            ir::Func::Catalog { object, overload } => {
+                let (name, id, overloads) = self.object(*object, ir::ObjectKind::ScalarFunction)?;
+                let overload = *overload as usize;
+                if overload >= overloads {
+                    return Err(IrLoadError::InvalidOverload(name.to_string(), overload));
+                }
+                CallName::ById(name.to_string(), id, overload)
+            }
+        })
+    }
+
+    fn path_pattern(
+        &self,
+        pattern: &ir_graph::PathPatternMatch,
+    ) -> Result<graph::PathPatternMatch> {
+        Ok(match pattern {
+            ir_graph::PathPatternMatch::Node(node) => {
+                graph::PathPatternMatch::Node(graph::NodeMatch {
+                    binder: graph::BindSpec(node.binder.clone()),
+                    spec: graph::NodeFilter {
+                        label: label_filter(&node.spec.label),
+                        filter: self.value_filter(&node.spec.filter)?,
+                    },
+                })
+            }
+            ir_graph::PathPatternMatch::Match(triple) => {
+                graph::PathPatternMatch::Match(self.triple(triple)?)
+            }
+            ir_graph::PathPatternMatch::Concat(series, mode) => graph::PathPatternMatch::Concat(
+                series
+                    .iter()
+                    .map(|series| {
+                        Ok(graph::TripleSeriesMatch {
+                            triples: series
+                                .triples
+                                .iter()
+                                .map(|triple| self.triple(triple))
+                                .collect::<Result<_>>()?,
+                            filter: self.value_filter(&series.filter)?,
+                            path_mode: path_mode(series.path_mode),
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+                path_mode(*mode),
+            ),
+        })
+    }
+
+    fn triple(&self, triple: &ir_graph::TripleMatch) -> Result<graph::TripleMatch> {
+        let (lhs, edge, rhs) = &triple.binders;
+        let filter = &triple.spec.triple;
+        Ok(graph::TripleMatch {
+            binders: (
+                graph::BindSpec(lhs.clone()),
+                graph::BindSpec(edge.clone()),
+                graph::BindSpec(rhs.clone()),
+            ),
+            spec: graph::StepFilter {
+                dir: direction(triple.spec.dir),
+                triple: graph::TripleFilter {
+                    lhs: graph::NodeFilter {
+                        label: label_filter(&filter.lhs.label),
+                        filter: self.value_filter(&filter.lhs.filter)?,
+                    },
+                    e: graph::EdgeFilter {
+                        label: label_filter(&filter.edge.label),
+                        filter: self.value_filter(&filter.edge.filter)?,
+                    },
+                    rhs: graph::NodeFilter {
+                        label: label_filter(&filter.rhs.label),
+                        filter: self.value_filter(&filter.rhs.filter)?,
+                    },
+                },
+            },
+            filter: self.value_filter(&triple.filter)?,
+            path_mode: path_mode(triple.path_mode),
+        })
+    }
+
+    fn value_filter(&self, filter: &ir_graph::ValueFilter) -> Result<graph::ValueFilter> {
+        Ok(match filter {
+            None => graph::ValueFilter::Always,
+            Some(exprs) => graph::ValueFilter::Filter(self.exprs(exprs)?),
+        })
+    }
+}
+
+fn exclude_step(step: &ir::ExcludeStep) -> logical::ExcludeStep {
+    match step {
+        ir::ExcludeStep::Key(key) => logical::ExcludeStep::Key(name(key)),
+        ir::ExcludeStep::Index(idx) => logical::ExcludeStep::Index(*idx),
+        ir::ExcludeStep::AllElements => logical::ExcludeStep::AllElements,
+        ir::ExcludeStep::AllFields => logical::ExcludeStep::AllFields,
+    }
+}
+
+fn name(name: &ir::Name) -> BindingsName<'static> {
+    match name {
+        ir::Name::CaseSensitive(s) => BindingsName::CaseSensitive(Cow::Owned(s.clone())),
+        ir::Name::CaseInsensitive(s) => BindingsName::CaseInsensitive(Cow::Owned(s.clone())),
+    }
+}
+
+fn setq(setq: ir::SetQuantifier) -> logical::SetQuantifier {
+    match setq {
+        ir::SetQuantifier::All => logical::SetQuantifier::All,
+        ir::SetQuantifier::Distinct => logical::SetQuantifier::Distinct,
+    }
+}
+
+fn binary_op(op: ir::BinaryOp) -> logical::BinaryOp {
+    use logical::BinaryOp;
+    match op {
+        ir::BinaryOp::And => BinaryOp::And,
+        ir::BinaryOp::Or => BinaryOp::Or,
+        ir::BinaryOp::Concat => BinaryOp::Concat,
+        ir::BinaryOp::Eq => BinaryOp::Eq,
+        ir::BinaryOp::Neq => BinaryOp::Neq,
+        ir::BinaryOp::Gt => BinaryOp::Gt,
+        ir::BinaryOp::Gteq => BinaryOp::Gteq,
+        ir::BinaryOp::Lt => BinaryOp::Lt,
+        ir::BinaryOp::Lteq => BinaryOp::Lteq,
+        ir::BinaryOp::Add => BinaryOp::Add,
+        ir::BinaryOp::Sub => BinaryOp::Sub,
+        ir::BinaryOp::Mul => BinaryOp::Mul,
+        ir::BinaryOp::Div => BinaryOp::Div,
+        ir::BinaryOp::Mod => BinaryOp::Mod,
+        ir::BinaryOp::Exp => BinaryOp::Exp,
+        ir::BinaryOp::In => BinaryOp::In,
+    }
+}
+
+fn lit(lit: &ir::Lit) -> Result<logical::Lit> {
+    Ok(match lit {
+        ir::Lit::Null => logical::Lit::Null,
+        ir::Lit::Missing => logical::Lit::Missing,
+        ir::Lit::Int8(i) => logical::Lit::Int8(*i),
+        ir::Lit::Int16(i) => logical::Lit::Int16(*i),
+        ir::Lit::Int32(i) => logical::Lit::Int32(*i),
+        ir::Lit::Int64(i) => logical::Lit::Int64(*i),
+        ir::Lit::Decimal(d) => logical::Lit::Decimal(
+            Decimal::from_str(d).map_err(|_| IrLoadError::InvalidDecimal(d.clone()))?,
+        ),
+        ir::Lit::Double(f) => logical::Lit::Double(OrderedFloat(*f)),
+        ir::Lit::Bool(b) => logical::Lit::Bool(*b),
+        ir::Lit::String(s) => logical::Lit::String(s.clone()),
+        ir::Lit::Variant { bytes, type_name } => {
+            logical::Lit::Variant(bytes.clone(), type_name.clone())
+        }
+        ir::Lit::Struct(attrs) => logical::Lit::Struct(
+            attrs
+                .iter()
+                .map(|(name, value)| Ok((name.clone(), self::lit(value)?)))
+                .collect::<Result<_>>()?,
+        ),
+        ir::Lit::Bag(elements) => {
+            logical::Lit::Bag(elements.iter().map(self::lit).collect::<Result<_>>()?)
+        }
+        ir::Lit::List(elements) => {
+            logical::Lit::List(elements.iter().map(self::lit).collect::<Result<_>>()?)
+        }
+    })
+}
+
+fn ty(ty: ir::Type) -> logical::Type {
+    use logical::Type;
+    match ty {
+        ir::Type::Null => Type::NullType,
+        ir::Type::Boolean => Type::BooleanType,
+        ir::Type::Integer2 => Type::Integer2Type,
+        ir::Type::Integer4 => Type::Integer4Type,
+        ir::Type::Integer8 => Type::Integer8Type,
+        ir::Type::Decimal(p, s) => Type::DecimalType(p, s),
+        ir::Type::Numeric(p, s) => Type::NumericType(p, s),
+        ir::Type::Real => Type::RealType,
+        ir::Type::DoublePrecision => Type::DoublePrecisionType,
+        ir::Type::Timestamp(p) => Type::TimestampType(p),
+        ir::Type::Character(l) => Type::CharacterType(l),
+        ir::Type::CharacterVarying(l) => Type::CharacterVaryingType(l),
+        ir::Type::Missing => Type::MissingType,
+        ir::Type::String => Type::StringType,
+        ir::Type::Symbol => Type::SymbolType,
+        ir::Type::Blob => Type::BlobType,
+        ir::Type::Clob => Type::ClobType,
+        ir::Type::Date => Type::DateType,
+        ir::Type::Time(p) => Type::TimeType(p),
+        ir::Type::ZonedTime(p) => Type::ZonedTimeType(p),
+        ir::Type::ZonedTimestamp(p) => Type::ZonedTimestampType(p),
+        ir::Type::Struct => Type::StructType,
+        ir::Type::Tuple => Type::TupleType,
+        ir::Type::List => Type::ListType,
+        ir::Type::Sexp => Type::SexpType,
+        ir::Type::Bag => Type::BagType,
+        ir::Type::Any => Type::AnyType,
+    }
+}
+
+fn frame_bound(bound: ir::WindowFrameBound) -> logical::WindowFrameBound {
+    match bound {
+        ir::WindowFrameBound::UnboundedPreceding => logical::WindowFrameBound::UnboundedPreceding,
+        ir::WindowFrameBound::Preceding(n) => logical::WindowFrameBound::Preceding(n),
+        ir::WindowFrameBound::CurrentRow => logical::WindowFrameBound::CurrentRow,
+        ir::WindowFrameBound::Following(n) => logical::WindowFrameBound::Following(n),
+        ir::WindowFrameBound::UnboundedFollowing => logical::WindowFrameBound::UnboundedFollowing,
+    }
+}
+
+fn direction(dir: ir_graph::DirectionFilter) -> graph::DirectionFilter {
+    match dir {
+        ir_graph::DirectionFilter::Left => graph::DirectionFilter::L,
+        ir_graph::DirectionFilter::Undirected => graph::DirectionFilter::U,
+        ir_graph::DirectionFilter::Right => graph::DirectionFilter::R,
+        ir_graph::DirectionFilter::LeftOrUndirected => graph::DirectionFilter::LU,
+        ir_graph::DirectionFilter::UndirectedOrRight => graph::DirectionFilter::UR,
+        ir_graph::DirectionFilter::LeftOrRight => graph::DirectionFilter::LR,
+        ir_graph::DirectionFilter::Any => graph::DirectionFilter::LUR,
+    }
+}
+
+fn path_mode(mode: ir_graph::PathMode) -> graph::PathMode {
+    match mode {
+        ir_graph::PathMode::Walk => graph::PathMode::Walk,
+        ir_graph::PathMode::Trail => graph::PathMode::Trail,
+        ir_graph::PathMode::Acyclic => graph::PathMode::Acyclic,
+        ir_graph::PathMode::Simple => graph::PathMode::Simple,
+    }
+}
+
+fn label_filter(label: &ir_graph::LabelFilter) -> graph::LabelFilter {
+    match label {
+        ir_graph::LabelFilter::Always => graph::LabelFilter::Always,
+        ir_graph::LabelFilter::Named(name) => graph::LabelFilter::Named(name.clone()),
+        ir_graph::LabelFilter::Negated(inner) => {
+            graph::LabelFilter::Negated(Box::new(label_filter(inner)))
+        }
+        ir_graph::LabelFilter::Conjunction(labels) => {
+            graph::LabelFilter::Conjunction(labels.iter().map(label_filter).collect())
+        }
+        ir_graph::LabelFilter::Disjunction(labels) => {
+            graph::LabelFilter::Disjunction(labels.iter().map(label_filter).collect())
+        }
+        ir_graph::LabelFilter::Never => graph::LabelFilter::Never,
+    }
+}
@@ -16,8 +16,8 @@ impl Function for FunctionEntry<'_> {
             FunctionEntryFunction::Scalar(scfn) => {
                 ScalarFnResolver { oid, scfn }.resolve(name, args)
             }
-            FunctionEntryFunction::Aggregate() => {
-                todo!("Aggregate function resolution")
+            FunctionEntryFunction::Aggregate(_) => {
+                Err(CallLookupError::AggregateAsScalar(name.into()))
             }
         }
     }
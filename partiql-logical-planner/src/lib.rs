@@ -33,7 +33,7 @@ impl<'c> LogicalPlanner<'c> {
         let q = &parsed.ast;
         let mut resolver = NameResolver::new(self.catalog);
         let registry = resolver.resolve(q)?;
-        let planner = AstToLogical::new(self.catalog, registry);
+        let planner = AstToLogical::new(self.catalog, registry, &parsed.locations);
         planner.lower_query(q)
     }
 }
@@ -5,12 +5,13 @@ use ordered_float::OrderedFloat;
 use partiql_ast::ast;
 use partiql_ast::ast::{
     Assignment, Bag, BagOpExpr, BagOperator, Between, BinOp, BinOpKind, Call, CallAgg, CallArg,
-    CallArgNamed, CaseSensitivity, CreateIndex, CreateTable, Ddl, DdlOp, Delete, Dml, DmlOp,
-    DropIndex, DropTable, Exclusion, Expr, FromClause, FromLet, FromLetKind, GroupByExpr, GroupKey,
-    GroupingStrategy, Insert, InsertValue, Item, Join, JoinKind, JoinSpec, Like, List, Lit,
-    NullOrderingSpec, OnConflict, OrderByExpr, OrderingSpec, Path, PathStep, ProjectExpr,
-    Projection, ProjectionKind, Query, QuerySet, Remove, SearchedCase, Select, Set, SetQuantifier,
-    SimpleCase, SortSpec, Struct, SymbolPrimitive, UniOp, UniOpKind, VarRef,
+    CallArgNamed, CallArgNamedType, CaseSensitivity, ColumnComponent, CreateIndex, CreateTable,
+    Ddl, DdlOp, Dml, DmlOp, DropIndex, DropTable, ExcludePath, ExcludePathStep, Exclusion, Expr,
+    FromClause, FromLet, FromLetKind, FromSource, GroupByExpr, GroupKey, GroupingStrategy, Insert,
+    InsertValue, Item, Join, JoinKind, JoinSpec, Like, List, Lit, NullOrderingSpec, OrderByExpr,
+    OrderingSpec, Path, PathStep, ProjectExpr, Projection, ProjectionKind, Query, QuerySet,
+    QueryTable, Remove, ReturningExpr, SearchedCase, Select, SetQuantifier, SimpleCase, SortSpec,
+    Struct, SymbolPrimitive, UniOp, UniOpKind, VarRef, Window, WindowSpec, WithClause, WithElement,
 };
 use partiql_ast::visit::{Traverse, Visit, Visitor};
 use partiql_logical as logical;
@@ -32,12 +33,18 @@ use partiql_ast_passes::error::{AstTransformError, AstTransformationError};
 
 use crate::functions::Function;
 use partiql_ast_passes::name_resolver::NameRef;
-use partiql_catalog::catalog::SharedCatalog;
+use partiql_catalog::catalog::{FunctionEntryFunction, SharedCatalog};
+use partiql_common::catalog::ObjectId;
 use partiql_common::node::{IdAnnotated, NodeId};
+use partiql_common::pretty::ToPretty;
+use partiql_common::syntax::metadata::LocationMap;
 
-use partiql_logical::AggFunc::{AggAny, AggAvg, AggCount, AggEvery, AggMax, AggMin, AggSum};
+use partiql_logical::AggFunc::{
+    AggAny, AggAvg, AggById, AggCount, AggEvery, AggMax, AggMin, AggSum,
+};
 use partiql_logical::ValueExpr::DynamicLookup;
 use rustc_hash::{FxBuildHasher, FxHashMap};
+use std::ops::RangeInclusive;
 use std::sync::atomic::{AtomicU32, Ordering};
 
 type FnvIndexMap<K, V> = IndexMap<K, V, FnvBuildHasher>;
@@ -100,6 +107,18 @@ pub(crate) fn extract_vexpr_by_id(
     Some(v.remove(position).1)
 }
 
+/// Converts a `SymbolPrimitive` into a `BindingsName`
+fn symprim_to_binding(sym: &SymbolPrimitive) -> BindingsName<'static> {
+    match sym.case {
+        CaseSensitivity::CaseSensitive => {
+            BindingsName::CaseSensitive(Cow::Owned(sym.value.clone()))
+        }
+        CaseSensitivity::CaseInsensitive => {
+            BindingsName::CaseInsensitive(Cow::Owned(sym.value.clone()))
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 enum QueryContext {
     FromLet,
@@ -115,10 +134,14 @@ struct QueryClauses {
     where_clause: Option<logical::OpId>,
     group_by_clause: Option<logical::OpId>,
     having_clause: Option<logical::OpId>,
+    window_clause: Option<logical::OpId>,
+    exclude_clause: Option<logical::OpId>,
     order_by_clause: Option<logical::OpId>,
     limit_offset_clause: Option<logical::OpId>,
     select_clause: Option<logical::OpId>,
     distinct: Option<logical::OpId>,
+    /// The filtering clause (`WHERE` or `HAVING`) being lowered, if any
+    filter: Option<&'static str>,
 }
 
 impl QueryClauses {
@@ -129,6 +152,8 @@ impl QueryClauses {
             self.where_clause,
             self.group_by_clause,
             self.having_clause,
+            self.window_clause,
+            self.exclude_clause,
             self.order_by_clause,
             self.limit_offset_clause,
             self.select_clause,
@@ -141,6 +166,23 @@ impl QueryClauses {
     }
 }
 
+/// The parts of a data modification statement lowered so far.
+#[derive(Clone, Debug, Default)]
+struct DmlClauses {
+    target: Option<BindingsName<'static>>,
+    insert: Option<(ValueExpr, Option<ValueExpr>)>,
+    /// The lowered target and, for a `SET`, value of each `SET` and `REMOVE`
+    changes: Vec<(ValueExpr, Option<ValueExpr>)>,
+    returning: Vec<logical::ReturningElem>,
+}
+
+/// The common table expressions of a `WITH` clause lowered so far.
+#[derive(Clone, Debug, Default)]
+struct WithClauses {
+    recursive: bool,
+    ctes: Vec<logical::CommonTableExpr>,
+}
+
 #[derive(Debug)]
 struct IdGenerator {
     next_id: AtomicU32,
@@ -177,13 +219,19 @@ pub struct AstToLogical<'a> {
     path_stack: Vec<Vec<PathComponent>>,
     sort_stack: Vec<Vec<logical::SortSpec>>,
     aggregate_exprs: Vec<Vec<AggregateExpression>>,
+    window_exprs: Vec<Vec<logical::WindowExpression>>,
+    window_specs: Vec<(Vec<ValueExpr>, Vec<logical::SortSpec>)>,
     projection_renames: Vec<FnvIndexMap<String, BindingsName<'a>>>,
+    // the `WITH` clauses being lowered, keyed by the query they belong to
+    with_clauses: Vec<(NodeId, WithClauses)>,
+    dml: Option<DmlClauses>,
 
     aliases: FnvIndexMap<NodeId, SymbolPrimitive>,
 
     // generator of 'fresh' ids
     id: IdGenerator,
     agg_id: IdGenerator,
+    win_id: IdGenerator,
 
     // output
     plan_stack: Vec<LogicalPlan<BindingsOp>>,
@@ -193,8 +241,12 @@ pub struct AstToLogical<'a> {
     fnsym_tab: &'static FnSymTab,
     catalog: &'a dyn SharedCatalog,
 
-    // list of errors encountered during AST lowering
+    // the locations of the AST nodes, attached to the operators lowered from them
+    locations: &'a LocationMap,
+
+    // list of errors encountered during AST lowering, and the ids of the nodes at which they occur
     errors: Vec<AstTransformError>,
+    error_nodes: Vec<Option<NodeId>>,
 }
 
 /// Attempt to infer an alias for a simple variable reference expression.
@@ -232,7 +284,11 @@ fn infer_id(expr: &ValueExpr) -> Option<SymbolPrimitive> {
 }
 
 impl<'a> AstToLogical<'a> {
-    pub fn new(catalog: &'a dyn SharedCatalog, registry: name_resolver::KeyRegistry) -> Self {
+    pub fn new(
+        catalog: &'a dyn SharedCatalog,
+        registry: name_resolver::KeyRegistry,
+        locations: &'a LocationMap,
+    ) -> Self {
         let fnsym_tab: &FnSymTab = &FN_SYM_TAB;
         AstToLogical {
             id_stack: Default::default(),
@@ -245,14 +301,19 @@ impl<'a> AstToLogical<'a> {
             path_stack: Default::default(),
             sort_stack: Default::default(),
             aggregate_exprs: Default::default(),
+            window_exprs: Default::default(),
+            window_specs: Default::default(),
 
             projection_renames: Default::default(),
+            with_clauses: Default::default(),
+            dml: Default::default(),
 
             aliases: Default::default(),
 
             // generator of 'fresh' ids
             id: Default::default(),
             agg_id: Default::default(),
+            win_id: Default::default(),
 
             // output
             plan_stack: Default::default(),
@@ -261,7 +322,10 @@ impl<'a> AstToLogical<'a> {
             fnsym_tab,
             catalog,
 
+            locations,
+
             errors: vec![],
+            error_nodes: vec![],
         }
     }
 
@@ -277,8 +341,10 @@ impl<'a> AstToLogical<'a> {
             "self.plan_stack.len() != 1"
         );
         if !self.errors.is_empty() {
+            self.locate_errors();
             return Err(AstTransformationError {
                 errors: self.errors,
+                nodes: self.error_nodes,
             });
         }
         Ok(self.plan_stack.pop().unwrap())
@@ -289,6 +355,23 @@ impl<'a> AstToLogical<'a> {
         self.id_stack.last().unwrap()
     }
 
+    /// Removes the `WITH` clause of the current query, if it has one.
+    #[inline]
+    fn take_with_clause(&mut self) -> Option<WithClauses> {
+        let query = *self.current_node();
+        match self.with_clauses.last() {
+            Some((id, _)) if *id == query => self.with_clauses.pop().map(|(_, with)| with),
+            _ => None,
+        }
+    }
+
+    /// Records the current node as the node at which the errors since the last call occurred.
+    #[inline]
+    fn locate_errors(&mut self) {
+        let node = self.id_stack.last().copied();
+        self.error_nodes.resize(self.errors.len(), node);
+    }
+
     #[inline]
     fn gen_id(&self) -> SymbolPrimitive {
         // TODO assure non-collision with provided identifiers. e.g., we shouldn't generate `_1` if the query contains `AS _1`
@@ -318,17 +401,6 @@ impl<'a> AstToLogical<'a> {
             }
         }
 
-        // Convert a `SymbolPrimitive` into a `BindingsName`
-        fn symprim_to_binding(sym: &SymbolPrimitive) -> BindingsName<'static> {
-            match sym.case {
-                CaseSensitivity::CaseSensitive => {
-                    BindingsName::CaseSensitive(Cow::Owned(sym.value.clone()))
-                }
-                CaseSensitivity::CaseInsensitive => {
-                    BindingsName::CaseInsensitive(Cow::Owned(sym.value.clone()))
-                }
-            }
-        }
         // Convert a `name_resolver::Symbol` into a `BindingsName`
         fn sym_to_binding(sym: &name_resolver::Symbol) -> Option<BindingsName<'static>> {
             match sym {
@@ -497,10 +569,163 @@ impl<'a> AstToLogical<'a> {
         ValueExpr::VarRef(symprim_to_binding(&varref.name), VarRefType::Global)
     }
 
+    fn lower_insert(
+        &mut self,
+        target: &Expr,
+        values: ValueExpr,
+        on_conflict: Option<ValueExpr>,
+    ) -> Traverse {
+        let Some(name) = dml_target_name(target) else {
+            self.errors.push(AstTransformError::InvalidDmlTarget(
+                "INSERT must target a collection name".to_string(),
+            ));
+            return Traverse::Stop;
+        };
+        if let Some(dml) = self.dml.as_mut() {
+            dml.target = Some(name);
+            dml.insert = Some((values, on_conflict));
+        }
+        Traverse::Continue
+    }
+
+    /// The id of the aggregation function registered in the catalog under `name`, if any.
+    fn catalog_aggregate(&self, name: &str) -> Option<(ObjectId, RangeInclusive<usize>)> {
+        self.catalog
+            .get_function(name)
+            .and_then(|entry| match entry.entry() {
+                FunctionEntryFunction::Aggregate(expr) => Some((*entry.id(), expr.arity())),
+                _ => None,
+            })
+    }
+
+    /// Lowers a call of `CAST`, `CAN_CAST` or `CAN_LOSSLESS_CAST`, which the parser produces as,
+    /// e.g., `cast(x, "as": INT)`.
+    fn lower_cast(
+        &mut self,
+        name: String,
+        kind: logical::CastKind,
+        args: Vec<CallArgument>,
+    ) -> Traverse {
+        let expr = match <[CallArgument; 2]>::try_from(args) {
+            Ok([CallArgument::Positional(expr), CallArgument::NamedType(arg, cast_type)])
+                if arg == "as" =>
+            {
+                Ok(ValueExpr::CastExpr(logical::CastExpr {
+                    kind,
+                    expr: Box::new(expr),
+                    cast_type,
+                }))
+            }
+            Ok([CallArgument::Positional(_), CallArgument::Named(arg, _)]) if arg == "as" => Err(
+                AstTransformError::InvalidType(format!("target of `{}`", name.to_uppercase())),
+            ),
+            _ => Err(AstTransformError::InvalidNumberOfArguments(name)),
+        };
+        let expr = expr.unwrap_or_else(|err| {
+            self.errors.push(err);
+            ValueExpr::Lit(Box::new(logical::Lit::Missing)) // dummy expression to allow lowering to continue
+        });
+        self.push_vexpr(expr);
+        Traverse::Continue
+    }
+
+    /// Lowers a type, reporting an invalid type and lowering it to `ANY` to allow lowering to
+    /// continue.
+    fn lower_type(&mut self, ty: &ast::Type) -> logical::Type {
+        type_to_type(ty).unwrap_or_else(|err| {
+            self.errors.push(err);
+            logical::Type::AnyType
+        })
+    }
+
+    fn lower_aggregate(&mut self, name: String, mut args: Vec<CallArgument>) -> Traverse {
+        // Rewrites the SQL aggregation function call to be a variable reference that the `GROUP BY`
+        // clause will add to the binding tuples.
+        // E.g. SELECT a, SUM(b) FROM t GROUP BY a
+        //      SELECT a AS a, $__agg_1 AS b FROM t GROUP BY a
+        let new_name = "$__agg".to_owned() + &self.agg_id.id();
+        let new_binding_name = BindingsName::CaseSensitive(Cow::Owned(new_name.clone()));
+        let new_expr = ValueExpr::VarRef(new_binding_name, VarRefType::Local);
+        self.push_vexpr(new_expr);
+
+        true_or_fault!(self, !args.is_empty(), "env is empty");
+        // The aggregated value is the first argument; only catalog functions have further ones
+        let num_args = args.len();
+        let extra_args = args.split_off(1);
+        // Default set quantifier if the set quantifier keyword is omitted will be `ALL`
+        let (setq, arg) = match args.pop().unwrap() {
+            CallArgument::Positional(ve) => (logical::SetQuantifier::All, ve),
+            CallArgument::Named(name, ve) => match name.as_ref() {
+                "all" => (logical::SetQuantifier::All, ve),
+                "distinct" => (logical::SetQuantifier::Distinct, ve),
+                _ => {
+                    self.errors.push(AstTransformError::IllegalState(
+                        "Invalid set quantifier".to_string(),
+                    ));
+                    return Traverse::Stop;
+                }
+            },
+            CallArgument::Star => (
+                logical::SetQuantifier::All,
+                ValueExpr::Lit(Box::new(logical::Lit::Int8(1))),
+            ),
+            CallArgument::PositionalType(_) | CallArgument::NamedType(..) => {
+                self.errors
+                    .push(AstTransformError::UnsupportedAggregationFunction(name));
+                return Traverse::Continue;
+            }
+        };
+
+        let (func, arity) = match name.as_str() {
+            "avg" => (AggAvg, 1..=1),
+            "count" => (AggCount, 1..=1),
+            "max" => (AggMax, 1..=1),
+            "min" => (AggMin, 1..=1),
+            "sum" => (AggSum, 1..=1),
+            "any" | "some" => (AggAny, 1..=1),
+            "every" => (AggEvery, 1..=1),
+            _ => match self.catalog_aggregate(&name) {
+                Some((oid, arity)) => (AggById(name.clone(), oid), arity),
+                None => {
+                    // Include as an error but allow lowering to proceed for multiple error reporting
+                    self.errors
+                        .push(AstTransformError::UnsupportedFunction(name.clone()));
+                    // continue lowering with `AggAvg` aggregation function
+                    (AggAvg, num_args..=num_args)
+                }
+            },
+        };
+        let args = extra_args
+            .into_iter()
+            .map(|arg| match arg {
+                CallArgument::Positional(ve) => Some(ve),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let args = match args {
+            Some(args) if arity.contains(&num_args) => args,
+            _ => {
+                self.errors
+                    .push(AstTransformError::InvalidNumberOfArguments(name));
+                return Traverse::Continue;
+            }
+        };
+        let agg_expr = AggregateExpression {
+            name: new_name,
+            expr: arg,
+            args,
+            func,
+            setq,
+        };
+        self.aggregate_exprs.last_mut().unwrap().push(agg_expr);
+        Traverse::Continue
+    }
+
     #[inline]
     fn enter_q(&mut self) {
         self.q_stack.push(Default::default());
         self.aggregate_exprs.push(Default::default());
+        self.window_exprs.push(Default::default());
         self.ctx_stack.push(QueryContext::Query);
         self.projection_renames.push(Default::default());
     }
@@ -510,6 +735,7 @@ impl<'a> AstToLogical<'a> {
         self.projection_renames.pop().expect("q level");
         self.ctx_stack.pop().expect("q level");
         self.aggregate_exprs.pop().expect("q level");
+        self.window_exprs.pop().expect("q level");
         self.q_stack.pop().expect("q level")
     }
 
@@ -543,6 +769,22 @@ impl<'a> AstToLogical<'a> {
         self.plan_stack.last_mut().expect("plan")
     }
 
+    /// Adds `op` to the current plan, located at the current node.
+    #[inline]
+    fn add_operator(&mut self, op: BindingsOp) -> OpId {
+        let location = self
+            .id_stack
+            .last()
+            .and_then(|id| self.locations.get(id))
+            .cloned();
+        let plan = self.plan_stack.last_mut().expect("plan");
+        let id = plan.add_operator(op);
+        if let Some(location) = location {
+            plan.set_location(id, location);
+        }
+        id
+    }
+
     #[inline]
     fn enter_benv(&mut self) {
         self.bexpr_stack.push(vec![]);
@@ -645,10 +887,12 @@ impl<'a> AstToLogical<'a> {
 //
 impl<'ast> Visitor<'ast> for AstToLogical<'_> {
     fn enter_ast_node(&mut self, id: NodeId) -> Traverse {
+        self.locate_errors();
         self.id_stack.push(id);
         Traverse::Continue
     }
     fn exit_ast_node(&mut self, id: NodeId) -> Traverse {
+        self.locate_errors();
         let cur_node = self.id_stack.pop();
         eq_or_fault!(self, cur_node, Some(id), "id_stack node id != id");
         Traverse::Continue
@@ -683,39 +927,232 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
     }
 
     fn enter_dml(&mut self, _dml: &'ast Dml) -> Traverse {
-        not_yet_implemented_fault!(self, "Dml".to_string());
+        self.enter_q();
+        self.enter_env();
+        self.dml.replace(DmlClauses::default());
+        Traverse::Continue
     }
 
-    fn enter_dml_op(&mut self, _dml_op: &'ast DmlOp) -> Traverse {
-        not_yet_implemented_fault!(self, "DmlOp".to_string());
+    fn exit_dml(&mut self, dml: &'ast Dml) -> Traverse {
+        let mut env = self.exit_env();
+        true_or_fault!(self, env.len() <= 1, "dml env.len() > 1");
+        let filter = env.pop().map(|(_, filter)| filter);
+        let clauses = self.exit_q();
+        let Some(DmlClauses {
+            target,
+            insert,
+            changes,
+            returning,
+        }) = self.dml.take()
+        else {
+            self.errors.push(AstTransformError::IllegalState(
+                "missing dml clauses".to_string(),
+            ));
+            return Traverse::Stop;
+        };
+
+        let op = if let Some((values, on_conflict)) = insert {
+            let Some(name) = target else {
+                self.errors.push(AstTransformError::IllegalState(
+                    "missing INSERT target".to_string(),
+                ));
+                return Traverse::Stop;
+            };
+            let as_key = binding_name(&name).to_string();
+            let target = logical::DmlTarget { name, as_key };
+            let op = logical::DmlOp::Insert {
+                values,
+                on_conflict,
+            };
+            self.add_operator(BindingsOp::Dml(logical::Dml {
+                target,
+                op,
+                returning,
+            }))
+        } else {
+            let source = dml.from_clause.as_ref().map(|from| &from.node.source);
+            let Some(ast::FromSource::FromLet(from_let)) = source else {
+                self.errors.push(AstTransformError::IllegalState(
+                    "missing UPDATE or DELETE target".to_string(),
+                ));
+                return Traverse::Stop;
+            };
+            let Some(name) = dml_target_name(&from_let.node.expr) else {
+                self.errors.push(AstTransformError::InvalidDmlTarget(
+                    "UPDATE and DELETE must target a collection name".to_string(),
+                ));
+                return Traverse::Stop;
+            };
+            // The target is lowered as a scan; replace the scan with the statement
+            let Some(scan_id) = clauses.from_clause else {
+                self.errors.push(AstTransformError::IllegalState(
+                    "missing dml from clause".to_string(),
+                ));
+                return Traverse::Stop;
+            };
+            let Some(BindingsOp::Scan(scan)) = self.curr_plan().operator_as_mut(scan_id) else {
+                self.errors.push(AstTransformError::IllegalState(
+                    "dml from clause is not a scan".to_string(),
+                ));
+                return Traverse::Stop;
+            };
+            let as_key = std::mem::take(&mut scan.as_key);
+
+            let op = if matches!(dml.ops.as_slice(), [DmlOp::Delete(_)]) {
+                logical::DmlOp::Delete { filter }
+            } else {
+                let mut update_changes = Vec::with_capacity(changes.len());
+                for (path, value) in changes {
+                    let Some(path) = assignment_path(&path, &as_key) else {
+                        self.errors
+                            .push(AstTransformError::InvalidDmlTarget(format!(
+                                "SET and REMOVE must target a path from `{as_key}`"
+                            )));
+                        return Traverse::Stop;
+                    };
+                    update_changes.push(match value {
+                        Some(value) => logical::UpdateChange::Set(path, value),
+                        None => logical::UpdateChange::Remove(path),
+                    });
+                }
+                logical::DmlOp::Update {
+                    filter,
+                    changes: update_changes,
+                }
+            };
+            let target = logical::DmlTarget { name, as_key };
+            let dml = BindingsOp::Dml(logical::Dml {
+                target,
+                op,
+                returning,
+            });
+            if let Some(op) = self.curr_plan().operator_as_mut(scan_id) {
+                *op = dml;
+            }
+            scan_id
+        };
+        self.push_bexpr(op);
+        Traverse::Continue
     }
 
     fn enter_insert(&mut self, _insert: &'ast Insert) -> Traverse {
-        not_yet_implemented_fault!(self, "Insert".to_string());
+        self.enter_plan();
+        self.enter_benv();
+        self.enter_env();
+        Traverse::Continue
+    }
+
+    fn exit_insert(&mut self, insert: &'ast Insert) -> Traverse {
+        let subplan = self.exit_plan();
+        let benv = self.exit_benv();
+        let mut env = self.exit_env();
+        let expected = 2 + usize::from(insert.on_conflict.is_some());
+        eq_or_fault!(
+            self,
+            env.len() + benv.len(),
+            expected,
+            "env.len()+benv.len() != expected"
+        );
+
+        let on_conflict = insert.on_conflict.as_ref().map(|_| env.pop().unwrap().1);
+        let values = if !benv.is_empty() {
+            // Subquery, e.g. `INSERT INTO t SELECT ...`
+            ValueExpr::SubQueryExpr(logical::SubQueryExpr { plan: subplan })
+        } else {
+            self.curr_plan().merge_plan(subplan);
+            env.pop().unwrap().1
+        };
+        self.lower_insert(&insert.target, values, on_conflict)
     }
 
     fn enter_insert_value(&mut self, _insert_value: &'ast InsertValue) -> Traverse {
-        not_yet_implemented_fault!(self, "InsertValue".to_string());
+        self.enter_env();
+        Traverse::Continue
     }
 
-    fn enter_set(&mut self, _set: &'ast Set) -> Traverse {
-        not_yet_implemented_fault!(self, "Set".to_string());
+    fn exit_insert_value(&mut self, insert_value: &'ast InsertValue) -> Traverse {
+        if insert_value.index.is_some() {
+            not_yet_implemented_fault!(self, "INSERT ... VALUE ... AT".to_string());
+        }
+        let mut env = self.exit_env().into_iter().map(|(_, expr)| expr).skip(1);
+        let Some(value) = env.next() else {
+            self.errors.push(AstTransformError::IllegalState(
+                "missing INSERT value".to_string(),
+            ));
+            return Traverse::Stop;
+        };
+        let values = ValueExpr::BagExpr(BagExpr {
+            elements: vec![value],
+        });
+        let on_conflict = env.next();
+        self.lower_insert(&insert_value.target, values, on_conflict)
     }
 
     fn enter_assignment(&mut self, _assignment: &'ast Assignment) -> Traverse {
-        not_yet_implemented_fault!(self, "Assignment".to_string());
+        self.enter_env();
+        Traverse::Continue
+    }
+
+    fn exit_assignment(&mut self, _assignment: &'ast Assignment) -> Traverse {
+        let mut env = self.exit_env();
+        eq_or_fault!(self, env.len(), 2, "env.len() != 2");
+        let (_, value) = env.pop().unwrap();
+        let (_, target) = env.pop().unwrap();
+        if let Some(dml) = self.dml.as_mut() {
+            dml.changes.push((target, Some(value)));
+        }
+        Traverse::Continue
     }
 
     fn enter_remove(&mut self, _remove: &'ast Remove) -> Traverse {
-        not_yet_implemented_fault!(self, "Remove".to_string());
+        self.enter_env();
+        Traverse::Continue
     }
 
-    fn enter_delete(&mut self, _delete: &'ast Delete) -> Traverse {
-        not_yet_implemented_fault!(self, "Delete".to_string());
+    fn exit_remove(&mut self, _remove: &'ast Remove) -> Traverse {
+        let mut env = self.exit_env();
+        eq_or_fault!(self, env.len(), 1, "env.len() != 1");
+        let (_, target) = env.pop().unwrap();
+        if let Some(dml) = self.dml.as_mut() {
+            dml.changes.push((target, None));
+        }
+        Traverse::Continue
     }
 
-    fn enter_on_conflict(&mut self, _on_conflict: &'ast OnConflict) -> Traverse {
-        not_yet_implemented_fault!(self, "OnConflict".to_string());
+    fn enter_returning_expr(&mut self, _returning_expr: &'ast ReturningExpr) -> Traverse {
+        self.enter_env();
+        Traverse::Continue
+    }
+
+    fn exit_returning_expr(&mut self, returning_expr: &'ast ReturningExpr) -> Traverse {
+        let mut env = self.exit_env().into_iter().map(|(_, expr)| expr);
+        let mut returning = Vec::with_capacity(returning_expr.elems.len());
+        for elem in &returning_expr.elems {
+            let mapping = match elem.mapping {
+                ast::ReturningMapping::ModifiedNew => logical::ReturningMapping::ModifiedNew,
+                ast::ReturningMapping::ModifiedOld => logical::ReturningMapping::ModifiedOld,
+                ast::ReturningMapping::AllNew => logical::ReturningMapping::AllNew,
+                ast::ReturningMapping::AllOld => logical::ReturningMapping::AllOld,
+            };
+            let column = match elem.column {
+                ColumnComponent::ReturningWildcard => logical::ReturningColumn::Wildcard,
+                ColumnComponent::ReturningColumn(_) => {
+                    let Some(expr) = env.next() else {
+                        self.errors.push(AstTransformError::IllegalState(
+                            "missing RETURNING expression".to_string(),
+                        ));
+                        return Traverse::Stop;
+                    };
+                    let name = self.infer_id(&expr, &None).value;
+                    logical::ReturningColumn::Expr(name, expr)
+                }
+            };
+            returning.push(logical::ReturningElem { mapping, column });
+        }
+        if let Some(dml) = self.dml.as_mut() {
+            dml.returning = returning;
+        }
+        Traverse::Continue
     }
 
     fn enter_top_level_query(&mut self, _query: &'ast ast::TopLevelQuery) -> Traverse {
@@ -726,12 +1163,187 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
         let mut benv = self.exit_benv();
         eq_or_fault!(self, benv.len(), 1, "Expect benv.len() == 1");
         let out = benv.pop().unwrap();
-        let sink_id = self.curr_plan().add_operator(BindingsOp::Sink);
+        let sink_id = self.add_operator(BindingsOp::Sink);
         self.curr_plan().add_flow(out, sink_id);
+
+        if let Some(WithClauses { ctes, .. }) = self.take_with_clause() {
+            // the query lowered so far is the body of the `WITH`; nest it within the `With` operator
+            let body = std::mem::take(self.curr_plan());
+            let with = BindingsOp::With(logical::With { ctes, body });
+            let with_id = self.add_operator(with);
+            let sink_id = self.add_operator(BindingsOp::Sink);
+            self.curr_plan().add_flow(with_id, sink_id);
+        }
+        Traverse::Continue
+    }
+
+    fn enter_with_clause(&mut self, with_clause: &'ast WithClause) -> Traverse {
+        // the clause belongs to the (top-level) query enclosing it
+        let Some(query) = self.id_stack.iter().rev().nth(1).copied() else {
+            self.errors.push(AstTransformError::IllegalState(
+                "WITH clause outside of query".to_string(),
+            ));
+            return Traverse::Stop;
+        };
+        self.with_clauses.push((
+            query,
+            WithClauses {
+                recursive: with_clause.recursive,
+                ctes: vec![],
+            },
+        ));
+        Traverse::Continue
+    }
+
+    fn enter_with_element(&mut self, _with_element: &'ast WithElement) -> Traverse {
+        self.enter_plan();
+        self.enter_benv();
+        Traverse::Continue
+    }
+
+    fn exit_with_element(&mut self, with_element: &'ast WithElement) -> Traverse {
+        let mut benv = self.exit_benv();
+        let plan = self.exit_plan();
+        eq_or_fault!(self, benv.len(), 1, "with element benv.len() != 1");
+        let out = benv.pop().unwrap();
+
+        let WithElement {
+            query_name,
+            columns,
+            subquery,
+            search,
+            cycle,
+        } = with_element;
+        let name = query_name.value.clone();
+        let Some((_, with_clause)) = self.with_clauses.last() else {
+            self.errors.push(AstTransformError::IllegalState(
+                "WITH element outside of WITH clause".to_string(),
+            ));
+            return Traverse::Stop;
+        };
+        if with_clause
+            .ctes
+            .iter()
+            .any(|cte| unicase::eq(cte.name.as_str(), name.as_str()))
+        {
+            self.errors
+                .push(AstTransformError::DuplicateWithElement(name));
+            return Traverse::Stop;
+        }
+
+        let recursive = with_clause.recursive
+            && refers_to(
+                subquery,
+                std::slice::from_ref(query_name),
+                &self.key_registry,
+            );
+        let (plan, recursive) = if recursive {
+            // A recursive element is `<anchor> UNION [ALL] <recursive>`; split the two branches
+            // of the union into separate plans.
+            let setq = match plan.operator(out) {
+                Some(BindingsOp::BagOp(BagOp {
+                    bag_op: logical::BagOperator::Union | logical::BagOperator::OuterUnion,
+                    setq,
+                })) => setq.clone(),
+                _ => {
+                    self.errors
+                        .push(AstTransformError::InvalidRecursiveWithElement(name));
+                    return Traverse::Stop;
+                }
+            };
+            let branch = |num: u8| {
+                plan.flows()
+                    .iter()
+                    .find(|(_, dst, branch)| *dst == out && *branch == num)
+                    .map(|(src, _, _)| *src)
+            };
+            let (Some(anchor), Some(step)) = (branch(0), branch(1)) else {
+                self.errors.push(AstTransformError::IllegalState(
+                    "recursive union without two branches".to_string(),
+                ));
+                return Traverse::Stop;
+            };
+
+            let search = search.as_ref().map(|search| logical::CteSearch {
+                order: match search.order {
+                    ast::SearchOrder::DepthFirst => logical::SearchOrder::DepthFirst,
+                    ast::SearchOrder::BreadthFirst => logical::SearchOrder::BreadthFirst,
+                },
+                by: search.by.iter().map(|sym| sym.value.clone()).collect(),
+                set: search.set.value.clone(),
+            });
+            let cycle = match cycle {
+                None => None,
+                Some(cycle) => {
+                    let (mark_value, default_value) = match &cycle.mark_values {
+                        None => (logical::Lit::Bool(true), logical::Lit::Bool(false)),
+                        Some((mark, default)) => match (lit_to_lit(mark), lit_to_lit(default)) {
+                            (Ok(mark), Ok(default)) => (mark, default),
+                            (Err(e), _) | (_, Err(e)) => {
+                                self.errors.push(e);
+                                return Traverse::Stop;
+                            }
+                        },
+                    };
+                    Some(logical::CteCycle {
+                        columns: cycle.columns.iter().map(|sym| sym.value.clone()).collect(),
+                        mark: cycle.mark.value.clone(),
+                        mark_value,
+                        default_value,
+                        path: cycle.path.value.clone(),
+                    })
+                }
+            };
+
+            let mut step_plan = upstream_plan(&plan, step);
+            let parent = if search.is_some() || cycle.is_some() {
+                // The `SEARCH` and `CYCLE` columns of a row are derived from the row it derives
+                // from, which the recursive part must therefore carry along.
+                let Some(parent) = carry_parent(&mut step_plan, &name) else {
+                    self.errors
+                        .push(AstTransformError::InvalidRecursiveWithElement(name));
+                    return Traverse::Stop;
+                };
+                Some(parent)
+            } else {
+                None
+            };
+            let step = logical::RecursiveStep {
+                plan: step_plan,
+                setq,
+                search,
+                cycle,
+                parent,
+            };
+            (upstream_plan(&plan, anchor), Some(step))
+        } else {
+            if search.is_some() || cycle.is_some() {
+                self.errors
+                    .push(AstTransformError::InvalidRecursiveWithElement(name));
+                return Traverse::Stop;
+            }
+            (upstream_plan(&plan, out), None)
+        };
+
+        let cte = logical::CommonTableExpr {
+            name,
+            columns: columns
+                .as_ref()
+                .map(|cols| cols.iter().map(|sym| sym.value.clone()).collect()),
+            plan,
+            recursive,
+        };
+        if let Some((_, with_clause)) = self.with_clauses.last_mut() {
+            with_clause.ctes.push(cte);
+        }
         Traverse::Continue
     }
 
     fn enter_query(&mut self, query: &'ast Query) -> Traverse {
+        if query.with.is_some() {
+            // the query is the body of its `WITH`, lowered into a plan of its own
+            self.enter_plan();
+        }
         self.enter_benv();
         if let QuerySet::Select(_) = query.set.node {
             self.enter_q();
@@ -741,17 +1353,26 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
 
     fn exit_query(&mut self, query: &'ast Query) -> Traverse {
         let benv = self.exit_benv();
-        match query.set.node {
+        let out = match query.set.node {
             QuerySet::Select(_) => {
+                // Window functions are computed after grouping, but before ordering and projection
+                let exprs = std::mem::take(self.window_exprs.last_mut().unwrap());
+                if !exprs.is_empty() {
+                    let window = BindingsOp::Window(logical::Window { exprs });
+                    let id = self.add_operator(window);
+                    self.current_clauses_mut().window_clause.replace(id);
+                }
+
                 let clauses = self.exit_q();
                 let mut clauses = clauses.evaluation_order().into_iter();
-                if let Some(mut src_id) = clauses.next() {
-                    for dst_id in clauses {
-                        self.curr_plan().add_flow(src_id, dst_id);
-                        src_id = dst_id;
-                    }
-                    self.push_bexpr(src_id);
+                let Some(mut src_id) = clauses.next() else {
+                    return Traverse::Continue;
+                };
+                for dst_id in clauses {
+                    self.curr_plan().add_flow(src_id, dst_id);
+                    src_id = dst_id;
                 }
+                src_id
             }
             _ => {
                 true_or_fault!(
@@ -764,9 +1385,19 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                     self.curr_plan().add_flow(out, op);
                     out = op;
                 });
-                self.push_bexpr(out);
+                out
             }
-        }
+        };
+
+        let out = match self.take_with_clause() {
+            Some(WithClauses { ctes, .. }) => {
+                // nest the plan of the query within the `With` operator of its `WITH` clause
+                let body = upstream_plan(&self.exit_plan(), out);
+                self.add_operator(BindingsOp::With(logical::With { ctes, body }))
+            }
+            None => out,
+        };
+        self.push_bexpr(out);
         Traverse::Continue
     }
 
@@ -778,12 +1409,9 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
             QuerySet::BagOp(_) => {}
             QuerySet::Select(_) => {}
             QuerySet::Expr(_) => {}
-            QuerySet::Values(_) => {
-                not_yet_implemented_fault!(self, "QuerySet::Values".to_string());
-            }
-            QuerySet::Table(_) => {
-                not_yet_implemented_fault!(self, "QuerySet::Table".to_string());
-            }
+            QuerySet::Dml(_) => {}
+            QuerySet::Values(_) => {}
+            QuerySet::Table(_) => {}
         }
         Traverse::Continue
     }
@@ -812,7 +1440,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                     None => logical::SetQuantifier::Distinct,
                 };
 
-                let id = self.curr_plan().add_operator(BindingsOp::BagOp(BagOp {
+                let id = self.add_operator(BindingsOp::BagOp(BagOp {
                     bag_op: bag_operator,
                     setq,
                 }));
@@ -825,14 +1453,41 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                 eq_or_fault!(self, env.len(), 1, "env.len() != 1");
                 let (_, expr) = env.into_iter().next().unwrap();
                 let op = BindingsOp::ExprQuery(logical::ExprQuery { expr });
-                let id = self.curr_plan().add_operator(op);
+                let id = self.add_operator(op);
                 self.push_bexpr(id);
             }
-            QuerySet::Values(_) => {
-                not_yet_implemented_fault!(self, "QuerySet::Values".to_string());
+            QuerySet::Dml(_) => {
+                eq_or_fault!(self, benv.len(), 1, "qs benv.len() != 1");
+                self.push_bexpr(benv.pop().unwrap());
             }
-            QuerySet::Table(_) => {
-                not_yet_implemented_fault!(self, "QuerySet::Table".to_string());
+            QuerySet::Values(rows) => {
+                // `VALUES (1, 'a'), (2, 'b')` is the bag of its rows, `<< [1, 'a'], [2, 'b'] >>`
+                eq_or_fault!(self, env.len(), rows.len(), "env.len() != rows.len()");
+                let elements = env.into_iter().map(|(_, row)| row).collect();
+                let expr = ValueExpr::BagExpr(BagExpr { elements });
+                let op = BindingsOp::ExprQuery(logical::ExprQuery { expr });
+                let id = self.add_operator(op);
+                self.push_bexpr(id);
+            }
+            QuerySet::Table(QueryTable { table_name }) => {
+                // `TABLE t` is `SELECT VALUE t FROM t AS t`
+                let as_key = table_name.value.clone();
+                let scan = BindingsOp::Scan(logical::Scan {
+                    expr: ValueExpr::VarRef(symprim_to_binding(table_name), VarRefType::Global),
+                    as_key: as_key.clone(),
+                    at_key: None,
+                    columns: None,
+                });
+                let project = BindingsOp::ProjectValue(logical::ProjectValue {
+                    expr: ValueExpr::VarRef(
+                        BindingsName::CaseSensitive(Cow::Owned(as_key)),
+                        VarRefType::Local,
+                    ),
+                });
+                let scan = self.add_operator(scan);
+                let project = self.add_operator(project);
+                self.curr_plan().add_flow(scan, project);
+                self.push_bexpr(project);
             }
         }
         Traverse::Continue
@@ -847,7 +1502,63 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
     }
 
     fn enter_exclusion(&mut self, _exclusion: &'ast Exclusion) -> Traverse {
-        not_yet_implemented_fault!(self, "EXCLUDE");
+        // The roots of exclude paths name binding tuple attributes; lower them as local lookups
+        self.enter_env();
+        self.ctx_stack.push(QueryContext::Path);
+        Traverse::Continue
+    }
+
+    fn exit_exclusion(&mut self, exclusion: &'ast Exclusion) -> Traverse {
+        self.ctx_stack.pop();
+        let env = self.exit_env();
+        eq_or_fault!(
+            self,
+            env.len(),
+            exclusion.items.len(),
+            "env.len() != items.len()"
+        );
+
+        let mut paths = Vec::with_capacity(exclusion.items.len());
+        for item in &exclusion.items {
+            let ExcludePath { root, steps } = &item.node;
+            let root = symprim_to_binding(&root.node.name);
+            let mut lowered = Vec::with_capacity(steps.len());
+            for step in steps {
+                let step = match step {
+                    ExcludePathStep::PathProject(sym) => {
+                        logical::ExcludeStep::Key(symprim_to_binding(&sym.node))
+                    }
+                    ExcludePathStep::PathIndex(lit) => match &lit.node {
+                        Lit::Int8Lit(idx) => logical::ExcludeStep::Index((*idx).into()),
+                        Lit::Int16Lit(idx) => logical::ExcludeStep::Index((*idx).into()),
+                        Lit::Int32Lit(idx) => logical::ExcludeStep::Index((*idx).into()),
+                        Lit::Int64Lit(idx) => logical::ExcludeStep::Index(*idx),
+                        Lit::CharStringLit(key) | Lit::NationalCharStringLit(key) => {
+                            logical::ExcludeStep::Key(BindingsName::CaseSensitive(Cow::Owned(
+                                key.clone(),
+                            )))
+                        }
+                        _ => {
+                            not_yet_implemented_fault!(
+                                self,
+                                "EXCLUDE path index that is not an integer or string".to_string()
+                            );
+                        }
+                    },
+                    ExcludePathStep::PathForEach => logical::ExcludeStep::AllElements,
+                    ExcludePathStep::PathUnpivot => logical::ExcludeStep::AllFields,
+                };
+                lowered.push(step);
+            }
+            paths.push(logical::ExcludePath {
+                root,
+                steps: lowered,
+            });
+        }
+
+        let id = self.add_operator(BindingsOp::Exclude(logical::Exclude { paths }));
+        self.current_clauses_mut().exclude_clause.replace(id);
+        Traverse::Continue
     }
 
     fn enter_select(&mut self, select: &'ast Select) -> Traverse {
@@ -875,7 +1586,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                 aggregate_exprs: self.aggregate_exprs.last().unwrap().clone(),
                 group_as_alias: None,
             });
-            let id = self.curr_plan().add_operator(group_by);
+            let id = self.add_operator(group_by);
             self.current_clauses_mut().group_by_clause.replace(id);
         }
         Traverse::Continue
@@ -895,7 +1606,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
         eq_or_fault!(self, env.len(), 0, "env.len() != 0");
 
         if let Some(SetQuantifier::Distinct) = projection.setq {
-            let id = self.curr_plan().add_operator(BindingsOp::Distinct);
+            let id = self.add_operator(BindingsOp::Distinct);
             self.current_clauses_mut().distinct.replace(id);
         }
         Traverse::Continue
@@ -970,7 +1681,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                 logical::BindingsOp::ProjectValue(logical::ProjectValue { expr })
             }
         };
-        let id = self.curr_plan().add_operator(select);
+        let id = self.add_operator(select);
         self.current_clauses_mut().select_clause.replace(id);
         Traverse::Continue
     }
@@ -995,13 +1706,27 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
         Traverse::Continue
     }
 
-    fn exit_bin_op(&mut self, _bin_op: &'ast BinOp) -> Traverse {
+    fn exit_bin_op(&mut self, bin_op: &'ast BinOp) -> Traverse {
         let mut env = self.exit_env();
+
+        // A type on the right of `IS` (e.g., `INT` in `a IS INT`) is not visited
+        if let Expr::Type(ty) = bin_op.rhs.as_ref() {
+            eq_or_fault!(self, env.len(), 1, "env.len() != 1");
+            let (_, lhs) = env.pop().unwrap();
+            let is_type = self.lower_type(&ty.node);
+            self.push_vexpr(ValueExpr::IsTypeExpr(IsTypeExpr {
+                not: false,
+                expr: Box::new(lhs),
+                is_type,
+            }));
+            return Traverse::Continue;
+        }
+
         eq_or_fault!(self, env.len(), 2, "env.len() != 2");
 
         let (_, rhs) = env.pop().unwrap();
         let (_, lhs) = env.pop().unwrap();
-        if _bin_op.kind == BinOpKind::Is {
+        if bin_op.kind == BinOpKind::Is {
             let is_type = match rhs {
                 ValueExpr::Lit(lit) => match lit.as_ref() {
                     logical::Lit::Null => logical::Type::NullType,
@@ -1023,7 +1748,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                 is_type,
             }));
         } else {
-            let op = match _bin_op.kind {
+            let op = match bin_op.kind {
                 BinOpKind::Add => logical::BinaryOp::Add,
                 BinOpKind::Div => logical::BinaryOp::Div,
                 BinOpKind::Exp => logical::BinaryOp::Exp,
@@ -1155,6 +1880,21 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
         let args = self.exit_call();
         let name = call.func_name.value.to_lowercase();
 
+        let cast_kind = match name.as_str() {
+            "cast" => Some(logical::CastKind::Cast),
+            "can_cast" => Some(logical::CastKind::CanCast),
+            "can_lossless_cast" => Some(logical::CastKind::CanLosslessCast),
+            _ => None,
+        };
+        if let Some(kind) = cast_kind {
+            return self.lower_cast(name, kind, args);
+        }
+
+        // Aggregation functions registered in the catalog are parsed as ordinary calls
+        if self.fnsym_tab.lookup(&name).is_none() && self.catalog_aggregate(&name).is_some() {
+            return self.lower_aggregate(name, args);
+        }
+
         let call_def_to_vexpr = |call_def: &CallDef| call_def.lookup(&args, &name);
 
         let call_expr = self
@@ -1202,11 +1942,14 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                 let name = name.value.to_lowercase();
                 self.push_call_arg(CallArgument::Named(name, env.pop().unwrap().1));
             }
-            CallArg::PositionalType(_) => {
-                not_yet_implemented_fault!(self, "PositionalType call argument".to_string());
+            CallArg::PositionalType(ty) => {
+                let ty = self.lower_type(ty);
+                self.push_call_arg(CallArgument::PositionalType(ty));
             }
-            CallArg::NamedType(_) => {
-                not_yet_implemented_fault!(self, "NamedType call argument".to_string());
+            CallArg::NamedType(CallArgNamedType { name, ty }) => {
+                let ty = self.lower_type(ty);
+                let name = name.value.to_lowercase();
+                self.push_call_arg(CallArgument::NamedType(name, ty));
             }
         }
         Traverse::Continue
@@ -1227,6 +1970,15 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
         Traverse::Continue
     }
 
+    fn enter_parameter(&mut self, parameter: &'ast ast::Parameter) -> Traverse {
+        let parameter = match parameter {
+            ast::Parameter::Positional(n) => logical::Parameter::Positional(*n),
+            ast::Parameter::Named(name) => logical::Parameter::Named(name.value.clone()),
+        };
+        self.push_vexpr(ValueExpr::Parameter(parameter));
+        Traverse::Continue
+    }
+
     fn enter_struct(&mut self, _struct: &'ast Struct) -> Traverse {
         self.enter_env();
         Traverse::Continue
@@ -1281,95 +2033,204 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
     fn exit_call_agg(&mut self, call_agg: &'ast CallAgg) -> Traverse {
         // Relates to the SQL aggregation functions (e.g. AVG, COUNT, SUM) -- not the `COLL_`
         // functions
-        let mut env = self.exit_call();
+        let env = self.exit_call();
         let name = call_agg.func_name.value.to_lowercase();
+        self.lower_aggregate(name, env)
+    }
 
-        // Rewrites the SQL aggregation function call to be a variable reference that the `GROUP BY`
-        // clause will add to the binding tuples.
-        // E.g. SELECT a, SUM(b) FROM t GROUP BY a
-        //      SELECT a AS a, $__agg_1 AS b FROM t GROUP BY a
-        let new_name = "$__agg".to_owned() + &self.agg_id.id();
-        let new_binding_name = BindingsName::CaseSensitive(Cow::Owned(new_name.clone()));
-        let new_expr = ValueExpr::VarRef(new_binding_name, VarRefType::Local);
-        self.push_vexpr(new_expr);
+    fn enter_window(&mut self, window: &'ast Window) -> Traverse {
+        // window functions are evaluated after the filtering clauses of their query
+        if let Some(clause) = self.q_stack.last().and_then(|clauses| clauses.filter) {
+            self.errors
+                .push(AstTransformError::WindowFunctionNotAllowed(
+                    window.func_name.value.clone(),
+                    clause.to_string(),
+                ));
+            return Traverse::Stop;
+        }
+        self.enter_call();
+        Traverse::Continue
+    }
 
-        true_or_fault!(self, !env.is_empty(), "env is empty");
-        // Default set quantifier if the set quantifier keyword is omitted will be `ALL`
-        let (setq, arg) = match env.pop().unwrap() {
-            CallArgument::Positional(ve) => (logical::SetQuantifier::All, ve),
-            CallArgument::Named(name, ve) => match name.as_ref() {
-                "all" => (logical::SetQuantifier::All, ve),
-                "distinct" => (logical::SetQuantifier::Distinct, ve),
-                _ => {
-                    self.errors.push(AstTransformError::IllegalState(
-                        "Invalid set quantifier".to_string(),
-                    ));
-                    return Traverse::Stop;
+    fn exit_window(&mut self, window: &'ast Window) -> Traverse {
+        let mut args = self.exit_call();
+        let name = window.func_name.value.to_lowercase();
+        let Some((partition_by, order_by)) = self.window_specs.pop() else {
+            self.errors.push(AstTransformError::IllegalState(
+                "window specification".to_string(),
+            ));
+            return Traverse::Stop;
+        };
+
+        // Rewrites the window function call to be a variable reference that the `Window` operator
+        // will add to the binding tuples.
+        // E.g. SELECT a, RANK() OVER (ORDER BY b) FROM t
+        //      SELECT a AS a, $__win_1 AS _2 FROM t
+        let new_name = "$__win".to_owned() + &self.win_id.id();
+        let new_binding_name = BindingsName::CaseSensitive(Cow::Owned(new_name.clone()));
+        let new_expr = ValueExpr::VarRef(new_binding_name, VarRefType::Local);
+        self.push_vexpr(new_expr);
+
+        let (func, arity) = match name.as_str() {
+            "row_number" => (logical::WindowFunc::RowNumber, 0..=0),
+            "rank" => (logical::WindowFunc::Rank, 0..=0),
+            "dense_rank" => (logical::WindowFunc::DenseRank, 0..=0),
+            "lag" => (logical::WindowFunc::Lag, 1..=3),
+            "lead" => (logical::WindowFunc::Lead, 1..=3),
+            "first_value" => (logical::WindowFunc::FirstValue, 1..=1),
+            "last_value" => (logical::WindowFunc::LastValue, 1..=1),
+            "avg" => (logical::WindowFunc::Agg(AggAvg), 1..=1),
+            "count" => (logical::WindowFunc::Agg(AggCount), 1..=1),
+            "max" => (logical::WindowFunc::Agg(AggMax), 1..=1),
+            "min" => (logical::WindowFunc::Agg(AggMin), 1..=1),
+            "sum" => (logical::WindowFunc::Agg(AggSum), 1..=1),
+            "any" | "some" => (logical::WindowFunc::Agg(AggAny), 1..=1),
+            "every" => (logical::WindowFunc::Agg(AggEvery), 1..=1),
+            _ => match self.catalog_aggregate(&name) {
+                Some((oid, arity)) => (logical::WindowFunc::Agg(AggById(name.clone(), oid)), arity),
+                None => {
+                    self.errors
+                        .push(AstTransformError::UnsupportedFunction(name));
+                    return Traverse::Continue;
                 }
             },
-            CallArgument::Star => (
-                logical::SetQuantifier::All,
-                ValueExpr::Lit(Box::new(logical::Lit::Int8(1))),
-            ),
         };
+        if !arity.contains(&args.len()) {
+            self.errors
+                .push(AstTransformError::InvalidNumberOfArguments(name));
+            return Traverse::Continue;
+        }
 
-        let agg_expr = match name.as_str() {
-            "avg" => AggregateExpression {
-                name: new_name,
-                expr: arg,
-                func: AggAvg,
-                setq,
-            },
-            "count" => AggregateExpression {
-                name: new_name,
-                expr: arg,
-                func: AggCount,
-                setq,
-            },
-            "max" => AggregateExpression {
-                name: new_name,
-                expr: arg,
-                func: AggMax,
-                setq,
-            },
-            "min" => AggregateExpression {
-                name: new_name,
-                expr: arg,
-                func: AggMin,
-                setq,
-            },
-            "sum" => AggregateExpression {
-                name: new_name,
-                expr: arg,
-                func: AggSum,
-                setq,
-            },
-            "any" | "some" => AggregateExpression {
-                name: new_name,
-                expr: arg,
-                func: AggAny,
-                setq,
-            },
-            "every" => AggregateExpression {
-                name: new_name,
-                expr: arg,
-                func: AggEvery,
-                setq,
-            },
-            _ => {
-                // Include as an error but allow lowering to proceed for multiple error reporting
-                self.errors
-                    .push(AstTransformError::UnsupportedFunction(name));
-                // continue lowering with `AggAvg` aggregation function
-                AggregateExpression {
-                    name: new_name,
-                    expr: arg,
-                    func: AggAvg,
-                    setq,
+        // Only aggregation functions accept a set quantifier or `*`
+        let mut setq = logical::SetQuantifier::All;
+        if let logical::WindowFunc::Agg(_) = func {
+            // the aggregated value is the first argument
+            let arg = match args.remove(0) {
+                CallArgument::Named(quantifier, ve) if quantifier == "distinct" => {
+                    setq = logical::SetQuantifier::Distinct;
+                    CallArgument::Positional(ve)
+                }
+                CallArgument::Named(quantifier, ve) if quantifier == "all" => {
+                    CallArgument::Positional(ve)
+                }
+                CallArgument::Star => {
+                    CallArgument::Positional(ValueExpr::Lit(Box::new(logical::Lit::Int8(1))))
+                }
+                arg => arg,
+            };
+            args.insert(0, arg);
+        }
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                CallArgument::Positional(ve) => Ok(ve),
+                _ => Err(AstTransformError::UnsupportedFunction(name.clone())),
+            })
+            .collect::<Result<Vec<_>, _>>();
+        let args = match args {
+            Ok(args) => args,
+            Err(err) => {
+                self.errors.push(err);
+                return Traverse::Continue;
+            }
+        };
+
+        let frame = match &window.spec.node.frame {
+            Some(frame) => {
+                let bound = |bound: &ast::WindowFrameBound| match bound {
+                    ast::WindowFrameBound::UnboundedPreceding => {
+                        logical::WindowFrameBound::UnboundedPreceding
+                    }
+                    ast::WindowFrameBound::Preceding(n) => logical::WindowFrameBound::Preceding(*n),
+                    ast::WindowFrameBound::CurrentRow => logical::WindowFrameBound::CurrentRow,
+                    ast::WindowFrameBound::Following(n) => logical::WindowFrameBound::Following(*n),
+                    ast::WindowFrameBound::UnboundedFollowing => {
+                        logical::WindowFrameBound::UnboundedFollowing
+                    }
+                };
+                let units = match frame.units {
+                    ast::WindowFrameUnits::Rows => logical::WindowFrameUnits::Rows,
+                    ast::WindowFrameUnits::Range => logical::WindowFrameUnits::Range,
+                };
+                logical::WindowFrame {
+                    units,
+                    start: bound(&frame.start),
+                    end: bound(&frame.end),
                 }
             }
+            // Per SQL, an ordered window defaults to the rows up to and including the current row's
+            // peers; an unordered window defaults to the whole partition.
+            None if order_by.is_empty() => logical::WindowFrame {
+                units: logical::WindowFrameUnits::Range,
+                start: logical::WindowFrameBound::UnboundedPreceding,
+                end: logical::WindowFrameBound::UnboundedFollowing,
+            },
+            None => logical::WindowFrame {
+                units: logical::WindowFrameUnits::Range,
+                start: logical::WindowFrameBound::UnboundedPreceding,
+                end: logical::WindowFrameBound::CurrentRow,
+            },
         };
-        self.aggregate_exprs.last_mut().unwrap().push(agg_expr);
+
+        // The position of a bound relative to the current row, so that a frame whose start
+        // comes after its end can be rejected rather than always being empty.
+        let position = |bound: &logical::WindowFrameBound| match bound {
+            logical::WindowFrameBound::UnboundedPreceding => i128::MIN,
+            logical::WindowFrameBound::Preceding(n) => -i128::from(*n),
+            logical::WindowFrameBound::CurrentRow => 0,
+            logical::WindowFrameBound::Following(n) => i128::from(*n),
+            logical::WindowFrameBound::UnboundedFollowing => i128::MAX,
+        };
+        if matches!(frame.start, logical::WindowFrameBound::UnboundedFollowing)
+            || matches!(frame.end, logical::WindowFrameBound::UnboundedPreceding)
+            || position(&frame.start) > position(&frame.end)
+        {
+            self.errors
+                .push(AstTransformError::InvalidWindowSpecification(format!(
+                    "invalid frame bounds for `{name}`"
+                )));
+            return Traverse::Continue;
+        }
+        let has_offset = |bound: &logical::WindowFrameBound| {
+            matches!(
+                bound,
+                logical::WindowFrameBound::Preceding(_) | logical::WindowFrameBound::Following(_)
+            )
+        };
+        if frame.units == logical::WindowFrameUnits::Range
+            && (has_offset(&frame.start) || has_offset(&frame.end))
+            && order_by.len() != 1
+        {
+            self.errors
+                .push(AstTransformError::InvalidWindowSpecification(format!(
+                    "RANGE frame with an offset for `{name}` requires exactly one ORDER BY key"
+                )));
+            return Traverse::Continue;
+        }
+
+        let window_expr = logical::WindowExpression {
+            name: new_name,
+            func,
+            args,
+            setq,
+            partition_by,
+            order_by,
+            frame,
+        };
+        self.window_exprs.last_mut().unwrap().push(window_expr);
+        Traverse::Continue
+    }
+
+    fn enter_window_spec(&mut self, _window_spec: &'ast WindowSpec) -> Traverse {
+        self.enter_env();
+        self.sort_stack.push(vec![]);
+        Traverse::Continue
+    }
+
+    fn exit_window_spec(&mut self, _window_spec: &'ast WindowSpec) -> Traverse {
+        let order_by = self.sort_stack.pop().expect("sort specs");
+        let partition_by = self.exit_env().into_iter().map(|(_, v)| v).collect();
+        self.window_specs.push((partition_by, order_by));
         Traverse::Continue
     }
 
@@ -1463,12 +2324,8 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                     }
                 }
             }
-            PathStep::PathForEach => {
-                not_yet_implemented_fault!(self, "PathStep::PathForEach".to_string());
-            }
-            PathStep::PathUnpivot => {
-                not_yet_implemented_fault!(self, "PathStep::PathUnpivot".to_string());
-            }
+            PathStep::PathForEach => logical::PathComponent::ForEach,
+            PathStep::PathUnpivot => logical::PathComponent::Unpivot,
         };
 
         self.push_path_step(step);
@@ -1532,12 +2389,16 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
             kind,
             as_alias,
             at_alias,
+            columns,
             ..
         } = from_let;
         let as_key = self.infer_id(&expr, as_alias).value;
         let at_key = at_alias
             .as_ref()
             .map(|SymbolPrimitive { value, case: _ }| value.clone());
+        let columns = columns
+            .as_ref()
+            .map(|cols| cols.iter().map(|sym| sym.value.clone()).collect());
 
         let (bexpr, project_all_mode) = match kind {
             FromLetKind::Scan => (
@@ -1545,6 +2406,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                     expr,
                     as_key,
                     at_key,
+                    columns,
                 }),
                 ProjectAllMode::Unwrap,
             ),
@@ -1561,25 +2423,49 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
                     expr,
                     as_key,
                     at_key,
+                    columns,
                 }),
                 ProjectAllMode::Unwrap,
             ),
         };
 
-        let id = self.curr_plan().add_operator(bexpr);
+        let id = self.add_operator(bexpr);
         self.push_bexpr(id);
 
         if let Some(select_id) = self.current_clauses_mut().select_clause {
-            if let Some(BindingsOp::ProjectAll(mode)) = self.curr_plan().operator_as_mut(select_id)
+            if let Some(BindingsOp::ProjectAll(project_all)) =
+                self.curr_plan().operator_as_mut(select_id)
             {
-                *mode = project_all_mode
+                project_all.mode = project_all_mode
             }
         }
 
         Traverse::Continue
     }
 
-    fn enter_join(&mut self, _join: &'ast Join) -> Traverse {
+    fn enter_join(&mut self, join: &'ast Join) -> Traverse {
+        // Only the right operand of `INNER`, `LEFT`, and `CROSS` joins may depend upon the bindings
+        // of the left operand; the operands of `RIGHT` and `FULL` joins are evaluated independently
+        let kind = match join.kind {
+            JoinKind::Right => Some("RIGHT"),
+            JoinKind::Full => Some("FULL"),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            let mut names = vec![];
+            from_source_names(&join.left, &self.key_registry, &mut names);
+            let reference = names.iter().find(|name| {
+                refers_to(&*join.right, std::slice::from_ref(name), &self.key_registry)
+            });
+            if let Some(name) = reference {
+                self.errors.push(AstTransformError::InvalidJoinReference(
+                    kind.to_string(),
+                    name.value.clone(),
+                ));
+                return Traverse::Stop;
+            }
+        }
+
         self.enter_benv();
         self.enter_env();
         Traverse::Continue
@@ -1596,7 +2482,9 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
             "env.len() is not between 0 and 1"
         );
 
-        let Join { kind, .. } = join;
+        let Join {
+            kind, predicate, ..
+        } = join;
 
         let kind = match kind {
             JoinKind::Inner => logical::JoinKind::Inner,
@@ -1606,19 +2494,67 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
             JoinKind::Cross => logical::JoinKind::Cross,
         };
 
-        let on = env.pop().map(|(_, v)| v);
+        let mut on = env.pop().map(|(_, v)| v);
 
         let rid = benv.pop().unwrap();
         let lid = benv.pop().unwrap();
         let left = Box::new(self.curr_plan().operator(lid).unwrap().clone());
         let right = Box::new(self.curr_plan().operator(rid).unwrap().clone());
+
+        // The columns of a `USING` or `NATURAL` join, as determined by the name resolver, are
+        // joined on the equality of their values in either side and bound to their coalesced value
+        let columns = predicate
+            .as_ref()
+            .and_then(|spec| self.key_registry.schema.get(&spec.id))
+            .map(|schema| schema.produce.iter())
+            .into_iter()
+            .flatten()
+            .filter_map(|column| match column {
+                name_resolver::Symbol::Known(sym) => Some(sym),
+                name_resolver::Symbol::Unknown(_) => None,
+            });
+        let mut using = vec![];
+        for column in columns {
+            let lhs = join_column_value(&left, column);
+            let rhs = join_column_value(&right, column);
+            let eq = ValueExpr::BinaryExpr(
+                logical::BinaryOp::Eq,
+                Box::new(lhs.clone()),
+                Box::new(rhs.clone()),
+            );
+            on = Some(match on {
+                None => eq,
+                Some(on) => {
+                    ValueExpr::BinaryExpr(logical::BinaryOp::And, Box::new(on), Box::new(eq))
+                }
+            });
+            let coalesced = ValueExpr::CoalesceExpr(logical::CoalesceExpr {
+                elements: vec![lhs, rhs],
+            });
+            using.push((column.value.clone(), coalesced));
+        }
+
+        // `SELECT *` projects each join column once, in place of the joined variables' attributes
+        if let Some(select_id) = self.current_clauses_mut().select_clause {
+            if let Some(BindingsOp::ProjectAll(project_all)) =
+                self.curr_plan().operator_as_mut(select_id)
+            {
+                for (column, _) in &using {
+                    if !project_all.join_columns.contains(column) {
+                        project_all.join_columns.push(column.clone());
+                    }
+                }
+            }
+        }
+
         let join = logical::BindingsOp::Join(logical::Join {
             kind,
             left,
             right,
             on,
+            using,
         });
-        let join = self.curr_plan().add_operator(join);
+        let join = self.add_operator(join);
         self.curr_plan().add_flow_with_branch_num(lid, join, 0);
         self.curr_plan().add_flow_with_branch_num(rid, join, 1);
         self.push_bexpr(join);
@@ -1630,47 +2566,79 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
             JoinSpec::On(_) => {
                 // visitor recurse into expr will put the condition in the current env
             }
-            JoinSpec::Using(_) => {
-                not_yet_implemented_fault!(self, "JoinSpec::Using".to_string());
-            }
-            JoinSpec::Natural => {
-                not_yet_implemented_fault!(self, "JoinSpec::Natural".to_string());
+            JoinSpec::Using(_) | JoinSpec::Natural => {
+                // the join's columns are resolved by the name resolver and lowered on exit
             }
         };
         Traverse::Continue
     }
 
+    fn enter_let(&mut self, _let: &'ast ast::Let) -> Traverse {
+        self.enter_benv();
+        self.enter_env();
+        Traverse::Continue
+    }
+
+    fn exit_let(&mut self, let_clause: &'ast ast::Let) -> Traverse {
+        let benv = self.exit_benv();
+        if !benv.is_empty() {
+            not_yet_implemented_fault!(self, "Subquery within LET".to_string());
+        }
+        let env = self.exit_env();
+        eq_or_fault!(
+            self,
+            env.len(),
+            let_clause.let_bindings.len(),
+            "env.len() != let_bindings.len()"
+        );
+
+        let bindings = let_clause
+            .let_bindings
+            .iter()
+            .zip(env)
+            .map(|(binding, (_, expr))| (binding.node.as_alias.value.clone(), expr))
+            .collect();
+        let id = self.add_operator(BindingsOp::Let(logical::Let { bindings }));
+
+        self.current_clauses_mut().let_clause.replace(id);
+        Traverse::Continue
+    }
+
     fn enter_where_clause(&mut self, _where_clause: &'ast ast::WhereClause) -> Traverse {
+        self.current_clauses_mut().filter = Some("WHERE");
         self.enter_env();
         Traverse::Continue
     }
 
     fn exit_where_clause(&mut self, _where_clause: &'ast ast::WhereClause) -> Traverse {
+        self.current_clauses_mut().filter = None;
         let mut env = self.exit_env();
         eq_or_fault!(self, env.len(), 1, "env.len() != 1");
 
         let filter = logical::BindingsOp::Filter(logical::Filter {
             expr: env.pop().unwrap().1,
         });
-        let id = self.curr_plan().add_operator(filter);
+        let id = self.add_operator(filter);
 
         self.current_clauses_mut().where_clause.replace(id);
         Traverse::Continue
     }
 
     fn enter_having_clause(&mut self, _having_clause: &'ast ast::HavingClause) -> Traverse {
+        self.current_clauses_mut().filter = Some("HAVING");
         self.enter_env();
         Traverse::Continue
     }
 
     fn exit_having_clause(&mut self, _having_clause: &'ast ast::HavingClause) -> Traverse {
+        self.current_clauses_mut().filter = None;
         let mut env = self.exit_env();
         eq_or_fault!(self, env.len(), 1, "env.len() is 1");
 
         let having = BindingsOp::Having(logical::Having {
             expr: env.pop().unwrap().1,
         });
-        let id = self.curr_plan().add_operator(having);
+        let id = self.add_operator(having);
 
         self.current_clauses_mut().having_clause.replace(id);
         Traverse::Continue
@@ -1779,7 +2747,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
             group_as_alias,
         });
 
-        let id = self.curr_plan().add_operator(group_by);
+        let id = self.add_operator(group_by);
         self.current_clauses_mut().group_by_clause.replace(id);
         Traverse::Continue
     }
@@ -1807,7 +2775,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
     fn exit_order_by_expr(&mut self, _order_by_expr: &'ast OrderByExpr) -> Traverse {
         let specs = self.exit_sort();
         let order_by = logical::BindingsOp::OrderBy(logical::OrderBy { specs });
-        let id = self.curr_plan().add_operator(order_by);
+        let id = self.add_operator(order_by);
         if matches!(self.current_ctx(), Some(QueryContext::Query)) {
             self.current_clauses_mut().order_by_clause.replace(id);
         } else {
@@ -1880,7 +2848,7 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
         };
 
         let limit_offset = logical::BindingsOp::LimitOffset(logical::LimitOffset { limit, offset });
-        let id = self.curr_plan().add_operator(limit_offset);
+        let id = self.add_operator(limit_offset);
         if matches!(self.current_ctx(), Some(QueryContext::Query)) {
             self.current_clauses_mut().limit_offset_clause.replace(id);
         } else {
@@ -1999,6 +2967,360 @@ impl<'ast> Visitor<'ast> for AstToLogical<'_> {
     }
 }
 
+/// Returns the portion of `plan` that flows into `root`, terminated by a [`BindingsOp::Sink`].
+fn upstream_plan(plan: &LogicalPlan<BindingsOp>, root: OpId) -> LogicalPlan<BindingsOp> {
+    let mut upstream = vec![root];
+    let mut pending = vec![root];
+    while let Some(dst) = pending.pop() {
+        for (src, _, _) in plan.flows().iter().filter(|(_, d, _)| *d == dst) {
+            if !upstream.contains(src) {
+                upstream.push(*src);
+                pending.push(*src);
+            }
+        }
+    }
+
+    let mut subplan = LogicalPlan::new();
+    let mut mapping = FxHashMap::default();
+    for (id, op) in plan.operators_by_id() {
+        if upstream.contains(&id) {
+            let sub_id = subplan.add_operator(op.clone());
+            if let Some(location) = plan.location(id) {
+                subplan.set_location(sub_id, location.clone());
+            }
+            mapping.insert(id, sub_id);
+        }
+    }
+    for (src, dst, branch) in plan.flows() {
+        if let (Some(src), Some(dst)) = (mapping.get(src), mapping.get(dst)) {
+            subplan.add_flow_with_branch_num(*src, *dst, *branch);
+        }
+    }
+    let sink = subplan.add_operator(BindingsOp::Sink);
+    subplan.add_flow(mapping[&root], sink);
+    subplan
+}
+
+/// The value of the join column `column` in the bindings of the `FROM` source `op`: the variable
+/// bound to the column by a `USING` or `NATURAL` join in `op`, or else the attribute named
+/// `column` of the tuples bound by the variables of `op`, the first of which that is not `MISSING`
+/// if `op` binds more than one variable.
+fn join_column_value(op: &BindingsOp, column: &SymbolPrimitive) -> ValueExpr {
+    fn candidates(op: &BindingsOp, column: &SymbolPrimitive, out: &mut Vec<ValueExpr>) {
+        match op {
+            BindingsOp::Join(join) if join.using.iter().any(|(name, _)| name == &column.value) => {
+                out.push(ValueExpr::VarRef(
+                    symprim_to_binding(column),
+                    VarRefType::Local,
+                ));
+            }
+            BindingsOp::Join(join) => {
+                candidates(&join.left, column, out);
+                candidates(&join.right, column, out);
+            }
+            BindingsOp::Scan(logical::Scan { as_key, .. })
+            | BindingsOp::Unpivot(logical::Unpivot { as_key, .. }) => {
+                out.push(ValueExpr::Path(
+                    Box::new(ValueExpr::VarRef(
+                        BindingsName::CaseSensitive(Cow::Owned(as_key.clone())),
+                        VarRefType::Local,
+                    )),
+                    vec![PathComponent::Key(symprim_to_binding(column))],
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    let mut values = vec![];
+    candidates(op, column, &mut values);
+    match values.len() {
+        1 => values.pop().unwrap(),
+        _ => DynamicLookup(Box::new(values)),
+    }
+}
+
+/// The name of the collection a data modification statement targets, e.g. `t` in `DELETE FROM t`.
+fn dml_target_name(expr: &Expr) -> Option<BindingsName<'static>> {
+    let Expr::VarRef(var_ref) = expr else {
+        return None;
+    };
+    let SymbolPrimitive { value, case } = &var_ref.node.name;
+    Some(match case {
+        CaseSensitivity::CaseSensitive => BindingsName::CaseSensitive(Cow::Owned(value.clone())),
+        CaseSensitivity::CaseInsensitive => {
+            BindingsName::CaseInsensitive(Cow::Owned(value.clone()))
+        }
+    })
+}
+
+fn binding_name<'a>(name: &'a BindingsName<'_>) -> &'a str {
+    match name {
+        BindingsName::CaseSensitive(name) | BindingsName::CaseInsensitive(name) => name.as_ref(),
+    }
+}
+
+/// The path from a row of the collection modified by a data modification statement (bound to
+/// `as_key`) to the part of it denoted by `expr`, e.g., `[a, b]` for either `x.a.b` or `a.b` in
+/// `UPDATE t AS x SET ...`.
+fn assignment_path(expr: &ValueExpr, as_key: &str) -> Option<Vec<PathComponent>> {
+    match expr {
+        ValueExpr::VarRef(name, _) => name.matcher().matches(as_key).then(Vec::new),
+        ValueExpr::Path(root, steps) => {
+            let mut path = assignment_path(root, as_key)?;
+            path.extend(steps.iter().cloned());
+            Some(path)
+        }
+        ValueExpr::DynamicLookup(lookups) => lookups
+            .iter()
+            .find_map(|lookup| assignment_path(lookup, as_key)),
+        _ => None,
+    }
+}
+
+/// Makes each row produced by the recursive part `plan` of the `WITH` element `name` hold the row
+/// of the previous iteration it derives from, returning the attribute under which it is held.
+///
+/// The recursive part must project a `SELECT` list and refer to `name` in exactly one `FROM` item.
+fn carry_parent(plan: &mut LogicalPlan<BindingsOp>, name: &str) -> Option<String> {
+    fn is_cte(expr: &ValueExpr, name: &str) -> bool {
+        match expr {
+            ValueExpr::VarRef(BindingsName::CaseSensitive(var), _) => var == name,
+            ValueExpr::VarRef(BindingsName::CaseInsensitive(var), _) => {
+                unicase::eq(var.as_ref(), name)
+            }
+            ValueExpr::DynamicLookup(lookups) => lookups.iter().any(|expr| is_cte(expr, name)),
+            _ => false,
+        }
+    }
+
+    fn cte_scans(op: &BindingsOp, name: &str, keys: &mut Vec<String>) {
+        match op {
+            // the operands of a join also appear as operators of the plan
+            BindingsOp::Scan(scan) if is_cte(&scan.expr, name) && !keys.contains(&scan.as_key) => {
+                keys.push(scan.as_key.clone());
+            }
+            BindingsOp::Join(join) => {
+                cte_scans(&join.left, name, keys);
+                cte_scans(&join.right, name, keys);
+            }
+            _ => {}
+        }
+    }
+
+    let mut keys = vec![];
+    let mut projections = vec![];
+    for (id, op) in plan.operators_by_id() {
+        cte_scans(op, name, &mut keys);
+        if let BindingsOp::Project(_) = op {
+            projections.push(id);
+        }
+    }
+    let ([key], [projection]) = (keys.as_slice(), projections.as_slice()) else {
+        return None;
+    };
+
+    let parent = "$__parent".to_string();
+    let var = ValueExpr::VarRef(
+        BindingsName::CaseSensitive(Cow::Owned(key.clone())),
+        VarRefType::Local,
+    );
+    match plan.operator_as_mut(*projection) {
+        Some(BindingsOp::Project(project)) => project.exprs.push((parent.clone(), var)),
+        _ => return None,
+    }
+    Some(parent)
+}
+
+/// Returns whether `a` and `b` name the same variable.
+fn same_name(a: &SymbolPrimitive, b: &SymbolPrimitive) -> bool {
+    match (&a.case, &b.case) {
+        (CaseSensitivity::CaseSensitive, CaseSensitivity::CaseSensitive) => a.value == b.value,
+        _ => unicase::eq(a.value.as_str(), b.value.as_str()),
+    }
+}
+
+/// Returns the names of the variables bound by the `FROM` item `from_let` with node id `id`.
+fn from_let_names(
+    id: NodeId,
+    from_let: &FromLet,
+    registry: &name_resolver::KeyRegistry,
+    names: &mut Vec<SymbolPrimitive>,
+) {
+    let produced = registry.schema.get(&id).map(|schema| &schema.produce);
+    names.extend(produced.into_iter().flatten().filter_map(|sym| match sym {
+        name_resolver::Symbol::Known(sym) => Some(sym.clone()),
+        name_resolver::Symbol::Unknown(_) => None,
+    }));
+    names.extend(from_let.by_alias.iter().cloned());
+}
+
+/// Returns the names of the variables bound by the `FROM` items of `source`.
+fn from_source_names(
+    source: &FromSource,
+    registry: &name_resolver::KeyRegistry,
+    names: &mut Vec<SymbolPrimitive>,
+) {
+    match source {
+        FromSource::FromLet(from_let) => {
+            from_let_names(from_let.id, &from_let.node, registry, names);
+        }
+        FromSource::Join(join) => {
+            from_source_names(&join.node.left, registry, names);
+            from_source_names(&join.node.right, registry, names);
+        }
+    }
+}
+
+/// Returns whether any variable reference within `node` refers to one of the variables `names`
+/// of its environment, rather than to a variable of the same name bound within `node` (e.g., by a
+/// `FROM` or `LET` clause or a `WITH` element of a nested query).
+fn refers_to<T: Visit>(
+    node: &T,
+    names: &[SymbolPrimitive],
+    registry: &name_resolver::KeyRegistry,
+) -> bool {
+    /// The variables bound within a query; `hidden` while evaluating the query's `FROM` clause,
+    /// whose items only see the variables of the items preceding them.
+    struct Scope {
+        names: Vec<SymbolPrimitive>,
+        hidden: bool,
+    }
+
+    struct ReferenceFinder<'n> {
+        names: &'n [SymbolPrimitive],
+        registry: &'n name_resolver::KeyRegistry,
+        scopes: Vec<Scope>,
+        ids: Vec<NodeId>,
+        // whether a path step `.a` or `[a]` is visited, whose variable reference is an attribute
+        in_path_step: bool,
+        found: bool,
+    }
+
+    impl ReferenceFinder<'_> {
+        fn push_scope(&mut self, names: Vec<SymbolPrimitive>) {
+            self.scopes.push(Scope {
+                names,
+                hidden: false,
+            });
+        }
+
+        fn is_bound(&self, name: &SymbolPrimitive) -> bool {
+            self.scopes
+                .iter()
+                .filter(|scope| !scope.hidden)
+                .any(|scope| scope.names.iter().any(|bound| same_name(bound, name)))
+        }
+    }
+
+    impl<'ast> Visitor<'ast> for ReferenceFinder<'_> {
+        fn enter_ast_node(&mut self, id: NodeId) -> Traverse {
+            self.ids.push(id);
+            Traverse::Continue
+        }
+        fn exit_ast_node(&mut self, _id: NodeId) -> Traverse {
+            self.ids.pop();
+            Traverse::Continue
+        }
+
+        fn enter_query(&mut self, query: &'ast Query) -> Traverse {
+            if let Some(with) = &query.with {
+                let names = with.node.withs.iter();
+                self.push_scope(names.map(|elem| elem.node.query_name.clone()).collect());
+            }
+            Traverse::Continue
+        }
+        fn exit_query(&mut self, query: &'ast Query) -> Traverse {
+            if query.with.is_some() {
+                self.scopes.pop();
+            }
+            Traverse::Continue
+        }
+
+        fn enter_select(&mut self, select: &'ast Select) -> Traverse {
+            let mut names = vec![];
+            if let Some(from) = &select.from {
+                from_source_names(&from.node.source, self.registry, &mut names);
+            }
+            if let Some(from_let) = &select.from_let {
+                let bindings = from_let.node.let_bindings.iter();
+                names.extend(bindings.map(|binding| binding.node.as_alias.clone()));
+            }
+            if let Some(group_by) = &select.group_by {
+                let keys = group_by.node.keys.iter();
+                names.extend(keys.filter_map(|key| key.node.as_alias.clone()));
+                names.extend(group_by.node.group_as_alias.iter().cloned());
+            }
+            self.push_scope(names);
+            Traverse::Continue
+        }
+        fn exit_select(&mut self, _select: &'ast Select) -> Traverse {
+            self.scopes.pop();
+            Traverse::Continue
+        }
+
+        fn enter_from_clause(&mut self, _from_clause: &'ast FromClause) -> Traverse {
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.hidden = true;
+            }
+            self.push_scope(vec![]);
+            Traverse::Continue
+        }
+        fn exit_from_clause(&mut self, _from_clause: &'ast FromClause) -> Traverse {
+            self.scopes.pop();
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.hidden = false;
+            }
+            Traverse::Continue
+        }
+
+        fn exit_from_let(&mut self, from_let: &'ast FromLet) -> Traverse {
+            // the item's variables are in scope of the `FROM` items following it
+            let mut names = vec![];
+            if let Some(id) = self.ids.last() {
+                from_let_names(*id, from_let, self.registry, &mut names);
+            }
+            if let Some(scope) = self.scopes.last_mut() {
+                scope.names.extend(names);
+            }
+            Traverse::Continue
+        }
+
+        fn enter_path_step(&mut self, path_step: &'ast PathStep) -> Traverse {
+            if let PathStep::PathProject(step) | PathStep::PathIndex(step) = path_step {
+                self.in_path_step = matches!(*step.index, Expr::VarRef(_));
+            }
+            Traverse::Continue
+        }
+        fn exit_path_step(&mut self, _path_step: &'ast PathStep) -> Traverse {
+            self.in_path_step = false;
+            Traverse::Continue
+        }
+
+        fn enter_var_ref(&mut self, var_ref: &'ast VarRef) -> Traverse {
+            self.found = !self.in_path_step
+                && !self.is_bound(&var_ref.name)
+                && self.names.iter().any(|name| same_name(name, &var_ref.name));
+            if self.found {
+                Traverse::Stop
+            } else {
+                Traverse::Continue
+            }
+        }
+    }
+
+    let mut finder = ReferenceFinder {
+        names,
+        registry,
+        scopes: vec![],
+        ids: vec![],
+        in_path_step: false,
+        found: false,
+    };
+    node.visit(&mut finder);
+    finder.found
+}
+
 fn lit_to_lit(lit: &Lit) -> Result<logical::Lit, AstTransformError> {
     fn tuple_pair(
         field: &ast::LitField,
@@ -2063,15 +3385,120 @@ fn lit_to_lit(lit: &Lit) -> Result<logical::Lit, AstTransformError> {
     Ok(val)
 }
 
+fn type_to_type(ty: &ast::Type) -> Result<logical::Type, AstTransformError> {
+    let invalid = || AstTransformError::InvalidType(ty.to_pretty_string(80).unwrap_or_default());
+
+    let parts = match ty {
+        ast::Type::NullType => return Ok(logical::Type::NullType),
+        ast::Type::BooleanType => return Ok(logical::Type::BooleanType),
+        ast::Type::Integer2Type => return Ok(logical::Type::Integer2Type),
+        ast::Type::Integer4Type => return Ok(logical::Type::Integer4Type),
+        ast::Type::Integer8Type => return Ok(logical::Type::Integer8Type),
+        ast::Type::DecimalType => return Ok(logical::Type::DecimalType(None, None)),
+        ast::Type::NumericType => return Ok(logical::Type::NumericType(None, None)),
+        ast::Type::RealType => return Ok(logical::Type::RealType),
+        ast::Type::DoublePrecisionType => return Ok(logical::Type::DoublePrecisionType),
+        ast::Type::TimestampType => return Ok(logical::Type::TimestampType(None)),
+        ast::Type::CharacterType => return Ok(logical::Type::CharacterType(None)),
+        ast::Type::CharacterVaryingType => return Ok(logical::Type::CharacterVaryingType(None)),
+        ast::Type::MissingType => return Ok(logical::Type::MissingType),
+        ast::Type::StringType => return Ok(logical::Type::StringType),
+        ast::Type::SymbolType => return Ok(logical::Type::SymbolType),
+        ast::Type::BlobType => return Ok(logical::Type::BlobType),
+        ast::Type::ClobType => return Ok(logical::Type::ClobType),
+        ast::Type::DateType => return Ok(logical::Type::DateType),
+        ast::Type::TimeType => return Ok(logical::Type::TimeType(None)),
+        ast::Type::ZonedTimestampType => return Ok(logical::Type::ZonedTimestampType(None)),
+        ast::Type::StructType => return Ok(logical::Type::StructType),
+        ast::Type::TupleType => return Ok(logical::Type::TupleType),
+        ast::Type::ListType => return Ok(logical::Type::ListType),
+        ast::Type::BagType => return Ok(logical::Type::BagType),
+        ast::Type::AnyType => return Ok(logical::Type::AnyType),
+        ast::Type::CustomType(custom) => &custom.parts,
+    };
+
+    // Only the first part of a custom type may have parameters, which must be non-negative
+    // integers, e.g. `TIME(3) WITH TIME ZONE`
+    let param = |param: &ast::CustomTypeParam| match param {
+        ast::CustomTypeParam::Lit(Lit::Int8Lit(n)) => u32::try_from(*n).ok(),
+        ast::CustomTypeParam::Lit(Lit::Int16Lit(n)) => u32::try_from(*n).ok(),
+        ast::CustomTypeParam::Lit(Lit::Int32Lit(n)) => u32::try_from(*n).ok(),
+        ast::CustomTypeParam::Lit(Lit::Int64Lit(n)) => u32::try_from(*n).ok(),
+        _ => None,
+    };
+    let (name, params) = match parts.first() {
+        Some(ast::CustomTypePart::Name(name)) => (name, vec![]),
+        Some(ast::CustomTypePart::Parameterized(name, params)) => (
+            name,
+            params
+                .iter()
+                .map(param)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?,
+        ),
+        None => return Err(invalid()),
+    };
+    let suffix = parts[1..]
+        .iter()
+        .map(|part| match part {
+            ast::CustomTypePart::Name(name) => Ok(name.value.to_uppercase()),
+            ast::CustomTypePart::Parameterized(..) => Err(invalid()),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let suffix = suffix.iter().map(String::as_str).collect::<Vec<_>>();
+    let zoned = match suffix.as_slice() {
+        [] | ["WITHOUT", "TIME", "ZONE"] => false,
+        ["WITH", "TIME", "ZONE"] => true,
+        _ => return Err(invalid()),
+    };
+
+    let name = name.value.to_uppercase();
+    let ty = match (name.as_str(), params.as_slice(), zoned) {
+        ("DECIMAL" | "DEC" | "NUMERIC", [], false) => logical::Type::DecimalType(None, None),
+        ("DECIMAL" | "DEC" | "NUMERIC", [p], false) if *p > 0 => {
+            logical::Type::DecimalType(Some(*p), Some(0))
+        }
+        ("DECIMAL" | "DEC" | "NUMERIC", [p, s], false) if *p > 0 && s <= p => {
+            logical::Type::DecimalType(Some(*p), Some(*s))
+        }
+        ("CHAR" | "CHARACTER", [], false) => logical::Type::CharacterType(None),
+        ("CHAR" | "CHARACTER", [n], false) if *n > 0 => logical::Type::CharacterType(Some(*n)),
+        ("VARCHAR", [], false) => logical::Type::CharacterVaryingType(None),
+        ("VARCHAR", [n], false) if *n > 0 => logical::Type::CharacterVaryingType(Some(*n)),
+        ("FLOAT", [p], false) if (1..=24).contains(p) => logical::Type::RealType,
+        ("FLOAT", [p], false) if (25..=53).contains(p) => logical::Type::DoublePrecisionType,
+        ("DATE", [], false) => logical::Type::DateType,
+        ("TIME", [], false) => logical::Type::TimeType(None),
+        ("TIME", [], true) => logical::Type::ZonedTimeType(None),
+        ("TIME", [p], false) if *p <= 9 => logical::Type::TimeType(Some(*p)),
+        ("TIME", [p], true) if *p <= 9 => logical::Type::ZonedTimeType(Some(*p)),
+        ("TIMESTAMP", [], false) => logical::Type::TimestampType(None),
+        ("TIMESTAMP", [], true) => logical::Type::ZonedTimestampType(None),
+        ("TIMESTAMP", [p], false) if *p <= 9 => logical::Type::TimestampType(Some(*p)),
+        ("TIMESTAMP", [p], true) if *p <= 9 => logical::Type::ZonedTimestampType(Some(*p)),
+        ("SEXP", [], false) => logical::Type::SexpType,
+        _ => return Err(invalid()),
+    };
+    // `NUMERIC` is a synonym of `DECIMAL`
+    Ok(match (name.as_str(), ty) {
+        ("NUMERIC", logical::Type::DecimalType(p, s)) => logical::Type::NumericType(p, s),
+        (_, ty) => ty,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::LogicalPlanner;
     use assert_matches::assert_matches;
+    use indexmap::IndexSet;
     use partiql_catalog::catalog::{MutableCatalog, PartiqlCatalog, TypeEnvEntry};
     use partiql_logical::BindingsOp::Project;
     use partiql_logical::ValueExpr;
-    use partiql_types::PartiqlShape;
+    use partiql_types::{
+        struct_fields, type_decimal, type_int, type_string, PartiqlNoIdShapeBuilder, PartiqlShape,
+        ShapeBuilderExtensions, StructConstraint, StructType,
+    };
 
     #[test]
     fn test_plan_non_existent_fns() {
@@ -2161,6 +3588,7 @@ mod tests {
             )])),
             as_key: "c".to_string(),
             at_key: None,
+            columns: None,
         }));
         let sink = expected_logical.add_operator(BindingsOp::Sink);
         expected_logical.add_flow_with_branch_num(scan, project, 0);
@@ -2180,4 +3608,40 @@ mod tests {
 
         println!("logical: {:?}", &logical);
     }
+
+    #[test]
+    fn test_plan_natural_join_columns_from_catalog() {
+        let mut bld = PartiqlNoIdShapeBuilder::default();
+        let table = |fields, bld: &mut PartiqlNoIdShapeBuilder| {
+            let fields = StructType::new(IndexSet::from([fields, StructConstraint::Open(false)]));
+            bld.new_struct(fields).into_bag(bld)
+        };
+        let fields = struct_fields![("id", type_int!(bld)), ("name", type_string!(bld))];
+        let customers = table(fields, &mut bld);
+        let fields = struct_fields![("ID", type_int!(bld)), ("total", type_decimal!(bld))];
+        let orders = table(fields, &mut bld);
+
+        let mut catalog = PartiqlCatalog::default();
+        let _oid = catalog.add_type_entry(TypeEnvEntry::new("customers", &[], customers));
+        let _oid = catalog.add_type_entry(TypeEnvEntry::new("orders", &[], orders));
+        let catalog = catalog.to_shared_catalog();
+        let statement = "SELECT * FROM customers AS c NATURAL JOIN orders AS o";
+        let parsed = partiql_parser::Parser::default()
+            .parse(statement)
+            .expect("Expect successful parse");
+        let planner = LogicalPlanner::new(&catalog);
+        let logical = planner.lower(&parsed).expect("Expect successful lowering");
+
+        let join = logical.operators().iter().find_map(|op| match op {
+            BindingsOp::Join(join) => Some(join),
+            _ => None,
+        });
+        let using: Vec<_> = join
+            .expect("Expect a join")
+            .using
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(using, vec!["id"]);
+    }
 }
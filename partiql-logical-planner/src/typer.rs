@@ -1,19 +1,26 @@
-use crate::typer::LookupOrder::{GlobalLocal, LocalGlobal};
 use indexmap::{IndexMap, IndexSet};
 use partiql_ast::ast::{CaseSensitivity, SymbolPrimitive};
-use partiql_catalog::catalog::SharedCatalog;
-use partiql_logical::{BindingsOp, Lit, LogicalPlan, OpId, PathComponent, ValueExpr, VarRefType};
+use partiql_catalog::catalog::{FunctionEntryFunction, SharedCatalog};
+use partiql_logical::{
+    AggFunc, BagOp, BinaryOp, BindingsOp, CallName, CastExpr, CastKind, CoalesceExpr, Dml, DmlOp,
+    Join, LimitOffset, Lit, LogicalPlan, NullIfExpr, PathComponent, Pattern, PatternMatchExpr,
+    ProjectAllMode, ReturningColumn, SearchedCase, SimpleCase, TupleExpr, Type, UnaryOp,
+    UpdateChange, ValueExpr, VarRefType, WindowFunc,
+};
 use partiql_types::{
-    type_array, type_bag, type_bool, type_decimal, type_dynamic, type_float64, type_int,
-    type_string, type_struct, ArrayType, BagType, PartiqlShape, PartiqlShapeBuilder,
-    ShapeResultError, Static, StructConstraint, StructField, StructType,
+    type_bool, type_datetime, type_decimal, type_dynamic, type_float32, type_float64, type_int,
+    type_int16, type_int32, type_int64, type_string, ArrayType, BagType, PartiqlShape,
+    PartiqlShapeBuilder, ShapeResultError, Static, StructConstraint, StructField, StructType,
 };
 use partiql_value::BindingsName;
 use petgraph::algo::toposort;
 use petgraph::graph::NodeIndex;
 use petgraph::prelude::StableGraph;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
 use rustc_hash::FxHashMap;
 use thiserror::Error;
+use unicase::UniCase;
 
 #[macro_export]
 macro_rules! ty_ctx {
@@ -29,8 +36,6 @@ macro_rules! ty_env {
     };
 }
 
-const OUTPUT_SCHEMA_KEY: &str = "_output_schema";
-
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct TypeErr {
     pub errors: Vec<TypingError>,
@@ -70,17 +75,6 @@ pub enum TypingMode {
     Strict,
 }
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-enum LookupOrder {
-    /// Global then Local
-    GlobalLocal,
-    /// Local then Global
-    LocalGlobal,
-    /// Signals for delegating the lookup order, e.g., to [VarRefType]
-    Delegate,
-}
-
 /// Represents a type environment context
 #[derive(Debug, Clone)]
 struct TypeEnvContext {
@@ -125,15 +119,26 @@ impl From<(&LocalTypeEnv, &PartiqlShape)> for TypeEnvContext {
 /// Represents a Local Type Environment as opposed to the Global Type Environment in the Catalog.
 type LocalTypeEnv = IndexMap<SymbolPrimitive, PartiqlShape>;
 
+/// Represents the typed output of a [`BindingsOp`]
+#[derive(Debug, Clone)]
+enum OpOutput {
+    /// Binding tuples of the variables in `env`, e.g., of a `FROM` clause; `ordered` if the
+    /// tuples are in a specific order, e.g., after an `ORDER BY` clause.
+    Bindings { env: LocalTypeEnv, ordered: bool },
+    /// A value, e.g., the collection of a `SELECT` clause or the result of an expression query.
+    Value(PartiqlShape),
+}
+
 #[derive(Debug)]
 pub struct PlanTyper<'c> {
     typing_mode: TypingMode,
     catalog: &'c dyn SharedCatalog,
     logical_plan: LogicalPlan<BindingsOp>,
     errors: Vec<TypingError>,
+    /// The variables in lexical scope, with the innermost scope last
     type_env_stack: Vec<TypeEnvContext>,
-    current_bindings_op: Option<BindingsOp>,
-    output: Option<PartiqlShape>,
+    /// The common table expressions in scope, with the innermost last
+    ctes: Vec<(String, PartiqlShape)>,
     bld: PartiqlShapeBuilder,
 }
 
@@ -147,8 +152,7 @@ impl<'c> PlanTyper<'c> {
             logical_plan: ir.clone(),
             errors: Default::default(),
             type_env_stack: Default::default(),
-            current_bindings_op: Default::default(),
-            output: None,
+            ctes: Default::default(),
             bld: Default::default(),
         }
     }
@@ -161,423 +165,1194 @@ impl<'c> PlanTyper<'c> {
             logical_plan: lg.clone(),
             errors: Default::default(),
             type_env_stack: Default::default(),
-            current_bindings_op: Default::default(),
-            output: None,
+            ctes: Default::default(),
             bld: Default::default(),
         }
     }
 
     /// Returns the typing result for the Typer
     pub fn type_plan(&mut self) -> Result<PartiqlShape, TypeErr> {
-        let ops = self.sort()?;
-
-        for idx in ops {
-            let graph = self.to_stable_graph()?;
-            if let Some(binop) = graph.node_weight(idx) {
-                self.type_bindings_op(binop);
-            }
-        }
+        let plan = self.logical_plan.clone();
+        let output = self.type_logical_plan(&plan)?;
 
         if self.errors.is_empty() {
-            Ok(self.output.clone().unwrap_or(self.bld.new_undefined()))
+            Ok(output)
         } else {
-            let output_schema = self.get_singleton_type_from_env();
             Err(TypeErr {
                 errors: self.errors.clone(),
-                output: Some(output_schema),
+                output: Some(output),
             })
         }
     }
 
-    fn type_bindings_op(&mut self, op: &BindingsOp) {
-        self.current_bindings_op = Some(op.clone());
+    /// Types the operators of `plan` in data flow order and returns the type of its result.
+    fn type_logical_plan(
+        &mut self,
+        plan: &LogicalPlan<BindingsOp>,
+    ) -> Result<PartiqlShape, TypeErr> {
+        let graph = to_stable_graph(plan)?;
+        let ops = toposort(&graph, None).map_err(|e| TypeErr {
+            errors: vec![TypingError::IllegalState(format!(
+                "Malformed plan detected: {e:?}"
+            ))],
+            output: None,
+        })?;
+
+        let mut outputs: FxHashMap<NodeIndex, OpOutput> = FxHashMap::default();
+        for idx in &ops {
+            // The operands of a `Join` are typed as part of the `Join`
+            let mut successors = graph
+                .neighbors_directed(*idx, Direction::Outgoing)
+                .peekable();
+            if successors.peek().is_some()
+                && successors.all(|succ| matches!(graph[succ], BindingsOp::Join(_)))
+            {
+                continue;
+            }
+
+            let mut inputs: Vec<_> = graph
+                .edges_directed(*idx, Direction::Incoming)
+                .map(|edge| (*edge.weight(), edge.source()))
+                .collect();
+            inputs.sort_by_key(|(branch, _)| *branch);
+            let inputs = inputs
+                .into_iter()
+                .filter_map(|(_, src)| outputs.get(&src).cloned())
+                .collect();
+
+            let output = self.type_bindings_op(&graph[*idx], inputs);
+            outputs.insert(*idx, output);
+        }
+
+        match ops.last().and_then(|idx| outputs.remove(idx)) {
+            Some(output) => Ok(self.output_type(output)),
+            None => Err(TypeErr {
+                errors: vec![TypingError::IllegalState("Malformed IR".to_string())],
+                output: None,
+            }),
+        }
+    }
+
+    /// Types a plan nested in the plan being typed, e.g., of a sub-query.
+    fn type_sub_plan(&mut self, plan: &LogicalPlan<BindingsOp>) -> PartiqlShape {
+        match self.type_logical_plan(plan) {
+            Ok(ty) => ty,
+            Err(err) => {
+                self.errors.extend(err.errors);
+                self.bld.new_dynamic()
+            }
+        }
+    }
+
+    fn type_bindings_op(&mut self, op: &BindingsOp, inputs: Vec<OpOutput>) -> OpOutput {
+        let mut inputs = inputs.into_iter();
+        let input = inputs.next().unwrap_or_else(|| OpOutput::Bindings {
+            env: LocalTypeEnv::new(),
+            ordered: false,
+        });
+
         match op {
             BindingsOp::Scan(partiql_logical::Scan {
                 expr,
                 as_key,
                 at_key,
+                columns,
             }) => {
-                if let Some(_at_key) = at_key {
-                    self.errors.push(TypingError::NotYetImplemented(
-                        "Scan operator with AT key is not implemented yet".to_string(),
-                    ));
+                let mut ty = self.type_vexpr(expr);
+                if let Some(columns) = columns {
+                    ty = self.rename_columns(&ty, columns);
                 }
-
-                self.type_vexpr(expr, LookupOrder::Delegate);
-                if !as_key.is_empty() {
-                    let type_ctx = &self.local_type_ctx();
-                    for (_name, ty) in type_ctx.env() {
-                        if let Ok(_s) = ty.expect_struct() {
-                            self.type_env_stack.push(ty_ctx![(
-                                &ty_env![(string_to_sym(as_key.as_str()), ty.clone())],
-                                ty
-                            )]);
-                        }
+                let mut env = ty_env![(string_to_sym(as_key), self.element_type(&ty))];
+                if let Some(at_key) = at_key {
+                    let at_ty = if ty.is_ordered_collection() {
+                        type_int!(self.bld)
+                    } else {
+                        type_dynamic!(self.bld)
+                    };
+                    env.insert(string_to_sym(at_key), at_ty);
+                }
+                OpOutput::Bindings {
+                    env,
+                    ordered: false,
+                }
+            }
+            BindingsOp::Unpivot(partiql_logical::Unpivot {
+                expr,
+                as_key,
+                at_key,
+            }) => {
+                let ty = self.type_vexpr(expr);
+                let mut env = ty_env![(string_to_sym(as_key), self.attr_values_type(&ty))];
+                if let Some(at_key) = at_key {
+                    env.insert(string_to_sym(at_key), type_string!(self.bld));
+                }
+                OpOutput::Bindings {
+                    env,
+                    ordered: false,
+                }
+            }
+            BindingsOp::Join(Join {
+                kind: _,
+                left,
+                right,
+                on,
+                using,
+            }) => {
+                let left = self.type_bindings_op(left, vec![]);
+                let mut env = bindings_env(&left);
+                // The right operand may refer to the variables of the left one, e.g., in
+                // `FROM customers AS c, c.orders AS o`
+                let right = self.in_scope(&env, |typer| typer.type_bindings_op(right, vec![]));
+                env.extend(bindings_env(&right));
+                if let Some(on) = on {
+                    self.in_scope(&env, |typer| typer.type_vexpr(on));
+                }
+                let columns = self.in_scope(&env, |typer| {
+                    using
+                        .iter()
+                        .map(|(name, expr)| (string_to_sym(name), typer.type_vexpr(expr)))
+                        .collect::<Vec<_>>()
+                });
+                env.extend(columns);
+                OpOutput::Bindings {
+                    env,
+                    ordered: false,
+                }
+            }
+            BindingsOp::Filter(partiql_logical::Filter { expr })
+            | BindingsOp::Having(partiql_logical::Having { expr }) => {
+                self.in_scope(&bindings_env(&input), |typer| typer.type_vexpr(expr));
+                input
+            }
+            BindingsOp::OrderBy(partiql_logical::OrderBy { specs }) => {
+                self.in_scope(&bindings_env(&input), |typer| {
+                    for spec in specs {
+                        typer.type_vexpr(&spec.expr);
+                    }
+                });
+                match input {
+                    OpOutput::Bindings { env, .. } => OpOutput::Bindings { env, ordered: true },
+                    OpOutput::Value(ty) => {
+                        let element = self.element_type(&ty);
+                        OpOutput::Value(self.bld.new_array(ArrayType::new(Box::new(element))))
+                    }
+                }
+            }
+            BindingsOp::LimitOffset(LimitOffset { limit, offset }) => {
+                for expr in limit.iter().chain(offset) {
+                    self.type_vexpr(expr);
+                }
+                input
+            }
+            BindingsOp::GroupBy(partiql_logical::GroupBy {
+                strategy: _,
+                exprs,
+                aggregate_exprs,
+                group_as_alias,
+            }) => {
+                let env = bindings_env(&input);
+                let mut keys: Vec<_> = exprs.iter().collect();
+                keys.sort_by_key(|(name, _)| *name);
+
+                let mut group_env = self.in_scope(&env, |typer| {
+                    let mut group_env = LocalTypeEnv::new();
+                    for (name, expr) in keys {
+                        group_env.insert(string_to_sym(name), typer.type_vexpr(expr));
+                    }
+                    for agg in aggregate_exprs {
+                        let arg = typer.type_vexpr(&agg.expr);
+                        group_env.insert(string_to_sym(&agg.name), typer.type_agg(&agg.func, &arg));
                     }
+                    group_env
+                });
+                if let Some(alias) = group_as_alias {
+                    let group = self.bindings_type(&env);
+                    group_env.insert(string_to_sym(alias), self.bld.new_bag_of(group));
+                }
+                OpOutput::Bindings {
+                    env: group_env,
+                    ordered: false,
+                }
+            }
+            BindingsOp::Window(partiql_logical::Window { exprs }) => {
+                let (mut env, ordered) = match input {
+                    OpOutput::Bindings { env, ordered } => (env, ordered),
+                    OpOutput::Value(_) => (LocalTypeEnv::new(), false),
+                };
+                let fns = self.in_scope(&env, |typer| {
+                    exprs
+                        .iter()
+                        .map(|expr| {
+                            let args: Vec<_> =
+                                expr.args.iter().map(|arg| typer.type_vexpr(arg)).collect();
+                            for expr in expr
+                                .partition_by
+                                .iter()
+                                .chain(expr.order_by.iter().map(|spec| &spec.expr))
+                            {
+                                typer.type_vexpr(expr);
+                            }
+                            let arg = args.first().cloned().unwrap_or(PartiqlShape::Dynamic);
+                            let ty = match &expr.func {
+                                WindowFunc::RowNumber
+                                | WindowFunc::Rank
+                                | WindowFunc::DenseRank => {
+                                    type_int!(typer.bld)
+                                }
+                                WindowFunc::Lag
+                                | WindowFunc::Lead
+                                | WindowFunc::FirstValue
+                                | WindowFunc::LastValue => arg,
+                                WindowFunc::Agg(func) => typer.type_agg(func, &arg),
+                            };
+                            (string_to_sym(&expr.name), ty)
+                        })
+                        .collect::<Vec<_>>()
+                });
+                env.extend(fns);
+                OpOutput::Bindings { env, ordered }
+            }
+            BindingsOp::Let(partiql_logical::Let { bindings }) => {
+                let (mut env, ordered) = match input {
+                    OpOutput::Bindings { env, ordered } => (env, ordered),
+                    OpOutput::Value(_) => (LocalTypeEnv::new(), false),
+                };
+                // each binding is in scope of the preceding ones
+                for (name, expr) in bindings {
+                    let ty = self.in_scope(&env, |typer| typer.type_vexpr(expr));
+                    env.extend([(string_to_sym(name), ty)]);
                 }
+                OpOutput::Bindings { env, ordered }
             }
             BindingsOp::Project(partiql_logical::Project { exprs }) => {
-                let fields = exprs
-                    .iter()
-                    .map(|(k, v)| {
-                        self.type_vexpr(v, LookupOrder::LocalGlobal);
-                        StructField::new(k.as_str(), self.get_singleton_type_from_env())
-                    })
-                    .collect();
-
+                let fields = self.in_scope(&bindings_env(&input), |typer| {
+                    exprs
+                        .iter()
+                        .map(|(k, v)| StructField::new(k.as_str(), typer.type_vexpr(v)))
+                        .collect()
+                });
                 let ty = self.bld.new_struct(StructType::new(IndexSet::from([
                     StructConstraint::Fields(fields),
+                    StructConstraint::Open(false),
                 ])));
-
-                let derived_type_ctx = self.local_type_ctx();
-                let derived_type = &self.derived_type(&derived_type_ctx);
-                let schema = if derived_type.is_ordered_collection() {
-                    self.bld.new_array(ArrayType::new(Box::new(ty)))
-                } else if derived_type.is_unordered_collection() {
-                    self.bld.new_static(Static::Bag(BagType::new(Box::new(ty))))
-                } else {
-                    self.errors.push(TypingError::IllegalState(format!(
-                        "Expecting Collection for the output Schema but found {:?}",
-                        &ty
-                    )));
-                    ty
+                OpOutput::Value(self.collection_of(ty, is_ordered(&input)))
+            }
+            BindingsOp::ProjectAll(partiql_logical::ProjectAll { mode, join_columns }) => {
+                let env = bindings_env(&input);
+                let ty = match mode {
+                    ProjectAllMode::PassThrough => self.bindings_type(&env),
+                    ProjectAllMode::Unwrap => {
+                        let is_join_column =
+                            |name: &str| join_columns.iter().any(|c| unicase::eq(c.as_str(), name));
+                        let mut fields: IndexSet<_> = join_columns
+                            .iter()
+                            .filter_map(|column| {
+                                let ty = env.get(&string_to_sym(column))?;
+                                Some(StructField::new(column.as_str(), ty.clone()))
+                            })
+                            .collect();
+                        let mut closed = true;
+                        for (var, ty) in env.iter() {
+                            if is_join_column(&var.value) {
+                                continue;
+                            }
+                            match ty.expect_struct() {
+                                Ok(s) => {
+                                    closed &= s.is_closed();
+                                    fields.extend(
+                                        s.fields().filter(|f| !is_join_column(f.name())).cloned(),
+                                    );
+                                }
+                                Err(_) => closed = false,
+                            }
+                        }
+                        self.bld.new_struct(StructType::new(IndexSet::from([
+                            StructConstraint::Fields(fields),
+                            StructConstraint::Open(!closed),
+                        ])))
+                    }
                 };
-
-                // Marking the output Schema in Typing Environment:
-                //
-                // The clauses of an PartiQL SFW query are evaluated in the following order:
-                // WITH, FROM, LET, WHERE, GROUP BY, HAVING, LETTING (which is special to PartiQL),
-                // ORDER BY, LIMIT / OFFSET
-                // and SELECT (or SELECT VALUE or PIVOT, which are both special to ion PartiQL).
-                // -- PartiQL Spec. 2019 Section 3.3:
-                self.type_env_stack.push(ty_ctx![(
-                    &ty_env![(string_to_sym(OUTPUT_SCHEMA_KEY), schema)],
-                    derived_type
-                )]);
-            }
-            BindingsOp::Sink => {
-                if let Some(ty) =
-                    self.retrieve_type_from_local_ctx(&string_to_sym(OUTPUT_SCHEMA_KEY))
-                {
-                    self.output = Some(ty);
+                OpOutput::Value(self.collection_of(ty, is_ordered(&input)))
+            }
+            BindingsOp::ProjectValue(partiql_logical::ProjectValue { expr }) => {
+                let ty = self.in_scope(&bindings_env(&input), |typer| typer.type_vexpr(expr));
+                OpOutput::Value(self.collection_of(ty, is_ordered(&input)))
+            }
+            BindingsOp::ExprQuery(partiql_logical::ExprQuery { expr }) => {
+                OpOutput::Value(self.type_vexpr(expr))
+            }
+            BindingsOp::Exclude(partiql_logical::Exclude { paths }) => match input {
+                OpOutput::Bindings { mut env, ordered } => {
+                    // Attributes nested within an excluded path's root may now be absent
+                    for (var, ty) in env.iter_mut() {
+                        if paths
+                            .iter()
+                            .any(|path| name_matches(&path.root, &var.value))
+                        {
+                            *ty = PartiqlShape::Dynamic;
+                        }
+                    }
+                    OpOutput::Bindings { env, ordered }
                 }
+                output => output,
+            },
+            BindingsOp::Distinct | BindingsOp::Sink => input,
+            BindingsOp::BagOp(BagOp { .. }) => {
+                let elements: Vec<_> = std::iter::once(input)
+                    .chain(inputs)
+                    .map(|input| {
+                        let ty = self.output_type(input);
+                        self.element_type(&ty)
+                    })
+                    .collect();
+                let element = self.union_of(elements);
+                OpOutput::Value(self.bld.new_bag_of(element))
             }
-            _ => self.errors.push(TypingError::NotYetImplemented(format!(
-                "Unsupported BindingOperator: {:?}",
-                &op
-            ))),
-        }
-    }
-
-    fn type_vexpr(&mut self, v: &ValueExpr, lookup_order: LookupOrder) {
-        fn binding_to_sym(binding: &BindingsName<'_>) -> SymbolPrimitive {
-            match binding {
-                BindingsName::CaseSensitive(s) => SymbolPrimitive {
-                    value: s.to_string(),
-                    case: CaseSensitivity::CaseSensitive,
-                },
-                BindingsName::CaseInsensitive(s) => SymbolPrimitive {
-                    value: s.to_string(),
-                    case: CaseSensitivity::CaseInsensitive,
-                },
+            BindingsOp::Pivot(partiql_logical::Pivot { key, value }) => {
+                self.in_scope(&bindings_env(&input), |typer| {
+                    typer.type_vexpr(key);
+                    typer.type_vexpr(value);
+                });
+                OpOutput::Value(self.bld.new_struct(StructType::new_any()))
             }
-        }
-
-        match v {
-            ValueExpr::VarRef(binding_name, _) => {
-                let key = binding_to_sym(binding_name);
-                match lookup_order {
-                    GlobalLocal => {
-                        let ty = self.resolve_global_then_local(&key);
-                        self.type_varef(&key, &ty);
+            BindingsOp::With(partiql_logical::With { ctes, body }) => {
+                let outer_ctes = self.ctes.len();
+                for cte in ctes {
+                    let mut ty = self.type_sub_plan(&cte.plan);
+                    if let Some(recursive) = &cte.recursive {
+                        self.ctes.push((cte.name.clone(), ty.clone()));
+                        let step = self.type_sub_plan(&recursive.plan);
+                        self.ctes.pop();
+                        let elements = [self.element_type(&ty), self.element_type(&step)];
+                        let element = self.union_of(elements);
+                        ty = self.bld.new_bag_of(element);
+                    }
+                    if let Some(columns) = &cte.columns {
+                        ty = self.rename_columns(&ty, columns);
+                    }
+                    self.ctes.push((cte.name.clone(), ty));
+                }
+                let ty = self.type_sub_plan(body);
+                self.ctes.truncate(outer_ctes);
+                OpOutput::Value(ty)
+            }
+            BindingsOp::Dml(Dml {
+                target,
+                op,
+                returning,
+            }) => {
+                let row = match self.resolve_global(&target.name) {
+                    Some(ty) => self.element_type(&ty),
+                    None => type_dynamic!(self.bld),
+                };
+                let env = ty_env![(string_to_sym(&target.as_key), row)];
+                let (fields, closed) = self.in_scope(&env, |typer| {
+                    match op {
+                        DmlOp::Insert {
+                            values,
+                            on_conflict,
+                        } => {
+                            for expr in std::iter::once(values).chain(on_conflict) {
+                                typer.type_vexpr(expr);
+                            }
+                        }
+                        DmlOp::Update { filter, changes } => {
+                            if let Some(expr) = filter {
+                                typer.type_vexpr(expr);
+                            }
+                            for change in changes {
+                                if let UpdateChange::Set(_, expr) = change {
+                                    typer.type_vexpr(expr);
+                                }
+                            }
+                        }
+                        DmlOp::Delete { filter } => {
+                            if let Some(expr) = filter {
+                                typer.type_vexpr(expr);
+                            }
+                        }
                     }
-                    LocalGlobal => {
-                        let ty = self.resolve_local_then_global(&key);
-                        self.type_varef(&key, &ty);
+
+                    let mut fields = IndexSet::new();
+                    let mut closed = true;
+                    for elem in returning {
+                        match &elem.column {
+                            ReturningColumn::Wildcard => closed = false,
+                            ReturningColumn::Expr(name, expr) => {
+                                fields.insert(StructField::new(name, typer.type_vexpr(expr)));
+                            }
+                        }
                     }
-                    LookupOrder::Delegate => self.type_vexpr(v, self.lookup_order(v)),
+                    (fields, closed)
+                });
+
+                let ty = if returning.is_empty() {
+                    type_dynamic!(self.bld)
+                } else {
+                    let row = self.bld.new_struct(StructType::new(IndexSet::from([
+                        StructConstraint::Fields(fields),
+                        StructConstraint::Open(!closed),
+                    ])));
+                    self.bld.new_bag_of(row)
                 };
+                OpOutput::Value(ty)
             }
+        }
+    }
+
+    fn type_vexpr(&mut self, v: &ValueExpr) -> PartiqlShape {
+        match v {
+            ValueExpr::VarRef(name, ref_type) => self
+                .resolve_var(name, ref_type)
+                .unwrap_or(PartiqlShape::Dynamic),
+            ValueExpr::DynamicLookup(lookups) => self.type_dynamic_lookup(lookups),
             ValueExpr::Path(v, components) => {
-                self.type_vexpr(v, LookupOrder::Delegate);
+                let mut ty = self.type_vexpr(v);
+                let mut wildcard = false;
                 for component in components {
-                    match component {
-                        PathComponent::Key(key) => {
-                            let var = ValueExpr::VarRef(key.clone(), VarRefType::Local);
-                            self.type_vexpr(&var, LookupOrder::LocalGlobal);
-
-                            let key_as_sym = binding_to_sym(key);
-                            if let Some(ty) = self.retrieve_type_from_local_ctx(&key_as_sym) {
-                                let ctx = ty_ctx![(&ty_env![(key_as_sym, ty.clone())], &ty)];
-                                self.type_env_stack.push(ctx);
-                            } else {
-                                let ctx = ty_ctx![(
-                                    &ty_env![(key_as_sym, self.bld.new_undefined())],
-                                    &self.bld.new_undefined()
-                                )];
-                                self.type_env_stack.push(ctx);
+                    ty = match component {
+                        PathComponent::Key(key) => self.type_key(&ty, key),
+                        PathComponent::Index(idx) => match self.index_type(&ty) {
+                            Ok(ty) => ty,
+                            Err(ty) => self.type_absent(format!(
+                                "No Typing Information for index {idx} in {ty:?}"
+                            )),
+                        },
+                        PathComponent::KeyExpr(expr) => {
+                            self.type_vexpr(expr);
+                            match &**expr {
+                                ValueExpr::Lit(lit) => match &**lit {
+                                    Lit::String(key) => {
+                                        let key = BindingsName::CaseSensitive(key.clone().into());
+                                        self.type_key(&ty, &key)
+                                    }
+                                    _ => self.any_component_type(&ty),
+                                },
+                                _ => self.any_component_type(&ty),
                             }
                         }
-                        PathComponent::Index(_) => {
-                            self.errors.push(TypingError::NotYetImplemented(
-                                "Typing [Index] [PathComponent]s".to_string(),
-                            ));
+                        PathComponent::IndexExpr(expr) => {
+                            self.type_vexpr(expr);
+                            self.any_component_type(&ty)
                         }
-                        PathComponent::KeyExpr(_) => {
-                            self.errors.push(TypingError::NotYetImplemented(
-                                "Typing [KeyExpr] [PathComponent]s".to_string(),
-                            ));
+                        PathComponent::ForEach => {
+                            wildcard = true;
+                            self.element_type(&ty)
                         }
-                        PathComponent::IndexExpr(_) => {
-                            self.errors.push(TypingError::NotYetImplemented(
-                                "Typing [IndexExpr] [PathComponent]s".to_string(),
-                            ));
+                        PathComponent::Unpivot => {
+                            wildcard = true;
+                            self.attr_values_type(&ty)
                         }
                     }
                 }
+                if wildcard {
+                    self.bld.new_bag_of(ty)
+                } else {
+                    ty
+                }
             }
-            ValueExpr::Lit(v) => {
-                let ty = match **v {
-                    Lit::Null | Lit::Missing => self.bld.new_undefined(),
-                    Lit::Int8(_) | Lit::Int16(_) | Lit::Int32(_) | Lit::Int64(_) => {
-                        type_int!(self.bld)
+            ValueExpr::Lit(lit) => self.type_lit(lit),
+            ValueExpr::Parameter(_) | ValueExpr::GraphMatch(_) => type_dynamic!(self.bld),
+            ValueExpr::UnExpr(op, expr) => {
+                let ty = self.type_vexpr(expr);
+                match op {
+                    UnaryOp::Not => type_bool!(self.bld),
+                    UnaryOp::Pos | UnaryOp::Neg => {
+                        let zero = type_int!(self.bld);
+                        self.type_arithmetic(&ty, &zero);
+                        ty
                     }
-                    Lit::Decimal(_) => type_decimal!(self.bld),
-                    Lit::Double(_) => type_float64!(self.bld),
-                    Lit::Bool(_) => type_bool!(self.bld),
-                    Lit::String(_) => type_string!(self.bld),
-                    Lit::Variant(_, _) => type_dynamic!(self.bld), // TODO
-                    Lit::Struct(_) => type_struct!(self.bld),
-                    Lit::Bag(_) => type_bag!(self.bld),
-                    Lit::List(_) => type_array!(self.bld),
-                };
+                }
+            }
+            ValueExpr::BinaryExpr(op, lhs, rhs) => {
+                let (lhs, rhs) = (self.type_vexpr(lhs), self.type_vexpr(rhs));
+                match op {
+                    BinaryOp::And
+                    | BinaryOp::Or
+                    | BinaryOp::Eq
+                    | BinaryOp::Neq
+                    | BinaryOp::Gt
+                    | BinaryOp::Gteq
+                    | BinaryOp::Lt
+                    | BinaryOp::Lteq
+                    | BinaryOp::In => type_bool!(self.bld),
+                    BinaryOp::Concat => type_string!(self.bld),
+                    BinaryOp::Add
+                    | BinaryOp::Sub
+                    | BinaryOp::Mul
+                    | BinaryOp::Div
+                    | BinaryOp::Mod
+                    | BinaryOp::Exp => self.type_arithmetic(&lhs, &rhs),
+                }
+            }
+            ValueExpr::TupleExpr(TupleExpr { attrs, values }) => {
+                let mut fields = IndexSet::new();
+                let mut closed = true;
+                for (attr, value) in attrs.iter().zip(values) {
+                    self.type_vexpr(attr);
+                    let ty = self.type_vexpr(value);
+                    match as_string_lit(attr) {
+                        // an attribute with a `MISSING` value is not part of the tuple
+                        Some(_) if ty.is_undefined() => {}
+                        Some(name) => {
+                            fields.insert(StructField::new(name, ty));
+                        }
+                        None => closed = false,
+                    }
+                }
+                self.new_struct(fields, closed)
+            }
+            ValueExpr::ListExpr(partiql_logical::ListExpr { elements }) => {
+                let element = self.elements_type(elements);
+                self.bld.new_array(ArrayType::new(Box::new(element)))
+            }
+            ValueExpr::BagExpr(partiql_logical::BagExpr { elements }) => {
+                let element = self.elements_type(elements);
+                self.bld.new_bag(BagType::new(Box::new(element)))
+            }
+            ValueExpr::BetweenExpr(partiql_logical::BetweenExpr { value, from, to }) => {
+                for expr in [value, from, to] {
+                    self.type_vexpr(expr);
+                }
+                type_bool!(self.bld)
+            }
+            ValueExpr::PatternMatchExpr(PatternMatchExpr { value, pattern }) => {
+                self.type_vexpr(value);
+                if let Pattern::LikeNonStringNonLiteral(pattern) = pattern {
+                    self.type_vexpr(&pattern.pattern);
+                    self.type_vexpr(&pattern.escape);
+                }
+                type_bool!(self.bld)
+            }
+            ValueExpr::IsTypeExpr(partiql_logical::IsTypeExpr { expr, .. }) => {
+                self.type_vexpr(expr);
+                type_bool!(self.bld)
+            }
+            ValueExpr::SubQueryExpr(partiql_logical::SubQueryExpr { plan }) => {
+                self.type_sub_plan(plan)
+            }
+            ValueExpr::SimpleCase(SimpleCase {
+                expr,
+                cases,
+                default,
+            }) => {
+                self.type_vexpr(expr);
+                self.type_case(cases, default)
+            }
+            ValueExpr::SearchedCase(SearchedCase { cases, default }) => {
+                self.type_case(cases, default)
+            }
+            ValueExpr::CastExpr(CastExpr {
+                kind,
+                expr,
+                cast_type,
+            }) => {
+                self.type_vexpr(expr);
+                match kind {
+                    CastKind::Cast => self.type_of(cast_type),
+                    CastKind::CanCast | CastKind::CanLosslessCast => type_bool!(self.bld),
+                }
+            }
+            ValueExpr::NullIfExpr(NullIfExpr { lhs, rhs }) => {
+                self.type_vexpr(rhs);
+                self.type_vexpr(lhs)
+            }
+            ValueExpr::CoalesceExpr(CoalesceExpr { elements }) => {
+                let types: Vec<_> = elements.iter().map(|e| self.type_vexpr(e)).collect();
+                self.union_of(types)
+            }
+            ValueExpr::Call(partiql_logical::CallExpr { name, arguments }) => {
+                let args: Vec<_> = arguments.iter().map(|arg| self.type_vexpr(arg)).collect();
+                self.type_call(name, &args)
+            }
+        }
+    }
+
+    /// Types a lookup of a variable that is resolved at evaluation time, e.g., `a` in
+    /// `SELECT a FROM t`, which is either `t.a` or a global `a`.
+    fn type_dynamic_lookup(&mut self, lookups: &[ValueExpr]) -> PartiqlShape {
+        // As in evaluation, the first lookup that does not result in `MISSING` is used; lookups
+        // of variables that are not in scope are skipped.
+        let mut first = None;
+        for lookup in lookups {
+            if !self.is_in_scope(lookup) {
+                continue;
+            }
+            let errors_before = self.errors.len();
+            let ty = self.type_vexpr(lookup);
+            let errors = self.errors.split_off(errors_before);
+            if !ty.is_undefined() {
+                self.errors.extend(errors);
+                return ty;
+            }
+            first.get_or_insert((ty, errors));
+        }
+
+        match first {
+            Some((ty, errors)) => {
+                self.errors.extend(errors);
+                ty
+            }
+            None => type_dynamic!(self.bld),
+        }
+    }
 
-                let new_type_env = IndexMap::from([(string_to_sym("_1"), ty.clone())]);
-                self.type_env_stack.push(ty_ctx![(&new_type_env, &ty)]);
+    fn type_lit(&mut self, lit: &Lit) -> PartiqlShape {
+        match lit {
+            Lit::Null | Lit::Missing => self.bld.new_undefined(),
+            Lit::Int8(_) | Lit::Int16(_) | Lit::Int32(_) | Lit::Int64(_) => {
+                type_int!(self.bld)
+            }
+            Lit::Decimal(_) => type_decimal!(self.bld),
+            Lit::Double(_) => type_float64!(self.bld),
+            Lit::Bool(_) => type_bool!(self.bld),
+            Lit::String(_) => type_string!(self.bld),
+            Lit::Variant(_, _) => type_dynamic!(self.bld), // TODO
+            Lit::Struct(attrs) => {
+                let fields = attrs
+                    .iter()
+                    .map(|(name, lit)| StructField::new(name, self.type_lit(lit)))
+                    .collect();
+                self.new_struct(fields, true)
             }
-            ValueExpr::DynamicLookup(v) => {
-                if v.is_empty() {
-                    self.errors.push(TypingError::IllegalState(format!(
-                        "Unexpected Empty DynamicLookup found: {:?}",
-                        &v
-                    )));
+            Lit::Bag(elements) => {
+                let types: Vec<_> = elements.iter().map(|lit| self.type_lit(lit)).collect();
+                let element = self.union_or_dynamic(types);
+                self.bld.new_bag(BagType::new(Box::new(element)))
+            }
+            Lit::List(elements) => {
+                let types: Vec<_> = elements.iter().map(|lit| self.type_lit(lit)).collect();
+                let element = self.union_or_dynamic(types);
+                self.bld.new_array(ArrayType::new(Box::new(element)))
+            }
+        }
+    }
+
+    fn type_case(
+        &mut self,
+        cases: &[(Box<ValueExpr>, Box<ValueExpr>)],
+        default: &Option<Box<ValueExpr>>,
+    ) -> PartiqlShape {
+        let mut types = vec![];
+        for (when, then) in cases {
+            self.type_vexpr(when);
+            types.push(self.type_vexpr(then));
+        }
+        if let Some(default) = default {
+            types.push(self.type_vexpr(default));
+        }
+        self.union_of(types)
+    }
+
+    /// The type of the result of an arithmetic operation on values of types `lhs` and `rhs`.
+    fn type_arithmetic(&mut self, lhs: &PartiqlShape, rhs: &PartiqlShape) -> PartiqlShape {
+        match (lhs, rhs) {
+            (PartiqlShape::Undefined, _) | (_, PartiqlShape::Undefined) => self.bld.new_undefined(),
+            (PartiqlShape::Static(l), PartiqlShape::Static(r)) => {
+                match (numeric_rank(l.ty()), numeric_rank(r.ty())) {
+                    (Some(l_rank), Some(r_rank)) if l_rank == r_rank && l.ty() != r.ty() => {
+                        // decimals of different precision or scale
+                        type_decimal!(self.bld)
+                    }
+                    (Some(l_rank), Some(r_rank)) if l_rank >= r_rank => lhs.clone(),
+                    (Some(_), Some(_)) => rhs.clone(),
+                    _ => self.type_absent(format!(
+                        "Expected numeric types for arithmetic operation but found {lhs:?} and {rhs:?}"
+                    )),
                 }
+            }
+            _ => type_dynamic!(self.bld),
+        }
+    }
 
-                // TODO for Typing we handle multiple lookups through `[LookupOrder]` hence using
-                // the first element. Remove this workaround once we remove DynamicLookup
-                let expr = &v[0];
-                self.type_vexpr(expr, self.lookup_order(expr));
+    /// The type of the result of a call of function `name` with arguments of types `args`.
+    fn type_call(&mut self, name: &CallName, args: &[PartiqlShape]) -> PartiqlShape {
+        let first_arg = args.first().cloned().unwrap_or(PartiqlShape::Dynamic);
+        match name {
+            CallName::Lower
+            | CallName::Upper
+            | CallName::LTrim
+            | CallName::BTrim
+            | CallName::RTrim
+            | CallName::Substring
+            | CallName::Overlay => type_string!(self.bld),
+            CallName::CharLength
+            | CallName::OctetLength
+            | CallName::BitLength
+            | CallName::Position
+            | CallName::Cardinality
+            | CallName::ExtractYear
+            | CallName::ExtractMonth
+            | CallName::ExtractDay
+            | CallName::ExtractHour
+            | CallName::ExtractMinute
+            | CallName::ExtractTimezoneHour
+            | CallName::ExtractTimezoneMinute
+            | CallName::CollCount(_) => type_int!(self.bld),
+            CallName::ExtractSecond | CallName::CollAvg(_) => type_decimal!(self.bld),
+            CallName::Exists | CallName::CollAny(_) | CallName::CollEvery(_) => {
+                type_bool!(self.bld)
+            }
+            CallName::Abs => first_arg,
+            CallName::Mod => match args {
+                [lhs, rhs] => self.type_arithmetic(lhs, rhs),
+                _ => type_dynamic!(self.bld),
+            },
+            CallName::CollMax(_) | CallName::CollMin(_) | CallName::CollSum(_) => {
+                self.element_type(&first_arg)
+            }
+            CallName::ByName(_) => type_dynamic!(self.bld),
+            CallName::ById(_, oid, overload_idx) => {
+                let func = self.catalog.get_function_by_id(*oid);
+                match func.as_ref().map(|func| func.entry()) {
+                    Some(FunctionEntryFunction::Scalar(overloads)) => overloads
+                        .get(*overload_idx)
+                        .map(|overload| overload.output.result_type(args))
+                        .unwrap_or(PartiqlShape::Dynamic),
+                    Some(FunctionEntryFunction::Table(_)) => self.bld.new_bag_of_dyn(),
+                    _ => type_dynamic!(self.bld),
+                }
             }
-            _ => self.errors.push(TypingError::NotYetImplemented(format!(
-                "Unsupported Value Expression: {:?}",
-                &v
-            ))),
         }
     }
 
-    fn sort(&self) -> Result<Vec<NodeIndex>, TypeErr> {
-        let graph = self.to_stable_graph()?;
+    /// The type of the result of aggregation function `func` over values of type `arg`.
+    fn type_agg(&mut self, func: &AggFunc, arg: &PartiqlShape) -> PartiqlShape {
+        match func {
+            AggFunc::AggAvg => type_decimal!(self.bld),
+            AggFunc::AggCount => type_int!(self.bld),
+            AggFunc::AggMax | AggFunc::AggMin | AggFunc::AggSum => arg.clone(),
+            AggFunc::AggAny | AggFunc::AggEvery => type_bool!(self.bld),
+            AggFunc::AggById(_, oid) => {
+                let func = self.catalog.get_function_by_id(*oid);
+                match func.as_ref().map(|func| func.entry()) {
+                    Some(FunctionEntryFunction::Aggregate(expr)) => expr.result_type(arg),
+                    _ => type_dynamic!(self.bld),
+                }
+            }
+        }
+    }
 
-        toposort(&graph, None).map_err(|e| TypeErr {
-            errors: vec![TypingError::IllegalState(format!(
-                "Malformed plan detected: {e:?}"
-            ))],
-            output: None,
-        })
+    /// The shape of the values of `PartiQL` type `ty`, e.g., of `CAST(a AS ty)`.
+    fn type_of(&mut self, ty: &Type) -> PartiqlShape {
+        match ty {
+            Type::NullType | Type::MissingType => self.bld.new_undefined(),
+            Type::BooleanType => type_bool!(self.bld),
+            Type::Integer2Type => type_int16!(self.bld),
+            Type::Integer4Type => type_int32!(self.bld),
+            Type::Integer8Type => type_int64!(self.bld),
+            Type::DecimalType(Some(precision), scale)
+            | Type::NumericType(Some(precision), scale) => {
+                let scale = scale.unwrap_or(0);
+                self.bld
+                    .new_static(Static::DecimalP(*precision as usize, scale as usize))
+            }
+            Type::DecimalType(None, _) | Type::NumericType(None, _) => type_decimal!(self.bld),
+            Type::RealType => type_float32!(self.bld),
+            Type::DoublePrecisionType => type_float64!(self.bld),
+            Type::CharacterType(_)
+            | Type::CharacterVaryingType(_)
+            | Type::StringType
+            | Type::SymbolType
+            | Type::ClobType => type_string!(self.bld),
+            Type::DateType
+            | Type::TimeType(_)
+            | Type::ZonedTimeType(_)
+            | Type::TimestampType(_)
+            | Type::ZonedTimestampType(_) => type_datetime!(self.bld),
+            Type::StructType | Type::TupleType => self.bld.new_struct_of_dyn(),
+            Type::ListType | Type::SexpType => self.bld.new_array_of_dyn(),
+            Type::BagType => self.bld.new_bag_of_dyn(),
+            Type::BlobType | Type::AnyType => type_dynamic!(self.bld),
+        }
     }
 
-    fn to_stable_graph(&self) -> Result<StableGraph<BindingsOp, u8>, TypeErr> {
-        let lg = &self.logical_plan;
-        let flows = lg.flows();
+    /// Types attribute `key` of a value of type `ty`, e.g., `b` in `a.b`.
+    fn type_key(&mut self, ty: &PartiqlShape, key: &BindingsName<'_>) -> PartiqlShape {
+        match self.attr_type(ty, key) {
+            Ok(ty) => ty,
+            Err(ty) => self.type_absent(format!(
+                "No Typing Information for {:?} in closed Schema {:?}",
+                binding_to_sym(key),
+                ty
+            )),
+        }
+    }
 
-        let mut graph: StableGraph<_, _> = Default::default();
-        let mut seen = FxHashMap::default();
+    /// The type of attribute `key` of values of type `ty`, or `Err` with the type of the values
+    /// that cannot have the attribute.
+    fn attr_type(
+        &mut self,
+        ty: &PartiqlShape,
+        key: &BindingsName<'_>,
+    ) -> Result<PartiqlShape, PartiqlShape> {
+        match ty {
+            PartiqlShape::Dynamic | PartiqlShape::Undefined => Ok(ty.clone()),
+            PartiqlShape::AnyOf(any_of) => {
+                let types: Vec<_> = any_of
+                    .types()
+                    .filter_map(|ty| self.attr_type(ty, key).ok())
+                    .collect();
+                if types.is_empty() {
+                    Err(ty.clone())
+                } else {
+                    Ok(self.union_of(types))
+                }
+            }
+            PartiqlShape::Static(s) => match s.ty() {
+                Static::Struct(s) => match s.fields().find(|f| name_matches(key, f.name())) {
+                    Some(field) => Ok(field.ty().clone()),
+                    None if s.is_partial() => Ok(PartiqlShape::Dynamic),
+                    None => Err(ty.clone()),
+                },
+                // TODO Revise this behavior once the following discussion is conclusive and spec. is
+                // in place: https://github.com/partiql/partiql-spec/discussions/65
+                Static::Bag(b) => self.attr_type(b.element_type(), key),
+                Static::Array(a) => self.attr_type(a.element_type(), key),
+                _ => Err(ty.clone()),
+            },
+        }
+    }
 
-        for (s, d, w) in flows {
-            let mut add_node = |op_id: &OpId| {
-                if let Some(logical_op) = lg.operator(*op_id) {
-                    Ok(*seen
-                        .entry(*op_id)
-                        .or_insert_with(|| graph.add_node(logical_op.clone())))
+    /// The type of the elements of arrays of type `ty`, e.g., of `a[0]`, or `Err` with the type of
+    /// the values that are not arrays.
+    fn index_type(&mut self, ty: &PartiqlShape) -> Result<PartiqlShape, PartiqlShape> {
+        match ty {
+            PartiqlShape::Dynamic | PartiqlShape::Undefined => Ok(ty.clone()),
+            PartiqlShape::AnyOf(any_of) => {
+                let types: Vec<_> = any_of
+                    .types()
+                    .filter_map(|ty| self.index_type(ty).ok())
+                    .collect();
+                if types.is_empty() {
+                    Err(ty.clone())
                 } else {
-                    Err(TypeErr {
-                        errors: vec![TypingError::IllegalState("Malformed IR".to_string())],
-                        output: None,
-                    })
+                    Ok(self.union_of(types))
                 }
-            };
+            }
+            PartiqlShape::Static(s) => match s.ty() {
+                Static::Array(a) => Ok(a.element_type().clone()),
+                _ => Err(ty.clone()),
+            },
+        }
+    }
 
-            let (s, d) = (add_node(s)?, add_node(d)?);
-            graph.add_edge(s, d, *w);
+    /// The type of any attribute or element of a value of type `ty`, e.g., of `a[b]`.
+    fn any_component_type(&mut self, ty: &PartiqlShape) -> PartiqlShape {
+        match ty {
+            PartiqlShape::Dynamic | PartiqlShape::Undefined => ty.clone(),
+            PartiqlShape::AnyOf(any_of) => {
+                let types: Vec<_> = any_of
+                    .types()
+                    .map(|ty| self.any_component_type(ty))
+                    .collect();
+                self.union_of(types)
+            }
+            PartiqlShape::Static(_) if ty.is_collection() => self.element_type(ty),
+            PartiqlShape::Static(_) => self.attr_values_type(ty),
         }
+    }
 
-        Ok(graph)
+    /// The type of the values of the attributes of values of type `ty`, e.g., of `v` in
+    /// `UNPIVOT a AS v`.
+    fn attr_values_type(&mut self, ty: &PartiqlShape) -> PartiqlShape {
+        match ty.expect_struct() {
+            Ok(s) if s.is_partial() => type_dynamic!(self.bld),
+            Ok(s) => {
+                let types: Vec<_> = s.fields().map(|f| f.ty().clone()).collect();
+                self.union_of(types)
+            }
+            Err(_) => ty.clone(),
+        }
     }
 
-    fn element_type<'a>(&'a mut self, ty: &'a PartiqlShape) -> PartiqlShape {
+    /// The type of the elements of collections of type `ty`; values that are not collections are
+    /// treated as collections of themselves, e.g., in `FROM`.
+    fn element_type(&mut self, ty: &PartiqlShape) -> PartiqlShape {
         match ty {
-            PartiqlShape::Dynamic => PartiqlShape::Dynamic,
+            PartiqlShape::Dynamic | PartiqlShape::Undefined => ty.clone(),
             PartiqlShape::Static(s) => match s.ty() {
                 Static::Bag(b) => b.element_type().clone(),
                 Static::Array(a) => a.element_type().clone(),
                 _ => ty.clone(),
             },
-            PartiqlShape::Undefined => {
-                todo!("type_undefined type in catalog")
-            }
-            PartiqlShape::AnyOf(_any_of) => ty.clone(),
-        }
-    }
-
-    fn retrieve_type_from_local_ctx(&mut self, key: &SymbolPrimitive) -> Option<PartiqlShape> {
-        let type_ctx = self.local_type_ctx();
-        let env = type_ctx.env().clone();
-        let derived_type = self.derived_type(&type_ctx);
-
-        if let Some(ty) = env.get(key) {
-            Some(ty.clone())
-        } else if let Ok(s) = derived_type.expect_struct() {
-            if s.is_partial() {
-                Some(PartiqlShape::Dynamic)
-            } else {
-                match &self.typing_mode {
-                    TypingMode::Permissive => Some(self.bld.new_undefined()),
-                    TypingMode::Strict => {
-                        self.errors.push(TypingError::TypeCheck(format!(
-                            "No Typing Information for {:?} in closed Schema {:?}",
-                            &key, &derived_type
-                        )));
-                        None
-                    }
-                }
+            PartiqlShape::AnyOf(any_of) => {
+                let types: Vec<_> = any_of.types().map(|ty| self.element_type(ty)).collect();
+                self.union_of(types)
             }
-        } else if derived_type.is_dynamic() {
-            Some(PartiqlShape::Dynamic)
-        } else {
-            self.errors.push(TypingError::IllegalState(format!(
-                "Illegal Derive Type {:?}",
-                &derived_type
-            )));
-            None
         }
     }
 
-    fn derived_type(&mut self, ty_ctx: &TypeEnvContext) -> PartiqlShape {
-        let ty = ty_ctx.derived_type();
-        ty.clone()
+    fn elements_type(&mut self, elements: &[ValueExpr]) -> PartiqlShape {
+        let types: Vec<_> = elements.iter().map(|e| self.type_vexpr(e)).collect();
+        self.union_or_dynamic(types)
     }
 
-    fn local_type_ctx(&mut self) -> TypeEnvContext {
-        let out = self
-            .type_env_stack
-            .last()
-            .ok_or_else(|| TypingError::IllegalState("Malformed TypeEnv stack".to_string()));
+    /// The type of an expression that results in `MISSING`, e.g., a reference to an attribute
+    /// that a closed struct does not have; this is an error when typing in `Strict` mode.
+    fn type_absent(&mut self, msg: String) -> PartiqlShape {
+        if let TypingMode::Strict = &self.typing_mode {
+            self.errors.push(TypingError::TypeCheck(msg));
+        }
+        self.bld.new_undefined()
+    }
 
-        match out {
-            Ok(out) => out.clone(),
-            Err(err) => {
-                self.errors.push(err);
-                TypeEnvContext::new()
+    /// The union of `types`, without duplicates and absent (i.e., `Undefined`) types.
+    fn union_of(&mut self, types: impl IntoIterator<Item = PartiqlShape>) -> PartiqlShape {
+        let mut union: Vec<PartiqlShape> = vec![];
+        for ty in types {
+            let members = match ty {
+                PartiqlShape::AnyOf(any_of) => any_of.types().cloned().collect(),
+                ty => vec![ty],
+            };
+            for ty in members {
+                if !ty.is_undefined() && !union.iter().any(|other| same_shape(other, &ty)) {
+                    union.push(ty);
+                }
             }
         }
+        self.bld.union_of(union)
     }
 
-    fn lookup_order(&self, v: &ValueExpr) -> LookupOrder {
-        match v {
-            ValueExpr::VarRef(_, varef_type) => match varef_type {
-                VarRefType::Global => GlobalLocal,
-                VarRefType::Local => LocalGlobal,
-            },
-            _ => match self.current_bindings_op {
-                Some(BindingsOp::Scan(_)) => GlobalLocal,
-                _ => LocalGlobal,
-            },
+    /// As [`Self::union_of`] but `Dynamic` for no `types`, e.g., for the elements of `[]`.
+    fn union_or_dynamic(&mut self, types: Vec<PartiqlShape>) -> PartiqlShape {
+        if types.is_empty() {
+            type_dynamic!(self.bld)
+        } else {
+            self.union_of(types)
         }
     }
 
-    fn resolve_global_then_local(&mut self, key: &SymbolPrimitive) -> PartiqlShape {
-        let ty = self.resolve_global(key);
-        match ty.is_undefined() {
-            true => self.resolve_local(key),
-            false => ty,
-        }
+    fn new_struct(&mut self, fields: IndexSet<StructField>, closed: bool) -> PartiqlShape {
+        self.bld.new_struct(StructType::new(IndexSet::from([
+            StructConstraint::Fields(fields),
+            StructConstraint::Open(!closed),
+        ])))
     }
 
-    fn resolve_local_then_global(&mut self, key: &SymbolPrimitive) -> PartiqlShape {
-        let ty = self.resolve_local(key);
-        match ty.is_undefined() {
-            true => self.resolve_global(key),
-            false => ty,
-        }
+    /// The type of the binding tuples of the variables in `env`.
+    fn bindings_type(&mut self, env: &LocalTypeEnv) -> PartiqlShape {
+        let fields = env
+            .iter()
+            .map(|(name, ty)| StructField::new(&name.value, ty.clone()))
+            .collect();
+        self.new_struct(fields, true)
     }
 
-    fn resolve_global(&mut self, key: &SymbolPrimitive) -> PartiqlShape {
-        if let Some(type_entry) = self.catalog.resolve_type(key.value.as_str()) {
-            let ty = self.element_type(type_entry.ty());
-            ty
+    fn collection_of(&mut self, element: PartiqlShape, ordered: bool) -> PartiqlShape {
+        if ordered {
+            self.bld.new_array(ArrayType::new(Box::new(element)))
         } else {
-            self.bld.new_undefined()
+            self.bld.new_bag(BagType::new(Box::new(element)))
         }
     }
 
-    fn resolve_local(&mut self, key: &SymbolPrimitive) -> PartiqlShape {
-        for type_ctx in self.type_env_stack.iter().rev() {
-            if let Some(ty) = type_ctx.env().get(key) {
-                return ty.clone();
+    /// The type of the value of an operator's output, e.g., of the result of a query.
+    fn output_type(&mut self, output: OpOutput) -> PartiqlShape {
+        match output {
+            OpOutput::Value(ty) => ty,
+            OpOutput::Bindings { env, ordered } => {
+                let ty = self.bindings_type(&env);
+                self.collection_of(ty, ordered)
             }
         }
+    }
 
-        self.bld.new_undefined()
+    /// Renames the attributes of the elements of collection type `ty` to `columns`, e.g., for
+    /// `WITH b (x, y) AS (SELECT a.c, a.d FROM a) ...`; elements which are not structs (e.g., the
+    /// rows of `VALUES (1, 'a')`) become structs of `columns`.
+    fn rename_columns(&mut self, ty: &PartiqlShape, columns: &[String]) -> PartiqlShape {
+        let element = self.element_type(ty);
+        let element = match element.expect_struct() {
+            Ok(s) => {
+                let fields = s
+                    .fields()
+                    .zip(columns)
+                    .map(|(field, name)| StructField::new(name, field.ty().clone()))
+                    .collect();
+                self.new_struct(fields, s.is_closed())
+            }
+            Err(_) => {
+                let fields = columns
+                    .iter()
+                    .map(|name| StructField::new(name, PartiqlShape::Dynamic))
+                    .collect();
+                self.new_struct(fields, false)
+            }
+        };
+        self.collection_of(element, ty.is_ordered_collection())
     }
 
-    fn type_with_type_undefined(&mut self, key: &SymbolPrimitive) {
-        if let TypingMode::Permissive = &self.typing_mode {
-            // TODO Revise this once the following discussion is conclusive and spec. is
-            // in place: https://github.com/partiql/partiql-spec/discussions/64
-            let type_ctx = ty_ctx![(
-                &ty_env![(key.clone(), self.bld.new_undefined())],
-                &self.bld.new_undefined()
-            )];
+    /// Types `f` with the variables of `env` in scope.
+    fn in_scope<T>(&mut self, env: &LocalTypeEnv, f: impl FnOnce(&mut Self) -> T) -> T {
+        let derived_type = self.bindings_type(env);
+        self.type_env_stack.push(ty_ctx![(env, &derived_type)]);
+        let result = f(self);
+        self.type_env_stack.pop();
+        result
+    }
 
-            self.type_env_stack.push(type_ctx);
+    /// Whether the variable `v` refers to, if any, is in scope, e.g., for `a.b`, whether `a` is.
+    fn is_in_scope(&self, v: &ValueExpr) -> bool {
+        match v {
+            ValueExpr::VarRef(name, ref_type) => self.resolve_var(name, ref_type).is_some(),
+            ValueExpr::Path(v, _) => self.is_in_scope(v),
+            ValueExpr::DynamicLookup(lookups) => lookups.iter().any(|v| self.is_in_scope(v)),
+            _ => true,
         }
     }
 
-    // A helper function to extract one type out of the environment when we expect it.
-    // E.g., in projections, when we expect to infer one type from the project list items.
-    fn get_singleton_type_from_env(&mut self) -> PartiqlShape {
-        let ctx = self.local_type_ctx();
-        let env = ctx.env();
-        if env.len() != 1 {
-            self.errors.push(TypingError::IllegalState(format!(
-                "Unexpected Typing Environment; expected typing environment with only one type but found {:?} types",
-                &env.len()
-            )));
-            self.bld.new_undefined()
-        } else {
-            env[0].clone()
+    fn resolve_var(&self, name: &BindingsName<'_>, ref_type: &VarRefType) -> Option<PartiqlShape> {
+        match ref_type {
+            VarRefType::Global => self
+                .resolve_global(name)
+                .or_else(|| self.resolve_local(name)),
+            VarRefType::Local => self
+                .resolve_local(name)
+                .or_else(|| self.resolve_global(name)),
         }
     }
 
-    fn type_varef(&mut self, key: &SymbolPrimitive, ty: &PartiqlShape) {
-        if ty.is_undefined() {
-            self.type_with_type_undefined(key);
-        } else {
-            let mut new_type_env = LocalTypeEnv::new();
-            if let Ok(s) = ty.expect_struct() {
-                for b in to_bindings(&s) {
-                    new_type_env.insert(b.0, b.1);
-                }
+    fn resolve_global(&self, name: &BindingsName<'_>) -> Option<PartiqlShape> {
+        let cte = self
+            .ctes
+            .iter()
+            .rev()
+            .find(|(cte, _)| name_matches(name, cte))
+            .map(|(_, ty)| ty.clone());
+        cte.or_else(|| {
+            self.catalog
+                .resolve_type(binding_to_sym(name).value.as_str())
+                .map(|entry| entry.ty().clone())
+        })
+    }
+
+    fn resolve_local(&self, name: &BindingsName<'_>) -> Option<PartiqlShape> {
+        self.type_env_stack.iter().rev().find_map(|type_ctx| {
+            type_ctx
+                .env()
+                .iter()
+                .find(|(var, _)| name_matches(name, &var.value))
+                .map(|(_, ty)| ty.clone())
+        })
+    }
+}
 
-                let type_ctx = ty_ctx![(&new_type_env, ty)];
-                self.type_env_stack.push(type_ctx);
-            } else {
-                new_type_env.insert(key.clone(), ty.clone());
-                let type_ctx = ty_ctx![(&new_type_env, ty)];
-                self.type_env_stack.push(type_ctx);
+fn to_stable_graph(lg: &LogicalPlan<BindingsOp>) -> Result<StableGraph<BindingsOp, u8>, TypeErr> {
+    let mut graph: StableGraph<_, _> = Default::default();
+    let mut nodes = FxHashMap::default();
+    for (op_id, op) in lg.operators_by_id() {
+        nodes.insert(op_id, graph.add_node(op.clone()));
+    }
+
+    for (s, d, w) in lg.flows() {
+        match (nodes.get(s), nodes.get(d)) {
+            (Some(s), Some(d)) => {
+                graph.add_edge(*s, *d, *w);
+            }
+            _ => {
+                return Err(TypeErr {
+                    errors: vec![TypingError::IllegalState("Malformed IR".to_string())],
+                    output: None,
+                })
             }
         }
     }
+
+    Ok(graph)
+}
+
+fn bindings_env(output: &OpOutput) -> LocalTypeEnv {
+    match output {
+        OpOutput::Bindings { env, .. } => env.clone(),
+        OpOutput::Value(_) => LocalTypeEnv::new(),
+    }
+}
+
+fn is_ordered(output: &OpOutput) -> bool {
+    match output {
+        OpOutput::Bindings { ordered, .. } => *ordered,
+        OpOutput::Value(ty) => ty.is_ordered_collection(),
+    }
+}
+
+fn as_string_lit(v: &ValueExpr) -> Option<&str> {
+    match v {
+        ValueExpr::Lit(lit) => match &**lit {
+            Lit::String(s) => Some(s.as_str()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The rank of numeric type `ty` in numeric type promotion, e.g., for `1 + 2.5`.
+fn numeric_rank(ty: &Static) -> Option<u8> {
+    match ty {
+        Static::Int8 => Some(0),
+        Static::Int16 => Some(1),
+        Static::Int32 => Some(2),
+        Static::Int64 => Some(3),
+        Static::Int => Some(4),
+        Static::Decimal | Static::DecimalP(_, _) => Some(5),
+        Static::Float32 => Some(6),
+        Static::Float64 => Some(7),
+        _ => None,
+    }
+}
+
+/// Whether `lhs` and `rhs` are the same shape, regardless of their ids.
+fn same_shape(lhs: &PartiqlShape, rhs: &PartiqlShape) -> bool {
+    match (lhs, rhs) {
+        (PartiqlShape::Dynamic, PartiqlShape::Dynamic)
+        | (PartiqlShape::Undefined, PartiqlShape::Undefined) => true,
+        (PartiqlShape::AnyOf(lhs), PartiqlShape::AnyOf(rhs)) => {
+            lhs.types().count() == rhs.types().count()
+                && lhs.types().zip(rhs.types()).all(|(l, r)| same_shape(l, r))
+        }
+        (PartiqlShape::Static(lhs), PartiqlShape::Static(rhs)) => {
+            lhs.is_nullable() == rhs.is_nullable()
+                && match (lhs.ty(), rhs.ty()) {
+                    (Static::Struct(lhs), Static::Struct(rhs)) => {
+                        lhs.is_closed() == rhs.is_closed()
+                            && lhs.fields().count() == rhs.fields().count()
+                            && lhs.fields().zip(rhs.fields()).all(|(l, r)| {
+                                l.name() == r.name()
+                                    && l.is_optional() == r.is_optional()
+                                    && same_shape(l.ty(), r.ty())
+                            })
+                    }
+                    (Static::Bag(lhs), Static::Bag(rhs)) => {
+                        same_shape(lhs.element_type(), rhs.element_type())
+                    }
+                    (Static::Array(lhs), Static::Array(rhs)) => {
+                        same_shape(lhs.element_type(), rhs.element_type())
+                    }
+                    (lhs, rhs) => lhs == rhs,
+                }
+        }
+        _ => false,
+    }
+}
+
+fn name_matches(name: &BindingsName<'_>, other: &str) -> bool {
+    match name {
+        BindingsName::CaseSensitive(name) => name == other,
+        BindingsName::CaseInsensitive(name) => UniCase::new(name.as_ref()) == UniCase::new(other),
+    }
+}
+
+fn binding_to_sym(binding: &BindingsName<'_>) -> SymbolPrimitive {
+    match binding {
+        BindingsName::CaseSensitive(s) => SymbolPrimitive {
+            value: s.to_string(),
+            case: CaseSensitivity::CaseSensitive,
+        },
+        BindingsName::CaseInsensitive(s) => SymbolPrimitive {
+            value: s.to_string(),
+            case: CaseSensitivity::CaseInsensitive,
+        },
+    }
 }
 
 fn string_to_sym(name: &str) -> SymbolPrimitive {
@@ -587,29 +1362,26 @@ fn string_to_sym(name: &str) -> SymbolPrimitive {
     }
 }
 
-fn to_bindings(s: &StructType) -> Vec<(SymbolPrimitive, PartiqlShape)> {
-    s.fields()
-        .map(|field| {
-            let sym = SymbolPrimitive {
-                value: field.name().to_string(),
-                case: CaseSensitivity::CaseInsensitive,
-            };
-
-            (sym, field.ty().clone())
-        })
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{logical, LogicalPlanner};
+    use assert_matches::assert_matches;
     use partiql_ast_passes::error::AstTransformationError;
-    use partiql_catalog::catalog::{MutableCatalog, PartiqlCatalog, SharedCatalog, TypeEnvEntry};
+    use partiql_catalog::call_defs::{CallSpecArg, ScalarFnCallDef, ScalarFnCallSpec};
+    use partiql_catalog::catalog::{
+        MutableCatalog, PartiqlCatalog, PartiqlSharedCatalog, SharedCatalog, TypeEnvEntry,
+    };
+    use partiql_catalog::context::SessionContext;
+    use partiql_catalog::scalar_fn::{
+        ScalarFnExpr, ScalarFnExprResult, ScalarFunction, SimpleScalarFunctionInfo,
+    };
     use partiql_parser::{Parsed, Parser};
     use partiql_types::{
-        struct_fields, PartiqlNoIdShapeBuilder, ShapeBuilderExtensions, StructType,
+        struct_fields, type_struct, PartiqlNoIdShapeBuilder, ShapeBuilderExtensions, StructType,
     };
+    use partiql_value::Value;
+    use std::borrow::Cow;
 
     #[test]
     fn simple_sfw() {
@@ -823,8 +1595,7 @@ mod tests {
             IndexSet::from([details_fields, StructConstraint::Open(false)])
         ];
 
-        let err1 = r#"No Typing Information for SymbolPrimitive { value: "details", case: CaseInsensitive } in closed Schema Static(StaticType { id: NodeId(0), ty: Struct(StructType { constraints: {Fields({StructField { optional: false, name: "age", ty: Static(StaticType { id: NodeId(0), ty: Int, nullable: true }) }}), Open(false)} }), nullable: true })"#;
-        let err2 = r"Illegal Derive Type Undefined";
+        let err1 = r#"No Typing Information for SymbolPrimitive { value: "bar", case: CaseInsensitive } in closed Schema Static(StaticType { id: NodeId(0), ty: Struct(StructType { constraints: {Fields({StructField { optional: false, name: "age", ty: Static(StaticType { id: NodeId(0), ty: Int, nullable: true }) }}), Open(false)} }), nullable: true })"#;
 
         assert_err(
             assert_query_typing(
@@ -842,13 +1613,233 @@ mod tests {
                 ),
                 vec![],
             ),
-            vec![
-                TypingError::TypeCheck(err1.to_string()),
-                TypingError::IllegalState(err2.to_string()),
-            ],
+            vec![TypingError::TypeCheck(err1.to_string())],
         );
     }
 
+    #[test]
+    fn sfw_clauses() {
+        let cases = [
+            (
+                "SELECT c.name FROM customers AS c WHERE c.age > 21",
+                "<<{name: String}>>",
+            ),
+            (
+                "SELECT c.name FROM customers AS c ORDER BY c.age LIMIT 2 OFFSET 1",
+                "[{name: String}]",
+            ),
+            ("SELECT VALUE c.age + 1 FROM customers AS c", "<<Int>>"),
+            (
+                "SELECT DISTINCT c.age * 1.5 AS x, c.age || '' AS y FROM customers AS c",
+                "<<{x: Decimal, y: String}>>",
+            ),
+            (
+                "SELECT * FROM customers AS c",
+                "<<{id: Int, name: String, age: Int, orders: [{total: Decimal}]}>>",
+            ),
+            (
+                "SELECT c.age, COUNT(*) AS n, AVG(c.id) AS a, MAX(c.name) AS m \
+                 FROM customers AS c GROUP BY c.age HAVING COUNT(*) > 1",
+                "<<{age: Int, n: Int, a: Decimal, m: String}>>",
+            ),
+            (
+                "SELECT a, g FROM customers AS c GROUP BY c.age AS a GROUP AS g",
+                "<<{a: Int, g: <<{c: {id: Int, name: String, age: Int, orders: [{total: Decimal}]}}>>}>>",
+            ),
+            (
+                "SELECT VALUE c.id FROM customers AS c UNION ALL SELECT VALUE c.name FROM customers AS c",
+                "<<AnyOf(Int, String)>>",
+            ),
+            ("PIVOT c.name AT c.id FROM customers AS c", "{...}"),
+            (
+                "SELECT c.name, a FROM customers AS c LET c.age + 1 AS a WHERE a > 21",
+                "<<{name: String, a: Int}>>",
+            ),
+            (
+                "SELECT c.orders[*].total AS t FROM customers AS c",
+                "<<{t: <<Decimal>>}>>",
+            ),
+        ];
+        for (query, expected) in cases {
+            assert_typed(TypingMode::Strict, query, expected);
+        }
+    }
+
+    #[test]
+    fn from_sources() {
+        let cases = [
+            (
+                "SELECT c.name, o.total FROM customers AS c, c.orders AS o",
+                "<<{name: String, total: Decimal}>>",
+            ),
+            (
+                "SELECT c.name, o.total FROM customers AS c LEFT JOIN c.orders AS o ON o.total > 10",
+                "<<{name: String, total: Decimal}>>",
+            ),
+            (
+                "SELECT k, v FROM customers AS c, UNPIVOT c AS v AT k",
+                "<<{k: String, v: AnyOf(Int, String, [{total: Decimal}])}>>",
+            ),
+            (
+                "SELECT c.name, t FROM customers AS c, (SELECT VALUE o.total FROM c.orders AS o) AS t",
+                "<<{name: String, t: Decimal}>>",
+            ),
+            (
+                "WITH adults AS (SELECT c.name FROM customers AS c WHERE c.age >= 18) \
+                 SELECT VALUE a.name FROM adults AS a",
+                "<<String>>",
+            ),
+            ("SELECT VALUE x FROM unknown AS x", "<<Dynamic>>"),
+        ];
+        for (query, expected) in cases {
+            assert_typed(TypingMode::Strict, query, expected);
+        }
+    }
+
+    #[test]
+    fn expressions() {
+        let cases = [
+            ("1 + 2.5", "Decimal"),
+            ("2 * CAST(1 AS DOUBLE)", "Float64"),
+            ("NOT TRUE", "Bool"),
+            ("1 BETWEEN 0 AND 2", "Bool"),
+            ("'a' LIKE 'b'", "Bool"),
+            ("1 IS INT", "Bool"),
+            ("CAST('1' AS INT)", "Int32"),
+            ("CAST(1 AS DECIMAL(5, 2))", "Decimal(5,2)"),
+            ("CAN_CAST('1' AS INT)", "Bool"),
+            ("[1, 'a', 2]", "[AnyOf(Int, String)]"),
+            ("<<1, 2>>", "<<Int>>"),
+            ("{'a': 1, 'b': MISSING}", "{a: Int}"),
+            ("CASE WHEN TRUE THEN 1 ELSE 'a' END", "AnyOf(Int, String)"),
+            ("COALESCE(NULL, 1)", "Int"),
+            ("NULLIF(1, 2)", "Int"),
+            ("UPPER('a')", "String"),
+            ("CHAR_LENGTH('a')", "Int"),
+            ("EXTRACT(SECOND FROM CAST('10:00:00' AS TIME))", "Decimal"),
+            ("COLL_MAX(<<1, 2>>)", "Int"),
+            ("COLL_AVG(<<1, 2>>)", "Decimal"),
+            ("?", "Dynamic"),
+        ];
+        for (query, expected) in cases {
+            assert_typed(TypingMode::Strict, query, expected);
+        }
+    }
+
+    #[test]
+    fn paths() {
+        let cases = [
+            (
+                "SELECT c.orders[0].total AS t FROM customers AS c",
+                "<<{t: Decimal}>>",
+            ),
+            (
+                "SELECT c.orders[c.id].total AS t FROM customers AS c",
+                "<<{t: Decimal}>>",
+            ),
+            (
+                "SELECT c['name'] AS n, c[c.name] AS v FROM customers AS c",
+                "<<{n: String, v: AnyOf(Int, String, [{total: Decimal}])}>>",
+            ),
+            (
+                "SELECT VALUE customers.orders FROM customers",
+                "<<[{total: Decimal}]>>",
+            ),
+        ];
+        for (query, expected) in cases {
+            assert_typed(TypingMode::Strict, query, expected);
+        }
+
+        let res = type_query_with_catalog(
+            TypingMode::Strict,
+            "SELECT c.name[0] AS x FROM customers AS c",
+            &orders_catalog(),
+        );
+        assert_matches!(res, Err(TypeErr { errors, .. }) if matches!(errors.as_slice(), [TypingError::TypeCheck(_)]));
+
+        assert_typed(
+            TypingMode::Permissive,
+            "SELECT c.name[0] AS x FROM customers AS c",
+            "<<{x: Undefined}>>",
+        );
+    }
+
+    #[test]
+    fn catalog_functions() {
+        #[derive(Debug, Clone)]
+        struct Identity;
+
+        impl ScalarFnExpr for Identity {
+            fn evaluate<'c>(
+                &self,
+                args: &[Cow<'_, Value>],
+                _ctx: &'c dyn SessionContext,
+            ) -> ScalarFnExprResult<'c> {
+                Ok(Cow::Owned(args[0].clone().into_owned()))
+            }
+
+            fn result_type(&self, args: &[PartiqlShape]) -> PartiqlShape {
+                args[0].clone()
+            }
+        }
+
+        let mut catalog = PartiqlCatalog::default();
+        let call_def = ScalarFnCallDef {
+            names: vec!["identity"],
+            overloads: vec![ScalarFnCallSpec {
+                input: vec![CallSpecArg::Positional],
+                output: Box::new(Identity),
+            }],
+        };
+        catalog
+            .add_scalar_function(ScalarFunction::new(Box::new(
+                SimpleScalarFunctionInfo::new(call_def),
+            )))
+            .expect("add function");
+        let catalog = catalog.to_shared_catalog();
+
+        let ty = type_query_with_catalog(TypingMode::Strict, "identity('a')", &catalog);
+        assert_eq!(ty.expect("type").to_string(), "String");
+    }
+
+    #[track_caller]
+    fn assert_typed(mode: TypingMode, query: &str, expected: &str) {
+        let ty = type_query_with_catalog(mode, query, &orders_catalog());
+        match ty {
+            Ok(ty) => assert_eq!(ty.to_string(), expected, "{query}"),
+            Err(err) => panic!("{query}: {err:?}"),
+        }
+    }
+
+    /// A catalog with a closed schema of `customers` with nested `orders`.
+    fn orders_catalog() -> PartiqlSharedCatalog {
+        let mut bld = PartiqlNoIdShapeBuilder::default();
+        let order = type_struct![
+            bld,
+            IndexSet::from([
+                struct_fields![("total", type_decimal!(bld))],
+                StructConstraint::Open(false)
+            ])
+        ];
+        let schema = create_customer_schema(
+            false,
+            [
+                StructField::new("id", type_int!(bld)),
+                StructField::new("name", type_string!(bld)),
+                StructField::new("age", type_int!(bld)),
+                StructField::new("orders", bld.new_array_of(order)),
+            ]
+            .into(),
+            &mut bld,
+        );
+
+        let mut catalog = PartiqlCatalog::default();
+        catalog
+            .add_type_entry(TypeEnvEntry::new("customers", &[], schema))
+            .expect("add type");
+        catalog.to_shared_catalog()
+    }
+
     fn assert_err(result: Result<(), TypeErr>, expected_errors: Vec<TypingError>) {
         match result {
             Ok(()) => {
@@ -922,12 +1913,20 @@ mod tests {
         let _oid = catalog.add_type_entry(type_env_entry);
         let catalog = catalog.to_shared_catalog();
 
+        type_query_with_catalog(mode, query, &catalog)
+    }
+
+    fn type_query_with_catalog(
+        mode: TypingMode,
+        query: &str,
+        catalog: &dyn SharedCatalog,
+    ) -> Result<PartiqlShape, TypeErr> {
         let parsed = parse(query);
-        let lg = lower(&parsed, &catalog).expect("Logical plan");
+        let lg = lower(&parsed, catalog).expect("Logical plan");
 
         let mut typer = match mode {
-            TypingMode::Permissive => PlanTyper::new_permissive(&catalog, &lg),
-            TypingMode::Strict => PlanTyper::new_strict(&catalog, &lg),
+            TypingMode::Permissive => PlanTyper::new_permissive(catalog, &lg),
+            TypingMode::Strict => PlanTyper::new_strict(catalog, &lg),
         };
 
         typer.type_plan()
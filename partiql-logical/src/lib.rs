@@ -19,6 +19,7 @@ pub mod graph;
 
 use ordered_float::OrderedFloat;
 use partiql_common::catalog::ObjectId;
+use partiql_common::syntax::location::{BytePosition, Location};
 use partiql_value::BindingsName;
 /// # Examples
 /// ```
@@ -33,6 +34,7 @@ use partiql_value::BindingsName;
 ///     expr: ValueExpr::VarRef(BindingsName::CaseInsensitive("data".into()), VarRefType::Global),
 ///     as_key: "v".to_string(),
 ///     at_key: None,
+///     columns: None,
 /// }));
 ///
 /// let va = ValueExpr::Path(
@@ -66,7 +68,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display, Formatter};
 
 /// Represents a `PartiQL` logical plan.
-#[derive(Debug, Clone, Eq, PartialEq, Default)]
+#[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LogicalPlan<T>
 where
@@ -75,8 +77,23 @@ where
     nodes: Vec<T>,
     /// Third argument indicates the branch number into the outgoing node.
     edges: Vec<(OpId, OpId, u8)>,
+    /// The spans of the query text from which operators were lowered, if known.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    locations: FxHashMap<OpId, Location<BytePosition>>,
 }
 
+// The locations of the operators are not part of the plan's identity.
+impl<T> PartialEq for LogicalPlan<T>
+where
+    T: Default + Debug + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.edges == other.edges
+    }
+}
+
+impl<T> Eq for LogicalPlan<T> where T: Default + Debug + Eq {}
+
 impl<T> LogicalPlan<T>
 where
     T: Default + Debug,
@@ -139,10 +156,17 @@ where
 
     #[inline]
     pub fn merge_plan(&mut self, other: Self) {
-        let LogicalPlan { nodes, edges } = other;
+        let LogicalPlan {
+            nodes,
+            edges,
+            mut locations,
+        } = other;
         let mut mapping = FxHashMap::default();
         for (old_id, op) in nodes.into_iter().enumerate().map(|(i, n)| (OpId(i + 1), n)) {
             let new_id = self.add_operator(op);
+            if let Some(location) = locations.remove(&old_id) {
+                self.set_location(new_id, location);
+            }
             mapping.insert(old_id, new_id);
         }
 
@@ -186,6 +210,17 @@ where
         self.nodes.get_mut(id.0 - 1)
     }
 
+    /// Records `location` as the span of the query text from which the operator `id` was lowered.
+    pub fn set_location(&mut self, id: OpId, location: Location<BytePosition>) {
+        self.locations.insert(id, location);
+    }
+
+    /// Returns the span of the query text from which the operator `id` was lowered, if known.
+    #[must_use]
+    pub fn location(&self, id: OpId) -> Option<&Location<BytePosition>> {
+        self.locations.get(&id)
+    }
+
     // TODO add DAG validation method.
 }
 
@@ -228,21 +263,37 @@ pub enum BindingsOp {
     Pivot(Pivot),
     Unpivot(Unpivot),
     Filter(Filter),
+    Let(Let),
     OrderBy(OrderBy),
     LimitOffset(LimitOffset),
     Join(Join),
     BagOp(BagOp),
     Project(Project),
-    ProjectAll(ProjectAllMode),
+    ProjectAll(ProjectAll),
     ProjectValue(ProjectValue),
     ExprQuery(ExprQuery),
     Distinct,
     GroupBy(GroupBy),
     Having(Having),
+    With(With),
+    Window(Window),
+    Exclude(Exclude),
+    Dml(Dml),
     #[default]
     Sink,
 }
 
+/// [`ProjectAll`] represents a `SELECT *` projection.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProjectAll {
+    pub mode: ProjectAllMode,
+    /// The join columns of the `USING` and `NATURAL` joins which produce the binding tuples; each
+    /// is projected once, ahead of the other attributes, and the attributes of the same name of the
+    /// joined variables are not projected.
+    pub join_columns: Vec<String>,
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ProjectAllMode {
@@ -262,6 +313,9 @@ pub struct Scan {
     pub expr: ValueExpr,
     pub as_key: String,
     pub at_key: Option<String>,
+    /// The names to which the elements (or attribute values) of each scanned value are bound by
+    /// position, e.g. `(x, y)` in `FROM t AS v (x, y)`.
+    pub columns: Option<Vec<String>>,
 }
 
 /// [`Pivot`] represents a PIVOT operator, e.g. `PIVOT sp.price AT sp."symbol` in
@@ -291,6 +345,14 @@ pub struct Filter {
     pub expr: ValueExpr,
 }
 
+/// [`Let`] extends each of its input binding tuples with the values of its expressions under
+/// their names, e.g. `LET t.a * 2 AS b` in `SELECT b FROM t LET t.a * 2 AS b WHERE b > 0`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Let {
+    pub bindings: Vec<(String, ValueExpr)>,
+}
+
 /// [`Having`] represents the having operator, e.g. `HAVING a = 10` in `SELECT b FROM t GROUP BY a, b HAVING a = 10`.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -365,6 +427,10 @@ pub struct Join {
     pub left: Box<BindingsOp>,
     pub right: Box<BindingsOp>,
     pub on: Option<ValueExpr>,
+    /// The join columns of a `USING` or `NATURAL` join, e.g. `id` in
+    /// `FROM tbl1 AS t1 JOIN tbl2 AS t2 USING (id)`, each bound in the joined binding tuples to
+    /// the value of its expression (i.e., the coalesced values of the column of either side).
+    pub using: Vec<(String, ValueExpr)>,
 }
 
 /// Represents join types.
@@ -378,12 +444,82 @@ pub enum JoinKind {
     Cross,
 }
 
+/// [`With`] represents a `WITH` clause together with the query it scopes, e.g.
+/// `WITH b AS (SELECT * FROM a) SELECT * FROM b`. Each common table expression is evaluated once, in
+/// order, and bound under its name while evaluating subsequent common table expressions and `body`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct With {
+    pub ctes: Vec<CommonTableExpr>,
+    pub body: LogicalPlan<BindingsOp>,
+}
+
+/// A single named element of a `WITH` clause, e.g. `b (x, y) AS (SELECT a.c, a.d FROM a)`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CommonTableExpr {
+    pub name: String,
+    /// Names positionally assigned to the attributes of the query's output tuples
+    pub columns: Option<Vec<String>>,
+    /// The query, or, for a recursive common table expression, its non-recursive (anchor) part
+    pub plan: LogicalPlan<BindingsOp>,
+    pub recursive: Option<RecursiveStep>,
+}
+
+/// The recursive part of a `WITH RECURSIVE` element, e.g. `SELECT ... FROM r ...` in
+/// `WITH RECURSIVE r AS (SELECT ... UNION ALL SELECT ... FROM r ...)`.
+///
+/// The recursive part is evaluated repeatedly with the common table expression's name bound to the
+/// rows produced by the previous iteration until no new rows are produced.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RecursiveStep {
+    pub plan: LogicalPlan<BindingsOp>,
+    pub setq: SetQuantifier,
+    pub search: Option<CteSearch>,
+    pub cycle: Option<CteCycle>,
+    /// The attribute under which each row produced by `plan` holds the row of the previous
+    /// iteration it derives from, from which its `SEARCH` and `CYCLE` columns are derived; set
+    /// only if either clause is given.
+    pub parent: Option<String>,
+}
+
+/// Represents `SEARCH {DEPTH | BREADTH} FIRST BY <by> SET <set>`
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CteSearch {
+    pub order: SearchOrder,
+    pub by: Vec<String>,
+    pub set: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SearchOrder {
+    DepthFirst,
+    BreadthFirst,
+}
+
+/// Represents `CYCLE <columns> SET <mark> TO <mark_value> DEFAULT <default_value> USING <path>`
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CteCycle {
+    pub columns: Vec<String>,
+    pub mark: String,
+    pub mark_value: Lit,
+    pub default_value: Lit,
+    pub path: String,
+}
+
 /// An SQL aggregation function call with its arguments
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct AggregateExpression {
     pub name: String,
     pub expr: ValueExpr,
+    /// The further arguments of a call of a catalog aggregation function (e.g., the delimiter of
+    /// `string_agg(x, ',')`), evaluated with each aggregated value.
+    pub args: Vec<ValueExpr>,
     pub func: AggFunc,
     pub setq: SetQuantifier,
 }
@@ -407,6 +543,109 @@ pub enum AggFunc {
     AggAny,
     /// Represents SQL's `EVERY` aggregation function
     AggEvery,
+    /// Represents an aggregation function registered in the catalog
+    AggById(String, ObjectId),
+}
+
+/// [`Window`] computes window functions over its input binding tuples, adding each function's
+/// result to the binding tuple under the function's `name`, e.g.
+/// `RANK() OVER (PARTITION BY a ORDER BY b)` in `SELECT RANK() OVER (PARTITION BY a ORDER BY b) FROM t`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Window {
+    pub exprs: Vec<WindowExpression>,
+}
+
+/// [`Exclude`] removes nested attributes and collection elements from its input binding tuples,
+/// e.g. `EXCLUDE t.customer.ssn, t.orders[*].card_number` in
+/// `SELECT * EXCLUDE t.customer.ssn, t.orders[*].card_number FROM t`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Exclude {
+    pub paths: Vec<ExcludePath>,
+}
+
+/// A path to the values removed by an [`Exclude`], starting at the binding `root`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExcludePath {
+    pub root: BindingsName<'static>,
+    pub steps: Vec<ExcludeStep>,
+}
+
+/// A step of an [`ExcludePath`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExcludeStep {
+    /// E.g. `b` in `t.a.b` or `t.a['b']`
+    Key(BindingsName<'static>),
+    /// E.g. 4 in `t.a[4]`
+    Index(i64),
+    /// E.g. `[*]` in `t.a[*].b`, i.e., every element of a list or bag
+    AllElements,
+    /// E.g. `.*` in `t.a.*.b`, i.e., every attribute of a tuple
+    AllFields,
+}
+
+/// A window function call with its arguments and window specification
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowExpression {
+    pub name: String,
+    pub func: WindowFunc,
+    pub args: Vec<ValueExpr>,
+    pub setq: SetQuantifier,
+    pub partition_by: Vec<ValueExpr>,
+    pub order_by: Vec<SortSpec>,
+    pub frame: WindowFrame,
+}
+
+/// SQL window function
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowFunc {
+    /// Represents SQL's `ROW_NUMBER` window function
+    RowNumber,
+    /// Represents SQL's `RANK` window function
+    Rank,
+    /// Represents SQL's `DENSE_RANK` window function
+    DenseRank,
+    /// Represents SQL's `LAG` window function
+    Lag,
+    /// Represents SQL's `LEAD` window function
+    Lead,
+    /// Represents SQL's `FIRST_VALUE` window function
+    FirstValue,
+    /// Represents SQL's `LAST_VALUE` window function
+    LastValue,
+    /// Represents an SQL aggregation function computed over the window frame
+    Agg(AggFunc),
+}
+
+/// The rows of a partition a window function is computed over, relative to the current row
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowFrame {
+    pub units: WindowFrameUnits,
+    pub start: WindowFrameBound,
+    pub end: WindowFrameBound,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowFrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
 }
 
 /// Represents `GROUP BY` <strategy> <`group_key`>[, <`group_key`>] ... \[AS <`as_alias`>\]
@@ -449,6 +688,82 @@ pub struct ExprQuery {
     pub expr: ValueExpr,
 }
 
+/// [`Dml`] modifies the collection it targets, e.g. `UPDATE t AS x SET x.a = 1 WHERE x.b > 0`,
+/// and outputs the rows of its `RETURNING` clause, if any.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dml {
+    pub target: DmlTarget,
+    pub op: DmlOp,
+    pub returning: Vec<ReturningElem>,
+}
+
+/// The collection modified by a [`Dml`], e.g. `t AS x` in `DELETE FROM t AS x WHERE x.a = 1`;
+/// each row of the collection is bound to `as_key` in the statement's expressions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DmlTarget {
+    pub name: BindingsName<'static>,
+    pub as_key: String,
+}
+
+/// The modification made by a [`Dml`]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DmlOp {
+    /// Represents `INSERT INTO <target> <values> [ON CONFLICT WHERE <on_conflict> DO NOTHING]`;
+    /// a value is not inserted if `on_conflict` holds for any existing row, with the new value
+    /// bound to `EXCLUDED`.
+    Insert {
+        values: ValueExpr,
+        on_conflict: Option<ValueExpr>,
+    },
+    /// Represents `UPDATE <target> SET ... | REMOVE ... [WHERE <filter>]`
+    Update {
+        filter: Option<ValueExpr>,
+        changes: Vec<UpdateChange>,
+    },
+    /// Represents `DELETE FROM <target> [WHERE <filter>]`
+    Delete { filter: Option<ValueExpr> },
+}
+
+/// A change to each row modified by an `UPDATE`, addressed by its path from the row.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UpdateChange {
+    /// E.g. `SET x.a.b = 1`
+    Set(Vec<PathComponent>, ValueExpr),
+    /// E.g. `REMOVE x.a[0]`
+    Remove(Vec<PathComponent>),
+}
+
+/// An element of a `RETURNING` clause, e.g. `MODIFIED NEW x.a` in `RETURNING MODIFIED NEW x.a`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReturningElem {
+    pub mapping: ReturningMapping,
+    pub column: ReturningColumn,
+}
+
+/// Which rows, and which version of those rows, a [`ReturningElem`] is computed over.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReturningMapping {
+    ModifiedNew,
+    ModifiedOld,
+    AllNew,
+    AllOld,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ReturningColumn {
+    /// E.g. `*` in `RETURNING ALL NEW *`
+    Wildcard,
+    /// An expression over the row, output under the name
+    Expr(String, ValueExpr),
+}
+
 /// Represents a `PartiQL` value expression. Evaluation of a [`ValueExpr`] leads to a `PartiQL` value as
 /// specified by [PartiQL Specification 2019](https://partiql.org/assets/PartiQL-Specification.pdf).
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -460,6 +775,7 @@ pub enum ValueExpr {
     DynamicLookup(Box<Vec<ValueExpr>>),
     Path(Box<ValueExpr>, Vec<PathComponent>),
     VarRef(BindingsName<'static>, VarRefType),
+    Parameter(Parameter),
     TupleExpr(TupleExpr),
     ListExpr(ListExpr),
     BagExpr(BagExpr),
@@ -469,6 +785,7 @@ pub enum ValueExpr {
     SimpleCase(SimpleCase),
     SearchedCase(SearchedCase),
     IsTypeExpr(IsTypeExpr),
+    CastExpr(CastExpr),
     NullIfExpr(NullIfExpr),
     CoalesceExpr(CoalesceExpr),
     Call(CallExpr),
@@ -540,6 +857,10 @@ pub enum PathComponent {
     Index(i64),
     KeyExpr(Box<ValueExpr>),
     IndexExpr(Box<ValueExpr>),
+    /// E.g. `[*]` in `a[*].b`, which navigates to each element of a collection
+    ForEach,
+    /// E.g. `.*` in `a.*.b`, which navigates to each attribute value of a tuple
+    Unpivot,
 }
 
 /// Represents a `PartiQL` tuple expression, e.g: `{ a.b: a.c * 2, 'count': a.c + 10}`.
@@ -678,6 +999,9 @@ pub struct IsTypeExpr {
 }
 
 /// Represents a `PartiQL` Type.
+///
+/// The optional parameters of a type are as given in a query, e.g. `Some(20)` for `VARCHAR(20)`
+/// and `None` for `VARCHAR`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Type {
@@ -686,21 +1010,30 @@ pub enum Type {
     Integer2Type,
     Integer4Type,
     Integer8Type,
-    DecimalType,
-    NumericType,
+    /// `DECIMAL` with an optional precision and scale
+    DecimalType(Option<u32>, Option<u32>),
+    /// `NUMERIC` with an optional precision and scale
+    NumericType(Option<u32>, Option<u32>),
     RealType,
     DoublePrecisionType,
-    TimestampType,
-    CharacterType,
-    CharacterVaryingType,
+    /// `TIMESTAMP` with an optional fractional seconds precision
+    TimestampType(Option<u32>),
+    /// `CHAR` with an optional length
+    CharacterType(Option<u32>),
+    /// `VARCHAR` with an optional maximum length
+    CharacterVaryingType(Option<u32>),
     MissingType,
     StringType,
     SymbolType,
     BlobType,
     ClobType,
     DateType,
-    TimeType,
-    ZonedTimestampType,
+    /// `TIME` with an optional fractional seconds precision
+    TimeType(Option<u32>),
+    /// `TIME WITH TIME ZONE` with an optional fractional seconds precision
+    ZonedTimeType(Option<u32>),
+    /// `TIMESTAMP WITH TIME ZONE` with an optional fractional seconds precision
+    ZonedTimestampType(Option<u32>),
     StructType,
     TupleType,
     ListType,
@@ -710,6 +1043,28 @@ pub enum Type {
     // TODO CustomType
 }
 
+/// Represents a `CAST`, `CAN_CAST` or `CAN_LOSSLESS_CAST` expression, e.g. `CAST(a AS INT)`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CastExpr {
+    pub kind: CastKind,
+    pub expr: Box<ValueExpr>,
+    pub cast_type: Type,
+}
+
+/// Represents the kind of a [`CastExpr`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CastKind {
+    /// `CAST(a AS t)`, which converts `a` to type `t`
+    Cast,
+    /// `CAN_CAST(a AS t)`, which is whether `a` can be converted to type `t`
+    CanCast,
+    /// `CAN_LOSSLESS_CAST(a AS t)`, which is whether `a` can be converted to type `t` and back
+    /// without a change of its value
+    CanLosslessCast,
+}
+
 /// Represents a `NULLIF` expression, e.g. `NULLIF(v1, v2)` in `SELECT NULLIF(v1, v2) FROM data`.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -780,6 +1135,25 @@ pub enum SetQuantifier {
     Distinct,
 }
 
+/// A query parameter, the value of which is bound when the query is executed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Parameter {
+    /// A positional parameter (i.e., `?`), numbered from 1 in order of appearance in the query.
+    Positional(usize),
+    /// A named parameter (e.g., `:name`); names are matched case-insensitively.
+    Named(String),
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Parameter::Positional(n) => write!(f, "?{n}"),
+            Parameter::Named(name) => write!(f, ":{name}"),
+        }
+    }
+}
+
 /// Indicates whether to look in the local/lexical or global environment when resolving a variable.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
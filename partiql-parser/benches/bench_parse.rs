@@ -49,7 +49,7 @@ const Q_COMPLEX_MATCH: &str = r#"
             "#;
 
 fn parse_bench(c: &mut Criterion) {
-    fn parse(text: &str) -> ParserResult {
+    fn parse(text: &str) -> ParserResult<'_> {
         Parser::default().parse(text)
     }
     c.bench_function("parse-simple", |b| b.iter(|| parse(black_box(Q_STAR))));
@@ -69,6 +69,19 @@ where
     IllegalState(String),
 }
 
+impl LexError<'_> {
+    /// Converts a `LexError` into one which owns all its data, detaching it from the input.
+    #[must_use]
+    pub fn into_owned(self) -> LexError<'static> {
+        match self {
+            LexError::InvalidInput(input) => LexError::InvalidInput(input.into_owned().into()),
+            LexError::UnterminatedDocLiteral => LexError::UnterminatedDocLiteral,
+            LexError::UnterminatedComment => LexError::UnterminatedComment,
+            LexError::Unknown => LexError::Unknown,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UnexpectedTokenData<'input> {
@@ -97,6 +110,27 @@ where
             _ => ParseError::IllegalState("Unhandled internal error".to_string()),
         }
     }
+
+    /// Converts a `ParseError` into one which owns all its data, detaching it from the input.
+    #[must_use]
+    pub fn into_owned(self) -> ParseError<'static, Loc> {
+        match self {
+            ParseError::SyntaxError(l) => ParseError::SyntaxError(l),
+            ParseError::UnexpectedEndOfInput(l) => ParseError::UnexpectedEndOfInput(l),
+            ParseError::Unknown(l) => ParseError::Unknown(l),
+            ParseError::UnexpectedToken(Located { inner, location }) => {
+                let inner = UnexpectedTokenData {
+                    token: inner.token.into_owned().into(),
+                };
+                ParseError::UnexpectedToken(Located { inner, location })
+            }
+            ParseError::LexicalError(Located { inner, location }) => {
+                let inner = inner.into_owned();
+                ParseError::LexicalError(Located { inner, location })
+            }
+            ParseError::IllegalState(s) => ParseError::IllegalState(s),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -149,4 +183,19 @@ mod tests {
         let e2 = e1.map_loc(|x| x);
         assert_eq!(e2.to_string(), "Illegal State: uh oh");
     }
+
+    #[test]
+    fn into_owned() {
+        let input = String::from("/");
+        let e1 = ParseError::UnexpectedToken(
+            UnexpectedTokenData {
+                token: input.as_str().into(),
+            }
+            .to_located(BytePosition(0.into())..ByteOffset::from(1).into()),
+        );
+
+        let e2: ParseError<'static, BytePosition> = e1.into_owned();
+        drop(input);
+        assert_eq!(e2.to_string(), "Unexpected token `/` at `(b0..b1)`");
+    }
 }
@@ -325,22 +325,36 @@ pub enum Token<'input> {
     At,
     #[regex("(?i:Between)")]
     Between,
+    #[regex("(?i:Breadth)", |lex| lex.slice())]
+    Breadth(&'input str),
     #[regex("(?i:By)")]
     By,
     #[regex("(?i:Case)")]
     Case,
     #[regex("(?i:Columns)")]
     Columns,
+    #[regex("(?i:Conflict)", |lex| lex.slice())]
+    Conflict(&'input str),
     #[regex("(?i:Cross)")]
     Cross,
+    #[regex("(?i:Current)", |lex| lex.slice())]
+    Current(&'input str),
     #[regex("(?i:Cycle)")]
     Cycle,
     #[regex("(?i:Date)")]
     Date,
+    #[regex("(?i:Default)", |lex| lex.slice())]
+    Default(&'input str),
+    #[regex("(?i:Delete)")]
+    Delete,
+    #[regex("(?i:Depth)", |lex| lex.slice())]
+    Depth(&'input str),
     #[regex("(?i:Desc)")]
     Desc,
     #[regex("(?i:Distinct)")]
     Distinct,
+    #[regex("(?i:Do)", |lex| lex.slice())]
+    Do(&'input str),
     #[regex("(?i:Element)")]
     Element,
     #[regex("(?i:Else)")]
@@ -359,6 +373,8 @@ pub enum Token<'input> {
     False,
     #[regex("(?i:First)")]
     First,
+    #[regex("(?i:Following)", |lex| lex.slice())]
+    Following(&'input str),
     #[regex("(?i:For)")]
     For,
     #[regex("(?i:Full)")]
@@ -375,10 +391,14 @@ pub enum Token<'input> {
     In,
     #[regex("(?i:Inner)")]
     Inner,
+    #[regex("(?i:Insert)")]
+    Insert,
     #[regex("(?i:Is)")]
     Is,
     #[regex("(?i:Intersect)")]
     Intersect,
+    #[regex("(?i:Into)")]
+    Into,
     #[regex("(?i:Join)")]
     Join,
     #[regex("(?i:Keep)")]
@@ -389,6 +409,8 @@ pub enum Token<'input> {
     Lateral,
     #[regex("(?i:Left)")]
     Left,
+    #[regex("(?i:Let)")]
+    Let,
     #[regex("(?i:Like)")]
     Like,
     #[regex("(?i:Limit)")]
@@ -397,18 +419,26 @@ pub enum Token<'input> {
     Match,
     #[regex("(?i:Missing)")]
     Missing,
+    #[regex("(?i:Modified)", |lex| lex.slice())]
+    Modified(&'input str),
     #[regex("(?i:Natural)")]
     Natural,
+    #[regex("(?i:New)", |lex| lex.slice())]
+    New(&'input str),
     #[regex("(?i:No)")]
     No,
     #[regex("(?i:Not)")]
     Not,
+    #[regex("(?i:Nothing)", |lex| lex.slice())]
+    Nothing(&'input str),
     #[regex("(?i:Null)")]
     Null,
     #[regex("(?i:Nulls)")]
     Nulls,
     #[regex("(?i:Offset)")]
     Offset,
+    #[regex("(?i:Old)", |lex| lex.slice())]
+    Old(&'input str),
     #[regex("(?i:On)")]
     On,
     #[regex("(?i:One)")]
@@ -419,24 +449,38 @@ pub enum Token<'input> {
     Order,
     #[regex("(?i:Outer)")]
     Outer,
+    #[regex("(?i:Over)", |lex| lex.slice())]
+    Over(&'input str),
     #[regex("(?i:Path)")]
     Path,
     #[regex("(?i:Partial)")]
     Partial,
+    #[regex("(?i:Partition)", |lex| lex.slice())]
+    Partition(&'input str),
     #[regex("(?i:Per)")]
     Per,
     #[regex("(?i:Pivot)")]
     Pivot,
+    #[regex("(?i:Preceding)", |lex| lex.slice())]
+    Preceding(&'input str),
     #[regex("(?i:Preserve)")]
     Preserve,
+    #[regex("(?i:Range)", |lex| lex.slice())]
+    Range(&'input str),
     #[regex("(?i:Recursive)")]
     Recursive,
     #[regex("(?i:REPEATABLE)")]
     Repeatable,
+    #[regex("(?i:Remove)", |lex| lex.slice())]
+    Remove(&'input str),
+    #[regex("(?i:Returning)")]
+    Returning,
     #[regex("(?i:Right)")]
     Right,
     #[regex("(?i:Row)")]
     Row,
+    #[regex("(?i:Rows)", |lex| lex.slice())]
+    Rows(&'input str),
     #[regex("(?i:Select)")]
     Select,
     #[regex("(?i:Search)")]
@@ -447,16 +491,24 @@ pub enum Token<'input> {
     Time,
     #[regex("(?i:Timestamp)")]
     Timestamp,
+    #[regex("(?i:Set)", |lex| lex.slice())]
+    Set(&'input str),
     #[regex("(?i:Simple)", |lex| lex.slice())]
     Simple(&'input str),
     #[regex("(?i:Then)")]
     Then,
+    #[regex("(?i:To)", |lex| lex.slice())]
+    To(&'input str),
     #[regex("(?i:True)")]
     True,
+    #[regex("(?i:Unbounded)", |lex| lex.slice())]
+    Unbounded(&'input str),
     #[regex("(?i:Union)")]
     Union,
     #[regex("(?i:Unpivot)")]
     Unpivot,
+    #[regex("(?i:Update)")]
+    Update,
     #[regex("(?i:Using)")]
     Using,
     #[regex("(?i:Value)")]
@@ -559,12 +611,60 @@ pub enum Token<'input> {
 impl Token<'_> {
     #[inline]
     pub fn is_var_non_reserved(&self) -> bool {
-        matches!(self, Token::Any(_) | Token::Simple(_)) || self.is_graph_non_reserved()
+        matches!(self, Token::Any(_) | Token::Simple(_))
+            || self.is_with_non_reserved()
+            || self.is_dml_non_reserved()
+            || self.is_window_non_reserved()
+            || self.is_graph_non_reserved()
     }
 
     #[inline]
     pub fn is_fn_non_reserved(&self) -> bool {
-        matches!(self, Token::Any(_) | Token::Simple(_)) || self.is_graph_non_reserved()
+        matches!(self, Token::Any(_) | Token::Simple(_))
+            || self.is_with_non_reserved()
+            || self.is_dml_non_reserved()
+            || self.is_window_non_reserved()
+            || self.is_graph_non_reserved()
+    }
+
+    /// Keywords of recursive `WITH` elements' `SEARCH` and `CYCLE` clauses
+    #[inline]
+    pub fn is_with_non_reserved(&self) -> bool {
+        matches!(
+            self,
+            Token::Breadth(_) | Token::Default(_) | Token::Depth(_) | Token::Set(_) | Token::To(_)
+        )
+    }
+
+    /// Keywords of DML statements' `ON CONFLICT`, `REMOVE` and `RETURNING` clauses
+    #[inline]
+    pub fn is_dml_non_reserved(&self) -> bool {
+        matches!(
+            self,
+            Token::Conflict(_)
+                | Token::Do(_)
+                | Token::Modified(_)
+                | Token::New(_)
+                | Token::Nothing(_)
+                | Token::Old(_)
+                | Token::Remove(_)
+        )
+    }
+
+    /// Keywords of window functions' `OVER` clauses
+    #[inline]
+    pub fn is_window_non_reserved(&self) -> bool {
+        matches!(
+            self,
+            Token::Current(_)
+                | Token::Following(_)
+                | Token::Over(_)
+                | Token::Partition(_)
+                | Token::Preceding(_)
+                | Token::Range(_)
+                | Token::Rows(_)
+                | Token::Unbounded(_)
+        )
     }
 
     #[inline]
@@ -635,6 +735,7 @@ impl Token<'_> {
                 | Token::Cross
                 | Token::Cycle
                 | Token::Date
+                | Token::Delete
                 | Token::Desc
                 | Token::Distinct
                 | Token::Element
@@ -649,13 +750,16 @@ impl Token<'_> {
                 | Token::Having
                 | Token::In
                 | Token::Inner
+                | Token::Insert
                 | Token::Is
                 | Token::Intersect
+                | Token::Into
                 | Token::Join
                 | Token::Keep
                 | Token::Last
                 | Token::Lateral
                 | Token::Left
+                | Token::Let
                 | Token::Like
                 | Token::Limit
                 | Token::Match
@@ -676,6 +780,7 @@ impl Token<'_> {
                 | Token::Right
                 | Token::Recursive
                 | Token::Repeatable
+                | Token::Returning
                 | Token::Search
                 | Token::Select
                 | Token::Table
@@ -687,12 +792,16 @@ impl Token<'_> {
                 | Token::Trail(_)
                 | Token::Union
                 | Token::Unpivot
+                | Token::Update
                 | Token::Using
                 | Token::Value
                 | Token::Values
                 | Token::Where
                 | Token::With
-        ) || self.is_graph_reserved()
+        ) || self.is_with_non_reserved()
+            || self.is_dml_non_reserved()
+            || self.is_window_non_reserved()
+            || self.is_graph_reserved()
             || self.is_graph_non_reserved()
     }
 }
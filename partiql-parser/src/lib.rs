@@ -61,7 +61,7 @@ mod token_parser;
 use parse::{parse_partiql, AstData, ErrorData};
 use partiql_ast::ast;
 use partiql_common::syntax::line_offset_tracker::LineOffsetTracker;
-use partiql_common::syntax::location::BytePosition;
+use partiql_common::syntax::location::{BytePosition, LineAndColumn};
 use partiql_common::syntax::metadata::LocationMap;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -72,6 +72,10 @@ pub type LexicalError<'input> = error::LexError<'input>;
 /// [`std::error::Error`] type for errors in the syntactic structure for the `PartiQL` parser.
 pub type ParseError<'input> = error::ParseError<'input, BytePosition>;
 
+/// [`std::error::Error`] type for errors in the syntactic structure for the `PartiQL` parser, located
+/// by line and column rather than by byte offset.
+pub type LineAndColumnParseError<'input> = error::ParseError<'input, LineAndColumn>;
+
 /// General [`Result`] type for the `PartiQL` [`Parser`].
 pub type ParserResult<'input> = Result<Parsed<'input>, ParserError<'input>>;
 
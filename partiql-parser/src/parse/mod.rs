@@ -536,6 +536,177 @@ mod tests {
                                   SELECT title FROM engineering.employees  -- T2"
             );
         }
+
+        #[test]
+        fn select_with_join_using_and_natural() {
+            parse!(r"SELECT * FROM a AS x JOIN b AS y USING (id)");
+            parse!(r"SELECT * FROM a AS x LEFT JOIN b AS y USING (id, k) JOIN c AS z USING (id)");
+            parse!(r"SELECT * FROM a AS x NATURAL JOIN b AS y");
+            parse!(r"SELECT * FROM a AS x NATURAL FULL OUTER JOIN b AS y");
+        }
+
+        #[test]
+        fn select_with_let() {
+            parse!(r"SELECT y FROM t LET t.a * 2 AS y WHERE y > 0");
+            parse!(r"SELECT y, z FROM t AS t LET t.a AS y, t.b || 'c' AS z GROUP BY y, z");
+            parse!(r"FROM t AS t LET t.a AS y WHERE y > 0 SELECT y");
+        }
+    }
+
+    mod with {
+        use super::*;
+
+        #[test]
+        fn with() {
+            parse!(r"WITH a AS (SELECT * FROM t) SELECT * FROM a");
+            parse!(
+                r"WITH a (x, y) AS (SELECT t.b, t.c FROM t), b AS (SELECT * FROM a) SELECT * FROM b"
+            );
+        }
+
+        #[test]
+        fn with_recursive() {
+            parse!(
+                r"WITH RECURSIVE r (n) AS (SELECT VALUE 1 UNION ALL SELECT r.n + 1 AS n FROM r WHERE r.n < 5)
+                  SELECT * FROM r"
+            );
+        }
+
+        #[test]
+        fn with_recursive_search_cycle() {
+            parse!(
+                r"WITH RECURSIVE sub AS (SELECT * FROM emp WHERE emp.mgr IS NULL UNION ALL SELECT e.* FROM emp AS e, sub AS s WHERE e.mgr = s.id)
+                  SEARCH DEPTH FIRST BY id SET ord
+                  SELECT * FROM sub"
+            );
+            parse!(
+                r"WITH RECURSIVE sub AS (SELECT * FROM emp WHERE emp.mgr IS NULL UNION ALL SELECT e.* FROM emp AS e, sub AS s WHERE e.mgr = s.id)
+                  SEARCH BREADTH FIRST BY id, name SET ord
+                  CYCLE id SET is_cycle TO 'Y' DEFAULT 'N' USING cycle_path
+                  SELECT * FROM sub"
+            );
+            parse!(
+                r"WITH RECURSIVE sub AS (SELECT * FROM emp WHERE emp.mgr IS NULL UNION ALL SELECT e.* FROM emp AS e, sub AS s WHERE e.mgr = s.id)
+                  CYCLE id SET is_cycle USING cycle_path
+                  SELECT * FROM sub"
+            );
+        }
+
+        #[test]
+        fn with_nested() {
+            parse!(r"SELECT * FROM (WITH a AS (SELECT * FROM t) SELECT * FROM a) AS x");
+            parse!(
+                r"SELECT * FROM t WHERE t.a IN (WITH a AS (SELECT * FROM t) SELECT VALUE a.b FROM a)"
+            );
+            parse!(r"INSERT INTO t WITH a AS (SELECT * FROM s) SELECT * FROM a");
+            parse!(
+                r"WITH a AS (SELECT * FROM t) SELECT * FROM (WITH b AS (SELECT * FROM a) SELECT * FROM b) AS x"
+            );
+        }
+    }
+
+    mod window {
+        use super::*;
+
+        #[test]
+        fn window_functions() {
+            parse!(r"SELECT ROW_NUMBER() OVER () FROM t");
+            parse!(
+                r"SELECT RANK() OVER (PARTITION BY t.a, t.b ORDER BY t.c DESC NULLS LAST) FROM t"
+            );
+            parse!(r"SELECT LAG(t.a, 2, 0) OVER (ORDER BY t.b) AS prev FROM t");
+            parse!(r"SELECT SUM(DISTINCT t.a) OVER (PARTITION BY t.b) FROM t");
+            parse!(r"SELECT COUNT(*) OVER (ORDER BY t.b) FROM t");
+        }
+
+        #[test]
+        fn window_frames() {
+            parse!(r"SELECT SUM(t.a) OVER (ORDER BY t.b ROWS UNBOUNDED PRECEDING) FROM t");
+            parse!(r"SELECT SUM(t.a) OVER (ORDER BY t.b ROWS 2 PRECEDING) FROM t");
+            parse!(
+                r"SELECT AVG(t.a) OVER (ORDER BY t.b ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) FROM t"
+            );
+            parse!(
+                r"SELECT LAST_VALUE(t.a) OVER (ORDER BY t.b RANGE BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) FROM t"
+            );
+        }
+    }
+
+    mod dml {
+        use super::*;
+
+        #[test]
+        fn insert() {
+            parse!(r"INSERT INTO t << {'a': 1}, {'a': 2} >>");
+            parse!(r"INSERT INTO t SELECT s.a FROM s WHERE s.b > 1");
+            parse!(r"INSERT INTO t VALUE {'a': 1} RETURNING MODIFIED NEW *");
+            parse!(r"INSERT INTO t VALUE {'a': 1} ON CONFLICT WHERE t.a = EXCLUDED.a DO NOTHING");
+        }
+
+        #[test]
+        fn update() {
+            parse!(r"UPDATE t SET a = 1");
+            parse!(r"UPDATE t AS x SET x.a = 1, x.b.c = x.a + 1 WHERE x.d > 0");
+            parse!(
+                r"UPDATE t AS x SET x.a = 1 REMOVE x.b[0] RETURNING ALL OLD x.a, MODIFIED NEW *"
+            );
+        }
+
+        #[test]
+        fn delete() {
+            parse!(r"DELETE FROM t");
+            parse!(r"DELETE FROM t AS x WHERE x.a = 1 RETURNING ALL OLD *");
+        }
+    }
+
+    mod parameters {
+        use super::*;
+        use partiql_ast::visit::{Traverse, Visit, Visitor};
+
+        #[test]
+        fn parameters() {
+            parse!(r"?");
+            parse!(r":name");
+            parse!(r"SELECT t.a FROM t WHERE t.b = ? AND t.c = :c");
+            parse!(r"SELECT * FROM t LIMIT ? OFFSET :skip");
+            parse!(r"{'a': ?, 'b': :b}");
+            parse!(r"[?, :b, ? + 1]");
+            parse!(r"SUBSTRING(:s FROM ? FOR ?)");
+        }
+
+        #[test]
+        fn positional_parameters_numbered_in_order() {
+            #[derive(Default)]
+            struct Params(Vec<ast::Parameter>);
+
+            impl<'ast> Visitor<'ast> for Params {
+                fn enter_parameter(&mut self, param: &'ast ast::Parameter) -> Traverse {
+                    self.0.push(param.clone());
+                    Traverse::Continue
+                }
+            }
+
+            let ast = parse!(r"SELECT ? AS a, :b AS b FROM t WHERE t.c = ? AND t.d = ?");
+            let mut params = Params::default();
+            ast.visit(&mut params);
+            let mut params = params.0;
+            params.sort_by_key(|p| match p {
+                ast::Parameter::Positional(n) => *n,
+                ast::Parameter::Named(_) => 0,
+            });
+            assert_eq!(
+                params,
+                vec![
+                    ast::Parameter::Named(ast::SymbolPrimitive {
+                        value: "b".to_string(),
+                        case: ast::CaseSensitivity::CaseInsensitive,
+                    }),
+                    ast::Parameter::Positional(1),
+                    ast::Parameter::Positional(2),
+                    ast::Parameter::Positional(3),
+                ]
+            );
+        }
     }
 
     mod set_ops {
@@ -692,6 +863,103 @@ mod tests {
             parse!(r"CAST(a AS TIME WITH TIME ZONE)");
             parse!(r"CAST(a AS TIME WITH TIME ZONE)");
             parse!(r"CAST(a AS TIME(20) WITH TIME ZONE)");
+            parse!(r"CAN_CAST(a AS DECIMAL(5, 2))");
+            parse!(r"can_lossless_cast(a AS INT)");
+        }
+
+        #[test]
+        fn cast_type_args() {
+            let type_arg = |q| {
+                let ast = parse!(q);
+                let ast::QuerySet::Expr(expr) = ast.node.query.node.set.node else {
+                    panic!("expected expression");
+                };
+                let ast::Expr::Call(call) = *expr else {
+                    panic!("expected call");
+                };
+                match &call.node.args[1].node {
+                    ast::CallArg::NamedType(ast::CallArgNamedType { ty, .. }) => ty.clone(),
+                    arg => panic!("expected type argument, found {arg:?}"),
+                }
+            };
+            let custom = |part| ast::Type::CustomType(ast::CustomType { parts: vec![part] });
+            let sym = |s: &str| ast::SymbolPrimitive {
+                value: s.to_string(),
+                case: ast::CaseSensitivity::CaseInsensitive,
+            };
+
+            assert_eq!(type_arg("CAST(a AS INT)"), ast::Type::Integer4Type);
+            assert_eq!(type_arg("CAN_CAST(a AS string)"), ast::Type::StringType);
+            assert_eq!(type_arg("CAST(a AS NULL)"), ast::Type::NullType);
+            assert_eq!(
+                type_arg("CAST(a AS DECIMAL(5, 2))"),
+                custom(ast::CustomTypePart::Parameterized(
+                    sym("DECIMAL"),
+                    vec![
+                        ast::CustomTypeParam::Lit(ast::Lit::Int64Lit(5)),
+                        ast::CustomTypeParam::Lit(ast::Lit::Int64Lit(2))
+                    ]
+                ))
+            );
+            assert_eq!(
+                type_arg("CAN_LOSSLESS_CAST(a AS DATE)"),
+                custom(ast::CustomTypePart::Name(sym("DATE")))
+            );
+        }
+
+        #[test]
+        fn is_type() {
+            let is_rhs = |q| {
+                let ast = parse!(q);
+                let ast::QuerySet::Expr(expr) = ast.node.query.node.set.node else {
+                    panic!("expected expression");
+                };
+                let ast::Expr::BinOp(is) = *expr else {
+                    panic!("expected `IS`");
+                };
+                *is.node.rhs
+            };
+            let is_type = |q| match is_rhs(q) {
+                ast::Expr::Type(ty) => ty.node,
+                rhs => panic!("expected type, found {rhs:?}"),
+            };
+            let sym = |s: &str| ast::SymbolPrimitive {
+                value: s.to_string(),
+                case: ast::CaseSensitivity::CaseInsensitive,
+            };
+
+            assert_eq!(is_type("a IS INT"), ast::Type::Integer4Type);
+            assert_eq!(is_type("a IS struct"), ast::Type::StructType);
+            assert_eq!(
+                is_type("a IS DECIMAL(10, 2)"),
+                ast::Type::CustomType(ast::CustomType {
+                    parts: vec![ast::CustomTypePart::Parameterized(
+                        sym("DECIMAL"),
+                        vec![
+                            ast::CustomTypeParam::Lit(ast::Lit::Int64Lit(10)),
+                            ast::CustomTypeParam::Lit(ast::Lit::Int64Lit(2))
+                        ]
+                    )]
+                })
+            );
+            assert_eq!(
+                is_type("a IS TIME(3) WITH TIME ZONE"),
+                ast::Type::CustomType(ast::CustomType {
+                    parts: vec![
+                        ast::CustomTypePart::Parameterized(
+                            sym("TIME"),
+                            vec![ast::CustomTypeParam::Lit(ast::Lit::Int64Lit(3))]
+                        ),
+                        ast::CustomTypePart::Name(sym("WITH")),
+                        ast::CustomTypePart::Name(sym("TIME")),
+                        ast::CustomTypePart::Name(sym("ZONE")),
+                    ]
+                })
+            );
+            parse!("a IS NOT TIMESTAMP");
+            parse!("a IS TIMESTAMP AND b IS NOT DATE");
+            assert!(matches!(is_rhs("a IS NULL"), ast::Expr::Lit(_)));
+            assert!(matches!(is_rhs("a IS b.c"), ast::Expr::Path(_)));
         }
 
         #[test]
@@ -750,6 +1018,18 @@ mod tests {
             parse!(r"pUbLIC");
             parse!(r"domain");
         }
+
+        #[test]
+        fn with_search_cycle_keywords() {
+            parse!(r"SELECT depth, breadth, set, to, default FROM t");
+            parse!(r"SELECT t.depth, set.to FROM t, set");
+        }
+
+        #[test]
+        fn window_keywords() {
+            parse!(r"SELECT over, partition, rows, range, current FROM t");
+            parse!(r"SELECT t.preceding, t.following, unbounded FROM t");
+        }
     }
 
     mod graph {
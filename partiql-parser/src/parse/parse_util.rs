@@ -4,7 +4,7 @@ use crate::parse::parser_state::ParserState;
 use crate::ParseError;
 use bitflags::bitflags;
 use partiql_ast::ast::{Expr, Lit};
-use partiql_common::node::NodeIdGenerator;
+use partiql_common::node::{IdAnnotated, NodeIdGenerator};
 use partiql_common::syntax::location::{ByteOffset, BytePosition};
 
 bitflags! {
@@ -102,6 +102,7 @@ pub(crate) fn strip_query(q: ast::AstNode<ast::Query>) -> ast::AstNode<ast::Quer
             ast::AstNode {
                 id: outer_id,
                 node: ast::Query {
+                    with: None,
                     set,
                     order_by: None,
                     limit_offset: None,
@@ -141,6 +142,7 @@ where
             inner_q
         } else {
             let query = ast::Query {
+                with: None,
                 set: ast::AstNode {
                     id: inner_id,
                     node: ast::QuerySet::Expr(q),
@@ -152,6 +154,7 @@ where
         }
     } else {
         let query = ast::Query {
+            with: None,
             set: qs,
             order_by: None,
             limit_offset: None,
@@ -167,6 +170,7 @@ pub(crate) fn strip_expr(q: ast::AstNode<ast::Query>) -> Box<ast::Expr> {
     if let ast::AstNode {
         node:
             ast::Query {
+                with: None,
                 set:
                     ast::AstNode {
                         node: ast::QuerySet::Expr(e),
@@ -241,3 +245,114 @@ pub(crate) fn expr_to_lit<'a>(expr: ast::Expr) -> LitFlattenResult<'a, ast::AstN
         _ => illegal_literal(),
     }
 }
+
+/// The functions whose `AS` argument is a type, e.g. `CAST(x AS INT)`.
+const CAST_FNS: [&str; 3] = ["cast", "can_cast", "can_lossless_cast"];
+
+/// Converts the `"as"` argument of a call to a cast function from an expression to a type.
+///
+/// The preprocessor rewrites e.g. `CAST(x AS VARCHAR(20))` to `cast(x, "as": VARCHAR(20))`, in
+/// which most type names can only be parsed as expressions (e.g., `INT` as a variable reference
+/// and `VARCHAR(20)` as a function call).
+pub(crate) fn cast_type_args(
+    func_name: &ast::SymbolPrimitive,
+    args: Vec<ast::AstNode<ast::CallArg>>,
+) -> Vec<ast::AstNode<ast::CallArg>> {
+    let is_cast = CAST_FNS
+        .iter()
+        .any(|name| func_name.value.eq_ignore_ascii_case(name));
+    if !is_cast {
+        return args;
+    }
+    args.into_iter()
+        .map(|arg| match arg.node {
+            ast::CallArg::Named(ast::CallArgNamed { name, value })
+                if name.value.eq_ignore_ascii_case("as") =>
+            {
+                let node = match expr_to_type(&value) {
+                    Some(ty) => ast::CallArg::NamedType(ast::CallArgNamedType { name, ty }),
+                    None => ast::CallArg::Named(ast::CallArgNamed { name, value }),
+                };
+                ast::AstNode { id: arg.id, node }
+            }
+            _ => arg,
+        })
+        .collect()
+}
+
+/// Converts an expression which is the name of a type (e.g., `INT`, `NULL` or `DECIMAL(5, 2)`)
+/// to that type.
+pub(crate) fn expr_to_type(expr: &ast::Expr) -> Option<ast::Type> {
+    match expr {
+        Expr::Lit(lit) => match lit.node {
+            Lit::Null => Some(ast::Type::NullType),
+            Lit::Missing => Some(ast::Type::MissingType),
+            _ => None,
+        },
+        Expr::VarRef(var) if var.qualifier == ast::ScopeQualifier::Unqualified => {
+            Some(type_name_to_type(&var.name))
+        }
+        Expr::Call(call) => {
+            let params = call
+                .args
+                .iter()
+                .map(|arg| match &arg.node {
+                    ast::CallArg::Positional(param) => match param.as_ref() {
+                        Expr::Lit(lit) => Some(ast::CustomTypeParam::Lit(lit.node.clone())),
+                        param => expr_to_type(param).map(ast::CustomTypeParam::Type),
+                    },
+                    ast::CallArg::PositionalType(ty) => {
+                        Some(ast::CustomTypeParam::Type(ty.clone()))
+                    }
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let part = ast::CustomTypePart::Parameterized(call.func_name.clone(), params);
+            Some(ast::Type::CustomType(ast::CustomType { parts: vec![part] }))
+        }
+        _ => None,
+    }
+}
+
+/// Converts the right-hand side of an `IS` predicate to a type if it is the name of one (e.g.,
+/// `INT` or `DECIMAL(5, 2)`); `NULL` and `MISSING` are left as literals.
+pub(crate) fn is_type_rhs(rhs: ast::Expr) -> ast::Expr {
+    let ty = match &rhs {
+        Expr::VarRef(_) | Expr::Call(_) => expr_to_type(&rhs),
+        _ => None,
+    };
+    match ty {
+        Some(ty) => Expr::Type(ast::AstNode {
+            id: rhs.id(),
+            node: ty,
+        }),
+        None => rhs,
+    }
+}
+
+fn type_name_to_type(name: &ast::SymbolPrimitive) -> ast::Type {
+    match name.value.to_lowercase().as_str() {
+        "bool" | "boolean" => ast::Type::BooleanType,
+        "smallint" | "int2" | "integer2" => ast::Type::Integer2Type,
+        "int" | "integer" | "int4" | "integer4" => ast::Type::Integer4Type,
+        "bigint" | "int8" | "integer8" => ast::Type::Integer8Type,
+        "decimal" | "dec" => ast::Type::DecimalType,
+        "numeric" => ast::Type::NumericType,
+        "real" => ast::Type::RealType,
+        "float" | "double" => ast::Type::DoublePrecisionType,
+        "char" | "character" => ast::Type::CharacterType,
+        "varchar" => ast::Type::CharacterVaryingType,
+        "string" => ast::Type::StringType,
+        "symbol" => ast::Type::SymbolType,
+        "blob" => ast::Type::BlobType,
+        "clob" => ast::Type::ClobType,
+        "struct" => ast::Type::StructType,
+        "tuple" => ast::Type::TupleType,
+        "list" => ast::Type::ListType,
+        "bag" => ast::Type::BagType,
+        "any" => ast::Type::AnyType,
+        _ => ast::Type::CustomType(ast::CustomType {
+            parts: vec![ast::CustomTypePart::Name(name.clone())],
+        }),
+    }
+}
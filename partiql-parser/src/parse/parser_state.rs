@@ -27,6 +27,8 @@ pub(crate) struct ParserState<'input, Id: NodeIdGenerator> {
     pub locations: LocationMap,
     /// Any errors accumulated during parse.
     pub errors: ParseErrors<'input>,
+    /// Number of positional parameters (i.e., `?`) encountered so far.
+    positional_params: usize,
 
     /// Pattern to match names of aggregate functions.
     aggregates_pat: &'static Regex,
@@ -53,6 +55,7 @@ where
             node_builder: AstNodeBuilder::new(id_gen),
             locations: LocationMap::with_capacity(INIT_LOCATIONS),
             errors: ParseErrors::default(),
+            positional_params: 0,
             aggregates_pat: &KNOWN_AGGREGATE_PATTERN,
         }
     }
@@ -75,6 +78,13 @@ impl<IdGen: NodeIdGenerator> ParserState<'_, IdGen> {
         self.create_node(ast, start.into()..end.into())
     }
 
+    /// Returns the (1-based) ordinal of the next positional parameter in the query.
+    #[inline]
+    pub fn next_positional_param(&mut self) -> usize {
+        self.positional_params += 1;
+        self.positional_params
+    }
+
     /// Check if a given `name` corresponds to a known aggregate function.
     #[inline]
     pub fn is_agg_fn(&self, name: &SymbolPrimitive) -> bool {
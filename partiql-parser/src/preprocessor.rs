@@ -63,7 +63,8 @@ mod built_ins {
     use regex::Regex;
 
     use FnExprArgMatch::{
-        AnyOne, AnyZeroOrMore as AnyStar, NamedArgId as Id, NamedArgKw as Kw, Synthesize as Syn,
+        AnyOne, AnyZeroOrMore as AnyStar, Match, NamedArgId as Id, NamedArgKw as Kw,
+        Synthesize as Syn,
     };
 
     const TRIM_SPECIFIER: &str = "(?i:leading)|(?i:trailing)|(?i:both)";
@@ -143,6 +144,24 @@ mod built_ins {
         }
     }
 
+    /// Calls of any other function (e.g., a user-defined aggregation function) which begin with a
+    /// set quantifier.
+    pub(crate) fn set_quantified() -> FnExpr<'static> {
+        FnExpr {
+            fn_names: vec![],
+            #[rustfmt::skip]
+            patterns: vec![
+                // e.g., median(all x) => median("all": x)
+                vec![Kw(Token::All), AnyOne(true), AnyStar(false)],
+                // e.g., median(distinct x) => median("distinct": x)
+                vec![Kw(Token::Distinct), AnyOne(true), AnyStar(false)],
+                // e.g., string_agg(distinct x, ',') => string_agg("distinct": x, ',')
+                vec![Kw(Token::All), AnyOne(true), AnyStar(false), Match(Token::Comma), AnyOne(true), AnyStar(false)],
+                vec![Kw(Token::Distinct), AnyOne(true), AnyStar(false), Match(Token::Comma), AnyOne(true), AnyStar(false)],
+            ],
+        }
+    }
+
     pub(crate) fn built_in_substring() -> FnExpr<'static> {
         FnExpr {
             fn_names: vec!["substring"],
@@ -160,7 +179,7 @@ mod built_ins {
 
     pub(crate) fn built_in_cast() -> FnExpr<'static> {
         FnExpr {
-            fn_names: vec!["cast"],
+            fn_names: vec!["cast", "can_cast", "can_lossless_cast"],
             #[rustfmt::skip]
             patterns: vec![
                 // e.g., cast(9 as VARCHAR(5)) => cast(9 "as": VARCHAR(5))
@@ -182,6 +201,8 @@ pub(crate) struct FnExprSet<'a> {
     fn_names: RegexSet,
     /// A union of multiple "function expression" matches
     fn_exprs: Vec<FnExpr<'a>>,
+    /// The "function expression" match for calls of other functions which begin with a set quantifier
+    set_quantified: FnExpr<'a>,
 }
 
 impl<'a> FnExprSet<'a> {
@@ -201,7 +222,11 @@ impl<'a> FnExprSet<'a> {
             .case_insensitive(true)
             .build()
             .unwrap();
-        FnExprSet { fn_names, fn_exprs }
+        FnExprSet {
+            fn_names,
+            fn_exprs,
+            set_quantified: built_ins::set_quantified(),
+        }
     }
 
     /// Find the [`FnExpr`] corresponding to a given function name, if it exists.
@@ -330,7 +355,7 @@ where
 
         if let Some(id) = fn_candidate {
             if let Some(((_, Token::OpenParen, _), _)) = self.parser.peek_n(next_idx) {
-                if let Some(fn_expr) = self.fn_exprs.find(id) {
+                if let Some(fn_expr) = self.find_fn_expr(id, next_idx) {
                     let replacement = match self.rewrite_fn_expr(fn_expr) {
                         Ok(rewrites) => rewrites,
                         Err(_err) => self.parser.flush().into_iter().map(|(t, _)| t).collect(),
@@ -343,6 +368,34 @@ where
         (tok, None)
     }
 
+    /// Find the [`FnExpr`] for a call of the function `name`, whose opening '(' is the `paren_idx`th
+    /// next [`Token`]. A call of any other function is rewritten only if it begins with a set
+    /// quantifier.
+    #[inline]
+    fn find_fn_expr(
+        &mut self,
+        name: &'input str,
+        paren_idx: usize,
+    ) -> Option<&'input FnExpr<'input>> {
+        let fn_exprs = self.fn_exprs;
+        fn_exprs.find(name).or_else(|| {
+            self.is_set_quantified_call(paren_idx)
+                .then_some(&fn_exprs.set_quantified)
+        })
+    }
+
+    /// `true` if the `paren_idx`th next [`Token`] is a '(' followed by `ALL` or `DISTINCT`.
+    #[inline]
+    fn is_set_quantified_call(&mut self, paren_idx: usize) -> bool {
+        matches!(
+            self.parser.peek_n(paren_idx),
+            Some(((_, Token::OpenParen, _), _))
+        ) && matches!(
+            self.parser.peek_n(paren_idx + 1),
+            Some(((_, Token::All | Token::Distinct, _), _))
+        )
+    }
+
     /// Parse and rewrite the [`Token`]s representing the specified function expression.
     fn rewrite_fn_expr(
         &mut self,
@@ -361,6 +414,7 @@ where
         let mut span: Option<Range<ByteOffset>> = None;
         while nesting > 0 && !patterns.is_empty() {
             let is_nested = nesting > 1;
+            let set_quantified_call = self.is_set_quantified_call(1);
             let next_tok = self.parser.peek_n(0);
             match &next_tok {
                 None => break,
@@ -391,7 +445,7 @@ where
                             self.parser.consume();
                         }
                         Token::UnquotedIdent(id) | Token::QuotedIdent(id)
-                            if self.fn_exprs.contains(id) =>
+                            if self.fn_exprs.contains(id) || set_quantified_call =>
                         {
                             let buffered: BufferedToken<'input> = (*buffered).clone();
                             // backup the state of the buffered tokens
@@ -986,6 +1040,20 @@ mod tests {
         let q_sum_star = r"sum(*)";
         assert_eq!(preprocess(q_sum_star)?, lex(q_sum_star)?);
 
+        assert_eq!(preprocess(r"median(a)")?, lex(r"median(a)")?);
+        assert_eq!(
+            preprocess(r"median(DISTINCT a)")?,
+            lex(r#"median("DISTINCT": a)"#)?
+        );
+        assert_eq!(
+            preprocess(r"string_agg(DISTINCT a, ', ')")?,
+            lex(r#"string_agg("DISTINCT": a, ', ')"#)?
+        );
+        assert_eq!(
+            preprocess(r"cast(median(all a) as INT)")?,
+            lex(r#"cast(median("all": a), "as": INT)"#)?
+        );
+
         assert_eq!(
             preprocess(r"COUNT(DISTINCT [1,1,1,1,2])")?,
             lex(r#"COUNT("DISTINCT" : [1,1,1,1,2])"#)?
@@ -1,10 +1,302 @@
 #![deny(rust_2018_idioms)]
 #![deny(clippy::all)]
 
+//! A rule-based rewriter of `PartiQL` [`LogicalPlan`]s.
+//!
+//! A [`Rewriter`] repeatedly applies a set of [`Rule`]s to a plan and the plans nested in it (e.g.,
+//! those of sub-queries) until none of them changes it further. [`Rewriter::default`] applies the
+//! rules of [`rules`]; custom rules may be added with [`Rewriter::with_rule`], either by
+//! implementing [`Rule`] to restructure the data flows of a plan or [`ExprRule`] to rewrite
+//! individual expressions.
+//!
+//! # Example
+//!
+//! ```
+//! use partiql_logical::{BindingsOp, Filter, Lit, LogicalPlan, ValueExpr};
+//! use partiql_rewriter::Rewriter;
+//!
+//! let mut plan: LogicalPlan<BindingsOp> = LogicalPlan::new();
+//! plan.add_operator(BindingsOp::Filter(Filter {
+//!     expr: ValueExpr::Lit(Box::new(Lit::Bool(true))),
+//! }));
+//!
+//! let rewritten = Rewriter::default().rewrite(plan);
+//! assert_eq!(rewritten.plan.operator_count(), 0);
+//! assert_eq!(rewritten.fired, vec!["merge_filters"]);
+//! ```
+
+use partiql_logical::{BindingsOp, LogicalPlan, ValueExpr};
+use std::fmt::{Debug, Formatter};
+
+pub mod plan;
+pub mod rules;
+mod visit;
+
+/// A rewrite of a [`LogicalPlan`].
+pub trait Rule: Send + Sync {
+    /// The name by which the rule is reported in [`Rewritten::fired`].
+    fn name(&self) -> &'static str;
+
+    /// Rewrites `plan` in place, returning whether it was changed.
+    ///
+    /// Rules are applied repeatedly until no rule changes the plan, so a rule must eventually
+    /// stop reporting changes for a plan it has already rewritten. Plans nested in the
+    /// expressions of `plan` are rewritten separately and need not be visited.
+    fn apply(&self, plan: &mut LogicalPlan<BindingsOp>) -> bool;
+}
+
+/// A rewrite of individual [`ValueExpr`]s.
+///
+/// An `ExprRule` is a [`Rule`] that visits every expression of every operator of a plan
+/// bottom-up, replacing each expression for which [`ExprRule::rewrite`] returns a replacement.
+pub trait ExprRule: Send + Sync {
+    /// The name by which the rule is reported in [`Rewritten::fired`].
+    fn name(&self) -> &'static str;
+
+    /// Returns the replacement of `expr`, or `None` if it should be left unchanged.
+    fn rewrite(&self, expr: &ValueExpr) -> Option<ValueExpr>;
+}
+
+impl<T: ExprRule> Rule for T {
+    fn name(&self) -> &'static str {
+        ExprRule::name(self)
+    }
+
+    fn apply(&self, plan: &mut LogicalPlan<BindingsOp>) -> bool {
+        fn rewrite_expr(rule: &dyn ExprRule, expr: &mut ValueExpr) -> bool {
+            let mut changed = false;
+            for child in visit::children_mut(expr) {
+                changed |= rewrite_expr(rule, child);
+            }
+            if let Some(replacement) = rule.rewrite(expr) {
+                *expr = replacement;
+                changed = true;
+            }
+            changed
+        }
+
+        let ids: Vec<_> = plan.operators_by_id().map(|(id, _)| id).collect();
+        let mut changed = false;
+        for id in ids {
+            let op = plan.operator_as_mut(id).expect("operator");
+            for expr in visit::op_exprs_mut(op) {
+                changed |= rewrite_expr(self, expr);
+            }
+        }
+        changed
+    }
+}
+
+/// The result of [`Rewriter::rewrite`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rewritten {
+    /// The rewritten plan.
+    pub plan: LogicalPlan<BindingsOp>,
+    /// The names of the rules that changed the plan, in the order in which they did so. A rule
+    /// is listed once for each plan it changed in each pass.
+    pub fired: Vec<&'static str>,
+}
+
+/// Applies a set of [`Rule`]s to a [`LogicalPlan`] until none of them changes it.
+pub struct Rewriter {
+    rules: Vec<Box<dyn Rule>>,
+    max_passes: usize,
+}
+
+impl Default for Rewriter {
+    /// Creates a `Rewriter` applying the rules of [`rules`].
+    fn default() -> Self {
+        Rewriter::new()
+            .with_rule(rules::ConstantFolding)
+            .with_rule(rules::MergeFilters)
+            .with_rule(rules::PredicatePushdown)
+            .with_rule(rules::ProjectionPruning)
+            .with_rule(rules::LimitPushdown)
+            .with_rule(rules::RemoveRedundantDistinct)
+    }
+}
+
+impl Debug for Rewriter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rewriter")
+            .field(
+                "rules",
+                &self
+                    .rules
+                    .iter()
+                    .map(|rule| rule.name())
+                    .collect::<Vec<_>>(),
+            )
+            .field("max_passes", &self.max_passes)
+            .finish()
+    }
+}
+
+impl Rewriter {
+    /// Creates a `Rewriter` without any rules.
+    #[must_use]
+    pub fn new() -> Self {
+        Rewriter {
+            rules: vec![],
+            max_passes: 32,
+        }
+    }
+
+    /// Adds `rule` to the rules applied by the rewriter, after those already added.
+    #[must_use]
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Sets the maximum number of passes over a plan; defaults to 32.
+    ///
+    /// Each pass applies every rule once to the plan and each plan nested in it. Rewriting stops
+    /// early once a pass leaves the plan unchanged.
+    #[must_use]
+    pub fn with_max_passes(mut self, max_passes: usize) -> Self {
+        self.max_passes = max_passes;
+        self
+    }
+
+    /// The names of the rules applied by the rewriter, in the order in which they are applied.
+    pub fn rules(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.rules.iter().map(|rule| rule.name())
+    }
+
+    /// Rewrites `plan` until a pass leaves it unchanged or the maximum number of passes is reached.
+    #[must_use]
+    pub fn rewrite(&self, mut plan: LogicalPlan<BindingsOp>) -> Rewritten {
+        let mut fired = vec![];
+        for _ in 0..self.max_passes {
+            if !self.pass(&mut plan, &mut fired) {
+                break;
+            }
+        }
+        Rewritten { plan, fired }
+    }
+
+    /// Applies every rule once to the plans nested in `plan` and then to `plan` itself.
+    fn pass(&self, plan: &mut LogicalPlan<BindingsOp>, fired: &mut Vec<&'static str>) -> bool {
+        let ids: Vec<_> = plan.operators_by_id().map(|(id, _)| id).collect();
+        let mut changed = false;
+        for id in ids {
+            let op = plan.operator_as_mut(id).expect("operator");
+            for subplan in visit::subplans_mut(op) {
+                changed |= self.pass(subplan, fired);
+            }
+        }
+
+        for rule in &self.rules {
+            if rule.apply(plan) {
+                fired.push(rule.name());
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use partiql_catalog::catalog::PartiqlCatalog;
+    use partiql_logical::{Filter, Lit};
+    use partiql_logical_planner::LogicalPlanner;
+    use partiql_parser::Parser;
+
+    pub(crate) fn lower(query: &str) -> LogicalPlan<BindingsOp> {
+        let catalog = PartiqlCatalog::default().to_shared_catalog();
+        let parsed = Parser::default().parse(query).expect("parse");
+        LogicalPlanner::new(&catalog).lower(&parsed).expect("lower")
+    }
+
+    /// Returns the operators along the single data flow ending in the output of `plan`, starting
+    /// from its first `FROM` source.
+    pub(crate) fn chain(plan: &LogicalPlan<BindingsOp>) -> Vec<&BindingsOp> {
+        let mut id = plan::output(plan);
+        let mut ops = vec![];
+        while let Some(op_id) = id {
+            ops.push(plan.operator(op_id).expect("operator"));
+            id = plan::inputs(plan, op_id).first().copied();
+        }
+        ops.reverse();
+        ops
+    }
+
+    struct TrueToFalse;
+
+    impl ExprRule for TrueToFalse {
+        fn name(&self) -> &'static str {
+            "true_to_false"
+        }
+
+        fn rewrite(&self, expr: &ValueExpr) -> Option<ValueExpr> {
+            match expr {
+                ValueExpr::Lit(lit) if **lit == Lit::Bool(true) => {
+                    Some(ValueExpr::Lit(Box::new(Lit::Bool(false))))
+                }
+                _ => None,
+            }
+        }
+    }
+
+    struct AlwaysChanges;
+
+    impl Rule for AlwaysChanges {
+        fn name(&self) -> &'static str {
+            "always_changes"
+        }
+
+        fn apply(&self, _plan: &mut LogicalPlan<BindingsOp>) -> bool {
+            true
+        }
+    }
+
     #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
+    fn custom_expr_rule() {
+        let plan = lower("SELECT VALUE y FROM (SELECT VALUE TRUE FROM b) AS y WHERE TRUE");
+        let rewritten = Rewriter::new().with_rule(TrueToFalse).rewrite(plan);
+        // once for the sub-query, once for the query
+        assert_eq!(rewritten.fired, vec!["true_to_false", "true_to_false"]);
+        assert!(!format!("{:?}", rewritten.plan).contains("Bool(true)"));
+    }
+
+    #[test]
+    fn max_passes() {
+        let plan = lower("SELECT VALUE x FROM a AS x");
+        let rewritten = Rewriter::new()
+            .with_rule(AlwaysChanges)
+            .with_max_passes(3)
+            .rewrite(plan.clone());
+        assert_eq!(rewritten.fired, vec!["always_changes"; 3]);
+        assert_eq!(rewritten.plan, plan);
+    }
+
+    #[test]
+    fn no_rules() {
+        let plan = lower("SELECT x.a FROM a AS x WHERE x.b > 1 + 2");
+        let rewritten = Rewriter::new().rewrite(plan.clone());
+        assert!(rewritten.fired.is_empty());
+        assert_eq!(rewritten.plan, plan);
+    }
+
+    #[test]
+    fn edit_flows() {
+        let original = lower("SELECT VALUE x FROM a AS x");
+        let mut plan = original.clone();
+        let project = plan::inputs(&plan, plan::output(&plan).unwrap())[0];
+        let scan = plan::sole_input(&plan, project).unwrap();
+
+        let filter = BindingsOp::Filter(Filter {
+            expr: ValueExpr::Lit(Box::new(Lit::Bool(true))),
+        });
+        let id = plan::insert_between(&mut plan, scan, project, filter.clone()).unwrap();
+        assert_eq!(plan::sole_input(&plan, project), Some(id));
+        assert_eq!(plan::sole_consumer(&plan, scan), Some(id));
+        assert!(plan::insert_between(&mut plan, scan, project, filter.clone()).is_none());
+
+        assert_eq!(plan::remove_operator(&mut plan, id), Some(filter));
+        assert_eq!(plan, original);
     }
 }
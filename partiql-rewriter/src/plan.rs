@@ -0,0 +1,159 @@
+//! Helpers for navigating and editing the data flows of a [`LogicalPlan`].
+//!
+//! Operators that are removed from a plan shift the [`OpId`]s of all operators added after them;
+//! rules that remove operators should not hold on to [`OpId`]s across such edits.
+
+use partiql_logical::{BindingsOp, LogicalPlan, OpId};
+use rustc_hash::FxHashMap;
+
+/// Returns the operators flowing into `id`, ordered by branch number.
+#[must_use]
+pub fn inputs(plan: &LogicalPlan<BindingsOp>, id: OpId) -> Vec<OpId> {
+    let mut inputs: Vec<_> = plan
+        .flows()
+        .iter()
+        .filter(|(_, dst, _)| *dst == id)
+        .map(|(src, _, branch)| (*branch, *src))
+        .collect();
+    inputs.sort_by_key(|(branch, _)| *branch);
+    inputs.into_iter().map(|(_, src)| src).collect()
+}
+
+/// Returns the operators that `id` flows into.
+#[must_use]
+pub fn consumers(plan: &LogicalPlan<BindingsOp>, id: OpId) -> Vec<OpId> {
+    plan.flows()
+        .iter()
+        .filter(|(src, _, _)| *src == id)
+        .map(|(_, dst, _)| *dst)
+        .collect()
+}
+
+/// Returns the operator flowing into `id` if there is exactly one.
+#[must_use]
+pub fn sole_input(plan: &LogicalPlan<BindingsOp>, id: OpId) -> Option<OpId> {
+    match inputs(plan, id).as_slice() {
+        [input] => Some(*input),
+        _ => None,
+    }
+}
+
+/// Returns the operator that `id` flows into if there is exactly one.
+#[must_use]
+pub fn sole_consumer(plan: &LogicalPlan<BindingsOp>, id: OpId) -> Option<OpId> {
+    match consumers(plan, id).as_slice() {
+        [consumer] => Some(*consumer),
+        _ => None,
+    }
+}
+
+/// Returns the operator producing the result of `plan`, i.e., its only operator that does not
+/// flow into another.
+#[must_use]
+pub fn output(plan: &LogicalPlan<BindingsOp>) -> Option<OpId> {
+    let mut outputs = plan
+        .operators_by_id()
+        .map(|(id, _)| id)
+        .filter(|id| !plan.flows().iter().any(|(src, _, _)| src == id));
+    match (outputs.next(), outputs.next()) {
+        (Some(output), None) => Some(output),
+        _ => None,
+    }
+}
+
+/// Removes the operator `id` from `plan`, connecting its input directly to its consumers.
+///
+/// Returns the removed operator, or `None` (leaving `plan` unchanged) if `id` has more than one
+/// input.
+pub fn remove_operator(plan: &mut LogicalPlan<BindingsOp>, id: OpId) -> Option<BindingsOp> {
+    let input = match inputs(plan, id).as_slice() {
+        [] => None,
+        [input] => Some(*input),
+        _ => return None,
+    };
+    let flows = plan
+        .flows()
+        .iter()
+        .filter_map(|&(src, dst, branch)| match (src == id, dst == id) {
+            (false, false) => Some((src, dst, branch)),
+            (true, _) => input.map(|input| (input, dst, branch)),
+            (false, true) => None,
+        })
+        .collect();
+    rebuild(plan, Some(id), flows)
+}
+
+/// Adds `op` to `plan` on the data flow from `src` into `dst`.
+///
+/// Returns the id of the new operator, or `None` (leaving `plan` unchanged) if `src` does not
+/// flow into `dst`.
+pub fn insert_between(
+    plan: &mut LogicalPlan<BindingsOp>,
+    src: OpId,
+    dst: OpId,
+    op: BindingsOp,
+) -> Option<OpId> {
+    let branch = plan
+        .flows()
+        .iter()
+        .find(|(s, d, _)| *s == src && *d == dst)
+        .map(|(_, _, branch)| *branch)?;
+    let id = plan.add_operator(op);
+    let flows = plan
+        .flows()
+        .iter()
+        .flat_map(|&(s, d, b)| match s == src && d == dst {
+            true => vec![(src, id, 0), (id, dst, branch)],
+            false => vec![(s, d, b)],
+        })
+        .collect();
+    rebuild(plan, None, flows);
+    Some(id)
+}
+
+/// Adds `op` to `plan` so that `id` flows into it and it flows into all former consumers of `id`.
+///
+/// Returns the id of the new operator.
+pub fn insert_above(plan: &mut LogicalPlan<BindingsOp>, id: OpId, op: BindingsOp) -> OpId {
+    let new_id = plan.add_operator(op);
+    let flows = plan
+        .flows()
+        .iter()
+        .map(|&(s, d, b)| match s == id {
+            true => (new_id, d, b),
+            false => (s, d, b),
+        })
+        .chain(std::iter::once((id, new_id, 0)))
+        .collect();
+    rebuild(plan, None, flows);
+    new_id
+}
+
+/// Replaces `plan` with its operators other than `removed`, connected by `flows`, and returns the
+/// `removed` operator.
+fn rebuild(
+    plan: &mut LogicalPlan<BindingsOp>,
+    removed: Option<OpId>,
+    flows: Vec<(OpId, OpId, u8)>,
+) -> Option<BindingsOp> {
+    let mut old = std::mem::take(plan);
+    let ids: Vec<_> = old.operators_by_id().map(|(id, _)| id).collect();
+    let mut mapping = FxHashMap::default();
+    let mut removed_op = None;
+    for id in ids {
+        let op = std::mem::take(old.operator_as_mut(id).expect("operator"));
+        if Some(id) == removed {
+            removed_op = Some(op);
+        } else {
+            let new_id = plan.add_operator(op);
+            if let Some(location) = old.location(id) {
+                plan.set_location(new_id, location.clone());
+            }
+            mapping.insert(id, new_id);
+        }
+    }
+    for (src, dst, branch) in flows {
+        plan.add_flow_with_branch_num(mapping[&src], mapping[&dst], branch);
+    }
+    removed_op
+}
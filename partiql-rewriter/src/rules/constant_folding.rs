@@ -0,0 +1,192 @@
+use crate::rules::{bool_lit, int_lit};
+use crate::ExprRule;
+use partiql_logical::{BinaryOp, Lit, UnaryOp, ValueExpr};
+use rust_decimal::Decimal;
+use std::cmp::Ordering;
+
+/// Evaluates operators whose operands are all literals.
+///
+/// Folds `NOT` of booleans, `AND`, `OR`, `=` and `<>` of booleans, `||` of strings, comparisons
+/// of strings and exact numbers, and unary `+`/`-`, `+`, `-` and `*` of exact numbers. Operations
+/// that would overflow, or whose result depends on rounding (i.e., division and approximate
+/// numbers), are left to the evaluator.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConstantFolding;
+
+impl ExprRule for ConstantFolding {
+    fn name(&self) -> &'static str {
+        "constant_folding"
+    }
+
+    fn rewrite(&self, expr: &ValueExpr) -> Option<ValueExpr> {
+        match expr {
+            ValueExpr::UnExpr(op, operand) => fold_unary(op, operand),
+            ValueExpr::BinaryExpr(op, lhs, rhs) => fold_binary(op, lhs, rhs),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Num {
+    Int(i64),
+    Dec(Decimal),
+}
+
+impl Num {
+    fn of(expr: &ValueExpr) -> Option<Self> {
+        match (int_lit(expr), expr) {
+            (Some(i), _) => Some(Num::Int(i)),
+            (None, ValueExpr::Lit(lit)) => match **lit {
+                Lit::Decimal(d) => Some(Num::Dec(d)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn to_decimal(self) -> Decimal {
+        match self {
+            Num::Int(i) => Decimal::from(i),
+            Num::Dec(d) => d,
+        }
+    }
+
+    fn into_expr(self) -> ValueExpr {
+        ValueExpr::Lit(Box::new(match self {
+            Num::Int(i) => Lit::Int64(i),
+            Num::Dec(d) => Lit::Decimal(d),
+        }))
+    }
+}
+
+fn string_lit(expr: &ValueExpr) -> Option<&str> {
+    match expr {
+        ValueExpr::Lit(lit) => match &**lit {
+            Lit::String(s) => Some(s),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn boolean_lit(expr: &ValueExpr) -> Option<bool> {
+    match expr {
+        ValueExpr::Lit(lit) => match **lit {
+            Lit::Bool(b) => Some(b),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn fold_unary(op: &UnaryOp, operand: &ValueExpr) -> Option<ValueExpr> {
+    match op {
+        UnaryOp::Not => boolean_lit(operand).map(|b| bool_lit(!b)),
+        UnaryOp::Pos => Num::of(operand).map(Num::into_expr),
+        UnaryOp::Neg => match Num::of(operand)? {
+            Num::Int(i) => i.checked_neg().map(|i| Num::Int(i).into_expr()),
+            Num::Dec(d) => Some(Num::Dec(-d).into_expr()),
+        },
+    }
+}
+
+fn fold_binary(op: &BinaryOp, lhs: &ValueExpr, rhs: &ValueExpr) -> Option<ValueExpr> {
+    if let (Some(l), Some(r)) = (boolean_lit(lhs), boolean_lit(rhs)) {
+        return match op {
+            BinaryOp::And => Some(bool_lit(l && r)),
+            BinaryOp::Or => Some(bool_lit(l || r)),
+            BinaryOp::Eq => Some(bool_lit(l == r)),
+            BinaryOp::Neq => Some(bool_lit(l != r)),
+            _ => None,
+        };
+    }
+    if let (Some(l), Some(r)) = (string_lit(lhs), string_lit(rhs)) {
+        return match op {
+            BinaryOp::Concat => Some(ValueExpr::Lit(Box::new(Lit::String(format!("{l}{r}"))))),
+            _ => compare(op, l.cmp(r)),
+        };
+    }
+
+    let (l, r) = (Num::of(lhs)?, Num::of(rhs)?);
+    match (op, l, r) {
+        (BinaryOp::Add, Num::Int(l), Num::Int(r)) => l.checked_add(r).map(Num::Int),
+        (BinaryOp::Sub, Num::Int(l), Num::Int(r)) => l.checked_sub(r).map(Num::Int),
+        (BinaryOp::Mul, Num::Int(l), Num::Int(r)) => l.checked_mul(r).map(Num::Int),
+        (BinaryOp::Add, _, _) => l.to_decimal().checked_add(r.to_decimal()).map(Num::Dec),
+        (BinaryOp::Sub, _, _) => l.to_decimal().checked_sub(r.to_decimal()).map(Num::Dec),
+        (BinaryOp::Mul, _, _) => l.to_decimal().checked_mul(r.to_decimal()).map(Num::Dec),
+        // equality of integers and decimals goes through an approximate conversion
+        (BinaryOp::Eq | BinaryOp::Neq, Num::Int(_), Num::Dec(_))
+        | (BinaryOp::Eq | BinaryOp::Neq, Num::Dec(_), Num::Int(_)) => None,
+        (_, Num::Int(l), Num::Int(r)) => return compare(op, l.cmp(&r)),
+        _ => return compare(op, l.to_decimal().cmp(&r.to_decimal())),
+    }
+    .map(Num::into_expr)
+}
+
+fn compare(op: &BinaryOp, ordering: Ordering) -> Option<ValueExpr> {
+    let result = match op {
+        BinaryOp::Eq => ordering == Ordering::Equal,
+        BinaryOp::Neq => ordering != Ordering::Equal,
+        BinaryOp::Lt => ordering == Ordering::Less,
+        BinaryOp::Lteq => ordering != Ordering::Greater,
+        BinaryOp::Gt => ordering == Ordering::Greater,
+        BinaryOp::Gteq => ordering != Ordering::Less,
+        _ => return None,
+    };
+    Some(bool_lit(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::lower;
+    use crate::Rewriter;
+    use partiql_logical::BindingsOp;
+
+    fn fold(expr: &str) -> ValueExpr {
+        let rewritten = Rewriter::new()
+            .with_rule(ConstantFolding)
+            .rewrite(lower(&format!("SELECT VALUE {expr} FROM a")));
+        let project = rewritten.plan.operators().iter().find_map(|op| match op {
+            BindingsOp::ProjectValue(project) => Some(project.expr.clone()),
+            _ => None,
+        });
+        project.expect("projection")
+    }
+
+    fn lit(lit: Lit) -> ValueExpr {
+        ValueExpr::Lit(Box::new(lit))
+    }
+
+    #[test]
+    fn folds_literals() {
+        assert_eq!(fold("1 + 2 * 3"), lit(Lit::Int64(7)));
+        assert_eq!(fold("-(2 - 5)"), lit(Lit::Int64(3)));
+        assert_eq!(fold("1.5 + 1"), lit(Lit::Decimal(Decimal::new(25, 1))));
+        assert_eq!(fold("1 < 2 AND NOT (3 >= 4.5)"), lit(Lit::Bool(true)));
+        assert_eq!(fold("1.0 = 1.00"), lit(Lit::Bool(true)));
+        assert_eq!(fold("'a' || 'b' = 'ab'"), lit(Lit::Bool(true)));
+        assert_eq!(fold("'b' < 'a' OR FALSE"), lit(Lit::Bool(false)));
+    }
+
+    #[test]
+    fn leaves_non_literals() {
+        assert_eq!(
+            fold("x + (1 + 2)"),
+            ValueExpr::BinaryExpr(
+                BinaryOp::Add,
+                Box::new(fold("x")),
+                Box::new(lit(Lit::Int64(3)))
+            )
+        );
+        assert!(matches!(fold("1 / 2"), ValueExpr::BinaryExpr(..)));
+        assert!(matches!(fold("1 = 1.0"), ValueExpr::BinaryExpr(..)));
+        assert!(matches!(fold("NULL AND TRUE"), ValueExpr::BinaryExpr(..)));
+        assert!(matches!(
+            fold("9223372036854775807 + 1"),
+            ValueExpr::BinaryExpr(..)
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::plan;
+use crate::Rule;
+use partiql_logical::{BagOp, BindingsOp, LogicalPlan, OpId, SetQuantifier};
+
+/// Removes a `DISTINCT` over an operator whose output is already free of duplicates, i.e., another
+/// `DISTINCT` or a `UNION`, `INTERSECT` or `EXCEPT` without `ALL`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoveRedundantDistinct;
+
+impl Rule for RemoveRedundantDistinct {
+    fn name(&self) -> &'static str {
+        "remove_redundant_distinct"
+    }
+
+    fn apply(&self, plan: &mut LogicalPlan<BindingsOp>) -> bool {
+        let mut changed = false;
+        while let Some(id) = find(plan) {
+            plan::remove_operator(plan, id);
+            changed = true;
+        }
+        changed
+    }
+}
+
+fn find(plan: &LogicalPlan<BindingsOp>) -> Option<OpId> {
+    plan.operators_by_id().find_map(|(id, op)| {
+        let input = match op {
+            BindingsOp::Distinct => plan::sole_input(plan, id)?,
+            _ => return None,
+        };
+        match plan.operator(input)? {
+            BindingsOp::Distinct
+            | BindingsOp::BagOp(BagOp {
+                setq: SetQuantifier::Distinct,
+                ..
+            }) => Some(id),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{chain, lower};
+    use crate::Rewriter;
+
+    fn distincts(query: &str) -> (usize, Vec<&'static str>) {
+        let rewritten = Rewriter::new()
+            .with_rule(RemoveRedundantDistinct)
+            .rewrite(lower(query));
+        let count = chain(&rewritten.plan)
+            .into_iter()
+            .filter(|op| matches!(op, BindingsOp::Distinct))
+            .count();
+        (count, rewritten.fired)
+    }
+
+    #[test]
+    fn removes_redundant_distinct() {
+        let mut plan = lower("SELECT DISTINCT x.a FROM a AS x");
+        let distinct = plan
+            .operators_by_id()
+            .find(|(_, op)| matches!(op, BindingsOp::Distinct))
+            .map(|(id, _)| id)
+            .unwrap();
+        plan::insert_above(&mut plan, distinct, BindingsOp::Distinct);
+        let rewritten = Rewriter::new()
+            .with_rule(RemoveRedundantDistinct)
+            .rewrite(plan);
+        assert_eq!(rewritten.fired, vec!["remove_redundant_distinct"]);
+        assert_eq!(rewritten.plan, lower("SELECT DISTINCT x.a FROM a AS x"));
+    }
+
+    #[test]
+    fn keeps_required_distinct() {
+        assert_eq!(distincts("SELECT DISTINCT x.a FROM a AS x"), (1, vec![]));
+        assert_eq!(
+            distincts("SELECT DISTINCT x.a FROM (SELECT DISTINCT y.a FROM b AS y) AS x"),
+            (1, vec![])
+        );
+    }
+}
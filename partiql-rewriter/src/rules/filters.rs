@@ -0,0 +1,165 @@
+use crate::plan;
+use crate::rules::{and_all, is_predicate};
+use crate::Rule;
+use partiql_logical::{BindingsOp, Lit, LogicalPlan, OpId, ValueExpr};
+
+/// Merges a filter directly over another filter into a single filter of the conjunction of their
+/// predicates, and removes filters whose predicate is `TRUE`.
+///
+/// Filters are only merged if both of their conditions always evaluate to a boolean, `NULL` or
+/// `MISSING` (e.g., comparisons), as `AND` rejects other values in strict evaluation mode.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MergeFilters;
+
+impl Rule for MergeFilters {
+    fn name(&self) -> &'static str {
+        "merge_filters"
+    }
+
+    fn apply(&self, plan: &mut LogicalPlan<BindingsOp>) -> bool {
+        let mut changed = false;
+        while let Some(action) = find(plan) {
+            match action {
+                Action::RemoveTrue(id) => {
+                    plan::remove_operator(plan, id);
+                }
+                Action::Merge { upper, lower } => {
+                    let lower_expr = match plan.operator_as_mut(lower) {
+                        Some(BindingsOp::Filter(filter)) => take_expr(&mut filter.expr),
+                        _ => unreachable!("lower is a filter"),
+                    };
+                    if let Some(BindingsOp::Filter(filter)) = plan.operator_as_mut(upper) {
+                        let upper_expr = take_expr(&mut filter.expr);
+                        filter.expr = and_all([lower_expr, upper_expr]).expect("two conjuncts");
+                    }
+                    plan::remove_operator(plan, lower);
+                }
+            }
+            changed = true;
+        }
+        changed
+    }
+}
+
+fn take_expr(expr: &mut ValueExpr) -> ValueExpr {
+    std::mem::replace(expr, ValueExpr::Lit(Box::new(Lit::Null)))
+}
+
+enum Action {
+    RemoveTrue(OpId),
+    Merge { upper: OpId, lower: OpId },
+}
+
+fn find(plan: &LogicalPlan<BindingsOp>) -> Option<Action> {
+    let filters = || {
+        plan.operators_by_id().filter_map(|(id, op)| match op {
+            BindingsOp::Filter(filter) => Some((id, &filter.expr)),
+            _ => None,
+        })
+    };
+    let always_true = filters().find(|(id, expr)| {
+        matches!(expr, ValueExpr::Lit(lit) if **lit == Lit::Bool(true))
+            && plan::inputs(plan, *id).len() <= 1
+    });
+    if let Some((id, _)) = always_true {
+        return Some(Action::RemoveTrue(id));
+    }
+
+    filters().find_map(|(upper, upper_expr)| {
+        let lower = plan::sole_input(plan, upper)?;
+        match plan.operator(lower)? {
+            BindingsOp::Filter(filter)
+                if plan::sole_consumer(plan, lower) == Some(upper)
+                    && is_predicate(&filter.expr)
+                    && is_predicate(upper_expr) =>
+            {
+                Some(Action::Merge { upper, lower })
+            }
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::ConstantFolding;
+    use crate::tests::{chain, lower};
+    use crate::Rewriter;
+    use partiql_logical::{BinaryOp, Filter};
+
+    #[test]
+    fn merges_filters() {
+        let mut plan = lower("SELECT VALUE x FROM a AS x WHERE x.a > 1");
+        let filter = plan
+            .operators_by_id()
+            .find(|(_, op)| matches!(op, BindingsOp::Filter(_)))
+            .map(|(id, _)| id)
+            .unwrap();
+        let scan = plan::sole_input(&plan, filter).unwrap();
+        let lower_expr = ValueExpr::BinaryExpr(
+            BinaryOp::Lt,
+            Box::new(ValueExpr::Lit(Box::new(Lit::Int64(1)))),
+            Box::new(ValueExpr::Lit(Box::new(Lit::Int64(2)))),
+        );
+        plan::insert_between(
+            &mut plan,
+            scan,
+            filter,
+            BindingsOp::Filter(Filter {
+                expr: lower_expr.clone(),
+            }),
+        );
+
+        let rewritten = Rewriter::new()
+            .with_rule(MergeFilters)
+            .rewrite(plan.clone());
+        assert_eq!(rewritten.fired, vec!["merge_filters"]);
+        let filters: Vec<_> = chain(&rewritten.plan)
+            .into_iter()
+            .filter_map(|op| match op {
+                BindingsOp::Filter(filter) => Some(&filter.expr),
+                _ => None,
+            })
+            .collect();
+        assert!(matches!(
+            filters.as_slice(),
+            [ValueExpr::BinaryExpr(BinaryOp::And, lhs, _)] if **lhs == lower_expr
+        ));
+
+        // the folded `1 < 2` conjunct leaves the original filter
+        let rewritten = Rewriter::new()
+            .with_rule(ConstantFolding)
+            .with_rule(MergeFilters)
+            .rewrite(plan);
+        assert_eq!(rewritten.fired, vec!["constant_folding", "merge_filters"]);
+        assert_eq!(
+            rewritten.plan,
+            lower("SELECT VALUE x FROM a AS x WHERE x.a > 1")
+        );
+    }
+
+    #[test]
+    fn keeps_non_predicates_apart() {
+        let mut plan = lower("SELECT VALUE x FROM a AS x WHERE x.flag");
+        let filter = plan
+            .operators_by_id()
+            .find(|(_, op)| matches!(op, BindingsOp::Filter(_)))
+            .map(|(id, _)| id)
+            .unwrap();
+        let scan = plan::sole_input(&plan, filter).unwrap();
+        plan::insert_between(
+            &mut plan,
+            scan,
+            filter,
+            BindingsOp::Filter(Filter {
+                expr: ValueExpr::Lit(Box::new(Lit::Bool(false))),
+            }),
+        );
+        let rewritten = Rewriter::new()
+            .with_rule(MergeFilters)
+            .rewrite(plan.clone());
+        assert!(rewritten.fired.is_empty());
+        assert_eq!(rewritten.plan, plan);
+    }
+}
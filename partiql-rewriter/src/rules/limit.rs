@@ -0,0 +1,214 @@
+use crate::plan;
+use crate::rules::int_lit;
+use crate::Rule;
+use partiql_logical::{
+    BagOp, BagOperator, BindingsOp, LimitOffset, Lit, LogicalPlan, OpId, Scan, SetQuantifier,
+    ValueExpr,
+};
+
+/// Moves `LIMIT`s closer to the sources of a plan.
+///
+/// A `LIMIT` (and `OFFSET`) directly over a projection is moved below it. A `LIMIT n OFFSET m` of
+/// integer literals over a `UNION ALL` or `OUTER UNION ALL`, or over the only `FROM` source when
+/// that is a sub-query, additionally limits each input of the union, or the sub-query, to its first
+/// `n + m` results.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LimitPushdown;
+
+impl Rule for LimitPushdown {
+    fn name(&self) -> &'static str {
+        "limit_pushdown"
+    }
+
+    fn apply(&self, plan: &mut LogicalPlan<BindingsOp>) -> bool {
+        let mut changed = false;
+        let ids: Vec<_> = plan.operators_by_id().map(|(id, _)| id).collect();
+        for id in ids {
+            let Some(BindingsOp::LimitOffset(limit_offset)) = plan.operator(id) else {
+                continue;
+            };
+            let count = row_count(limit_offset);
+            let Some(input) = plan::sole_input(plan, id) else {
+                continue;
+            };
+            match plan.operator(input) {
+                Some(BindingsOp::Project(_) | BindingsOp::ProjectValue(_))
+                    if plan::sole_consumer(plan, input) == Some(id) =>
+                {
+                    swap(plan, id, input);
+                    changed = true;
+                }
+                Some(BindingsOp::BagOp(BagOp {
+                    bag_op: BagOperator::Union | BagOperator::OuterUnion,
+                    setq: SetQuantifier::All,
+                })) => {
+                    let Some(count) = count else { continue };
+                    for branch in plan::inputs(plan, input) {
+                        if !is_limited(plan, branch, count) {
+                            plan::insert_between(plan, branch, input, limit(count));
+                            changed = true;
+                        }
+                    }
+                }
+                Some(BindingsOp::Scan(Scan {
+                    expr: ValueExpr::SubQueryExpr(_),
+                    ..
+                })) if plan::sole_consumer(plan, input) == Some(id) => {
+                    let Some(count) = count else { continue };
+                    let Some(BindingsOp::Scan(Scan {
+                        expr: ValueExpr::SubQueryExpr(subquery),
+                        ..
+                    })) = plan.operator_as_mut(input)
+                    else {
+                        unreachable!("input is a scan of a sub-query")
+                    };
+                    let subplan = &mut subquery.plan;
+                    let Some(output) = plan::output(subplan) else {
+                        continue;
+                    };
+                    let produces_values = matches!(
+                        subplan.operator(output),
+                        Some(
+                            BindingsOp::Project(_)
+                                | BindingsOp::ProjectValue(_)
+                                | BindingsOp::ProjectAll(_)
+                                | BindingsOp::Distinct
+                                | BindingsOp::BagOp(_)
+                                | BindingsOp::LimitOffset(_)
+                        )
+                    );
+                    if produces_values && !is_limited(subplan, output, count) {
+                        plan::insert_above(subplan, output, limit(count));
+                        changed = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        changed
+    }
+}
+
+/// Returns the number of rows needed to evaluate `limit_offset`, if its limit and offset are
+/// integer literals.
+fn row_count(limit_offset: &LimitOffset) -> Option<i64> {
+    let limit = int_lit(limit_offset.limit.as_ref()?)?;
+    let offset = match &limit_offset.offset {
+        None => 0,
+        Some(offset) => int_lit(offset)?,
+    };
+    match (limit >= 0, offset >= 0) {
+        (true, true) => limit.checked_add(offset),
+        _ => None,
+    }
+}
+
+/// Returns whether the output of `id` has at most `count` rows because of a `LIMIT`, possibly below
+/// projections.
+fn is_limited(plan: &LogicalPlan<BindingsOp>, id: OpId, count: i64) -> bool {
+    match plan.operator(id) {
+        Some(BindingsOp::LimitOffset(limit_offset)) => limit_offset
+            .limit
+            .as_ref()
+            .and_then(int_lit)
+            .is_some_and(|limit| limit <= count),
+        Some(BindingsOp::Project(_) | BindingsOp::ProjectValue(_)) => {
+            plan::sole_input(plan, id).is_some_and(|input| is_limited(plan, input, count))
+        }
+        _ => false,
+    }
+}
+
+fn limit(count: i64) -> BindingsOp {
+    BindingsOp::LimitOffset(LimitOffset {
+        limit: Some(ValueExpr::Lit(Box::new(Lit::Int64(count)))),
+        offset: None,
+    })
+}
+
+/// Swaps the operators `a` and `b`, keeping the data flows between the positions.
+fn swap(plan: &mut LogicalPlan<BindingsOp>, a: OpId, b: OpId) {
+    let op_a = std::mem::take(plan.operator_as_mut(a).expect("operator"));
+    let op_b = std::mem::replace(plan.operator_as_mut(b).expect("operator"), op_a);
+    *plan.operator_as_mut(a).expect("operator") = op_b;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{chain, lower};
+    use crate::Rewriter;
+
+    fn limits(plan: &LogicalPlan<BindingsOp>) -> Vec<Option<i64>> {
+        plan.operators()
+            .iter()
+            .filter_map(|op| match op {
+                BindingsOp::LimitOffset(limit_offset) => {
+                    Some(limit_offset.limit.as_ref().and_then(int_lit))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pushes_into_unions() {
+        let rewritten = Rewriter::new().with_rule(LimitPushdown).rewrite(lower(
+            "(SELECT VALUE x FROM a AS x) OUTER UNION ALL (SELECT VALUE y FROM b AS y) \
+             LIMIT 2 OFFSET 5",
+        ));
+        // the limits pushed into both inputs are moved below their projections
+        assert_eq!(rewritten.fired, vec!["limit_pushdown", "limit_pushdown"]);
+        let mut union_limits = limits(&rewritten.plan);
+        union_limits.sort();
+        assert_eq!(union_limits, vec![Some(2), Some(7), Some(7)]);
+        let ops = chain(&rewritten.plan);
+        assert!(matches!(
+            ops.as_slice(),
+            [
+                BindingsOp::Scan(_),
+                BindingsOp::LimitOffset(_),
+                BindingsOp::ProjectValue(_),
+                BindingsOp::BagOp(_),
+                BindingsOp::LimitOffset(_),
+                BindingsOp::Sink
+            ]
+        ));
+
+        let rewritten = Rewriter::new().with_rule(LimitPushdown).rewrite(lower(
+            "(SELECT VALUE x FROM a AS x) OUTER UNION (SELECT VALUE y FROM b AS y) LIMIT 2",
+        ));
+        assert!(rewritten.fired.is_empty());
+    }
+
+    #[test]
+    fn pushes_into_subqueries() {
+        let rewritten = Rewriter::new().with_rule(LimitPushdown).rewrite(lower(
+            "SELECT s.a FROM (SELECT x.a FROM t AS x LIMIT 10) AS s LIMIT 3 OFFSET 1",
+        ));
+        // the sub-query's limits are also moved below its projection
+        assert!(!rewritten.fired.is_empty());
+        assert!(rewritten.fired.iter().all(|rule| *rule == "limit_pushdown"));
+        let Some(BindingsOp::Scan(Scan {
+            expr: ValueExpr::SubQueryExpr(subquery),
+            ..
+        })) = chain(&rewritten.plan).first().copied()
+        else {
+            panic!("scan of a sub-query")
+        };
+        let mut subquery_limits = limits(&subquery.plan);
+        subquery_limits.sort();
+        assert_eq!(subquery_limits, vec![Some(4), Some(10)]);
+
+        for query in [
+            "SELECT s.a FROM (SELECT x.a FROM t AS x) AS s ORDER BY s.a LIMIT 3",
+            "SELECT s.a FROM (SELECT x.a FROM t AS x) AS s WHERE s.a > 1 LIMIT 3",
+            "SELECT s.a FROM (SELECT x.a FROM t AS x) AS s LIMIT ?",
+        ] {
+            let rewritten = Rewriter::new()
+                .with_rule(LimitPushdown)
+                .rewrite(lower(query));
+            assert!(rewritten.fired.is_empty(), "{query}");
+        }
+    }
+}
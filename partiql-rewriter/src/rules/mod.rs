@@ -0,0 +1,87 @@
+//! The rules applied by [`Rewriter::default`](crate::Rewriter::default).
+//!
+//! The rules preserve the result of a query. In strict evaluation mode however, a rewritten plan
+//! may evaluate a predicate for bindings that the original plan discarded before evaluating it
+//! (e.g., a predicate pushed into a join is evaluated for pairs of bindings that failed the join's
+//! own condition with `NULL`), and does not evaluate projections that are pruned, so the two may
+//! report different errors.
+
+mod constant_folding;
+mod distinct;
+mod filters;
+mod limit;
+mod projection;
+mod pushdown;
+
+pub use constant_folding::ConstantFolding;
+pub use distinct::RemoveRedundantDistinct;
+pub use filters::MergeFilters;
+pub use limit::LimitPushdown;
+pub use projection::ProjectionPruning;
+pub use pushdown::PredicatePushdown;
+
+use partiql_logical::{BinaryOp, Lit, UnaryOp, ValueExpr};
+
+/// Splits `expr` into the operands of its top-level `AND`s.
+fn conjuncts(expr: ValueExpr) -> Vec<ValueExpr> {
+    match expr {
+        ValueExpr::BinaryExpr(BinaryOp::And, lhs, rhs) => {
+            let mut all = conjuncts(*lhs);
+            all.extend(conjuncts(*rhs));
+            all
+        }
+        other => vec![other],
+    }
+}
+
+/// Combines `conjuncts` with `AND`, or returns `None` if there are none.
+fn and_all(conjuncts: impl IntoIterator<Item = ValueExpr>) -> Option<ValueExpr> {
+    conjuncts
+        .into_iter()
+        .reduce(|lhs, rhs| ValueExpr::BinaryExpr(BinaryOp::And, Box::new(lhs), Box::new(rhs)))
+}
+
+/// Returns whether `expr` always evaluates to a boolean, `NULL` or `MISSING` (or fails).
+///
+/// Only predicates may be combined with `AND`, which, unlike a `WHERE` clause, rejects other
+/// values in strict evaluation mode.
+fn is_predicate(expr: &ValueExpr) -> bool {
+    match expr {
+        ValueExpr::BinaryExpr(op, _, _) => matches!(
+            op,
+            BinaryOp::And
+                | BinaryOp::Or
+                | BinaryOp::Eq
+                | BinaryOp::Neq
+                | BinaryOp::Gt
+                | BinaryOp::Gteq
+                | BinaryOp::Lt
+                | BinaryOp::Lteq
+                | BinaryOp::In
+        ),
+        ValueExpr::UnExpr(UnaryOp::Not, _)
+        | ValueExpr::BetweenExpr(_)
+        | ValueExpr::PatternMatchExpr(_)
+        | ValueExpr::IsTypeExpr(_) => true,
+        ValueExpr::Lit(lit) => matches!(**lit, Lit::Bool(_)),
+        _ => false,
+    }
+}
+
+/// Returns the value of an integer literal.
+fn int_lit(expr: &ValueExpr) -> Option<i64> {
+    match expr {
+        ValueExpr::Lit(lit) => match **lit {
+            Lit::Int8(i) => Some(i.into()),
+            Lit::Int16(i) => Some(i.into()),
+            Lit::Int32(i) => Some(i.into()),
+            Lit::Int64(i) => Some(i),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn bool_lit(value: bool) -> ValueExpr {
+    ValueExpr::Lit(Box::new(Lit::Bool(value)))
+}
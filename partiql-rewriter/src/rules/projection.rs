@@ -0,0 +1,245 @@
+use crate::plan;
+use crate::visit::{self, attr_ref, is_var_ref, refers_to};
+use crate::Rule;
+use partiql_logical::{BindingsOp, LogicalPlan, PathComponent, Project, Scan, ValueExpr};
+use partiql_value::BindingsName;
+
+/// Removes the attributes of a `FROM` sub-query's `SELECT` that the enclosing query never
+/// references, e.g., `b` in `SELECT s.a FROM (SELECT x.a, x.b FROM t AS x) AS s`.
+///
+/// Attributes are only removed if the variable bound to the sub-query's results is referenced
+/// exclusively by paths to its attributes (i.e., not as a whole, as in `SELECT *` or `GROUP AS`),
+/// and the sub-query's `SELECT` produces its results directly (i.e., not through a `DISTINCT` or
+/// a `UNION`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProjectionPruning;
+
+impl Rule for ProjectionPruning {
+    fn name(&self) -> &'static str {
+        "projection_pruning"
+    }
+
+    fn apply(&self, plan: &mut LogicalPlan<BindingsOp>) -> bool {
+        let mut pruned: Vec<(String, Vec<String>)> = vec![];
+        for op in plan.operators() {
+            for scan in scans(op) {
+                let Some(project) = subquery_projection(scan) else {
+                    continue;
+                };
+                let Some(referenced) = referenced_attrs(plan, &scan.as_key) else {
+                    continue;
+                };
+                let unreferenced: Vec<_> = project
+                    .exprs
+                    .iter()
+                    .map(|(name, _)| name)
+                    .filter(|name| !referenced.iter().any(|attr| refers_to(attr, name)))
+                    .cloned()
+                    .collect();
+                if !unreferenced.is_empty() {
+                    pruned.push((scan.as_key.clone(), unreferenced));
+                }
+            }
+        }
+
+        let ids: Vec<_> = plan.operators_by_id().map(|(id, _)| id).collect();
+        for id in ids {
+            for scan in scans_mut(plan.operator_as_mut(id).expect("operator")) {
+                let Some((_, unreferenced)) = pruned.iter().find(|(var, _)| *var == scan.as_key)
+                else {
+                    continue;
+                };
+                if let Some(project) = subquery_projection_mut(scan) {
+                    project
+                        .exprs
+                        .retain(|(name, _)| !unreferenced.contains(name));
+                }
+            }
+        }
+        !pruned.is_empty()
+    }
+}
+
+/// Returns the scans in `op`, including those that are operands of a join.
+fn scans(op: &BindingsOp) -> Vec<&Scan> {
+    match op {
+        BindingsOp::Scan(scan) => vec![scan],
+        BindingsOp::Join(join) => {
+            let mut found = scans(&join.left);
+            found.extend(scans(&join.right));
+            found
+        }
+        _ => vec![],
+    }
+}
+
+fn scans_mut(op: &mut BindingsOp) -> Vec<&mut Scan> {
+    match op {
+        BindingsOp::Scan(scan) => vec![scan],
+        BindingsOp::Join(join) => {
+            let mut found = scans_mut(&mut join.left);
+            found.extend(scans_mut(&mut join.right));
+            found
+        }
+        _ => vec![],
+    }
+}
+
+/// Returns the `SELECT` producing the results of a scanned sub-query, unless the scan renames
+/// their attributes by position.
+fn subquery_projection(scan: &Scan) -> Option<&Project> {
+    let ValueExpr::SubQueryExpr(subquery) = &scan.expr else {
+        return None;
+    };
+    if scan.columns.is_some() {
+        return None;
+    }
+    match subquery.plan.operator(plan::output(&subquery.plan)?)? {
+        BindingsOp::Project(project) => Some(project),
+        _ => None,
+    }
+}
+
+fn subquery_projection_mut(scan: &mut Scan) -> Option<&mut Project> {
+    if scan.columns.is_some() {
+        return None;
+    }
+    let ValueExpr::SubQueryExpr(subquery) = &mut scan.expr else {
+        return None;
+    };
+    let output = plan::output(&subquery.plan)?;
+    match subquery.plan.operator_as_mut(output)? {
+        BindingsOp::Project(project) => Some(project),
+        _ => None,
+    }
+}
+
+/// Returns the attributes of `var` referenced in `plan` and the plans nested in it, or `None` if
+/// `var` is referenced other than through a path to one of its attributes.
+fn referenced_attrs<'a>(
+    plan: &'a LogicalPlan<BindingsOp>,
+    var: &str,
+) -> Option<Vec<&'a BindingsName<'static>>> {
+    let mut attrs = vec![];
+    plan.operators()
+        .iter()
+        .all(|op| op_references(op, var, &mut attrs))
+        .then_some(attrs)
+}
+
+fn op_references<'a>(
+    op: &'a BindingsOp,
+    var: &str,
+    attrs: &mut Vec<&'a BindingsName<'static>>,
+) -> bool {
+    let ok = match op {
+        BindingsOp::With(with) => with
+            .ctes
+            .iter()
+            .flat_map(|cte| {
+                std::iter::once(&cte.plan).chain(cte.recursive.as_ref().map(|r| &r.plan))
+            })
+            .chain(std::iter::once(&with.body))
+            .all(|plan| {
+                plan.operators()
+                    .iter()
+                    .all(|op| op_references(op, var, attrs))
+            }),
+        // `SELECT *` and `GROUP AS` refer to all variables as a whole
+        BindingsOp::ProjectAll(_) => false,
+        BindingsOp::GroupBy(group_by) => group_by.group_as_alias.is_none(),
+        _ => true,
+    };
+    ok && visit::op_exprs(op)
+        .into_iter()
+        .all(|expr| expr_references(expr, var, attrs))
+}
+
+fn expr_references<'a>(
+    expr: &'a ValueExpr,
+    var: &str,
+    attrs: &mut Vec<&'a BindingsName<'static>>,
+) -> bool {
+    if let Some((attr, rest)) = attr_ref(expr, var) {
+        attrs.push(attr);
+        return rest.iter().all(|component| match component {
+            PathComponent::KeyExpr(e) | PathComponent::IndexExpr(e) => {
+                expr_references(e, var, attrs)
+            }
+            _ => true,
+        });
+    }
+    match expr {
+        ValueExpr::SubQueryExpr(subquery) => subquery
+            .plan
+            .operators()
+            .iter()
+            .all(|op| op_references(op, var, attrs)),
+        ValueExpr::GraphMatch(_) => false,
+        ValueExpr::VarRef(name, _) if refers_to(name, var) => false,
+        _ if is_var_ref(expr, var) => false,
+        _ => visit::children(expr)
+            .into_iter()
+            .all(|child| expr_references(child, var, attrs)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{chain, lower};
+    use crate::Rewriter;
+
+    /// Returns the names projected by the `FROM` sub-query of `query` after pruning.
+    fn projected(query: &str) -> Vec<String> {
+        let rewritten = Rewriter::new()
+            .with_rule(ProjectionPruning)
+            .rewrite(lower(query));
+        let scan = chain(&rewritten.plan).into_iter().find_map(|op| match op {
+            BindingsOp::Scan(scan) => Some(scan.clone()),
+            _ => None,
+        });
+        let scan = scan.expect("scan");
+        subquery_projection(&scan)
+            .expect("projection")
+            .exprs
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn prunes_unreferenced_attributes() {
+        assert_eq!(
+            projected("SELECT s.a FROM (SELECT x.a, x.b, x.c FROM t AS x) AS s WHERE s.C > 1"),
+            vec!["a", "c"]
+        );
+        assert_eq!(
+            projected("SELECT s.a.b FROM (SELECT x.a, x.b FROM t AS x) AS s ORDER BY s.b"),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            projected("SELECT s.a FROM (SELECT x.a, x.b FROM t AS x) AS s, u AS y"),
+            vec!["a"]
+        );
+        assert_eq!(
+            projected("SELECT COUNT(*) AS n FROM (SELECT x.a, x.b FROM t AS x) AS s"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn keeps_attributes_of_whole_references() {
+        for query in [
+            "SELECT * FROM (SELECT x.a, x.b FROM t AS x) AS s",
+            "SELECT VALUE s FROM (SELECT x.a, x.b FROM t AS x) AS s",
+            "SELECT s.a FROM (SELECT x.a, x.b FROM t AS x) AS s GROUP BY s.a GROUP AS g",
+            "SELECT s.a FROM (SELECT x.a, x.b FROM t AS x) AS s, UNPIVOT s AS v",
+        ] {
+            let rewritten = Rewriter::new()
+                .with_rule(ProjectionPruning)
+                .rewrite(lower(query));
+            assert!(rewritten.fired.is_empty(), "{query}");
+        }
+    }
+}
@@ -0,0 +1,392 @@
+use crate::plan;
+use crate::rules::{and_all, conjuncts, is_predicate};
+use crate::visit::{self, attr_ref, bound_vars, local_vars, refers_to};
+use crate::Rule;
+use partiql_logical::{
+    BindingsOp, Filter, Join, JoinKind, Lit, LogicalPlan, OpId, PathComponent, Project, Scan,
+    ValueExpr, VarRefType,
+};
+
+/// Moves the conditions of a `WHERE` clause closer to the sources they filter.
+///
+/// The conditions of a filter over an inner or cross join (i.e., of `FROM` sources, including
+/// `UNPIVOT`s) are moved into the join, each into the innermost join that binds all variables it
+/// references, which allows them to be evaluated while joining (e.g., as a hash join). The
+/// conditions of a filter over a `FROM` sub-query that only reference the attributes its `SELECT`
+/// produces are moved into the sub-query, below its `SELECT`.
+///
+/// Conditions are only moved if the filter's condition always evaluates to a boolean, `NULL` or
+/// `MISSING` (e.g., comparisons), as they are combined with the existing conditions using `AND`,
+/// which rejects other values in strict evaluation mode.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PredicatePushdown;
+
+impl Rule for PredicatePushdown {
+    fn name(&self) -> &'static str {
+        "predicate_pushdown"
+    }
+
+    fn apply(&self, plan: &mut LogicalPlan<BindingsOp>) -> bool {
+        let mut changed = false;
+        while let Some((filter, input)) = find(plan) {
+            changed = true;
+            match plan.operator(input) {
+                Some(BindingsOp::Join(_)) => into_join(plan, filter, input),
+                _ => into_subquery(plan, filter, input),
+            }
+        }
+        changed
+    }
+}
+
+/// Finds a filter from which conditions can be pushed into its input.
+fn find(plan: &LogicalPlan<BindingsOp>) -> Option<(OpId, OpId)> {
+    plan.operators_by_id().find_map(|(id, op)| {
+        let BindingsOp::Filter(filter) = op else {
+            return None;
+        };
+        let input = plan::sole_input(plan, id)?;
+        if !is_predicate(&filter.expr) || plan::sole_consumer(plan, input) != Some(id) {
+            return None;
+        }
+        let pushable = match plan.operator(input)? {
+            BindingsOp::Join(join) => {
+                accepts_conditions(join)
+                    && conjuncts(filter.expr.clone())
+                        .iter()
+                        .any(|condition| join_binds(join, condition))
+            }
+            BindingsOp::Scan(scan) => subquery_pushable(scan, &filter.expr).is_some(),
+            _ => false,
+        };
+        pushable.then_some((id, input))
+    })
+}
+
+/// Returns whether `join` binds all variables referenced by `condition`.
+fn join_binds(join: &Join, condition: &ValueExpr) -> bool {
+    let mut vars = vec![];
+    let bound: Vec<_> = [&join.left, &join.right]
+        .into_iter()
+        .flat_map(|operand| bound_vars(operand).unwrap_or_default())
+        .collect();
+    local_vars(condition, &mut vars)
+        && vars
+            .iter()
+            .all(|var| bound.iter().any(|bound| refers_to(var, bound)))
+}
+
+/// Returns whether conditions may be added to `join`.
+fn accepts_conditions(join: &Join) -> bool {
+    matches!(join.kind, JoinKind::Inner | JoinKind::Cross)
+        && join.on.as_ref().is_none_or(is_predicate)
+}
+
+fn into_join(plan: &mut LogicalPlan<BindingsOp>, filter: OpId, join: OpId) {
+    let Some(BindingsOp::Filter(Filter { expr })) = plan.operator_as_mut(filter) else {
+        unreachable!("filter is a filter")
+    };
+    let expr = std::mem::replace(expr, ValueExpr::Lit(Box::new(Lit::Bool(true))));
+
+    let mut replaced = vec![];
+    let mut kept = vec![];
+    let Some(BindingsOp::Join(op)) = plan.operator_as_mut(join) else {
+        unreachable!("join is a join")
+    };
+    for condition in conjuncts(expr) {
+        match join_binds(op, &condition) {
+            true => push_into_join(op, condition, &mut replaced),
+            false => kept.push(condition),
+        }
+    }
+
+    // the operands of a join are also operators of the plan that flow into it
+    let ids: Vec<_> = plan.operators_by_id().map(|(id, _)| id).collect();
+    for id in ids.into_iter().filter(|id| *id != join) {
+        let op = plan.operator_as_mut(id).expect("operator");
+        if let Some((_, rewritten)) = replaced.iter().find(|(original, _)| original == op) {
+            *op = rewritten.clone();
+        }
+    }
+    keep_or_remove(plan, filter, kept);
+}
+
+/// Replaces the condition of `filter` with the conjunction of `kept`, or removes `filter` if there
+/// are none.
+fn keep_or_remove(plan: &mut LogicalPlan<BindingsOp>, filter: OpId, kept: Vec<ValueExpr>) {
+    match and_all(kept) {
+        Some(kept) => {
+            if let Some(BindingsOp::Filter(filter)) = plan.operator_as_mut(filter) {
+                filter.expr = kept;
+            }
+        }
+        None => {
+            plan::remove_operator(plan, filter);
+        }
+    }
+}
+
+/// Adds `condition` to the innermost join in `join` binding all variables it references.
+///
+/// Records the original and the rewritten version of every join operand of `join` that is
+/// rewritten in `replaced`.
+fn push_into_join(
+    join: &mut Join,
+    condition: ValueExpr,
+    replaced: &mut Vec<(BindingsOp, BindingsOp)>,
+) {
+    let inner = [&mut join.left, &mut join.right]
+        .into_iter()
+        .find(|operand| match &***operand {
+            BindingsOp::Join(inner) => {
+                accepts_conditions(inner) && has_vars(&condition) && join_binds(inner, &condition)
+            }
+            _ => false,
+        });
+
+    match inner {
+        Some(operand) => {
+            let original = (**operand).clone();
+            let BindingsOp::Join(inner) = &mut **operand else {
+                unreachable!("operand is a join")
+            };
+            push_into_join(inner, condition, replaced);
+            let rewritten = (**operand).clone();
+            // an operand rewritten by an earlier condition is only recorded once
+            match replaced
+                .iter_mut()
+                .find(|(_, earlier)| *earlier == original)
+            {
+                Some((_, earlier)) => *earlier = rewritten,
+                None => replaced.push((original, rewritten)),
+            }
+        }
+        None => {
+            let on = join.on.take();
+            join.on = and_all(on.into_iter().chain(std::iter::once(condition)));
+            join.kind = JoinKind::Inner;
+        }
+    }
+}
+
+/// Returns the `SELECT` of the sub-query scanned by `scan` if at least one of the conditions of
+/// `expr` can be pushed into it.
+fn subquery_pushable<'a>(scan: &'a Scan, expr: &ValueExpr) -> Option<&'a Project> {
+    let project = subquery_projection(scan)?;
+    conjuncts(expr.clone())
+        .iter()
+        .any(|condition| substitute(condition, &scan.as_key, project).is_some())
+        .then_some(project)
+}
+
+fn subquery_projection(scan: &Scan) -> Option<&Project> {
+    let ValueExpr::SubQueryExpr(subquery) = &scan.expr else {
+        return None;
+    };
+    if scan.at_key.is_some() || scan.columns.is_some() {
+        return None;
+    }
+    let output = plan::output(&subquery.plan)?;
+    plan::sole_input(&subquery.plan, output)?;
+    match subquery.plan.operator(output)? {
+        BindingsOp::Project(project) => Some(project),
+        _ => None,
+    }
+}
+
+/// Rewrites `condition` over the results of a sub-query bound to `var` into a condition over the
+/// bindings of the sub-query's `SELECT` `project`, or returns `None` if `condition` references
+/// anything but the attributes produced by `project`.
+fn substitute(condition: &ValueExpr, var: &str, project: &Project) -> Option<ValueExpr> {
+    if let Some((attr, rest)) = attr_ref(condition, var) {
+        let mut matching = project
+            .exprs
+            .iter()
+            .filter(|(name, _)| refers_to(attr, name));
+        let (Some((_, expr)), None) = (matching.next(), matching.next()) else {
+            return None;
+        };
+        if !local_vars(expr, &mut vec![]) {
+            return None;
+        }
+        let mut rest = rest.to_vec();
+        for component in &mut rest {
+            if let PathComponent::KeyExpr(e) | PathComponent::IndexExpr(e) = component {
+                **e = substitute(e, var, project)?;
+            }
+        }
+        return Some(match rest.is_empty() {
+            true => expr.clone(),
+            false => ValueExpr::Path(Box::new(expr.clone()), rest),
+        });
+    }
+
+    match condition {
+        ValueExpr::SubQueryExpr(_) | ValueExpr::GraphMatch(_) => None,
+        ValueExpr::VarRef(_, VarRefType::Local) => None,
+        ValueExpr::DynamicLookup(_) if references_locals(condition) => None,
+        _ => {
+            let mut substituted = condition.clone();
+            for child in visit::children_mut(&mut substituted) {
+                *child = substitute(child, var, project)?;
+            }
+            Some(substituted)
+        }
+    }
+}
+
+fn has_vars(condition: &ValueExpr) -> bool {
+    let mut vars = vec![];
+    local_vars(condition, &mut vars) && !vars.is_empty()
+}
+
+/// Returns whether `expr` references a local variable.
+fn references_locals(expr: &ValueExpr) -> bool {
+    let mut vars = vec![];
+    !local_vars(expr, &mut vars) || !vars.is_empty()
+}
+
+fn into_subquery(plan: &mut LogicalPlan<BindingsOp>, filter: OpId, scan: OpId) {
+    let (Some(BindingsOp::Filter(Filter { expr })), Some(BindingsOp::Scan(scan_op))) =
+        (plan.operator(filter), plan.operator(scan))
+    else {
+        unreachable!("filter over a scan")
+    };
+    let project = subquery_projection(scan_op).expect("scan of a sub-query");
+
+    let (mut pushed, mut kept) = (vec![], vec![]);
+    for condition in conjuncts(expr.clone()) {
+        match substitute(&condition, &scan_op.as_key, project) {
+            Some(substituted) => pushed.push(substituted),
+            None => kept.push(condition),
+        }
+    }
+
+    if let Some(BindingsOp::Scan(Scan {
+        expr: ValueExpr::SubQueryExpr(subquery),
+        ..
+    })) = plan.operator_as_mut(scan)
+    {
+        let subplan = &mut subquery.plan;
+        let output = plan::output(subplan).expect("sub-query output");
+        let input = plan::sole_input(subplan, output).expect("input of the sub-query output");
+        let expr = and_all(pushed).expect("pushed condition");
+        plan::insert_between(subplan, input, output, BindingsOp::Filter(Filter { expr }));
+    }
+    keep_or_remove(plan, filter, kept);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::MergeFilters;
+    use crate::tests::{chain, lower};
+    use crate::Rewriter;
+    use partiql_logical::BinaryOp;
+
+    fn pushdown(query: &str) -> LogicalPlan<BindingsOp> {
+        let rewritten = Rewriter::new()
+            .with_rule(PredicatePushdown)
+            .rewrite(lower(query));
+        assert_eq!(rewritten.fired, vec!["predicate_pushdown"]);
+        rewritten.plan
+    }
+
+    fn filter_of(query: &str) -> ValueExpr {
+        let plan = lower(query);
+        let filter = plan.operators().iter().find_map(|op| match op {
+            BindingsOp::Filter(filter) => Some(filter.expr.clone()),
+            _ => None,
+        });
+        filter.expect("filter")
+    }
+
+    #[test]
+    fn pushes_into_joins() {
+        let plan = pushdown(
+            "SELECT x.a FROM a AS x, b AS y, c AS z \
+             WHERE x.id = y.id AND z.n = y.n AND x.v > 1",
+        );
+        let ops = chain(&plan);
+        assert!(!ops.iter().any(|op| matches!(op, BindingsOp::Filter(_))));
+        let Some(BindingsOp::Join(outer)) =
+            ops.iter().rfind(|op| matches!(op, BindingsOp::Join(_)))
+        else {
+            panic!("join")
+        };
+        let BindingsOp::Join(inner) = &*outer.left else {
+            panic!("inner join")
+        };
+
+        let expected = conjuncts(filter_of(
+            "SELECT x.a FROM a AS x, b AS y, c AS z \
+             WHERE x.id = y.id AND z.n = y.n AND x.v > 1",
+        ));
+        assert_eq!(
+            (&outer.kind, &inner.kind),
+            (&JoinKind::Inner, &JoinKind::Inner)
+        );
+        assert_eq!(outer.on.as_ref(), Some(&expected[1]));
+        assert_eq!(
+            inner.on,
+            and_all([expected[0].clone(), expected[2].clone()])
+        );
+        // the operator flowing into the outer join is rewritten along with its operand
+        assert!(plan.operators().contains(&outer.left));
+    }
+
+    #[test]
+    fn pushes_into_subqueries() {
+        let query = "SELECT s.k FROM (SELECT x.k, x.id FROM a AS x WHERE x.k <> 'a') AS s \
+                     WHERE s.id = 2 AND s.other = 1";
+        let rewritten = Rewriter::new()
+            .with_rule(PredicatePushdown)
+            .with_rule(MergeFilters)
+            .rewrite(lower(query));
+        assert_eq!(rewritten.fired, vec!["predicate_pushdown", "merge_filters"]);
+
+        let ops = chain(&rewritten.plan);
+        let (Some(BindingsOp::Scan(scan)), Some(BindingsOp::Filter(outer))) =
+            (ops.first(), ops.get(1))
+        else {
+            panic!("filtered scan")
+        };
+        let conditions = conjuncts(filter_of(query));
+        assert_eq!(outer.expr, conditions[1]);
+
+        let ValueExpr::SubQueryExpr(subquery) = &scan.expr else {
+            panic!("sub-query")
+        };
+        let inner = chain(&subquery.plan);
+        let (Some(BindingsOp::Filter(filter)), Some(BindingsOp::Project(project))) =
+            (inner.get(1), inner.get(2))
+        else {
+            panic!("filtered projection")
+        };
+        let id = project.exprs[1].1.clone();
+        assert_eq!(
+            conjuncts(filter.expr.clone())[1],
+            ValueExpr::BinaryExpr(
+                BinaryOp::Eq,
+                Box::new(id),
+                Box::new(ValueExpr::Lit(Box::new(Lit::Int64(2))))
+            )
+        );
+    }
+
+    #[test]
+    fn keeps_filters() {
+        for query in [
+            "SELECT x.a FROM a AS x LEFT JOIN b AS y ON x.id = y.id WHERE x.id = y.id",
+            "SELECT x.a FROM a AS x, b AS y WHERE x.flag",
+            "SELECT s.k FROM (SELECT x.k FROM a AS x) AS s WHERE s.other = 1",
+            "SELECT s.k FROM (SELECT x.k FROM a AS x) AS s WHERE s IS NOT NULL",
+            "SELECT s.k FROM (SELECT DISTINCT x.k FROM a AS x) AS s WHERE s.k = 1",
+        ] {
+            let rewritten = Rewriter::new()
+                .with_rule(PredicatePushdown)
+                .rewrite(lower(query));
+            assert!(rewritten.fired.is_empty(), "{query}");
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Traversals over the expressions and nested plans of [`BindingsOp`]s.
+
+use partiql_logical::{
+    BindingsOp, DmlOp, LogicalPlan, PathComponent, Pattern, ReturningColumn, UpdateChange,
+    ValueExpr, VarRefType,
+};
+use partiql_value::BindingsName;
+use unicase::UniCase;
+
+macro_rules! expr_children {
+    ($expr:expr, $out:ident, $($mut_:ident)?) => {
+        match $expr {
+            ValueExpr::UnExpr(_, e) => $out.push(&$($mut_)? **e),
+            ValueExpr::BinaryExpr(_, l, r) => {
+                $out.push(&$($mut_)? **l);
+                $out.push(&$($mut_)? **r);
+            }
+            ValueExpr::DynamicLookup(lookups) => $out.extend(&$($mut_)? **lookups),
+            ValueExpr::Path(root, components) => {
+                $out.push(&$($mut_)? **root);
+                for component in components {
+                    match component {
+                        PathComponent::KeyExpr(e) | PathComponent::IndexExpr(e) => {
+                            $out.push(&$($mut_)? **e)
+                        }
+                        PathComponent::Key(_)
+                        | PathComponent::Index(_)
+                        | PathComponent::ForEach
+                        | PathComponent::Unpivot => {}
+                    }
+                }
+            }
+            ValueExpr::TupleExpr(tuple) => {
+                $out.extend(&$($mut_)? tuple.attrs);
+                $out.extend(&$($mut_)? tuple.values);
+            }
+            ValueExpr::ListExpr(list) => $out.extend(&$($mut_)? list.elements),
+            ValueExpr::BagExpr(bag) => $out.extend(&$($mut_)? bag.elements),
+            ValueExpr::BetweenExpr(between) => {
+                $out.push(&$($mut_)? *between.value);
+                $out.push(&$($mut_)? *between.from);
+                $out.push(&$($mut_)? *between.to);
+            }
+            ValueExpr::PatternMatchExpr(pattern) => {
+                $out.push(&$($mut_)? *pattern.value);
+                if let Pattern::LikeNonStringNonLiteral(like) = &$($mut_)? pattern.pattern {
+                    $out.push(&$($mut_)? *like.pattern);
+                    $out.push(&$($mut_)? *like.escape);
+                }
+            }
+            ValueExpr::SimpleCase(case) => {
+                $out.push(&$($mut_)? *case.expr);
+                for (when, then) in &$($mut_)? case.cases {
+                    $out.push(&$($mut_)? **when);
+                    $out.push(&$($mut_)? **then);
+                }
+                if let Some(default) = &$($mut_)? case.default {
+                    $out.push(&$($mut_)? **default);
+                }
+            }
+            ValueExpr::SearchedCase(case) => {
+                for (when, then) in &$($mut_)? case.cases {
+                    $out.push(&$($mut_)? **when);
+                    $out.push(&$($mut_)? **then);
+                }
+                if let Some(default) = &$($mut_)? case.default {
+                    $out.push(&$($mut_)? **default);
+                }
+            }
+            ValueExpr::IsTypeExpr(is_type) => $out.push(&$($mut_)? *is_type.expr),
+            ValueExpr::CastExpr(cast) => $out.push(&$($mut_)? *cast.expr),
+            ValueExpr::NullIfExpr(null_if) => {
+                $out.push(&$($mut_)? *null_if.lhs);
+                $out.push(&$($mut_)? *null_if.rhs);
+            }
+            ValueExpr::CoalesceExpr(coalesce) => $out.extend(&$($mut_)? coalesce.elements),
+            ValueExpr::Call(call) => $out.extend(&$($mut_)? call.arguments),
+            ValueExpr::GraphMatch(graph_match) => $out.push(&$($mut_)? *graph_match.value),
+            ValueExpr::Lit(_)
+            | ValueExpr::VarRef(_, _)
+            | ValueExpr::Parameter(_)
+            | ValueExpr::SubQueryExpr(_) => {}
+        }
+    };
+}
+
+macro_rules! op_exprs {
+    ($op:expr, $out:ident, $recurse:ident, $($mut_:ident)?) => {
+        match $op {
+            BindingsOp::Scan(scan) => $out.push(&$($mut_)? scan.expr),
+            BindingsOp::Pivot(pivot) => {
+                $out.push(&$($mut_)? pivot.key);
+                $out.push(&$($mut_)? pivot.value);
+            }
+            BindingsOp::Unpivot(unpivot) => $out.push(&$($mut_)? unpivot.expr),
+            BindingsOp::Filter(filter) => $out.push(&$($mut_)? filter.expr),
+            BindingsOp::Having(having) => $out.push(&$($mut_)? having.expr),
+            BindingsOp::OrderBy(order_by) => {
+                $out.extend((&$($mut_)? order_by.specs).into_iter().map(|spec| &$($mut_)? spec.expr))
+            }
+            BindingsOp::LimitOffset(limit_offset) => {
+                $out.extend(&$($mut_)? limit_offset.limit);
+                $out.extend(&$($mut_)? limit_offset.offset);
+            }
+            BindingsOp::Join(join) => {
+                $recurse(&$($mut_)? *join.left, $out);
+                $recurse(&$($mut_)? *join.right, $out);
+                $out.extend(&$($mut_)? join.on);
+                $out.extend((&$($mut_)? join.using).into_iter().map(|(_, e)| e));
+            }
+            BindingsOp::Project(project) => {
+                $out.extend((&$($mut_)? project.exprs).into_iter().map(|(_, e)| e))
+            }
+            BindingsOp::Let(let_) => {
+                $out.extend((&$($mut_)? let_.bindings).into_iter().map(|(_, e)| e))
+            }
+            BindingsOp::ProjectValue(project) => $out.push(&$($mut_)? project.expr),
+            BindingsOp::ExprQuery(query) => $out.push(&$($mut_)? query.expr),
+            BindingsOp::GroupBy(group_by) => {
+                $out.extend((&$($mut_)? group_by.exprs).into_iter().map(|(_, e)| e));
+                $out.extend(
+                    (&$($mut_)? group_by.aggregate_exprs).into_iter().map(|agg| &$($mut_)? agg.expr),
+                );
+            }
+            BindingsOp::Window(window) => {
+                for expr in &$($mut_)? window.exprs {
+                    $out.extend(&$($mut_)? expr.args);
+                    $out.extend(&$($mut_)? expr.partition_by);
+                    $out.extend((&$($mut_)? expr.order_by).into_iter().map(|spec| &$($mut_)? spec.expr));
+                }
+            }
+            BindingsOp::Dml(dml) => {
+                match &$($mut_)? dml.op {
+                    DmlOp::Insert {
+                        values,
+                        on_conflict,
+                    } => {
+                        $out.push(values);
+                        $out.extend(on_conflict);
+                    }
+                    DmlOp::Update { filter, changes } => {
+                        $out.extend(filter);
+                        for change in changes {
+                            let path = match change {
+                                UpdateChange::Set(path, value) => {
+                                    $out.push(value);
+                                    path
+                                }
+                                UpdateChange::Remove(path) => path,
+                            };
+                            for component in path {
+                                match component {
+                                    PathComponent::KeyExpr(e) | PathComponent::IndexExpr(e) => {
+                                        $out.push(&$($mut_)? **e)
+                                    }
+                                    PathComponent::Key(_)
+                                    | PathComponent::Index(_)
+                                    | PathComponent::ForEach
+                                    | PathComponent::Unpivot => {}
+                                }
+                            }
+                        }
+                    }
+                    DmlOp::Delete { filter } => $out.extend(filter),
+                }
+                for elem in &$($mut_)? dml.returning {
+                    if let ReturningColumn::Expr(_, e) = &$($mut_)? elem.column {
+                        $out.push(e);
+                    }
+                }
+            }
+            BindingsOp::BagOp(_)
+            | BindingsOp::ProjectAll(_)
+            | BindingsOp::Distinct
+            | BindingsOp::With(_)
+            | BindingsOp::Exclude(_)
+            | BindingsOp::Sink => {}
+        }
+    };
+}
+
+/// Returns the direct sub-expressions of `expr`. The plans of sub-queries are not included.
+pub(crate) fn children(expr: &ValueExpr) -> Vec<&ValueExpr> {
+    let mut out = vec![];
+    expr_children!(expr, out,);
+    out
+}
+
+/// Returns the direct sub-expressions of `expr`. The plans of sub-queries are not included.
+pub(crate) fn children_mut(expr: &mut ValueExpr) -> Vec<&mut ValueExpr> {
+    let mut out = vec![];
+    expr_children!(expr, out, mut);
+    out
+}
+
+/// Returns the expressions of `op`, including those of the operands of a [`BindingsOp::Join`].
+pub(crate) fn op_exprs(op: &BindingsOp) -> Vec<&ValueExpr> {
+    fn collect<'a>(op: &'a BindingsOp, out: &mut Vec<&'a ValueExpr>) {
+        op_exprs!(op, out, collect,);
+    }
+    let mut out = vec![];
+    collect(op, &mut out);
+    out
+}
+
+/// Returns the expressions of `op`, including those of the operands of a [`BindingsOp::Join`].
+pub(crate) fn op_exprs_mut(op: &mut BindingsOp) -> Vec<&mut ValueExpr> {
+    fn collect<'a>(op: &'a mut BindingsOp, out: &mut Vec<&'a mut ValueExpr>) {
+        op_exprs!(op, out, collect, mut);
+    }
+    let mut out = vec![];
+    collect(op, &mut out);
+    out
+}
+
+/// Returns the plans nested directly in `op`, i.e., those of its sub-queries and `WITH` clauses.
+pub(crate) fn subplans_mut(op: &mut BindingsOp) -> Vec<&mut LogicalPlan<BindingsOp>> {
+    fn from_expr<'a>(expr: &'a mut ValueExpr, out: &mut Vec<&'a mut LogicalPlan<BindingsOp>>) {
+        match expr {
+            ValueExpr::SubQueryExpr(subquery) => out.push(&mut subquery.plan),
+            other => children_mut(other)
+                .into_iter()
+                .for_each(|child| from_expr(child, out)),
+        }
+    }
+
+    let mut out = vec![];
+    match op {
+        BindingsOp::With(with) => {
+            for cte in &mut with.ctes {
+                out.push(&mut cte.plan);
+                if let Some(recursive) = &mut cte.recursive {
+                    out.push(&mut recursive.plan);
+                }
+            }
+            out.push(&mut with.body);
+        }
+        other => {
+            for expr in op_exprs_mut(other) {
+                from_expr(expr, &mut out);
+            }
+        }
+    }
+    out
+}
+
+/// Collects the names of the local variables referenced by `expr` into `vars`.
+///
+/// Returns `false` if `expr` contains a sub-query or graph match, whose references are not
+/// tracked.
+pub(crate) fn local_vars<'a>(
+    expr: &'a ValueExpr,
+    vars: &mut Vec<&'a BindingsName<'static>>,
+) -> bool {
+    match expr {
+        ValueExpr::SubQueryExpr(_) | ValueExpr::GraphMatch(_) => false,
+        ValueExpr::VarRef(name, VarRefType::Local) => {
+            vars.push(name);
+            true
+        }
+        other => children(other)
+            .into_iter()
+            .all(|child| local_vars(child, vars)),
+    }
+}
+
+/// Returns the names of the variables bound by a `FROM` source `op`, or `None` if `op` is not a
+/// `FROM` source.
+pub(crate) fn bound_vars(op: &BindingsOp) -> Option<Vec<&str>> {
+    match op {
+        BindingsOp::Scan(scan) => Some(
+            std::iter::once(scan.as_key.as_str())
+                .chain(scan.at_key.as_deref())
+                .collect(),
+        ),
+        BindingsOp::Unpivot(unpivot) => Some(
+            std::iter::once(unpivot.as_key.as_str())
+                .chain(unpivot.at_key.as_deref())
+                .collect(),
+        ),
+        BindingsOp::Join(join) => {
+            let mut vars = bound_vars(&join.left)?;
+            vars.extend(bound_vars(&join.right)?);
+            vars.extend(join.using.iter().map(|(name, _)| name.as_str()));
+            Some(vars)
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the variable reference `name` refers to the variable `var`.
+pub(crate) fn refers_to(name: &BindingsName<'_>, var: &str) -> bool {
+    match name {
+        BindingsName::CaseSensitive(n) => n == var,
+        BindingsName::CaseInsensitive(n) => UniCase::new(n.as_ref()) == UniCase::new(var),
+    }
+}
+
+/// If `expr` is a reference to an attribute of the variable `var` (i.e., `var.attr...`), returns
+/// the name of that attribute and the remaining path components.
+pub(crate) fn attr_ref<'a>(
+    expr: &'a ValueExpr,
+    var: &str,
+) -> Option<(&'a BindingsName<'static>, &'a [PathComponent])> {
+    let ValueExpr::Path(root, components) = expr else {
+        return None;
+    };
+    let (PathComponent::Key(attr), rest) = components.split_first()? else {
+        return None;
+    };
+    is_var_ref(root, var).then_some((attr, rest))
+}
+
+/// Returns whether `expr` is a reference to the local variable `var`, possibly in a
+/// [`ValueExpr::DynamicLookup`] that falls back to a global of the same name.
+pub(crate) fn is_var_ref(expr: &ValueExpr, var: &str) -> bool {
+    match expr {
+        ValueExpr::VarRef(name, VarRefType::Local) => refers_to(name, var),
+        ValueExpr::DynamicLookup(lookups) => match lookups.as_slice() {
+            [ValueExpr::VarRef(local, VarRefType::Local), ValueExpr::VarRef(global, VarRefType::Global)] => {
+                refers_to(local, var) && local == global
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
@@ -25,7 +25,7 @@ pub trait DynBoxedVariantTypeFactory {
 }
 
 pub type BoxedVariantTypeTag = Box<dyn BoxedVariantType>;
-pub trait BoxedVariantType: Debug + DynClone {
+pub trait BoxedVariantType: Debug + DynClone + Send + Sync {
     fn construct(&self, bytes: Vec<u8>) -> BoxedVariantResult<DynBoxedVariant>;
     fn name(&self) -> &'static str;
 
@@ -71,7 +71,7 @@ where
 pub type DynBoxedVariant = Box<dyn BoxedVariant>;
 #[cfg_attr(feature = "serde", typetag::serde)]
 pub trait BoxedVariant:
-    Display + Debug + DynHash + DynClone + Datum<Value> + DatumLower<Value>
+    Display + Debug + DynHash + DynClone + Datum<Value> + DatumLower<Value> + Send + Sync
 {
     fn type_tag(&self) -> BoxedVariantTypeTag;
 
@@ -2,47 +2,70 @@ use crate::Value;
 use lasso::{Key, Rodeo, RodeoReader, Spur};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
-use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub enum Graph {
-    Simple(Rc<SimpleGraph>),
+    Simple(Arc<SimpleGraph>),
+}
+
+/// The structure of a [`Graph`], by which graphs are compared, hashed and serialized.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum GraphRepr<'a> {
+    Simple(SimpleGraphRepr<'a>),
+}
+
+impl Graph {
+    fn repr(&self) -> GraphRepr<'_> {
+        match self {
+            Graph::Simple(g) => GraphRepr::Simple(g.repr()),
+        }
+    }
 }
 
 #[cfg(feature = "serde")]
 impl Serialize for Graph {
-    fn serialize<S>(&self, _: S) -> Result<S::Ok, S::Error>
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        todo!("Serialize for Graph")
+        self.repr().serialize(serializer)
     }
 }
 
 #[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Graph {
-    fn deserialize<D>(_: D) -> Result<Self, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        todo!("Deserialize for Graph")
+        match GraphRepr::deserialize(deserializer)? {
+            GraphRepr::Simple(repr) => SimpleGraph::from_repr(repr)
+                .map(|g| Graph::Simple(Arc::new(g)))
+                .map_err(serde::de::Error::custom),
+        }
     }
 }
 
 impl Hash for Graph {
-    fn hash<H: Hasher>(&self, _state: &mut H) {
-        todo!("Hash for Graph")
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.repr().hash(state);
     }
 }
 
 impl Eq for Graph {}
 impl PartialEq for Graph {
-    fn eq(&self, _other: &Self) -> bool {
-        todo!("PartialEq for Graph")
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Graph::Simple(l), Graph::Simple(r)) if Arc::ptr_eq(l, r) => true,
+            _ => self.repr() == other.repr(),
+        }
     }
 }
 
@@ -53,8 +76,11 @@ impl PartialOrd for Graph {
 }
 
 impl Ord for Graph {
-    fn cmp(&self, _other: &Self) -> Ordering {
-        todo!("Ord for Graph")
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Graph::Simple(l), Graph::Simple(r)) if Arc::ptr_eq(l, r) => Ordering::Equal,
+            _ => self.repr().cmp(&other.repr()),
+        }
     }
 }
 
@@ -178,11 +204,7 @@ impl SimpleGraph {
         let (ids, labels, values) = node_specs;
         assert_eq!(ids.len(), labels.len());
         assert_eq!(ids.len(), values.len());
-        for ((id, labels), value) in ids
-            .into_iter()
-            .zip(labels.into_iter())
-            .zip(values.into_iter())
-        {
+        for ((id, labels), value) in ids.into_iter().zip(labels).zip(values) {
             let nid = node_ids.get_or_intern(id);
             let labels: HashSet<_> = labels
                 .into_iter()
@@ -204,11 +226,7 @@ impl SimpleGraph {
         assert_eq!(ids.len(), labels.len());
         assert_eq!(ids.len(), ends.len());
         assert_eq!(ids.len(), values.len());
-        for (((id, labels), edge_spec), value) in ids
-            .into_iter()
-            .zip(labels.into_iter())
-            .zip(ends.into_iter())
-            .zip(values.into_iter())
+        for (((id, labels), edge_spec), value) in ids.into_iter().zip(labels).zip(ends).zip(values)
         {
             let eid = edge_ids.get_or_intern(id);
             let labels: HashSet<_> = labels
@@ -246,3 +264,117 @@ impl SimpleGraph {
         }
     }
 }
+
+/// The structure of a [`SimpleGraph`]: its elements in order of id, with their labels by name
+/// (in sorted order), and the ends of its edges (with undirected edges' ends in sorted order), as
+/// `(node, edge, node)` triples sorted by edge.
+///
+/// Two graphs are equal iff their structures are equal, i.e., iff they have the same elements
+/// under the same ids, regardless of the interning of their labels.
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SimpleGraphRepr<'a> {
+    nodes: Vec<GElemRepr<'a>>,
+    edges: Vec<GElemRepr<'a>>,
+    directed: Vec<(usize, usize, usize)>,
+    undirected: Vec<(usize, usize, usize)>,
+}
+
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct GElemRepr<'a> {
+    labels: Vec<Cow<'a, str>>,
+    value: Option<Cow<'a, Value>>,
+}
+
+impl SimpleGraph {
+    fn repr(&self) -> SimpleGraphRepr<'_> {
+        fn elem<'a>(elem: &'a GElem, reader: &'a RodeoReader) -> GElemRepr<'a> {
+            let mut labels: Vec<_> = elem
+                .labels
+                .0
+                .iter()
+                .map(|l| Cow::Borrowed(reader.resolve(&l.0)))
+                .collect();
+            labels.sort();
+            GElemRepr {
+                labels,
+                value: elem.value.as_ref().map(Cow::Borrowed),
+            }
+        }
+        let ends = |ends: &[(GNodeId, GEdgeId, GNodeId)], directed: bool| {
+            let mut ends: Vec<_> = ends
+                .iter()
+                .map(|(l, e, r)| match directed || l.0 <= r.0 {
+                    true => (l.0, e.0, r.0),
+                    false => (r.0, e.0, l.0),
+                })
+                .collect();
+            ends.sort_by_key(|(_, e, _)| *e);
+            ends
+        };
+
+        SimpleGraphRepr {
+            nodes: self.nodes.iter().map(|n| elem(n, &self.labels)).collect(),
+            edges: self.edges.iter().map(|e| elem(e, &self.labels)).collect(),
+            directed: ends(&self.g_dir, true),
+            undirected: ends(&self.g_undir, false),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    fn from_repr(repr: SimpleGraphRepr<'_>) -> Result<Self, String> {
+        let SimpleGraphRepr {
+            nodes,
+            edges,
+            directed,
+            undirected,
+        } = repr;
+
+        let mut label_ids = Rodeo::default();
+        let mut elems = |elems: Vec<GElemRepr<'_>>| {
+            elems
+                .into_iter()
+                .map(|GElemRepr { labels, value }| {
+                    let labels = labels
+                        .iter()
+                        .map(|l| GLabelId(label_ids.get_or_intern(l)))
+                        .collect();
+                    GElem::new(value.map(Cow::into_owned), GLabels(labels))
+                })
+                .collect::<Vec<_>>()
+        };
+        let nodes = elems(nodes);
+        let edges = elems(edges);
+
+        let mut seen = vec![false; edges.len()];
+        let mut ends = |ends: Vec<(usize, usize, usize)>| {
+            ends.into_iter()
+                .map(|(l, e, r)| {
+                    if l >= nodes.len() || r >= nodes.len() {
+                        return Err(format!("edge {e} has an end which is not a node"));
+                    }
+                    match seen.get_mut(e) {
+                        Some(seen) if !*seen => *seen = true,
+                        Some(_) => return Err(format!("edge {e} has more than one pair of ends")),
+                        None => return Err(format!("edge {e} is not an edge")),
+                    }
+                    Ok((GNodeId(l), GEdgeId(e), GNodeId(r)))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let g_dir = ends(directed)?;
+        let g_undir = ends(undirected)?;
+        if let Some(e) = seen.iter().position(|seen| !seen) {
+            return Err(format!("edge {e} has no ends"));
+        }
+
+        Ok(SimpleGraph {
+            nodes,
+            edges,
+            g_dir,
+            g_undir,
+            labels: label_ids.into_reader(),
+        })
+    }
+}
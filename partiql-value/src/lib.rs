@@ -1256,4 +1256,70 @@ mod tests {
         assert_eq!(Ordering::Greater, null_sorted_v3.cmp(&null_sorted_v2));
         assert_eq!(Ordering::Equal, null_sorted_v1.cmp(&null_sorted_v3));
     }
+
+    /// A graph `(a:A:B {payload}) -[e:E]-> (b:C)`, with labels interned in the given order.
+    fn graph(labels: &[&str], payload: i64, undirected: bool) -> Value {
+        let interned: HashSet<String> = labels.iter().map(|l| l.to_string()).collect();
+        let nodes = (
+            vec!["a".to_string(), "b".to_string()],
+            vec![interned.clone(), HashSet::from(["C".to_string()])],
+            vec![Some(Value::from(payload)), None],
+        );
+        let ends = match undirected {
+            true => EdgeSpec::Undirected("b".to_string(), "a".to_string()),
+            false => EdgeSpec::Directed("a".to_string(), "b".to_string()),
+        };
+        let edges = (
+            vec!["e".to_string()],
+            vec![HashSet::from(["E".to_string()])],
+            vec![ends],
+            vec![None],
+        );
+        Graph::Simple(std::sync::Arc::new(SimpleGraph::from_spec(nodes, edges))).into()
+    }
+
+    #[test]
+    fn graph_equality_and_ordering() {
+        let g1 = graph(&["A", "B"], 1, false);
+        let g2 = graph(&["B", "A"], 1, false);
+        assert_eq!(g1, g2);
+        assert_eq!(Ordering::Equal, g1.cmp(&g2));
+        assert_eq!(HashSet::from([g1.clone(), g2.clone()]).len(), 1);
+
+        let g3 = graph(&["A", "B"], 2, false);
+        let g4 = graph(&["A"], 1, false);
+        let g5 = graph(&["A", "B"], 1, true);
+        let g6 = graph(&["B", "A"], 1, true);
+        assert_ne!(g1, g3);
+        assert_ne!(g1, g4);
+        assert_ne!(g1, g5);
+        assert_eq!(g5, g6);
+        assert_eq!(HashSet::from([&g1, &g2, &g3, &g4, &g5, &g6]).len(), 4);
+
+        let mut sorted = vec![&g5, &g3, &g1, &g4];
+        sorted.sort();
+        let mut reversed = vec![&g4, &g1, &g3, &g5];
+        reversed.sort();
+        assert_eq!(sorted, reversed);
+        assert_eq!(Ordering::Less, g1.cmp(&g3));
+        assert_eq!(Ordering::Greater, g3.cmp(&g1));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn graph_serde() {
+        for g in [graph(&["A", "B"], 1, false), graph(&["A"], 2, true)] {
+            let json = serde_json::to_string(&g).expect("serialize");
+            assert_eq!(
+                g,
+                serde_json::from_str::<Value>(&json).expect("deserialize")
+            );
+        }
+
+        let dangling = r#"{"Graph":{"Simple":{"nodes":[],"edges":[{"labels":[],"value":null}],"directed":[[0,0,1]],"undirected":[]}}}"#;
+        let err = serde_json::from_str::<Value>(dangling).expect_err("dangling edge");
+        assert!(err
+            .to_string()
+            .contains("edge 0 has an end which is not a node"));
+    }
 }
@@ -3,7 +3,7 @@ use std::time::Duration;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use itertools::Itertools;
-use partiql_catalog::catalog::{Catalog, PartiqlCatalog, PartiqlSharedCatalog, SharedCatalog};
+use partiql_catalog::catalog::{PartiqlCatalog, PartiqlSharedCatalog, SharedCatalog};
 use partiql_catalog::context::SystemContext;
 use partiql_eval::env::basic::MapBindings;
 use partiql_eval::eval::{BasicContext, EvalPlan};
@@ -339,7 +339,7 @@ const QUERY_30: &str = "
             ";
 
 #[inline]
-fn parse(text: &str) -> ParserResult {
+fn parse(text: &str) -> ParserResult<'_> {
     Parser::default().parse(text)
 }
 #[inline]
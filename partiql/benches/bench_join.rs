@@ -2,7 +2,7 @@ use std::ops::Deref;
 use std::time::Duration;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use partiql_catalog::catalog::{Catalog, PartiqlCatalog, PartiqlSharedCatalog, SharedCatalog};
+use partiql_catalog::catalog::{PartiqlCatalog, PartiqlSharedCatalog, SharedCatalog};
 use partiql_catalog::context::SystemContext;
 use partiql_eval::env::basic::MapBindings;
 use partiql_eval::eval::{BasicContext, EvalPlan};
@@ -39,7 +39,7 @@ fn tables() -> MapBindings<Value> {
 }
 
 #[inline]
-fn parse(text: &str) -> ParserResult {
+fn parse(text: &str) -> ParserResult<'_> {
     Parser::default().parse(text)
 }
 #[inline]
@@ -0,0 +1,352 @@
+//! A high-level engine for preparing and executing `PartiQL` queries.
+
+use partiql_catalog::catalog::{PartiqlCatalog, PartiqlSharedCatalog, SharedCatalog};
+use partiql_catalog::context::SystemContext;
+use partiql_catalog::extension::Extension;
+use partiql_catalog::mutation::MutationSink;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::error::PlanErr;
+use partiql_eval::eval::graph::named::NamedGraphs;
+use partiql_eval::eval::{BasicContext, EvalPlan, Parameters};
+use partiql_eval::plan::{EvaluationMode, EvaluatorPlanner, ExecutionMode};
+use partiql_ir::IrPlan;
+use partiql_irgen::{IrGenerator, IrLoader};
+use partiql_logical::{BindingsOp, LogicalPlan};
+use partiql_logical_planner::LogicalPlanner;
+use partiql_parser::{Parsed, Parser};
+use partiql_rewriter::Rewriter;
+use partiql_value::{DateTime, Value};
+
+use crate::error::{Error, Result};
+
+/// Prepares and executes `PartiQL` queries against a catalog of functions and types.
+///
+/// An `Engine` runs each query through the full pipeline of parsing, lowering to a logical plan,
+/// optionally rewriting that plan, compiling it to an evaluation plan and evaluating that plan,
+/// reporting failures in any stage as an [`Error`](crate::Error).
+///
+/// # Example
+///
+/// ```
+/// use partiql::Engine;
+/// use partiql_value::{bag, tuple, Value};
+///
+/// let engine = Engine::default();
+/// let stmt = engine.prepare("SELECT VALUE x.a FROM data AS x WHERE x.a > 1").unwrap();
+///
+/// let mut globals = partiql_eval::env::basic::MapBindings::default();
+/// globals.insert("data", bag![tuple![("a", 1)], tuple![("a", 2)]].into());
+/// assert_eq!(stmt.execute(globals).unwrap(), Value::from(bag![2]));
+/// ```
+#[derive(Debug)]
+pub struct Engine {
+    catalog: PartiqlSharedCatalog,
+    mode: EvaluationMode,
+    execution_mode: ExecutionMode,
+    rewriter: Option<Rewriter>,
+}
+
+impl Default for Engine {
+    fn default() -> Self {
+        Engine::builder().build()
+    }
+}
+
+impl Engine {
+    /// Creates an [`EngineBuilder`] for configuring an `Engine`.
+    #[must_use]
+    pub fn builder() -> EngineBuilder {
+        EngineBuilder::default()
+    }
+
+    /// The catalog against which queries are prepared.
+    #[must_use]
+    pub fn catalog(&self) -> &dyn SharedCatalog {
+        &self.catalog
+    }
+
+    /// The [`EvaluationMode`] in which prepared queries are evaluated.
+    #[must_use]
+    pub fn mode(&self) -> EvaluationMode {
+        self.mode
+    }
+
+    /// The [`ExecutionMode`] in which prepared queries are executed.
+    #[must_use]
+    pub fn execution_mode(&self) -> ExecutionMode {
+        self.execution_mode
+    }
+
+    /// The [`Rewriter`] applied to the logical plans of prepared queries, if any.
+    #[must_use]
+    pub fn rewriter(&self) -> Option<&Rewriter> {
+        self.rewriter.as_ref()
+    }
+
+    /// Parses, lowers, rewrites and compiles `query` into a [`PreparedStatement`].
+    pub fn prepare(&self, query: &str) -> Result<PreparedStatement> {
+        let parsed = Parser::default().parse(query)?;
+        let (logical, fired_rules) = self.logical_plan(&parsed)?;
+        let plan = self
+            .compile(&logical)
+            .map_err(|err| Error::planning(err, &parsed))?;
+        Ok(PreparedStatement { plan, fired_rules })
+    }
+
+    /// Parses, lowers and rewrites `query` into the serializable IR of its plan, which may be
+    /// prepared by [`Engine::prepare_ir`] in another process, without parsing the query again.
+    ///
+    /// ```
+    /// use partiql::Engine;
+    /// use partiql_value::{bag, tuple, Value};
+    ///
+    /// let ir = Engine::default().generate_ir("SELECT VALUE x.a FROM data AS x").unwrap();
+    /// // ... serialized, e.g., with `serde_json`, and sent to a worker which executes it
+    /// let stmt = Engine::default().prepare_ir(&ir).unwrap();
+    ///
+    /// let mut globals = partiql_eval::env::basic::MapBindings::default();
+    /// globals.insert("data", bag![tuple![("a", 1)]].into());
+    /// assert_eq!(stmt.execute(globals).unwrap(), Value::from(bag![1]));
+    /// ```
+    pub fn generate_ir(&self, query: &str) -> Result<IrPlan> {
+        let parsed = Parser::default().parse(query)?;
+        let (logical, _) = self.logical_plan(&parsed)?;
+        Ok(IrGenerator::new().generate(&logical))
+    }
+
+    /// Loads the IR of a plan generated by [`Engine::generate_ir`] against the engine's catalog and
+    /// compiles it into a [`PreparedStatement`].
+    pub fn prepare_ir(&self, ir: &IrPlan) -> Result<PreparedStatement> {
+        let logical = IrLoader::new(&self.catalog).load(ir)?;
+        let plan = self.compile(&logical)?;
+        Ok(PreparedStatement {
+            plan,
+            fired_rules: vec![],
+        })
+    }
+
+    fn logical_plan(
+        &self,
+        parsed: &Parsed<'_>,
+    ) -> Result<(LogicalPlan<BindingsOp>, Vec<&'static str>)> {
+        let logical = LogicalPlanner::new(&self.catalog)
+            .lower(parsed)
+            .map_err(|err| Error::lowering(err, parsed))?;
+        Ok(match &self.rewriter {
+            Some(rewriter) => {
+                let rewritten = rewriter.rewrite(logical);
+                (rewritten.plan, rewritten.fired)
+            }
+            None => (logical, vec![]),
+        })
+    }
+
+    fn compile(&self, logical: &LogicalPlan<BindingsOp>) -> std::result::Result<EvalPlan, PlanErr> {
+        EvaluatorPlanner::new(self.mode, &self.catalog)
+            .with_execution_mode(self.execution_mode)
+            .compile(logical)
+    }
+
+    /// Prepares and executes `query` once with the global bindings `globals`.
+    pub fn execute(&self, query: &str, globals: MapBindings<Value>) -> Result<Value> {
+        self.prepare(query)?.execute(globals)
+    }
+}
+
+/// Builder for an [`Engine`].
+#[derive(Debug)]
+pub struct EngineBuilder {
+    catalog: PartiqlCatalog,
+    mode: EvaluationMode,
+    execution_mode: ExecutionMode,
+    rewriter: Option<Rewriter>,
+}
+
+impl Default for EngineBuilder {
+    fn default() -> Self {
+        EngineBuilder {
+            catalog: PartiqlCatalog::default(),
+            mode: EvaluationMode::Permissive,
+            execution_mode: ExecutionMode::default(),
+            rewriter: None,
+        }
+    }
+}
+
+impl EngineBuilder {
+    /// Sets the [`EvaluationMode`] of the engine; defaults to [`EvaluationMode::Permissive`].
+    #[must_use]
+    pub fn mode(mut self, mode: EvaluationMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the [`ExecutionMode`] of the engine; defaults to [`ExecutionMode::Sequential`].
+    #[must_use]
+    pub fn execution_mode(mut self, execution_mode: ExecutionMode) -> Self {
+        self.execution_mode = execution_mode;
+        self
+    }
+
+    /// Sets the [`Rewriter`] applied to the logical plans of queries before they are compiled;
+    /// by default plans are not rewritten.
+    ///
+    /// ```
+    /// use partiql::Engine;
+    /// use partiql_rewriter::Rewriter;
+    ///
+    /// let engine = Engine::builder().rewriter(Rewriter::default()).build();
+    /// let stmt = engine.prepare("SELECT VALUE x FROM data AS x WHERE 1 < 2").unwrap();
+    /// assert_eq!(stmt.fired_rules(), ["constant_folding", "merge_filters"]);
+    /// ```
+    #[must_use]
+    pub fn rewriter(mut self, rewriter: Rewriter) -> Self {
+        self.rewriter = Some(rewriter);
+        self
+    }
+
+    /// Loads `extension` into the engine's catalog.
+    pub fn extension(mut self, extension: &dyn Extension) -> Result<Self> {
+        extension.load(&mut self.catalog)?;
+        Ok(self)
+    }
+
+    /// Creates the [`Engine`].
+    #[must_use]
+    pub fn build(self) -> Engine {
+        let EngineBuilder {
+            catalog,
+            mode,
+            execution_mode,
+            rewriter,
+        } = self;
+        Engine {
+            catalog: catalog.to_shared_catalog(),
+            mode,
+            execution_mode,
+            rewriter,
+        }
+    }
+}
+
+/// A query that has been compiled by [`Engine::prepare`] and may be executed repeatedly.
+#[derive(Debug)]
+pub struct PreparedStatement {
+    plan: EvalPlan,
+    fired_rules: Vec<&'static str>,
+}
+
+impl PreparedStatement {
+    /// Executes the statement with the global bindings `globals`.
+    pub fn execute(&self, globals: MapBindings<Value>) -> Result<Value> {
+        self.execution().globals(globals).execute()
+    }
+
+    /// Creates an [`Execution`] for configuring the global bindings, parameters, mutation sink and
+    /// graphs the statement is executed with.
+    ///
+    /// ```
+    /// use partiql::Engine;
+    /// use partiql_value::{bag, tuple, Value};
+    ///
+    /// let stmt = Engine::default().prepare("SELECT VALUE x.a + ? FROM data AS x").unwrap();
+    ///
+    /// let mut globals = partiql_eval::env::basic::MapBindings::default();
+    /// globals.insert("data", bag![tuple![("a", 1)], tuple![("a", 2)]].into());
+    /// let mut execution = stmt
+    ///     .execution()
+    ///     .globals(globals)
+    ///     .parameters(vec![Value::from(10)].into());
+    ///
+    /// let values: Vec<_> = execution.execute_iter().unwrap().collect();
+    /// assert_eq!(values.len(), 2);
+    /// assert_eq!(execution.execute().unwrap(), Value::from(bag![11, 12]));
+    /// ```
+    #[must_use]
+    pub fn execution<'u>(&self) -> Execution<'_, 'u> {
+        Execution {
+            plan: &self.plan,
+            ctx: BasicContext::new(
+                MapBindings::default(),
+                SystemContext {
+                    now: DateTime::from_system_now_utc(),
+                },
+            ),
+        }
+    }
+
+    /// The compiled evaluation plan of the statement.
+    #[must_use]
+    pub fn plan(&self) -> &EvalPlan {
+        &self.plan
+    }
+
+    /// The names of the rewrite rules that changed the statement's logical plan, in the order in
+    /// which they did so (see [`EngineBuilder::rewriter`]).
+    #[must_use]
+    pub fn fired_rules(&self) -> &[&'static str] {
+        &self.fired_rules
+    }
+}
+
+/// Builder for an execution of a [`PreparedStatement`], created by
+/// [`PreparedStatement::execution`].
+pub struct Execution<'s, 'u> {
+    plan: &'s EvalPlan,
+    ctx: BasicContext<'u>,
+}
+
+impl std::fmt::Debug for Execution<'_, '_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Execution")
+            .field("bindings", &self.ctx.bindings)
+            .field("parameters", &self.ctx.parameters)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'u> Execution<'_, 'u> {
+    /// Sets the global bindings of the execution; by default there are none.
+    #[must_use]
+    pub fn globals(mut self, globals: MapBindings<Value>) -> Self {
+        self.ctx.bindings = globals;
+        self
+    }
+
+    /// Binds `parameters` to the statement's parameters (i.e., `?` and `:name`).
+    #[must_use]
+    pub fn parameters(mut self, parameters: Parameters) -> Self {
+        self.ctx = self.ctx.with_parameters(parameters);
+        self
+    }
+
+    /// Applies the changes of a data modification statement (i.e., `INSERT`, `UPDATE` or
+    /// `DELETE`) through `sink`.
+    #[must_use]
+    pub fn mutation_sink(mut self, sink: &'u dyn MutationSink) -> Self {
+        self.ctx = self.ctx.with_mutation_sink(sink);
+        self
+    }
+
+    /// Evaluates `MATCH` expressions over variables named as one of `graphs` against that graph.
+    #[must_use]
+    pub fn graphs(mut self, graphs: &'u NamedGraphs) -> Self {
+        self.ctx = self.ctx.with_graphs(graphs);
+        self
+    }
+
+    /// Executes the statement, materializing its result.
+    pub fn execute(&mut self) -> Result<Value> {
+        self.ctx.errors.get_mut().clear();
+        let evaluated = self.plan.execute(&self.ctx)?;
+        Ok(evaluated.result)
+    }
+
+    /// Executes the statement lazily, returning an iterator over the values of its result (see
+    /// [`EvalPlan::execute_iter`]).
+    pub fn execute_iter(&mut self) -> Result<impl Iterator<Item = Result<Value>> + '_> {
+        self.ctx.errors.get_mut().clear();
+        let values = self.plan.execute_iter(&self.ctx)?;
+        Ok(values.map(|value| value.map_err(Error::from)))
+    }
+}
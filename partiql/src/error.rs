@@ -0,0 +1,171 @@
+//! [`Error`] type for the [`Engine`](crate::Engine).
+
+use std::fmt::{Display, Formatter};
+
+use partiql_ast_passes::error::{AstTransformError, AstTransformationError};
+use partiql_catalog::extension::ExtensionResultError;
+use partiql_common::syntax::line_offset_tracker::LineOffsetTracker;
+use partiql_common::syntax::location::{
+    BytePosition, LineAndCharPosition, LineAndColumn, Location,
+};
+use partiql_eval::error::{EvalErr, EvaluationError, PlanErr, PlanningError};
+use partiql_irgen::IrLoadError;
+use partiql_parser::{LineAndColumnParseError, Parsed, ParserError};
+use thiserror::Error;
+
+/// A syntax error located by line and column in the text of the query.
+pub type ParseError = LineAndColumnParseError<'static>;
+
+/// A lowering or planning error, located by the span of the query text from which it arose, if
+/// known.
+#[derive(Debug)]
+pub struct LocatedError<E> {
+    /// The error.
+    pub error: E,
+    /// The span of the query text at which the error occurred, if known.
+    pub location: Option<Location<LineAndColumn>>,
+}
+
+impl<E: Display> Display for LocatedError<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            None => write!(f, "{}", self.error),
+            Some(location) => write!(f, "{} at `{location}`", self.error),
+        }
+    }
+}
+
+/// Errors from preparing or executing a `PartiQL` query.
+///
+/// ### Notes
+/// This is marked `#[non_exhaustive]`, to reserve the right to add more variants in the future.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The query text is not syntactically valid.
+    #[error("Parse error: {}", join(.0))]
+    Parse(Vec<ParseError>),
+    /// The query could not be lowered into a logical plan.
+    #[error("Lowering error: {}", join(.0))]
+    Lower(Vec<LocatedError<AstTransformError>>),
+    /// The logical plan could not be compiled into an evaluation plan.
+    #[error("Planning error: {}", join(.0))]
+    Plan(Vec<LocatedError<PlanningError>>),
+    /// The query failed during evaluation.
+    #[error("Evaluation error: {}", join(.0))]
+    Eval(Vec<EvaluationError>),
+    /// An extension failed to load into the engine's catalog.
+    #[error("Extension error: {0}")]
+    Extension(#[from] ExtensionResultError),
+    /// The IR of a query plan could not be loaded against the engine's catalog.
+    #[error("IR error: {0}")]
+    Ir(#[from] IrLoadError),
+}
+
+/// General [`Result`] type for the [`Engine`](crate::Engine).
+pub type Result<T> = std::result::Result<T, Error>;
+
+fn join<E: Display>(errors: &[E]) -> String {
+    let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+    errors.join("; ")
+}
+
+impl From<ParserError<'_>> for Error {
+    fn from(err: ParserError<'_>) -> Self {
+        let ParserError {
+            text,
+            offsets,
+            errors,
+            ..
+        } = err;
+        let errors = errors
+            .into_iter()
+            .map(|e| {
+                e.map_loc(|loc| line_and_column(text, &offsets, loc))
+                    .into_owned()
+            })
+            .collect();
+        Error::Parse(errors)
+    }
+}
+
+impl Error {
+    /// Converts the lowering errors of the query `parsed`, located at the spans of its AST nodes.
+    pub(crate) fn lowering(err: AstTransformationError, parsed: &Parsed<'_>) -> Self {
+        let locations: Vec<_> = err
+            .located()
+            .map(|(_, node)| {
+                node.and_then(|id| parsed.locations.get(&id))
+                    .map(|loc| locate(parsed, loc))
+            })
+            .collect();
+        let errors = std::iter::zip(err.errors, locations)
+            .map(|(error, location)| LocatedError { error, location })
+            .collect();
+        Error::Lower(errors)
+    }
+
+    /// Converts the planning errors of the query `parsed`, located at the spans from which the
+    /// failing operators were lowered.
+    pub(crate) fn planning(err: PlanErr, parsed: &Parsed<'_>) -> Self {
+        let PlanErr { errors, locations } = err;
+        let locations = locations.into_iter().chain(std::iter::repeat(None));
+        let errors = std::iter::zip(errors, locations)
+            .map(|(error, location)| LocatedError {
+                error,
+                location: location.map(|loc| locate(parsed, &loc)),
+            })
+            .collect();
+        Error::Plan(errors)
+    }
+}
+
+/// Lowering errors without the query text are not located.
+impl From<AstTransformationError> for Error {
+    fn from(err: AstTransformationError) -> Self {
+        let errors = err.errors.into_iter().map(LocatedError::from).collect();
+        Error::Lower(errors)
+    }
+}
+
+/// Planning errors without the query text (e.g., of a plan loaded from IR) are not located.
+impl From<PlanErr> for Error {
+    fn from(err: PlanErr) -> Self {
+        let errors = err.errors.into_iter().map(LocatedError::from).collect();
+        Error::Plan(errors)
+    }
+}
+
+impl<E> From<E> for LocatedError<E> {
+    fn from(error: E) -> Self {
+        LocatedError {
+            error,
+            location: None,
+        }
+    }
+}
+
+impl From<EvalErr> for Error {
+    fn from(err: EvalErr) -> Self {
+        Error::Eval(err.errors)
+    }
+}
+
+/// Converts the byte range `loc` of the text of `parsed` into a range of lines and columns.
+fn locate(parsed: &Parsed<'_>, loc: &Location<BytePosition>) -> Location<LineAndColumn> {
+    Location {
+        start: line_and_column(parsed.text, &parsed.offsets, loc.start),
+        end: line_and_column(parsed.text, &parsed.offsets, loc.end),
+    }
+}
+
+/// Converts the byte position `loc` in `text` into a line and column; positions which do not
+/// fall on a character boundary are clamped to the end of the text.
+fn line_and_column(text: &str, offsets: &LineOffsetTracker, loc: BytePosition) -> LineAndColumn {
+    let end = BytePosition::from(text.len());
+    offsets
+        .at(text, loc)
+        .or_else(|_| offsets.at(text, end))
+        .unwrap_or(LineAndCharPosition::new(0, 0))
+        .into()
+}
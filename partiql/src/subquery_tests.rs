@@ -19,7 +19,7 @@ mod tests {
         catalog: &dyn SharedCatalog,
         logical: &LogicalPlan<partiql_logical::BindingsOp>,
         bindings: MapBindings<Value>,
-        ctx_vals: &[(String, &(dyn Any))],
+        ctx_vals: &[(String, &dyn Any)],
     ) -> Result<Value, EvalErr> {
         let mut planner =
             partiql_eval::plan::EvaluatorPlanner::new(EvaluationMode::Strict, catalog);
@@ -52,6 +52,7 @@ mod tests {
                 expr: partiql_logical::ValueExpr::Lit(data),
                 as_key: "foo".into(),
                 at_key: None,
+                columns: None,
             }));
         let project_value_op_id = sub_query.add_operator(
             partiql_logical::BindingsOp::ProjectValue(partiql_logical::ProjectValue {
@@ -74,6 +75,7 @@ mod tests {
                 }),
                 as_key: "_1".into(),
                 at_key: None,
+                columns: None,
             }));
 
         let project_value_op_id =
@@ -109,6 +111,7 @@ mod tests {
                 ),
                 as_key: "foo".into(),
                 at_key: None,
+                columns: None,
             }));
         let project_value_op_id = sub_query.add_operator(
             partiql_logical::BindingsOp::ProjectValue(partiql_logical::ProjectValue {
@@ -131,6 +134,7 @@ mod tests {
                 }),
                 as_key: "_1".into(),
                 at_key: None,
+                columns: None,
             }));
 
         let project_value_op_id =
@@ -0,0 +1,314 @@
+use assert_matches::assert_matches;
+use partiql::{Engine, Error};
+use partiql_catalog::aggregate_fn::{
+    AggregateFnCallDef, AggregateFnExpr, AggregateFnExprResult, AggregateFunction,
+    SimpleAggregateFunctionInfo,
+};
+use partiql_catalog::catalog::Catalog;
+use partiql_catalog::extension::{Extension, ExtensionResultError};
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::{EvaluationMode, ExecutionMode};
+use partiql_value::{bag, list, tuple, Bag, Value};
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+
+#[derive(Debug)]
+struct MedianExtension;
+
+impl Extension for MedianExtension {
+    fn name(&self) -> String {
+        "median".into()
+    }
+
+    fn load(&self, catalog: &mut dyn Catalog) -> Result<(), ExtensionResultError> {
+        let call_defs = [
+            AggregateFnCallDef {
+                names: vec!["median"],
+                expr: Box::new(Median),
+            },
+            AggregateFnCallDef {
+                names: vec!["string_agg"],
+                expr: Box::new(StringAgg),
+            },
+        ];
+        for call_def in call_defs {
+            let info = SimpleAggregateFunctionInfo::new(call_def);
+            if let Err(e) = catalog.add_aggregate_function(AggregateFunction::new(Box::new(info))) {
+                return Err(ExtensionResultError::LoadError(e.into()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The median of a group's numbers, e.g. `median(x)` of `<<1, 4, 2, 3>>` is `(2 + 3) / 2`.
+#[derive(Debug, Clone)]
+struct Median;
+
+impl AggregateFnExpr for Median {
+    fn init(&self) -> Value {
+        Value::from(list![])
+    }
+
+    fn accumulate(
+        &self,
+        state: &mut Value,
+        value: &Value,
+        _args: &[Value],
+    ) -> Result<(), ExtensionResultError> {
+        match (state, value.is_number()) {
+            (Value::List(values), true) => {
+                values.push(value.clone());
+                Ok(())
+            }
+            _ => Err(ExtensionResultError::DataError(
+                format!("median of non-numeric value {value:?}").into(),
+            )),
+        }
+    }
+
+    fn merge(&self, state: &mut Value, other: Value) -> Result<(), ExtensionResultError> {
+        if let Value::List(other) = other {
+            for value in other.iter() {
+                self.accumulate(state, value, &[])?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finalize(&self, state: Value) -> AggregateFnExprResult {
+        let Value::List(values) = state else {
+            return Ok(Value::Missing);
+        };
+        let mut values = values.to_vec();
+        values.sort();
+        let mid = values.len() / 2;
+        Ok(match values.len() {
+            0 => Value::Null,
+            n if n % 2 == 1 => values.swap_remove(mid),
+            _ => &(&values[mid - 1] + &values[mid]) / &Value::from(2),
+        })
+    }
+}
+
+/// The concatenation of a group's strings, separated by the call's second argument, e.g.
+/// `string_agg(x, ', ')`.
+///
+/// The state of a group is the list of the concatenation and the delimiter, which joins the
+/// concatenations of the group's parts when they are merged.
+#[derive(Debug, Clone)]
+struct StringAgg;
+
+impl AggregateFnExpr for StringAgg {
+    fn init(&self) -> Value {
+        Value::Null
+    }
+
+    fn accumulate(
+        &self,
+        state: &mut Value,
+        value: &Value,
+        args: &[Value],
+    ) -> Result<(), ExtensionResultError> {
+        let (Value::String(value), [Value::String(delimiter)]) = (value, args) else {
+            return Err(ExtensionResultError::DataError(
+                format!("string_agg of non-string value {value:?} or delimiter {args:?}").into(),
+            ));
+        };
+        let acc = match state {
+            Value::List(state) => match state.get(0) {
+                Some(Value::String(acc)) => format!("{acc}{delimiter}{value}"),
+                _ => value.to_string(),
+            },
+            _ => value.to_string(),
+        };
+        *state = Value::from(list![acc, delimiter.to_string()]);
+        Ok(())
+    }
+
+    fn merge(&self, state: &mut Value, other: Value) -> Result<(), ExtensionResultError> {
+        let (Value::List(acc), Value::List(other)) = (&*state, &other) else {
+            if !matches!(other, Value::Null) {
+                *state = other;
+            }
+            return Ok(());
+        };
+        let (Some(Value::String(acc)), Some(Value::String(delimiter)), Some(Value::String(rest))) =
+            (acc.get(0), acc.get(1), other.get(0))
+        else {
+            return Err(ExtensionResultError::DataError(
+                format!("string_agg of invalid states {state:?} and {other:?}").into(),
+            ));
+        };
+        *state = Value::from(list![
+            format!("{acc}{delimiter}{rest}"),
+            delimiter.to_string()
+        ]);
+        Ok(())
+    }
+
+    fn finalize(&self, state: Value) -> AggregateFnExprResult {
+        Ok(match state {
+            Value::List(state) => state.get(0).cloned().unwrap_or(Value::Null),
+            state => state,
+        })
+    }
+
+    fn arity(&self) -> RangeInclusive<usize> {
+        2..=2
+    }
+}
+
+fn engine(mode: EvaluationMode) -> Engine {
+    Engine::builder()
+        .mode(mode)
+        .extension(&MedianExtension)
+        .expect("extension")
+        .build()
+}
+
+fn globals() -> MapBindings<Value> {
+    let mut globals = MapBindings::default();
+    globals.insert(
+        "t",
+        bag![
+            tuple![("g", "a"), ("v", 1)],
+            tuple![("g", "a"), ("v", 5)],
+            tuple![("g", "a"), ("v", 5)],
+            tuple![("g", "a"), ("v", Value::Null)],
+            tuple![("g", "b"), ("v", 4)],
+            tuple![("g", "b"), ("v", 2)],
+        ]
+        .into(),
+    );
+    globals
+}
+
+#[test]
+fn group_by() {
+    let res = engine(EvaluationMode::Strict)
+        .execute(
+            "SELECT t.g AS g, median(t.v) AS m, MEDIAN(DISTINCT t.v) AS d \
+             FROM t GROUP BY t.g ORDER BY t.g",
+            globals(),
+        )
+        .expect("execute");
+    assert_eq!(
+        res,
+        Value::from(list![
+            tuple![("g", "a"), ("m", 5), ("d", 3)],
+            tuple![("g", "b"), ("m", 3), ("d", 3)],
+        ])
+    );
+}
+
+#[test]
+fn further_arguments() {
+    let res = engine(EvaluationMode::Strict)
+        .execute(
+            "SELECT t.g AS g, string_agg(CAST(t.v AS STRING), '-' || t.g) AS s \
+             FROM t WHERE t.v IS NOT NULL GROUP BY t.g ORDER BY t.g",
+            globals(),
+        )
+        .expect("execute");
+    assert_eq!(
+        res,
+        Value::from(list![
+            tuple![("g", "a"), ("s", "1-a5-a5")],
+            tuple![("g", "b"), ("s", "4-b2")],
+        ])
+    );
+
+    let res = engine(EvaluationMode::Strict)
+        .execute(
+            "SELECT VALUE string_agg(DISTINCT x, ', ') FROM << 'a', 'b', 'a' >> AS x",
+            Default::default(),
+        )
+        .expect("execute");
+    assert_eq!(res, Value::from(bag!["a, b"]));
+}
+
+#[test]
+fn implicit_group() {
+    let engine = engine(EvaluationMode::Strict);
+    let res = engine
+        .execute("SELECT median(t.v) AS m FROM t WHERE t.g = 'b'", globals())
+        .expect("execute");
+    assert_eq!(res, Value::from(bag![tuple![("m", 3)]]));
+
+    // `NULL`s are not accumulated, so the group's state is the initial one
+    let res = engine
+        .execute(
+            "SELECT median(t.v) AS m FROM t WHERE t.v IS NULL",
+            globals(),
+        )
+        .expect("execute");
+    assert_eq!(res, Value::from(bag![tuple![("m", Value::Null)]]));
+}
+
+#[test]
+fn window() {
+    let res = engine(EvaluationMode::Strict)
+        .execute(
+            "SELECT t.v AS v, median(t.v) OVER (PARTITION BY t.g) AS m \
+             FROM t WHERE t.g = 'b' ORDER BY t.v",
+            globals(),
+        )
+        .expect("execute");
+    assert_eq!(
+        res,
+        Value::from(list![
+            tuple![("v", 2), ("m", 3)],
+            tuple![("v", 4), ("m", 3)]
+        ])
+    );
+}
+
+#[test]
+fn accumulate_error() {
+    let query = "SELECT VALUE median(x) FROM <<1, 'two'>> AS x";
+    let res = engine(EvaluationMode::Strict).execute(query, Default::default());
+    assert_matches!(res, Err(Error::Eval(_)));
+
+    let res = engine(EvaluationMode::Permissive).execute(query, Default::default());
+    assert_matches!(res, Ok(_));
+}
+
+#[test]
+fn invalid_calls() {
+    let engine = engine(EvaluationMode::Strict);
+    let res = engine.prepare("SELECT median(t.v, t.g) AS m FROM t");
+    assert_matches!(res, Err(Error::Lower(_)));
+
+    let res = engine.prepare("SELECT string_agg(t.v) AS s FROM t");
+    assert_matches!(res, Err(Error::Lower(_)));
+
+    let res = engine.prepare("SELECT SUM(t.v, t.g) AS s FROM t");
+    assert_matches!(res, Err(Error::Lower(_)));
+
+    let res = Engine::default().prepare("SELECT median(t.v) AS m FROM t");
+    assert_matches!(res, Err(Error::Lower(_)));
+}
+
+#[test]
+fn parallel_group_by() {
+    // the partial states of a group aggregated across threads are merged in order of the rows
+    let rows = (0..10_000).map(|i| tuple![("g", i % 7), ("v", i)]);
+    let mut globals = MapBindings::default();
+    globals.insert("t", rows.collect::<Bag>().into());
+
+    let query = "SELECT t.g AS g, median(t.v) AS m, string_agg(CAST(t.v AS STRING), ',') AS s \
+                 FROM t GROUP BY t.g ORDER BY t.g";
+    let sequential = engine(EvaluationMode::Strict)
+        .execute(query, globals.clone())
+        .expect("execute");
+    let parallel = Engine::builder()
+        .mode(EvaluationMode::Strict)
+        .execution_mode(ExecutionMode::Parallel(NonZeroUsize::new(4).unwrap()))
+        .extension(&MedianExtension)
+        .expect("extension")
+        .build()
+        .execute(query, globals)
+        .expect("execute");
+    assert_eq!(sequential, parallel);
+}
@@ -0,0 +1,209 @@
+use assert_matches::assert_matches;
+use partiql::{Engine, Error};
+use partiql_eval::error::EvaluationError;
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, list, tuple, Value};
+use rust_decimal::Decimal;
+
+mod common;
+use common::execute_query;
+
+#[track_caller]
+fn assert_casts(cases: &[(&str, Value)]) {
+    for (query, expected) in cases {
+        let res = execute_query(query, EvaluationMode::Strict, Default::default()).expect(query);
+        assert_eq!(&res, expected, "{query}");
+    }
+}
+
+fn dec(num: i64, scale: u32) -> Value {
+    Value::from(Decimal::new(num, scale))
+}
+
+#[test]
+fn numbers() {
+    assert_casts(&[
+        ("CAST(TRUE AS INT)", Value::from(1)),
+        ("CAST(1.9 AS INT)", Value::from(1)),
+        ("CAST(-1.9 AS INTEGER)", Value::from(-1)),
+        ("CAST(' 42 ' AS BIGINT)", Value::from(42)),
+        ("CAST('1e2' AS SMALLINT)", Value::from(100)),
+        ("CAST(`2.5e0` AS INT)", Value::from(2)),
+        ("CAST(3 AS DECIMAL)", dec(3, 0)),
+        ("CAST(1.255 AS DECIMAL(5, 2))", dec(126, 2)),
+        ("CAST('-1.255' AS NUMERIC(5, 2))", dec(-126, 2)),
+        ("CAST(12.5 AS DECIMAL(3))", dec(13, 0)),
+        ("CAST(1 AS DOUBLE)", Value::from(1.0)),
+        ("CAST('2.5' AS REAL)", Value::from(2.5)),
+        ("CAST(0 AS BOOL)", Value::from(false)),
+        ("CAST(' True' AS BOOLEAN)", Value::from(true)),
+    ]);
+}
+
+#[test]
+fn strings() {
+    assert_casts(&[
+        ("CAST(1 AS STRING)", Value::from("1")),
+        ("CAST(1.50 AS VARCHAR)", Value::from("1.50")),
+        ("CAST(FALSE AS SYMBOL)", Value::from("false")),
+        ("CAST('abcdef' AS VARCHAR(3))", Value::from("abc")),
+        ("CAST('ab' AS CHAR(4))", Value::from("ab  ")),
+        ("CAST('ab' AS CHAR)", Value::from("a")),
+        (
+            "CAST(CAST('2024-01-02' AS DATE) AS STRING)",
+            Value::from("2024-01-02"),
+        ),
+    ]);
+}
+
+#[test]
+fn datetimes() {
+    let as_string = |query: &str| format!("CAST({query} AS STRING)");
+    let cases = [
+        (
+            "CAST(CAST('2024-01-02T10:30:00' AS TIMESTAMP) AS DATE)",
+            "2024-01-02",
+        ),
+        ("CAST('10:30:15.123456' AS TIME(3))", "10:30:15.123"),
+        ("CAST('10:30:15' AS TIME WITH TIME ZONE)", "10:30:15+00:00"),
+        (
+            "CAST('10:30:15-05:30' AS TIME WITH TIME ZONE)",
+            "10:30:15-05:30",
+        ),
+        ("CAST('10:30:15-05:30' AS TIME)", "10:30:15"),
+        (
+            "CAST('2024-01-02 10:30:00' AS TIMESTAMP)",
+            "2024-01-02T10:30:00",
+        ),
+        ("CAST('2024-01-02' AS TIMESTAMP)", "2024-01-02T00:00:00"),
+        (
+            "CAST('2024-01-02T10:30:00.5+01:00' AS TIMESTAMP WITH TIME ZONE)",
+            "2024-01-02T10:30:00.5+01:00",
+        ),
+        (
+            "CAST(CAST('2024-01-02T10:30:00.5+01:00' AS TIMESTAMP WITH TIME ZONE) AS TIMESTAMP(0))",
+            "2024-01-02T10:30:00",
+        ),
+        (
+            "CAST(CAST('2024-01-02T10:30:00Z' AS TIMESTAMP WITH TIME ZONE) AS TIME WITH TIME ZONE)",
+            "10:30:00+00:00",
+        ),
+    ];
+    let cases: Vec<_> = cases
+        .into_iter()
+        .map(|(query, expected)| (as_string(query), Value::from(expected)))
+        .collect();
+    let cases: Vec<_> = cases.iter().map(|(q, v)| (q.as_str(), v.clone())).collect();
+    assert_casts(&cases);
+}
+
+#[test]
+fn collections_and_absent_values() {
+    assert_casts(&[
+        ("CAST([1, 2] AS BAG)", bag![1, 2].into()),
+        ("CAST(<<1>> AS LIST)", list![1].into()),
+        ("CAST({'a': 1} AS STRUCT)", tuple![("a", 1)].into()),
+        ("CAST(NULL AS INT)", Value::Null),
+        ("CAST(MISSING AS INT)", Value::Missing),
+        ("CAST(1 AS NULL)", Value::Null),
+        ("CAST(1 AS MISSING)", Value::Missing),
+        ("CAST('a' AS ANY)", Value::from("a")),
+    ]);
+}
+
+#[test]
+fn invalid_casts() {
+    for query in [
+        "CAST('abc' AS INT)",
+        "CAST(70000 AS SMALLINT)",
+        "CAST(123.4 AS DECIMAL(3, 1))",
+        "CAST([1] AS INT)",
+        "CAST({'a': 1} AS LIST)",
+        "CAST('2024-13-01' AS DATE)",
+        "CAST(1 AS DATE)",
+        "CAST('maybe' AS BOOL)",
+    ] {
+        let res = execute_query(query, EvaluationMode::Strict, Default::default());
+        assert_matches!(res, Err(Error::Eval(_)), "{query}");
+
+        let res = execute_query(query, EvaluationMode::Permissive, Default::default());
+        assert_eq!(res.expect(query), Value::Missing, "{query}");
+    }
+}
+
+#[test]
+fn invalid_cast_messages() {
+    for (query, expected) in [
+        ("CAST('abc' AS INT)", "'abc' cannot be converted to INT"),
+        (
+            "CAST(70000 AS SMALLINT)",
+            "70000 is out of the range of SMALLINT",
+        ),
+        (
+            "CAST('1e30' AS BIGINT)",
+            "'1e30' is out of the range of BIGINT",
+        ),
+    ] {
+        let res = execute_query(query, EvaluationMode::Strict, Default::default());
+        assert_matches!(
+            res,
+            Err(Error::Eval(errs)) if matches!(
+                errs.as_slice(),
+                [EvaluationError::InvalidCast(msg)] if msg == expected
+            ),
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn can_cast() {
+    assert_casts(&[
+        ("CAN_CAST('1' AS INT)", Value::from(true)),
+        ("CAN_CAST('one' AS INT)", Value::from(false)),
+        ("CAN_CAST(NULL AS INT)", Value::from(true)),
+        ("can_cast(300 AS DECIMAL(2))", Value::from(false)),
+        ("CAN_CAST([1] AS BAG)", Value::from(true)),
+    ]);
+}
+
+#[test]
+fn can_lossless_cast() {
+    assert_casts(&[
+        ("CAN_LOSSLESS_CAST(1 AS STRING)", Value::from(true)),
+        ("CAN_LOSSLESS_CAST(1.5 AS INT)", Value::from(false)),
+        ("CAN_LOSSLESS_CAST(1.0 AS INT)", Value::from(true)),
+        ("CAN_LOSSLESS_CAST('01' AS INT)", Value::from(false)),
+        ("CAN_LOSSLESS_CAST('abc' AS VARCHAR(2))", Value::from(false)),
+        ("CAN_LOSSLESS_CAST('abc' AS INT)", Value::from(false)),
+        (
+            "can_lossless_cast(1.25 AS DECIMAL(3, 2))",
+            Value::from(true),
+        ),
+    ]);
+}
+
+#[test]
+fn in_queries() {
+    let res = execute_query(
+        "SELECT VALUE CAST(x AS INT) FROM ['1', '2.5', 'x'] AS x WHERE CAN_CAST(x AS INT)",
+        EvaluationMode::Permissive,
+        Default::default(),
+    );
+    assert_eq!(res.expect("execute"), Value::from(bag![1, 2]));
+}
+
+#[test]
+fn invalid_types() {
+    for query in [
+        "CAST(1 AS foo)",
+        "CAST(1 AS DECIMAL(2, 3))",
+        "CAST(1 AS VARCHAR(0))",
+        "CAST(1 AS TIME(10))",
+        "CAST(1 AS 1 + 2)",
+        "CAST(1)",
+    ] {
+        let res = Engine::default().prepare(query);
+        assert_matches!(res, Err(Error::Lower(_)), "{query}");
+    }
+}
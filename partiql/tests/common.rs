@@ -128,3 +128,17 @@ pub fn eval_query(statement: &str, mode: EvaluationMode) -> Result<Evaluated, Te
     let catalog = PartiqlCatalog::default().to_shared_catalog();
     eval_query_with_catalog(statement, &catalog, mode)
 }
+
+#[allow(dead_code)]
+#[track_caller]
+#[inline]
+pub fn execute_query(
+    statement: &str,
+    mode: EvaluationMode,
+    globals: MapBindings<Value>,
+) -> Result<Value, partiql::Error> {
+    partiql::Engine::builder()
+        .mode(mode)
+        .build()
+        .execute(statement, globals)
+}
@@ -9,7 +9,12 @@ mod common;
 
 #[track_caller]
 #[inline]
-pub fn eval_modes(statement: &str) -> (Result<Evaluated, TestError>, Result<Evaluated, TestError>) {
+pub fn eval_modes(
+    statement: &str,
+) -> (
+    Result<Evaluated, TestError<'_>>,
+    Result<Evaluated, TestError<'_>>,
+) {
     let permissive = eval_query(statement, EvaluationMode::Permissive);
     let strict = eval_query(statement, EvaluationMode::Strict);
     (permissive, strict)
@@ -0,0 +1,259 @@
+use assert_matches::assert_matches;
+use partiql::{Engine, Error};
+use partiql_catalog::mutation::{InMemoryMutationSink, MutationError};
+use partiql_eval::error::EvaluationError;
+use partiql_eval::eval::Parameters;
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, list, tuple, BindingsName, Value};
+use std::borrow::Cow;
+
+fn sink() -> InMemoryMutationSink {
+    let mut sink = InMemoryMutationSink::new();
+    sink.insert(
+        "t",
+        bag![
+            tuple![("id", 1), ("a", 10), ("tags", list!["x", "y"])],
+            tuple![("id", 2), ("a", 20), ("tags", list![])],
+        ]
+        .into(),
+    );
+    sink
+}
+
+fn execute(sink: &InMemoryMutationSink, statement: &str) -> Value {
+    Engine::default()
+        .prepare(statement)
+        .expect("prepare")
+        .execution()
+        .mutation_sink(sink)
+        .execute()
+        .expect("execute")
+}
+
+fn contents(sink: &InMemoryMutationSink) -> Value {
+    sink.get(&BindingsName::CaseInsensitive(Cow::Borrowed("t")))
+        .expect("target")
+}
+
+#[test]
+fn insert() {
+    let sink = sink();
+    let res = execute(&sink, "INSERT INTO t << {'id': 3, 'a': 30} >>");
+    assert_eq!(res, Value::from(bag![]));
+    assert_eq!(
+        contents(&sink),
+        Value::from(bag![
+            tuple![("id", 1), ("a", 10), ("tags", list!["x", "y"])],
+            tuple![("id", 2), ("a", 20), ("tags", list![])],
+            tuple![("id", 3), ("a", 30)],
+        ])
+    );
+}
+
+#[test]
+fn insert_query() {
+    let sink = sink();
+    let engine = Engine::default();
+    let stmt = engine
+        .prepare("INSERT INTO t SELECT s.id * 10 AS id FROM src AS s WHERE s.id > 1")
+        .expect("prepare");
+    let mut globals = partiql_eval::env::basic::MapBindings::default();
+    globals.insert("src", bag![tuple![("id", 1)], tuple![("id", 2)]].into());
+    stmt.execution()
+        .globals(globals)
+        .mutation_sink(&sink)
+        .execute()
+        .expect("execute");
+    assert_eq!(
+        contents(&sink),
+        Value::from(bag![
+            tuple![("id", 1), ("a", 10), ("tags", list!["x", "y"])],
+            tuple![("id", 2), ("a", 20), ("tags", list![])],
+            tuple![("id", 20)],
+        ])
+    );
+}
+
+#[test]
+fn insert_with_query() {
+    let sink = sink();
+    execute(
+        &sink,
+        "INSERT INTO t WITH ids (id) AS (SELECT VALUE x FROM << 3, 4 >> AS x) \
+         SELECT i.id AS id FROM ids AS i WHERE i.id > 3",
+    );
+    assert_eq!(
+        contents(&sink),
+        Value::from(bag![
+            tuple![("id", 1), ("a", 10), ("tags", list!["x", "y"])],
+            tuple![("id", 2), ("a", 20), ("tags", list![])],
+            tuple![("id", 4)],
+        ])
+    );
+}
+
+#[test]
+fn insert_value_on_conflict() {
+    let sink = sink();
+    let statement = "INSERT INTO t VALUE {'id': ?, 'a': 0} \
+                     ON CONFLICT WHERE t.id = EXCLUDED.id DO NOTHING \
+                     RETURNING MODIFIED NEW *";
+    let stmt = Engine::default().prepare(statement).expect("prepare");
+
+    let mut params = Parameters::new();
+    params.push(2);
+    let res = stmt
+        .execution()
+        .parameters(params)
+        .mutation_sink(&sink)
+        .execute()
+        .expect("execute");
+    assert_eq!(res, Value::from(bag![]));
+
+    let mut params = Parameters::new();
+    params.push(3);
+    let res = stmt
+        .execution()
+        .parameters(params)
+        .mutation_sink(&sink)
+        .execute()
+        .expect("execute");
+    assert_eq!(res, Value::from(bag![tuple![("id", 3), ("a", 0)]]));
+    assert_matches!(contents(&sink), Value::Bag(b) if b.len() == 3);
+}
+
+#[test]
+fn insert_on_conflict_with_inserted_rows() {
+    // the second row with `id` 3 conflicts with the first one, inserted by the same statement
+    let sink = sink();
+    let res = execute(
+        &sink,
+        "INSERT INTO t << {'id': 3, 'a': 30}, {'id': 2, 'a': 0}, {'id': 3, 'a': 31} >> \
+         ON CONFLICT WHERE t.id = EXCLUDED.id DO NOTHING \
+         RETURNING MODIFIED NEW *",
+    );
+    assert_eq!(res, Value::from(bag![tuple![("id", 3), ("a", 30)]]));
+    assert_eq!(
+        contents(&sink),
+        Value::from(bag![
+            tuple![("id", 1), ("a", 10), ("tags", list!["x", "y"])],
+            tuple![("id", 2), ("a", 20), ("tags", list![])],
+            tuple![("id", 3), ("a", 30)],
+        ])
+    );
+
+    let res = execute(
+        &sink,
+        "INSERT INTO t SELECT VALUE {'id': s} FROM << 4, 1, 4 >> AS s \
+         ON CONFLICT WHERE t.id = EXCLUDED.id DO NOTHING \
+         RETURNING MODIFIED NEW *",
+    );
+    assert_eq!(res, Value::from(bag![tuple![("id", 4)]]));
+    assert_matches!(contents(&sink), Value::Bag(b) if b.len() == 4);
+}
+
+#[test]
+fn update() {
+    let sink = sink();
+    let res = execute(
+        &sink,
+        "UPDATE t AS x SET x.a = x.a + 1, b = x.id REMOVE x.tags[0] WHERE x.id = 1 \
+         RETURNING MODIFIED OLD x.tags, MODIFIED NEW x.a",
+    );
+    assert_eq!(
+        res,
+        Value::from(bag![tuple![("tags", list!["x", "y"]), ("a", 11)]])
+    );
+    assert_eq!(
+        contents(&sink),
+        Value::from(bag![
+            tuple![("id", 1), ("a", 11), ("tags", list!["y"]), ("b", 1)],
+            tuple![("id", 2), ("a", 20), ("tags", list![])],
+        ])
+    );
+}
+
+#[test]
+fn delete() {
+    let sink = sink();
+    let res = execute(&sink, "DELETE FROM t WHERE t.a > 15 RETURNING ALL OLD t.id");
+    assert_eq!(res, Value::from(bag![tuple![("id", 1)], tuple![("id", 2)]]));
+    assert_eq!(
+        contents(&sink),
+        Value::from(bag![tuple![
+            ("id", 1),
+            ("a", 10),
+            ("tags", list!["x", "y"])
+        ]])
+    );
+}
+
+#[test]
+fn invalid_assignment_makes_no_changes() {
+    let sink = sink();
+    let res = Engine::builder()
+        .mode(EvaluationMode::Strict)
+        .build()
+        .prepare("UPDATE t AS x SET x.a.b = 1")
+        .expect("prepare")
+        .execution()
+        .mutation_sink(&sink)
+        .execute();
+    assert_matches!(res, Err(Error::Eval(_)));
+    assert_eq!(contents(&sink), contents(&self::sink()));
+}
+
+#[test]
+fn no_mutation_sink() {
+    let engine = Engine::builder().mode(EvaluationMode::Strict).build();
+    let res = engine.execute("DELETE FROM t", Default::default());
+    assert_matches!(res, Err(Error::Eval(_)));
+}
+
+#[test]
+fn failures_in_permissive_mode() {
+    // a statement which cannot apply its changes fails in every mode
+    let engine = Engine::default();
+    assert_eq!(engine.mode(), EvaluationMode::Permissive);
+    let execute = |statement: &str, sink: &InMemoryMutationSink| {
+        engine
+            .prepare(statement)
+            .expect("prepare")
+            .execution()
+            .mutation_sink(sink)
+            .execute()
+    };
+
+    let sink = sink();
+    assert_matches!(
+        execute("INSERT INTO nope VALUE 1", &sink),
+        Err(Error::Eval(errs)) if matches!(
+            errs.as_slice(),
+            [EvaluationError::Mutation(MutationError::UnknownTarget(target))] if target == "nope"
+        )
+    );
+    assert_matches!(
+        execute("UPDATE t AS x SET x.a.b = 1", &sink),
+        Err(Error::Eval(errs)) if matches!(
+            errs.as_slice(),
+            [EvaluationError::InvalidAssignment(_)]
+        )
+    );
+    assert_matches!(
+        execute("INSERT INTO t 1", &sink),
+        Err(Error::Eval(errs)) if matches!(
+            errs.as_slice(),
+            [EvaluationError::IllegalState(_), EvaluationError::DmlNotApplied(target)]
+                if target == "t"
+        )
+    );
+    assert_eq!(contents(&sink), contents(&self::sink()));
+
+    assert_matches!(
+        engine.execute("DELETE FROM t", Default::default()),
+        Err(Error::Eval(errs)) if matches!(
+            errs.as_slice(),
+            [EvaluationError::NoMutationSink]
+        )
+    );
+}
@@ -0,0 +1,280 @@
+use assert_matches::assert_matches;
+use partiql::{Engine, Error, PreparedStatement};
+use partiql_common::syntax::location::LineAndColumn;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::eval::{EvalPlan, Parameters};
+use partiql_eval::plan::EvaluationMode;
+use partiql_extension_value_functions::PartiqlValueFnExtension;
+use partiql_value::{bag, list, tuple, Bag, List, Tuple, Value, Variant};
+
+#[test]
+fn prepared_statement_executes_with_different_globals() {
+    let engine = Engine::default();
+    let stmt = engine
+        .prepare("SELECT VALUE x.a * 10 FROM data AS x WHERE x.a > 1")
+        .expect("prepare");
+
+    for (data, expected) in [
+        (bag![tuple![("a", 1)], tuple![("a", 2)]], bag![20]),
+        (bag![tuple![("a", 3)], tuple![("a", 4)]], bag![30, 40]),
+    ] {
+        let mut globals = MapBindings::default();
+        globals.insert("data", data.into());
+        let res = stmt.execute(globals).expect("execute");
+        assert_eq!(Value::from(expected), res);
+    }
+}
+
+#[test]
+fn prepared_statement_executes_lazily() {
+    let engine = Engine::builder().mode(EvaluationMode::Strict).build();
+    let stmt = engine
+        .prepare("SELECT VALUE x + 1 FROM data AS x")
+        .expect("prepare");
+
+    let mut globals = MapBindings::default();
+    globals.insert("data", list![1, 2, "a", 5].into());
+    let mut execution = stmt.execution().globals(globals);
+    let mut values = execution.execute_iter().expect("execute");
+    assert_matches!(values.next(), Some(Ok(v)) if v == Value::from(2));
+    assert_matches!(values.next(), Some(Ok(v)) if v == Value::from(3));
+    assert_matches!(values.next(), Some(Err(Error::Eval(_))));
+    assert_matches!(values.next(), None);
+    drop(values);
+
+    // the execution may be repeated
+    let values: Vec<_> = execution.execute_iter().expect("execute").collect();
+    assert_eq!(values.len(), 3);
+    assert_matches!(execution.execute(), Err(Error::Eval(_)));
+}
+
+#[test]
+fn parse_error_has_line_and_column() {
+    let engine = Engine::default();
+    let err = engine
+        .prepare("SELECT *\nFROM a AY a")
+        .expect_err("parse error");
+    assert_matches!(err, Error::Parse(_));
+    assert_eq!(
+        err.to_string(),
+        "Parse error: Unexpected token `<a:UNQUOTED_IDENT>` at `(2:11..2:12)`"
+    );
+}
+
+#[test]
+fn strict_evaluation_error() {
+    let engine = Engine::builder().mode(EvaluationMode::Strict).build();
+    let res = engine.execute("SELECT VALUE 1 + 'a' FROM [1] AS x", Default::default());
+    assert_matches!(res, Err(Error::Eval(_)));
+
+    let engine = Engine::builder().mode(EvaluationMode::Permissive).build();
+    let res = engine.execute("SELECT VALUE 1 + 'a' FROM [1] AS x", Default::default());
+    assert_matches!(res, Ok(_));
+}
+
+#[test]
+fn extension_functions() {
+    let query = "tupleconcat({'a': 1}, {'b': 2})";
+
+    let res = Engine::default().prepare(query);
+    assert_matches!(res, Err(Error::Lower(_)));
+
+    let engine = Engine::builder()
+        .extension(&PartiqlValueFnExtension::default())
+        .expect("extension")
+        .build();
+    let res = engine.execute(query, Default::default()).expect("execute");
+    assert_eq!(Value::from(tuple![("a", 1), ("b", 2)]), res);
+}
+
+#[test]
+fn parameters() {
+    let engine = Engine::default();
+    let stmt = engine
+        .prepare("SELECT VALUE x FROM data AS x WHERE x >= ? AND x < :upper")
+        .expect("prepare");
+    let mut globals = MapBindings::default();
+    globals.insert("data", bag![1, 2, 3, 4, 5].into());
+
+    let mut params = Parameters::new();
+    params.push(2);
+    params.insert("UPPER", 4);
+    let res = stmt
+        .execution()
+        .globals(globals)
+        .parameters(params)
+        .execute()
+        .expect("execute");
+    assert_eq!(Value::from(bag![2, 3]), res);
+
+    let mut globals = MapBindings::default();
+    globals.insert("data", bag![1, 2, 3, 4, 5].into());
+    let mut params = Parameters::new();
+    params.push(4);
+    params.insert("upper", 10);
+    let res = stmt
+        .execution()
+        .globals(globals)
+        .parameters(params)
+        .execute()
+        .expect("execute");
+    assert_eq!(Value::from(bag![4, 5]), res);
+}
+
+#[test]
+fn parameters_in_subquery() {
+    let engine = Engine::default();
+    let stmt = engine
+        .prepare("SELECT VALUE x FROM (SELECT VALUE y FROM [1, 2, 3] AS y WHERE y > ?) AS x")
+        .expect("prepare");
+    let res = stmt
+        .execution()
+        .parameters(vec![Value::from(1)].into())
+        .execute()
+        .expect("execute");
+    assert_eq!(Value::from(bag![2, 3]), res);
+}
+
+#[test]
+fn parameter_binding_errors() {
+    let engine = Engine::default();
+    let stmt = engine
+        .prepare("SELECT VALUE ? + :n FROM [1] AS x")
+        .expect("prepare");
+
+    let err = stmt.execute(Default::default()).expect_err("unbound");
+    assert_eq!(
+        err.to_string(),
+        "Evaluation error: Parameter binding error: expected 1 positional parameter value(s), \
+         but 0 were bound; Parameter binding error: no value bound for parameter `:n`"
+    );
+
+    let mut params = Parameters::new();
+    params.push(1);
+    params.push(2);
+    params.insert("n", 3);
+    params.insert("m", 4);
+    let err = stmt
+        .execution()
+        .parameters(params)
+        .execute()
+        .expect_err("over-bound");
+    assert_eq!(
+        err.to_string(),
+        "Evaluation error: Parameter binding error: expected 1 positional parameter value(s), \
+         but 2 were bound; Parameter binding error: value bound for unknown parameter `:m`"
+    );
+}
+
+#[test]
+fn values_and_plans_are_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Value>();
+    assert_send_sync::<Tuple>();
+    assert_send_sync::<Bag>();
+    assert_send_sync::<List>();
+    assert_send_sync::<Variant>();
+    assert_send_sync::<EvalPlan>();
+    assert_send_sync::<PreparedStatement>();
+    assert_send_sync::<Engine>();
+}
+
+#[test]
+fn prepared_statement_executes_across_threads() {
+    let engine = Engine::default();
+    let stmt = engine
+        .prepare("SELECT VALUE x.a * 10 FROM data AS x WHERE x.a > 1")
+        .expect("prepare");
+
+    std::thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let stmt = &stmt;
+                s.spawn(move || {
+                    let mut globals = MapBindings::default();
+                    globals.insert("data", bag![tuple![("a", i)], tuple![("a", i + 1)]].into());
+                    stmt.execute(globals).expect("execute")
+                })
+            })
+            .collect();
+
+        for (i, handle) in handles.into_iter().enumerate() {
+            let i = i as i64;
+            let expected = (i..=i + 1).filter(|a| *a > 1).map(|a| a * 10);
+            let expected = Value::from(expected.map(Value::from).collect::<Bag>());
+            assert_eq!(expected, handle.join().expect("join"));
+        }
+    });
+}
+
+#[test]
+fn parameter_type_errors() {
+    let engine = Engine::default();
+    let stmt = engine
+        .prepare("SELECT VALUE x FROM [1, 2, 3] AS x WHERE x >= CAST(? AS INT) AND :name <> 'c'")
+        .expect("prepare");
+
+    let mut params = Parameters::new();
+    params.push("two");
+    params.insert("name", 5);
+    let err = stmt
+        .execution()
+        .parameters(params)
+        .execute()
+        .expect_err("mistyped");
+    assert_eq!(
+        err.to_string(),
+        "Evaluation error: Parameter binding error: parameter `?1` expects a value of type INT4, \
+         but a value of type STRING was bound; Parameter binding error: parameter `:name` \
+         expects a value of type STRING, but a value of type INT8 was bound"
+    );
+
+    // values of the same kind as the parameter's type, and `NULL`, may be bound
+    let mut params = Parameters::new();
+    params.push(Value::from(rust_decimal::Decimal::new(25, 1)));
+    params.insert("name", "a");
+    let res = stmt
+        .execution()
+        .parameters(params)
+        .execute()
+        .expect("execute");
+    assert_eq!(Value::from(bag![2, 3]), res);
+
+    let mut params = Parameters::new();
+    params.push(Value::Null);
+    params.insert("name", "a");
+    let res = stmt
+        .execution()
+        .parameters(params)
+        .execute()
+        .expect("execute");
+    assert_eq!(Value::from(bag![]), res);
+}
+
+#[test]
+fn error_locations() {
+    let engine = Engine::default();
+    let err = engine
+        .prepare("SELECT VALUE x\nFROM [1, 2] AS x\nWHERE foo(x) > 1")
+        .expect_err("unknown function");
+    assert_eq!(
+        err.to_string(),
+        "Lowering error: Unsupported function: foo at `(3:7..3:13)`"
+    );
+    let Error::Lower(errors) = err else {
+        panic!("expected a lowering error");
+    };
+    let location = errors[0].location.as_ref().expect("location");
+    assert_eq!(LineAndColumn::new(3, 7), Some(location.start));
+    assert_eq!(LineAndColumn::new(3, 13), Some(location.end));
+
+    // planning errors are located at the clause of the failing operator
+    let err = engine
+        .prepare("SELECT VALUE x\nFROM ['a'] AS x\nWHERE x LIKE 'a' ESCAPE 'ab'")
+        .expect_err("invalid escape");
+    assert_eq!(
+        err.to_string(),
+        "Planning error: Illegal State: Invalid LIKE expression pattern: ab at `(3:1..3:29)`"
+    );
+}
@@ -0,0 +1,174 @@
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, list, tuple, Value};
+
+mod common;
+use common::execute_query;
+
+fn globals() -> MapBindings<Value> {
+    let customers = bag![tuple![
+        ("id", 1),
+        ("customer", tuple![("name", "Ann"), ("ssn", "111")]),
+        (
+            "orders",
+            list![
+                tuple![("id", 10), ("card_number", "4111"), ("amount", 5)],
+                tuple![("id", 11), ("card_number", "4222"), ("amount", 6)],
+            ]
+        ),
+        ("tags", bag!["a", "b"]),
+    ]];
+
+    let mut globals = MapBindings::default();
+    globals.insert("customers", Value::from(customers));
+    globals
+}
+
+#[track_caller]
+fn assert_excludes(exclude: &str, expected: Value) {
+    let query = format!("SELECT * EXCLUDE {exclude} FROM customers AS t");
+    assert_eq!(
+        execute_query(&query, EvaluationMode::Strict, globals()).expect("execute"),
+        Value::from(bag![expected]),
+        "{query}"
+    );
+}
+
+#[test]
+fn nested_attributes() {
+    assert_excludes(
+        "t.customer.ssn, t.orders[*].card_number",
+        tuple![
+            ("id", 1),
+            ("customer", tuple![("name", "Ann")]),
+            (
+                "orders",
+                list![
+                    tuple![("id", 10), ("amount", 5)],
+                    tuple![("id", 11), ("amount", 6)],
+                ]
+            ),
+            ("tags", bag!["a", "b"]),
+        ]
+        .into(),
+    );
+}
+
+#[test]
+fn indexes_and_wildcards() {
+    let orders = |orders: Value| {
+        Value::from(tuple![
+            ("id", 1),
+            ("customer", tuple![("name", "Ann"), ("ssn", "111")]),
+            ("orders", orders),
+            ("tags", bag!["a", "b"]),
+        ])
+    };
+    assert_excludes(
+        "t.orders[0]",
+        orders(list![tuple![("id", 11), ("card_number", "4222"), ("amount", 6)]].into()),
+    );
+    // all paths address the value before any exclusion
+    assert_excludes("t.orders[1], t.orders[0]", orders(list![].into()));
+    assert_excludes(
+        "t.orders[1].card_number, t.orders[*].amount",
+        orders(
+            list![
+                tuple![("id", 10), ("card_number", "4111")],
+                tuple![("id", 11)],
+            ]
+            .into(),
+        ),
+    );
+    assert_excludes("t.orders[*].*", orders(list![tuple![], tuple![]].into()));
+
+    assert_excludes(
+        "t.customer.*, t.tags[*]",
+        tuple![
+            ("id", 1),
+            ("customer", tuple![]),
+            (
+                "orders",
+                list![
+                    tuple![("id", 10), ("card_number", "4111"), ("amount", 5)],
+                    tuple![("id", 11), ("card_number", "4222"), ("amount", 6)],
+                ]
+            ),
+            ("tags", bag![]),
+        ]
+        .into(),
+    );
+}
+
+#[test]
+fn attribute_names() {
+    let without_ssn: Value = tuple![
+        ("id", 1),
+        ("customer", tuple![("name", "Ann")]),
+        (
+            "orders",
+            list![
+                tuple![("id", 10), ("card_number", "4111"), ("amount", 5)],
+                tuple![("id", 11), ("card_number", "4222"), ("amount", 6)],
+            ]
+        ),
+        ("tags", bag!["a", "b"]),
+    ]
+    .into();
+    assert_excludes("t.CUSTOMER.SSN", without_ssn.clone());
+    assert_excludes("T.customer['ssn']", without_ssn.clone());
+    assert_excludes("t.customer.\"ssn\"", without_ssn);
+
+    let unchanged = execute_query(
+        "SELECT * FROM customers AS t",
+        EvaluationMode::Strict,
+        globals(),
+    )
+    .expect("execute");
+    for exclude in [
+        "t.customer.\"SSN\"",
+        "t.customer['SSN']",
+        "t.customer.phone",
+        "t.orders[2]",
+        "t.orders.card_number",
+        "t.customer[0]",
+        "t.id.*",
+        "t.id[*]",
+        "u.customer",
+    ] {
+        let query = format!("SELECT * EXCLUDE {exclude} FROM customers AS t");
+        assert_eq!(
+            execute_query(&query, EvaluationMode::Strict, globals()).expect("execute"),
+            unchanged,
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn clauses() {
+    // `WHERE` is evaluated before `EXCLUDE`, the projection after it
+    assert_eq!(
+        execute_query(
+            "SELECT t.customer, t.orders[0].card_number AS card EXCLUDE t.customer.ssn, \
+             t.orders[*].card_number FROM customers AS t WHERE t.customer.ssn = '111'",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("customer", tuple![("name", "Ann")])]])
+    );
+    assert_eq!(
+        execute_query(
+            "SELECT VALUE o EXCLUDE o.card_number, t.customer \
+             FROM customers AS t, t.orders AS o",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![
+            tuple![("id", 10), ("amount", 5)],
+            tuple![("id", 11), ("amount", 6)],
+        ])
+    );
+}
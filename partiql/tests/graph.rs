@@ -4,8 +4,16 @@ use partiql_eval::plan::EvaluationMode;
 use partiql_extension_ion::decode::{IonDecodeResult, IonDecoderBuilder, IonDecoderConfig};
 
 use crate::common::{compile, evaluate, lower, parse};
+use partiql::Engine;
+use partiql_catalog::mutation::InMemoryMutationSink;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::eval::graph::adjacency_list::{AdjacencyListGraph, AdjacencyListGraphEngine};
+use partiql_eval::eval::graph::named::NamedGraphs;
+use partiql_eval::eval::Parameters;
 use partiql_extension_ion::Encoding;
-use partiql_value::{tuple, Value};
+use partiql_value::{bag, list, tuple, BindingsName, Value};
+use std::borrow::Cow;
+use std::sync::Arc;
 
 mod common;
 
@@ -216,3 +224,154 @@ fn select_star_gpml_paper() {
         "SELECT * FROM GRAPH_TABLE (g MATCH (x) -[e]- (y))",
     );
 }
+
+#[test]
+fn group_by_and_distinct_graphs() {
+    let graph = |contents: &str| {
+        decode_ion_text(contents, Encoding::PartiqlEncodedAsIon).expect("graph decode")
+    };
+    let g1 = r##"$graph::{ nodes: [ {id: n1, labels: ["a", "b"], payload: 1}, {id: n2} ],
+                          edges: [ {id: e1, ends: (n1 -> n2)} ] }"##;
+    // the same graph as `g1`, with its labels and element ids given differently
+    let g1_alt = r##"$graph::{ nodes: [ {id: x, labels: ["b", "a"], payload: 1}, {id: y} ],
+                              edges: [ {id: z, ends: (x -> y)} ] }"##;
+    let g2 = r##"$graph::{ nodes: [ {id: n1, labels: ["a"], payload: 1}, {id: n2} ],
+                          edges: [ {id: e1, ends: (n1 -- n2)} ] }"##;
+
+    let data = bag![
+        tuple![("k", 1), ("g", graph(g1))],
+        tuple![("k", 2), ("g", graph(g2))],
+        tuple![("k", 3), ("g", graph(g1_alt))],
+        tuple![("k", 4), ("g", graph(g2))],
+        tuple![("k", 5), ("g", graph(g1))],
+    ];
+    let mut globals = MapBindings::default();
+    globals.insert("data", data.into());
+    let engine = Engine::builder().mode(EvaluationMode::Strict).build();
+
+    let res = engine
+        .execute(
+            "SELECT COUNT(*) AS c, MIN(d.k) AS k FROM data AS d GROUP BY d.g ORDER BY k",
+            globals.clone(),
+        )
+        .expect("group by");
+    assert_eq!(
+        res,
+        Value::from(list![
+            tuple![("c", 3), ("k", 1)],
+            tuple![("c", 2), ("k", 2)]
+        ])
+    );
+
+    let res = engine
+        .execute("SELECT DISTINCT VALUE d.g FROM data AS d", globals.clone())
+        .expect("distinct");
+    let Value::Bag(distinct) = res else {
+        panic!("expected a bag")
+    };
+    assert_eq!(distinct.len(), 2);
+
+    // the order of graphs is total, so sorting on them is deterministic
+    let res = engine
+        .execute("SELECT VALUE d.k FROM data AS d ORDER BY d.g, d.k", globals)
+        .expect("order by");
+    assert_eq!(res, Value::from(list![2, 4, 1, 3, 5]));
+}
+
+/// The graph of `GRAPH`, held in an adjacency list rather than in a value.
+const GRAPH: &str = r##"$graph::{
+    nodes: [ {id: n1, labels: ["a"], payload: 1},
+             {id: n2, labels: ["b"], payload: 2},
+             {id: n3, labels: ["a", "c"], payload: 3} ],
+    edges: [ {id: e12, labels: ["e"], payload: 1.2, ends: (n1 -> n2) },
+             {id: e23, labels: ["d"], payload: 2.3, ends: (n2 -> n3) },
+             {id: e31, labels: ["e"], payload: 3.1, ends: (n3 -> n1) },
+             {id: e13, labels: ["u"], payload: "u", ends: (n1 -- n3) },
+             {id: e_u, labels: ["self"], payload: $bag::[], ends: (n3 -- n3) } ] }"##;
+
+fn adjacency_list_graph() -> AdjacencyListGraph {
+    let mut graph = AdjacencyListGraph::default();
+    let n1 = graph.add_node(["a"], Some(1.into()));
+    let n2 = graph.add_node(["b"], Some(2.into()));
+    let n3 = graph.add_node(["a", "c"], Some(3.into()));
+    graph.add_edge(n1, n2, true, ["e"], Some(1.2.into()));
+    graph.add_edge(n2, n3, true, ["d"], Some(2.3.into()));
+    graph.add_edge(n3, n1, true, ["e"], Some(3.1.into()));
+    graph.add_edge(n1, n3, false, ["u"], Some("u".into()));
+    graph.add_edge(n3, n3, false, ["self"], Some(bag![].into()));
+    graph
+}
+
+#[test]
+fn named_graphs() {
+    let engine = Engine::builder().mode(EvaluationMode::Strict).build();
+    let mut graphs = NamedGraphs::new();
+    graphs.insert(
+        "g",
+        AdjacencyListGraphEngine::new(Arc::new(adjacency_list_graph())),
+    );
+    let value = decode_ion_text(GRAPH, Encoding::PartiqlEncodedAsIon).expect("graph decode");
+
+    for query in [
+        "(g MATCH (x))",
+        "(g MATCH (x:a))",
+        "(g MATCH (x:a&c))",
+        "(g MATCH (x) -[e]-> (y))",
+        "(g MATCH (x) <-[e]- (y))",
+        "(g MATCH (x) ~[e]~ (y))",
+        "(g MATCH (x) <-[e]-> (y))",
+        "(g MATCH (x) -[e]- (y))",
+        "(g MATCH (x WHERE x > 1) -[e:e|d]-> (y))",
+        "(g MATCH (x) -[e WHERE e < 3]-> (y) -> (z))",
+        "(g MATCH (x) -> (y) -> (x))",
+        "(g MATCH TRAIL (x) - (y) - (z))",
+        "SELECT VALUE m.y FROM (G MATCH (x:b) -> (y)) AS m",
+    ] {
+        let mut globals = MapBindings::default();
+        globals.insert("g", value.clone());
+        let expected = engine.execute(query, globals).expect(query);
+
+        let stmt = engine.prepare(query).expect(query);
+        let res = stmt.execution().graphs(&graphs).execute().expect(query);
+        assert_eq!(res, expected, "{query}");
+    }
+
+    // variables which do not name a graph are matched against their values
+    let mut globals = MapBindings::default();
+    globals.insert("h", value);
+    let stmt = engine.prepare("(h MATCH (x:b))").expect("prepare");
+    let res = stmt
+        .execution()
+        .globals(globals)
+        .graphs(&graphs)
+        .execute()
+        .expect("execute");
+    assert_eq!(res, Value::from(bag![tuple![("x", 2)]]));
+}
+
+#[test]
+fn named_graphs_with_parameters_and_mutation_sink() {
+    let engine = Engine::builder().mode(EvaluationMode::Strict).build();
+    let mut graphs = NamedGraphs::new();
+    graphs.insert(
+        "g",
+        AdjacencyListGraphEngine::new(Arc::new(adjacency_list_graph())),
+    );
+    let mut sink = InMemoryMutationSink::new();
+    sink.insert("t", bag![].into());
+
+    let stmt = engine
+        .prepare("INSERT INTO t SELECT m.x AS x FROM (g MATCH (x:a)) AS m WHERE m.x > ?")
+        .expect("prepare");
+    let mut params = Parameters::new();
+    params.push(1);
+    stmt.execution()
+        .parameters(params)
+        .mutation_sink(&sink)
+        .graphs(&graphs)
+        .execute()
+        .expect("execute");
+
+    let contents = sink.get(&BindingsName::CaseInsensitive(Cow::Borrowed("t")));
+    assert_eq!(contents, Some(Value::from(bag![tuple![("x", 3)]])));
+}
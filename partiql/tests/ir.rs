@@ -0,0 +1,161 @@
+use assert_matches::assert_matches;
+use partiql::{Engine, Error};
+use partiql_catalog::aggregate_fn::{
+    AggregateFnCallDef, AggregateFnExpr, AggregateFnExprResult, AggregateFunction,
+    SimpleAggregateFunctionInfo,
+};
+use partiql_catalog::catalog::Catalog;
+use partiql_catalog::extension::{Extension, ExtensionResultError};
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::EvaluationMode;
+use partiql_extension_value_functions::PartiqlValueFnExtension;
+use partiql_ir::IrPlan;
+use partiql_irgen::IrLoadError;
+use partiql_value::{bag, tuple, Value};
+
+#[derive(Debug)]
+struct ProductExtension;
+
+impl Extension for ProductExtension {
+    fn name(&self) -> String {
+        "product".into()
+    }
+
+    fn load(&self, catalog: &mut dyn Catalog) -> Result<(), ExtensionResultError> {
+        let call_def = AggregateFnCallDef {
+            names: vec!["product"],
+            expr: Box::new(Product),
+        };
+        let info = SimpleAggregateFunctionInfo::new(call_def);
+        match catalog.add_aggregate_function(AggregateFunction::new(Box::new(info))) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ExtensionResultError::LoadError(e.into())),
+        }
+    }
+}
+
+/// The product of a group's numbers.
+#[derive(Debug, Clone)]
+struct Product;
+
+impl AggregateFnExpr for Product {
+    fn init(&self) -> Value {
+        Value::from(1)
+    }
+
+    fn accumulate(
+        &self,
+        state: &mut Value,
+        value: &Value,
+        _args: &[Value],
+    ) -> Result<(), ExtensionResultError> {
+        *state = &*state * value;
+        Ok(())
+    }
+
+    fn merge(&self, state: &mut Value, other: Value) -> Result<(), ExtensionResultError> {
+        *state = &*state * &other;
+        Ok(())
+    }
+
+    fn finalize(&self, state: Value) -> AggregateFnExprResult {
+        Ok(state)
+    }
+}
+
+/// An engine with the extensions loaded in the given order, which determines the ids of their
+/// functions in the engine's catalog.
+fn engine(extensions: &[&dyn Extension]) -> Engine {
+    extensions
+        .iter()
+        .fold(
+            Engine::builder().mode(EvaluationMode::Strict),
+            |builder, ext| builder.extension(*ext).expect("extension"),
+        )
+        .build()
+}
+
+fn globals() -> MapBindings<Value> {
+    let mut globals = MapBindings::default();
+    globals.insert(
+        "t",
+        bag![
+            tuple![("g", "a"), ("v", 2)],
+            tuple![("g", "a"), ("v", 3)],
+            tuple![("g", "b"), ("v", 4)],
+        ]
+        .into(),
+    );
+    globals
+}
+
+#[test]
+fn executes_in_another_engine() {
+    let control = engine(&[&PartiqlValueFnExtension::default(), &ProductExtension]);
+    let worker = engine(&[&ProductExtension, &PartiqlValueFnExtension::default()]);
+
+    for query in [
+        "SELECT t.g AS g, product(t.v) AS p FROM t GROUP BY t.g ORDER BY t.g",
+        "SELECT VALUE tupleunion(t, {'w': t.v * 2}) FROM t WHERE t.v > 2",
+        "SELECT t.v AS v, product(t.v) OVER (PARTITION BY t.g) AS p FROM t ORDER BY t.v",
+        "SELECT * EXCLUDE t.g FROM t AS t",
+        "SELECT VALUE y FROM t AS t LET t.v * 2 AS y WHERE y > 4",
+        "SELECT * FROM t AS a JOIN t AS b USING (g)",
+        "t[*].v",
+        "VALUES (1, 'a'), (2, 'b')",
+        "WITH RECURSIVE r (n) AS (SELECT VALUE x FROM << 1 >> AS x \
+                                  UNION ALL \
+                                  SELECT c.n + 1 FROM r AS c WHERE c.n < 3) \
+                  SEARCH DEPTH FIRST BY n SET ord \
+         SELECT r.n AS n FROM r AS r ORDER BY r.ord",
+    ] {
+        let bytes = serde_json::to_vec(&control.generate_ir(query).expect(query)).expect(query);
+        let ir: IrPlan = serde_json::from_slice(&bytes).expect(query);
+
+        let expected = control.execute(query, globals()).expect(query);
+        let stmt = worker.prepare_ir(&ir).expect(query);
+        assert_eq!(stmt.execute(globals()).expect(query), expected, "{query}");
+    }
+}
+
+/// The IR of a plan exercising each operator, as serialized by this version of the IR.
+///
+/// A change to this snapshot changes the IR's schema, and so requires a change of
+/// `partiql_ir::IR_VERSION` before the IR is released.
+#[test]
+fn schema() {
+    let engine = engine(&[&ProductExtension]);
+    let ir: Vec<_> = [
+        "SELECT t.g AS g, product(t.v) AS p FROM t GROUP BY t.g ORDER BY t.g LIMIT 1",
+        "SELECT t.v AS v, product(t.v) OVER (PARTITION BY t.g) AS p FROM t",
+        "SELECT * EXCLUDE t.g FROM t AS t LET t.v * 2 AS y WHERE y > 4",
+        "SELECT * FROM t AS a JOIN t AS b USING (g)",
+        "VALUES (1, 'a')",
+        "WITH RECURSIVE r (n) AS (SELECT VALUE x FROM << 1 >> AS x \
+                                  UNION ALL \
+                                  SELECT c.n + 1 FROM r AS c WHERE c.n < 3) \
+                  SEARCH DEPTH FIRST BY n SET ord \
+         SELECT r.n AS n FROM r AS r ORDER BY r.ord",
+    ]
+    .into_iter()
+    .map(|query| {
+        let ir = engine.generate_ir(query).expect(query);
+        serde_json::to_string(&ir).expect(query)
+    })
+    .collect();
+    insta::assert_snapshot!(ir.join("\n"));
+}
+
+#[test]
+fn missing_function() {
+    let control = engine(&[&ProductExtension]);
+    let ir = control
+        .generate_ir("SELECT product(t.v) AS p FROM t")
+        .expect("generate");
+
+    let res = Engine::default().prepare_ir(&ir);
+    assert_matches!(
+        res,
+        Err(Error::Ir(IrLoadError::UnknownObject(name))) if name == "product"
+    );
+}
@@ -0,0 +1,128 @@
+use assert_matches::assert_matches;
+use partiql::{Engine, Error};
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, tuple, Value};
+
+mod common;
+use common::execute_query;
+
+#[track_caller]
+fn assert_is(cases: &[(&str, bool)]) {
+    for (query, expected) in cases {
+        assert_eq!(
+            execute_query(query, EvaluationMode::Strict, Default::default()).expect("execute"),
+            Value::from(*expected),
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn scalars() {
+    assert_is(&[
+        ("1 IS INT", true),
+        ("1 IS BIGINT", true),
+        ("40000 IS SMALLINT", false),
+        ("40000 IS INTEGER", true),
+        ("1 IS STRING", false),
+        ("1.5 IS INT", false),
+        ("'a' IS STRING", true),
+        ("'a' IS NOT STRING", false),
+        ("TRUE IS BOOL", true),
+        ("`1.5e0` IS DOUBLE", true),
+        ("`1.5e0` IS REAL", true),
+        ("`1.1e0` IS REAL", false),
+        ("`{{YWJj}}` IS BLOB", true),
+        ("`{{YWJj}}` IS CLOB", false),
+    ]);
+}
+
+#[test]
+fn parameterized() {
+    assert_is(&[
+        ("1.5 IS DECIMAL", true),
+        ("1.5 IS DECIMAL(10, 2)", true),
+        ("1.50 IS DECIMAL(2, 1)", true),
+        ("1.555 IS DECIMAL(10, 2)", false),
+        ("123.5 IS DECIMAL(3, 1)", false),
+        ("12 IS DECIMAL(2)", false),
+        ("12. IS NUMERIC(2)", true),
+        ("'abc' IS VARCHAR", true),
+        ("'abc' IS VARCHAR(3)", true),
+        ("'abcd' IS VARCHAR(3)", false),
+        ("'a' IS CHAR", true),
+        ("'ab' IS CHAR(3)", false),
+        ("'abc' IS CHAR(3)", true),
+    ]);
+}
+
+#[test]
+fn datetimes() {
+    assert_is(&[
+        ("CAST('2024-01-02' AS DATE) IS DATE", true),
+        ("CAST('2024-01-02' AS DATE) IS TIMESTAMP", false),
+        (
+            "CAST('2024-01-02 10:30:00' AS TIMESTAMP) IS TIMESTAMP",
+            true,
+        ),
+        (
+            "CAST('2024-01-02 10:30:00' AS TIMESTAMP) IS TIMESTAMP WITH TIME ZONE",
+            false,
+        ),
+        (
+            "CAST('2024-01-02T10:30:00Z' AS TIMESTAMP WITH TIME ZONE) IS TIMESTAMP WITH TIME ZONE",
+            true,
+        ),
+        (
+            "CAST('2024-01-02T10:30:00Z' AS TIMESTAMP WITH TIME ZONE) IS TIMESTAMP",
+            true,
+        ),
+        ("`2020-01-01T00:00:00Z` IS TIMESTAMP", true),
+        ("`2020-01-01T00:00:00.5+01:00` IS TIMESTAMP(0)", false),
+        ("CAST('10:30:00.25' AS TIME) IS TIME", true),
+        ("CAST('10:30:00.25' AS TIME) IS TIME(2)", true),
+        ("CAST('10:30:00.25' AS TIME) IS TIME(1)", false),
+        (
+            "CAST('10:30:00.25' AS TIME) IS NOT TIME WITH TIME ZONE",
+            true,
+        ),
+    ]);
+}
+
+#[test]
+fn collections_and_absent_values() {
+    assert_is(&[
+        ("{'a': 1} IS STRUCT", true),
+        ("{'a': 1} IS TUPLE", true),
+        ("[1] IS LIST", true),
+        ("[1] IS SEXP", false),
+        ("[1] IS BAG", false),
+        ("<<1>> IS BAG", true),
+        ("NULL IS NULL", true),
+        ("MISSING IS NULL", true),
+        ("NULL IS MISSING", false),
+        ("NULL IS INT", false),
+        ("NULL IS ANY", true),
+        ("'a' IS ANY", true),
+    ]);
+}
+
+#[test]
+fn filter() {
+    let res = execute_query(
+        "SELECT VALUE x FROM [1, 'a', 2.5, {'b': 2}, <<3>>, NULL] AS x \
+         WHERE x IS INT OR x IS STRUCT OR x IS BAG",
+        EvaluationMode::Strict,
+        Default::default(),
+    )
+    .expect("execute");
+    assert_eq!(res, Value::from(bag![1, tuple![("b", 2)], bag![3]]));
+}
+
+#[test]
+fn invalid_types() {
+    for query in ["1 IS foo", "1 IS DECIMAL(2, 3)", "1 IS TIMESTAMP(10)"] {
+        let res = Engine::default().prepare(query);
+        assert_matches!(res, Err(Error::Lower(_)), "{query}");
+    }
+}
@@ -0,0 +1,280 @@
+use assert_matches::assert_matches;
+use partiql::{Engine, Error, LocatedError};
+use partiql_ast_passes::error::AstTransformError;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, list, tuple, Bag, Value};
+use rust_decimal::Decimal;
+
+mod common;
+use common::execute_query;
+
+fn globals() -> MapBindings<Value> {
+    let left = bag![
+        tuple![("id", 1), ("k", "a")],
+        tuple![("id", 2), ("k", "b")],
+        tuple![("id", 2.0), ("k", "b")],
+        tuple![("id", Decimal::new(30, 1)), ("k", "c")],
+        tuple![("id", f64::NAN), ("k", "d")],
+        tuple![("id", Value::Null), ("k", "a")],
+        tuple![("k", "a")],
+        tuple![("id", "1"), ("k", "e")],
+        tuple![("id", list![1, 2.5]), ("k", "f")],
+        tuple![("id", tuple![("a", 1)]), ("k", "g")],
+        tuple![("id", 9_007_199_254_740_993_i64), ("k", "h")],
+    ];
+    let right = bag![
+        tuple![("id", 1.0), ("k", "a"), ("n", 1)],
+        tuple![("id", 2), ("k", "b"), ("n", 2)],
+        tuple![("id", 3), ("k", "c"), ("n", 3)],
+        tuple![("id", f64::NAN), ("k", "d"), ("n", 4)],
+        tuple![("id", Value::Null), ("k", "a"), ("n", 5)],
+        tuple![("k", "a"), ("n", 6)],
+        tuple![("id", "1"), ("k", "e"), ("n", 7)],
+        tuple![
+            ("id", list![Decimal::new(10, 1), Decimal::new(25, 1)]),
+            ("k", "f"),
+            ("n", 8)
+        ],
+        tuple![("id", tuple![("a", 1.0)]), ("k", "g"), ("n", 9)],
+        tuple![("id", 9_007_199_254_740_992.0), ("k", "h"), ("n", 10)],
+        tuple![("id", 4), ("k", "i"), ("n", 11)],
+    ];
+
+    let mut globals = MapBindings::default();
+    globals.insert("lhs", Value::from(left));
+    globals.insert("rhs", Value::from(right));
+    globals.insert(
+        "nested",
+        Value::from(bag![
+            tuple![
+                ("id", 1),
+                ("rhs", bag![tuple![("id", 1)], tuple![("id", 2)]])
+            ],
+            tuple![("id", 2), ("rhs", bag![tuple![("id", 1)]])],
+        ]),
+    );
+    globals
+}
+
+/// Asserts that joining on `on` produces the same bindings, in the same order, as joining on a
+/// condition that is equivalent to `on` but has no equality conjuncts, which is evaluated as a
+/// nested loop.
+#[track_caller]
+fn assert_nested_loop_eq(join: &str, on: &str) {
+    let query = format!("SELECT * FROM lhs AS x {join} rhs AS y ON {on}");
+    let nested_loop = format!("SELECT * FROM lhs AS x {join} rhs AS y ON ({on}) OR FALSE");
+    assert_eq!(
+        execute_query(&query, EvaluationMode::Strict, globals())
+            .expect("execute")
+            .into_iter()
+            .collect::<Vec<_>>(),
+        execute_query(&nested_loop, EvaluationMode::Strict, globals())
+            .expect("execute")
+            .into_iter()
+            .collect::<Vec<_>>(),
+        "{query}"
+    );
+}
+
+#[test]
+fn equi_join_kinds() {
+    for join in ["JOIN", "LEFT JOIN", "RIGHT JOIN", "FULL JOIN"] {
+        assert_nested_loop_eq(join, "x.id = y.id");
+        assert_nested_loop_eq(join, "y.id = x.id");
+        assert_nested_loop_eq(join, "x.id = y.id AND y.n > 1");
+        assert_nested_loop_eq(join, "x.k = y.k AND y.id = x.id");
+        assert_nested_loop_eq(join, "x.k || 'z' = y.k || 'z'");
+    }
+}
+
+#[test]
+fn equi_join_numeric_keys() {
+    let ids = execute_query(
+        "SELECT VALUE [x.k, y.n] FROM lhs AS x JOIN rhs AS y ON x.id = y.id",
+        EvaluationMode::Strict,
+        globals(),
+    )
+    .expect("execute")
+    .into_iter()
+    .collect::<Vec<_>>();
+    let expected: Vec<Value> = vec![
+        list!["a", 1].into(),
+        list!["b", 2].into(),
+        list!["b", 2].into(),
+        list!["c", 3].into(),
+        list!["d", 4].into(),
+        list!["e", 7].into(),
+        list!["f", 8].into(),
+        list!["g", 9].into(),
+        list!["h", 10].into(),
+    ];
+    assert_eq!(ids, expected);
+}
+
+#[test]
+fn lateral_join() {
+    let query = "SELECT VALUE [x.id, y.id] FROM nested AS x JOIN x.rhs AS y ON x.id = y.id";
+    let expected: Vec<Value> = vec![list![1, 1].into()];
+    assert_eq!(
+        execute_query(query, EvaluationMode::Strict, globals())
+            .expect("execute")
+            .into_iter()
+            .collect::<Vec<_>>(),
+        expected
+    );
+
+    // `rhs` is looked up in the bindings of `x`, as there is no such global
+    let query = "SELECT VALUE [x.id, y.id] FROM nested AS x JOIN rhs AS y ON x.id = y.id";
+    let mut globals = MapBindings::default();
+    globals.insert(
+        "nested",
+        Value::from(bag![
+            tuple![
+                ("id", 1),
+                ("rhs", bag![tuple![("id", 1)], tuple![("id", 2)]])
+            ],
+            tuple![("id", 2), ("rhs", bag![tuple![("id", 1)]])],
+        ]),
+    );
+    let joined = execute_query(query, EvaluationMode::Strict, globals).expect(query);
+    assert_eq!(
+        joined,
+        Value::from([list![1, 1]].into_iter().collect::<Bag>())
+    );
+}
+
+#[test]
+fn join_using() {
+    for join in ["JOIN", "LEFT JOIN", "RIGHT JOIN", "FULL JOIN"] {
+        let using = format!("SELECT VALUE [id, x.k, y.n] FROM lhs AS x {join} rhs AS y USING (id)");
+        let on = format!(
+            "SELECT VALUE [COALESCE(x.id, y.id), x.k, y.n] FROM lhs AS x {join} rhs AS y \
+             ON x.id = y.id"
+        );
+        assert_eq!(
+            execute_query(&using, EvaluationMode::Strict, globals())
+                .expect("execute")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            execute_query(&on, EvaluationMode::Strict, globals())
+                .expect("execute")
+                .into_iter()
+                .collect::<Vec<_>>(),
+            "{using}"
+        );
+    }
+
+    // the columns of a `USING` join are variables of its bindings, e.g., in a subsequent join
+    let using = "SELECT VALUE [id, k, y.n, z.n] FROM lhs AS x JOIN rhs AS y USING (id) \
+                 LEFT JOIN rhs AS z USING (id, k)";
+    let on = "SELECT VALUE [COALESCE(COALESCE(x.id, y.id), z.id), COALESCE(x.k, z.k), y.n, z.n] \
+              FROM lhs AS x JOIN rhs AS y ON x.id = y.id \
+              LEFT JOIN rhs AS z ON COALESCE(x.id, y.id) = z.id AND x.k = z.k";
+    assert_eq!(
+        execute_query(using, EvaluationMode::Strict, globals())
+            .expect("execute")
+            .into_iter()
+            .collect::<Vec<_>>(),
+        execute_query(on, EvaluationMode::Strict, globals())
+            .expect("execute")
+            .into_iter()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn join_using_select_star() {
+    // each join column is projected once, as its coalesced value, ahead of the other attributes
+    let query = "SELECT * FROM lhs AS x JOIN rhs AS y USING (id) WHERE x.k = 'a'";
+    let joined = execute_query(query, EvaluationMode::Strict, globals()).expect(query);
+    assert_eq!(
+        joined,
+        Value::from(bag![tuple![("id", 1), ("k", "a"), ("k", "a"), ("n", 1)]])
+    );
+
+    let query = "SELECT * FROM lhs AS x JOIN rhs AS y USING (id) \
+                 LEFT JOIN rhs AS z USING (id, k) WHERE x.k = 'a'";
+    let joined = execute_query(query, EvaluationMode::Strict, globals()).expect(query);
+    assert_eq!(
+        joined,
+        Value::from(bag![tuple![("id", 1), ("k", "a"), ("n", 1), ("n", 1)]])
+    );
+
+    let query = "SELECT * FROM (VALUES (1, 'a'), (2, 'b')) AS v (id, s) \
+                 NATURAL FULL JOIN (VALUES (1, 'c'), (3, 'd')) AS w (id, t)";
+    let joined = execute_query(query, EvaluationMode::Strict, globals()).expect(query);
+    assert_eq!(
+        joined,
+        Value::from(bag![
+            tuple![("id", 1), ("s", "a"), ("t", "c")],
+            tuple![("id", 2), ("s", "b")],
+            tuple![("id", 3), ("t", "d")],
+        ])
+    );
+}
+
+#[test]
+fn natural_join() {
+    let query = "SELECT id, s, t \
+                 FROM (VALUES (1, 'a'), (2, 'b')) AS v (id, s) \
+                 NATURAL FULL JOIN (VALUES (1, 'c'), (3, 'd')) AS w (id, t)";
+    let joined = execute_query(query, EvaluationMode::Strict, globals()).expect(query);
+    assert_eq!(
+        joined,
+        Value::from(bag![
+            tuple![("id", 1), ("s", "a"), ("t", "c")],
+            tuple![("id", 2), ("s", "b")],
+            tuple![("id", 3), ("t", "d")],
+        ])
+    );
+
+    // the attributes of `lhs` and `rhs` are not statically known
+    let query = "SELECT * FROM lhs AS x NATURAL JOIN rhs AS y";
+    assert_matches!(
+        Engine::default().prepare(query),
+        Err(Error::Lower(errs)) if matches!(
+            errs.as_slice(),
+            [LocatedError {
+                error: AstTransformError::UnknownNaturalJoinColumns(_),
+                ..
+            }]
+        )
+    );
+}
+
+#[test]
+fn outer_join_correlated_right_operand() {
+    // only the right operand of an `INNER` or `LEFT` join may depend upon the left operand
+    for join in ["RIGHT JOIN", "FULL JOIN"] {
+        let query = format!(
+            "SELECT c.id AS cid, o AS o \
+             FROM << {{'id': 1, 'xs': [1, 2]}}, {{'id': 2, 'xs': []}} >> AS c \
+             {join} c.xs AS o ON true"
+        );
+        assert_matches!(
+            Engine::default().prepare(&query),
+            Err(Error::Lower(errs)) if matches!(
+                errs.as_slice(),
+                [LocatedError {
+                    error: AstTransformError::InvalidJoinReference(_, name),
+                    ..
+                }] if name == "c"
+            ),
+            "{query}"
+        );
+    }
+
+    let query = "SELECT c.id AS cid, o AS o \
+                 FROM << {'id': 1, 'xs': [1, 2]}, {'id': 2, 'xs': []} >> AS c \
+                 LEFT JOIN c.xs AS o ON true";
+    let joined = execute_query(query, EvaluationMode::Strict, globals()).expect(query);
+    assert_eq!(
+        joined,
+        Value::from(bag![
+            tuple![("cid", 1), ("o", 1)],
+            tuple![("cid", 1), ("o", 2)],
+            tuple![("cid", 2), ("o", Value::Null)],
+        ])
+    );
+}
@@ -0,0 +1,127 @@
+use partiql::Error;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, list, tuple, Value};
+
+mod common;
+use common::execute_query;
+
+fn globals() -> MapBindings<Value> {
+    let mut globals = MapBindings::default();
+    globals.insert(
+        "t",
+        bag![
+            tuple![("a", 1), ("b", "x")],
+            tuple![("a", 2), ("b", "y")],
+            tuple![("a", 3), ("b", "x")],
+        ]
+        .into(),
+    );
+    globals
+}
+
+#[test]
+fn let_in_where_and_projection() {
+    assert_eq!(
+        execute_query(
+            "SELECT t.a, y FROM t AS t LET t.a * 2 AS y WHERE y > 2",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("a", 2), ("y", 4)], tuple![("a", 3), ("y", 6)]])
+    );
+    assert_eq!(
+        execute_query(
+            "FROM t AS t LET t.a * 2 AS y WHERE y > 4 SELECT y",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("y", 6)]])
+    );
+}
+
+#[test]
+fn let_multiple_bindings() {
+    assert_eq!(
+        execute_query(
+            "SELECT y, z FROM t AS t LET t.a + 1 AS y, t.b || '!' AS z WHERE t.a = 1",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("y", 2), ("z", "x!")]])
+    );
+}
+
+#[test]
+fn let_bindings_in_sequence() {
+    // each binding is in scope of the preceding bindings
+    assert_eq!(
+        execute_query(
+            "SELECT t.a, y, z FROM t AS t LET t.a * 2 AS y, y + 1 AS z WHERE t.a = 1",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("a", 1), ("y", 2), ("z", 3)]])
+    );
+    assert_eq!(
+        execute_query(
+            "SELECT VALUE w FROM t AS t LET t.a AS y, y * 10 AS z, y + z AS w",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![11, 22, 33])
+    );
+
+    // but not of the following ones, so that `z` is an attribute of `t` here
+    assert_eq!(
+        execute_query(
+            "SELECT t.a, y FROM t AS t LET z AS y, t.a AS z WHERE t.a = 1",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("a", 1)]])
+    );
+}
+
+#[test]
+fn let_in_group_by_and_having() {
+    assert_eq!(
+        execute_query(
+            "SELECT k, SUM(y) AS s FROM t AS t LET t.b AS k, t.a * 10 AS y \
+             GROUP BY k HAVING k = 'x'",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("k", "x"), ("s", 40)]])
+    );
+}
+
+#[test]
+fn let_in_order_by() {
+    assert_eq!(
+        execute_query(
+            "SELECT VALUE y FROM t AS t LET -t.a AS y ORDER BY y",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(list![-3, -2, -1])
+    );
+}
+
+#[test]
+fn let_subquery() {
+    let err = execute_query(
+        "SELECT y FROM t AS t LET (SELECT VALUE 1 FROM <<1>>) AS y",
+        EvaluationMode::Strict,
+        globals(),
+    );
+    assert!(matches!(err, Err(Error::Lower(_))));
+}
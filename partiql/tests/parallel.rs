@@ -0,0 +1,120 @@
+use std::num::NonZeroUsize;
+
+use assert_matches::assert_matches;
+use partiql::{Engine, Error};
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::{EvaluationMode, ExecutionMode};
+use partiql_value::datum::Datum;
+use partiql_value::{list, tuple, Bag, List, Tuple, Value};
+
+const ROWS: i64 = 20_000;
+
+fn engine(execution_mode: ExecutionMode) -> Engine {
+    Engine::builder()
+        .mode(EvaluationMode::Strict)
+        .execution_mode(execution_mode)
+        .build()
+}
+
+fn globals() -> MapBindings<Value> {
+    let rows = (0..ROWS).map(|i| {
+        Value::from(tuple![
+            ("id", i),
+            ("grp", i % 37),
+            ("v", (i * 7) % 101),
+            ("tags", list![i % 3, i % 5]),
+        ])
+    });
+    let wide = (0..ROWS).map(|i| (format!("a{i}"), Value::from(i)));
+
+    let mut globals = MapBindings::default();
+    globals.insert("data", Value::from(rows.clone().collect::<Bag>()));
+    globals.insert("ordered", Value::from(rows.collect::<List>()));
+    globals.insert("wide", Value::from(wide.collect::<Tuple>()));
+    globals
+}
+
+/// Asserts that `query` evaluates to the same values, in the same order, in parallel as it does
+/// sequentially.
+#[track_caller]
+fn assert_parallel_eq(query: &str) {
+    let sequential = engine(ExecutionMode::Sequential)
+        .execute(query, globals())
+        .expect(query);
+    let threads = NonZeroUsize::new(4).unwrap();
+    let parallel = engine(ExecutionMode::Parallel(threads))
+        .execute(query, globals())
+        .expect(query);
+
+    assert_eq!(sequential.is_ordered(), parallel.is_ordered(), "{query}");
+    assert_eq!(
+        sequential.into_iter().collect::<Vec<_>>(),
+        parallel.into_iter().collect::<Vec<_>>(),
+        "{query}"
+    );
+}
+
+#[test]
+fn scan_filter_project() {
+    assert_parallel_eq("SELECT VALUE x.id FROM data AS x WHERE x.v > 50");
+    assert_parallel_eq("SELECT x.id, x.v * 2 AS dbl FROM data AS x WHERE x.grp = 3");
+    assert_parallel_eq("SELECT * FROM data AS x WHERE x.id < 5000");
+    assert_parallel_eq("SELECT VALUE [i, x.id] FROM ordered AS x AT i WHERE i > 10");
+    assert_parallel_eq("SELECT x.id, t FROM data AS x, x.tags AS t WHERE x.v < 10");
+}
+
+#[test]
+fn unpivot() {
+    assert_parallel_eq("SELECT k, v FROM UNPIVOT wide AS v AT k WHERE v > 100");
+}
+
+#[test]
+fn group_by() {
+    assert_parallel_eq(
+        "SELECT x.grp AS g, COUNT(*) AS c, SUM(x.v) AS s, COUNT(DISTINCT x.v) AS d, MAX(x.id) AS m \
+         FROM data AS x GROUP BY x.grp",
+    );
+    assert_parallel_eq(
+        "SELECT x.v AS v, grouped FROM data AS x WHERE x.id < 3000 GROUP BY x.v GROUP AS grouped",
+    );
+    // without `DISTINCT` aggregates, the partial states of the partitions are merged
+    assert_parallel_eq(
+        "SELECT x.grp AS g, COUNT(*) AS c, SUM(x.v) AS s, AVG(x.v) AS a, MIN(x.v) AS mn, \
+                MAX(x.id) AS mx, EVERY(x.v > 0) AS e, ANY(x.v > 99) AS y \
+         FROM data AS x GROUP BY x.grp ORDER BY g",
+    );
+    assert_parallel_eq(
+        "SELECT x.grp AS g, grouped FROM data AS x GROUP BY x.grp GROUP AS grouped ORDER BY g",
+    );
+}
+
+#[test]
+fn group_by_order() {
+    // groups are output in order of their first rows, in parallel as well as sequentially
+    let query = "SELECT VALUE g FROM data AS x GROUP BY x.grp AS g";
+    assert_parallel_eq(query);
+    assert_parallel_eq("SELECT x.grp AS g, COUNT(DISTINCT x.v) AS d FROM data AS x GROUP BY x.grp");
+
+    let threads = NonZeroUsize::new(4).unwrap();
+    let groups = engine(ExecutionMode::Parallel(threads))
+        .execute(query, globals())
+        .expect(query);
+    assert_eq!(
+        groups.into_iter().collect::<Vec<_>>(),
+        (0..37).map(Value::from).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn order_by() {
+    assert_parallel_eq("SELECT x.id, x.v FROM data AS x ORDER BY x.v DESC, x.grp");
+    assert_parallel_eq("SELECT VALUE x.v FROM data AS x ORDER BY x.v LIMIT 10 OFFSET 5000");
+}
+
+#[test]
+fn errors() {
+    let threads = NonZeroUsize::new(4).unwrap();
+    let res = engine(ExecutionMode::Parallel(threads))
+        .execute("SELECT VALUE x.v + 'a' FROM data AS x", globals());
+    assert_matches!(res, Err(Error::Eval(_)));
+}
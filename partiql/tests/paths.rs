@@ -0,0 +1,109 @@
+use assert_matches::assert_matches;
+use partiql::Error;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, list, tuple, Value};
+
+mod common;
+use common::execute_query;
+
+fn globals() -> MapBindings<Value> {
+    let mut globals = MapBindings::default();
+    globals.insert(
+        "customers",
+        bag![
+            tuple![
+                ("name", "a"),
+                (
+                    "orders",
+                    list![tuple![("price", 10)], tuple![("price", 20)]]
+                ),
+                ("phones", tuple![("home", "111"), ("work", "222")]),
+            ],
+            tuple![
+                ("name", "b"),
+                ("orders", list![]),
+                ("phones", tuple![("home", "333")]),
+            ],
+            tuple![("name", "c"), ("orders", tuple![("price", 5)])],
+        ]
+        .into(),
+    );
+    globals
+}
+
+fn assert_all_modes(statement: &str, expected: Value) {
+    for mode in [EvaluationMode::Strict, EvaluationMode::Permissive] {
+        assert_eq!(
+            execute_query(statement, mode, globals()).expect("execute"),
+            expected,
+            "{mode:?}: {statement}"
+        );
+    }
+}
+
+/// Asserts that `statement` is an error in `STRICT` mode and evaluates to `expected` in
+/// `PERMISSIVE` mode.
+#[track_caller]
+fn assert_permissive_only(statement: &str, expected: Value) {
+    let res = execute_query(statement, EvaluationMode::Strict, globals());
+    assert_matches!(res, Err(Error::Eval(_)), "{statement}");
+    let res = execute_query(statement, EvaluationMode::Permissive, globals());
+    assert_eq!(res.expect("execute"), expected, "{statement}");
+}
+
+#[test]
+fn for_each() {
+    assert_all_modes(
+        "SELECT c.name, c.orders[*].price AS prices FROM customers AS c WHERE c.name <> 'c'",
+        Value::from(bag![
+            tuple![("name", "a"), ("prices", bag![10, 20])],
+            tuple![("name", "b"), ("prices", bag![])],
+        ]),
+    );
+    assert_all_modes(
+        "[{'a': 1}, {'a': 2}, {'b': 3}][*].a",
+        Value::from(bag![1, 2, Value::Missing]),
+    );
+}
+
+#[test]
+fn for_each_non_collection() {
+    // `c.orders` is a tuple for customer `c`
+    assert_permissive_only(
+        "SELECT c.name, c.orders[*].price AS prices FROM customers AS c",
+        Value::from(bag![
+            tuple![("name", "a"), ("prices", bag![10, 20])],
+            tuple![("name", "b"), ("prices", bag![])],
+            tuple![("name", "c"), ("prices", bag![5])],
+        ]),
+    );
+    assert_permissive_only("5[*]", Value::from(bag![5]));
+    assert_permissive_only("MISSING[*]", Value::from(bag![Value::Missing]));
+    assert_permissive_only("[[1, 2], 3][*][*]", Value::from(bag![1, 2, 3]));
+}
+
+#[test]
+fn unpivot() {
+    assert_all_modes(
+        "SELECT VALUE c.phones.* FROM customers AS c WHERE c.name = 'a'",
+        Value::from(bag![bag!["111", "222"]]),
+    );
+    assert_all_modes("{}.*", Value::from(bag![]));
+}
+
+#[test]
+fn unpivot_non_tuple() {
+    assert_permissive_only("MISSING.*", Value::from(bag![]));
+    assert_permissive_only("'abc'.*", Value::from(bag!["abc"]));
+    assert_permissive_only("[1, 2].*", Value::from(bag![list![1, 2]]));
+}
+
+#[test]
+fn nested_wildcards_flatten() {
+    assert_all_modes("[[1, 2], [3]][*][*]", Value::from(bag![1, 2, 3]));
+    assert_all_modes(
+        "{'a': {'b': [1, 2]}, 'c': {'b': [3]}}.*.b[*]",
+        Value::from(bag![1, 2, 3]),
+    );
+}
@@ -271,6 +271,79 @@ fn pretty_case() {
     pretty_print_test("pretty_case_2","SELECT VALUE CASE x + 1 WHEN NULL THEN 'shouldnt be null' WHEN MISSING THEN 'shouldnt be missing' WHEN i THEN 'ONE' WHEN f THEN 'TWO' WHEN d THEN 'THREE' END FROM << i, f, d, null, missing >> AS x");
 }
 
+#[test]
+fn pretty_window() {
+    pretty_print_test(
+        "pretty_window",
+        "SELECT t.a AS a, RANK() OVER (PARTITION BY t.b ORDER BY t.c DESC) AS r, \
+                SUM(t.c) OVER (ORDER BY t.c ROWS BETWEEN 1 PRECEDING AND UNBOUNDED FOLLOWING) AS s \
+         FROM t",
+    );
+}
+
+#[test]
+fn pretty_parameters() {
+    pretty_print_test(
+        "pretty_parameters",
+        "SELECT t.a AS a FROM t WHERE t.b = ? AND t.c = :c LIMIT ?",
+    );
+}
+
+#[test]
+fn pretty_dml() {
+    pretty_print_test(
+        "pretty_dml_insert",
+        "INSERT INTO t VALUE {'a': 1} ON CONFLICT WHERE t.a = EXCLUDED.a DO NOTHING RETURNING MODIFIED NEW *",
+    );
+    pretty_print_test(
+        "pretty_dml_insert_query",
+        "INSERT INTO t SELECT VALUE {'a': s} FROM s ON CONFLICT WHERE t.a = EXCLUDED.a DO NOTHING",
+    );
+    pretty_print_test(
+        "pretty_dml_update",
+        "UPDATE t AS x SET x.a = 1, x.b = x.a + 1 REMOVE x.c[0] WHERE x.d > 0 RETURNING ALL OLD x.a",
+    );
+    pretty_print_test("pretty_dml_delete", "DELETE FROM t AS x WHERE x.a = 1");
+}
+
+#[test]
+fn pretty_let() {
+    pretty_print_test(
+        "pretty_let",
+        "SELECT t.a, y, z FROM t AS t LET t.a * 2 AS y, t.b || 'c' AS z WHERE y > 0",
+    );
+}
+
+#[test]
+fn pretty_values() {
+    pretty_print_test("pretty_values", "VALUES (1, 'a'), (2, 'b')");
+    pretty_print_test(
+        "pretty_values_from_columns",
+        "SELECT v.code FROM (VALUES (1, 'a'), (2, 'b')) AS v (code, name) WHERE v.code > 1",
+    );
+    pretty_print_test("pretty_table", "TABLE t");
+}
+
+#[test]
+fn pretty_with() {
+    pretty_print_test(
+        "pretty_with",
+        "WITH big AS (SELECT t.a FROM t AS t WHERE t.a > 10), small (a) AS (SELECT t.a FROM t AS t WHERE t.a < 5) SELECT b.a FROM big AS b UNION ALL SELECT s.a FROM small AS s",
+    );
+    pretty_print_test(
+        "pretty_with_recursive",
+        "WITH RECURSIVE r (n, up) AS (SELECT e.n AS n, e.up AS up FROM links AS e WHERE e.up IS NULL UNION ALL SELECT e.n AS n, e.up AS up FROM links AS e, r AS r WHERE e.up = r.n) SEARCH DEPTH FIRST BY n SET ord CYCLE n SET looped TO 'Y' DEFAULT 'N' USING route SELECT r.n FROM r AS r ORDER BY r.ord",
+    );
+    pretty_print_test(
+        "pretty_with_subquery",
+        "SELECT x.a FROM (WITH big AS (SELECT t.a FROM t AS t WHERE t.a > 10) SELECT b.a FROM big AS b) AS x",
+    );
+    pretty_print_test(
+        "pretty_with_insert",
+        "INSERT INTO t WITH big AS (SELECT s.a FROM s AS s WHERE s.a > 10) SELECT b.a FROM big AS b",
+    );
+}
+
 #[test]
 fn pretty_pivot() {
     pretty_print_test(
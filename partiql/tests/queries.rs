@@ -1,10 +1,13 @@
 use crate::common::{eval_query_with_catalog, TestError};
 use assert_matches::assert_matches;
+use partiql_ast_passes::error::{AstTransformError, AstTransformationError};
 use partiql_catalog::catalog::PartiqlCatalog;
 use partiql_catalog::extension::Extension;
+use partiql_eval::error::EvaluationError;
 use partiql_eval::eval::Evaluated;
 use partiql_eval::plan::EvaluationMode;
 use partiql_extension_value_functions::PartiqlValueFnExtension;
+use partiql_value::{bag, Value};
 
 mod common;
 
@@ -28,3 +31,438 @@ fn select_star_unpivot() {
     let res = res.unwrap().result;
     insta::assert_debug_snapshot!(res);
 }
+
+#[test]
+fn right_join() {
+    let query = "SELECT c.name AS name, o.productId AS productId \
+                 FROM << {'id': 5, 'name': 'Joe'}, {'id': 7, 'name': 'Mary'} >> AS c \
+                 RIGHT JOIN << {'custId': 7, 'productId': 101}, {'custId': 9, 'productId': 523} >> AS o \
+                 ON c.id = o.custId";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn full_join() {
+    let query = "SELECT c.name AS name, o.productId AS productId \
+                 FROM << {'id': 5, 'name': 'Joe'}, {'id': 7, 'name': 'Mary'} >> AS c \
+                 FULL JOIN << {'custId': 7, 'productId': 101}, {'custId': 9, 'productId': 523} >> AS o \
+                 ON c.id = o.custId";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_cte() {
+    let query = "WITH cheap AS (SELECT p.name AS name, p.price AS price \
+                                FROM << {'name': 'pen', 'price': 2}, {'name': 'ink', 'price': 11} >> AS p \
+                                WHERE p.price < 10) \
+                 SELECT c.name AS name FROM cheap AS c";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_cte_columns_and_chaining() {
+    let query = "WITH nums (n) AS (SELECT VALUE x FROM << 1, 2, 3 >> AS x), \
+                      doubled (n, twice) AS (SELECT v.n, v.n * 2 FROM nums AS v) \
+                 SELECT d.twice AS twice FROM (SELECT * FROM doubled AS d2 WHERE d2.n > 1) AS d";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_recursive() {
+    let query = "WITH RECURSIVE counter (n) AS (SELECT VALUE x FROM << 1 >> AS x \
+                                                UNION ALL \
+                                                SELECT c.n + 1 FROM counter AS c WHERE c.n < 5) \
+                 SELECT c.n AS n FROM counter AS c ORDER BY c.n";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_recursive_search_depth_first() {
+    let query = "WITH RECURSIVE emp AS (SELECT * FROM << {'id': 1, 'name': 'Ada', 'mgr': NULL}, \
+                                             {'id': 2, 'name': 'Bob', 'mgr': 1}, \
+                                             {'id': 3, 'name': 'Cy', 'mgr': 1}, \
+                                             {'id': 4, 'name': 'Di', 'mgr': 2} >> AS e), \
+                      chart AS (SELECT e.id, e.name FROM emp AS e WHERE e.mgr IS NULL \
+                                     UNION ALL \
+                                     SELECT e.id, e.name FROM emp AS e, chart AS c WHERE e.mgr = c.id) \
+                     SEARCH DEPTH FIRST BY id SET ord \
+                 SELECT c.name AS name FROM chart AS c ORDER BY c.ord";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_recursive_search_breadth_first() {
+    let query = "WITH RECURSIVE emp AS (SELECT * FROM << {'id': 1, 'name': 'Ada', 'mgr': NULL}, \
+                                                       {'id': 2, 'name': 'Bob', 'mgr': 1}, \
+                                                       {'id': 3, 'name': 'Cy', 'mgr': 1}, \
+                                                       {'id': 4, 'name': 'Di', 'mgr': 2} >> AS e), \
+                      chart AS (SELECT e.id, e.name FROM emp AS e WHERE e.mgr IS NULL \
+                                UNION ALL \
+                                SELECT e.id, e.name FROM emp AS e, chart AS c WHERE e.mgr = c.id) \
+                     SEARCH BREADTH FIRST BY id SET ord \
+                 SELECT c.name AS name, c.ord AS ord FROM chart AS c ORDER BY c.ord";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_recursive_cycle() {
+    let query = "WITH RECURSIVE links AS (SELECT * FROM << {'src': 1, 'dst': 2}, \
+                                                         {'src': 2, 'dst': 3}, \
+                                                         {'src': 3, 'dst': 1} >> AS e), \
+                      tour (stop) AS (SELECT VALUE e.src FROM links AS e WHERE e.src = 1 \
+                                      UNION ALL \
+                                      SELECT e.dst FROM links AS e, tour AS w WHERE e.src = w.stop) \
+                     CYCLE stop SET looped TO 'Y' DEFAULT 'N' USING route \
+                 SELECT w.stop AS stop, w.looped AS looped, w.route AS route FROM tour AS w ORDER BY w.route";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_recursive_union_distinct() {
+    let query = "WITH RECURSIVE links AS (SELECT * FROM << {'src': 1, 'dst': 2}, \
+                                                         {'src': 2, 'dst': 3}, \
+                                                         {'src': 3, 'dst': 1} >> AS e), \
+                      reach (n) AS (SELECT VALUE e.dst FROM links AS e WHERE e.src = 1 \
+                                       UNION \
+                                       SELECT e.dst FROM links AS e, reach AS r WHERE e.src = r.n) \
+                 SELECT VALUE r.n FROM reach AS r ORDER BY r.n";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_recursive_without_union() {
+    let query = "WITH RECURSIVE r AS (SELECT x.n + 1 AS n FROM r AS x) SELECT * FROM r";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Err(_));
+}
+
+#[test]
+fn with_recursive_cyclic_without_cycle() {
+    let query = "WITH RECURSIVE links AS (SELECT * FROM << {'src': 1, 'dst': 2}, \
+                                                         {'src': 2, 'dst': 1} >> AS e), \
+                      hops (n) AS (SELECT VALUE e.src FROM links AS e WHERE e.src = 1 \
+                                   UNION ALL \
+                                   SELECT e.dst FROM links AS e, hops AS w WHERE e.src = w.n) \
+                 SELECT VALUE w.n FROM hops AS w";
+
+    let res = eval(query, EvaluationMode::Strict);
+    assert_matches!(res, Err(_));
+    insta::assert_debug_snapshot!(res.unwrap_err());
+
+    // non-termination is an error in permissive mode as well
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Err(TestError::Eval(err)) => {
+        assert_matches!(err.errors.as_slice(), [EvaluationError::RecursionLimit(..)]);
+    });
+}
+
+#[test]
+fn with_recursive_search_without_select_list() {
+    // the rows of the recursive part of an element with `SEARCH` must carry the rows they derive
+    // from, which requires a `SELECT` list
+    let query = "WITH RECURSIVE r AS (SELECT * FROM << {'n': 1} >> AS x \
+                                     UNION ALL \
+                                     SELECT * FROM r AS p WHERE p.n < 3) \
+                     SEARCH DEPTH FIRST BY n SET ord \
+                 SELECT VALUE x.n FROM r AS x";
+
+    let res = eval(query, EvaluationMode::Strict);
+    assert_matches!(res, Err(TestError::Lower(err)) => {
+        assert_matches!(
+            err.errors.as_slice(),
+            [AstTransformError::InvalidRecursiveWithElement(name)] if name == "r"
+        );
+    });
+}
+
+#[test]
+fn with_recursive_shadowed_name() {
+    // `t` within the element refers to the `FROM` item, which shadows the element's name
+    let query = "WITH RECURSIVE t AS (SELECT t.n AS n FROM << {'n': 1}, {'n': 2} >> AS t) \
+                 SELECT VALUE x.n FROM t AS x";
+
+    let res = eval(query, EvaluationMode::Strict);
+    assert_matches!(res, Ok(_));
+    assert_eq!(res.unwrap().result, Value::from(bag![1, 2]));
+}
+
+#[test]
+fn with_cte_in_subquery() {
+    let query = "SELECT d.n AS n \
+                 FROM (WITH nums (n) AS (SELECT VALUE x FROM << 1, 2, 3 >> AS x) \
+                       SELECT v.n FROM nums AS v WHERE v.n > 1) AS d";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn with_cte_nested() {
+    let query = "WITH nums (n) AS (SELECT VALUE x FROM << 1, 2, 3 >> AS x) \
+                 SELECT o.n AS n, d.twice AS twice \
+                 FROM nums AS o, \
+                      (WITH doubled (twice) AS (SELECT VALUE v.n * 2 FROM nums AS v) \
+                       SELECT t.twice FROM doubled AS t) AS d \
+                 WHERE d.twice = o.n + 1";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+const EMPLOYEES: &str = "<< {'name': 'ann', 'dept': 'eng', 'salary': 120}, \
+                            {'name': 'bob', 'dept': 'eng', 'salary': 100}, \
+                            {'name': 'cat', 'dept': 'eng', 'salary': 120}, \
+                            {'name': 'dan', 'dept': 'ops', 'salary': 90}, \
+                            {'name': 'eve', 'dept': 'ops', 'salary': 80} >>";
+
+#[test]
+fn window_ranking() {
+    let query = format!(
+        "SELECT e.name AS name, \
+                ROW_NUMBER() OVER (PARTITION BY e.dept ORDER BY e.salary DESC, e.name) AS rn, \
+                RANK() OVER (PARTITION BY e.dept ORDER BY e.salary DESC) AS rnk, \
+                DENSE_RANK() OVER (PARTITION BY e.dept ORDER BY e.salary DESC) AS dense \
+         FROM {EMPLOYEES} AS e ORDER BY e.name"
+    );
+
+    let res = eval(&query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn window_lag_lead() {
+    let query = "SELECT x AS x, LAG(x) OVER (ORDER BY x) AS prev, LEAD(x, 2, 0) OVER (ORDER BY x) AS next2 \
+                 FROM << 3, 1, 4, 2 >> AS x ORDER BY x";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn window_aggregates() {
+    let query = format!(
+        "SELECT e.name AS name, \
+                SUM(e.salary) OVER (PARTITION BY e.dept) AS dept_total, \
+                COUNT(*) OVER (ORDER BY e.salary) AS running_count, \
+                AVG(e.salary) OVER (ORDER BY e.name ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS moving_avg, \
+                MAX(e.salary) OVER (ORDER BY e.name ROWS UNBOUNDED PRECEDING) AS running_max \
+         FROM {EMPLOYEES} AS e ORDER BY e.name"
+    );
+
+    let res = eval(&query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn window_range_frame() {
+    let query = "SELECT x AS x, \
+                        SUM(x) OVER (ORDER BY x RANGE BETWEEN 2 PRECEDING AND CURRENT ROW) AS near_sum, \
+                        FIRST_VALUE(x) OVER (ORDER BY x DESC RANGE BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS first_near, \
+                        LAST_VALUE(x) OVER (ORDER BY x ROWS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) AS final \
+                 FROM << 1, 2, 2, 5, 6 >> AS x ORDER BY x";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn window_over_group_by() {
+    let query = format!(
+        "SELECT e.dept AS dept, SUM(e.salary) AS total, \
+                RANK() OVER (ORDER BY SUM(e.salary) DESC) AS rnk \
+         FROM {EMPLOYEES} AS e GROUP BY e.dept ORDER BY dept"
+    );
+
+    let res = eval(&query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    let res = res.unwrap().result;
+    insta::assert_debug_snapshot!(res);
+}
+
+#[test]
+fn window_range_offset_without_single_order_key() {
+    let query =
+        "SELECT SUM(x) OVER (RANGE BETWEEN 1 PRECEDING AND CURRENT ROW) AS s FROM << 1, 2 >> AS x";
+
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Err(_));
+}
+
+#[test]
+fn window_frame_starting_after_its_end() {
+    for query in [
+        "SELECT SUM(x) OVER (ORDER BY x ROWS BETWEEN 1 FOLLOWING AND 1 PRECEDING) AS s \
+         FROM << 1, 2 >> AS x",
+        "SELECT SUM(x) OVER (ORDER BY x ROWS BETWEEN CURRENT ROW AND 1 PRECEDING) AS s \
+         FROM << 1, 2 >> AS x",
+        "SELECT SUM(x) OVER (ORDER BY x RANGE BETWEEN 2 FOLLOWING AND 1 FOLLOWING) AS s \
+         FROM << 1, 2 >> AS x",
+        "SELECT SUM(x) OVER (ORDER BY x ROWS BETWEEN UNBOUNDED FOLLOWING AND CURRENT ROW) AS s \
+         FROM << 1, 2 >> AS x",
+    ] {
+        let res = eval(query, EvaluationMode::Permissive);
+        assert_matches!(
+            res,
+            Err(TestError::Lower(AstTransformationError { errors, .. }))
+                if matches!(errors.as_slice(), [AstTransformError::InvalidWindowSpecification(_)]),
+            "{query}"
+        );
+    }
+
+    let query = "SELECT SUM(x) OVER (ORDER BY x ROWS BETWEEN 1 PRECEDING AND 1 PRECEDING) AS s \
+                 FROM << 1, 2 >> AS x";
+    assert_matches!(eval(query, EvaluationMode::Permissive), Ok(_));
+}
+
+#[test]
+fn window_function_in_filter() {
+    for query in [
+        "SELECT VALUE RANK() OVER (ORDER BY t.a) FROM << {'a': 1} >> AS t \
+         WHERE RANK() OVER (ORDER BY t.a) > 0",
+        "SELECT t.a AS a FROM << {'a': 1} >> AS t GROUP BY t.a \
+         HAVING RANK() OVER (ORDER BY t.a) > 0",
+    ] {
+        let res = eval(query, EvaluationMode::Permissive);
+        assert_matches!(
+            res,
+            Err(TestError::Lower(AstTransformationError { errors, .. }))
+                if matches!(errors.as_slice(), [AstTransformError::WindowFunctionNotAllowed(..)]),
+            "{query}"
+        );
+    }
+
+    // the window functions of the projection are evaluated over the filtered rows
+    let query = "SELECT VALUE RANK() OVER (ORDER BY t.a) FROM << {'a': 1}, {'a': 2} >> AS t \
+                 WHERE t.a > 1";
+    let res = eval(query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    assert_eq!(res.unwrap().result, Value::from(bag![1]));
+}
+
+#[test]
+fn execute_iter_matches_execute() {
+    use crate::common::{compile, lower, parse};
+    use partiql_catalog::context::SystemContext;
+    use partiql_eval::eval::BasicContext;
+    use partiql_value::{DateTime, Value};
+
+    let queries = [
+        "SELECT e.name AS name FROM << {'name': 'a', 'x': 1}, {'name': 'b', 'x': 2} >> AS e WHERE e.x > 1",
+        "SELECT VALUE x * 2 FROM [1, 2, 3, 4, 5] AS x LIMIT 2 OFFSET 1",
+        "SELECT DISTINCT VALUE x FROM << 1, 2, 1, 3, 2 >> AS x",
+        "SELECT x AS x, y AS y FROM [1, 2] AS x, [3, 4] AS y",
+        "SELECT x AS x, y AS y FROM [1, 2] AS x LEFT JOIN [2, 3] AS y ON x = y",
+        "SELECT x AS x, y AS y FROM [1, 2] AS x RIGHT JOIN [2, 3] AS y ON x = y",
+        "SELECT x AS x, y AS y FROM [1, 2] AS x FULL JOIN [2, 3] AS y ON x = y",
+        "SELECT * FROM UNPIVOT {'amzn': 840.05, 'tdc': 31.06} AS price AT sym",
+        "SELECT VALUE x FROM [3, 1, 2] AS x ORDER BY x DESC LIMIT 2",
+        "SELECT k AS k, COUNT(*) AS c FROM [1, 2, 3, 4] AS x GROUP BY x % 2 AS k",
+        "1 + 1",
+    ];
+
+    let catalog = PartiqlCatalog::default().to_shared_catalog();
+    for query in queries {
+        let parsed = parse(query).expect("parse");
+        let lowered = lower(&catalog, &parsed).expect("lower");
+        let plan = compile(EvaluationMode::Strict, &catalog, lowered).expect("compile");
+
+        let now = DateTime::from_system_now_utc();
+        let sys = SystemContext { now: now.clone() };
+        let ctx = BasicContext::new(Default::default(), sys);
+        let expected = plan.execute(&ctx).expect("execute").result;
+        let expected: Vec<Value> = expected.into_iter().collect();
+
+        let sys = SystemContext { now };
+        let ctx = BasicContext::new(Default::default(), sys);
+        let streamed = plan
+            .execute_iter(&ctx)
+            .expect("execute_iter")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("execute_iter");
+
+        assert_eq!(expected, streamed, "{query}");
+    }
+}
+
+const ABSENT_KEYS: &str =
+    "<< {'id': 1, 'a': 1}, {'id': 2, 'a': NULL}, {'id': 3}, {'id': 4, 'a': 1}, {'id': 5} >>";
+
+#[test]
+fn group_all_coalesces_missing() {
+    let query = format!(
+        "SELECT k AS k, COUNT(*) AS c FROM {ABSENT_KEYS} AS t GROUP ALL BY t.a AS k ORDER BY c"
+    );
+
+    let res = eval(&query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    insta::assert_debug_snapshot!(res.unwrap().result);
+}
+
+#[test]
+fn group_partial() {
+    let query = format!(
+        "SELECT k AS k, COUNT(*) AS c FROM {ABSENT_KEYS} AS t GROUP PARTIAL BY t.a AS k ORDER BY c, k"
+    );
+
+    let res = eval(&query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    insta::assert_debug_snapshot!(res.unwrap().result);
+}
+
+#[test]
+fn group_partial_group_as() {
+    let query = format!("SELECT k AS k, g AS g FROM {ABSENT_KEYS} AS t GROUP PARTIAL BY t.a AS k GROUP AS g ORDER BY k");
+
+    let res = eval(&query, EvaluationMode::Permissive);
+    assert_matches!(res, Ok(_));
+    insta::assert_debug_snapshot!(res.unwrap().result);
+}
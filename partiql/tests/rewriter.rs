@@ -0,0 +1,163 @@
+use partiql::Engine;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::EvaluationMode;
+use partiql_logical::{Lit, ValueExpr};
+use partiql_rewriter::{ExprRule, Rewriter};
+use partiql_value::{bag, tuple, Value};
+
+fn globals() -> MapBindings<Value> {
+    let left = bag![
+        tuple![("id", 1), ("k", "a")],
+        tuple![("id", 2), ("k", "b")],
+        tuple![("id", 2.0), ("k", "c")],
+        tuple![("id", Value::Null), ("k", "d")],
+        tuple![("k", "e")],
+        tuple![("id", 3), ("k", "f")],
+    ];
+    let right = bag![
+        tuple![("id", 1), ("k", "a"), ("n", 1)],
+        tuple![("id", 2), ("k", "b"), ("n", 2)],
+        tuple![("id", 3), ("k", "c"), ("n", 3)],
+        tuple![("id", Value::Null), ("k", "d"), ("n", 4)],
+        tuple![("id", 2), ("k", "e"), ("n", 5)],
+    ];
+
+    let mut globals = MapBindings::default();
+    globals.insert("lhs", Value::from(left));
+    globals.insert("rhs", Value::from(right));
+    globals
+}
+
+/// Asserts that `query` evaluates to the same value with and without rewriting its plan with
+/// `rewriter`, and returns the rules that fired.
+#[track_caller]
+fn assert_rewrite_eq(rewriter: Rewriter, query: &str) -> Vec<&'static str> {
+    let plain = Engine::builder().mode(EvaluationMode::Strict).build();
+    let rewriting = Engine::builder()
+        .mode(EvaluationMode::Strict)
+        .rewriter(rewriter)
+        .build();
+
+    let expected = plain.execute(query, globals()).expect(query);
+    let stmt = rewriting.prepare(query).expect(query);
+    assert_eq!(stmt.execute(globals()).expect(query), expected, "{query}");
+    stmt.fired_rules().to_vec()
+}
+
+#[track_caller]
+fn assert_fired(query: &str, rules: &[&str]) {
+    let fired = assert_rewrite_eq(Rewriter::default(), query);
+    for rule in rules {
+        assert!(
+            fired.contains(rule),
+            "{rule} did not fire for {query}: {fired:?}"
+        );
+    }
+    if rules.is_empty() {
+        assert!(fired.is_empty(), "rules fired for {query}: {fired:?}");
+    }
+}
+
+#[test]
+fn predicate_pushdown() {
+    assert_fired(
+        "SELECT x.k, y.n FROM lhs AS x, rhs AS y WHERE x.id = y.id AND y.n > 1",
+        &["predicate_pushdown"],
+    );
+    assert_fired(
+        "SELECT x.k, y.n, z.n AS m FROM lhs AS x, rhs AS y, rhs AS z \
+         WHERE x.id = y.id AND z.n = y.n + 1 AND z.n < 5",
+        &["predicate_pushdown"],
+    );
+    assert_fired(
+        "SELECT x.k, y.n FROM lhs AS x JOIN rhs AS y ON x.k = y.k WHERE x.id = y.id",
+        &["predicate_pushdown"],
+    );
+    assert_fired(
+        "SELECT y.n, v FROM rhs AS y, UNPIVOT y AS v AT a WHERE a = 'k' AND v <> 'c'",
+        &["predicate_pushdown"],
+    );
+    assert_fired(
+        "SELECT s.k FROM (SELECT x.k, x.id FROM lhs AS x WHERE x.k <> 'a') AS s WHERE s.id = 2",
+        &["predicate_pushdown", "merge_filters"],
+    );
+    // outer joins keep their filters
+    assert_fired(
+        "SELECT x.k, y.n FROM lhs AS x LEFT JOIN rhs AS y ON x.k = y.k WHERE x.id = y.id",
+        &[],
+    );
+}
+
+#[test]
+fn projection_pruning() {
+    assert_fired(
+        "SELECT s.k FROM (SELECT x.k, x.id, x.id + 1 AS next FROM lhs AS x) AS s",
+        &["projection_pruning"],
+    );
+    assert_fired("SELECT * FROM (SELECT x.k, x.id FROM lhs AS x) AS s", &[]);
+    assert_fired(
+        "SELECT s.k, COUNT(*) AS c FROM (SELECT x.k, x.id FROM lhs AS x) AS s GROUP BY s.k",
+        &["projection_pruning"],
+    );
+}
+
+#[test]
+fn limit_pushdown() {
+    assert_fired(
+        "SELECT s.k FROM (SELECT x.k FROM lhs AS x) AS s LIMIT 2 OFFSET 1",
+        &["limit_pushdown"],
+    );
+    assert_fired(
+        "(SELECT VALUE x.k FROM lhs AS x) OUTER UNION ALL (SELECT VALUE y.k FROM rhs AS y) \
+         LIMIT 3 OFFSET 4",
+        &["limit_pushdown"],
+    );
+    assert_fired("SELECT x.k FROM lhs AS x ORDER BY x.k DESC LIMIT 2", &[]);
+}
+
+#[test]
+fn constant_folding() {
+    assert_fired(
+        "SELECT VALUE x.k FROM lhs AS x WHERE 1 + 1 = 2",
+        &["constant_folding", "merge_filters"],
+    );
+    assert_fired(
+        "SELECT VALUE x.id * (2 - 1) FROM lhs AS x",
+        &["constant_folding"],
+    );
+}
+
+struct ZToB;
+
+impl ExprRule for ZToB {
+    fn name(&self) -> &'static str {
+        "z_to_b"
+    }
+
+    fn rewrite(&self, expr: &ValueExpr) -> Option<ValueExpr> {
+        match expr {
+            ValueExpr::Lit(lit) if **lit == Lit::String("z".to_string()) => {
+                Some(ValueExpr::Lit(Box::new(Lit::String("b".to_string()))))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[test]
+fn custom_rule() {
+    let engine = Engine::builder()
+        .mode(EvaluationMode::Strict)
+        .rewriter(Rewriter::new().with_rule(ZToB))
+        .build();
+    let stmt = engine
+        .prepare("SELECT VALUE y.n FROM rhs AS y WHERE y.k = 'z'")
+        .unwrap();
+    assert_eq!(stmt.fired_rules(), ["z_to_b"]);
+    assert_eq!(stmt.execute(globals()).unwrap(), Value::from(bag![2]));
+
+    let stmt = Engine::default()
+        .prepare("SELECT VALUE 1 + 1 FROM rhs")
+        .unwrap();
+    assert!(stmt.fired_rules().is_empty());
+}
@@ -0,0 +1,134 @@
+use partiql::Engine;
+use partiql_catalog::mutation::InMemoryMutationSink;
+use partiql_eval::env::basic::MapBindings;
+use partiql_eval::plan::EvaluationMode;
+use partiql_value::{bag, list, tuple, BindingsName, Value};
+use std::borrow::Cow;
+
+mod common;
+use common::execute_query;
+
+fn globals() -> MapBindings<Value> {
+    let mut globals = MapBindings::default();
+    globals.insert(
+        "t",
+        bag![tuple![("a", 1), ("b", 2)], tuple![("a", 2), ("b", 4)]].into(),
+    );
+    globals
+}
+
+#[test]
+fn values() {
+    assert_eq!(
+        execute_query(
+            "VALUES (1, 'a'), (2, 'b')",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![list![1, "a"], list![2, "b"]])
+    );
+}
+
+#[test]
+fn values_in_from() {
+    assert_eq!(
+        execute_query(
+            "SELECT v FROM (VALUES (1, 'a'), (2, 'b')) AS v",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![
+            tuple![("v", list![1, "a"])],
+            tuple![("v", list![2, "b"])],
+        ])
+    );
+}
+
+#[test]
+fn values_in_from_with_column_aliases() {
+    assert_eq!(
+        execute_query(
+            "SELECT * FROM (VALUES (1, 'a'), (2, 'b')) AS v (code, name) WHERE v.code > 1",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("code", 2), ("name", "b")]])
+    );
+    assert_eq!(
+        execute_query(
+            "SELECT t.b, l.name FROM t AS t, (VALUES (1, 'one'), (2, 'two')) AS l (code, name) \
+             WHERE t.a = l.code",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![
+            tuple![("b", 2), ("name", "one")],
+            tuple![("b", 4), ("name", "two")],
+        ])
+    );
+}
+
+#[test]
+fn values_in_with() {
+    assert_eq!(
+        execute_query(
+            "WITH r (n, s) AS (VALUES (1, 'a')) SELECT * FROM r",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("n", 1), ("s", "a")]])
+    );
+}
+
+#[test]
+fn table() {
+    let expected = Value::from(bag![tuple![("a", 1), ("b", 2)], tuple![("a", 2), ("b", 4)]]);
+    assert_eq!(
+        execute_query("TABLE t", EvaluationMode::Strict, globals()).expect("execute"),
+        expected
+    );
+    assert_eq!(
+        execute_query("TABLE T", EvaluationMode::Strict, globals()).expect("execute"),
+        expected
+    );
+}
+
+#[test]
+fn scan_with_column_aliases() {
+    assert_eq!(
+        execute_query(
+            "SELECT x.c FROM t AS x (c, d)",
+            EvaluationMode::Strict,
+            globals()
+        )
+        .expect("execute"),
+        Value::from(bag![tuple![("c", 1)], tuple![("c", 2)]])
+    );
+}
+
+#[test]
+fn insert_values() {
+    let mut sink = InMemoryMutationSink::new();
+    sink.insert("t", bag![tuple![("id", 1)]].into());
+    let res = Engine::default()
+        .prepare("INSERT INTO t VALUES ({'id': 2}), ({'id': 3})")
+        .expect("prepare")
+        .execution()
+        .mutation_sink(&sink)
+        .execute()
+        .expect("execute");
+    assert_eq!(res, Value::from(bag![]));
+    assert_eq!(
+        sink.get(&BindingsName::CaseInsensitive(Cow::Borrowed("t"))),
+        Some(Value::from(bag![
+            tuple![("id", 1)],
+            tuple![("id", 2)],
+            tuple![("id", 3)],
+        ]))
+    );
+}